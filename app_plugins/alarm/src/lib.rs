@@ -0,0 +1,185 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+pub struct AlarmPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    high_limit: f64,
+    low_limit: f64,
+    latching: bool,
+    signal: f64,
+    reset: f64,
+    latched: bool,
+    alarm: bool,
+    /// `tripped` or `cleared` with the signal value, until drained.
+    events: Vec<(String, Value)>,
+}
+
+impl AlarmPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Alarm".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("high_limit".to_string(), Value::from(1.0)),
+                    ("low_limit".to_string(), Value::from(-1.0)),
+                    ("latching".to_string(), Value::from(false)),
+                ],
+            },
+            inputs: vec![
+                Port { id: PortId("signal".to_string()) },
+                Port { id: PortId("reset".to_string()) },
+            ],
+            outputs: vec![Port { id: PortId("alarm".to_string()) }],
+            high_limit: 1.0,
+            low_limit: -1.0,
+            latching: false,
+            signal: 0.0,
+            reset: 0.0,
+            latched: false,
+            alarm: false,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn set_config(&mut self, high_limit: f64, low_limit: f64, latching: bool) {
+        self.high_limit = high_limit;
+        self.low_limit = low_limit;
+        self.latching = latching;
+        if !latching {
+            self.latched = false;
+        }
+    }
+
+    pub fn set_inputs(&mut self, signal: f64, reset: f64) {
+        self.signal = signal;
+        self.reset = reset;
+    }
+
+    pub fn alarm(&self) -> f64 {
+        if self.alarm {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    pub fn is_latched(&self) -> bool {
+        self.latched
+    }
+
+    /// Alarm changes since the last call, oldest first.
+    pub fn drain_events(&mut self) -> std::vec::Drain<'_, (String, Value)> {
+        self.events.drain(..)
+    }
+}
+
+impl Plugin for AlarmPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        // A reset above 0.5 clears the latch; it re-arms on the same tick if
+        // the signal is still outside the band.
+        if self.reset > 0.5 {
+            self.latched = false;
+        }
+        let violated = self.signal > self.high_limit || self.signal < self.low_limit;
+        if violated && self.latching {
+            self.latched = true;
+        }
+        let alarm = violated || self.latched;
+        if alarm != self.alarm {
+            let name = if alarm { "tripped" } else { "cleared" };
+            self.events.push((name.to_string(), Value::from(self.signal)));
+        }
+        self.alarm = alarm;
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::float("high_limit", "High limit")
+                        .step_f(0.1)
+                        .default_value(Value::from(1.0)),
+                )
+                .field(
+                    ConfigField::float("low_limit", "Low limit")
+                        .step_f(0.1)
+                        .default_value(Value::from(-1.0)),
+                )
+                .field(
+                    ConfigField::boolean("latching", "Latching")
+                        .default_value(Value::from(false))
+                        .hint("Hold the alarm until the reset input goes above 0.5"),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: false,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["alarm".to_string()],
+            inputs: vec!["signal".to_string(), "reset".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "high_limit" => Some(Value::from(self.high_limit)),
+            "low_limit" => Some(Value::from(self.low_limit)),
+            "latching" => Some(Value::from(self.latching)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        match name {
+            "high_limit" => {
+                if let Some(v) = value.as_f64() {
+                    self.high_limit = v;
+                }
+            }
+            "low_limit" => {
+                if let Some(v) = value.as_f64() {
+                    self.low_limit = v;
+                }
+            }
+            "latching" => {
+                if let Some(v) = value.as_bool() {
+                    self.set_config(self.high_limit, self.low_limit, v);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
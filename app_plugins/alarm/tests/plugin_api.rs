@@ -0,0 +1,54 @@
+use alarm_plugin::AlarmPlugin;
+use rtsyn_plugin::prelude::*;
+
+fn step(plugin: &mut AlarmPlugin, signal: f64, reset: f64) -> f64 {
+    let mut ctx = PluginContext::default();
+    plugin.set_inputs(signal, reset);
+    plugin.process(&mut ctx).unwrap();
+    plugin.alarm()
+}
+
+#[test]
+fn non_latching_alarm_follows_the_band() {
+    let mut plugin = AlarmPlugin::new(1);
+    plugin.set_config(10.0, 0.0, false);
+
+    assert_eq!(step(&mut plugin, 5.0, 0.0), 0.0);
+    assert_eq!(step(&mut plugin, 11.0, 0.0), 1.0);
+    assert_eq!(step(&mut plugin, 5.0, 0.0), 0.0);
+    assert_eq!(step(&mut plugin, -1.0, 0.0), 1.0);
+}
+
+#[test]
+fn latching_alarm_holds_until_reset() {
+    let mut plugin = AlarmPlugin::new(1);
+    plugin.set_config(10.0, 0.0, true);
+
+    assert_eq!(step(&mut plugin, 11.0, 0.0), 1.0);
+    // Back in band: still latched
+    assert_eq!(step(&mut plugin, 5.0, 0.0), 1.0);
+    assert!(plugin.is_latched());
+    // Reset while still out of band re-latches
+    assert_eq!(step(&mut plugin, 12.0, 1.0), 1.0);
+    // Reset pulse with the signal in band clears it
+    assert_eq!(step(&mut plugin, 5.0, 1.0), 0.0);
+    assert_eq!(step(&mut plugin, 5.0, 0.0), 0.0);
+}
+
+#[test]
+fn alarm_changes_are_reported_as_events() {
+    let mut plugin = AlarmPlugin::new(1);
+    plugin.set_config(10.0, 0.0, true);
+    step(&mut plugin, 11.0, 0.0);
+    step(&mut plugin, 12.0, 0.0);
+    step(&mut plugin, 5.0, 1.0);
+    let events: Vec<_> = plugin.drain_events().collect();
+    assert_eq!(
+        events,
+        [
+            ("tripped".to_string(), serde_json::Value::from(11.0)),
+            ("cleared".to_string(), serde_json::Value::from(5.0)),
+        ]
+    );
+    assert_eq!(plugin.drain_events().count(), 0);
+}
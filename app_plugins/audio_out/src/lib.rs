@@ -0,0 +1,347 @@
+use rtrb::{Consumer, Producer, RingBuffer};
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+use std::f64::consts::TAU;
+
+pub const MODES: [&str; 3] = ["direct", "am", "fm"];
+
+const DEFAULT_SAMPLE_RATE: u32 = 44_100;
+/// Audio buffered between the runtime and the audio device.
+const BUFFER_SECONDS: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// The input itself, resampled to the audio rate.
+    #[default]
+    Direct,
+    /// A carrier tone whose amplitude follows the input.
+    Am,
+    /// A tone whose pitch moves `deviation_hz` per unit of input around the carrier.
+    Fm,
+}
+
+impl Mode {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "am" => Mode::Am,
+            "fm" => Mode::Fm,
+            _ => Mode::Direct,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mode::Direct => "direct",
+            Mode::Am => "am",
+            Mode::Fm => "fm",
+        }
+    }
+}
+
+pub struct AudioOutPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    sample_rate: u32,
+    gain: f64,
+    mode: Mode,
+    carrier_hz: f64,
+    deviation_hz: f64,
+    input: f64,
+    last_input: f64,
+    /// Fraction of an audio sample carried over to the next tick.
+    pending: f64,
+    /// Carrier position within its period, in [0, 1).
+    carrier_phase: f64,
+    producer: Producer<f32>,
+    /// Read end of the ring buffer while no audio device drains it.
+    consumer: Option<Consumer<f32>>,
+    dropped: u64,
+    #[cfg(feature = "device")]
+    stream: Option<cpal::Stream>,
+    last_error: Option<String>,
+}
+
+impl AudioOutPlugin {
+    pub fn new(id: u64) -> Self {
+        let (producer, consumer) = RingBuffer::new(buffer_capacity(DEFAULT_SAMPLE_RATE));
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Audio Out".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("sample_rate".to_string(), Value::from(DEFAULT_SAMPLE_RATE)),
+                    ("gain".to_string(), Value::from(1.0)),
+                    ("mode".to_string(), Value::from("direct")),
+                    ("carrier_hz".to_string(), Value::from(440.0)),
+                    ("deviation_hz".to_string(), Value::from(220.0)),
+                ],
+            },
+            inputs: vec![Port { id: PortId("in".to_string()) }],
+            sample_rate: DEFAULT_SAMPLE_RATE,
+            gain: 1.0,
+            mode: Mode::Direct,
+            carrier_hz: 440.0,
+            deviation_hz: 220.0,
+            input: 0.0,
+            last_input: 0.0,
+            pending: 0.0,
+            carrier_phase: 0.0,
+            producer,
+            consumer: Some(consumer),
+            dropped: 0,
+            #[cfg(feature = "device")]
+            stream: None,
+            last_error: None,
+        }
+    }
+
+    /// The audio device is opened while `playing` is set and reopened when
+    /// the sample rate changes. Builds without the `device` feature only fill
+    /// the ring buffer, see [`Self::take_consumer`].
+    pub fn set_config(
+        &mut self,
+        sample_rate: u32,
+        gain: f64,
+        mode: Mode,
+        carrier_hz: f64,
+        deviation_hz: f64,
+        playing: bool,
+    ) {
+        self.set_sample_rate(sample_rate);
+        if !playing {
+            self.stop();
+        }
+        self.gain = gain;
+        self.mode = mode;
+        self.carrier_hz = carrier_hz.max(0.0);
+        self.deviation_hz = deviation_hz;
+        // A failed open is retried only after the plugin is stopped and
+        // started again, not on every tick.
+        #[cfg(feature = "device")]
+        if playing && self.stream.is_none() && self.last_error.is_none() {
+            let consumer = self.consumer.take().unwrap_or_else(|| self.fresh_consumer());
+            match open_stream(self.sample_rate, consumer) {
+                Ok(stream) => self.stream = Some(stream),
+                Err(err) => self.last_error = Some(err),
+            }
+        }
+    }
+
+    pub fn set_input(&mut self, input: f64) {
+        self.input = input;
+    }
+
+    /// Hands out the read end of the ring buffer, e.g. to feed another sink.
+    /// Samples are mono f32 at the configured sample rate.
+    pub fn take_consumer(&mut self) -> Option<Consumer<f32>> {
+        self.consumer.take()
+    }
+
+    /// Samples lost because the reader fell behind.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Why the audio device could not be opened.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    fn set_sample_rate(&mut self, sample_rate: u32) {
+        let sample_rate = sample_rate.max(1);
+        if self.sample_rate != sample_rate {
+            self.sample_rate = sample_rate;
+            self.consumer = Some(self.fresh_consumer());
+            self.stop();
+        }
+    }
+
+    fn stop(&mut self) {
+        #[cfg(feature = "device")]
+        {
+            self.stream = None;
+        }
+        self.last_error = None;
+    }
+
+    fn fresh_consumer(&mut self) -> Consumer<f32> {
+        let (producer, consumer) = RingBuffer::new(buffer_capacity(self.sample_rate));
+        self.producer = producer;
+        consumer
+    }
+
+    fn sample(&mut self, input: f64) -> f64 {
+        let sample_rate = f64::from(self.sample_rate);
+        match self.mode {
+            Mode::Direct => self.gain * input,
+            Mode::Am => {
+                let out = self.gain * input * (TAU * self.carrier_phase).sin();
+                self.carrier_phase = (self.carrier_phase + self.carrier_hz / sample_rate).fract();
+                out
+            }
+            Mode::Fm => {
+                let out = self.gain * (TAU * self.carrier_phase).sin();
+                let frequency = (self.carrier_hz + self.deviation_hz * input).max(0.0);
+                self.carrier_phase = (self.carrier_phase + frequency / sample_rate).fract();
+                out
+            }
+        }
+    }
+}
+
+fn buffer_capacity(sample_rate: u32) -> usize {
+    ((f64::from(sample_rate) * BUFFER_SECONDS) as usize).max(1)
+}
+
+#[cfg(feature = "device")]
+fn open_stream(sample_rate: u32, mut consumer: Consumer<f32>) -> Result<cpal::Stream, String> {
+    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+
+    let device = cpal::default_host()
+        .default_output_device()
+        .ok_or_else(|| "no audio output device".to_string())?;
+    let channels = device
+        .default_output_config()
+        .map_err(|err| err.to_string())?
+        .channels();
+    let config = cpal::StreamConfig {
+        channels,
+        sample_rate: cpal::SampleRate(sample_rate),
+        buffer_size: cpal::BufferSize::Default,
+    };
+    let stream = device
+        .build_output_stream(
+            &config,
+            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+                for frame in data.chunks_mut(usize::from(channels.max(1))) {
+                    // Silence when the runtime falls behind
+                    frame.fill(consumer.pop().unwrap_or(0.0));
+                }
+            },
+            |_| {},
+            None,
+        )
+        .map_err(|err| err.to_string())?;
+    stream.play().map_err(|err| err.to_string())?;
+    Ok(stream)
+}
+
+impl Plugin for AudioOutPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &[]
+    }
+
+    /// Emits the audio samples that fall within this tick, interpolating the
+    /// input from the previous tick's value.
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
+        let input = if self.input.is_finite() { self.input } else { 0.0 };
+        self.pending += ctx.period_seconds.max(0.0) * f64::from(self.sample_rate);
+        let count = self.pending.floor();
+        self.pending -= count;
+        let count = count as usize;
+        for idx in 1..=count {
+            let value = self.last_input + (input - self.last_input) * idx as f64 / count as f64;
+            let sample = self.sample(value).clamp(-1.0, 1.0) as f32;
+            if self.producer.push(sample).is_err() && self.consumer.is_none() {
+                self.dropped += 1;
+            }
+        }
+        self.last_input = input;
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::integer("sample_rate", "Sample rate (Hz)")
+                        .min(1)
+                        .default_value(Value::from(DEFAULT_SAMPLE_RATE)),
+                )
+                .field(
+                    ConfigField::float("gain", "Gain")
+                        .step_f(0.1)
+                        .default_value(Value::from(1.0))
+                        .hint("Samples are clipped to -1..1 after the gain"),
+                )
+                .field(
+                    ConfigField::text("mode", "Mode")
+                        .default_value(Value::from("direct"))
+                        .hint("direct, am or fm"),
+                )
+                .field(
+                    ConfigField::float("carrier_hz", "Carrier (Hz)")
+                        .min_f(0.0)
+                        .step_f(10.0)
+                        .default_value(Value::from(440.0)),
+                )
+                .field(
+                    ConfigField::float("deviation_hz", "FM deviation (Hz per unit)")
+                        .step_f(10.0)
+                        .default_value(Value::from(220.0)),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: false,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: Vec::new(),
+            inputs: vec!["in".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "sample_rate" => Some(Value::from(self.sample_rate)),
+            "gain" => Some(Value::from(self.gain)),
+            "mode" => Some(Value::from(self.mode.name())),
+            "carrier_hz" => Some(Value::from(self.carrier_hz)),
+            "deviation_hz" => Some(Value::from(self.deviation_hz)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        match name {
+            "sample_rate" => {
+                if let Some(rate) = value.as_u64().and_then(|v| u32::try_from(v).ok()) {
+                    self.set_sample_rate(rate);
+                }
+            }
+            "gain" => self.gain = value.as_f64().unwrap_or(self.gain),
+            "mode" => {
+                if let Some(v) = value.as_str() {
+                    self.mode = Mode::from_name(v);
+                }
+            }
+            "carrier_hz" => self.carrier_hz = value.as_f64().unwrap_or(self.carrier_hz).max(0.0),
+            "deviation_hz" => self.deviation_hz = value.as_f64().unwrap_or(self.deviation_hz),
+            _ => {}
+        }
+        Ok(())
+    }
+}
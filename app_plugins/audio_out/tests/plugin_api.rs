@@ -0,0 +1,57 @@
+use audio_out_plugin::{AudioOutPlugin, Mode};
+use rtsyn_plugin::prelude::*;
+
+/// Runs one 250 ms tick with `input` and returns the samples it emitted.
+fn tick(plugin: &mut AudioOutPlugin, consumer: &mut rtrb::Consumer<f32>, input: f64) -> Vec<f32> {
+    let mut ctx = PluginContext {
+        period_seconds: 0.25,
+        ..PluginContext::default()
+    };
+    plugin.set_input(input);
+    plugin.process(&mut ctx).unwrap();
+    std::iter::from_fn(|| consumer.pop().ok()).collect()
+}
+
+#[test]
+fn direct_mode_resamples_the_input_between_ticks() {
+    let mut plugin = AudioOutPlugin::new(1);
+    // 8 Hz at 250 ms per tick: two samples per tick
+    plugin.set_config(8, 0.5, Mode::Direct, 440.0, 220.0, false);
+    let mut consumer = plugin.take_consumer().expect("ring buffer reader");
+
+    assert_eq!(tick(&mut plugin, &mut consumer, 1.0), vec![0.25, 0.5]);
+    assert_eq!(tick(&mut plugin, &mut consumer, 1.0), vec![0.5, 0.5]);
+    // Clipped after the gain
+    assert_eq!(tick(&mut plugin, &mut consumer, 5.0), vec![1.0, 1.0]);
+    assert_eq!(plugin.dropped(), 0);
+}
+
+#[test]
+fn am_and_fm_modulate_a_carrier() {
+    let close = |samples: Vec<f32>, expected: [f32; 4]| {
+        assert_eq!(samples.len(), 4);
+        for (sample, expected) in samples.iter().zip(expected) {
+            assert!((sample - expected).abs() < 1e-6, "{samples:?}");
+        }
+    };
+
+    // A 1 Hz carrier sampled at 16 Hz, four samples per tick
+    let mut am = AudioOutPlugin::new(1);
+    am.set_config(16, 1.0, Mode::Am, 1.0, 0.0, false);
+    let mut consumer = am.take_consumer().unwrap();
+    // The input ramps 0.125, 0.25, 0.375, 0.5 over the tick
+    close(
+        tick(&mut am, &mut consumer, 0.5),
+        [0.0, 0.095_670_86, 0.265_165_04, 0.461_939_77],
+    );
+
+    // Carrier at 0 Hz, swept to 1, 2, 3, 4 Hz as the input ramps to 1
+    let mut fm = AudioOutPlugin::new(2);
+    fm.set_config(16, 1.0, Mode::Fm, 0.0, 4.0, false);
+    let mut consumer = fm.take_consumer().unwrap();
+    close(tick(&mut fm, &mut consumer, 0.0), [0.0; 4]);
+    close(
+        tick(&mut fm, &mut consumer, 1.0),
+        [0.0, 0.382_683_43, 0.923_879_5, 0.707_106_77],
+    );
+}
@@ -0,0 +1,168 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+pub struct BangBangPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    hysteresis: f64,
+    output_high: f64,
+    output_low: f64,
+    setpoint: f64,
+    process_variable: f64,
+    high: bool,
+    control: f64,
+}
+
+impl BangBangPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Bang-Bang Controller".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("hysteresis".to_string(), Value::from(0.5)),
+                    ("output_high".to_string(), Value::from(1.0)),
+                    ("output_low".to_string(), Value::from(0.0)),
+                    ("setpoint_default".to_string(), Value::from(0.0)),
+                ],
+            },
+            inputs: vec![
+                Port { id: PortId("setpoint".to_string()) },
+                Port { id: PortId("process_variable".to_string()) },
+            ],
+            outputs: vec![Port { id: PortId("control".to_string()) }],
+            hysteresis: 0.5,
+            output_high: 1.0,
+            output_low: 0.0,
+            setpoint: 0.0,
+            process_variable: 0.0,
+            high: false,
+            control: 0.0,
+        }
+    }
+
+    pub fn set_config(&mut self, hysteresis: f64, output_high: f64, output_low: f64) {
+        self.hysteresis = hysteresis.abs();
+        self.output_high = output_high;
+        self.output_low = output_low;
+    }
+
+    pub fn set_inputs(&mut self, setpoint: f64, process_variable: f64) {
+        self.setpoint = setpoint;
+        self.process_variable = process_variable;
+    }
+
+    pub fn control(&self) -> f64 {
+        self.control
+    }
+
+    pub fn is_high(&self) -> bool {
+        self.high
+    }
+}
+
+impl Plugin for BangBangPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        // Inside the hysteresis band the previous state is held.
+        if self.process_variable < self.setpoint - self.hysteresis {
+            self.high = true;
+        } else if self.process_variable > self.setpoint + self.hysteresis {
+            self.high = false;
+        }
+        self.control = if self.high {
+            self.output_high
+        } else {
+            self.output_low
+        };
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::float("hysteresis", "Hysteresis")
+                        .min_f(0.0)
+                        .step_f(0.1)
+                        .default_value(Value::from(0.5))
+                        .hint("Half-width of the band around the setpoint"),
+                )
+                .field(
+                    ConfigField::float("output_high", "Output high")
+                        .step_f(0.1)
+                        .default_value(Value::from(1.0))
+                        .hint("Output when the process variable is below the band"),
+                )
+                .field(
+                    ConfigField::float("output_low", "Output low")
+                        .step_f(0.1)
+                        .default_value(Value::from(0.0))
+                        .hint("Output when the process variable is above the band"),
+                )
+                .field(
+                    ConfigField::float("setpoint_default", "Default setpoint")
+                        .step_f(0.1)
+                        .default_value(Value::from(0.0))
+                        .hint("Setpoint used while the setpoint input is unconnected"),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: false,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["control".to_string()],
+            inputs: vec!["setpoint".to_string(), "process_variable".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "hysteresis" => Some(Value::from(self.hysteresis)),
+            "output_high" => Some(Value::from(self.output_high)),
+            "output_low" => Some(Value::from(self.output_low)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        let Some(v) = value.as_f64() else {
+            return Ok(());
+        };
+        match name {
+            "hysteresis" => self.hysteresis = v.abs(),
+            "output_high" => self.output_high = v,
+            "output_low" => self.output_low = v,
+            _ => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use bang_bang_plugin::BangBangPlugin;
+use rtsyn_plugin::prelude::*;
+
+fn step(plugin: &mut BangBangPlugin, setpoint: f64, pv: f64) -> f64 {
+    let mut ctx = PluginContext::default();
+    plugin.set_inputs(setpoint, pv);
+    plugin.process(&mut ctx).unwrap();
+    plugin.control()
+}
+
+#[test]
+fn bang_bang_switches_outside_band_and_holds_inside() {
+    let mut plugin = BangBangPlugin::new(1);
+    plugin.set_config(1.0, 5.0, -5.0);
+
+    // Below setpoint - hysteresis: switch high
+    assert_eq!(step(&mut plugin, 20.0, 18.5), 5.0);
+    // Inside the band: hold high
+    assert_eq!(step(&mut plugin, 20.0, 20.9), 5.0);
+    // Above setpoint + hysteresis: switch low
+    assert_eq!(step(&mut plugin, 20.0, 21.5), -5.0);
+    // Inside the band again: hold low
+    assert_eq!(step(&mut plugin, 20.0, 19.1), -5.0);
+}
+
+#[test]
+fn bang_bang_ports() {
+    let plugin = BangBangPlugin::new(1);
+    let inputs: Vec<&str> = plugin.inputs().iter().map(|p| p.id.0.as_str()).collect();
+    let outputs: Vec<&str> = plugin.outputs().iter().map(|p| p.id.0.as_str()).collect();
+    assert_eq!(inputs, vec!["setpoint", "process_variable"]);
+    assert_eq!(outputs, vec!["control"]);
+}
+
+#[test]
+fn bang_bang_can_be_started() {
+    let behavior = BangBangPlugin::new(1).behavior();
+    assert!(behavior.supports_start_stop);
+    assert!(!behavior.loads_started);
+}
@@ -0,0 +1,140 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+/// Widest word; every packed value stays an exact integer in an `f64`.
+pub const MAX_BITS: usize = 32;
+
+/// An input above this level is a set bit.
+const BIT_THRESHOLD: f64 = 0.5;
+
+pub fn bit_port(idx: usize) -> String {
+    format!("bit_{idx}")
+}
+
+/// Packs digital inputs `bit_0..bit_<bits - 1>` into the integer `word`,
+/// `bit_0` being the least significant bit. An input counts as set above
+/// 0.5, so NaN and negative values are clear bits.
+pub struct BitPackPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    bit_values: Vec<f64>,
+    word: f64,
+}
+
+impl BitPackPlugin {
+    pub fn new(id: u64) -> Self {
+        let mut plugin = Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Bit Pack".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![("bits".to_string(), Value::from(8))],
+            },
+            inputs: Vec::new(),
+            outputs: vec![Port { id: PortId("word".to_string()) }],
+            bit_values: Vec::new(),
+            word: 0.0,
+        };
+        plugin.set_config(8);
+        plugin
+    }
+
+    /// `bits` is clamped to `1..=MAX_BITS`; added inputs start clear.
+    pub fn set_config(&mut self, bits: usize) {
+        let bits = bits.clamp(1, MAX_BITS);
+        if self.bit_values.len() == bits {
+            return;
+        }
+        self.inputs = (0..bits).map(|idx| Port { id: PortId(bit_port(idx)) }).collect();
+        self.bit_values.resize(bits, 0.0);
+    }
+
+    pub fn bits(&self) -> usize {
+        self.bit_values.len()
+    }
+
+    /// Inputs past the configured bit count are ignored.
+    pub fn set_bit(&mut self, idx: usize, value: f64) {
+        if let Some(slot) = self.bit_values.get_mut(idx) {
+            *slot = value;
+        }
+    }
+
+    pub fn word(&self) -> f64 {
+        self.word
+    }
+}
+
+impl Plugin for BitPackPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        let word = self
+            .bit_values
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| **value > BIT_THRESHOLD)
+            .fold(0u64, |word, (idx, _)| word | (1 << idx));
+        self.word = word as f64;
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new().field(
+                ConfigField::integer("bits", "Bits")
+                    .min(1)
+                    .max(MAX_BITS as i64)
+                    .default_value(Value::from(8))
+                    .hint("One bit_N input per bit, bit_0 least significant"),
+            ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: false,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["word".to_string()],
+            inputs: self.inputs.iter().map(|p| p.id.0.clone()).collect(),
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "bits" => Some(Value::from(self.bits())),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        if let ("bits", Some(bits)) = (name, value.as_u64()) {
+            self.set_config(bits as usize);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use bit_pack_plugin::{BitPackPlugin, MAX_BITS};
+use rtsyn_plugin::prelude::*;
+
+fn pack(plugin: &mut BitPackPlugin, bits: &[f64]) -> f64 {
+    for (idx, value) in bits.iter().enumerate() {
+        plugin.set_bit(idx, *value);
+    }
+    plugin.process(&mut PluginContext::default()).unwrap();
+    plugin.word()
+}
+
+#[test]
+fn inputs_pack_least_significant_bit_first() {
+    let mut plugin = BitPackPlugin::new(1);
+    assert_eq!(plugin.inputs().len(), 8);
+    assert_eq!(pack(&mut plugin, &[1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0]), 133.0);
+    // Only values above 0.5 are set bits
+    assert_eq!(pack(&mut plugin, &[0.5, 0.51, f64::NAN, -1.0, 0.0, 0.0, 0.0, 0.0]), 2.0);
+}
+
+#[test]
+fn bit_count_sets_the_inputs_and_is_clamped() {
+    let mut plugin = BitPackPlugin::new(1);
+    plugin.set_config(3);
+    let ports: Vec<&str> = plugin.inputs().iter().map(|p| p.id.0.as_str()).collect();
+    assert_eq!(ports, ["bit_0", "bit_1", "bit_2"]);
+    assert_eq!(pack(&mut plugin, &[1.0, 1.0, 1.0, 1.0]), 7.0);
+
+    plugin.set_config(100);
+    assert_eq!(plugin.bits(), MAX_BITS);
+    assert_eq!(pack(&mut plugin, &[1.0; MAX_BITS]), u32::MAX as f64);
+    plugin.set_config(0);
+    assert_eq!(plugin.get_variable("bits"), Some(1.into()));
+}
@@ -0,0 +1,134 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+/// Widest word; every word up to it is an exact integer in an `f64`.
+pub const MAX_BITS: usize = 32;
+
+pub fn bit_port(idx: usize) -> String {
+    format!("bit_{idx}")
+}
+
+/// Splits the integer `word` into digital outputs `bit_0..bit_<bits - 1>`,
+/// each 1 or 0, `bit_0` being the least significant bit. The word is
+/// rounded to the nearest integer; NaN and negative words give all zeros
+/// and bits above the configured count are ignored.
+pub struct BitUnpackPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    word: f64,
+    bit_values: Vec<f64>,
+}
+
+impl BitUnpackPlugin {
+    pub fn new(id: u64) -> Self {
+        let mut plugin = Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Bit Unpack".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![("bits".to_string(), Value::from(8))],
+            },
+            inputs: vec![Port { id: PortId("word".to_string()) }],
+            outputs: Vec::new(),
+            word: 0.0,
+            bit_values: Vec::new(),
+        };
+        plugin.set_config(8);
+        plugin
+    }
+
+    /// `bits` is clamped to `1..=MAX_BITS`; added outputs start at 0.
+    pub fn set_config(&mut self, bits: usize) {
+        let bits = bits.clamp(1, MAX_BITS);
+        if self.bit_values.len() == bits {
+            return;
+        }
+        self.outputs = (0..bits).map(|idx| Port { id: PortId(bit_port(idx)) }).collect();
+        self.bit_values.resize(bits, 0.0);
+    }
+
+    pub fn bits(&self) -> usize {
+        self.bit_values.len()
+    }
+
+    pub fn set_input(&mut self, word: f64) {
+        self.word = word;
+    }
+
+    /// Value of `bit_<idx>`, 0 past the configured bit count.
+    pub fn bit(&self, idx: usize) -> f64 {
+        self.bit_values.get(idx).copied().unwrap_or(0.0)
+    }
+}
+
+impl Plugin for BitUnpackPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        // Saturating cast: NaN and negatives become 0
+        let word = self.word.round() as u64;
+        for (idx, bit) in self.bit_values.iter_mut().enumerate() {
+            *bit = ((word >> idx) & 1) as f64;
+        }
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new().field(
+                ConfigField::integer("bits", "Bits")
+                    .min(1)
+                    .max(MAX_BITS as i64)
+                    .default_value(Value::from(8))
+                    .hint("One bit_N output per bit, bit_0 least significant"),
+            ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: false,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: self.outputs.iter().map(|p| p.id.0.clone()).collect(),
+            inputs: vec!["word".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "bits" => Some(Value::from(self.bits())),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        if let ("bits", Some(bits)) = (name, value.as_u64()) {
+            self.set_config(bits as usize);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use bit_unpack_plugin::{BitUnpackPlugin, MAX_BITS};
+use rtsyn_plugin::prelude::*;
+
+fn unpack(plugin: &mut BitUnpackPlugin, word: f64) -> Vec<f64> {
+    plugin.set_input(word);
+    plugin.process(&mut PluginContext::default()).unwrap();
+    (0..plugin.bits()).map(|idx| plugin.bit(idx)).collect()
+}
+
+#[test]
+fn word_unpacks_least_significant_bit_first() {
+    let mut plugin = BitUnpackPlugin::new(1);
+    assert_eq!(plugin.outputs().len(), 8);
+    assert_eq!(unpack(&mut plugin, 133.0), [1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+    // Rounded, and bits above the count are dropped
+    assert_eq!(unpack(&mut plugin, 256.0 + 2.6), [1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+    assert_eq!(unpack(&mut plugin, -5.0), [0.0; 8]);
+    assert_eq!(unpack(&mut plugin, f64::NAN), [0.0; 8]);
+}
+
+#[test]
+fn bit_count_sets_the_outputs_and_is_clamped() {
+    let mut plugin = BitUnpackPlugin::new(1);
+    plugin.set_config(2);
+    let ports: Vec<&str> = plugin.outputs().iter().map(|p| p.id.0.as_str()).collect();
+    assert_eq!(ports, ["bit_0", "bit_1"]);
+    assert_eq!(plugin.bit(5), 0.0);
+
+    plugin.set_config(64);
+    assert_eq!(plugin.bits(), MAX_BITS);
+    assert_eq!(unpack(&mut plugin, u32::MAX as f64), vec![1.0; MAX_BITS]);
+}
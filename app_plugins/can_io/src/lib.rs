@@ -0,0 +1,618 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// Frames drained from the socket per tick, so a flooded bus cannot stall
+/// the loop.
+const MAX_RX_FRAMES_PER_TICK: usize = 64;
+
+/// How long a device test listens for the RX frames.
+const TEST_LISTEN_WINDOW: std::time::Duration = std::time::Duration::from_millis(500);
+
+mod socketcan {
+    use libc::{c_int, c_void};
+    use std::ffi::CString;
+
+    const AF_CAN: c_int = 29;
+    const CAN_RAW: c_int = 1;
+    const CAN_EFF_FLAG: u32 = 0x8000_0000;
+    const CAN_RTR_FLAG: u32 = 0x4000_0000;
+    const CAN_ERR_FLAG: u32 = 0x2000_0000;
+    const CAN_EFF_MASK: u32 = 0x1FFF_FFFF;
+    const CAN_SFF_MASK: u32 = 0x0000_07FF;
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct can_frame {
+        can_id: u32,
+        can_dlc: u8,
+        _pad: u8,
+        _res0: u8,
+        _res1: u8,
+        data: [u8; 8],
+    }
+
+    #[repr(C)]
+    struct sockaddr_can {
+        can_family: libc::sa_family_t,
+        can_ifindex: c_int,
+        can_addr: [u64; 2],
+    }
+
+    fn last_error() -> String {
+        std::io::Error::last_os_error().to_string()
+    }
+
+    /// Non-blocking raw SocketCAN socket bound to one interface.
+    pub struct CanSocket {
+        fd: c_int,
+    }
+
+    impl CanSocket {
+        pub fn open(interface: &str) -> Result<Self, String> {
+            let name = CString::new(interface).map_err(|_| "invalid interface name".to_string())?;
+            let ifindex = unsafe { libc::if_nametoindex(name.as_ptr()) };
+            if ifindex == 0 {
+                return Err(format!("unknown CAN interface {interface}"));
+            }
+            let fd = unsafe { libc::socket(AF_CAN, libc::SOCK_RAW | libc::SOCK_NONBLOCK, CAN_RAW) };
+            if fd < 0 {
+                return Err(last_error());
+            }
+            let socket = Self { fd };
+            let addr = sockaddr_can {
+                can_family: AF_CAN as libc::sa_family_t,
+                can_ifindex: ifindex as c_int,
+                can_addr: [0; 2],
+            };
+            let res = unsafe {
+                libc::bind(
+                    fd,
+                    &addr as *const sockaddr_can as *const libc::sockaddr,
+                    std::mem::size_of::<sockaddr_can>() as libc::socklen_t,
+                )
+            };
+            if res < 0 {
+                return Err(last_error());
+            }
+            Ok(socket)
+        }
+
+        pub fn send(&self, can_id: u32, data: &[u8]) -> Result<(), String> {
+            let mut frame = can_frame {
+                can_id: if can_id > CAN_SFF_MASK {
+                    (can_id & CAN_EFF_MASK) | CAN_EFF_FLAG
+                } else {
+                    can_id
+                },
+                can_dlc: data.len().min(8) as u8,
+                ..Default::default()
+            };
+            frame.data[..data.len().min(8)].copy_from_slice(&data[..data.len().min(8)]);
+            let size = std::mem::size_of::<can_frame>();
+            let written = unsafe {
+                libc::write(self.fd, &frame as *const can_frame as *const c_void, size)
+            };
+            if written < 0 {
+                Err(last_error())
+            } else {
+                Ok(())
+            }
+        }
+
+        /// Next pending data frame, skipping remote and error frames.
+        pub fn recv(&self) -> Option<(u32, [u8; 8])> {
+            loop {
+                let mut frame = can_frame::default();
+                let size = std::mem::size_of::<can_frame>();
+                let read = unsafe {
+                    libc::read(self.fd, &mut frame as *mut can_frame as *mut c_void, size)
+                };
+                if read < size as isize {
+                    return None;
+                }
+                if frame.can_id & (CAN_RTR_FLAG | CAN_ERR_FLAG) != 0 {
+                    continue;
+                }
+                let id = if frame.can_id & CAN_EFF_FLAG != 0 {
+                    frame.can_id & CAN_EFF_MASK
+                } else {
+                    frame.can_id & CAN_SFF_MASK
+                };
+                return Some((id, frame.data));
+            }
+        }
+    }
+
+    impl Drop for CanSocket {
+        fn drop(&mut self) {
+            unsafe { libc::close(self.fd) };
+        }
+    }
+}
+
+/// One signal packed into a CAN frame: little-endian (Intel) bit layout,
+/// unsigned raw value, `physical = raw * scale + offset`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalMapping {
+    pub name: String,
+    pub can_id: u32,
+    pub start_bit: u32,
+    pub length: u32,
+    pub scale: f64,
+    pub offset: f64,
+}
+
+impl SignalMapping {
+    /// Parses `name:id:start_bit:length[:scale[:offset]]`, where `id` may be
+    /// decimal or `0x` hex, e.g. `speed:0x100:0:16:0.01:0`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = spec.split(':').map(str::trim).collect();
+        if parts.len() < 4 || parts.len() > 6 {
+            return Err(format!("expected name:id:start:length[:scale[:offset]], got '{spec}'"));
+        }
+        let name = parts[0].to_string();
+        if name.is_empty() {
+            return Err("signal name is empty".to_string());
+        }
+        let can_id = match parts[1].strip_prefix("0x").or_else(|| parts[1].strip_prefix("0X")) {
+            Some(hex) => u32::from_str_radix(hex, 16),
+            None => parts[1].parse(),
+        }
+        .map_err(|_| format!("invalid CAN id '{}'", parts[1]))?;
+        if can_id > 0x1FFF_FFFF {
+            return Err(format!("CAN id '{}' is out of range", parts[1]));
+        }
+        let start_bit: u32 = parts[2]
+            .parse()
+            .map_err(|_| format!("invalid start bit '{}'", parts[2]))?;
+        let length: u32 = parts[3]
+            .parse()
+            .map_err(|_| format!("invalid length '{}'", parts[3]))?;
+        if length == 0 || start_bit + length > 64 {
+            return Err(format!("signal '{name}' does not fit in 64 bits"));
+        }
+        let scale = match parts.get(4) {
+            Some(v) => v.parse().map_err(|_| format!("invalid scale '{v}'"))?,
+            None => 1.0,
+        };
+        if scale == 0.0 {
+            return Err(format!("signal '{name}' has a zero scale"));
+        }
+        let offset = match parts.get(5) {
+            Some(v) => v.parse().map_err(|_| format!("invalid offset '{v}'"))?,
+            None => 0.0,
+        };
+        Ok(Self {
+            name,
+            can_id,
+            start_bit,
+            length,
+            scale,
+            offset,
+        })
+    }
+
+    fn max_raw(&self) -> u64 {
+        if self.length == 64 {
+            u64::MAX
+        } else {
+            (1u64 << self.length) - 1
+        }
+    }
+
+    /// Number of data bytes the signal reaches into.
+    pub fn byte_len(&self) -> usize {
+        (self.start_bit + self.length).div_ceil(8) as usize
+    }
+
+    /// Writes `value` into `data`, saturating at the signal's raw range.
+    pub fn encode(&self, value: f64, data: &mut [u8; 8]) {
+        let raw = ((value - self.offset) / self.scale).round();
+        let raw = if raw.is_nan() || raw <= 0.0 {
+            0
+        } else {
+            (raw as u64).min(self.max_raw())
+        };
+        let mask = self.max_raw() << self.start_bit;
+        let word = (u64::from_le_bytes(*data) & !mask) | (raw << self.start_bit);
+        *data = word.to_le_bytes();
+    }
+
+    pub fn decode(&self, data: &[u8; 8]) -> f64 {
+        let raw = (u64::from_le_bytes(*data) >> self.start_bit) & self.max_raw();
+        raw as f64 * self.scale + self.offset
+    }
+}
+
+fn parse_mappings(specs: &[String]) -> Vec<SignalMapping> {
+    specs
+        .iter()
+        .filter(|spec| !spec.trim().is_empty())
+        .filter_map(|spec| SignalMapping::parse(spec).ok())
+        .collect()
+}
+
+/// Parses every non-empty spec of `direction`, failing on the first invalid
+/// one instead of skipping it.
+fn check_mappings(direction: &str, specs: &[String]) -> Result<usize, String> {
+    let mut count = 0;
+    for spec in specs.iter().filter(|spec| !spec.trim().is_empty()) {
+        SignalMapping::parse(spec).map_err(|err| format!("{direction} signal '{spec}': {err}"))?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Per-channel outcome of a one-shot device test, in mapping order.
+pub type ChannelResults = Vec<(String, Result<f64, String>)>;
+
+pub struct CanIoPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    interface: String,
+    tx_specs: Vec<String>,
+    rx_specs: Vec<String>,
+    tx_signals: Vec<SignalMapping>,
+    rx_signals: Vec<SignalMapping>,
+    input_values: HashMap<String, f64>,
+    output_values: HashMap<String, f64>,
+    safe_value: f64,
+    socket: Option<socketcan::CanSocket>,
+    open_error: Option<String>,
+}
+
+impl CanIoPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "CAN I/O Device Driver".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("interface".to_string(), Value::from("can0")),
+                    ("tx_signals".to_string(), Value::Array(Vec::new())),
+                    ("rx_signals".to_string(), Value::Array(Vec::new())),
+                    ("safe_value".to_string(), Value::from(0.0)),
+                ],
+            },
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            interface: "can0".to_string(),
+            tx_specs: Vec::new(),
+            rx_specs: Vec::new(),
+            tx_signals: Vec::new(),
+            rx_signals: Vec::new(),
+            input_values: HashMap::new(),
+            output_values: HashMap::new(),
+            safe_value: 0.0,
+            socket: None,
+            open_error: None,
+        }
+    }
+
+    /// Applies the interface and signal mappings. Invalid mapping entries are
+    /// skipped; changing the interface closes the socket so it is reopened on
+    /// the new one.
+    pub fn set_config(&mut self, interface: &str, tx_specs: &[String], rx_specs: &[String]) {
+        if self.interface != interface {
+            self.interface = interface.to_string();
+            self.socket = None;
+            self.open_error = None;
+        }
+        if self.tx_specs != tx_specs || self.rx_specs != rx_specs {
+            self.tx_specs = tx_specs.to_vec();
+            self.rx_specs = rx_specs.to_vec();
+            self.tx_signals = parse_mappings(tx_specs);
+            self.rx_signals = parse_mappings(rx_specs);
+            self.update_ports();
+        }
+    }
+
+    /// Checks the configuration and that the interface can be opened, so a
+    /// driver that cannot run is not started. Invalid signal specs, which
+    /// [`Self::set_config`] skips, are reported here.
+    pub fn check_start(&self) -> Result<(), String> {
+        if self.interface.trim().is_empty() {
+            return Err("no CAN interface is set".to_string());
+        }
+        let mapped = check_mappings("tx", &self.tx_specs)? + check_mappings("rx", &self.rx_specs)?;
+        if mapped == 0 {
+            return Err("no tx or rx signals are mapped".to_string());
+        }
+        socketcan::CanSocket::open(&self.interface)
+            .map(drop)
+            .map_err(|err| format!("cannot open {}: {err}", self.interface))
+    }
+
+    /// Opens the interface, sends the safe value on every TX frame when
+    /// `write_safe` is set, listens for the RX frames for a short window and
+    /// closes. RX signals whose frame never arrives get an error of their
+    /// own; only an interface that cannot be opened fails the whole test.
+    pub fn test_channels(&self, write_safe: bool) -> Result<ChannelResults, String> {
+        let socket = socketcan::CanSocket::open(&self.interface)
+            .map_err(|err| format!("cannot open {}: {err}", self.interface))?;
+        let mut results = Vec::new();
+        if write_safe {
+            let frames = self.pack_frames(|_| self.safe_value);
+            for signal in &self.tx_signals {
+                let result = socket
+                    .send(signal.can_id, &frames[&signal.can_id])
+                    .map(|()| self.safe_value);
+                results.push((format!("{} (write)", signal.name), result));
+            }
+        }
+        let mut latest: HashMap<u32, [u8; 8]> = HashMap::new();
+        let deadline = std::time::Instant::now() + TEST_LISTEN_WINDOW;
+        while std::time::Instant::now() < deadline
+            && !self.rx_signals.iter().all(|s| latest.contains_key(&s.can_id))
+        {
+            match socket.recv() {
+                Some((can_id, data)) => {
+                    latest.insert(can_id, data);
+                }
+                None => std::thread::sleep(std::time::Duration::from_millis(5)),
+            }
+        }
+        for signal in &self.rx_signals {
+            let result = latest
+                .get(&signal.can_id)
+                .map(|data| signal.decode(data))
+                .ok_or_else(|| format!("no frame with id 0x{:X} received", signal.can_id));
+            results.push((signal.name.clone(), result));
+        }
+        Ok(results)
+    }
+
+    fn update_ports(&mut self) {
+        self.inputs = self
+            .tx_signals
+            .iter()
+            .map(|s| Port { id: PortId(s.name.clone()) })
+            .collect();
+        self.outputs = self
+            .rx_signals
+            .iter()
+            .map(|s| Port { id: PortId(s.name.clone()) })
+            .collect();
+        self.input_values
+            .retain(|name, _| self.tx_signals.iter().any(|s| &s.name == name));
+        self.output_values
+            .retain(|name, _| self.rx_signals.iter().any(|s| &s.name == name));
+    }
+
+    pub fn input_port_names(&self) -> Vec<String> {
+        self.tx_signals.iter().map(|s| s.name.clone()).collect()
+    }
+
+    pub fn output_port_names(&self) -> Vec<String> {
+        self.rx_signals.iter().map(|s| s.name.clone()).collect()
+    }
+
+    pub fn set_input(&mut self, port_name: &str, value: f64) {
+        self.input_values.insert(port_name.to_string(), value);
+    }
+
+    pub fn get_output(&self, port_name: &str) -> f64 {
+        self.output_values.get(port_name).copied().unwrap_or(0.0)
+    }
+
+    /// Value every TX signal is commanded to in the safe state.
+    pub fn set_safe_value(&mut self, safe_value: f64) {
+        self.safe_value = safe_value;
+    }
+
+    /// Overrides every TX signal with the safe value until the next inputs
+    /// are set; the runtime calls it each tick while an emergency stop is
+    /// asserted.
+    pub fn safe_state(&mut self) {
+        for signal in &self.tx_signals {
+            self.input_values.insert(signal.name.clone(), self.safe_value);
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.socket.is_some()
+    }
+
+    /// False after a failed open until the interface changes, the driver is
+    /// closed or [`Self::retry_open`] is called, so a missing interface is not
+    /// retried every tick.
+    pub fn can_retry_open(&self) -> bool {
+        self.open_error.is_none()
+    }
+
+    /// Why the last open failed, e.g. the interface does not exist.
+    pub fn open_error(&self) -> Option<&str> {
+        self.open_error.as_deref()
+    }
+
+    /// Lets the runtime try opening the interface again.
+    pub fn retry_open(&mut self) {
+        self.open_error = None;
+    }
+
+    /// Packs the current inputs into one data payload per CAN id.
+    pub fn tx_frames(&self) -> BTreeMap<u32, Vec<u8>> {
+        self.pack_frames(|signal| self.input_values.get(&signal.name).copied().unwrap_or(0.0))
+    }
+
+    fn pack_frames(&self, value_of: impl Fn(&SignalMapping) -> f64) -> BTreeMap<u32, Vec<u8>> {
+        let mut frames: BTreeMap<u32, ([u8; 8], usize)> = BTreeMap::new();
+        for signal in &self.tx_signals {
+            let value = value_of(signal);
+            let entry = frames.entry(signal.can_id).or_insert(([0; 8], 0));
+            signal.encode(value, &mut entry.0);
+            entry.1 = entry.1.max(signal.byte_len());
+        }
+        frames
+            .into_iter()
+            .map(|(id, (data, len))| (id, data[..len].to_vec()))
+            .collect()
+    }
+
+    /// Updates the outputs mapped to `can_id` from a received payload.
+    pub fn handle_rx_frame(&mut self, can_id: u32, data: &[u8; 8]) {
+        for signal in &self.rx_signals {
+            if signal.can_id == can_id {
+                self.output_values
+                    .insert(signal.name.clone(), signal.decode(data));
+            }
+        }
+    }
+}
+
+impl Plugin for CanIoPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        let Some(socket) = self.socket.as_ref() else {
+            return Ok(());
+        };
+        let mut result = Ok(());
+        for (can_id, data) in self.tx_frames() {
+            if socket.send(can_id, &data).is_err() {
+                result = Err(PluginError::ProcessingFailed);
+            }
+        }
+        let mut received = Vec::new();
+        while received.len() < MAX_RX_FRAMES_PER_TICK {
+            match socket.recv() {
+                Some(frame) => received.push(frame),
+                None => break,
+            }
+        }
+        for (can_id, data) in received {
+            self.handle_rx_frame(can_id, &data);
+        }
+        result
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: false,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::text("interface", "Interface")
+                        .default_value(Value::from("can0"))
+                        .hint("SocketCAN interface (e.g. can0, vcan0)"),
+                )
+                .field(
+                    ConfigField::dynamic_list("tx_signals", "TX signals")
+                        .hint("name:id:start_bit:length[:scale[:offset]], one input per signal"),
+                )
+                .field(
+                    ConfigField::dynamic_list("rx_signals", "RX signals")
+                        .hint("name:id:start_bit:length[:scale[:offset]], one output per signal"),
+                )
+                .field(
+                    ConfigField::float("safe_value", "Safe value")
+                        .default_value(Value::from(0.0))
+                        .hint("Sent on every TX signal while an emergency stop is asserted"),
+                ),
+        )
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            inputs: self.input_port_names(),
+            outputs: self.output_port_names(),
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        let specs_value =
+            |specs: &[String]| Value::Array(specs.iter().cloned().map(Value::String).collect());
+        match name {
+            "interface" => Some(Value::String(self.interface.clone())),
+            "tx_signals" => Some(specs_value(&self.tx_specs)),
+            "rx_signals" => Some(specs_value(&self.rx_specs)),
+            "safe_value" => Some(Value::from(self.safe_value)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        let specs = |value: &Value| -> Vec<String> {
+            value
+                .as_array()
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|v| v.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        match name {
+            "interface" => {
+                if let Some(interface) = value.as_str() {
+                    let (tx, rx) = (self.tx_specs.clone(), self.rx_specs.clone());
+                    self.set_config(interface, &tx, &rx);
+                }
+            }
+            "tx_signals" => {
+                let (interface, rx) = (self.interface.clone(), self.rx_specs.clone());
+                self.set_config(&interface, &specs(&value), &rx);
+            }
+            "rx_signals" => {
+                let (interface, tx) = (self.interface.clone(), self.tx_specs.clone());
+                self.set_config(&interface, &tx, &specs(&value));
+            }
+            "safe_value" => {
+                if let Some(v) = value.as_f64() {
+                    self.safe_value = v;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl DeviceDriver for CanIoPlugin {
+    fn open(&mut self) -> Result<(), PluginError> {
+        match socketcan::CanSocket::open(&self.interface) {
+            Ok(socket) => {
+                self.socket = Some(socket);
+                self.open_error = None;
+                Ok(())
+            }
+            Err(err) => {
+                self.open_error = Some(format!("{}: {err}", self.interface));
+                Err(PluginError::ProcessingFailed)
+            }
+        }
+    }
+
+    fn close(&mut self) -> Result<(), PluginError> {
+        self.socket = None;
+        self.open_error = None;
+        Ok(())
+    }
+}
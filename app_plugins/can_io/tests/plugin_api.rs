@@ -0,0 +1,107 @@
+use can_io_plugin::{CanIoPlugin, SignalMapping};
+use rtsyn_plugin::prelude::*;
+
+#[test]
+fn signal_spec_parsing() {
+    let signal = SignalMapping::parse("speed:0x100:8:16:0.01:-10").unwrap();
+    assert_eq!(signal.name, "speed");
+    assert_eq!(signal.can_id, 0x100);
+    assert_eq!((signal.start_bit, signal.length), (8, 16));
+    assert_eq!((signal.scale, signal.offset), (0.01, -10.0));
+    assert_eq!(signal.byte_len(), 3);
+
+    let defaults = SignalMapping::parse("flag:256:0:1").unwrap();
+    assert_eq!((defaults.can_id, defaults.scale, defaults.offset), (256, 1.0, 0.0));
+
+    assert!(SignalMapping::parse("bad:0x100:60:8").is_err());
+    assert!(SignalMapping::parse("bad:0x100:0:0").is_err());
+    assert!(SignalMapping::parse(":0x100:0:8").is_err());
+    assert!(SignalMapping::parse("bad:zz:0:8").is_err());
+}
+
+#[test]
+fn encode_decode_round_trip_and_saturation() {
+    let signal = SignalMapping::parse("temp:0x10:4:12:0.5:-40").unwrap();
+    let mut data = [0xFFu8; 8];
+    signal.encode(25.0, &mut data);
+    assert_eq!(signal.decode(&data), 25.0);
+    // Bits outside the signal are untouched
+    assert_eq!(data[0] & 0x0F, 0x0F);
+    assert_eq!(data[2], 0xFF);
+
+    signal.encode(1.0e9, &mut data);
+    assert_eq!(signal.decode(&data), 4095.0 * 0.5 - 40.0);
+    signal.encode(-1.0e9, &mut data);
+    assert_eq!(signal.decode(&data), -40.0);
+}
+
+#[test]
+fn ports_follow_mappings_and_frames_group_by_id() {
+    let mut plugin = CanIoPlugin::new(1);
+    plugin.set_config(
+        "vcan0",
+        &[
+            "a:0x200:0:8".to_string(),
+            "b:0x200:8:8".to_string(),
+            "not a mapping".to_string(),
+        ],
+        &["rpm:0x300:0:16:0.25".to_string()],
+    );
+    assert_eq!(plugin.input_port_names(), vec!["a", "b"]);
+    assert_eq!(plugin.output_port_names(), vec!["rpm"]);
+    assert_eq!(plugin.inputs().len(), 2);
+
+    plugin.set_input("a", 3.0);
+    plugin.set_input("b", 7.0);
+    let frames = plugin.tx_frames();
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[&0x200], vec![3, 7]);
+
+    plugin.handle_rx_frame(0x300, &[0x10, 0x27, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(plugin.get_output("rpm"), 2500.0);
+    plugin.handle_rx_frame(0x301, &[0xFF; 8]);
+    assert_eq!(plugin.get_output("rpm"), 2500.0);
+
+    // Without an open socket processing is a no-op
+    let mut ctx = PluginContext::default();
+    assert!(plugin.process(&mut ctx).is_ok());
+    assert!(!plugin.is_open());
+}
+
+#[test]
+fn safe_state_commands_every_tx_signal() {
+    let mut plugin = CanIoPlugin::new(1);
+    plugin.set_config(
+        "vcan0",
+        &["a:0x200:0:8".to_string(), "b:0x200:8:8".to_string()],
+        &[],
+    );
+    plugin.set_input("a", 3.0);
+    plugin.set_input("b", 7.0);
+    plugin.set_safe_value(1.0);
+    plugin.safe_state();
+    assert_eq!(plugin.tx_frames()[&0x200], vec![1, 1]);
+}
+
+#[test]
+fn failed_open_keeps_the_reason_until_retried() {
+    let mut plugin = CanIoPlugin::new(1);
+    plugin.set_config("rtsyn_missing0", &[], &[]);
+    assert!(plugin.can_retry_open());
+
+    assert!(plugin.open().is_err());
+    assert!(!plugin.can_retry_open());
+    assert!(plugin.open_error().unwrap().starts_with("rtsyn_missing0: "));
+
+    plugin.retry_open();
+    assert!(plugin.can_retry_open());
+    assert_eq!(plugin.open_error(), None);
+}
+
+#[test]
+fn test_channels_fails_as_a_whole_without_an_interface() {
+    let mut plugin = CanIoPlugin::new(1);
+    plugin.set_config("rtsyn_missing0", &[], &["speed:0x100:0:16".to_string()]);
+    let err = plugin.test_channels(false).unwrap_err();
+    assert!(err.starts_with("cannot open rtsyn_missing0: "));
+}
@@ -178,6 +178,9 @@ mod comedilib {
     }
 }
 
+/// Per-channel outcome of a one-shot device test, in port order.
+pub type ChannelResults = Vec<(String, Result<f64, String>)>;
+
 pub struct ComediDaqPlugin {
     id: PluginId,
     meta: PluginMeta,
@@ -192,8 +195,10 @@ pub struct ComediDaqPlugin {
 
     input_values: HashMap<String, f64>,
     output_values: HashMap<String, f64>,
+    safe_value: f64,
 
     is_open: bool,
+    open_error: Option<String>,
     last_scan_devices: bool,
     last_scan_nonce: u64,
     active_inputs: Vec<bool>,
@@ -222,6 +227,7 @@ impl ComediDaqPlugin {
                     ("device_path".to_string(), Value::from("/dev/comedi0")),
                     ("scan_devices".to_string(), Value::from(false)),
                     ("scan_nonce".to_string(), Value::from(0_u64)),
+                    ("safe_value".to_string(), Value::from(0.0)),
                 ],
             },
             inputs: Vec::new(),
@@ -233,7 +239,9 @@ impl ComediDaqPlugin {
             ao_channels: Vec::new(),
             input_values: HashMap::new(),
             output_values: HashMap::new(),
+            safe_value: 0.0,
             is_open: false,
+            open_error: None,
             last_scan_devices: false,
             last_scan_nonce: 0,
             active_inputs: Vec::new(),
@@ -249,6 +257,7 @@ impl ComediDaqPlugin {
         let changed = self.device_path != device_path;
         if changed {
             self.device_path = device_path;
+            self.open_error = None;
         }
         if changed || (scan_devices && !self.last_scan_devices) || scan_nonce != self.last_scan_nonce
         {
@@ -266,10 +275,92 @@ impl ComediDaqPlugin {
         self.output_values.get(port_name).copied().unwrap_or(0.0)
     }
 
+    /// Value every analog output is driven to in the safe state.
+    pub fn set_safe_value(&mut self, safe_value: f64) {
+        self.safe_value = safe_value;
+    }
+
+    /// Drives every analog output, connected or not, to the safe value on the
+    /// next `process`; the runtime calls it each tick while an emergency stop
+    /// is asserted.
+    pub fn safe_state(&mut self) {
+        self.active_inputs = vec![true; self.input_port_names.len()];
+        for port in &self.input_port_names {
+            self.input_values.insert(port.clone(), self.safe_value);
+        }
+    }
+
     pub fn is_open(&self) -> bool {
         self.is_open
     }
 
+    /// False after a failed open until the device path changes, the driver
+    /// is closed or [`Self::retry_open`] is called.
+    pub fn can_retry_open(&self) -> bool {
+        self.open_error.is_none()
+    }
+
+    /// Why the last open failed, e.g. the device node does not exist.
+    pub fn open_error(&self) -> Option<&str> {
+        self.open_error.as_deref()
+    }
+
+    /// Lets the runtime try opening the device again.
+    pub fn retry_open(&mut self) {
+        self.open_error = None;
+    }
+
+    /// Opens and closes the device, so a driver whose device is missing or
+    /// unusable is not started. The error carries comedilib's message.
+    pub fn check_start(&self) -> Result<(), String> {
+        let device_path = Self::normalize_device_path(&self.device_path);
+        if device_path.trim().is_empty() {
+            return Err("no device path is set".to_string());
+        }
+        let dev = unsafe { comedilib::open(device_path) }
+            .map_err(|err| format!("cannot open {device_path}: {err}"))?;
+        unsafe { comedilib::close(dev) };
+        if self.input_port_names.is_empty() && self.output_port_names.is_empty() {
+            return Err(format!("{device_path} has no analog input or output channels"));
+        }
+        Ok(())
+    }
+
+    /// Opens the device, writes the safe value to every analog output when
+    /// `write_safe` is set, reads every analog input once and closes. Each
+    /// channel gets its value or its own comedilib error; only a device that
+    /// cannot be opened fails the whole test.
+    pub fn test_channels(&self, write_safe: bool) -> Result<ChannelResults, String> {
+        let device_path = Self::normalize_device_path(&self.device_path);
+        let dev = unsafe { comedilib::open(device_path) }
+            .map_err(|err| format!("cannot open {device_path}: {err}"))?;
+        let mut results = Vec::new();
+        if write_safe {
+            for (sd, ch) in &self.ao_channels {
+                let result = unsafe {
+                    comedilib::get_range(dev, *sd, *ch).and_then(|range| {
+                        let max = comedilib::get_maxdata(dev, *sd, *ch)?;
+                        let raw = comedilib::from_phys(self.safe_value, &range, max);
+                        comedilib::write(dev, *sd, *ch, raw)
+                    })
+                };
+                results.push((format!("ao{sd}_{ch} (write)"), result.map(|()| self.safe_value)));
+            }
+        }
+        for (sd, ch) in &self.ai_channels {
+            let result = unsafe {
+                comedilib::read(dev, *sd, *ch).and_then(|raw| {
+                    let range = comedilib::get_range(dev, *sd, *ch)?;
+                    let max = comedilib::get_maxdata(dev, *sd, *ch)?;
+                    Ok(comedilib::to_phys(raw, &range, max))
+                })
+            };
+            results.push((format!("ai{sd}_{ch}"), result));
+        }
+        unsafe { comedilib::close(dev) };
+        Ok(results)
+    }
+
     pub fn set_active_ports(
         &mut self,
         input_ports: &std::collections::HashSet<String>,
@@ -462,6 +553,11 @@ impl Plugin for ComediDaqPlugin {
                     ConfigField::boolean("scan_devices", "Scan Channels")
                         .default_value(Value::Bool(false))
                         .hint("Toggle to rescan channels"),
+                )
+                .field(
+                    ConfigField::float("safe_value", "Safe value")
+                        .default_value(Value::from(0.0))
+                        .hint("Written to every analog output while an emergency stop is asserted"),
                 ),
         )
     }
@@ -479,6 +575,7 @@ impl Plugin for ComediDaqPlugin {
             "device_path" => Some(Value::String(self.device_path.clone())),
             "scan_devices" => Some(Value::Bool(self.last_scan_devices)),
             "scan_nonce" => Some(Value::from(self.last_scan_nonce)),
+            "safe_value" => Some(Value::from(self.safe_value)),
             _ => None,
         }
     }
@@ -489,6 +586,7 @@ impl Plugin for ComediDaqPlugin {
                 if let Value::String(s) = value {
                     if self.device_path != s {
                         self.device_path = s;
+                        self.open_error = None;
                         self.auto_configure();
                     }
                 }
@@ -509,6 +607,11 @@ impl Plugin for ComediDaqPlugin {
                     }
                 }
             }
+            "safe_value" => {
+                if let Some(v) = value.as_f64() {
+                    self.safe_value = v;
+                }
+            }
             _ => {}
         }
         Ok(())
@@ -519,9 +622,16 @@ impl Plugin for ComediDaqPlugin {
 impl DeviceDriver for ComediDaqPlugin {
     fn open(&mut self) -> Result<(), PluginError> {
         let device_path = Self::normalize_device_path(&self.device_path);
-        let dev = unsafe { comedilib::open(device_path) }.map_err(Self::comedi_error)?;
+        let dev = match unsafe { comedilib::open(device_path) } {
+            Ok(dev) => dev,
+            Err(err) => {
+                self.open_error = Some(format!("{device_path}: {err}"));
+                return Err(Self::comedi_error(err));
+            }
+        };
         self.dev = std::ptr::NonNull::new(dev);
         self.is_open = true;
+        self.open_error = None;
         Ok(())
     }
 
@@ -530,6 +640,7 @@ impl DeviceDriver for ComediDaqPlugin {
             unsafe { comedilib::close(dev.as_ptr()) };
         }
         self.is_open = false;
+        self.open_error = None;
         Ok(())
     }
 }
@@ -1,8 +1,105 @@
 use rtsyn_plugin::prelude::*;
 use serde_json::Value;
 use std::fs::OpenOptions;
-use std::io::Write;
+use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const WRITE_MODES: [&str; 2] = ["overwrite", "append"];
+pub const WALL_TIME_FORMATS: [&str; 2] = ["iso8601", "unix"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WriteMode {
+    /// Each recording starts a fresh file.
+    #[default]
+    Overwrite,
+    /// Recordings are added to the end of the file, under one header.
+    Append,
+}
+
+impl WriteMode {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "append" => WriteMode::Append,
+            _ => WriteMode::Overwrite,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WriteMode::Overwrite => "overwrite",
+            WriteMode::Append => "append",
+        }
+    }
+}
+
+/// Format of the wall clock column, captured when each row is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WallTimeFormat {
+    /// UTC date and time, e.g. `2024-05-01T12:00:00.125000Z`.
+    #[default]
+    Iso8601,
+    /// Seconds since the Unix epoch, with microseconds.
+    Unix,
+}
+
+impl WallTimeFormat {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "unix" => WallTimeFormat::Unix,
+            _ => WallTimeFormat::Iso8601,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WallTimeFormat::Iso8601 => "iso8601",
+            WallTimeFormat::Unix => "unix",
+        }
+    }
+
+    fn column(&self) -> &'static str {
+        match self {
+            WallTimeFormat::Iso8601 => "wall_time",
+            WallTimeFormat::Unix => "unix_time",
+        }
+    }
+
+    pub fn timestamp(&self, time: SystemTime) -> String {
+        let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let seconds = since_epoch.as_secs();
+        let micros = since_epoch.subsec_micros();
+        match self {
+            WallTimeFormat::Unix => format!("{seconds}.{micros:06}"),
+            WallTimeFormat::Iso8601 => {
+                let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+                let time_of_day = seconds % 86_400;
+                format!(
+                    "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{micros:06}Z",
+                    time_of_day / 3600,
+                    time_of_day / 60 % 60,
+                    time_of_day % 60
+                )
+            }
+        }
+    }
+}
+
+/// Gregorian date of a day count since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    // Months counted from March, so the leap day is the last of the year
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month as u32, day as u32)
+}
 
 pub struct CsvRecorderedPlugin {
     id: PluginId,
@@ -11,15 +108,32 @@ pub struct CsvRecorderedPlugin {
     separator: String,
     columns: Vec<String>,
     include_time: bool,
+    /// Wall clock column written before the time column, if any.
+    wall_time: Option<WallTimeFormat>,
     time_scale: f64,
     time_label: String,
     time_seconds: f64,
     time_step: f64,
+    /// Tick of the last recorded row; the time column advances by the ticks
+    /// since, so ticks the scheduler skipped still count.
+    last_tick: Option<u64>,
     path: Option<PathBuf>,
     file: Option<std::fs::File>,
+    write_mode: WriteMode,
+    /// First line of a non-empty file opened for appending.
+    existing_header: Option<String>,
     header_written: bool,
+    error: Option<String>,
     input_values: Vec<f64>,
     recording: bool,
+    /// Bytes a file may reach before the next one is started; 0 never rotates.
+    rotate_bytes: u64,
+    /// Recorded seconds per file before the next one is started; 0 never rotates.
+    rotate_seconds: f64,
+    /// Index of the open file while rotating.
+    segment: Option<usize>,
+    file_bytes: u64,
+    file_seconds: f64,
 }
 
 impl CsvRecorderedPlugin {
@@ -34,21 +148,41 @@ impl CsvRecorderedPlugin {
                     ("path".to_string(), Value::from("")),
                     ("input_count".to_string(), Value::from(0)),
                     ("include_time".to_string(), Value::from(true)),
+                    ("include_wall_time".to_string(), Value::from(false)),
+                    ("wall_time_format".to_string(), Value::from("iso8601")),
+                    ("write_mode".to_string(), Value::from("overwrite")),
+                    ("skip_warmup".to_string(), Value::from(false)),
+                    (
+                        "column_template".to_string(),
+                        Value::from("{source_name}_{source_id}_{source_port}"),
+                    ),
+                    ("rotate_mb".to_string(), Value::from(0.0)),
+                    ("rotate_minutes".to_string(), Value::from(0.0)),
                 ],
             },
             inputs: Vec::new(),
             separator: ",".to_string(),
             columns: Vec::new(),
             include_time: true,
+            wall_time: None,
             time_scale: 1000.0,
             time_label: "time_ms".to_string(),
             time_seconds: 0.0,
             time_step: 0.001,
+            last_tick: None,
             path: None,
             file: None,
+            write_mode: WriteMode::Overwrite,
+            existing_header: None,
             header_written: false,
+            error: None,
             input_values: Vec::new(),
             recording: false,
+            rotate_bytes: 0,
+            rotate_seconds: 0.0,
+            segment: None,
+            file_bytes: 0,
+            file_seconds: 0.0,
         }
     }
 
@@ -56,6 +190,54 @@ impl CsvRecorderedPlugin {
         self.input_values = values;
     }
 
+    /// Takes effect on the next file open; reopens right away while
+    /// recording.
+    pub fn set_write_mode(&mut self, write_mode: WriteMode) {
+        if self.write_mode != write_mode {
+            self.write_mode = write_mode;
+            self.reopen_file();
+        }
+    }
+
+    /// Adds a wall clock column before the time column, read from the system
+    /// clock for every row; `None` leaves it out. Reopens the file while
+    /// recording, as the columns change.
+    pub fn set_wall_time(&mut self, wall_time: Option<WallTimeFormat>) {
+        if self.wall_time != wall_time {
+            self.wall_time = wall_time;
+            self.reopen_file();
+        }
+    }
+
+    /// Starts a new file once the current one holds `rotate_mb` megabytes or
+    /// `rotate_minutes` of recorded time; 0 turns a limit off. While rotating,
+    /// files are named `<stem>.000.csv`, `<stem>.001.csv`, ... next to the
+    /// configured path, and a new recording continues after the highest
+    /// existing index instead of overwriting earlier files.
+    pub fn set_rotation(&mut self, rotate_mb: f64, rotate_minutes: f64) {
+        let was_rotating = self.rotates();
+        self.rotate_bytes = (rotate_mb.max(0.0) * 1_000_000.0).round() as u64;
+        self.rotate_seconds = rotate_minutes.max(0.0) * 60.0;
+        if self.rotates() != was_rotating {
+            self.reopen_file();
+        }
+    }
+
+    /// The file rows are currently written to.
+    pub fn current_path(&self) -> Option<PathBuf> {
+        let path = self.path.as_ref()?;
+        Some(match self.segment {
+            Some(index) => segment_path(path, index),
+            None => path.clone(),
+        })
+    }
+
+    /// Why rows are not being written, e.g. an append target whose header
+    /// does not match the current columns.
+    pub fn last_error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
     pub fn set_config(
         &mut self,
         input_count: usize,
@@ -75,6 +257,7 @@ impl CsvRecorderedPlugin {
             || self.include_time != include_time;
         if !self.recording && recording {
             self.time_seconds = 0.0;
+            self.last_tick = None;
         }
         self.recording = recording;
         if changed {
@@ -101,46 +284,107 @@ impl CsvRecorderedPlugin {
     }
 
     fn reopen_file(&mut self) {
+        self.segment = None;
+        self.open_file();
+    }
+
+    fn rotates(&self) -> bool {
+        self.rotate_bytes > 0 || self.rotate_seconds > 0.0
+    }
+
+    /// Closes the current file and continues in the next one.
+    fn rotate(&mut self) {
+        self.segment = self.segment.map(|index| index + 1);
+        self.open_file();
+    }
+
+    fn open_file(&mut self) {
         self.file = None;
+        self.existing_header = None;
         self.header_written = false;
+        self.error = None;
+        self.file_bytes = 0;
+        self.file_seconds = 0.0;
         if !self.recording {
             return;
         }
-        let Some(path) = self.path.as_ref() else {
+        let Some(base) = self.path.as_ref() else {
             return;
         };
-        if path.as_os_str().is_empty() {
+        if base.as_os_str().is_empty() {
             return;
         }
+        let path = if self.rotates() {
+            let index = self.segment.unwrap_or_else(|| {
+                (0..)
+                    .find(|index| !segment_path(base, *index).exists())
+                    .unwrap_or(0)
+            });
+            self.segment = Some(index);
+            segment_path(base, index)
+        } else {
+            self.segment = None;
+            base.clone()
+        };
+        let path = path.as_path();
         if let Some(parent) = path.parent() {
             let _ = std::fs::create_dir_all(parent);
         }
-        let file = OpenOptions::new()
-            .create(true)
-            .write(true)
-            .truncate(true)
-            .open(path);
-        if let Ok(file) = file {
-            self.file = Some(file);
+        let mut options = OpenOptions::new();
+        options.create(true);
+        match self.write_mode {
+            WriteMode::Overwrite => options.write(true).truncate(true),
+            WriteMode::Append => options.append(true),
+        };
+        let file = match options.open(path) {
+            Ok(file) => file,
+            Err(err) => {
+                self.error = Some(format!("Cannot open {}: {err}", path.display()));
+                return;
+            }
+        };
+        if self.write_mode == WriteMode::Append
+            && file.metadata().map(|meta| meta.len() > 0).unwrap_or(false)
+        {
+            self.existing_header = first_line(path);
+        }
+        self.file = Some(file);
+    }
+
+    fn header(&self) -> String {
+        let mut columns = Vec::with_capacity(self.columns.len() + 2);
+        if let Some(wall_time) = self.wall_time {
+            columns.push(wall_time.column().to_string());
+        }
+        if self.include_time {
+            columns.push(self.time_label.clone());
         }
+        columns.extend(self.columns.iter().cloned());
+        columns.join(&self.separator)
     }
 
     fn write_header(&mut self) -> Result<(), PluginError> {
-        let Some(file) = self.file.as_mut() else {
-            return Ok(());
-        };
-        if self.header_written {
+        if self.file.is_none() || self.header_written {
             return Ok(());
         }
-        let header = if self.include_time {
-            let mut columns = Vec::with_capacity(self.columns.len() + 1);
-            columns.push(self.time_label.clone());
-            columns.extend(self.columns.iter().cloned());
-            columns.join(&self.separator)
-        } else {
-            self.columns.join(&self.separator)
-        };
-        writeln!(file, "{header}").map_err(|_| PluginError::ProcessingFailed)?;
+        let header = self.header();
+        match self.existing_header.as_deref() {
+            // Appending under the same columns: the header is already there
+            Some(existing) if existing == header => {}
+            Some(existing) => {
+                self.error = Some(format!(
+                    "Existing header \"{existing}\" does not match \"{header}\"; not appending"
+                ));
+                self.file = None;
+                return Ok(());
+            }
+            None => {
+                if let Some(file) = self.file.as_mut() {
+                    writeln!(file, "{header}").map_err(|_| PluginError::ProcessingFailed)?;
+                    self.file_bytes += header.len() as u64 + 1;
+                }
+            }
+        }
         self.header_written = true;
         Ok(())
     }
@@ -163,23 +407,34 @@ impl Plugin for CsvRecorderedPlugin {
         &[]
     }
 
-    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
         if !self.recording || self.file.is_none() {
             return Ok(());
         }
+        if let Some(last_tick) = self.last_tick {
+            self.time_seconds += ctx.tick.wrapping_sub(last_tick) as f64 * self.time_step.max(0.0);
+        }
+        self.last_tick = Some(ctx.tick);
         self.write_header()?;
         let Some(file) = self.file.as_mut() else {
             return Ok(());
         };
-        let mut values = Vec::with_capacity(self.input_values.len() + 1);
+        let mut values = Vec::with_capacity(self.input_values.len() + 2);
+        if let Some(wall_time) = self.wall_time {
+            values.push(wall_time.timestamp(SystemTime::now()));
+        }
         if self.include_time {
             values.push(format!("{}", self.time_seconds * self.time_scale));
         }
         values.extend(self.input_values.iter().map(|value| value.to_string()));
         let values = values.join(&self.separator);
         writeln!(file, "{values}").map_err(|_| PluginError::ProcessingFailed)?;
-        if self.include_time {
-            self.time_seconds += self.time_step.max(0.0);
+        self.file_bytes += values.len() as u64 + 1;
+        self.file_seconds += self.time_step.max(0.0);
+        let full = self.rotate_bytes > 0 && self.file_bytes >= self.rotate_bytes;
+        let old = self.rotate_seconds > 0.0 && self.file_seconds >= self.rotate_seconds;
+        if full || old {
+            self.rotate();
         }
         Ok(())
     }
@@ -210,6 +465,48 @@ impl Plugin for CsvRecorderedPlugin {
                             max_length: Some(100),
                         })
                         .add_label("Add column"),
+                )
+                .field(
+                    ConfigField::text("write_mode", "Write mode")
+                        .default_value(Value::from("overwrite"))
+                        .hint("overwrite or append; appending needs matching columns"),
+                )
+                .field(
+                    ConfigField::boolean("skip_warmup", "Skip rows during warmup")
+                        .default_value(Value::Bool(false))
+                        .hint("Start writing once the connected plugins have warmed up"),
+                )
+                .field(
+                    ConfigField::text("column_template", "Column name template")
+                        .default_value(Value::from("{source_name}_{source_id}_{source_port}"))
+                        .hint(
+                            "Names new columns; {source_name}, {source_kind}, {source_id}, \
+                             {source_port}, {recorder_name}, {recorder_id}, {input}",
+                        ),
+                )
+                .field(
+                    ConfigField::float("rotate_mb", "Rotate after (MB)")
+                        .min_f(0.0)
+                        .step_f(1.0)
+                        .default_value(Value::from(0.0))
+                        .hint("Start a new numbered file at this size; 0 never rotates"),
+                )
+                .field(
+                    ConfigField::float("rotate_minutes", "Rotate after (minutes)")
+                        .min_f(0.0)
+                        .step_f(1.0)
+                        .default_value(Value::from(0.0))
+                        .hint("Start a new numbered file after this much recorded time; 0 never rotates"),
+                )
+                .field(
+                    ConfigField::boolean("include_wall_time", "Include wall clock column")
+                        .default_value(Value::Bool(false))
+                        .hint("System time when each row is written"),
+                )
+                .field(
+                    ConfigField::text("wall_time_format", "Wall clock format")
+                        .default_value(Value::from("iso8601"))
+                        .hint("iso8601 (UTC) or unix (epoch seconds)"),
                 ),
         )
     }
@@ -233,7 +530,7 @@ impl Plugin for CsvRecorderedPlugin {
         Some(DisplaySchema {
             outputs: vec![],
             inputs: self.inputs.iter().map(|p| p.id.0.clone()).collect(),
-            variables: Vec::new(),
+            variables: vec!["error".to_string()],
         })
     }
 
@@ -241,6 +538,10 @@ impl Plugin for CsvRecorderedPlugin {
         match name {
             "separator" => Some(Value::String(self.separator.clone())),
             "include_time" => Some(Value::Bool(self.include_time)),
+            "include_wall_time" => Some(Value::Bool(self.wall_time.is_some())),
+            "wall_time_format" => Some(Value::from(
+                self.wall_time.unwrap_or_default().name(),
+            )),
             "path" => Some(Value::String(
                 self.path
                     .as_ref()
@@ -248,8 +549,15 @@ impl Plugin for CsvRecorderedPlugin {
                     .unwrap_or_default(),
             )),
             "columns" => Some(Value::Array(
-                self.columns.iter().map(|c| Value::String(c.clone())).collect()
+                self.columns
+                    .iter()
+                    .map(|c| Value::String(c.clone()))
+                    .collect(),
             )),
+            "write_mode" => Some(Value::from(self.write_mode.name())),
+            "rotate_mb" => Some(Value::from(self.rotate_bytes as f64 / 1_000_000.0)),
+            "rotate_minutes" => Some(Value::from(self.rotate_seconds / 60.0)),
+            "error" => Some(Value::from(self.error.clone().unwrap_or_default())),
             _ => None,
         }
     }
@@ -272,6 +580,16 @@ impl Plugin for CsvRecorderedPlugin {
                     }
                 }
             }
+            "include_wall_time" => {
+                if let Value::Bool(b) = value {
+                    self.set_wall_time(b.then(|| self.wall_time.unwrap_or_default()));
+                }
+            }
+            "wall_time_format" => {
+                if let (Value::String(format), Some(_)) = (value, self.wall_time) {
+                    self.set_wall_time(Some(WallTimeFormat::from_name(&format)));
+                }
+            }
             "path" => {
                 if let Value::String(p) = value {
                     let new_path = if p.trim().is_empty() {
@@ -285,6 +603,21 @@ impl Plugin for CsvRecorderedPlugin {
                     }
                 }
             }
+            "write_mode" => {
+                if let Value::String(mode) = value {
+                    self.set_write_mode(WriteMode::from_name(&mode));
+                }
+            }
+            "rotate_mb" => {
+                if let Some(mb) = value.as_f64() {
+                    self.set_rotation(mb, self.rotate_seconds / 60.0);
+                }
+            }
+            "rotate_minutes" => {
+                if let Some(minutes) = value.as_f64() {
+                    self.set_rotation(self.rotate_bytes as f64 / 1_000_000.0, minutes);
+                }
+            }
             "columns" => {
                 if let Value::Array(arr) = value {
                     let new_columns: Vec<String> = arr
@@ -303,7 +636,10 @@ impl Plugin for CsvRecorderedPlugin {
     }
 
     fn on_input_added(&mut self, port: &str) -> Result<(), PluginError> {
-        if let Some(idx) = port.strip_prefix("in_").and_then(|s| s.parse::<usize>().ok()) {
+        if let Some(idx) = port
+            .strip_prefix("in_")
+            .and_then(|s| s.parse::<usize>().ok())
+        {
             while self.inputs.len() <= idx {
                 let i = self.inputs.len();
                 self.inputs.push(Port {
@@ -317,7 +653,10 @@ impl Plugin for CsvRecorderedPlugin {
     }
 
     fn on_input_removed(&mut self, port: &str) -> Result<(), PluginError> {
-        if let Some(idx) = port.strip_prefix("in_").and_then(|s| s.parse::<usize>().ok()) {
+        if let Some(idx) = port
+            .strip_prefix("in_")
+            .and_then(|s| s.parse::<usize>().ok())
+        {
             if idx < self.inputs.len() {
                 self.inputs.remove(idx);
                 if idx < self.input_values.len() {
@@ -350,6 +689,27 @@ pub fn default_column_name(plugin_name: &str, plugin_id: u64, port: &str) -> Str
     format!("{}_{}_{}", safe_name, plugin_id, port.to_lowercase())
 }
 
+/// `run.csv` with index 2 becomes `run.002.csv`.
+fn segment_path(base: &Path, index: usize) -> PathBuf {
+    let stem = base
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = match base.extension() {
+        Some(ext) => format!("{stem}.{index:03}.{}", ext.to_string_lossy()),
+        None => format!("{stem}.{index:03}"),
+    };
+    base.with_file_name(name)
+}
+
+fn first_line(path: &Path) -> Option<String> {
+    let file = std::fs::File::open(path).ok()?;
+    let mut line = String::new();
+    BufReader::new(file).read_line(&mut line).ok()?;
+    let line = line.trim_end_matches(['\r', '\n']);
+    (!line.is_empty()).then(|| line.to_string())
+}
+
 pub fn normalize_path(path: &str) -> Option<PathBuf> {
     let trimmed = path.trim();
     if trimmed.is_empty() {
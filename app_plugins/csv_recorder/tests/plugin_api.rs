@@ -1,13 +1,15 @@
-use csv_recorder_plugin::CsvRecorderedPlugin;
+use csv_recorder_plugin::{CsvRecorderedPlugin, WallTimeFormat, WriteMode};
 use live_plotter_plugin::LivePlotterPlugin;
 use rtsyn_plugin::prelude::*;
 
 #[test]
 fn csv_recorder_has_ui_schema() {
     let plugin = CsvRecorderedPlugin::new(1);
-    let schema = plugin.ui_schema().expect("CSV recorder should have UI schema");
+    let schema = plugin
+        .ui_schema()
+        .expect("CSV recorder should have UI schema");
 
-    assert_eq!(schema.fields.len(), 4);
+    assert_eq!(schema.fields.len(), 11);
 
     // Check separator field
     assert_eq!(schema.fields[0].key, "separator");
@@ -31,6 +33,8 @@ fn csv_recorder_has_ui_schema() {
     } else {
         panic!("Expected DynamicList field type");
     }
+
+    assert_eq!(schema.fields[4].key, "write_mode");
 }
 
 #[test]
@@ -75,12 +79,197 @@ fn csv_recorder_dynamic_inputs() {
     assert_eq!(plugin.inputs()[1].id.0, "in_1"); // Reindexed from in_2
 }
 
+/// Records `rows` ticks of `values` into `path` as one start/stop session.
+fn record_session(
+    path: &std::path::Path,
+    columns: &[&str],
+    values: &[f64],
+    rows: usize,
+) -> Option<String> {
+    let mut plugin = CsvRecorderedPlugin::new(1);
+    let mut ctx = PluginContext::default();
+    plugin.set_write_mode(WriteMode::Append);
+    plugin.set_config(
+        columns.len(),
+        ",".to_string(),
+        columns.iter().map(|c| c.to_string()).collect(),
+        Some(path.to_path_buf()),
+        true,
+        false,
+        1.0,
+        "time".to_string(),
+        0.1,
+    );
+    for _ in 0..rows {
+        plugin.set_inputs(values.to_vec());
+        plugin.process(&mut ctx).unwrap();
+    }
+    plugin.last_error().map(str::to_string)
+}
+
+#[test]
+fn csv_recorder_append_mode_keeps_one_header() {
+    let path = std::env::temp_dir().join(format!("rtsyn_csv_append_{}.csv", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(record_session(&path, &["a", "b"], &[1.0, 2.0], 2), None);
+    assert_eq!(record_session(&path, &["a", "b"], &[3.0, 4.0], 1), None);
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "a,b\n1,2\n1,2\n3,4\n"
+    );
+
+    // A different column set is refused instead of mixing rows
+    let error = record_session(&path, &["a", "c"], &[5.0, 6.0], 1).expect("header mismatch");
+    assert!(error.contains("a,b"));
+    assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 4);
+
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(WriteMode::from_name("append"), WriteMode::Append);
+    assert_eq!(WriteMode::from_name("bogus"), WriteMode::Overwrite);
+}
+
+#[test]
+fn csv_recorder_prepends_a_wall_clock_column() {
+    let time = std::time::UNIX_EPOCH + std::time::Duration::from_micros(1_700_000_000_250_000);
+    assert_eq!(
+        WallTimeFormat::Iso8601.timestamp(time),
+        "2023-11-14T22:13:20.250000Z"
+    );
+    assert_eq!(WallTimeFormat::Unix.timestamp(time), "1700000000.250000");
+
+    let path = std::env::temp_dir().join(format!("rtsyn_csv_wall_{}.csv", std::process::id()));
+    let mut plugin = CsvRecorderedPlugin::new(1);
+    let mut ctx = PluginContext::default();
+    plugin.set_wall_time(Some(WallTimeFormat::Unix));
+    plugin.set_config(
+        1,
+        ",".to_string(),
+        vec!["a".to_string()],
+        Some(path.clone()),
+        true,
+        true,
+        1.0,
+        "time".to_string(),
+        0.5,
+    );
+    plugin.set_inputs(vec![2.0]);
+    plugin.process(&mut ctx).unwrap();
+    plugin.flush().unwrap();
+
+    let written = std::fs::read_to_string(&path).unwrap();
+    let mut lines = written.lines();
+    assert_eq!(lines.next(), Some("unix_time,time,a"));
+    let row: Vec<&str> = lines.next().unwrap().split(',').collect();
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs_f64();
+    assert!((now - row[0].parse::<f64>().unwrap()).abs() < 60.0);
+    assert_eq!(&row[1..], ["0", "2"]);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn csv_recorder_time_counts_skipped_ticks() {
+    let path = std::env::temp_dir().join(format!("rtsyn_csv_ticks_{}.csv", std::process::id()));
+    let mut plugin = CsvRecorderedPlugin::new(1);
+    plugin.set_config(
+        1,
+        ",".to_string(),
+        vec!["a".to_string()],
+        Some(path.clone()),
+        true,
+        true,
+        1.0,
+        "time".to_string(),
+        0.5,
+    );
+    // Ticks 2 and 3 were skipped by the scheduler
+    for tick in [0, 1, 4] {
+        plugin.set_inputs(vec![1.0]);
+        plugin.process(&mut PluginContext { tick, ..Default::default() }).unwrap();
+    }
+    plugin.flush().unwrap();
+
+    let written = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(written, "time,a\n0,1\n0.5,1\n2,1\n");
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn csv_recorder_rotates_into_numbered_files() {
+    let dir = std::env::temp_dir().join(format!("rtsyn_csv_rotate_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let path = dir.join("run.csv");
+    let mut plugin = CsvRecorderedPlugin::new(1);
+    let mut ctx = PluginContext::default();
+    // Three rows of 0.1 s per file
+    plugin.set_rotation(0.0, 0.3 / 60.0);
+    plugin.set_config(
+        1,
+        ",".to_string(),
+        vec!["a".to_string()],
+        Some(path.clone()),
+        true,
+        false,
+        1.0,
+        "time".to_string(),
+        0.1,
+    );
+    assert_eq!(plugin.current_path(), Some(dir.join("run.000.csv")));
+    for value in 0..7 {
+        plugin.set_inputs(vec![f64::from(value)]);
+        plugin.process(&mut ctx).unwrap();
+    }
+    plugin.flush().unwrap();
+    let read = |name: &str| std::fs::read_to_string(dir.join(name)).unwrap_or_default();
+    assert_eq!(read("run.000.csv"), "a\n0\n1\n2\n");
+    assert_eq!(read("run.001.csv"), "a\n3\n4\n5\n");
+    assert_eq!(read("run.002.csv"), "a\n6\n");
+    assert!(!path.exists());
+
+    // A new recording continues after the existing files
+    plugin.set_config(
+        1,
+        ",".to_string(),
+        vec!["a".to_string()],
+        Some(path.clone()),
+        false,
+        false,
+        1.0,
+        "time".to_string(),
+        0.1,
+    );
+    plugin.set_config(
+        1,
+        ",".to_string(),
+        vec!["a".to_string()],
+        Some(path.clone()),
+        true,
+        false,
+        1.0,
+        "time".to_string(),
+        0.1,
+    );
+    assert_eq!(plugin.current_path(), Some(dir.join("run.003.csv")));
+
+    // Size limits rotate too
+    plugin.set_rotation(0.000_005, 0.0);
+    plugin.set_inputs(vec![10.0]);
+    plugin.process(&mut ctx).unwrap();
+    assert_eq!(plugin.current_path(), Some(dir.join("run.004.csv")));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
 #[test]
 fn live_plotter_has_ui_schema() {
     let plugin = LivePlotterPlugin::new(1);
-    let schema = plugin.ui_schema().expect("Live plotter should have UI schema");
+    let schema = plugin
+        .ui_schema()
+        .expect("Live plotter should have UI schema");
 
-    assert_eq!(schema.fields.len(), 4);
+    assert_eq!(schema.fields.len(), 5);
 
     // Check refresh_hz field
     assert_eq!(schema.fields[0].key, "refresh_hz");
@@ -99,6 +288,9 @@ fn live_plotter_has_ui_schema() {
 
     // Check amplitude field
     assert_eq!(schema.fields[3].key, "amplitude");
+
+    // Check view_mode field
+    assert_eq!(schema.fields[4].key, "view_mode");
 }
 
 #[test]
@@ -153,7 +345,7 @@ fn ui_schema_json_serialization() {
 
     // Deserialize back
     let deserialized: UISchema = serde_json::from_str(&json).expect("Should deserialize");
-    assert_eq!(deserialized.fields.len(), 4);
+    assert_eq!(deserialized.fields.len(), 11);
 }
 
 #[test]
@@ -0,0 +1,180 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+/// Upper bound on buffered samples so a huge delay cannot exhaust memory.
+const MAX_DELAY_TICKS: usize = 10_000_000;
+
+pub struct DelayPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    delay_ms: f64,
+    initial_value: f64,
+    input: f64,
+    /// Ring of the last `delay_ticks` inputs, allocated when the delay
+    /// changes so `process()` never allocates.
+    buffer: Vec<f64>,
+    head: usize,
+    filled: usize,
+    out: f64,
+}
+
+impl DelayPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Delay".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("delay_ms".to_string(), Value::from(100.0)),
+                    ("initial_value".to_string(), Value::from(0.0)),
+                ],
+            },
+            inputs: vec![Port { id: PortId("in".to_string()) }],
+            outputs: vec![Port { id: PortId("out".to_string()) }],
+            delay_ms: 100.0,
+            initial_value: 0.0,
+            input: 0.0,
+            buffer: Vec::new(),
+            head: 0,
+            filled: 0,
+            out: 0.0,
+        }
+    }
+
+    /// Sizes the line for the runtime period; a different length empties
+    /// it and reallocates the ring.
+    pub fn set_config(&mut self, delay_ms: f64, initial_value: f64, period_seconds: f64) {
+        self.delay_ms = delay_ms.max(0.0);
+        self.initial_value = initial_value;
+        let ticks = self.delay_ticks(period_seconds);
+        if ticks != self.buffer.len() {
+            self.buffer = vec![0.0; ticks];
+            self.reset();
+        } else if self.filled == 0 {
+            self.out = initial_value;
+        }
+    }
+
+    pub fn set_input(&mut self, value: f64) {
+        self.input = value;
+    }
+
+    /// Empties the line; the output is `initial_value` until it fills again.
+    pub fn reset(&mut self) {
+        self.head = 0;
+        self.filled = 0;
+        self.out = self.initial_value;
+    }
+
+    pub fn out(&self) -> f64 {
+        self.out
+    }
+
+    /// Delay expressed in ticks of the given period.
+    pub fn delay_ticks(&self, period_seconds: f64) -> usize {
+        if period_seconds <= 0.0 {
+            return 0;
+        }
+        let ticks = (self.delay_ms / 1000.0 / period_seconds).round();
+        (ticks as usize).min(MAX_DELAY_TICKS)
+    }
+}
+
+impl Plugin for DelayPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        let ticks = self.buffer.len();
+        if ticks == 0 {
+            self.out = self.input;
+            return Ok(());
+        }
+        if self.filled == ticks {
+            self.out = self.buffer[self.head];
+        } else {
+            self.filled += 1;
+        }
+        self.buffer[self.head] = self.input;
+        self.head = (self.head + 1) % ticks;
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::float("delay_ms", "Delay (ms)")
+                        .min_f(0.0)
+                        .step_f(1.0)
+                        .default_value(Value::from(100.0))
+                        .hint("Rounded to a whole number of ticks"),
+                )
+                .field(
+                    ConfigField::float("initial_value", "Initial value")
+                        .step_f(0.1)
+                        .default_value(Value::from(0.0))
+                        .hint("Output until the delay line has filled"),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["out".to_string()],
+            inputs: vec!["in".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "delay_ms" => Some(Value::from(self.delay_ms)),
+            "initial_value" => Some(Value::from(self.initial_value)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        let Some(v) = value.as_f64() else {
+            return Ok(());
+        };
+        // The ring is resized by the next `set_config` with the period
+        match name {
+            "delay_ms" => self.delay_ms = v.max(0.0),
+            "initial_value" => {
+                self.initial_value = v;
+                if self.filled == 0 {
+                    self.out = v;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use delay_plugin::DelayPlugin;
+use rtsyn_plugin::prelude::*;
+
+fn step(plugin: &mut DelayPlugin, period_seconds: f64, input: f64) -> f64 {
+    let mut ctx = PluginContext {
+        period_seconds,
+        ..PluginContext::default()
+    };
+    plugin.set_input(input);
+    plugin.process(&mut ctx).unwrap();
+    plugin.out()
+}
+
+#[test]
+fn delay_outputs_initial_value_until_warmed_up() {
+    let mut plugin = DelayPlugin::new(1);
+    plugin.set_config(3.0, -1.0, 0.001);
+    assert_eq!(plugin.delay_ticks(0.001), 3);
+
+    let outputs: Vec<f64> = (1..=6).map(|i| step(&mut plugin, 0.001, i as f64)).collect();
+    assert_eq!(outputs, vec![-1.0, -1.0, -1.0, 1.0, 2.0, 3.0]);
+
+    plugin.reset();
+    assert_eq!(step(&mut plugin, 0.001, 10.0), -1.0);
+}
+
+#[test]
+fn zero_delay_passes_through() {
+    let mut plugin = DelayPlugin::new(1);
+    plugin.set_config(0.0, 0.0, 0.001);
+    assert_eq!(step(&mut plugin, 0.001, 4.0), 4.0);
+}
+
+#[test]
+fn shorter_period_lengthens_the_line() {
+    let mut plugin = DelayPlugin::new(1);
+    plugin.set_config(10.0, 0.0, 0.01);
+    assert_eq!(plugin.delay_ticks(0.01), 1);
+    assert_eq!(plugin.delay_ticks(0.001), 10);
+    assert_eq!(step(&mut plugin, 0.01, 1.0), 0.0);
+    assert_eq!(step(&mut plugin, 0.01, 2.0), 1.0);
+
+    // A new length empties the line
+    plugin.set_config(10.0, 0.0, 0.005);
+    assert_eq!(plugin.delay_ticks(0.005), 2);
+    let outputs: Vec<f64> = (3..=5).map(|i| step(&mut plugin, 0.005, i as f64)).collect();
+    assert_eq!(outputs, vec![0.0, 0.0, 3.0]);
+}
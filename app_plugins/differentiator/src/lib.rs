@@ -0,0 +1,130 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+pub struct DifferentiatorPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    gain: f64,
+    input: f64,
+    previous: Option<f64>,
+    out: f64,
+}
+
+impl DifferentiatorPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Differentiator".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("gain".to_string(), Value::from(1.0)),
+                    ("warmup_ticks".to_string(), Value::from(0)),
+                ],
+            },
+            inputs: vec![Port { id: PortId("in".to_string()) }],
+            outputs: vec![Port { id: PortId("out".to_string()) }],
+            gain: 1.0,
+            input: 0.0,
+            previous: None,
+            out: 0.0,
+        }
+    }
+
+    pub fn set_config(&mut self, gain: f64) {
+        self.gain = gain;
+    }
+
+    pub fn set_input(&mut self, value: f64) {
+        self.input = value;
+    }
+
+    /// Forgets the previous sample so the first tick after a restart does not
+    /// produce a spike.
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.out = 0.0;
+    }
+
+    pub fn out(&self) -> f64 {
+        self.out
+    }
+}
+
+impl Plugin for DifferentiatorPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
+        let time_step = ctx.period_seconds;
+        self.out = match self.previous {
+            Some(previous) if time_step > 0.0 => (self.input - previous) / time_step * self.gain,
+            _ => 0.0,
+        };
+        self.previous = Some(self.input);
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::float("gain", "Gain")
+                        .step_f(0.1)
+                        .default_value(Value::from(1.0)),
+                )
+                .field(
+                    ConfigField::integer("warmup_ticks", "Warmup (ticks)")
+                        .min(0)
+                        .default_value(Value::from(0))
+                        .hint("Outputs are held at 0 for this many ticks after start"),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["out".to_string()],
+            inputs: vec!["in".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "gain" => Some(Value::from(self.gain)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        if let ("gain", Some(v)) = (name, value.as_f64()) {
+            self.gain = v;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use differentiator_plugin::DifferentiatorPlugin;
+use rtsyn_plugin::prelude::*;
+
+fn step(plugin: &mut DifferentiatorPlugin, period_seconds: f64, input: f64) -> f64 {
+    let mut ctx = PluginContext {
+        period_seconds,
+        ..PluginContext::default()
+    };
+    plugin.set_input(input);
+    plugin.process(&mut ctx).unwrap();
+    plugin.out()
+}
+
+#[test]
+fn differentiator_uses_current_time_step() {
+    let mut plugin = DifferentiatorPlugin::new(1);
+    plugin.set_config(2.0);
+
+    // First sample has no history.
+    assert_eq!(step(&mut plugin, 0.1, 5.0), 0.0);
+    assert!((step(&mut plugin, 0.1, 6.0) - 20.0).abs() < 1e-9);
+    assert!((step(&mut plugin, 0.5, 7.0) - 4.0).abs() < 1e-9);
+}
+
+#[test]
+fn differentiator_reset_avoids_spike() {
+    let mut plugin = DifferentiatorPlugin::new(1);
+    step(&mut plugin, 0.01, 0.0);
+    plugin.reset();
+    assert_eq!(step(&mut plugin, 0.01, 100.0), 0.0);
+}
@@ -0,0 +1,246 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+/// Upper bound on the RMSE window so a huge `window_ms` cannot exhaust memory.
+const MAX_WINDOW_TICKS: usize = 1_000_000;
+
+/// Running sum with Neumaier compensation. Over hours at kHz rates the
+/// integrals add millions of tiny terms to a large total, and a plain sum
+/// would drop their low bits.
+#[derive(Debug, Default, Clone, Copy)]
+struct CompensatedSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl CompensatedSum {
+    fn add(&mut self, value: f64) {
+        let total = self.sum + value;
+        if self.sum.abs() >= value.abs() {
+            self.compensation += (self.sum - total) + value;
+        } else {
+            self.compensation += (value - total) + self.sum;
+        }
+        self.sum = total;
+    }
+
+    fn value(&self) -> f64 {
+        self.sum + self.compensation
+    }
+}
+
+/// Control-performance indices of `error = reference - actual`: the
+/// integrals of its absolute value (IAE) and square (ISE) since the plugin
+/// started, and its RMS over a sliding window.
+pub struct ErrorMetricsPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    window_ms: f64,
+    reference: f64,
+    actual: f64,
+    iae: CompensatedSum,
+    ise: CompensatedSum,
+    /// Ring of the last squared errors, allocated when the window changes so
+    /// `process()` never allocates.
+    squares: Vec<f64>,
+    head: usize,
+    filled: usize,
+    sum_squares: f64,
+    error: f64,
+    rmse: f64,
+}
+
+impl ErrorMetricsPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Error Metrics".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![("window_ms".to_string(), Value::from(1000.0))],
+            },
+            inputs: vec![
+                Port { id: PortId("reference".to_string()) },
+                Port { id: PortId("actual".to_string()) },
+            ],
+            outputs: vec![
+                Port { id: PortId("error".to_string()) },
+                Port { id: PortId("abs_error".to_string()) },
+                Port { id: PortId("iae".to_string()) },
+                Port { id: PortId("ise".to_string()) },
+                Port { id: PortId("rmse".to_string()) },
+            ],
+            window_ms: 1000.0,
+            reference: 0.0,
+            actual: 0.0,
+            iae: CompensatedSum::default(),
+            ise: CompensatedSum::default(),
+            squares: Vec::new(),
+            head: 0,
+            filled: 0,
+            sum_squares: 0.0,
+            error: 0.0,
+            rmse: 0.0,
+        }
+    }
+
+    /// Sizes the RMSE window for the runtime period; a different length
+    /// clears the window, the integrals carry on.
+    pub fn set_config(&mut self, window_ms: f64, period_seconds: f64) {
+        self.window_ms = window_ms.max(0.0);
+        let ticks = Self::window_ticks(self.window_ms, period_seconds);
+        if ticks != self.squares.len() {
+            self.squares = vec![0.0; ticks];
+            self.head = 0;
+            self.filled = 0;
+            self.sum_squares = 0.0;
+            self.rmse = 0.0;
+        }
+    }
+
+    /// Window length in ticks, at least one sample.
+    pub fn window_ticks(window_ms: f64, period_seconds: f64) -> usize {
+        if period_seconds <= 0.0 {
+            return 1;
+        }
+        let ticks = (window_ms / 1000.0 / period_seconds).round();
+        (ticks as usize).clamp(1, MAX_WINDOW_TICKS)
+    }
+
+    pub fn set_inputs(&mut self, reference: f64, actual: f64) {
+        self.reference = reference;
+        self.actual = actual;
+    }
+
+    pub fn reset(&mut self) {
+        self.iae = CompensatedSum::default();
+        self.ise = CompensatedSum::default();
+        self.squares.iter_mut().for_each(|square| *square = 0.0);
+        self.head = 0;
+        self.filled = 0;
+        self.sum_squares = 0.0;
+        self.error = 0.0;
+        self.rmse = 0.0;
+    }
+
+    pub fn error(&self) -> f64 {
+        self.error
+    }
+
+    pub fn abs_error(&self) -> f64 {
+        self.error.abs()
+    }
+
+    pub fn iae(&self) -> f64 {
+        self.iae.value()
+    }
+
+    pub fn ise(&self) -> f64 {
+        self.ise.value()
+    }
+
+    pub fn rmse(&self) -> f64 {
+        self.rmse
+    }
+}
+
+impl Plugin for ErrorMetricsPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
+        let time_step = ctx.period_seconds.max(0.0);
+        let error = self.reference - self.actual;
+        // A non-finite sample would poison the integrals for good
+        self.error = if error.is_finite() { error } else { 0.0 };
+        let square = self.error * self.error;
+        self.iae.add(self.error.abs() * time_step);
+        self.ise.add(square * time_step);
+
+        let capacity = self.squares.len();
+        if capacity == 0 {
+            return Ok(());
+        }
+        if self.filled == capacity {
+            self.sum_squares -= self.squares[self.head];
+        } else {
+            self.filled += 1;
+        }
+        self.squares[self.head] = square;
+        self.sum_squares += square;
+        self.head = (self.head + 1) % capacity;
+        if self.head == 0 {
+            // Recompute once per lap so rounding in the running sum cannot
+            // accumulate.
+            self.sum_squares = self.squares[..self.filled].iter().sum();
+        }
+        self.rmse = (self.sum_squares.max(0.0) / self.filled as f64).sqrt();
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new().field(
+                ConfigField::float("window_ms", "RMSE window (ms)")
+                    .min_f(0.0)
+                    .step_f(10.0)
+                    .default_value(Value::from(1000.0))
+                    .hint("Rounded to a whole number of ticks, at least one"),
+            ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec![
+                "error".to_string(),
+                "abs_error".to_string(),
+                "iae".to_string(),
+                "ise".to_string(),
+                "rmse".to_string(),
+            ],
+            inputs: vec!["reference".to_string(), "actual".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "window_ms" => Some(Value::from(self.window_ms)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        if name == "window_ms" {
+            if let Some(v) = value.as_f64() {
+                self.window_ms = v.max(0.0);
+            }
+        }
+        Ok(())
+    }
+}
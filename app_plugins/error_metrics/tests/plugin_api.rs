@@ -0,0 +1,50 @@
+use error_metrics_plugin::ErrorMetricsPlugin;
+use rtsyn_plugin::prelude::*;
+
+fn feed(plugin: &mut ErrorMetricsPlugin, period_seconds: f64, samples: impl IntoIterator<Item = (f64, f64)>) {
+    let mut ctx = PluginContext {
+        period_seconds,
+        ..PluginContext::default()
+    };
+    for (reference, actual) in samples {
+        plugin.set_inputs(reference, actual);
+        plugin.process(&mut ctx).unwrap();
+    }
+}
+
+#[test]
+fn error_integrals_and_windowed_rmse() {
+    let mut plugin = ErrorMetricsPlugin::new(1);
+    plugin.set_config(3.0, 0.001);
+
+    feed(&mut plugin, 0.001, [(1.0, 3.0)]);
+    assert_eq!(plugin.error(), -2.0);
+    assert_eq!(plugin.abs_error(), 2.0);
+    assert!((plugin.iae() - 0.002).abs() < 1e-15);
+    assert!((plugin.ise() - 0.004).abs() < 1e-15);
+    assert_eq!(plugin.rmse(), 2.0);
+
+    // The window keeps the last three errors: 1, 1, 1
+    feed(&mut plugin, 0.001, [(1.0, 0.0), (2.0, 1.0), (0.0, -1.0)]);
+    assert!((plugin.iae() - 0.005).abs() < 1e-15);
+    assert!((plugin.ise() - 0.007).abs() < 1e-15);
+    assert!((plugin.rmse() - 1.0).abs() < 1e-12);
+
+    // A non-finite sample counts as no error
+    feed(&mut plugin, 0.001, [(f64::NAN, 0.0)]);
+    assert_eq!(plugin.error(), 0.0);
+    assert!((plugin.iae() - 0.005).abs() < 1e-15);
+
+    plugin.reset();
+    assert_eq!((plugin.iae(), plugin.ise(), plugin.rmse()), (0.0, 0.0, 0.0));
+}
+
+#[test]
+fn integrals_do_not_drift_over_long_runs() {
+    let mut plugin = ErrorMetricsPlugin::new(1);
+    plugin.set_config(1.0, 1e-4);
+    // 1000 s at 10 kHz with a constant unit error
+    feed(&mut plugin, 1e-4, std::iter::repeat_n((1.0, 0.0), 10_000_000));
+    assert!((plugin.iae() - 1000.0).abs() < 1e-9, "{}", plugin.iae());
+    assert!((plugin.ise() - 1000.0).abs() < 1e-9, "{}", plugin.ise());
+}
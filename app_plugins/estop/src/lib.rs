@@ -0,0 +1,137 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+/// Emergency stop. The runtime reads [`EstopPlugin::is_active`] after each
+/// tick and, while any estop is active, puts the device drivers in their
+/// safe state and stops non-essential plugins.
+pub struct EstopPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    latching: bool,
+    trigger: bool,
+    reset: bool,
+    active: bool,
+}
+
+impl EstopPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Emergency Stop".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![("latching".to_string(), Value::from(true))],
+            },
+            inputs: vec![
+                Port { id: PortId("trigger".to_string()) },
+                Port { id: PortId("reset".to_string()) },
+            ],
+            outputs: vec![Port { id: PortId("active".to_string()) }],
+            latching: true,
+            trigger: false,
+            reset: false,
+            active: false,
+        }
+    }
+
+    /// A latching estop stays active after the trigger drops until `reset`
+    /// is asserted with the trigger low; otherwise it follows the trigger.
+    pub fn set_config(&mut self, latching: bool) {
+        self.latching = latching;
+    }
+
+    pub fn set_inputs(&mut self, trigger: bool, reset: bool) {
+        self.trigger = trigger;
+        self.reset = reset;
+    }
+
+    pub fn reset(&mut self) {
+        self.active = false;
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn active(&self) -> f64 {
+        if self.active {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Plugin for EstopPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        self.active = if self.trigger {
+            true
+        } else if self.latching {
+            self.active && !self.reset
+        } else {
+            false
+        };
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new().field(
+                ConfigField::boolean("latching", "Latching")
+                    .default_value(Value::from(true))
+                    .hint("Stay active after the trigger drops until reset"),
+            ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["active".to_string()],
+            inputs: vec!["trigger".to_string(), "reset".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "latching" => Some(Value::from(self.latching)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        if name == "latching" {
+            if let Some(v) = value.as_bool() {
+                self.set_config(v);
+            }
+        }
+        Ok(())
+    }
+}
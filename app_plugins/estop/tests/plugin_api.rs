@@ -0,0 +1,40 @@
+use estop_plugin::EstopPlugin;
+use rtsyn_plugin::prelude::*;
+
+/// Runs one tick per (trigger, reset) pair and returns `active`.
+fn run(plugin: &mut EstopPlugin, inputs: &[(bool, bool)]) -> Vec<f64> {
+    let mut ctx = PluginContext::default();
+    inputs
+        .iter()
+        .map(|&(trigger, reset)| {
+            plugin.set_inputs(trigger, reset);
+            plugin.process(&mut ctx).unwrap();
+            plugin.active()
+        })
+        .collect()
+}
+
+#[test]
+fn latching_holds_until_reset_with_the_trigger_low() {
+    let mut plugin = EstopPlugin::new(1);
+    assert_eq!(
+        run(
+            &mut plugin,
+            &[(false, false), (true, false), (false, false), (true, true), (false, true), (false, false)]
+        ),
+        vec![0.0, 1.0, 1.0, 1.0, 0.0, 0.0]
+    );
+}
+
+#[test]
+fn non_latching_follows_the_trigger() {
+    let mut plugin = EstopPlugin::new(1);
+    plugin.set_config(false);
+    assert_eq!(
+        run(&mut plugin, &[(true, false), (false, false), (true, false)]),
+        vec![1.0, 0.0, 1.0]
+    );
+    assert!(plugin.is_active());
+    plugin.reset();
+    assert!(!plugin.is_active());
+}
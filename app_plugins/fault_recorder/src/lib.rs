@@ -0,0 +1,423 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+use std::fs;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::thread::JoinHandle;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Trigger level; a capture starts when `trigger` rises above it.
+const TRIGGER_THRESHOLD: f64 = 0.5;
+
+/// The last `capacity` input rows, oldest first, in one flat allocation.
+struct History {
+    width: usize,
+    capacity: usize,
+    values: Vec<f64>,
+    start: usize,
+    len: usize,
+}
+
+impl History {
+    fn new(width: usize, capacity: usize) -> Self {
+        Self {
+            width,
+            capacity,
+            values: vec![0.0; width * capacity],
+            start: 0,
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, inputs: &[f64], trigger: f64) {
+        if self.capacity == 0 {
+            return;
+        }
+        let slot = if self.len < self.capacity {
+            self.len += 1;
+            (self.start + self.len - 1) % self.capacity
+        } else {
+            let slot = self.start;
+            self.start = (self.start + 1) % self.capacity;
+            slot
+        };
+        let row = &mut self.values[slot * self.width..(slot + 1) * self.width];
+        row[..inputs.len()].copy_from_slice(inputs);
+        row[inputs.len()] = trigger;
+    }
+
+    fn copy_to(&self, out: &mut Vec<f64>) {
+        for idx in 0..self.len {
+            let slot = (self.start + idx) % self.capacity;
+            out.extend_from_slice(&self.values[slot * self.width..(slot + 1) * self.width]);
+        }
+    }
+}
+
+/// Rows gathered since a trigger, pre-trigger window first.
+struct Capture {
+    path: PathBuf,
+    rows: Vec<f64>,
+    pre_rows: usize,
+    remaining: usize,
+}
+
+/// Keeps a rolling pre-trigger window of its inputs and, when `trigger`
+/// rises, writes that window plus a post-trigger window to a new timestamped
+/// CSV file, then re-arms.
+pub struct FaultRecorderPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    input_values: Vec<f64>,
+    trigger: f64,
+    trigger_high: bool,
+    path: Option<PathBuf>,
+    pre_seconds: f64,
+    post_seconds: f64,
+    period_seconds: f64,
+    post_rows: usize,
+    history: History,
+    capture: Option<Capture>,
+    /// Files still being written, off the tick thread.
+    writers: Vec<JoinHandle<Result<PathBuf, String>>>,
+    captures: u64,
+    last_capture: Option<PathBuf>,
+    error: Option<String>,
+}
+
+impl FaultRecorderPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Fault Recorder".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("input_count".to_string(), Value::from(0)),
+                    ("path".to_string(), Value::from("")),
+                    ("pre_trigger_s".to_string(), Value::from(1.0)),
+                    ("post_trigger_s".to_string(), Value::from(1.0)),
+                ],
+            },
+            inputs: vec![Port { id: PortId("trigger".to_string()) }],
+            input_values: Vec::new(),
+            trigger: 0.0,
+            trigger_high: false,
+            path: None,
+            pre_seconds: 0.0,
+            post_seconds: 0.0,
+            period_seconds: 0.0,
+            post_rows: 0,
+            history: History::new(1, 0),
+            capture: None,
+            writers: Vec::new(),
+            captures: 0,
+            last_capture: None,
+            error: None,
+        }
+    }
+
+    /// Windows are rounded up to whole ticks of `period_seconds`. Changing
+    /// the inputs or the windows drops the history and any capture in
+    /// progress; a new `path` applies from the next capture.
+    pub fn set_config(
+        &mut self,
+        input_count: usize,
+        path: Option<PathBuf>,
+        pre_seconds: f64,
+        post_seconds: f64,
+        period_seconds: f64,
+    ) {
+        self.path = path;
+        let pre_seconds = pre_seconds.max(0.0);
+        let post_seconds = post_seconds.max(0.0);
+        if self.input_values.len() == input_count
+            && self.pre_seconds == pre_seconds
+            && self.post_seconds == post_seconds
+            && self.period_seconds == period_seconds
+        {
+            return;
+        }
+        self.pre_seconds = pre_seconds;
+        self.post_seconds = post_seconds;
+        self.period_seconds = period_seconds;
+        let rows = |seconds: f64| {
+            if period_seconds > 0.0 {
+                (seconds / period_seconds - 1e-9).ceil().max(0.0) as usize
+            } else {
+                0
+            }
+        };
+        // The trigger tick itself is the first post-trigger row
+        self.post_rows = rows(post_seconds).max(1);
+        if self.input_values.len() != input_count {
+            self.inputs = (0..input_count)
+                .map(|idx| Port {
+                    id: PortId(format!("in_{idx}")),
+                })
+                .chain(std::iter::once(Port {
+                    id: PortId("trigger".to_string()),
+                }))
+                .collect();
+            self.input_values.resize(input_count, 0.0);
+        }
+        self.history = History::new(input_count + 1, rows(pre_seconds));
+        self.capture = None;
+    }
+
+    pub fn set_inputs(&mut self, values: Vec<f64>, trigger: f64) {
+        self.input_values = values;
+        self.trigger = trigger;
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        self.capture.is_some()
+    }
+
+    /// Captures written so far.
+    pub fn captures(&self) -> u64 {
+        self.captures
+    }
+
+    pub fn last_capture(&self) -> Option<&Path> {
+        self.last_capture.as_deref()
+    }
+
+    pub fn last_error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    fn header(&self) -> String {
+        let mut columns = vec!["time_s".to_string()];
+        columns.extend((0..self.input_values.len()).map(|idx| format!("in_{idx}")));
+        columns.push("trigger".to_string());
+        columns.join(",")
+    }
+
+    fn start_capture(&mut self) {
+        let Some(base) = self.path.as_ref() else {
+            self.error = Some("No output file set".to_string());
+            return;
+        };
+        let mut rows = Vec::with_capacity((self.history.len + self.post_rows) * self.history.width);
+        self.history.copy_to(&mut rows);
+        self.capture = Some(Capture {
+            path: capture_path(base, SystemTime::now()),
+            rows,
+            pre_rows: self.history.len,
+            remaining: self.post_rows,
+        });
+    }
+
+    fn finish_capture(&mut self, capture: Capture) {
+        let header = self.header();
+        let width = self.history.width;
+        let period = self.period_seconds;
+        self.writers.push(std::thread::spawn(move || {
+            write_capture(&capture.path, &header, &capture.rows, width, capture.pre_rows, period)
+                .map(|()| capture.path)
+        }));
+    }
+
+    /// Collects the results of finished writers; `wait` blocks until all are
+    /// done.
+    fn collect_writers(&mut self, wait: bool) {
+        let mut idx = 0;
+        while idx < self.writers.len() {
+            if !wait && !self.writers[idx].is_finished() {
+                idx += 1;
+                continue;
+            }
+            let result = self
+                .writers
+                .swap_remove(idx)
+                .join()
+                .unwrap_or_else(|_| Err("capture writer panicked".to_string()));
+            match result {
+                Ok(path) => {
+                    self.captures += 1;
+                    self.last_capture = Some(path);
+                    self.error = None;
+                }
+                Err(err) => self.error = Some(err),
+            }
+        }
+    }
+}
+
+impl Plugin for FaultRecorderPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &[]
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        self.collect_writers(false);
+        if self.input_values.len() + 1 != self.history.width {
+            return Ok(());
+        }
+        let high = self.trigger > TRIGGER_THRESHOLD;
+        let rising = high && !self.trigger_high;
+        self.trigger_high = high;
+        if rising && self.capture.is_none() {
+            self.start_capture();
+        }
+
+        if let Some(capture) = self.capture.as_mut() {
+            capture.rows.extend_from_slice(&self.input_values);
+            capture.rows.push(self.trigger);
+            capture.remaining -= 1;
+            if capture.remaining == 0 {
+                if let Some(capture) = self.capture.take() {
+                    self.finish_capture(capture);
+                }
+            }
+        }
+        self.history.push(&self.input_values, self.trigger);
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::filepath("path", "Output file")
+                        .mode(FileMode::SaveFile)
+                        .filter("CSV files", "*.csv")
+                        .filter("All files", "*")
+                        .hint("Each capture is written next to it as <name>-<timestamp>.csv"),
+                )
+                .field(
+                    ConfigField::float("pre_trigger_s", "Before trigger (s)")
+                        .min_f(0.0)
+                        .step_f(0.1)
+                        .default_value(Value::from(1.0))
+                        .hint("History kept and written ahead of each trigger"),
+                )
+                .field(
+                    ConfigField::float("post_trigger_s", "After trigger (s)")
+                        .min_f(0.0)
+                        .step_f(0.1)
+                        .default_value(Value::from(1.0))
+                        .hint("Recorded from the trigger on before re-arming"),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::Auto {
+                pattern: "in_{}".to_string(),
+            },
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec![],
+            inputs: self.inputs.iter().map(|p| p.id.0.clone()).collect(),
+            variables: vec![
+                "state".to_string(),
+                "captures".to_string(),
+                "last_capture".to_string(),
+                "error".to_string(),
+            ],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "state" => Some(Value::from(if self.is_capturing() { "capturing" } else { "armed" })),
+            "captures" => Some(Value::from(self.captures)),
+            "last_capture" => Some(Value::from(
+                self.last_capture
+                    .as_ref()
+                    .map(|path| path.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+            )),
+            "error" => Some(Value::from(self.error.clone().unwrap_or_default())),
+            "pre_trigger_s" => Some(Value::from(self.pre_seconds)),
+            "post_trigger_s" => Some(Value::from(self.post_seconds)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, _name: &str, _value: Value) -> Result<(), PluginError> {
+        Ok(())
+    }
+}
+
+impl EventLogger for FaultRecorderPlugin {
+    /// Waits for every finished capture to be on disk.
+    fn flush(&mut self) -> Result<(), PluginError> {
+        self.collect_writers(true);
+        Ok(())
+    }
+}
+
+/// `faults/run.csv` triggered at 10:20:30.456 becomes
+/// `faults/run-<day>-10-20-30-456.csv`, `<day>` counting from the Unix epoch.
+fn capture_path(base: &Path, now: SystemTime) -> PathBuf {
+    let now = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = now.as_secs();
+    let stamp = format!(
+        "{}-{:02}-{:02}-{:02}-{:03}",
+        secs / 86_400,
+        (secs % 86_400) / 3_600,
+        (secs % 3_600) / 60,
+        secs % 60,
+        now.subsec_millis()
+    );
+    let stem = base
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "fault".to_string());
+    let name = match base.extension() {
+        Some(ext) => format!("{stem}-{stamp}.{}", ext.to_string_lossy()),
+        None => format!("{stem}-{stamp}.csv"),
+    };
+    base.with_file_name(name)
+}
+
+/// Times are seconds relative to the trigger row, so pre-trigger rows are
+/// negative.
+fn write_capture(
+    path: &Path,
+    header: &str,
+    rows: &[f64],
+    width: usize,
+    pre_rows: usize,
+    period_seconds: f64,
+) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let file = fs::File::create(path).map_err(|err| format!("Cannot create {}: {err}", path.display()))?;
+    let mut out = BufWriter::new(file);
+    let write_err = |err: std::io::Error| format!("Cannot write {}: {err}", path.display());
+    writeln!(out, "{header}").map_err(write_err)?;
+    for (idx, row) in rows.chunks(width.max(1)).enumerate() {
+        let time = (idx as f64 - pre_rows as f64) * period_seconds;
+        write!(out, "{time}").map_err(write_err)?;
+        for value in row {
+            write!(out, ",{value}").map_err(write_err)?;
+        }
+        writeln!(out).map_err(write_err)?;
+    }
+    out.flush().map_err(write_err)
+}
@@ -0,0 +1,77 @@
+use fault_recorder_plugin::FaultRecorderPlugin;
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+use std::path::Path;
+
+fn feed(plugin: &mut FaultRecorderPlugin, values: &[f64], trigger: &[f64]) {
+    let mut ctx = PluginContext::default();
+    for (value, trigger) in values.iter().zip(trigger) {
+        plugin.set_inputs(vec![*value], *trigger);
+        plugin.process(&mut ctx).unwrap();
+    }
+}
+
+fn read(path: Option<&Path>) -> String {
+    std::fs::read_to_string(path.expect("a capture was written")).unwrap()
+}
+
+#[test]
+fn captures_the_windows_around_each_trigger() {
+    let dir = std::env::temp_dir().join(format!("rtsyn_fault_recorder_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let mut plugin = FaultRecorderPlugin::new(1);
+    // Two rows before the trigger, the trigger row and one after
+    plugin.set_config(1, Some(dir.join("run.csv")), 0.2, 0.2, 0.1);
+    let ports: Vec<&str> = plugin.inputs().iter().map(|p| p.id.0.as_str()).collect();
+    assert_eq!(ports, ["in_0", "trigger"]);
+
+    feed(&mut plugin, &[1.0, 2.0, 3.0, 4.0], &[0.0, 0.0, 0.0, 1.0]);
+    assert!(plugin.is_capturing());
+    assert_eq!(plugin.get_variable("state"), Some(Value::from("capturing")));
+    // Still high: not a new trigger
+    feed(&mut plugin, &[5.0], &[1.0]);
+    assert!(!plugin.is_capturing());
+    plugin.flush().unwrap();
+    assert_eq!(plugin.captures(), 1);
+    let first = plugin.last_capture().map(Path::to_path_buf);
+    let name = first.as_ref().unwrap().file_name().unwrap().to_string_lossy().to_string();
+    assert!(name.starts_with("run-") && name.ends_with(".csv"), "{name}");
+    assert_eq!(
+        read(first.as_deref()),
+        "time_s,in_0,trigger\n-0.2,2,0\n-0.1,3,0\n0,4,1\n0.1,5,1\n"
+    );
+
+    // Re-armed: the next rising edge captures again, with the history kept
+    // through the previous capture
+    std::thread::sleep(std::time::Duration::from_millis(2));
+    feed(&mut plugin, &[6.0, 7.0, 8.0], &[0.0, 1.0, 0.0]);
+    plugin.flush().unwrap();
+    assert_eq!(plugin.captures(), 2);
+    assert_ne!(plugin.last_capture(), first.as_deref());
+    assert_eq!(
+        read(plugin.last_capture()),
+        "time_s,in_0,trigger\n-0.2,5,1\n-0.1,6,0\n0,7,1\n0.1,8,0\n"
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn changing_the_window_drops_the_capture() {
+    let mut plugin = FaultRecorderPlugin::new(1);
+    plugin.set_config(1, Some("unused.csv".into()), 1.0, 1.0, 0.1);
+    feed(&mut plugin, &[1.0, 2.0], &[0.0, 1.0]);
+    assert!(plugin.is_capturing());
+
+    // Same settings keep it going
+    plugin.set_config(1, Some("other.csv".into()), 1.0, 1.0, 0.1);
+    assert!(plugin.is_capturing());
+    plugin.set_config(1, Some("other.csv".into()), 0.5, 1.0, 0.1);
+    assert!(!plugin.is_capturing());
+    assert_eq!(plugin.captures(), 0);
+
+    let mut plugin = FaultRecorderPlugin::new(1);
+    plugin.set_config(1, None, 1.0, 1.0, 0.1);
+    feed(&mut plugin, &[1.0], &[1.0]);
+    assert!(!plugin.is_capturing());
+    assert_eq!(plugin.last_error(), Some("No output file set"));
+}
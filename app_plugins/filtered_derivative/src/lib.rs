@@ -0,0 +1,152 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+/// Derivative with a first-order low-pass filter, `gain * s / (tau * s + 1)`,
+/// discretized with backward Euler so it stays stable for any time step.
+/// Unlike the raw differentiator, noise above `1 / tau` rad/s is attenuated
+/// instead of amplified; `tau = 0` gives the raw derivative.
+pub struct FilteredDerivativePlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    tau: f64,
+    gain: f64,
+    input: f64,
+    previous: Option<f64>,
+    out: f64,
+}
+
+impl FilteredDerivativePlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Filtered Derivative".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("tau".to_string(), Value::from(0.01)),
+                    ("gain".to_string(), Value::from(1.0)),
+                    ("warmup_ticks".to_string(), Value::from(0)),
+                ],
+            },
+            inputs: vec![Port { id: PortId("in".to_string()) }],
+            outputs: vec![Port { id: PortId("out".to_string()) }],
+            tau: 0.01,
+            gain: 1.0,
+            input: 0.0,
+            previous: None,
+            out: 0.0,
+        }
+    }
+
+    /// A negative `tau` is treated as 0.
+    pub fn set_config(&mut self, tau: f64, gain: f64) {
+        self.tau = tau.max(0.0);
+        self.gain = gain;
+    }
+
+    pub fn set_input(&mut self, value: f64) {
+        self.input = value;
+    }
+
+    /// Clears the filter state and the previous sample, so the first tick
+    /// after a restart does not produce a spike.
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.out = 0.0;
+    }
+
+    pub fn out(&self) -> f64 {
+        self.out
+    }
+}
+
+impl Plugin for FilteredDerivativePlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
+        let time_step = ctx.period_seconds;
+        self.out = match self.previous {
+            Some(previous) if self.tau + time_step > 0.0 => {
+                (self.tau * self.out + self.gain * (self.input - previous))
+                    / (self.tau + time_step)
+            }
+            _ => 0.0,
+        };
+        self.previous = Some(self.input);
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::float("tau", "Filter time constant (s)")
+                        .min_f(0.0)
+                        .step_f(0.001)
+                        .default_value(Value::from(0.01))
+                        .hint("Larger values filter more noise but add lag"),
+                )
+                .field(
+                    ConfigField::float("gain", "Gain")
+                        .step_f(0.1)
+                        .default_value(Value::from(1.0)),
+                )
+                .field(
+                    ConfigField::integer("warmup_ticks", "Warmup (ticks)")
+                        .min(0)
+                        .default_value(Value::from(0))
+                        .hint("Outputs are held at 0 for this many ticks after start"),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["out".to_string()],
+            inputs: vec!["in".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "tau" => Some(Value::from(self.tau)),
+            "gain" => Some(Value::from(self.gain)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        match (name, value.as_f64()) {
+            ("tau", Some(v)) => self.tau = v.max(0.0),
+            ("gain", Some(v)) => self.gain = v,
+            _ => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use filtered_derivative_plugin::FilteredDerivativePlugin;
+use rtsyn_plugin::prelude::*;
+
+fn step(plugin: &mut FilteredDerivativePlugin, period_seconds: f64, input: f64) -> f64 {
+    let mut ctx = PluginContext {
+        period_seconds,
+        ..PluginContext::default()
+    };
+    plugin.set_input(input);
+    plugin.process(&mut ctx).unwrap();
+    plugin.out()
+}
+
+#[test]
+fn ramp_settles_to_its_slope_with_the_filter_lag() {
+    let mut plugin = FilteredDerivativePlugin::new(1);
+    plugin.set_config(0.1, 2.0);
+
+    // First sample has no history
+    assert_eq!(step(&mut plugin, 0.1, 0.0), 0.0);
+    // Half way on the first step: (0.1 * 0 + 2 * 1) / 0.2
+    assert!((step(&mut plugin, 0.1, 1.0) - 10.0).abs() < 1e-9);
+    let mut out = 0.0;
+    for k in 2..60 {
+        out = step(&mut plugin, 0.1, k as f64);
+    }
+    // Slope 10/s times gain 2
+    assert!((out - 20.0).abs() < 1e-6);
+
+    // Without filtering it is the raw derivative
+    plugin.set_config(0.0, 1.0);
+    assert!((step(&mut plugin, 0.1, 62.0) - 30.0).abs() < 1e-9);
+    plugin.set_config(-1.0, 1.0);
+    assert_eq!(plugin.get_variable("tau"), Some(0.0.into()));
+}
+
+#[test]
+fn filter_attenuates_noise_and_reset_avoids_spike() {
+    let mut raw = FilteredDerivativePlugin::new(1);
+    raw.set_config(0.0, 1.0);
+    let mut filtered = FilteredDerivativePlugin::new(2);
+    filtered.set_config(0.05, 1.0);
+    let (mut raw_peak, mut filtered_peak) = (0.0_f64, 0.0_f64);
+    for k in 0..200 {
+        let noise = if k % 2 == 0 { 0.01 } else { -0.01 };
+        raw_peak = raw_peak.max(step(&mut raw, 0.001, noise).abs());
+        filtered_peak = filtered_peak.max(step(&mut filtered, 0.001, noise).abs());
+    }
+    assert!(filtered_peak < raw_peak / 10.0, "{filtered_peak} vs {raw_peak}");
+
+    filtered.reset();
+    assert_eq!(step(&mut filtered, 0.001, 100.0), 0.0);
+}
@@ -0,0 +1,263 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+use std::collections::VecDeque;
+
+pub const EDGES: [&str; 2] = ["rising", "falling"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Edge {
+    /// The input goes from below the threshold to at or above it.
+    #[default]
+    Rising,
+    /// The input goes from at or above the threshold to below it.
+    Falling,
+}
+
+impl Edge {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "falling" => Edge::Falling,
+            _ => Edge::Rising,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Edge::Rising => "rising",
+            Edge::Falling => "falling",
+        }
+    }
+}
+
+pub struct FrequencyCounterPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    threshold: f64,
+    edge: Edge,
+    periods: usize,
+    input: f64,
+    above: Option<bool>,
+    /// Whether an edge has been seen, so `elapsed` spans a whole period.
+    counting: bool,
+    /// Time since the last counted edge.
+    elapsed: f64,
+    /// Time at or above the threshold since the last counted edge.
+    high_time: f64,
+    /// Length and high time of the most recent periods, oldest first.
+    history: VecDeque<(f64, f64)>,
+    frequency_hz: f64,
+    period_ms: f64,
+    duty_cycle: f64,
+}
+
+impl FrequencyCounterPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Frequency Counter".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("threshold".to_string(), Value::from(0.5)),
+                    ("edge".to_string(), Value::from("rising")),
+                    ("periods".to_string(), Value::from(4)),
+                ],
+            },
+            inputs: vec![Port { id: PortId("in".to_string()) }],
+            outputs: vec![
+                Port { id: PortId("frequency_hz".to_string()) },
+                Port { id: PortId("period_ms".to_string()) },
+                Port { id: PortId("duty_cycle".to_string()) },
+            ],
+            threshold: 0.5,
+            edge: Edge::Rising,
+            periods: 4,
+            input: 0.0,
+            above: None,
+            counting: false,
+            elapsed: 0.0,
+            high_time: 0.0,
+            history: VecDeque::new(),
+            frequency_hz: 0.0,
+            period_ms: 0.0,
+            duty_cycle: 0.0,
+        }
+    }
+
+    /// Outputs average the last `periods` periods (at least one).
+    pub fn set_config(&mut self, threshold: f64, edge: Edge, periods: usize) {
+        self.threshold = threshold;
+        self.edge = edge;
+        self.periods = periods.max(1);
+        while self.history.len() > self.periods {
+            self.history.pop_front();
+        }
+    }
+
+    pub fn set_input(&mut self, input: f64) {
+        self.input = input;
+    }
+
+    pub fn reset(&mut self) {
+        self.above = None;
+        self.counting = false;
+        self.elapsed = 0.0;
+        self.high_time = 0.0;
+        self.history.clear();
+        self.update_outputs();
+    }
+
+    pub fn frequency_hz(&self) -> f64 {
+        self.frequency_hz
+    }
+
+    pub fn period_ms(&self) -> f64 {
+        self.period_ms
+    }
+
+    pub fn duty_cycle(&self) -> f64 {
+        self.duty_cycle
+    }
+
+    fn update_outputs(&mut self) {
+        let total: f64 = self.history.iter().map(|(period, _)| period).sum();
+        if total <= 0.0 {
+            self.frequency_hz = 0.0;
+            self.period_ms = 0.0;
+            self.duty_cycle = 0.0;
+            return;
+        }
+        let high: f64 = self.history.iter().map(|(_, high)| high).sum();
+        let count = self.history.len() as f64;
+        self.frequency_hz = count / total;
+        self.period_ms = total / count * 1000.0;
+        self.duty_cycle = high / total;
+    }
+}
+
+impl Plugin for FrequencyCounterPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    /// Each sample holds until the next one, so edges and high time are
+    /// resolved to one `time_step`. Once no edge arrives for twice the
+    /// average period, the signal is treated as stopped and the outputs
+    /// drop to zero until two new edges are seen.
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
+        if !self.input.is_finite() {
+            return Ok(());
+        }
+        let time_step = ctx.period_seconds.max(0.0);
+        let above = self.input >= self.threshold;
+        let edge = match (self.above, self.edge) {
+            (Some(false), Edge::Rising) => above,
+            (Some(true), Edge::Falling) => !above,
+            _ => false,
+        };
+        if edge {
+            if self.counting {
+                self.history.push_back((self.elapsed, self.high_time));
+                while self.history.len() > self.periods {
+                    self.history.pop_front();
+                }
+            }
+            self.counting = true;
+            self.elapsed = 0.0;
+            self.high_time = 0.0;
+        }
+        self.above = Some(above);
+        self.elapsed += time_step;
+        if above {
+            self.high_time += time_step;
+        }
+        self.update_outputs();
+        if !self.history.is_empty() && self.elapsed > 2.0 * self.period_ms / 1000.0 {
+            self.history.clear();
+            self.update_outputs();
+        }
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::float("threshold", "Threshold")
+                        .step_f(0.1)
+                        .default_value(Value::from(0.5)),
+                )
+                .field(
+                    ConfigField::text("edge", "Edge")
+                        .default_value(Value::from("rising"))
+                        .hint("rising or falling"),
+                )
+                .field(
+                    ConfigField::integer("periods", "Smoothing (periods)")
+                        .min(1)
+                        .default_value(Value::from(4))
+                        .hint("Number of recent periods averaged"),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec![
+                "frequency_hz".to_string(),
+                "period_ms".to_string(),
+                "duty_cycle".to_string(),
+            ],
+            inputs: vec!["in".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "threshold" => Some(Value::from(self.threshold)),
+            "edge" => Some(Value::from(self.edge.name())),
+            "periods" => Some(Value::from(self.periods as u64)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        let (mut threshold, mut edge, mut periods) = (self.threshold, self.edge, self.periods);
+        match name {
+            "threshold" => threshold = value.as_f64().unwrap_or(threshold),
+            "edge" => {
+                if let Some(v) = value.as_str() {
+                    edge = Edge::from_name(v);
+                }
+            }
+            "periods" => periods = value.as_u64().map(|v| v as usize).unwrap_or(periods),
+            _ => return Ok(()),
+        }
+        self.set_config(threshold, edge, periods);
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use frequency_counter_plugin::{Edge, FrequencyCounterPlugin};
+use rtsyn_plugin::prelude::*;
+
+/// Feeds `samples` at 125 ms per tick and returns frequency, period and duty cycle.
+fn run(plugin: &mut FrequencyCounterPlugin, samples: &[f64]) -> (f64, f64, f64) {
+    let mut ctx = PluginContext {
+        period_seconds: 0.125,
+        ..PluginContext::default()
+    };
+    for &sample in samples {
+        plugin.set_input(sample);
+        plugin.process(&mut ctx).unwrap();
+    }
+    (plugin.frequency_hz(), plugin.period_ms(), plugin.duty_cycle())
+}
+
+#[test]
+fn measures_frequency_period_and_duty_cycle() {
+    let square = [1.0, 1.0, 0.0, 0.0].repeat(4);
+    let mut plugin = FrequencyCounterPlugin::new(1);
+    // The first edge only starts the count
+    assert_eq!(run(&mut plugin, &square[..8]), (0.0, 0.0, 0.0));
+    assert_eq!(run(&mut plugin, &square[8..]), (2.0, 500.0, 0.5));
+
+    let pulse = [1.0, 0.0, 0.0, 0.0].repeat(4);
+    for edge in [Edge::Rising, Edge::Falling] {
+        plugin.set_config(0.5, edge, 4);
+        plugin.reset();
+        assert_eq!(run(&mut plugin, &pulse), (2.0, 500.0, 0.25));
+    }
+}
+
+#[test]
+fn averages_recent_periods_and_drops_to_zero_when_stopped() {
+    let mut plugin = FrequencyCounterPlugin::new(1);
+    plugin.set_config(0.5, Edge::Rising, 2);
+    // Periods of 250, 500 and 250 ms; the last two are averaged
+    let samples = [0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0];
+    let (frequency_hz, period_ms, duty_cycle) = run(&mut plugin, &samples);
+    assert_eq!(period_ms, 375.0);
+    assert!((frequency_hz - 1000.0 / 375.0).abs() < 1e-9);
+    assert!((duty_cycle - 1.0 / 3.0).abs() < 1e-9);
+
+    // Twice the average period without an edge
+    assert_eq!(run(&mut plugin, &[0.0; 5]).0, frequency_hz);
+    assert_eq!(run(&mut plugin, &[0.0]), (0.0, 0.0, 0.0));
+}
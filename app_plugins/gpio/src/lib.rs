@@ -0,0 +1,590 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// An input above this level drives its pin high.
+const HIGH_THRESHOLD: f64 = 0.5;
+
+mod gpiochip {
+    use libc::c_int;
+    use std::ffi::CString;
+    use std::mem::size_of;
+
+    const GPIOHANDLES_MAX: usize = 64;
+    pub const REQUEST_INPUT: u32 = 1 << 0;
+    pub const REQUEST_OUTPUT: u32 = 1 << 1;
+    pub const REQUEST_BIAS_PULL_UP: u32 = 1 << 5;
+    pub const REQUEST_BIAS_PULL_DOWN: u32 = 1 << 6;
+    pub const REQUEST_BIAS_DISABLE: u32 = 1 << 7;
+
+    #[repr(C)]
+    struct gpiohandle_request {
+        lineoffsets: [u32; GPIOHANDLES_MAX],
+        flags: u32,
+        default_values: [u8; GPIOHANDLES_MAX],
+        consumer_label: [u8; 32],
+        lines: u32,
+        fd: c_int,
+    }
+
+    #[repr(C)]
+    struct gpiohandle_data {
+        values: [u8; GPIOHANDLES_MAX],
+    }
+
+    /// `_IOWR(0xB4, nr, size)` of `linux/gpio.h`.
+    const fn iowr(nr: u64, size: usize) -> u64 {
+        (3 << 30) | ((size as u64) << 16) | (0xB4 << 8) | nr
+    }
+
+    const GPIO_GET_LINEHANDLE_IOCTL: u64 = iowr(0x03, size_of::<gpiohandle_request>());
+    const GPIOHANDLE_GET_LINE_VALUES_IOCTL: u64 = iowr(0x08, size_of::<gpiohandle_data>());
+    const GPIOHANDLE_SET_LINE_VALUES_IOCTL: u64 = iowr(0x09, size_of::<gpiohandle_data>());
+
+    fn last_error() -> String {
+        std::io::Error::last_os_error().to_string()
+    }
+
+    /// An open GPIO chip such as `/dev/gpiochip0`.
+    pub struct Chip {
+        fd: c_int,
+    }
+
+    impl Chip {
+        pub fn open(path: &str) -> Result<Self, String> {
+            let name = CString::new(path).map_err(|_| "invalid chip path".to_string())?;
+            let fd = unsafe { libc::open(name.as_ptr(), libc::O_RDWR | libc::O_CLOEXEC) };
+            if fd < 0 {
+                return Err(last_error());
+            }
+            Ok(Self { fd })
+        }
+
+        /// Claims line `offset` with the `REQUEST_*` `flags`; outputs start at
+        /// `high`. The line is released when the returned handle is dropped.
+        pub fn request_line(&self, offset: u32, flags: u32, high: bool) -> Result<Line, String> {
+            let mut request = gpiohandle_request {
+                lineoffsets: [0; GPIOHANDLES_MAX],
+                flags,
+                default_values: [0; GPIOHANDLES_MAX],
+                consumer_label: [0; 32],
+                lines: 1,
+                fd: -1,
+            };
+            request.lineoffsets[0] = offset;
+            request.default_values[0] = u8::from(high);
+            request.consumer_label[..5].copy_from_slice(b"rtsyn");
+            let res = unsafe {
+                libc::ioctl(self.fd, GPIO_GET_LINEHANDLE_IOCTL as _, &mut request as *mut gpiohandle_request)
+            };
+            if res < 0 || request.fd < 0 {
+                return Err(last_error());
+            }
+            Ok(Line { fd: request.fd })
+        }
+    }
+
+    impl Drop for Chip {
+        fn drop(&mut self) {
+            unsafe { libc::close(self.fd) };
+        }
+    }
+
+    /// One claimed GPIO line.
+    pub struct Line {
+        fd: c_int,
+    }
+
+    impl Line {
+        pub fn get(&self) -> Result<bool, String> {
+            let mut data = gpiohandle_data { values: [0; GPIOHANDLES_MAX] };
+            let res = unsafe {
+                libc::ioctl(self.fd, GPIOHANDLE_GET_LINE_VALUES_IOCTL as _, &mut data as *mut gpiohandle_data)
+            };
+            if res < 0 {
+                return Err(last_error());
+            }
+            Ok(data.values[0] != 0)
+        }
+
+        pub fn set(&self, high: bool) -> Result<(), String> {
+            let mut data = gpiohandle_data { values: [0; GPIOHANDLES_MAX] };
+            data.values[0] = u8::from(high);
+            let res = unsafe {
+                libc::ioctl(self.fd, GPIOHANDLE_SET_LINE_VALUES_IOCTL as _, &mut data as *mut gpiohandle_data)
+            };
+            if res < 0 {
+                Err(last_error())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    impl Drop for Line {
+        fn drop(&mut self) {
+            unsafe { libc::close(self.fd) };
+        }
+    }
+}
+
+/// Bias applied to a GPIO input pin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Pull {
+    #[default]
+    Off,
+    Up,
+    Down,
+}
+
+impl Pull {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "off" | "none" => Some(Self::Off),
+            "up" => Some(Self::Up),
+            "down" => Some(Self::Down),
+            _ => None,
+        }
+    }
+
+    fn request_flags(self) -> u32 {
+        match self {
+            Pull::Off => gpiochip::REQUEST_BIAS_DISABLE,
+            Pull::Up => gpiochip::REQUEST_BIAS_PULL_UP,
+            Pull::Down => gpiochip::REQUEST_BIAS_PULL_DOWN,
+        }
+    }
+}
+
+/// One port mapped to a GPIO line of the chip, numbered like the chip's line
+/// offsets, which on a Raspberry Pi are the BCM GPIO numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinMapping {
+    pub name: String,
+    pub pin: u32,
+    pub pull: Pull,
+}
+
+impl PinMapping {
+    /// Parses `name:pin[:pull]`, `pull` being `up`, `down` or `off`, e.g.
+    /// `button:27:up`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = spec.split(':').map(str::trim).collect();
+        if parts.len() < 2 || parts.len() > 3 {
+            return Err(format!("expected name:pin[:pull], got '{spec}'"));
+        }
+        let name = parts[0].to_string();
+        if name.is_empty() {
+            return Err("pin name is empty".to_string());
+        }
+        let pin = parts[1]
+            .parse()
+            .map_err(|_| format!("invalid pin '{}'", parts[1]))?;
+        let pull = match parts.get(2) {
+            Some(v) => Pull::from_name(v).ok_or_else(|| format!("invalid pull '{v}', use up, down or off"))?,
+            None => Pull::Off,
+        };
+        Ok(Self { name, pin, pull })
+    }
+}
+
+fn parse_mappings(specs: &[String]) -> Vec<PinMapping> {
+    specs
+        .iter()
+        .filter(|spec| !spec.trim().is_empty())
+        .filter_map(|spec| PinMapping::parse(spec).ok())
+        .collect()
+}
+
+/// Parses every non-empty spec of `direction`, failing on the first invalid
+/// one instead of skipping it.
+fn check_mappings(direction: &str, specs: &[String]) -> Result<Vec<PinMapping>, String> {
+    specs
+        .iter()
+        .filter(|spec| !spec.trim().is_empty())
+        .map(|spec| PinMapping::parse(spec).map_err(|err| format!("{direction} pin '{spec}': {err}")))
+        .collect()
+}
+
+/// Claimed output lines and input lines, in mapping order.
+type ClaimedLines = (Vec<gpiochip::Line>, Vec<gpiochip::Line>);
+
+/// Per-channel outcome of a one-shot device test, in mapping order.
+pub type ChannelResults = Vec<(String, Result<f64, String>)>;
+
+/// Drives GPIO output pins from its inputs and reports GPIO input pins on its
+/// outputs as 1 or 0. Every input port maps to one output pin, set high
+/// above 0.5, and every input pin to one output port.
+pub struct GpioPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    chip_path: String,
+    output_specs: Vec<String>,
+    input_specs: Vec<String>,
+    output_pins: Vec<PinMapping>,
+    input_pins: Vec<PinMapping>,
+    input_values: HashMap<String, f64>,
+    output_values: HashMap<String, f64>,
+    safe_value: f64,
+    lines: Option<ClaimedLines>,
+    open_error: Option<String>,
+}
+
+impl GpioPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "GPIO Device Driver".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("chip".to_string(), Value::from("/dev/gpiochip0")),
+                    ("output_pins".to_string(), Value::Array(Vec::new())),
+                    ("input_pins".to_string(), Value::Array(Vec::new())),
+                    ("safe_value".to_string(), Value::from(0.0)),
+                ],
+            },
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            chip_path: "/dev/gpiochip0".to_string(),
+            output_specs: Vec::new(),
+            input_specs: Vec::new(),
+            output_pins: Vec::new(),
+            input_pins: Vec::new(),
+            input_values: HashMap::new(),
+            output_values: HashMap::new(),
+            safe_value: 0.0,
+            lines: None,
+            open_error: None,
+        }
+    }
+
+    /// Applies the chip and pin mappings. Invalid mapping entries are
+    /// skipped; any change releases the claimed pins so they are claimed
+    /// again with the new mappings.
+    pub fn set_config(&mut self, chip_path: &str, output_specs: &[String], input_specs: &[String]) {
+        if self.chip_path != chip_path {
+            self.chip_path = chip_path.to_string();
+            self.lines = None;
+            self.open_error = None;
+        }
+        if self.output_specs != output_specs || self.input_specs != input_specs {
+            self.output_specs = output_specs.to_vec();
+            self.input_specs = input_specs.to_vec();
+            self.output_pins = parse_mappings(output_specs);
+            self.input_pins = parse_mappings(input_specs);
+            self.lines = None;
+            self.open_error = None;
+            self.update_ports();
+        }
+    }
+
+    /// Checks the configuration and that the pins can be claimed, so a
+    /// driver that cannot run is not started. Invalid pin specs, which
+    /// [`Self::set_config`] skips, are reported here.
+    pub fn check_start(&self) -> Result<(), String> {
+        if self.chip_path.trim().is_empty() {
+            return Err("no GPIO chip is set".to_string());
+        }
+        let outputs = check_mappings("output", &self.output_specs)?;
+        let inputs = check_mappings("input", &self.input_specs)?;
+        if outputs.is_empty() && inputs.is_empty() {
+            return Err("no output or input pins are mapped".to_string());
+        }
+        let mut pins: Vec<u32> = outputs.iter().chain(&inputs).map(|m| m.pin).collect();
+        pins.sort_unstable();
+        if let Some(pair) = pins.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(format!("pin {} is mapped more than once", pair[0]));
+        }
+        self.claim_lines()
+            .map(drop)
+            .map_err(|err| format!("cannot open {}: {err}", self.chip_path))
+    }
+
+    /// Opens the chip, drives every output pin to the safe value when
+    /// `write_safe` is set, reads every input pin once and releases the
+    /// lines. Each pin is claimed on its own, so a busy pin only fails its
+    /// own entry; a chip that cannot be opened fails the whole test.
+    pub fn test_channels(&self, write_safe: bool) -> Result<ChannelResults, String> {
+        let chip = gpiochip::Chip::open(&self.chip_path)
+            .map_err(|err| format!("cannot open {}: {err}", self.chip_path))?;
+        let mut results = Vec::new();
+        // Held until the inputs are read so the outputs keep their level
+        let mut claimed = Vec::new();
+        if write_safe {
+            let high = self.safe_value > HIGH_THRESHOLD;
+            for mapping in &self.output_pins {
+                let result = chip
+                    .request_line(mapping.pin, gpiochip::REQUEST_OUTPUT, high)
+                    .map(|line| {
+                        claimed.push(line);
+                        if high { 1.0 } else { 0.0 }
+                    })
+                    .map_err(|err| format!("pin {}: {err}", mapping.pin));
+                results.push((format!("{} (write)", mapping.name), result));
+            }
+        }
+        for mapping in &self.input_pins {
+            let result = chip
+                .request_line(
+                    mapping.pin,
+                    gpiochip::REQUEST_INPUT | mapping.pull.request_flags(),
+                    false,
+                )
+                .and_then(|line| line.get())
+                .map(|high| if high { 1.0 } else { 0.0 })
+                .map_err(|err| format!("pin {}: {err}", mapping.pin));
+            results.push((mapping.name.clone(), result));
+        }
+        drop(claimed);
+        Ok(results)
+    }
+
+    fn claim_lines(&self) -> Result<ClaimedLines, String> {
+        let chip = gpiochip::Chip::open(&self.chip_path)?;
+        let outputs = self
+            .output_pins
+            .iter()
+            .map(|m| {
+                chip.request_line(m.pin, gpiochip::REQUEST_OUTPUT, self.level(&m.name))
+                    .map_err(|err| format!("pin {}: {err}", m.pin))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let inputs = self
+            .input_pins
+            .iter()
+            .map(|m| {
+                chip.request_line(m.pin, gpiochip::REQUEST_INPUT | m.pull.request_flags(), false)
+                    .map_err(|err| format!("pin {}: {err}", m.pin))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((outputs, inputs))
+    }
+
+    fn update_ports(&mut self) {
+        self.inputs = self
+            .output_pins
+            .iter()
+            .map(|m| Port { id: PortId(m.name.clone()) })
+            .collect();
+        self.outputs = self
+            .input_pins
+            .iter()
+            .map(|m| Port { id: PortId(m.name.clone()) })
+            .collect();
+        self.input_values
+            .retain(|name, _| self.output_pins.iter().any(|m| &m.name == name));
+        self.output_values
+            .retain(|name, _| self.input_pins.iter().any(|m| &m.name == name));
+    }
+
+    pub fn input_port_names(&self) -> Vec<String> {
+        self.output_pins.iter().map(|m| m.name.clone()).collect()
+    }
+
+    pub fn output_port_names(&self) -> Vec<String> {
+        self.input_pins.iter().map(|m| m.name.clone()).collect()
+    }
+
+    pub fn set_input(&mut self, port_name: &str, value: f64) {
+        self.input_values.insert(port_name.to_string(), value);
+    }
+
+    pub fn get_output(&self, port_name: &str) -> f64 {
+        self.output_values.get(port_name).copied().unwrap_or(0.0)
+    }
+
+    /// Level the output pin of input `port_name` is driven to.
+    pub fn level(&self, port_name: &str) -> bool {
+        self.input_values.get(port_name).copied().unwrap_or(0.0) > HIGH_THRESHOLD
+    }
+
+    /// Value every output pin is commanded to in the safe state.
+    pub fn set_safe_value(&mut self, safe_value: f64) {
+        self.safe_value = safe_value;
+    }
+
+    /// Overrides every output pin with the safe value until the next inputs
+    /// are set; the runtime calls it each tick while an emergency stop is
+    /// asserted.
+    pub fn safe_state(&mut self) {
+        for mapping in &self.output_pins {
+            self.input_values.insert(mapping.name.clone(), self.safe_value);
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.lines.is_some()
+    }
+
+    /// False after a failed open until the config changes, the driver is
+    /// closed or [`Self::retry_open`] is called, so a busy pin is not
+    /// retried every tick.
+    pub fn can_retry_open(&self) -> bool {
+        self.open_error.is_none()
+    }
+
+    /// Why the last open failed, e.g. a pin is claimed by another program.
+    pub fn open_error(&self) -> Option<&str> {
+        self.open_error.as_deref()
+    }
+
+    /// Lets the runtime try claiming the pins again.
+    pub fn retry_open(&mut self) {
+        self.open_error = None;
+    }
+}
+
+impl Plugin for GpioPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        let Some((output_lines, input_lines)) = self.lines.as_ref() else {
+            return Ok(());
+        };
+        let mut result = Ok(());
+        for (mapping, line) in self.output_pins.iter().zip(output_lines) {
+            if line.set(self.level(&mapping.name)).is_err() {
+                result = Err(PluginError::ProcessingFailed);
+            }
+        }
+        for (mapping, line) in self.input_pins.iter().zip(input_lines) {
+            match line.get() {
+                Ok(high) => {
+                    self.output_values
+                        .insert(mapping.name.clone(), if high { 1.0 } else { 0.0 });
+                }
+                Err(_) => result = Err(PluginError::ProcessingFailed),
+            }
+        }
+        result
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: false,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::text("chip", "Chip")
+                        .default_value(Value::from("/dev/gpiochip0"))
+                        .hint("GPIO character device; gpiochip0 holds the header pins on most Raspberry Pis"),
+                )
+                .field(
+                    ConfigField::dynamic_list("output_pins", "Output pins")
+                        .hint("name:pin, one input per pin, driven high above 0.5"),
+                )
+                .field(
+                    ConfigField::dynamic_list("input_pins", "Input pins")
+                        .hint("name:pin[:up|down|off], one output per pin, 1 when high"),
+                )
+                .field(
+                    ConfigField::float("safe_value", "Safe value")
+                        .default_value(Value::from(0.0))
+                        .hint("Sent to every output pin while an emergency stop is asserted"),
+                ),
+        )
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            inputs: self.input_port_names(),
+            outputs: self.output_port_names(),
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        let specs_value =
+            |specs: &[String]| Value::Array(specs.iter().cloned().map(Value::String).collect());
+        match name {
+            "chip" => Some(Value::String(self.chip_path.clone())),
+            "output_pins" => Some(specs_value(&self.output_specs)),
+            "input_pins" => Some(specs_value(&self.input_specs)),
+            "safe_value" => Some(Value::from(self.safe_value)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        let specs = |value: &Value| -> Vec<String> {
+            value
+                .as_array()
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|v| v.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        match name {
+            "chip" => {
+                if let Some(chip) = value.as_str() {
+                    let (outputs, inputs) = (self.output_specs.clone(), self.input_specs.clone());
+                    self.set_config(chip, &outputs, &inputs);
+                }
+            }
+            "output_pins" => {
+                let (chip, inputs) = (self.chip_path.clone(), self.input_specs.clone());
+                self.set_config(&chip, &specs(&value), &inputs);
+            }
+            "input_pins" => {
+                let (chip, outputs) = (self.chip_path.clone(), self.output_specs.clone());
+                self.set_config(&chip, &outputs, &specs(&value));
+            }
+            "safe_value" => {
+                if let Some(v) = value.as_f64() {
+                    self.safe_value = v;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl DeviceDriver for GpioPlugin {
+    fn open(&mut self) -> Result<(), PluginError> {
+        match self.claim_lines() {
+            Ok(lines) => {
+                self.lines = Some(lines);
+                self.open_error = None;
+                Ok(())
+            }
+            Err(err) => {
+                self.open_error = Some(format!("{}: {err}", self.chip_path));
+                Err(PluginError::ProcessingFailed)
+            }
+        }
+    }
+
+    fn close(&mut self) -> Result<(), PluginError> {
+        self.lines = None;
+        self.open_error = None;
+        Ok(())
+    }
+}
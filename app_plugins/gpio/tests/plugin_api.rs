@@ -0,0 +1,92 @@
+use gpio_plugin::{GpioPlugin, PinMapping, Pull};
+use rtsyn_plugin::prelude::*;
+
+#[test]
+fn pin_spec_parsing() {
+    let output = PinMapping::parse("led:17").unwrap();
+    assert_eq!((output.name.as_str(), output.pin, output.pull), ("led", 17, Pull::Off));
+
+    let input = PinMapping::parse("button: 27 :up").unwrap();
+    assert_eq!((input.pin, input.pull), (27, Pull::Up));
+    assert_eq!(PinMapping::parse("sw:4:down").unwrap().pull, Pull::Down);
+
+    assert!(PinMapping::parse("led").is_err());
+    assert!(PinMapping::parse(":17").is_err());
+    assert!(PinMapping::parse("led:-1").is_err());
+    assert!(PinMapping::parse("led:17:sideways").is_err());
+    assert!(PinMapping::parse("led:17:up:1").is_err());
+}
+
+#[test]
+fn ports_follow_mappings_and_levels_use_the_threshold() {
+    let mut plugin = GpioPlugin::new(1);
+    plugin.set_config(
+        "/dev/gpiochip0",
+        &["led:17".to_string(), "relay:22".to_string(), "not a pin".to_string()],
+        &["button:27:up".to_string()],
+    );
+    assert_eq!(plugin.input_port_names(), vec!["led", "relay"]);
+    assert_eq!(plugin.output_port_names(), vec!["button"]);
+    assert_eq!(plugin.inputs().len(), 2);
+
+    plugin.set_input("led", 0.5);
+    plugin.set_input("relay", 0.51);
+    assert!(!plugin.level("led"));
+    assert!(plugin.level("relay"));
+    assert_eq!(plugin.get_output("button"), 0.0);
+
+    // Without claimed pins processing is a no-op
+    let mut ctx = PluginContext::default();
+    assert!(plugin.process(&mut ctx).is_ok());
+    assert!(!plugin.is_open());
+}
+
+#[test]
+fn safe_state_commands_every_output_pin() {
+    let mut plugin = GpioPlugin::new(1);
+    plugin.set_config("/dev/gpiochip0", &["a:5".to_string(), "b:6".to_string()], &[]);
+    plugin.set_input("a", 1.0);
+    plugin.set_safe_value(0.0);
+    plugin.safe_state();
+    assert!(!plugin.level("a"));
+
+    plugin.set_safe_value(1.0);
+    plugin.safe_state();
+    assert!(plugin.level("a") && plugin.level("b"));
+}
+
+#[test]
+fn check_start_rejects_bad_and_duplicate_pins() {
+    let mut plugin = GpioPlugin::new(1);
+    plugin.set_config("/dev/gpiochip0", &[], &[]);
+    assert!(plugin.check_start().unwrap_err().contains("no output or input pins"));
+
+    plugin.set_config("/dev/gpiochip0", &["led:x".to_string()], &[]);
+    assert!(plugin.check_start().unwrap_err().starts_with("output pin 'led:x'"));
+
+    plugin.set_config("/dev/gpiochip0", &["led:17".to_string()], &["button:17".to_string()]);
+    assert_eq!(plugin.check_start().unwrap_err(), "pin 17 is mapped more than once");
+}
+
+#[test]
+fn failed_open_keeps_the_reason_until_retried() {
+    let mut plugin = GpioPlugin::new(1);
+    plugin.set_config("/dev/rtsyn_missing_gpiochip", &["led:17".to_string()], &[]);
+    assert!(plugin.can_retry_open());
+
+    assert!(plugin.open().is_err());
+    assert!(!plugin.can_retry_open());
+    assert!(plugin.open_error().unwrap().starts_with("/dev/rtsyn_missing_gpiochip: "));
+
+    plugin.retry_open();
+    assert!(plugin.can_retry_open());
+    assert_eq!(plugin.open_error(), None);
+}
+
+#[test]
+fn test_channels_fails_as_a_whole_without_a_chip() {
+    let mut plugin = GpioPlugin::new(1);
+    plugin.set_config("/dev/rtsyn_missing_gpiochip", &[], &["button:4".to_string()]);
+    let err = plugin.test_channels(false).unwrap_err();
+    assert!(err.starts_with("cannot open /dev/rtsyn_missing_gpiochip: "));
+}
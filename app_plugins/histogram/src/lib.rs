@@ -0,0 +1,240 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+use std::collections::VecDeque;
+
+const DEFAULT_BINS: usize = 20;
+const DEFAULT_WINDOW: usize = 1000;
+
+pub struct HistogramPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    range_min: f64,
+    range_max: f64,
+    /// Number of most recent samples kept; 0 accumulates since the last reset.
+    window: usize,
+    input: f64,
+    /// Bin of every sample still in the window, `None` when it fell outside
+    /// the range.
+    samples: VecDeque<Option<usize>>,
+    counts: Vec<u64>,
+}
+
+impl HistogramPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Histogram".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("bins".to_string(), Value::from(DEFAULT_BINS as i64)),
+                    ("range_min".to_string(), Value::from(-1.0)),
+                    ("range_max".to_string(), Value::from(1.0)),
+                    ("window".to_string(), Value::from(DEFAULT_WINDOW as i64)),
+                ],
+            },
+            inputs: vec![Port { id: PortId("in".to_string()) }],
+            outputs: vec![
+                Port { id: PortId("count_total".to_string()) },
+                Port { id: PortId("mode_bin".to_string()) },
+            ],
+            range_min: -1.0,
+            range_max: 1.0,
+            window: DEFAULT_WINDOW,
+            input: 0.0,
+            samples: VecDeque::new(),
+            counts: vec![0; DEFAULT_BINS],
+        }
+    }
+
+    /// Changing the bin layout clears the histogram since existing counts
+    /// cannot be redistributed; a smaller window drops the oldest samples.
+    pub fn set_config(&mut self, bins: usize, range_min: f64, range_max: f64, window: usize) {
+        let bins = bins.max(1);
+        let (range_min, range_max) = if range_min <= range_max {
+            (range_min, range_max)
+        } else {
+            (range_max, range_min)
+        };
+        if bins != self.counts.len() || range_min != self.range_min || range_max != self.range_max {
+            self.range_min = range_min;
+            self.range_max = range_max;
+            self.counts = vec![0; bins];
+            self.samples.clear();
+        }
+        self.window = window;
+        self.trim_window();
+    }
+
+    pub fn set_input(&mut self, value: f64) {
+        self.input = value;
+    }
+
+    pub fn reset(&mut self) {
+        self.samples.clear();
+        self.counts.iter_mut().for_each(|count| *count = 0);
+    }
+
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Samples currently binned; out-of-range samples are not counted.
+    pub fn count_total(&self) -> f64 {
+        self.counts.iter().sum::<u64>() as f64
+    }
+
+    /// Index of the fullest bin, the lowest one on ties and 0 when empty.
+    pub fn mode_bin(&self) -> f64 {
+        let mut mode = 0;
+        for (idx, count) in self.counts.iter().enumerate() {
+            if *count > self.counts[mode] {
+                mode = idx;
+            }
+        }
+        mode as f64
+    }
+
+    /// Bin `value` falls in; the upper range limit belongs to the last bin.
+    pub fn bin_index(&self, value: f64) -> Option<usize> {
+        if !value.is_finite() || value < self.range_min || value > self.range_max {
+            return None;
+        }
+        let bins = self.counts.len();
+        let width = self.range_max - self.range_min;
+        if width <= 0.0 {
+            return Some(0);
+        }
+        let idx = ((value - self.range_min) / width * bins as f64) as usize;
+        Some(idx.min(bins - 1))
+    }
+
+    fn trim_window(&mut self) {
+        if self.window == 0 {
+            return;
+        }
+        while self.samples.len() > self.window {
+            if let Some(Some(bin)) = self.samples.pop_front() {
+                self.counts[bin] -= 1;
+            }
+        }
+    }
+}
+
+impl Plugin for HistogramPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        let bin = self.bin_index(self.input);
+        if let Some(bin) = bin {
+            self.counts[bin] += 1;
+        }
+        if self.window > 0 {
+            self.samples.push_back(bin);
+            self.trim_window();
+        }
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::integer("bins", "Bins")
+                        .min(1)
+                        .max(1000)
+                        .default_value(Value::from(DEFAULT_BINS as i64)),
+                )
+                .field(
+                    ConfigField::float("range_min", "Range min")
+                        .step_f(0.1)
+                        .default_value(Value::from(-1.0)),
+                )
+                .field(
+                    ConfigField::float("range_max", "Range max")
+                        .step_f(0.1)
+                        .default_value(Value::from(1.0))
+                        .hint("Samples outside the range are not counted"),
+                )
+                .field(
+                    ConfigField::integer("window", "Window (samples)")
+                        .min(0)
+                        .step(100)
+                        .default_value(Value::from(DEFAULT_WINDOW as i64))
+                        .hint("Most recent samples to keep (0 = since reset)"),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["count_total".to_string(), "mode_bin".to_string()],
+            inputs: vec!["in".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "bins" => Some(Value::from(self.counts.len() as i64)),
+            "range_min" => Some(Value::from(self.range_min)),
+            "range_max" => Some(Value::from(self.range_max)),
+            "window" => Some(Value::from(self.window as i64)),
+            "counts" => Some(Value::from(self.counts.clone())),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        let bins = self.counts.len();
+        match name {
+            "bins" => {
+                if let Some(v) = value.as_u64() {
+                    self.set_config(v as usize, self.range_min, self.range_max, self.window);
+                }
+            }
+            "range_min" => {
+                if let Some(v) = value.as_f64() {
+                    self.set_config(bins, v, self.range_max, self.window);
+                }
+            }
+            "range_max" => {
+                if let Some(v) = value.as_f64() {
+                    self.set_config(bins, self.range_min, v, self.window);
+                }
+            }
+            "window" => {
+                if let Some(v) = value.as_u64() {
+                    self.set_config(bins, self.range_min, self.range_max, v as usize);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
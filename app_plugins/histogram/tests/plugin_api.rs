@@ -0,0 +1,54 @@
+use histogram_plugin::HistogramPlugin;
+use rtsyn_plugin::prelude::*;
+
+fn feed(plugin: &mut HistogramPlugin, samples: &[f64]) {
+    let mut ctx = PluginContext::default();
+    for sample in samples {
+        plugin.set_input(*sample);
+        plugin.process(&mut ctx).unwrap();
+    }
+}
+
+#[test]
+fn samples_are_binned_across_the_range() {
+    let mut plugin = HistogramPlugin::new(1);
+    plugin.set_config(4, 0.0, 4.0, 0);
+    feed(&mut plugin, &[0.0, 0.5, 1.0, 2.5, 2.9, 2.1, 4.0, -0.1, 4.1]);
+    assert_eq!(plugin.counts(), &[2, 1, 3, 1]);
+    assert_eq!(plugin.count_total(), 7.0);
+    assert_eq!(plugin.mode_bin(), 2.0);
+    assert_eq!(plugin.bin_index(f64::NAN), None);
+}
+
+#[test]
+fn window_keeps_only_recent_samples() {
+    let mut plugin = HistogramPlugin::new(1);
+    plugin.set_config(2, 0.0, 2.0, 3);
+    feed(&mut plugin, &[0.5, 0.5, 0.5, 1.5, 1.5]);
+    assert_eq!(plugin.counts(), &[1, 2]);
+    assert_eq!(plugin.mode_bin(), 1.0);
+
+    // Out-of-range samples still take a slot in the window
+    feed(&mut plugin, &[9.0, 9.0]);
+    assert_eq!(plugin.counts(), &[0, 1]);
+
+    plugin.set_config(2, 0.0, 2.0, 1);
+    assert_eq!(plugin.counts(), &[0, 0]);
+}
+
+#[test]
+fn changing_bins_or_reset_clears_counts() {
+    let mut plugin = HistogramPlugin::new(1);
+    plugin.set_config(2, 0.0, 1.0, 0);
+    feed(&mut plugin, &[0.2, 0.7]);
+    plugin.set_config(2, 0.0, 1.0, 0);
+    assert_eq!(plugin.count_total(), 2.0);
+
+    plugin.set_config(5, 0.0, 1.0, 0);
+    assert_eq!(plugin.counts(), &[0; 5]);
+
+    feed(&mut plugin, &[0.1]);
+    plugin.reset();
+    assert_eq!(plugin.count_total(), 0.0);
+    assert_eq!(plugin.mode_bin(), 0.0);
+}
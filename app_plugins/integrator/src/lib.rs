@@ -0,0 +1,192 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+pub struct IntegratorPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    gain: f64,
+    initial_value: f64,
+    clamp: bool,
+    min: f64,
+    max: f64,
+    input: f64,
+    state: Option<f64>,
+}
+
+impl IntegratorPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Integrator".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("gain".to_string(), Value::from(1.0)),
+                    ("initial_value".to_string(), Value::from(0.0)),
+                    ("clamp".to_string(), Value::from(false)),
+                    ("min".to_string(), Value::from(-1.0)),
+                    ("max".to_string(), Value::from(1.0)),
+                    ("warmup_ticks".to_string(), Value::from(0)),
+                ],
+            },
+            inputs: vec![Port { id: PortId("in".to_string()) }],
+            outputs: vec![Port { id: PortId("out".to_string()) }],
+            gain: 1.0,
+            initial_value: 0.0,
+            clamp: false,
+            min: -1.0,
+            max: 1.0,
+            input: 0.0,
+            state: None,
+        }
+    }
+
+    pub fn set_config(&mut self, gain: f64, initial_value: f64, clamp: bool, min: f64, max: f64) {
+        self.gain = gain;
+        self.initial_value = initial_value;
+        self.clamp = clamp;
+        self.min = min.min(max);
+        self.max = max.max(min);
+    }
+
+    pub fn set_input(&mut self, value: f64) {
+        self.input = value;
+    }
+
+    /// Drops the accumulated state; the next tick starts from `initial_value`.
+    pub fn reset(&mut self) {
+        self.state = None;
+    }
+
+    pub fn out(&self) -> f64 {
+        self.limit(self.state.unwrap_or(self.initial_value))
+    }
+
+    fn limit(&self, value: f64) -> f64 {
+        if self.clamp {
+            value.clamp(self.min, self.max)
+        } else {
+            value
+        }
+    }
+}
+
+impl Plugin for IntegratorPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
+        // The step is taken from the context on every tick so period changes
+        // apply immediately.
+        let time_step = ctx.period_seconds.max(0.0);
+        let previous = self.state.unwrap_or(self.initial_value);
+        // Clamping the stored state (not only the output) is the anti-windup:
+        // the integrator leaves saturation as soon as the input changes sign.
+        self.state = Some(self.limit(previous + self.input * self.gain * time_step));
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::float("gain", "Gain")
+                        .step_f(0.1)
+                        .default_value(Value::from(1.0)),
+                )
+                .field(
+                    ConfigField::float("initial_value", "Initial value")
+                        .step_f(0.1)
+                        .default_value(Value::from(0.0))
+                        .hint("Output after start or restart"),
+                )
+                .field(
+                    ConfigField::boolean("clamp", "Clamp")
+                        .default_value(Value::from(false))
+                        .hint("Limit the output to [min, max] with anti-windup"),
+                )
+                .field(
+                    ConfigField::float("min", "Min")
+                        .step_f(0.1)
+                        .default_value(Value::from(-1.0)),
+                )
+                .field(
+                    ConfigField::float("max", "Max")
+                        .step_f(0.1)
+                        .default_value(Value::from(1.0)),
+                )
+                .field(
+                    ConfigField::integer("warmup_ticks", "Warmup (ticks)")
+                        .min(0)
+                        .default_value(Value::from(0))
+                        .hint("Outputs are held at 0 for this many ticks after start"),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["out".to_string()],
+            inputs: vec!["in".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "gain" => Some(Value::from(self.gain)),
+            "initial_value" => Some(Value::from(self.initial_value)),
+            "clamp" => Some(Value::from(self.clamp)),
+            "min" => Some(Value::from(self.min)),
+            "max" => Some(Value::from(self.max)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        match name {
+            "clamp" => {
+                if let Some(v) = value.as_bool() {
+                    self.clamp = v;
+                }
+            }
+            _ => {
+                let Some(v) = value.as_f64() else {
+                    return Ok(());
+                };
+                match name {
+                    "gain" => self.gain = v,
+                    "initial_value" => self.initial_value = v,
+                    "min" => self.set_config(self.gain, self.initial_value, self.clamp, v, self.max),
+                    "max" => self.set_config(self.gain, self.initial_value, self.clamp, self.min, v),
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+}
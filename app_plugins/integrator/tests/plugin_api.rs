@@ -0,0 +1,39 @@
+use integrator_plugin::IntegratorPlugin;
+use rtsyn_plugin::prelude::*;
+
+fn step(plugin: &mut IntegratorPlugin, period_seconds: f64, input: f64) -> f64 {
+    let mut ctx = PluginContext {
+        period_seconds,
+        ..PluginContext::default()
+    };
+    plugin.set_input(input);
+    plugin.process(&mut ctx).unwrap();
+    plugin.out()
+}
+
+#[test]
+fn integrator_accumulates_with_gain_and_time_step() {
+    let mut plugin = IntegratorPlugin::new(1);
+    plugin.set_config(2.0, 1.0, false, -1.0, 1.0);
+    assert_eq!(plugin.out(), 1.0);
+
+    assert!((step(&mut plugin, 0.1, 1.0) - 1.2).abs() < 1e-12);
+    // A period change takes effect on the next tick.
+    assert!((step(&mut plugin, 0.5, 1.0) - 2.2).abs() < 1e-12);
+
+    plugin.reset();
+    assert_eq!(plugin.out(), 1.0);
+}
+
+#[test]
+fn integrator_clamp_prevents_windup() {
+    let mut plugin = IntegratorPlugin::new(1);
+    plugin.set_config(1.0, 0.0, true, -1.0, 1.0);
+    for _ in 0..100 {
+        step(&mut plugin, 0.1, 1.0);
+    }
+    assert_eq!(plugin.out(), 1.0);
+
+    // Leaves saturation immediately once the input reverses.
+    assert!((step(&mut plugin, 0.1, -1.0) - 0.9).abs() < 1e-12);
+}
@@ -22,6 +22,7 @@ impl LivePlotterPlugin {
                     ("window_multiplier".to_string(), Value::from(1000)),
                     ("window_value".to_string(), Value::from(10)),
                     ("amplitude".to_string(), Value::from(0.0)),
+                    ("view_mode".to_string(), Value::from("time")),
                 ],
             },
             inputs: Vec::new(),
@@ -105,6 +106,11 @@ impl Plugin for LivePlotterPlugin {
                         .step_f(0.1)
                         .default_value(Value::from(0.0))
                         .hint("Y-axis amplitude (0 = auto)"),
+                )
+                .field(
+                    ConfigField::text("view_mode", "View mode")
+                        .default_value(Value::from("time"))
+                        .hint("time or spectrum"),
                 ),
         )
     }
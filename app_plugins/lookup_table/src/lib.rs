@@ -0,0 +1,304 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+pub const INTERPOLATIONS: [&str; 3] = ["nearest", "linear", "cubic"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    /// The y of the closest breakpoint.
+    Nearest,
+    /// Straight lines between breakpoints.
+    #[default]
+    Linear,
+    /// Monotone cubic (PCHIP): smooth, without overshooting the breakpoints.
+    Cubic,
+}
+
+impl Interpolation {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "nearest" => Interpolation::Nearest,
+            "cubic" => Interpolation::Cubic,
+            _ => Interpolation::Linear,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Interpolation::Nearest => "nearest",
+            Interpolation::Linear => "linear",
+            Interpolation::Cubic => "cubic",
+        }
+    }
+}
+
+pub struct LookupTablePlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    x_points: Vec<f64>,
+    y_points: Vec<f64>,
+    interpolation: Interpolation,
+    extrapolate: bool,
+    /// Breakpoints sorted by x with duplicates and non-finite pairs dropped.
+    table: Vec<(f64, f64)>,
+    /// Cubic slopes at each breakpoint of `table`.
+    tangents: Vec<f64>,
+    input: f64,
+    out: f64,
+}
+
+impl LookupTablePlugin {
+    pub fn new(id: u64) -> Self {
+        let mut plugin = Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Lookup Table".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("x_points".to_string(), Value::from(vec![0.0, 1.0])),
+                    ("y_points".to_string(), Value::from(vec![0.0, 1.0])),
+                    ("interpolation".to_string(), Value::from("linear")),
+                    ("extrapolate".to_string(), Value::from(false)),
+                ],
+            },
+            inputs: vec![Port { id: PortId("in".to_string()) }],
+            outputs: vec![Port { id: PortId("out".to_string()) }],
+            x_points: Vec::new(),
+            y_points: Vec::new(),
+            interpolation: Interpolation::Linear,
+            extrapolate: false,
+            table: Vec::new(),
+            tangents: Vec::new(),
+            input: 0.0,
+            out: 0.0,
+        };
+        plugin.set_config(&[0.0, 1.0], &[0.0, 1.0], Interpolation::Linear, false);
+        plugin
+    }
+
+    /// Breakpoints pair up by index; extra entries in the longer list are
+    /// ignored. With `extrapolate` false, inputs outside the table clamp to
+    /// the end values; otherwise the end segments extend as straight lines.
+    pub fn set_config(
+        &mut self,
+        x_points: &[f64],
+        y_points: &[f64],
+        interpolation: Interpolation,
+        extrapolate: bool,
+    ) {
+        self.interpolation = interpolation;
+        self.extrapolate = extrapolate;
+        if self.x_points == x_points && self.y_points == y_points {
+            return;
+        }
+        self.x_points = x_points.to_vec();
+        self.y_points = y_points.to_vec();
+
+        let mut table: Vec<(f64, f64)> = x_points
+            .iter()
+            .copied()
+            .zip(y_points.iter().copied())
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .collect();
+        table.sort_by(|a, b| a.0.total_cmp(&b.0));
+        table.dedup_by(|next, prev| next.0 == prev.0);
+        self.tangents = pchip_tangents(&table);
+        self.table = table;
+    }
+
+    pub fn set_input(&mut self, input: f64) {
+        self.input = input;
+    }
+
+    pub fn out(&self) -> f64 {
+        self.out
+    }
+
+    /// Looks `x` up in the table. An empty table passes `x` through.
+    pub fn evaluate(&self, x: f64) -> f64 {
+        let table = &self.table;
+        let (Some(&(x_first, y_first)), Some(&(x_last, y_last))) = (table.first(), table.last())
+        else {
+            return x;
+        };
+        if table.len() == 1 {
+            return y_first;
+        }
+        if x <= x_first || x >= x_last {
+            let at_start = x <= x_first;
+            let (x_end, y_end) = if at_start { (x_first, y_first) } else { (x_last, y_last) };
+            if !self.extrapolate || self.interpolation == Interpolation::Nearest {
+                return y_end;
+            }
+            let (a, b) = if at_start {
+                (table[0], table[1])
+            } else {
+                (table[table.len() - 2], table[table.len() - 1])
+            };
+            return y_end + (b.1 - a.1) / (b.0 - a.0) * (x - x_end);
+        }
+
+        // First breakpoint above x; x_first < x < x_last keeps it in 1..len
+        let upper = table.partition_point(|&(bx, _)| bx <= x);
+        let (x0, y0) = table[upper - 1];
+        let (x1, y1) = table[upper];
+        let h = x1 - x0;
+        let t = (x - x0) / h;
+        match self.interpolation {
+            Interpolation::Nearest => {
+                if t < 0.5 {
+                    y0
+                } else {
+                    y1
+                }
+            }
+            Interpolation::Linear => y0 + (y1 - y0) * t,
+            Interpolation::Cubic => {
+                let (m0, m1) = (self.tangents[upper - 1], self.tangents[upper]);
+                let t2 = t * t;
+                let t3 = t2 * t;
+                (2.0 * t3 - 3.0 * t2 + 1.0) * y0
+                    + (t3 - 2.0 * t2 + t) * h * m0
+                    + (-2.0 * t3 + 3.0 * t2) * y1
+                    + (t3 - t2) * h * m1
+            }
+        }
+    }
+}
+
+/// Fritsch-Carlson slopes: zero at local extrema, a weighted harmonic mean
+/// of the neighbouring secants elsewhere, the end secants at the ends.
+fn pchip_tangents(table: &[(f64, f64)]) -> Vec<f64> {
+    let n = table.len();
+    if n < 2 {
+        return vec![0.0; n];
+    }
+    let widths: Vec<f64> = table.windows(2).map(|w| w[1].0 - w[0].0).collect();
+    let secants: Vec<f64> = table
+        .windows(2)
+        .zip(&widths)
+        .map(|(w, h)| (w[1].1 - w[0].1) / h)
+        .collect();
+    let mut tangents = vec![0.0; n];
+    tangents[0] = secants[0];
+    tangents[n - 1] = secants[n - 2];
+    for k in 1..n - 1 {
+        let (d0, d1) = (secants[k - 1], secants[k]);
+        if d0 * d1 > 0.0 {
+            let w0 = 2.0 * widths[k] + widths[k - 1];
+            let w1 = widths[k] + 2.0 * widths[k - 1];
+            tangents[k] = (w0 + w1) / (w0 / d0 + w1 / d1);
+        }
+    }
+    tangents
+}
+
+fn f64_list(value: &Value) -> Option<Vec<f64>> {
+    value
+        .as_array()
+        .map(|items| items.iter().map(|v| v.as_f64().unwrap_or(0.0)).collect())
+}
+
+impl Plugin for LookupTablePlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        if !self.input.is_finite() {
+            return Ok(());
+        }
+        self.out = self.evaluate(self.input);
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        let point_type = FieldType::Float {
+            min: None,
+            max: None,
+            step: 0.1,
+        };
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::dynamic_list("x_points", "Breakpoints x")
+                        .item_type(point_type.clone())
+                        .add_label("Add x"),
+                )
+                .field(
+                    ConfigField::dynamic_list("y_points", "Breakpoints y")
+                        .item_type(point_type)
+                        .add_label("Add y"),
+                )
+                .field(
+                    ConfigField::text("interpolation", "Interpolation")
+                        .default_value(Value::from("linear"))
+                        .hint("nearest, linear or cubic"),
+                )
+                .field(
+                    ConfigField::boolean("extrapolate", "Extrapolate")
+                        .default_value(Value::from(false))
+                        .hint("Extend the end segments instead of clamping"),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: false,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["out".to_string()],
+            inputs: vec!["in".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "x_points" => Some(Value::from(self.x_points.clone())),
+            "y_points" => Some(Value::from(self.y_points.clone())),
+            "interpolation" => Some(Value::from(self.interpolation.name())),
+            "extrapolate" => Some(Value::from(self.extrapolate)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        let (mut x_points, mut y_points) = (self.x_points.clone(), self.y_points.clone());
+        let (mut interpolation, mut extrapolate) = (self.interpolation, self.extrapolate);
+        match name {
+            "x_points" => x_points = f64_list(&value).unwrap_or(x_points),
+            "y_points" => y_points = f64_list(&value).unwrap_or(y_points),
+            "interpolation" => {
+                if let Some(v) = value.as_str() {
+                    interpolation = Interpolation::from_name(v);
+                }
+            }
+            "extrapolate" => extrapolate = value.as_bool().unwrap_or(extrapolate),
+            _ => return Ok(()),
+        }
+        self.set_config(&x_points, &y_points, interpolation, extrapolate);
+        Ok(())
+    }
+}
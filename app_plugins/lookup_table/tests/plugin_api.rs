@@ -0,0 +1,52 @@
+use lookup_table_plugin::{Interpolation, LookupTablePlugin};
+use rtsyn_plugin::prelude::*;
+
+fn table(interpolation: Interpolation, extrapolate: bool) -> LookupTablePlugin {
+    let mut plugin = LookupTablePlugin::new(1);
+    // Unsorted on purpose; the duplicate x = 2 is dropped
+    plugin.set_config(
+        &[2.0, 0.0, 1.0, 2.0],
+        &[4.0, 0.0, 1.0, 9.0],
+        interpolation,
+        extrapolate,
+    );
+    plugin
+}
+
+#[test]
+fn interpolates_between_breakpoints() {
+    let linear = table(Interpolation::Linear, false);
+    assert_eq!(linear.evaluate(0.5), 0.5);
+    assert_eq!(linear.evaluate(1.5), 2.5);
+    assert_eq!(linear.evaluate(2.0), 4.0);
+
+    let nearest = table(Interpolation::Nearest, false);
+    assert_eq!(nearest.evaluate(0.4), 0.0);
+    assert_eq!(nearest.evaluate(1.6), 4.0);
+
+    // Monotone data stays monotone and hits the breakpoints exactly
+    let cubic = table(Interpolation::Cubic, false);
+    assert_eq!(cubic.evaluate(1.0), 1.0);
+    let samples: Vec<f64> = (0..=20).map(|i| cubic.evaluate(i as f64 * 0.1)).collect();
+    assert!(samples.windows(2).all(|w| w[1] >= w[0]));
+    assert!(samples[5] < 0.5 && samples[15] < 2.5);
+}
+
+#[test]
+fn clamps_or_extrapolates_outside_the_table() {
+    let clamped = table(Interpolation::Linear, false);
+    assert_eq!(clamped.evaluate(-1.0), 0.0);
+    assert_eq!(clamped.evaluate(5.0), 4.0);
+
+    let mut extended = table(Interpolation::Linear, true);
+    assert_eq!(extended.evaluate(-1.0), -1.0);
+    assert_eq!(extended.evaluate(3.0), 7.0);
+
+    extended.set_input(3.0);
+    extended.process(&mut PluginContext::default()).unwrap();
+    assert_eq!(extended.out(), 7.0);
+
+    // An empty table passes the input through
+    extended.set_config(&[], &[], Interpolation::Linear, true);
+    assert_eq!(extended.evaluate(3.0), 3.0);
+}
@@ -0,0 +1,970 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+use std::collections::HashMap;
+
+pub const MODES: [&str; 2] = ["tcp", "rtu"];
+pub const TABLES: [&str; 4] = ["holding", "coil", "input", "discrete"];
+
+pub use modbus::crc16;
+
+mod modbus {
+    use super::Table;
+    use libc::c_int;
+    use std::ffi::CString;
+    use std::io::{self, Read, Write};
+    use std::net::{TcpStream, ToSocketAddrs};
+    use std::time::Duration;
+
+    pub enum Error {
+        /// The link failed or the response was malformed; the connection is
+        /// no longer usable.
+        Io(String),
+        /// The device answered with an exception response.
+        Exception,
+    }
+
+    /// CRC-16/MODBUS, sent low byte first at the end of every RTU frame.
+    pub fn crc16(data: &[u8]) -> u16 {
+        let mut crc = 0xFFFFu16;
+        for &byte in data {
+            crc ^= u16::from(byte);
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 { (crc >> 1) ^ 0xA001 } else { crc >> 1 };
+            }
+        }
+        crc
+    }
+
+    fn invalid(message: &str) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, message)
+    }
+
+    /// Raw 8N1 serial line. Reads give up after the configured timeout.
+    pub struct SerialPort {
+        fd: c_int,
+    }
+
+    impl SerialPort {
+        pub fn open(path: &str, baud_rate: u32, timeout: Duration) -> Result<Self, String> {
+            let speed = match baud_rate {
+                1200 => libc::B1200,
+                2400 => libc::B2400,
+                4800 => libc::B4800,
+                9600 => libc::B9600,
+                19200 => libc::B19200,
+                38400 => libc::B38400,
+                57600 => libc::B57600,
+                115200 => libc::B115200,
+                230400 => libc::B230400,
+                _ => return Err(format!("unsupported baud rate {baud_rate}")),
+            };
+            let name = CString::new(path).map_err(|_| "invalid device path".to_string())?;
+            let fd = unsafe { libc::open(name.as_ptr(), libc::O_RDWR | libc::O_NOCTTY) };
+            if fd < 0 {
+                return Err(io::Error::last_os_error().to_string());
+            }
+            let port = Self { fd };
+            let mut tty: libc::termios = unsafe { std::mem::zeroed() };
+            if unsafe { libc::tcgetattr(fd, &mut tty) } < 0 {
+                return Err(io::Error::last_os_error().to_string());
+            }
+            unsafe { libc::cfmakeraw(&mut tty) };
+            tty.c_cflag |= libc::CLOCAL | libc::CREAD;
+            tty.c_cflag &= !libc::CSTOPB;
+            tty.c_cc[libc::VMIN] = 0;
+            // VTIME counts tenths of a second
+            tty.c_cc[libc::VTIME] = (timeout.as_millis() / 100).clamp(1, 255) as u8;
+            let res = unsafe {
+                libc::cfsetispeed(&mut tty, speed);
+                libc::cfsetospeed(&mut tty, speed);
+                libc::tcsetattr(fd, libc::TCSANOW, &tty)
+            };
+            if res < 0 {
+                return Err(io::Error::last_os_error().to_string());
+            }
+            Ok(port)
+        }
+
+        /// Drops bytes left over from an earlier, timed out response.
+        fn discard_input(&self) {
+            unsafe { libc::tcflush(self.fd, libc::TCIFLUSH) };
+        }
+    }
+
+    impl Read for SerialPort {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let read = unsafe { libc::read(self.fd, buf.as_mut_ptr().cast(), buf.len()) };
+            match read {
+                n if n < 0 => Err(io::Error::last_os_error()),
+                0 => Err(io::Error::new(io::ErrorKind::TimedOut, "no response")),
+                n => Ok(n as usize),
+            }
+        }
+    }
+
+    impl Write for SerialPort {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let written = unsafe { libc::write(self.fd, buf.as_ptr().cast(), buf.len()) };
+            if written < 0 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(written as usize)
+            }
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Drop for SerialPort {
+        fn drop(&mut self) {
+            unsafe { libc::close(self.fd) };
+        }
+    }
+
+    enum Transport {
+        Tcp { stream: TcpStream, transaction: u16 },
+        Rtu(SerialPort),
+    }
+
+    /// Modbus master talking to one unit, one request at a time.
+    pub struct Client {
+        transport: Transport,
+        unit_id: u8,
+    }
+
+    impl Client {
+        pub fn tcp(address: &str, unit_id: u8, timeout: Duration) -> Result<Self, String> {
+            let addr = address
+                .to_socket_addrs()
+                .map_err(|err| err.to_string())?
+                .next()
+                .ok_or_else(|| "address did not resolve".to_string())?;
+            let stream = TcpStream::connect_timeout(&addr, timeout).map_err(|err| err.to_string())?;
+            stream
+                .set_read_timeout(Some(timeout))
+                .and_then(|_| stream.set_write_timeout(Some(timeout)))
+                .and_then(|_| stream.set_nodelay(true))
+                .map_err(|err| err.to_string())?;
+            Ok(Self {
+                transport: Transport::Tcp {
+                    stream,
+                    transaction: 0,
+                },
+                unit_id,
+            })
+        }
+
+        pub fn rtu(device: &str, baud_rate: u32, unit_id: u8, timeout: Duration) -> Result<Self, String> {
+            Ok(Self {
+                transport: Transport::Rtu(SerialPort::open(device, baud_rate, timeout)?),
+                unit_id,
+            })
+        }
+
+        /// Reads one coil, discrete input or register.
+        pub fn read(&mut self, table: Table, address: u16) -> Result<u16, Error> {
+            let function = match table {
+                Table::Coil => 0x01,
+                Table::Discrete => 0x02,
+                Table::Holding => 0x03,
+                Table::Input => 0x04,
+            };
+            let [hi, lo] = address.to_be_bytes();
+            let response = self.transact(&[function, hi, lo, 0, 1])?;
+            let data = response.get(2..).unwrap_or_default();
+            let count = usize::from(response.get(1).copied().unwrap_or(0));
+            match table {
+                Table::Coil | Table::Discrete if count >= 1 && data.len() >= count => {
+                    Ok(u16::from(data[0] & 1))
+                }
+                Table::Holding | Table::Input if count >= 2 && data.len() >= count => {
+                    Ok(u16::from_be_bytes([data[0], data[1]]))
+                }
+                _ => Err(Error::Io("short response".to_string())),
+            }
+        }
+
+        /// Writes one coil (any non-zero `raw` turns it on) or holding register.
+        pub fn write(&mut self, table: Table, address: u16, raw: u16) -> Result<(), Error> {
+            let (function, value) = match table {
+                Table::Coil => (0x05, if raw != 0 { 0xFF00 } else { 0x0000 }),
+                _ => (0x06, raw),
+            };
+            let [hi, lo] = address.to_be_bytes();
+            let [value_hi, value_lo] = u16::to_be_bytes(value);
+            self.transact(&[function, hi, lo, value_hi, value_lo])?;
+            Ok(())
+        }
+
+        /// Sends `pdu` and returns the response PDU, function code first.
+        fn transact(&mut self, pdu: &[u8]) -> Result<Vec<u8>, Error> {
+            let response = match &mut self.transport {
+                Transport::Tcp {
+                    stream,
+                    transaction,
+                } => {
+                    *transaction = transaction.wrapping_add(1);
+                    tcp_transact(stream, *transaction, self.unit_id, pdu)
+                }
+                Transport::Rtu(port) => rtu_transact(port, self.unit_id, pdu),
+            }
+            .map_err(|err| Error::Io(err.to_string()))?;
+            match response.first() {
+                Some(&function) if function == pdu[0] | 0x80 => {
+                    Err(Error::Exception)
+                }
+                Some(&function) if function == pdu[0] => Ok(response),
+                _ => Err(Error::Io("unexpected function code in response".to_string())),
+            }
+        }
+    }
+
+    fn tcp_transact(stream: &mut TcpStream, transaction: u16, unit_id: u8, pdu: &[u8]) -> io::Result<Vec<u8>> {
+        let mut frame = Vec::with_capacity(7 + pdu.len());
+        frame.extend_from_slice(&transaction.to_be_bytes());
+        frame.extend_from_slice(&[0, 0]);
+        frame.extend_from_slice(&(pdu.len() as u16 + 1).to_be_bytes());
+        frame.push(unit_id);
+        frame.extend_from_slice(pdu);
+        stream.write_all(&frame)?;
+
+        let mut header = [0u8; 7];
+        stream.read_exact(&mut header)?;
+        if header[..2] != transaction.to_be_bytes() || header[2..4] != [0, 0] {
+            return Err(invalid("mismatched MBAP header"));
+        }
+        let length = usize::from(u16::from_be_bytes([header[4], header[5]]));
+        if !(2..=254).contains(&length) {
+            return Err(invalid("invalid MBAP length"));
+        }
+        let mut response = vec![0u8; length - 1];
+        stream.read_exact(&mut response)?;
+        Ok(response)
+    }
+
+    fn rtu_transact(port: &mut SerialPort, unit_id: u8, pdu: &[u8]) -> io::Result<Vec<u8>> {
+        let mut frame = Vec::with_capacity(3 + pdu.len());
+        frame.push(unit_id);
+        frame.extend_from_slice(pdu);
+        frame.extend_from_slice(&crc16(&frame).to_le_bytes());
+        port.discard_input();
+        port.write_all(&frame)?;
+
+        // RTU frames carry no length, so it follows from the function code
+        let mut response = vec![0u8; 2];
+        port.read_exact(&mut response)?;
+        let function = response[1];
+        let remaining = if function & 0x80 != 0 {
+            1
+        } else {
+            match function {
+                0x01..=0x04 => {
+                    let mut count = [0u8; 1];
+                    port.read_exact(&mut count)?;
+                    response.push(count[0]);
+                    usize::from(count[0])
+                }
+                0x05 | 0x06 => 4,
+                _ => return Err(invalid("unexpected function code in response")),
+            }
+        };
+        let start = response.len();
+        response.resize(start + remaining + 2, 0);
+        port.read_exact(&mut response[start..])?;
+        let (body, crc) = response.split_at(response.len() - 2);
+        if crc16(body).to_le_bytes() != crc {
+            return Err(invalid("CRC mismatch"));
+        }
+        if body[0] != unit_id {
+            return Err(invalid("response from another unit"));
+        }
+        Ok(body[1..].to_vec())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Modbus TCP to `address`.
+    #[default]
+    Tcp,
+    /// Modbus RTU over the serial `device`.
+    Rtu,
+}
+
+impl Mode {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "rtu" => Mode::Rtu,
+            _ => Mode::Tcp,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mode::Tcp => "tcp",
+            Mode::Rtu => "rtu",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Table {
+    /// Read/write single bits.
+    Coil,
+    /// Read-only single bits.
+    Discrete,
+    /// Read/write 16-bit registers.
+    Holding,
+    /// Read-only 16-bit registers.
+    Input,
+}
+
+impl Table {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "coil" => Some(Table::Coil),
+            "discrete" => Some(Table::Discrete),
+            "holding" => Some(Table::Holding),
+            "input" => Some(Table::Input),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Table::Coil => "coil",
+            Table::Discrete => "discrete",
+            Table::Holding => "holding",
+            Table::Input => "input",
+        }
+    }
+
+    pub fn is_writable(&self) -> bool {
+        matches!(self, Table::Coil | Table::Holding)
+    }
+
+    fn max_raw(&self) -> u16 {
+        match self {
+            Table::Coil | Table::Discrete => 1,
+            Table::Holding | Table::Input => u16::MAX,
+        }
+    }
+}
+
+/// One port mapped to a coil or register: unsigned raw value,
+/// `physical = raw * scale + offset`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegisterMapping {
+    pub name: String,
+    pub table: Table,
+    pub address: u16,
+    pub scale: f64,
+    pub offset: f64,
+}
+
+impl RegisterMapping {
+    /// Parses `name:table:address[:scale[:offset]]`, where `table` is one of
+    /// [`TABLES`] and `address` is the zero-based register number, decimal
+    /// or `0x` hex, e.g. `setpoint:holding:0x10:0.1`.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = spec.split(':').map(str::trim).collect();
+        if parts.len() < 3 || parts.len() > 5 {
+            return Err(format!("expected name:table:address[:scale[:offset]], got '{spec}'"));
+        }
+        let name = parts[0].to_string();
+        if name.is_empty() {
+            return Err("mapping name is empty".to_string());
+        }
+        let table =
+            Table::from_name(parts[1]).ok_or_else(|| format!("unknown register table '{}'", parts[1]))?;
+        let address = match parts[2].strip_prefix("0x").or_else(|| parts[2].strip_prefix("0X")) {
+            Some(hex) => u16::from_str_radix(hex, 16),
+            None => parts[2].parse(),
+        }
+        .map_err(|_| format!("invalid register address '{}'", parts[2]))?;
+        let scale = match parts.get(3) {
+            Some(v) => v.parse().map_err(|_| format!("invalid scale '{v}'"))?,
+            None => 1.0,
+        };
+        if scale == 0.0 {
+            return Err(format!("mapping '{name}' has a zero scale"));
+        }
+        let offset = match parts.get(4) {
+            Some(v) => v.parse().map_err(|_| format!("invalid offset '{v}'"))?,
+            None => 0.0,
+        };
+        Ok(Self {
+            name,
+            table,
+            address,
+            scale,
+            offset,
+        })
+    }
+
+    /// Raw value for `value`, saturating at the table's range (0..=1 for
+    /// coils).
+    pub fn encode(&self, value: f64) -> u16 {
+        let raw = ((value - self.offset) / self.scale).round();
+        if raw.is_nan() || raw <= 0.0 {
+            0
+        } else {
+            raw.min(f64::from(self.table.max_raw())) as u16
+        }
+    }
+
+    pub fn decode(&self, raw: u16) -> f64 {
+        f64::from(raw) * self.scale + self.offset
+    }
+}
+
+/// Invalid entries are skipped, as are write mappings on read-only tables.
+fn parse_mappings(specs: &[String], writes: bool) -> Vec<RegisterMapping> {
+    specs
+        .iter()
+        .filter(|spec| !spec.trim().is_empty())
+        .filter_map(|spec| RegisterMapping::parse(spec).ok())
+        .filter(|mapping| !writes || mapping.table.is_writable())
+        .collect()
+}
+
+/// Parses every non-empty spec, failing on the first invalid one, or on a
+/// write to a read-only table, instead of skipping it.
+fn check_mappings(specs: &[String], writes: bool) -> Result<usize, String> {
+    let direction = if writes { "write" } else { "read" };
+    let mut count = 0;
+    for spec in specs.iter().filter(|spec| !spec.trim().is_empty()) {
+        let mapping = RegisterMapping::parse(spec)
+            .map_err(|err| format!("{direction} mapping '{spec}': {err}"))?;
+        if writes && !mapping.table.is_writable() {
+            return Err(format!(
+                "write mapping '{spec}': the {} table is read-only",
+                mapping.table.name()
+            ));
+        }
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionConfig {
+    pub mode: Mode,
+    /// `host:port` of a Modbus TCP server.
+    pub address: String,
+    /// Serial device for RTU.
+    pub device: String,
+    pub baud_rate: u32,
+    pub unit_id: u8,
+    /// Applies to connecting and to every request.
+    pub timeout_ms: u64,
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        Self {
+            mode: Mode::Tcp,
+            address: "127.0.0.1:502".to_string(),
+            device: "/dev/ttyUSB0".to_string(),
+            baud_rate: 9600,
+            unit_id: 1,
+            timeout_ms: 100,
+        }
+    }
+}
+
+impl ConnectionConfig {
+    /// The address or device the connection goes to, for error messages.
+    pub fn endpoint(&self) -> &str {
+        match self.mode {
+            Mode::Tcp => &self.address,
+            Mode::Rtu => &self.device,
+        }
+    }
+}
+
+/// Per-channel outcome of a one-shot device test, in mapping order.
+pub type ChannelResults = Vec<(String, Result<f64, String>)>;
+
+pub struct ModbusIoPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    connection: ConnectionConfig,
+    poll_ms: u64,
+    write_specs: Vec<String>,
+    read_specs: Vec<String>,
+    writes: Vec<RegisterMapping>,
+    reads: Vec<RegisterMapping>,
+    input_values: HashMap<String, f64>,
+    output_values: HashMap<String, f64>,
+    safe_value: f64,
+    /// Time since the last bus cycle.
+    since_poll: f64,
+    client: Option<modbus::Client>,
+    open_error: Option<String>,
+}
+
+impl ModbusIoPlugin {
+    pub fn new(id: u64) -> Self {
+        let connection = ConnectionConfig::default();
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Modbus I/O Device Driver".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("mode".to_string(), Value::from(connection.mode.name())),
+                    ("address".to_string(), Value::from(connection.address.clone())),
+                    ("device".to_string(), Value::from(connection.device.clone())),
+                    ("baud_rate".to_string(), Value::from(connection.baud_rate)),
+                    ("unit_id".to_string(), Value::from(connection.unit_id)),
+                    ("timeout_ms".to_string(), Value::from(connection.timeout_ms)),
+                    ("poll_ms".to_string(), Value::from(100)),
+                    ("writes".to_string(), Value::Array(Vec::new())),
+                    ("reads".to_string(), Value::Array(Vec::new())),
+                    ("safe_value".to_string(), Value::from(0.0)),
+                ],
+            },
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            connection,
+            poll_ms: 100,
+            write_specs: Vec::new(),
+            read_specs: Vec::new(),
+            writes: Vec::new(),
+            reads: Vec::new(),
+            input_values: HashMap::new(),
+            output_values: HashMap::new(),
+            safe_value: 0.0,
+            since_poll: 0.0,
+            client: None,
+            open_error: None,
+        }
+    }
+
+    /// Applies the connection, bus cycle interval and register mappings.
+    /// Invalid mapping entries are skipped; changing the connection closes
+    /// it so it is reopened with the new settings.
+    pub fn set_config(
+        &mut self,
+        connection: ConnectionConfig,
+        poll_ms: u64,
+        write_specs: &[String],
+        read_specs: &[String],
+    ) {
+        if self.connection != connection {
+            self.connection = connection;
+            self.client = None;
+            self.open_error = None;
+        }
+        self.poll_ms = poll_ms;
+        if self.write_specs != write_specs || self.read_specs != read_specs {
+            self.write_specs = write_specs.to_vec();
+            self.read_specs = read_specs.to_vec();
+            self.writes = parse_mappings(write_specs, true);
+            self.reads = parse_mappings(read_specs, false);
+            self.update_ports();
+        }
+    }
+
+    /// Checks the configuration and that the device answers a connection, so
+    /// a driver that cannot run is not started. Invalid register specs,
+    /// which [`Self::set_config`] skips, are reported here.
+    pub fn check_start(&self) -> Result<(), String> {
+        let connection = &self.connection;
+        if connection.endpoint().trim().is_empty() {
+            return Err(match connection.mode {
+                Mode::Tcp => "no server address is set".to_string(),
+                Mode::Rtu => "no serial device is set".to_string(),
+            });
+        }
+        let mapped = check_mappings(&self.write_specs, true)? + check_mappings(&self.read_specs, false)?;
+        if mapped == 0 {
+            return Err("no write or read registers are mapped".to_string());
+        }
+        self.connect()
+            .map(drop)
+            .map_err(|err| format!("cannot connect to {}: {err}", connection.endpoint()))
+    }
+
+    /// Connects, writes the safe value to every write mapping when
+    /// `write_safe` is set, reads every read mapping once and disconnects.
+    /// A diagnostic that bypasses the graph: each register gets its value or
+    /// its own error, only a failed connection fails the whole test.
+    pub fn test_channels(&self, write_safe: bool) -> Result<ChannelResults, String> {
+        let mut client = self
+            .connect()
+            .map_err(|err| format!("cannot connect to {}: {err}", self.connection.endpoint()))?;
+        let message = |err: modbus::Error| match err {
+            modbus::Error::Io(err) => err,
+            modbus::Error::Exception => "the device answered with an exception".to_string(),
+        };
+        let mut results = Vec::new();
+        if write_safe {
+            for mapping in &self.writes {
+                let result = client
+                    .write(mapping.table, mapping.address, mapping.encode(self.safe_value))
+                    .map(|()| self.safe_value)
+                    .map_err(message);
+                results.push((format!("{} (write)", mapping.name), result));
+            }
+        }
+        for mapping in &self.reads {
+            let result = client
+                .read(mapping.table, mapping.address)
+                .map(|raw| mapping.decode(raw))
+                .map_err(message);
+            results.push((mapping.name.clone(), result));
+        }
+        Ok(results)
+    }
+
+    fn connect(&self) -> Result<modbus::Client, String> {
+        let connection = &self.connection;
+        let timeout = std::time::Duration::from_millis(connection.timeout_ms.max(1));
+        match connection.mode {
+            Mode::Tcp => modbus::Client::tcp(&connection.address, connection.unit_id, timeout),
+            Mode::Rtu => modbus::Client::rtu(
+                &connection.device,
+                connection.baud_rate,
+                connection.unit_id,
+                timeout,
+            ),
+        }
+    }
+
+    pub fn connection(&self) -> &ConnectionConfig {
+        &self.connection
+    }
+
+    fn update_ports(&mut self) {
+        self.inputs = self
+            .writes
+            .iter()
+            .map(|m| Port { id: PortId(m.name.clone()) })
+            .collect();
+        self.outputs = self
+            .reads
+            .iter()
+            .map(|m| Port { id: PortId(m.name.clone()) })
+            .collect();
+        self.input_values
+            .retain(|name, _| self.writes.iter().any(|m| &m.name == name));
+        self.output_values
+            .retain(|name, _| self.reads.iter().any(|m| &m.name == name));
+    }
+
+    pub fn input_port_names(&self) -> Vec<String> {
+        self.writes.iter().map(|m| m.name.clone()).collect()
+    }
+
+    pub fn output_port_names(&self) -> Vec<String> {
+        self.reads.iter().map(|m| m.name.clone()).collect()
+    }
+
+    pub fn set_input(&mut self, port_name: &str, value: f64) {
+        self.input_values.insert(port_name.to_string(), value);
+    }
+
+    pub fn get_output(&self, port_name: &str) -> f64 {
+        self.output_values.get(port_name).copied().unwrap_or(0.0)
+    }
+
+    /// Value every write mapping is commanded to in the safe state.
+    pub fn set_safe_value(&mut self, safe_value: f64) {
+        self.safe_value = safe_value;
+    }
+
+    /// Overrides every write mapping with the safe value until the next
+    /// inputs are set; the runtime calls it each tick while an emergency stop
+    /// is asserted.
+    pub fn safe_state(&mut self) {
+        for mapping in &self.writes {
+            self.input_values.insert(mapping.name.clone(), self.safe_value);
+        }
+    }
+
+    /// Raw values the next bus cycle writes, in mapping order.
+    pub fn write_values(&self) -> Vec<(Table, u16, u16)> {
+        self.writes
+            .iter()
+            .map(|m| {
+                let value = self.input_values.get(&m.name).copied().unwrap_or(0.0);
+                (m.table, m.address, m.encode(value))
+            })
+            .collect()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.client.is_some()
+    }
+
+    /// False after a failed open or a lost connection until the connection
+    /// settings change, the driver is closed or [`Self::retry_open`] is
+    /// called, so an unreachable device is not retried every tick.
+    pub fn can_retry_open(&self) -> bool {
+        self.open_error.is_none()
+    }
+
+    /// Why the last open failed or the connection was dropped.
+    pub fn open_error(&self) -> Option<&str> {
+        self.open_error.as_deref()
+    }
+
+    /// Lets the runtime try opening the connection again.
+    pub fn retry_open(&mut self) {
+        self.open_error = None;
+    }
+
+    /// Writes every write mapping, then reads every read mapping. Exception
+    /// responses fail the cycle but keep the connection; link errors drop it.
+    fn poll(&mut self) -> Result<(), PluginError> {
+        let writes = self.write_values();
+        let Some(client) = self.client.as_mut() else {
+            return Ok(());
+        };
+        let mut result = Ok(());
+        let mut link_error = None;
+        for (table, address, raw) in writes {
+            match client.write(table, address, raw) {
+                Ok(()) => {}
+                Err(modbus::Error::Exception) => result = Err(PluginError::ProcessingFailed),
+                Err(modbus::Error::Io(err)) => {
+                    link_error = Some(err);
+                    break;
+                }
+            }
+        }
+        if link_error.is_none() {
+            for mapping in &self.reads {
+                match client.read(mapping.table, mapping.address) {
+                    Ok(raw) => {
+                        self.output_values
+                            .insert(mapping.name.clone(), mapping.decode(raw));
+                    }
+                    Err(modbus::Error::Exception) => result = Err(PluginError::ProcessingFailed),
+                    Err(modbus::Error::Io(err)) => {
+                        link_error = Some(err);
+                        break;
+                    }
+                }
+            }
+        }
+        if let Some(err) = link_error {
+            self.client = None;
+            self.open_error = Some(format!("{}: {err}", self.connection.endpoint()));
+            return Err(PluginError::ProcessingFailed);
+        }
+        result
+    }
+}
+
+impl Plugin for ModbusIoPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    /// Runs a bus cycle every `poll_ms` (every tick when 0), starting with
+    /// the first tick after the connection opens. Requests block the tick
+    /// for up to `timeout_ms` each.
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
+        if self.client.is_none() {
+            return Ok(());
+        }
+        self.since_poll += ctx.period_seconds.max(0.0);
+        if self.since_poll * 1000.0 < self.poll_ms as f64 {
+            return Ok(());
+        }
+        self.since_poll = 0.0;
+        self.poll()
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: false,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::text("mode", "Mode")
+                        .default_value(Value::from("tcp"))
+                        .hint("tcp or rtu"),
+                )
+                .field(
+                    ConfigField::text("address", "TCP address")
+                        .default_value(Value::from("127.0.0.1:502"))
+                        .hint("host:port, used in tcp mode"),
+                )
+                .field(
+                    ConfigField::text("device", "Serial device")
+                        .default_value(Value::from("/dev/ttyUSB0"))
+                        .hint("Used in rtu mode, 8N1"),
+                )
+                .field(
+                    ConfigField::integer("baud_rate", "Baud rate")
+                        .min(1200)
+                        .default_value(Value::from(9600)),
+                )
+                .field(
+                    ConfigField::integer("unit_id", "Unit id")
+                        .min(0)
+                        .max(247)
+                        .default_value(Value::from(1)),
+                )
+                .field(
+                    ConfigField::integer("timeout_ms", "Timeout (ms)")
+                        .min(1)
+                        .default_value(Value::from(100))
+                        .hint("Per request; a request blocks the tick until it is answered"),
+                )
+                .field(
+                    ConfigField::integer("poll_ms", "Bus cycle (ms)")
+                        .min(0)
+                        .default_value(Value::from(100))
+                        .hint("0 runs a bus cycle every tick"),
+                )
+                .field(
+                    ConfigField::dynamic_list("writes", "Writes")
+                        .hint("name:holding|coil:address[:scale[:offset]], one input per mapping"),
+                )
+                .field(
+                    ConfigField::dynamic_list("reads", "Reads")
+                        .hint("name:input|holding|coil|discrete:address[:scale[:offset]], one output per mapping"),
+                )
+                .field(
+                    ConfigField::float("safe_value", "Safe value")
+                        .default_value(Value::from(0.0))
+                        .hint("Written to every write mapping while an emergency stop is asserted"),
+                ),
+        )
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            inputs: self.input_port_names(),
+            outputs: self.output_port_names(),
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        let specs_value =
+            |specs: &[String]| Value::Array(specs.iter().cloned().map(Value::String).collect());
+        match name {
+            "mode" => Some(Value::from(self.connection.mode.name())),
+            "address" => Some(Value::String(self.connection.address.clone())),
+            "device" => Some(Value::String(self.connection.device.clone())),
+            "baud_rate" => Some(Value::from(self.connection.baud_rate)),
+            "unit_id" => Some(Value::from(self.connection.unit_id)),
+            "timeout_ms" => Some(Value::from(self.connection.timeout_ms)),
+            "poll_ms" => Some(Value::from(self.poll_ms)),
+            "writes" => Some(specs_value(&self.write_specs)),
+            "reads" => Some(specs_value(&self.read_specs)),
+            "safe_value" => Some(Value::from(self.safe_value)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        let specs = |value: &Value| -> Vec<String> {
+            value
+                .as_array()
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|v| v.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let mut connection = self.connection.clone();
+        let mut poll_ms = self.poll_ms;
+        let mut writes = self.write_specs.clone();
+        let mut reads = self.read_specs.clone();
+        match name {
+            "mode" => {
+                if let Some(v) = value.as_str() {
+                    connection.mode = Mode::from_name(v);
+                }
+            }
+            "address" => {
+                if let Some(v) = value.as_str() {
+                    connection.address = v.to_string();
+                }
+            }
+            "device" => {
+                if let Some(v) = value.as_str() {
+                    connection.device = v.to_string();
+                }
+            }
+            "baud_rate" => {
+                if let Some(v) = value.as_u64().and_then(|v| u32::try_from(v).ok()) {
+                    connection.baud_rate = v;
+                }
+            }
+            "unit_id" => {
+                if let Some(v) = value.as_u64().and_then(|v| u8::try_from(v).ok()) {
+                    connection.unit_id = v;
+                }
+            }
+            "timeout_ms" => connection.timeout_ms = value.as_u64().unwrap_or(connection.timeout_ms),
+            "poll_ms" => poll_ms = value.as_u64().unwrap_or(poll_ms),
+            "writes" => writes = specs(&value),
+            "reads" => reads = specs(&value),
+            "safe_value" => {
+                if let Some(v) = value.as_f64() {
+                    self.safe_value = v;
+                }
+                return Ok(());
+            }
+            _ => return Ok(()),
+        }
+        self.set_config(connection, poll_ms, &writes, &reads);
+        Ok(())
+    }
+}
+
+impl DeviceDriver for ModbusIoPlugin {
+    fn open(&mut self) -> Result<(), PluginError> {
+        match self.connect() {
+            Ok(client) => {
+                self.client = Some(client);
+                self.open_error = None;
+                self.since_poll = f64::INFINITY;
+                Ok(())
+            }
+            Err(err) => {
+                self.open_error = Some(format!("{}: {err}", self.connection.endpoint()));
+                Err(PluginError::ProcessingFailed)
+            }
+        }
+    }
+
+    fn close(&mut self) -> Result<(), PluginError> {
+        self.client = None;
+        self.open_error = None;
+        Ok(())
+    }
+}
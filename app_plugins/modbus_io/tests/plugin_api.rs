@@ -0,0 +1,203 @@
+use modbus_io_plugin::{crc16, ConnectionConfig, Mode, ModbusIoPlugin, RegisterMapping, Table};
+use rtsyn_plugin::prelude::*;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+
+#[test]
+fn register_spec_parsing() {
+    let mapping = RegisterMapping::parse("setpoint:holding:0x10:0.1:-5").unwrap();
+    assert_eq!(mapping.name, "setpoint");
+    assert_eq!((mapping.table, mapping.address), (Table::Holding, 0x10));
+    assert_eq!((mapping.scale, mapping.offset), (0.1, -5.0));
+
+    let defaults = RegisterMapping::parse("run:coil:3").unwrap();
+    assert_eq!((defaults.table, defaults.address), (Table::Coil, 3));
+    assert_eq!((defaults.scale, defaults.offset), (1.0, 0.0));
+
+    assert!(RegisterMapping::parse("bad:holding").is_err());
+    assert!(RegisterMapping::parse("bad:eeprom:0").is_err());
+    assert!(RegisterMapping::parse("bad:input:70000").is_err());
+    assert!(RegisterMapping::parse("bad:input:0:0").is_err());
+    assert!(RegisterMapping::parse(":input:0").is_err());
+}
+
+#[test]
+fn encode_saturates_at_the_table_range() {
+    let register = RegisterMapping::parse("t:holding:0:0.5:-40").unwrap();
+    assert_eq!(register.encode(25.0), 130);
+    assert_eq!(register.decode(130), 25.0);
+    assert_eq!(register.encode(1.0e9), u16::MAX);
+    assert_eq!(register.encode(-1.0e9), 0);
+    assert_eq!(register.encode(f64::NAN), 0);
+
+    let coil = RegisterMapping::parse("run:coil:0").unwrap();
+    assert_eq!(coil.encode(0.2), 0);
+    assert_eq!(coil.encode(7.0), 1);
+}
+
+#[test]
+fn rtu_crc_matches_reference_frame() {
+    let crc = crc16(&[0x01, 0x03, 0x00, 0x00, 0x00, 0x0A]);
+    assert_eq!(crc.to_le_bytes(), [0xC5, 0xCD]);
+}
+
+#[test]
+fn ports_follow_mappings_and_safe_state_commands_every_write() {
+    let mut plugin = ModbusIoPlugin::new(1);
+    plugin.set_config(
+        ConnectionConfig::default(),
+        100,
+        &[
+            "a:holding:0".to_string(),
+            "b:coil:1".to_string(),
+            "ro:input:2".to_string(),
+            "not a mapping".to_string(),
+        ],
+        &["pv:input:0:0.1".to_string()],
+    );
+    assert_eq!(plugin.input_port_names(), vec!["a", "b"]);
+    assert_eq!(plugin.output_port_names(), vec!["pv"]);
+    assert_eq!(plugin.outputs().len(), 1);
+
+    plugin.set_input("a", 300.0);
+    plugin.set_input("b", 1.0);
+    assert_eq!(
+        plugin.write_values(),
+        vec![(Table::Holding, 0, 300), (Table::Coil, 1, 1)]
+    );
+    plugin.set_safe_value(0.0);
+    plugin.safe_state();
+    assert_eq!(
+        plugin.write_values(),
+        vec![(Table::Holding, 0, 0), (Table::Coil, 1, 0)]
+    );
+
+    // Without an open connection processing is a no-op
+    let mut ctx = PluginContext::default();
+    assert!(plugin.process(&mut ctx).is_ok());
+    assert!(!plugin.is_open());
+}
+
+/// Answers `requests` Modbus TCP requests: function 06 is echoed and
+/// reported on `written`, function 04 returns 1234 from address 7 and an
+/// illegal address exception elsewhere. Then the connection is closed.
+fn fake_server(requests: usize) -> (String, mpsc::Receiver<(u16, u16)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let (tx, written) = mpsc::channel();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        for _ in 0..requests {
+            let mut header = [0u8; 7];
+            if stream.read_exact(&mut header).is_err() {
+                return;
+            }
+            let length = usize::from(u16::from_be_bytes([header[4], header[5]]));
+            let mut pdu = vec![0u8; length - 1];
+            stream.read_exact(&mut pdu).unwrap();
+            let address = u16::from_be_bytes([pdu[1], pdu[2]]);
+            let response = match pdu[0] {
+                0x06 => {
+                    let _ = tx.send((address, u16::from_be_bytes([pdu[3], pdu[4]])));
+                    pdu.clone()
+                }
+                0x04 if address == 7 => vec![0x04, 2, 0x04, 0xD2],
+                function => vec![function | 0x80, 0x02],
+            };
+            let mut frame = header[..4].to_vec();
+            frame.extend_from_slice(&(response.len() as u16 + 1).to_be_bytes());
+            frame.push(header[6]);
+            frame.extend_from_slice(&response);
+            stream.write_all(&frame).unwrap();
+        }
+    });
+    (address, written)
+}
+
+#[test]
+fn tcp_cycle_writes_and_reads_registers() {
+    let (address, written) = fake_server(3);
+    let mut plugin = ModbusIoPlugin::new(1);
+    plugin.set_config(
+        ConnectionConfig {
+            address,
+            timeout_ms: 1000,
+            ..ConnectionConfig::default()
+        },
+        0,
+        &["sp:holding:5:0.1".to_string()],
+        &["pv:input:7:0.5:-10".to_string(), "missing:input:99".to_string()],
+    );
+    plugin.open().unwrap();
+    assert!(plugin.is_open());
+
+    plugin.set_input("sp", 25.0);
+    let mut ctx = PluginContext::default();
+    // The exception for the missing register fails the cycle but keeps the
+    // connection
+    assert!(plugin.process(&mut ctx).is_err());
+    assert!(plugin.is_open());
+    assert_eq!(written.recv().unwrap(), (5, 250));
+    assert_eq!(plugin.get_output("pv"), 1234.0 * 0.5 - 10.0);
+
+    // The server hangs up after three requests
+    assert!(plugin.process(&mut ctx).is_err());
+    assert!(!plugin.is_open());
+    assert!(!plugin.can_retry_open());
+    assert!(plugin.open_error().unwrap().starts_with("127.0.0.1:"));
+    assert_eq!(plugin.get_output("pv"), 1234.0 * 0.5 - 10.0);
+}
+
+#[test]
+fn test_channels_reports_every_register_separately() {
+    let (address, written) = fake_server(3);
+    let mut plugin = ModbusIoPlugin::new(1);
+    plugin.set_config(
+        ConnectionConfig {
+            address,
+            timeout_ms: 1000,
+            ..ConnectionConfig::default()
+        },
+        0,
+        &["sp:holding:5:0.1".to_string()],
+        &["pv:input:7:0.5:-10".to_string(), "missing:input:99".to_string()],
+    );
+    plugin.set_safe_value(3.0);
+
+    let results = plugin.test_channels(true).unwrap();
+    assert_eq!(written.recv().unwrap(), (5, 30));
+    assert_eq!(results[0], ("sp (write)".to_string(), Ok(3.0)));
+    assert_eq!(results[1], ("pv".to_string(), Ok(1234.0 * 0.5 - 10.0)));
+    assert_eq!(results[2].0, "missing");
+    assert!(results[2].1.is_err());
+    // The test does not leave the plugin open
+    assert!(!plugin.is_open());
+}
+
+#[test]
+fn failed_open_keeps_the_reason_until_retried() {
+    let mut plugin = ModbusIoPlugin::new(1);
+    plugin.set_config(
+        ConnectionConfig {
+            mode: Mode::Rtu,
+            device: "/dev/rtsyn_missing_tty".to_string(),
+            ..ConnectionConfig::default()
+        },
+        100,
+        &[],
+        &[],
+    );
+    assert!(plugin.can_retry_open());
+
+    assert!(plugin.open().is_err());
+    assert!(!plugin.can_retry_open());
+    assert!(plugin
+        .open_error()
+        .unwrap()
+        .starts_with("/dev/rtsyn_missing_tty: "));
+
+    plugin.retry_open();
+    assert!(plugin.can_retry_open());
+    assert_eq!(plugin.open_error(), None);
+}
@@ -0,0 +1,273 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+pub const DISTRIBUTIONS: [&str; 3] = ["uniform", "gaussian", "pink"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distribution {
+    Uniform,
+    Gaussian,
+    Pink,
+}
+
+impl Distribution {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "gaussian" => Distribution::Gaussian,
+            "pink" => Distribution::Pink,
+            _ => Distribution::Uniform,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Distribution::Uniform => "uniform",
+            Distribution::Gaussian => "gaussian",
+            Distribution::Pink => "pink",
+        }
+    }
+}
+
+/// xorshift64* generator; small, fast and reproducible for a given seed.
+struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // A zero state would get stuck at zero.
+        Self {
+            state: seed ^ 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Uniform sample in [0, 1).
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal sample (Box-Muller).
+    fn next_gaussian(&mut self) -> f64 {
+        let u1 = self.next_f64().max(f64::MIN_POSITIVE);
+        let u2 = self.next_f64();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+}
+
+pub struct NoiseGeneratorPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    distribution: Distribution,
+    amplitude: f64,
+    mean: f64,
+    seed: u64,
+    rng: Rng,
+    // Paul Kellet's pink noise filter state
+    pink: [f64; 7],
+    input: f64,
+    out: f64,
+}
+
+impl NoiseGeneratorPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Noise Generator".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("distribution".to_string(), Value::from("uniform")),
+                    ("amplitude".to_string(), Value::from(1.0)),
+                    ("mean".to_string(), Value::from(0.0)),
+                    ("seed".to_string(), Value::from(0)),
+                ],
+            },
+            inputs: vec![Port { id: PortId("in".to_string()) }],
+            outputs: vec![Port { id: PortId("out".to_string()) }],
+            distribution: Distribution::Uniform,
+            amplitude: 1.0,
+            mean: 0.0,
+            seed: 0,
+            rng: Rng::new(Self::effective_seed(0, id)),
+            pink: [0.0; 7],
+            input: 0.0,
+            out: 0.0,
+        }
+    }
+
+    /// A seed of 0 derives the sequence from the plugin id so that every
+    /// instance is different but still reproducible between runs.
+    fn effective_seed(seed: u64, id: u64) -> u64 {
+        if seed == 0 {
+            id.wrapping_add(1)
+        } else {
+            seed
+        }
+    }
+
+    pub fn set_config(&mut self, distribution: Distribution, amplitude: f64, mean: f64, seed: u64) {
+        if seed != self.seed {
+            self.seed = seed;
+            self.reset();
+        }
+        if distribution != self.distribution {
+            self.distribution = distribution;
+            self.pink = [0.0; 7];
+        }
+        self.amplitude = amplitude;
+        self.mean = mean;
+    }
+
+    pub fn reset(&mut self) {
+        self.rng = Rng::new(Self::effective_seed(self.seed, self.id.0));
+        self.pink = [0.0; 7];
+        self.out = 0.0;
+    }
+
+    pub fn set_input(&mut self, value: f64) {
+        self.input = value;
+    }
+
+    pub fn out(&self) -> f64 {
+        self.out
+    }
+
+    fn sample(&mut self) -> f64 {
+        match self.distribution {
+            Distribution::Uniform => (self.rng.next_f64() * 2.0 - 1.0) * self.amplitude,
+            Distribution::Gaussian => self.rng.next_gaussian() * self.amplitude,
+            Distribution::Pink => {
+                let white = self.rng.next_gaussian();
+                let b = &mut self.pink;
+                b[0] = 0.99886 * b[0] + white * 0.0555179;
+                b[1] = 0.99332 * b[1] + white * 0.0750759;
+                b[2] = 0.96900 * b[2] + white * 0.1538520;
+                b[3] = 0.86650 * b[3] + white * 0.3104856;
+                b[4] = 0.55000 * b[4] + white * 0.5329522;
+                b[5] = -0.7616 * b[5] - white * 0.0168980;
+                let pink = b[0] + b[1] + b[2] + b[3] + b[4] + b[5] + b[6] + white * 0.5362;
+                b[6] = white * 0.115926;
+                // The filter has a gain of roughly 3.5 on unit-variance white noise.
+                pink * 0.11 * self.amplitude
+            }
+        }
+    }
+}
+
+impl Plugin for NoiseGeneratorPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        self.out = self.input + self.mean + self.sample();
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::text("distribution", "Distribution")
+                        .default_value(Value::from("uniform"))
+                        .hint("uniform, gaussian or pink"),
+                )
+                .field(
+                    ConfigField::float("amplitude", "Amplitude")
+                        .min_f(0.0)
+                        .step_f(0.1)
+                        .default_value(Value::from(1.0))
+                        .hint("Half range (uniform) or standard deviation (gaussian, pink)"),
+                )
+                .field(
+                    ConfigField::float("mean", "Mean")
+                        .step_f(0.1)
+                        .default_value(Value::from(0.0)),
+                )
+                .field(
+                    ConfigField::integer("seed", "Seed")
+                        .min(0)
+                        .default_value(Value::from(0))
+                        .hint("0 derives the seed from the plugin id"),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: false,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["out".to_string()],
+            inputs: vec!["in".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "distribution" => Some(Value::from(self.distribution.name())),
+            "amplitude" => Some(Value::from(self.amplitude)),
+            "mean" => Some(Value::from(self.mean)),
+            "seed" => Some(Value::from(self.seed)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        match name {
+            "distribution" => {
+                if let Some(v) = value.as_str() {
+                    let distribution = Distribution::from_name(v);
+                    self.set_config(distribution, self.amplitude, self.mean, self.seed);
+                }
+            }
+            "amplitude" => {
+                if let Some(v) = value.as_f64() {
+                    self.amplitude = v;
+                }
+            }
+            "mean" => {
+                if let Some(v) = value.as_f64() {
+                    self.mean = v;
+                }
+            }
+            "seed" => {
+                if let Some(v) = value.as_u64() {
+                    self.set_config(self.distribution, self.amplitude, self.mean, v);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
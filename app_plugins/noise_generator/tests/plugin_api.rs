@@ -0,0 +1,49 @@
+use noise_generator_plugin::{Distribution, NoiseGeneratorPlugin};
+use rtsyn_plugin::prelude::*;
+
+fn samples(plugin: &mut NoiseGeneratorPlugin, count: usize) -> Vec<f64> {
+    let mut ctx = PluginContext::default();
+    (0..count)
+        .map(|_| {
+            plugin.process(&mut ctx).unwrap();
+            plugin.out()
+        })
+        .collect()
+}
+
+#[test]
+fn noise_generator_is_reproducible_for_a_seed() {
+    let mut a = NoiseGeneratorPlugin::new(1);
+    let mut b = NoiseGeneratorPlugin::new(2);
+    a.set_config(Distribution::Gaussian, 1.0, 0.0, 42);
+    b.set_config(Distribution::Gaussian, 1.0, 0.0, 42);
+    assert_eq!(samples(&mut a, 100), samples(&mut b, 100));
+}
+
+#[test]
+fn noise_generator_uniform_stays_in_range() {
+    let mut plugin = NoiseGeneratorPlugin::new(1);
+    plugin.set_config(Distribution::Uniform, 2.0, 10.0, 7);
+    for value in samples(&mut plugin, 1000) {
+        assert!((8.0..12.0).contains(&value), "{value} out of range");
+    }
+}
+
+#[test]
+fn noise_generator_gaussian_statistics() {
+    let mut plugin = NoiseGeneratorPlugin::new(1);
+    plugin.set_config(Distribution::Gaussian, 0.5, 3.0, 11);
+    let values = samples(&mut plugin, 20_000);
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+    assert!((mean - 3.0).abs() < 0.02, "mean {mean}");
+    assert!((var.sqrt() - 0.5).abs() < 0.02, "std {}", var.sqrt());
+}
+
+#[test]
+fn noise_generator_adds_noise_to_input() {
+    let mut plugin = NoiseGeneratorPlugin::new(1);
+    plugin.set_config(Distribution::Pink, 0.0, 0.0, 3);
+    plugin.set_input(4.5);
+    assert_eq!(samples(&mut plugin, 1), vec![4.5]);
+}
@@ -0,0 +1,158 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+/// Reset level; a rising edge is a crossing from at-or-below to above it.
+const RESET_THRESHOLD: f64 = 0.5;
+
+pub struct PeakHoldPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    decay_rate: f64,
+    input: f64,
+    reset: f64,
+    prev_reset: f64,
+    /// False until the first sample, which seeds both holds.
+    holding: bool,
+    max_hold: f64,
+    min_hold: f64,
+}
+
+impl PeakHoldPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Peak Hold".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![("decay_rate".to_string(), Value::from(0.0))],
+            },
+            inputs: vec![
+                Port { id: PortId("in".to_string()) },
+                Port { id: PortId("reset".to_string()) },
+            ],
+            outputs: vec![
+                Port { id: PortId("max_hold".to_string()) },
+                Port { id: PortId("min_hold".to_string()) },
+            ],
+            decay_rate: 0.0,
+            input: 0.0,
+            reset: 0.0,
+            prev_reset: 0.0,
+            holding: false,
+            max_hold: 0.0,
+            min_hold: 0.0,
+        }
+    }
+
+    /// `decay_rate` in units per second; 0 holds forever.
+    pub fn set_config(&mut self, decay_rate: f64) {
+        self.decay_rate = decay_rate.max(0.0);
+    }
+
+    pub fn set_inputs(&mut self, input: f64, reset: f64) {
+        self.input = input;
+        self.reset = reset;
+    }
+
+    /// Forgets the holds; the next sample seeds them again.
+    pub fn reset(&mut self) {
+        self.holding = false;
+        self.prev_reset = 0.0;
+        self.max_hold = 0.0;
+        self.min_hold = 0.0;
+    }
+
+    pub fn max_hold(&self) -> f64 {
+        self.max_hold
+    }
+
+    pub fn min_hold(&self) -> f64 {
+        self.min_hold
+    }
+}
+
+impl Plugin for PeakHoldPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
+        let rising = self.prev_reset <= RESET_THRESHOLD && self.reset > RESET_THRESHOLD;
+        self.prev_reset = self.reset;
+        if !self.input.is_finite() {
+            return Ok(());
+        }
+        if rising || !self.holding {
+            self.max_hold = self.input;
+            self.min_hold = self.input;
+            self.holding = true;
+            return Ok(());
+        }
+
+        // Decay toward the input, never past it
+        let time_step = ctx.period_seconds.max(0.0);
+        let decay = self.decay_rate * time_step;
+        self.max_hold = (self.max_hold - decay).max(self.input);
+        self.min_hold = (self.min_hold + decay).min(self.input);
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new().field(
+                ConfigField::float("decay_rate", "Decay rate (units/s)")
+                    .min_f(0.0)
+                    .step_f(0.1)
+                    .default_value(Value::from(0.0))
+                    .hint("0 holds the extremes until reset"),
+            ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["max_hold".to_string(), "min_hold".to_string()],
+            inputs: vec!["in".to_string(), "reset".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "decay_rate" => Some(Value::from(self.decay_rate)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        if name == "decay_rate" {
+            if let Some(v) = value.as_f64() {
+                self.set_config(v);
+            }
+        }
+        Ok(())
+    }
+}
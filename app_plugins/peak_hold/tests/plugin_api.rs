@@ -0,0 +1,58 @@
+use peak_hold_plugin::PeakHoldPlugin;
+use rtsyn_plugin::prelude::*;
+
+fn step(plugin: &mut PeakHoldPlugin, input: f64, reset: f64) -> (f64, f64) {
+    let mut ctx = PluginContext {
+        period_seconds: 0.1,
+        ..PluginContext::default()
+    };
+    plugin.set_inputs(input, reset);
+    plugin.process(&mut ctx).unwrap();
+    (plugin.max_hold(), plugin.min_hold())
+}
+
+#[test]
+fn infinite_hold_keeps_the_extremes() {
+    let mut plugin = PeakHoldPlugin::new(1);
+    plugin.set_config(0.0);
+
+    assert_eq!(step(&mut plugin, 1.0, 0.0), (1.0, 1.0));
+    assert_eq!(step(&mut plugin, 5.0, 0.0), (5.0, 1.0));
+    assert_eq!(step(&mut plugin, -3.0, 0.0), (5.0, -3.0));
+    assert_eq!(step(&mut plugin, 0.0, 0.0), (5.0, -3.0));
+}
+
+#[test]
+fn holds_decay_toward_the_input() {
+    let mut plugin = PeakHoldPlugin::new(1);
+    // 10 units/s at 0.1 s per tick: 1 unit per tick
+    plugin.set_config(10.0);
+
+    step(&mut plugin, 0.0, 0.0);
+    // The min hold rises toward the input at the same rate
+    assert_eq!(step(&mut plugin, 4.0, 0.0), (4.0, 1.0));
+    let (max, min) = step(&mut plugin, 0.0, 0.0);
+    assert!((max - 3.0).abs() < 1e-12);
+    assert_eq!(min, 0.0);
+    let (max, _) = step(&mut plugin, 2.5, 0.0);
+    assert_eq!(max, 2.5);
+
+    let (_, min) = step(&mut plugin, -2.0, 0.0);
+    assert_eq!(min, -2.0);
+    let (_, min) = step(&mut plugin, 2.5, 0.0);
+    assert!((min + 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn reset_edge_clears_the_holds_to_the_input() {
+    let mut plugin = PeakHoldPlugin::new(1);
+    step(&mut plugin, 8.0, 0.0);
+    step(&mut plugin, -8.0, 0.0);
+
+    assert_eq!(step(&mut plugin, 2.0, 1.0), (2.0, 2.0));
+    // Reset held high: holds track again
+    assert_eq!(step(&mut plugin, 3.0, 1.0), (3.0, 2.0));
+
+    plugin.reset();
+    assert_eq!(step(&mut plugin, -1.0, 0.0), (-1.0, -1.0));
+}
@@ -12,6 +12,8 @@ pub struct PerformanceMonitorPlugin {
     output_values: Vec<f64>,
     workspace_period_us: f64,
     period_history: Vec<f64>,
+    /// Ticks flagged as a realtime violation since the plugin was added.
+    overrun_count: u64,
 }
 
 impl PerformanceMonitorPlugin {
@@ -32,12 +34,15 @@ impl PerformanceMonitorPlugin {
                 Port { id: PortId("latency_us".to_string()) },
                 Port { id: PortId("jitter_us".to_string()) },
                 Port { id: PortId("realtime_violation".to_string()) },
+                Port { id: PortId("tick".to_string()) },
+                Port { id: PortId("overrun_count".to_string()) },
             ],
             last_trigger_time: None,
             max_latency_us: 1000.0,
-            output_values: vec![0.0; 4],
+            output_values: vec![0.0; 6],
             workspace_period_us: 1000.0,
             period_history: Vec::with_capacity(10),
+            overrun_count: 0,
         }
     }
 
@@ -72,7 +77,7 @@ impl Plugin for PerformanceMonitorPlugin {
         &self.outputs
     }
 
-    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
         let process_start = Instant::now();
         
         if let Some(last_time) = self.last_trigger_time {
@@ -104,12 +109,17 @@ impl Plugin for PerformanceMonitorPlugin {
             
             // Real-time violation if latency exceeds threshold
             let violation = if latency_us > self.max_latency_us { 1.0 } else { 0.0 };
+            if violation > 0.0 {
+                self.overrun_count += 1;
+            }
             
             self.output_values[0] = actual_period_us;        // period_us
             self.output_values[1] = latency_us;              // latency_us
             self.output_values[2] = jitter_us;               // jitter_us  
             self.output_values[3] = violation;               // realtime_violation
         }
+        self.output_values[4] = ctx.tick as f64;             // tick
+        self.output_values[5] = self.overrun_count as f64;   // overrun_count
         
         self.last_trigger_time = Some(process_start);
         
@@ -136,6 +146,8 @@ impl Plugin for PerformanceMonitorPlugin {
                 "latency_us".to_string(),
                 "jitter_us".to_string(),
                 "realtime_violation".to_string(),
+                "tick".to_string(),
+                "overrun_count".to_string(),
             ],
             inputs: Vec::new(),
             variables: vec!["running".to_string()],
@@ -0,0 +1,25 @@
+use performance_monitor_plugin::PerformanceMonitorPlugin;
+use rtsyn_plugin::prelude::*;
+use std::time::Duration;
+
+#[test]
+fn reports_the_tick_and_counts_overruns() {
+    let mut plugin = PerformanceMonitorPlugin::new(1);
+    // Any delay at all is over budget
+    plugin.set_config(0.0, 0.0);
+    let mut ctx = PluginContext::default();
+
+    for tick in [7, 8, 10] {
+        ctx.tick = tick;
+        plugin.process(&mut ctx).unwrap();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+
+    let ports: Vec<&str> = plugin.outputs().iter().map(|p| p.id.0.as_str()).collect();
+    assert_eq!(&ports[4..], ["tick", "overrun_count"]);
+    let values = plugin.get_output_values();
+    assert_eq!(values.len(), ports.len());
+    assert_eq!(values[4], 10.0);
+    // The first tick has no period to measure
+    assert_eq!(values[5], 2.0);
+}
@@ -0,0 +1,164 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+pub struct PwmPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    frequency_hz: f64,
+    deadband: f64,
+    invert: bool,
+    duty: f64,
+    /// Position within the current period, in [0, 1).
+    phase: f64,
+    out: f64,
+}
+
+impl PwmPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "PWM".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("frequency_hz".to_string(), Value::from(10.0)),
+                    ("deadband".to_string(), Value::from(0.0)),
+                    ("invert".to_string(), Value::from(false)),
+                ],
+            },
+            inputs: vec![Port { id: PortId("duty".to_string()) }],
+            outputs: vec![Port { id: PortId("out".to_string()) }],
+            frequency_hz: 10.0,
+            deadband: 0.0,
+            invert: false,
+            duty: 0.0,
+            phase: 0.0,
+            out: 0.0,
+        }
+    }
+
+    /// Duty cycles within `deadband` of 0 or 1 snap to fully off or on, so
+    /// the output never emits pulses shorter than the driver can follow.
+    /// Changing the frequency keeps the current phase.
+    pub fn set_config(&mut self, frequency_hz: f64, deadband: f64, invert: bool) {
+        self.frequency_hz = frequency_hz.max(0.0);
+        self.deadband = deadband.clamp(0.0, 0.5);
+        self.invert = invert;
+    }
+
+    pub fn set_input(&mut self, duty: f64) {
+        self.duty = duty;
+    }
+
+    pub fn reset(&mut self) {
+        self.phase = 0.0;
+        self.out = 0.0;
+    }
+
+    pub fn out(&self) -> f64 {
+        self.out
+    }
+
+    fn effective_duty(&self) -> f64 {
+        let duty = if self.duty.is_finite() { self.duty.clamp(0.0, 1.0) } else { 0.0 };
+        if duty <= self.deadband {
+            0.0
+        } else if duty >= 1.0 - self.deadband {
+            1.0
+        } else {
+            duty
+        }
+    }
+}
+
+impl Plugin for PwmPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
+        let high = self.phase < self.effective_duty();
+        self.out = if high != self.invert { 1.0 } else { 0.0 };
+        let time_step = ctx.period_seconds.max(0.0);
+        self.phase = (self.phase + self.frequency_hz * time_step).fract();
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::float("frequency_hz", "Frequency (Hz)")
+                        .min_f(0.0)
+                        .step_f(1.0)
+                        .default_value(Value::from(10.0))
+                        .hint("PWM periods per second"),
+                )
+                .field(
+                    ConfigField::float("deadband", "Deadband")
+                        .min_f(0.0)
+                        .max_f(0.5)
+                        .step_f(0.01)
+                        .default_value(Value::from(0.0))
+                        .hint("Duty cycles this close to 0 or 1 are fully off or on"),
+                )
+                .field(
+                    ConfigField::boolean("invert", "Invert output")
+                        .default_value(Value::from(false)),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["out".to_string()],
+            inputs: vec!["duty".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "frequency_hz" => Some(Value::from(self.frequency_hz)),
+            "deadband" => Some(Value::from(self.deadband)),
+            "invert" => Some(Value::from(self.invert)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        let (mut frequency_hz, mut deadband, mut invert) =
+            (self.frequency_hz, self.deadband, self.invert);
+        match name {
+            "frequency_hz" => frequency_hz = value.as_f64().unwrap_or(frequency_hz),
+            "deadband" => deadband = value.as_f64().unwrap_or(deadband),
+            "invert" => invert = value.as_bool().unwrap_or(invert),
+            _ => return Ok(()),
+        }
+        self.set_config(frequency_hz, deadband, invert);
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use pwm_plugin::PwmPlugin;
+use rtsyn_plugin::prelude::*;
+
+/// Runs `ticks` ticks at 125 ms per tick with a fixed duty and returns `out`.
+fn run(plugin: &mut PwmPlugin, duty: f64, ticks: usize) -> Vec<f64> {
+    let mut ctx = PluginContext {
+        period_seconds: 0.125,
+        ..PluginContext::default()
+    };
+    plugin.set_input(duty);
+    (0..ticks)
+        .map(|_| {
+            plugin.process(&mut ctx).unwrap();
+            plugin.out()
+        })
+        .collect()
+}
+
+#[test]
+fn high_for_the_duty_fraction_of_each_period() {
+    let mut plugin = PwmPlugin::new(1);
+    // 1 Hz at 8 ticks per second
+    plugin.set_config(1.0, 0.0, false);
+    assert_eq!(
+        run(&mut plugin, 0.25, 8),
+        vec![1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]
+    );
+    // Duty clamps to [0, 1]
+    assert_eq!(run(&mut plugin, 1.5, 4), vec![1.0; 4]);
+    assert_eq!(run(&mut plugin, -0.5, 4), vec![0.0; 4]);
+
+    plugin.set_config(1.0, 0.0, true);
+    plugin.reset();
+    assert_eq!(run(&mut plugin, 0.25, 4), vec![0.0, 0.0, 1.0, 1.0]);
+}
+
+#[test]
+fn deadband_and_frequency_changes() {
+    let mut plugin = PwmPlugin::new(1);
+    plugin.set_config(1.0, 0.1, false);
+    assert_eq!(run(&mut plugin, 0.05, 8), vec![0.0; 8]);
+    assert_eq!(run(&mut plugin, 0.95, 8), vec![1.0; 8]);
+
+    // Halfway through a period, doubling the frequency continues from there
+    plugin.set_config(1.0, 0.0, false);
+    plugin.reset();
+    assert_eq!(run(&mut plugin, 0.5, 4), vec![1.0; 4]);
+    plugin.set_config(2.0, 0.0, false);
+    assert_eq!(run(&mut plugin, 0.5, 4), vec![0.0, 0.0, 1.0, 1.0]);
+}
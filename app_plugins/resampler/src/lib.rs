@@ -0,0 +1,321 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+pub const INTERPOLATIONS: [&str; 2] = ["hold", "linear"];
+pub const DOWNSAMPLINGS: [&str; 2] = ["decimate", "average"];
+
+/// Slack on phase crossings, so rates that divide the tick rate evenly do
+/// not slip a tick to rounding.
+const PHASE_EPSILON: f64 = 1e-9;
+
+/// How output samples between two input samples are filled in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    /// Repeats the latest input sample.
+    Hold,
+    /// Ramps from the previous input sample to the latest one, which delays
+    /// the signal by one input period.
+    #[default]
+    Linear,
+}
+
+impl Interpolation {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "hold" => Interpolation::Hold,
+            _ => Interpolation::Linear,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Interpolation::Hold => "hold",
+            Interpolation::Linear => "linear",
+        }
+    }
+}
+
+/// How input samples are reduced when the output rate is the lower one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Downsampling {
+    /// Keeps the latest input sample.
+    Decimate,
+    /// Averages the input samples since the previous output sample.
+    #[default]
+    Average,
+}
+
+impl Downsampling {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "decimate" => Downsampling::Decimate,
+            _ => Downsampling::Average,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Downsampling::Decimate => "decimate",
+            Downsampling::Average => "average",
+        }
+    }
+}
+
+pub struct ResamplerPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    input_rate_hz: f64,
+    output_rate_hz: f64,
+    interpolation: Interpolation,
+    downsampling: Downsampling,
+    input: f64,
+    /// Fractions of an input and an output period elapsed; `None` before
+    /// the first tick, which samples and outputs right away.
+    input_phase: Option<f64>,
+    output_phase: f64,
+    previous_sample: f64,
+    latest_sample: f64,
+    /// Seconds since `latest_sample` was taken.
+    since_sample: f64,
+    pending_sum: f64,
+    pending_count: u32,
+    out: f64,
+}
+
+impl ResamplerPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Resampler".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("input_rate_hz".to_string(), Value::from(100.0)),
+                    ("output_rate_hz".to_string(), Value::from(1000.0)),
+                    ("interpolation".to_string(), Value::from("linear")),
+                    ("downsampling".to_string(), Value::from("average")),
+                ],
+            },
+            inputs: vec![Port { id: PortId("in".to_string()) }],
+            outputs: vec![Port { id: PortId("out".to_string()) }],
+            input_rate_hz: 100.0,
+            output_rate_hz: 1000.0,
+            interpolation: Interpolation::Linear,
+            downsampling: Downsampling::Average,
+            input: 0.0,
+            input_phase: None,
+            output_phase: 0.0,
+            previous_sample: 0.0,
+            latest_sample: 0.0,
+            since_sample: 0.0,
+            pending_sum: 0.0,
+            pending_count: 0,
+            out: 0.0,
+        }
+    }
+
+    /// Rates are in samples per second of runtime time; rates above the
+    /// workspace rate behave like the workspace rate.
+    pub fn set_config(
+        &mut self,
+        input_rate_hz: f64,
+        output_rate_hz: f64,
+        interpolation: Interpolation,
+        downsampling: Downsampling,
+    ) {
+        self.input_rate_hz = input_rate_hz.max(0.0);
+        self.output_rate_hz = output_rate_hz.max(0.0);
+        self.interpolation = interpolation;
+        self.downsampling = downsampling;
+    }
+
+    pub fn set_input(&mut self, input: f64) {
+        self.input = input;
+    }
+
+    pub fn reset(&mut self) {
+        self.input_phase = None;
+        self.output_phase = 0.0;
+        self.previous_sample = 0.0;
+        self.latest_sample = 0.0;
+        self.since_sample = 0.0;
+        self.pending_sum = 0.0;
+        self.pending_count = 0;
+        self.out = 0.0;
+    }
+
+    pub fn out(&self) -> f64 {
+        self.out
+    }
+
+    fn take_sample(&mut self) {
+        self.previous_sample = self.latest_sample;
+        self.latest_sample = self.input;
+        self.since_sample = 0.0;
+        self.pending_sum += self.input;
+        self.pending_count += 1;
+    }
+
+    fn output_sample(&self) -> f64 {
+        if self.output_rate_hz >= self.input_rate_hz {
+            match self.interpolation {
+                Interpolation::Hold => self.latest_sample,
+                Interpolation::Linear => {
+                    let fraction = (self.since_sample * self.input_rate_hz).clamp(0.0, 1.0);
+                    self.previous_sample + (self.latest_sample - self.previous_sample) * fraction
+                }
+            }
+        } else {
+            match self.downsampling {
+                Downsampling::Decimate => self.latest_sample,
+                Downsampling::Average if self.pending_count > 0 => {
+                    self.pending_sum / self.pending_count as f64
+                }
+                Downsampling::Average => self.latest_sample,
+            }
+        }
+    }
+}
+
+/// Advances `phase` by `step` periods and reports whether a period ended.
+fn advance(phase: &mut f64, step: f64) -> bool {
+    *phase += step;
+    if *phase >= 1.0 - PHASE_EPSILON {
+        *phase = (*phase - 1.0).max(0.0).fract();
+        true
+    } else {
+        false
+    }
+}
+
+impl Plugin for ResamplerPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
+        if !self.input.is_finite() {
+            return Ok(());
+        }
+        let time_step = ctx.period_seconds.max(0.0);
+        let Some(mut input_phase) = self.input_phase else {
+            self.input_phase = Some(0.0);
+            self.take_sample();
+            self.previous_sample = self.input;
+            self.out = self.input;
+            self.pending_sum = 0.0;
+            self.pending_count = 0;
+            return Ok(());
+        };
+
+        self.since_sample += time_step;
+        if advance(&mut input_phase, time_step * self.input_rate_hz) {
+            self.take_sample();
+        }
+        self.input_phase = Some(input_phase);
+        if advance(&mut self.output_phase, time_step * self.output_rate_hz) {
+            self.out = self.output_sample();
+            self.pending_sum = 0.0;
+            self.pending_count = 0;
+        }
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::float("input_rate_hz", "Input rate (Hz)")
+                        .min_f(0.0)
+                        .step_f(1.0)
+                        .default_value(Value::from(100.0))
+                        .hint("Rate the input signal is sampled at"),
+                )
+                .field(
+                    ConfigField::float("output_rate_hz", "Output rate (Hz)")
+                        .min_f(0.0)
+                        .step_f(1.0)
+                        .default_value(Value::from(1000.0))
+                        .hint("Rate the output is updated at"),
+                )
+                .field(
+                    ConfigField::text("interpolation", "Interpolation")
+                        .default_value(Value::from("linear"))
+                        .hint("Upsampling: hold or linear"),
+                )
+                .field(
+                    ConfigField::text("downsampling", "Downsampling")
+                        .default_value(Value::from("average"))
+                        .hint("Downsampling: decimate or average"),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["out".to_string()],
+            inputs: vec!["in".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "input_rate_hz" => Some(Value::from(self.input_rate_hz)),
+            "output_rate_hz" => Some(Value::from(self.output_rate_hz)),
+            "interpolation" => Some(Value::from(self.interpolation.name())),
+            "downsampling" => Some(Value::from(self.downsampling.name())),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        match name {
+            "input_rate_hz" => {
+                if let Some(v) = value.as_f64() {
+                    self.input_rate_hz = v.max(0.0);
+                }
+            }
+            "output_rate_hz" => {
+                if let Some(v) = value.as_f64() {
+                    self.output_rate_hz = v.max(0.0);
+                }
+            }
+            "interpolation" => {
+                if let Some(v) = value.as_str() {
+                    self.interpolation = Interpolation::from_name(v);
+                }
+            }
+            "downsampling" => {
+                if let Some(v) = value.as_str() {
+                    self.downsampling = Downsampling::from_name(v);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use resampler_plugin::{Downsampling, Interpolation, ResamplerPlugin};
+use rtsyn_plugin::prelude::*;
+
+/// Runs one tick per input value at 250 ms per tick and returns `out`.
+fn run(plugin: &mut ResamplerPlugin, inputs: &[f64]) -> Vec<f64> {
+    let mut ctx = PluginContext {
+        period_seconds: 0.25,
+        ..PluginContext::default()
+    };
+    inputs
+        .iter()
+        .map(|input| {
+            plugin.set_input(*input);
+            plugin.process(&mut ctx).unwrap();
+            plugin.out()
+        })
+        .collect()
+}
+
+#[test]
+fn upsampling_interpolates_between_input_samples() {
+    let mut plugin = ResamplerPlugin::new(1);
+    // Input sampled every 4th tick, output every tick
+    plugin.set_config(1.0, 4.0, Interpolation::Linear, Downsampling::Average);
+    let inputs = [0.0, 4.0, 4.0, 4.0, 4.0, 8.0, 8.0, 8.0, 8.0];
+    assert_eq!(
+        run(&mut plugin, &inputs),
+        vec![0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0]
+    );
+
+    plugin.reset();
+    plugin.set_config(1.0, 4.0, Interpolation::Hold, Downsampling::Average);
+    assert_eq!(
+        run(&mut plugin, &inputs),
+        vec![0.0, 0.0, 0.0, 0.0, 4.0, 4.0, 4.0, 4.0, 8.0]
+    );
+}
+
+#[test]
+fn downsampling_averages_or_decimates() {
+    let mut plugin = ResamplerPlugin::new(1);
+    // Input sampled every tick, output every 4th tick
+    plugin.set_config(4.0, 1.0, Interpolation::Linear, Downsampling::Average);
+    let inputs = [1.0, 2.0, 4.0, 6.0, 8.0, 0.0];
+    assert_eq!(
+        run(&mut plugin, &inputs),
+        vec![1.0, 1.0, 1.0, 1.0, 5.0, 5.0]
+    );
+
+    plugin.reset();
+    plugin.set_config(4.0, 1.0, Interpolation::Linear, Downsampling::Decimate);
+    assert_eq!(
+        run(&mut plugin, &inputs),
+        vec![1.0, 1.0, 1.0, 1.0, 8.0, 8.0]
+    );
+    assert_eq!(Downsampling::from_name("bogus"), Downsampling::Average);
+}
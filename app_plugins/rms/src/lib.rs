@@ -0,0 +1,231 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+use std::collections::VecDeque;
+
+/// Upper bound on the window so a huge `window_ms` cannot exhaust memory.
+const MAX_WINDOW_TICKS: usize = 1_000_000;
+
+pub struct RmsPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    window_ms: f64,
+    input: f64,
+    /// Ring of the last `capacity` samples, allocated when the window changes
+    /// so `process()` never allocates.
+    samples: Vec<f64>,
+    head: usize,
+    filled: usize,
+    sum_squares: f64,
+    /// Candidates for the window peak as (sample index, |value|), with
+    /// strictly decreasing magnitudes.
+    peaks: VecDeque<(u64, f64)>,
+    sample_index: u64,
+    rms: f64,
+    peak: f64,
+    crest_factor: f64,
+}
+
+impl RmsPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "RMS".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("window_ms".to_string(), Value::from(100.0)),
+                    ("warmup_ticks".to_string(), Value::from(0)),
+                ],
+            },
+            inputs: vec![Port { id: PortId("in".to_string()) }],
+            outputs: vec![
+                Port { id: PortId("rms".to_string()) },
+                Port { id: PortId("peak".to_string()) },
+                Port { id: PortId("crest_factor".to_string()) },
+            ],
+            window_ms: 100.0,
+            input: 0.0,
+            samples: Vec::new(),
+            head: 0,
+            filled: 0,
+            sum_squares: 0.0,
+            peaks: VecDeque::new(),
+            sample_index: 0,
+            rms: 0.0,
+            peak: 0.0,
+            crest_factor: 0.0,
+        }
+    }
+
+    /// Sizes the window for the runtime period; a different length clears
+    /// it and reallocates the buffers.
+    pub fn set_config(&mut self, window_ms: f64, period_seconds: f64) {
+        self.window_ms = window_ms.max(0.0);
+        let ticks = Self::window_ticks(self.window_ms, period_seconds);
+        if ticks != self.samples.len() {
+            self.samples = vec![0.0; ticks];
+            self.peaks = VecDeque::with_capacity(ticks);
+            self.reset();
+        }
+    }
+
+    /// Window length in ticks, at least one sample.
+    pub fn window_ticks(window_ms: f64, period_seconds: f64) -> usize {
+        if period_seconds <= 0.0 {
+            return 1;
+        }
+        let ticks = (window_ms / 1000.0 / period_seconds).round();
+        (ticks as usize).clamp(1, MAX_WINDOW_TICKS)
+    }
+
+    pub fn set_input(&mut self, value: f64) {
+        self.input = value;
+    }
+
+    pub fn reset(&mut self) {
+        self.samples.iter_mut().for_each(|sample| *sample = 0.0);
+        self.head = 0;
+        self.filled = 0;
+        self.sum_squares = 0.0;
+        self.peaks.clear();
+        self.sample_index = 0;
+        self.rms = 0.0;
+        self.peak = 0.0;
+        self.crest_factor = 0.0;
+    }
+
+    pub fn rms(&self) -> f64 {
+        self.rms
+    }
+
+    pub fn peak(&self) -> f64 {
+        self.peak
+    }
+
+    /// Peak over RMS; 0 while the RMS is 0.
+    pub fn crest_factor(&self) -> f64 {
+        self.crest_factor
+    }
+}
+
+impl Plugin for RmsPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        let capacity = self.samples.len();
+        if capacity == 0 {
+            return Ok(());
+        }
+        let value = if self.input.is_finite() { self.input } else { 0.0 };
+
+        let oldest = self.samples[self.head];
+        if self.filled == capacity {
+            self.sum_squares -= oldest * oldest;
+        } else {
+            self.filled += 1;
+        }
+        self.samples[self.head] = value;
+        self.sum_squares += value * value;
+        self.head = (self.head + 1) % capacity;
+        if self.head == 0 {
+            // Recompute once per lap so rounding in the running sum cannot
+            // accumulate.
+            self.sum_squares = self.samples[..self.filled].iter().map(|v| v * v).sum();
+        }
+
+        let magnitude = value.abs();
+        while self.peaks.back().is_some_and(|(_, peak)| *peak <= magnitude) {
+            self.peaks.pop_back();
+        }
+        self.peaks.push_back((self.sample_index, magnitude));
+        while self
+            .peaks
+            .front()
+            .is_some_and(|(idx, _)| idx + capacity as u64 <= self.sample_index)
+        {
+            self.peaks.pop_front();
+        }
+        self.sample_index += 1;
+
+        self.rms = (self.sum_squares.max(0.0) / self.filled as f64).sqrt();
+        self.peak = self.peaks.front().map(|(_, peak)| *peak).unwrap_or(0.0);
+        self.crest_factor = if self.rms > 0.0 {
+            self.peak / self.rms
+        } else {
+            0.0
+        };
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::float("window_ms", "Window (ms)")
+                        .min_f(0.0)
+                        .step_f(1.0)
+                        .default_value(Value::from(100.0))
+                        .hint("Rounded to a whole number of ticks, at least one"),
+                )
+                .field(
+                    ConfigField::integer("warmup_ticks", "Warmup (ticks)")
+                        .min(0)
+                        .default_value(Value::from(0))
+                        .hint("Outputs are held at 0 for this many ticks after start"),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec![
+                "rms".to_string(),
+                "peak".to_string(),
+                "crest_factor".to_string(),
+            ],
+            inputs: vec!["in".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "window_ms" => Some(Value::from(self.window_ms)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        if name == "window_ms" {
+            if let Some(v) = value.as_f64() {
+                self.window_ms = v.max(0.0);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use rms_plugin::RmsPlugin;
+use rtsyn_plugin::prelude::*;
+
+fn feed(plugin: &mut RmsPlugin, samples: impl IntoIterator<Item = f64>) {
+    let mut ctx = PluginContext {
+        period_seconds: 0.001,
+        ..PluginContext::default()
+    };
+    for sample in samples {
+        plugin.set_input(sample);
+        plugin.process(&mut ctx).unwrap();
+    }
+}
+
+#[test]
+fn sine_rms_and_crest_factor() {
+    let mut plugin = RmsPlugin::new(1);
+    // 100 ms window holds 25 periods of a 250 Hz sine, sampled at its peaks
+    plugin.set_config(100.0, 0.001);
+    let sine = (0..1000).map(|i| 2.0 * (2.0 * std::f64::consts::PI * 250.0 * i as f64 * 0.001).sin());
+    feed(&mut plugin, sine);
+
+    assert!((plugin.rms() - 2.0 / 2f64.sqrt()).abs() < 1e-9);
+    assert!((plugin.peak() - 2.0).abs() < 1e-9);
+    assert!((plugin.crest_factor() - 2f64.sqrt()).abs() < 1e-9);
+}
+
+#[test]
+fn window_slides_and_partial_windows_average_what_arrived() {
+    let mut plugin = RmsPlugin::new(1);
+    plugin.set_config(3.0, 0.001);
+    assert_eq!(RmsPlugin::window_ticks(3.0, 0.001), 3);
+
+    feed(&mut plugin, [-4.0]);
+    assert_eq!(plugin.rms(), 4.0);
+    assert_eq!(plugin.peak(), 4.0);
+
+    feed(&mut plugin, [0.0, 0.0]);
+    assert!((plugin.rms() - (16.0f64 / 3.0).sqrt()).abs() < 1e-12);
+    assert_eq!(plugin.peak(), 4.0);
+
+    // The -4 sample leaves the window
+    feed(&mut plugin, [1.0]);
+    assert!((plugin.rms() - (1.0f64 / 3.0).sqrt()).abs() < 1e-12);
+    assert_eq!(plugin.peak(), 1.0);
+}
+
+#[test]
+fn reset_and_window_changes_clear_the_history() {
+    let mut plugin = RmsPlugin::new(1);
+    plugin.set_config(10.0, 0.001);
+    feed(&mut plugin, [3.0, 3.0]);
+    plugin.reset();
+    assert_eq!(plugin.rms(), 0.0);
+    assert_eq!(plugin.crest_factor(), 0.0);
+
+    feed(&mut plugin, [1.0]);
+    plugin.set_config(20.0, 0.001);
+    feed(&mut plugin, [2.0]);
+    assert_eq!(plugin.rms(), 2.0);
+    assert_eq!(plugin.peak(), 2.0);
+}
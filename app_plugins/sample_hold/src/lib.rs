@@ -0,0 +1,136 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+/// Trigger level; a rising edge is a crossing from at-or-below to above it.
+const TRIGGER_THRESHOLD: f64 = 0.5;
+
+pub struct SampleHoldPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    initial_value: f64,
+    input: f64,
+    trigger: f64,
+    prev_trigger: f64,
+    sampled: bool,
+    out: f64,
+}
+
+impl SampleHoldPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Sample & Hold".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![("initial_value".to_string(), Value::from(0.0))],
+            },
+            inputs: vec![
+                Port { id: PortId("in".to_string()) },
+                Port { id: PortId("trigger".to_string()) },
+            ],
+            outputs: vec![Port { id: PortId("out".to_string()) }],
+            initial_value: 0.0,
+            input: 0.0,
+            trigger: 0.0,
+            prev_trigger: 0.0,
+            sampled: false,
+            out: 0.0,
+        }
+    }
+
+    pub fn set_config(&mut self, initial_value: f64) {
+        self.initial_value = initial_value;
+        if !self.sampled {
+            self.out = initial_value;
+        }
+    }
+
+    pub fn set_inputs(&mut self, input: f64, trigger: f64) {
+        self.input = input;
+        self.trigger = trigger;
+    }
+
+    /// Forgets the held sample; the output returns to `initial_value`.
+    pub fn reset(&mut self) {
+        self.sampled = false;
+        self.prev_trigger = 0.0;
+        self.out = self.initial_value;
+    }
+
+    pub fn out(&self) -> f64 {
+        self.out
+    }
+}
+
+impl Plugin for SampleHoldPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        if self.prev_trigger <= TRIGGER_THRESHOLD && self.trigger > TRIGGER_THRESHOLD {
+            self.out = self.input;
+            self.sampled = true;
+        }
+        self.prev_trigger = self.trigger;
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new().field(
+                ConfigField::float("initial_value", "Initial value")
+                    .step_f(0.1)
+                    .default_value(Value::from(0.0))
+                    .hint("Output until the first trigger"),
+            ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["out".to_string()],
+            inputs: vec!["in".to_string(), "trigger".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "initial_value" => Some(Value::from(self.initial_value)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        if name == "initial_value" {
+            if let Some(v) = value.as_f64() {
+                self.set_config(v);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use rtsyn_plugin::prelude::*;
+use sample_hold_plugin::SampleHoldPlugin;
+
+fn step(plugin: &mut SampleHoldPlugin, input: f64, trigger: f64) -> f64 {
+    let mut ctx = PluginContext::default();
+    plugin.set_inputs(input, trigger);
+    plugin.process(&mut ctx).unwrap();
+    plugin.out()
+}
+
+#[test]
+fn holds_input_captured_on_rising_edge() {
+    let mut plugin = SampleHoldPlugin::new(1);
+    plugin.set_config(-1.0);
+
+    assert_eq!(step(&mut plugin, 3.0, 0.0), -1.0);
+    assert_eq!(step(&mut plugin, 4.0, 1.0), 4.0);
+    // Trigger held high: no new sample
+    assert_eq!(step(&mut plugin, 5.0, 1.0), 4.0);
+    assert_eq!(step(&mut plugin, 6.0, 0.0), 4.0);
+    assert_eq!(step(&mut plugin, 7.0, 0.8), 7.0);
+}
+
+#[test]
+fn reset_restores_initial_value() {
+    let mut plugin = SampleHoldPlugin::new(1);
+    plugin.set_config(2.0);
+    assert_eq!(step(&mut plugin, 9.0, 1.0), 9.0);
+
+    plugin.reset();
+    assert_eq!(plugin.out(), 2.0);
+    // A trigger still high after the reset counts as a new edge
+    assert_eq!(step(&mut plugin, 8.0, 1.0), 8.0);
+}
@@ -0,0 +1,145 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+pub struct SaturationPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    min: f64,
+    max: f64,
+    input: f64,
+    out: f64,
+    out_of_range: bool,
+}
+
+impl SaturationPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Saturation".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("min".to_string(), Value::from(-1.0)),
+                    ("max".to_string(), Value::from(1.0)),
+                ],
+            },
+            inputs: vec![Port { id: PortId("in".to_string()) }],
+            outputs: vec![
+                Port { id: PortId("out".to_string()) },
+                Port { id: PortId("out_of_range".to_string()) },
+            ],
+            min: -1.0,
+            max: 1.0,
+            input: 0.0,
+            out: 0.0,
+            out_of_range: false,
+        }
+    }
+
+    pub fn set_config(&mut self, min: f64, max: f64) {
+        self.min = min;
+        self.max = max;
+    }
+
+    pub fn set_input(&mut self, value: f64) {
+        self.input = value;
+    }
+
+    pub fn out(&self) -> f64 {
+        self.out
+    }
+
+    /// 1.0 when the last input was clamped, 0.0 otherwise.
+    pub fn out_of_range(&self) -> f64 {
+        if self.out_of_range {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Plugin for SaturationPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        // Swapped limits are treated as the same band rather than rejected
+        let (low, high) = if self.min <= self.max {
+            (self.min, self.max)
+        } else {
+            (self.max, self.min)
+        };
+        self.out = self.input.clamp(low, high);
+        self.out_of_range = self.out != self.input;
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::float("min", "Minimum")
+                        .step_f(0.1)
+                        .default_value(Value::from(-1.0)),
+                )
+                .field(
+                    ConfigField::float("max", "Maximum")
+                        .step_f(0.1)
+                        .default_value(Value::from(1.0)),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: false,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["out".to_string(), "out_of_range".to_string()],
+            inputs: vec!["in".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "min" => Some(Value::from(self.min)),
+            "max" => Some(Value::from(self.max)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        let Some(v) = value.as_f64() else {
+            return Ok(());
+        };
+        match name {
+            "min" => self.min = v,
+            "max" => self.max = v,
+            _ => {}
+        }
+        Ok(())
+    }
+}
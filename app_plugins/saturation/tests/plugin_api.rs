@@ -0,0 +1,27 @@
+use rtsyn_plugin::prelude::*;
+use saturation_plugin::SaturationPlugin;
+
+fn step(plugin: &mut SaturationPlugin, input: f64) -> (f64, f64) {
+    let mut ctx = PluginContext::default();
+    plugin.set_input(input);
+    plugin.process(&mut ctx).unwrap();
+    (plugin.out(), plugin.out_of_range())
+}
+
+#[test]
+fn clamps_and_flags_out_of_range() {
+    let mut plugin = SaturationPlugin::new(1);
+    plugin.set_config(-2.0, 5.0);
+    assert_eq!(step(&mut plugin, 3.0), (3.0, 0.0));
+    assert_eq!(step(&mut plugin, 5.0), (5.0, 0.0));
+    assert_eq!(step(&mut plugin, 7.5), (5.0, 1.0));
+    assert_eq!(step(&mut plugin, -9.0), (-2.0, 1.0));
+}
+
+#[test]
+fn swapped_limits_are_reordered() {
+    let mut plugin = SaturationPlugin::new(1);
+    plugin.set_config(5.0, -2.0);
+    assert_eq!(step(&mut plugin, 7.5), (5.0, 1.0));
+    assert_eq!(step(&mut plugin, 0.0), (0.0, 0.0));
+}
@@ -0,0 +1,253 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+pub const MODES: [&str; 2] = ["gain_offset", "two_point"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScaleMode {
+    GainOffset,
+    TwoPoint,
+}
+
+impl ScaleMode {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "two_point" => ScaleMode::TwoPoint,
+            _ => ScaleMode::GainOffset,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScaleMode::GainOffset => "gain_offset",
+            ScaleMode::TwoPoint => "two_point",
+        }
+    }
+}
+
+/// Two reference points of a calibration line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration {
+    pub in_low: f64,
+    pub out_low: f64,
+    pub in_high: f64,
+    pub out_high: f64,
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self {
+            in_low: 0.0,
+            out_low: 0.0,
+            in_high: 1.0,
+            out_high: 1.0,
+        }
+    }
+}
+
+impl Calibration {
+    /// Gain and offset of the line through both points, or `None` when the
+    /// two inputs coincide.
+    pub fn gain_offset(&self) -> Option<(f64, f64)> {
+        let span = self.in_high - self.in_low;
+        if span == 0.0 || !span.is_finite() {
+            return None;
+        }
+        let gain = (self.out_high - self.out_low) / span;
+        Some((gain, self.out_low - gain * self.in_low))
+    }
+}
+
+pub struct ScalePlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    mode: ScaleMode,
+    gain: f64,
+    offset: f64,
+    calibration: Calibration,
+    input: f64,
+    out: f64,
+}
+
+impl ScalePlugin {
+    pub fn new(id: u64) -> Self {
+        let calibration = Calibration::default();
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Scale".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("mode".to_string(), Value::from("gain_offset")),
+                    ("gain".to_string(), Value::from(1.0)),
+                    ("offset".to_string(), Value::from(0.0)),
+                    ("in_low".to_string(), Value::from(calibration.in_low)),
+                    ("out_low".to_string(), Value::from(calibration.out_low)),
+                    ("in_high".to_string(), Value::from(calibration.in_high)),
+                    ("out_high".to_string(), Value::from(calibration.out_high)),
+                ],
+            },
+            inputs: vec![Port { id: PortId("in".to_string()) }],
+            outputs: vec![Port { id: PortId("out".to_string()) }],
+            mode: ScaleMode::GainOffset,
+            gain: 1.0,
+            offset: 0.0,
+            calibration,
+            input: 0.0,
+            out: 0.0,
+        }
+    }
+
+    pub fn set_config(&mut self, mode: ScaleMode, gain: f64, offset: f64) {
+        self.mode = mode;
+        self.gain = gain;
+        self.offset = offset;
+    }
+
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.calibration = calibration;
+    }
+
+    pub fn set_input(&mut self, value: f64) {
+        self.input = value;
+    }
+
+    pub fn out(&self) -> f64 {
+        self.out
+    }
+
+    /// Gain and offset currently applied. A degenerate two-point calibration
+    /// falls back to the plain gain/offset values.
+    pub fn effective_gain_offset(&self) -> (f64, f64) {
+        match self.mode {
+            ScaleMode::GainOffset => (self.gain, self.offset),
+            ScaleMode::TwoPoint => self
+                .calibration
+                .gain_offset()
+                .unwrap_or((self.gain, self.offset)),
+        }
+    }
+}
+
+impl Plugin for ScalePlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        let (gain, offset) = self.effective_gain_offset();
+        self.out = self.input * gain + offset;
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::text("mode", "Mode")
+                        .default_value(Value::from("gain_offset"))
+                        .hint("gain_offset or two_point"),
+                )
+                .field(
+                    ConfigField::float("gain", "Gain")
+                        .step_f(0.1)
+                        .default_value(Value::from(1.0)),
+                )
+                .field(
+                    ConfigField::float("offset", "Offset")
+                        .step_f(0.1)
+                        .default_value(Value::from(0.0)),
+                )
+                .field(
+                    ConfigField::float("in_low", "Input low")
+                        .step_f(0.1)
+                        .default_value(Value::from(0.0))
+                        .hint("Raw reading at the low reference"),
+                )
+                .field(
+                    ConfigField::float("out_low", "Output low")
+                        .step_f(0.1)
+                        .default_value(Value::from(0.0))
+                        .hint("Value the low reading maps to"),
+                )
+                .field(
+                    ConfigField::float("in_high", "Input high")
+                        .step_f(0.1)
+                        .default_value(Value::from(1.0))
+                        .hint("Raw reading at the high reference"),
+                )
+                .field(
+                    ConfigField::float("out_high", "Output high")
+                        .step_f(0.1)
+                        .default_value(Value::from(1.0))
+                        .hint("Value the high reading maps to"),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: false,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["out".to_string()],
+            inputs: vec!["in".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "mode" => Some(Value::from(self.mode.name())),
+            "gain" => Some(Value::from(self.gain)),
+            "offset" => Some(Value::from(self.offset)),
+            "in_low" => Some(Value::from(self.calibration.in_low)),
+            "out_low" => Some(Value::from(self.calibration.out_low)),
+            "in_high" => Some(Value::from(self.calibration.in_high)),
+            "out_high" => Some(Value::from(self.calibration.out_high)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        if name == "mode" {
+            if let Some(v) = value.as_str() {
+                self.mode = ScaleMode::from_name(v);
+            }
+            return Ok(());
+        }
+        let Some(v) = value.as_f64() else {
+            return Ok(());
+        };
+        match name {
+            "gain" => self.gain = v,
+            "offset" => self.offset = v,
+            "in_low" => self.calibration.in_low = v,
+            "out_low" => self.calibration.out_low = v,
+            "in_high" => self.calibration.in_high = v,
+            "out_high" => self.calibration.out_high = v,
+            _ => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use rtsyn_plugin::prelude::*;
+use scale_plugin::{Calibration, ScaleMode, ScalePlugin};
+
+fn step(plugin: &mut ScalePlugin, input: f64) -> f64 {
+    let mut ctx = PluginContext::default();
+    plugin.set_input(input);
+    plugin.process(&mut ctx).unwrap();
+    plugin.out()
+}
+
+#[test]
+fn gain_offset_mode_applies_linear_transform() {
+    let mut plugin = ScalePlugin::new(1);
+    plugin.set_config(ScaleMode::GainOffset, 2.0, -1.0);
+    assert_eq!(step(&mut plugin, 3.0), 5.0);
+}
+
+#[test]
+fn two_point_mode_derives_gain_and_offset() {
+    let mut plugin = ScalePlugin::new(1);
+    plugin.set_config(ScaleMode::TwoPoint, 1.0, 0.0);
+    // 4-20 mA loop mapped to 0-100 %
+    plugin.set_calibration(Calibration {
+        in_low: 4.0,
+        out_low: 0.0,
+        in_high: 20.0,
+        out_high: 100.0,
+    });
+    assert_eq!(plugin.effective_gain_offset(), (6.25, -25.0));
+    assert_eq!(step(&mut plugin, 12.0), 50.0);
+}
+
+#[test]
+fn degenerate_calibration_falls_back_to_gain_offset() {
+    let mut plugin = ScalePlugin::new(1);
+    plugin.set_config(ScaleMode::TwoPoint, 3.0, 1.0);
+    plugin.set_calibration(Calibration {
+        in_low: 1.0,
+        out_low: 0.0,
+        in_high: 1.0,
+        out_high: 10.0,
+    });
+    assert_eq!(step(&mut plugin, 2.0), 7.0);
+}
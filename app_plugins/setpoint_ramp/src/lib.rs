@@ -0,0 +1,138 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+pub struct SetpointRampPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    ramp_rate: f64,
+    target: f64,
+    enabled: bool,
+    /// `None` until the first tick, which starts the output at the target
+    /// instead of ramping up from zero.
+    out: Option<f64>,
+}
+
+impl SetpointRampPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Setpoint Ramp".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![("ramp_rate".to_string(), Value::from(1.0))],
+            },
+            inputs: vec![
+                Port { id: PortId("target".to_string()) },
+                Port { id: PortId("enable".to_string()) },
+            ],
+            outputs: vec![Port { id: PortId("out".to_string()) }],
+            ramp_rate: 1.0,
+            target: 0.0,
+            enabled: true,
+            out: None,
+        }
+    }
+
+    /// `ramp_rate` in units per second.
+    pub fn set_config(&mut self, ramp_rate: f64) {
+        self.ramp_rate = ramp_rate.max(0.0);
+    }
+
+    /// `enabled` false freezes the output where it is.
+    pub fn set_inputs(&mut self, target: f64, enabled: bool) {
+        self.target = target;
+        self.enabled = enabled;
+    }
+
+    /// The next tick starts again from the target, without a jump.
+    pub fn reset(&mut self) {
+        self.out = None;
+    }
+
+    pub fn out(&self) -> f64 {
+        self.out.unwrap_or(self.target)
+    }
+}
+
+impl Plugin for SetpointRampPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
+        if !self.target.is_finite() {
+            return Ok(());
+        }
+        let Some(out) = self.out else {
+            self.out = Some(self.target);
+            return Ok(());
+        };
+        if !self.enabled {
+            return Ok(());
+        }
+        let time_step = ctx.period_seconds.max(0.0);
+        let max_step = self.ramp_rate * time_step;
+        let delta = (self.target - out).clamp(-max_step, max_step);
+        self.out = Some(out + delta);
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new().field(
+                ConfigField::float("ramp_rate", "Ramp rate (units/s)")
+                    .min_f(0.0)
+                    .step_f(0.1)
+                    .default_value(Value::from(1.0))
+                    .hint("Largest change of the output per second"),
+            ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["out".to_string()],
+            inputs: vec!["target".to_string(), "enable".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "ramp_rate" => Some(Value::from(self.ramp_rate)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        if name == "ramp_rate" {
+            if let Some(v) = value.as_f64() {
+                self.set_config(v);
+            }
+        }
+        Ok(())
+    }
+}
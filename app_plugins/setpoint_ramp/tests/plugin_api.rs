@@ -0,0 +1,39 @@
+use rtsyn_plugin::prelude::*;
+use setpoint_ramp_plugin::SetpointRampPlugin;
+
+fn step(plugin: &mut SetpointRampPlugin, target: f64, enabled: bool) -> f64 {
+    let mut ctx = PluginContext {
+        period_seconds: 0.1,
+        ..PluginContext::default()
+    };
+    plugin.set_inputs(target, enabled);
+    plugin.process(&mut ctx).unwrap();
+    plugin.out()
+}
+
+#[test]
+fn ramps_toward_the_target_and_holds() {
+    let mut plugin = SetpointRampPlugin::new(1);
+    // 5 units/s at 0.1 s per tick: 0.5 per tick
+    plugin.set_config(5.0);
+
+    assert_eq!(step(&mut plugin, 0.0, true), 0.0);
+    assert_eq!(step(&mut plugin, 1.2, true), 0.5);
+    assert_eq!(step(&mut plugin, 1.2, true), 1.0);
+    assert_eq!(step(&mut plugin, 1.2, true), 1.2);
+    assert_eq!(step(&mut plugin, 1.2, true), 1.2);
+    assert_eq!(step(&mut plugin, -1.0, true), 0.7);
+}
+
+#[test]
+fn disabled_freezes_and_reset_starts_at_the_target() {
+    let mut plugin = SetpointRampPlugin::new(1);
+    plugin.set_config(5.0);
+    step(&mut plugin, 0.0, true);
+
+    assert_eq!(step(&mut plugin, 3.0, false), 0.0);
+    assert_eq!(step(&mut plugin, 3.0, true), 0.5);
+
+    plugin.reset();
+    assert_eq!(step(&mut plugin, 3.0, true), 3.0);
+}
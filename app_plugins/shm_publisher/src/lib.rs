@@ -0,0 +1,390 @@
+//! Publishes the plugin inputs to a named POSIX shared memory object
+//! (`shm_open`), so other processes can read live values by mapping it.
+//!
+//! Layout, native endianness, all fields 8-byte aligned:
+//!
+//! | offset | type           | field                          |
+//! |--------|----------------|--------------------------------|
+//! | 0      | `[u8; 8]`      | magic, `b"RTSYNSHM"`           |
+//! | 8      | `u32`          | layout version, currently 1    |
+//! | 12     | `u32`          | `count`, number of values      |
+//! | 16     | `u64`          | sequence counter               |
+//! | 24     | `u64`          | runtime tick of the values     |
+//! | 32     | `[f64; count]` | values of `in_0`, `in_1`, ...  |
+//!
+//! The sequence counter works as a seqlock: it is odd while a tick is being
+//! written and even once the values are consistent, advancing by 2 per
+//! tick. Readers load it, copy the header and values, and retry if it was
+//! odd or changed in the meantime. When `count` changes the object is
+//! resized in place; readers must map it again with the new size.
+
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+pub const MAGIC: [u8; 8] = *b"RTSYNSHM";
+pub const LAYOUT_VERSION: u32 = 1;
+pub const HEADER_SIZE: usize = 32;
+
+/// Shared memory object names need a single leading slash.
+pub fn object_name(name: &str) -> String {
+    format!("/{}", name.trim().trim_start_matches('/'))
+}
+
+mod shm {
+    use super::{HEADER_SIZE, LAYOUT_VERSION, MAGIC};
+    use libc::{c_int, c_void};
+    use std::ffi::CString;
+    use std::sync::atomic::{fence, AtomicU64, Ordering};
+
+    const SEQUENCE_OFFSET: usize = 16;
+    const TICK_OFFSET: usize = 24;
+
+    fn last_error() -> String {
+        std::io::Error::last_os_error().to_string()
+    }
+
+    fn size_for(count: usize) -> usize {
+        HEADER_SIZE + count * std::mem::size_of::<f64>()
+    }
+
+    struct Mapping {
+        ptr: *mut u8,
+        len: usize,
+    }
+
+    impl Mapping {
+        fn new(fd: c_int, len: usize, writable: bool) -> Result<Self, String> {
+            let prot = if writable {
+                libc::PROT_READ | libc::PROT_WRITE
+            } else {
+                libc::PROT_READ
+            };
+            let ptr = unsafe {
+                libc::mmap(std::ptr::null_mut(), len, prot, libc::MAP_SHARED, fd, 0)
+            };
+            if ptr == libc::MAP_FAILED {
+                return Err(last_error());
+            }
+            Ok(Self {
+                ptr: ptr as *mut u8,
+                len,
+            })
+        }
+
+        fn sequence(&self) -> &AtomicU64 {
+            unsafe { &*(self.ptr.add(SEQUENCE_OFFSET) as *const AtomicU64) }
+        }
+    }
+
+    impl Drop for Mapping {
+        fn drop(&mut self) {
+            unsafe { libc::munmap(self.ptr as *mut c_void, self.len) };
+        }
+    }
+
+    /// Writable segment owned by the publisher; unlinked when dropped.
+    pub struct Segment {
+        name: CString,
+        fd: c_int,
+        count: usize,
+        map: Mapping,
+    }
+
+    // The mapping is only touched through `&mut self` on the owning side.
+    unsafe impl Send for Segment {}
+
+    impl Segment {
+        pub fn create(name: &str, count: usize) -> Result<Self, String> {
+            let name = CString::new(name).map_err(|_| "invalid shared memory name".to_string())?;
+            let fd = unsafe {
+                libc::shm_open(name.as_ptr(), libc::O_CREAT | libc::O_RDWR, 0o644)
+            };
+            if fd < 0 {
+                return Err(last_error());
+            }
+            let map = match Self::map(fd, count) {
+                Ok(map) => map,
+                Err(err) => {
+                    unsafe {
+                        libc::close(fd);
+                        libc::shm_unlink(name.as_ptr());
+                    }
+                    return Err(err);
+                }
+            };
+            let mut segment = Self {
+                name,
+                fd,
+                count,
+                map,
+            };
+            segment.write_header();
+            Ok(segment)
+        }
+
+        fn map(fd: c_int, count: usize) -> Result<Mapping, String> {
+            let len = size_for(count);
+            if unsafe { libc::ftruncate(fd, len as libc::off_t) } < 0 {
+                return Err(last_error());
+            }
+            Mapping::new(fd, len, true)
+        }
+
+        fn write_header(&mut self) {
+            unsafe {
+                std::ptr::copy_nonoverlapping(MAGIC.as_ptr(), self.map.ptr, MAGIC.len());
+                (self.map.ptr.add(8) as *mut u32).write_volatile(LAYOUT_VERSION);
+                (self.map.ptr.add(12) as *mut u32).write_volatile(self.count as u32);
+            }
+        }
+
+        /// Grows or shrinks the object to hold `count` values, keeping the
+        /// sequence counter running.
+        pub fn resize(&mut self, count: usize) -> Result<(), String> {
+            if count == self.count {
+                return Ok(());
+            }
+            let sequence = self.map.sequence().load(Ordering::Relaxed);
+            self.map = Self::map(self.fd, count)?;
+            self.count = count;
+            self.map.sequence().store(sequence, Ordering::Relaxed);
+            self.write_header();
+            Ok(())
+        }
+
+        pub fn publish(&mut self, tick: u64, values: &[f64]) {
+            let sequence = self.map.sequence();
+            let start = sequence.load(Ordering::Relaxed) | 1;
+            sequence.store(start, Ordering::Relaxed);
+            fence(Ordering::Release);
+            unsafe {
+                (self.map.ptr.add(TICK_OFFSET) as *mut u64).write_volatile(tick);
+                let dst = self.map.ptr.add(HEADER_SIZE) as *mut f64;
+                for (idx, value) in values.iter().take(self.count).enumerate() {
+                    dst.add(idx).write_volatile(*value);
+                }
+            }
+            sequence.store(start + 1, Ordering::Release);
+        }
+    }
+
+    impl Drop for Segment {
+        fn drop(&mut self) {
+            unsafe {
+                libc::close(self.fd);
+                libc::shm_unlink(self.name.as_ptr());
+            }
+        }
+    }
+
+    /// Reads one consistent snapshot: `(sequence, tick, values)`.
+    pub fn read(name: &str) -> Result<(u64, u64, Vec<f64>), String> {
+        let name = CString::new(name).map_err(|_| "invalid shared memory name".to_string())?;
+        let fd = unsafe { libc::shm_open(name.as_ptr(), libc::O_RDONLY, 0) };
+        if fd < 0 {
+            return Err(last_error());
+        }
+        let mut stat: libc::stat = unsafe { std::mem::zeroed() };
+        let res = unsafe { libc::fstat(fd, &mut stat) };
+        let map = if res < 0 {
+            Err(last_error())
+        } else {
+            Mapping::new(fd, stat.st_size as usize, false)
+        };
+        unsafe { libc::close(fd) };
+        let map = map?;
+        if map.len < HEADER_SIZE || unsafe { std::slice::from_raw_parts(map.ptr, 8) } != MAGIC {
+            return Err("not an RTSyn shared memory segment".to_string());
+        }
+        loop {
+            let before = map.sequence().load(Ordering::Acquire);
+            if before % 2 == 1 {
+                std::hint::spin_loop();
+                continue;
+            }
+            let (tick, values) = unsafe {
+                let count = (map.ptr.add(12) as *const u32).read_volatile() as usize;
+                let count = count.min((map.len - HEADER_SIZE) / std::mem::size_of::<f64>());
+                let tick = (map.ptr.add(TICK_OFFSET) as *const u64).read_volatile();
+                let src = map.ptr.add(HEADER_SIZE) as *const f64;
+                let values: Vec<f64> = (0..count).map(|idx| src.add(idx).read_volatile()).collect();
+                (tick, values)
+            };
+            fence(Ordering::Acquire);
+            if map.sequence().load(Ordering::Relaxed) == before {
+                return Ok((before, tick, values));
+            }
+        }
+    }
+}
+
+/// One consistent read of a published segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub sequence: u64,
+    pub tick: u64,
+    pub values: Vec<f64>,
+}
+
+/// Reads the segment published under `name`, following the seqlock protocol
+/// described in the crate docs.
+pub fn read_snapshot(name: &str) -> Result<Snapshot, String> {
+    let (sequence, tick, values) = shm::read(&object_name(name))?;
+    Ok(Snapshot {
+        sequence,
+        tick,
+        values,
+    })
+}
+
+pub struct ShmPublisherPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    name: String,
+    publishing: bool,
+    input_values: Vec<f64>,
+    segment: Option<shm::Segment>,
+    last_error: Option<String>,
+}
+
+impl ShmPublisherPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Shared Memory Publisher".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("name".to_string(), Value::from("rtsyn")),
+                    ("input_count".to_string(), Value::from(0)),
+                ],
+            },
+            inputs: Vec::new(),
+            name: "rtsyn".to_string(),
+            publishing: false,
+            input_values: Vec::new(),
+            segment: None,
+            last_error: None,
+        }
+    }
+
+    /// The segment exists while `publishing` is set; it is recreated when
+    /// the name changes and resized when the input count does.
+    pub fn set_config(&mut self, input_count: usize, name: String, publishing: bool) {
+        if self.name != name || !publishing {
+            self.segment = None;
+            self.last_error = None;
+        }
+        self.name = name;
+        self.publishing = publishing;
+
+        if self.inputs.len() != input_count {
+            self.inputs = (0..input_count)
+                .map(|idx| Port {
+                    id: PortId(format!("in_{idx}")),
+                })
+                .collect();
+        }
+        if self.input_values.len() != input_count {
+            self.input_values.resize(input_count, 0.0);
+        }
+
+        if !publishing || self.last_error.is_some() {
+            return;
+        }
+        let result = match self.segment.as_mut() {
+            Some(segment) => segment.resize(input_count),
+            None => shm::Segment::create(&object_name(&self.name), input_count)
+                .map(|segment| self.segment = Some(segment)),
+        };
+        if let Err(err) = result {
+            self.segment = None;
+            self.last_error = Some(err);
+        }
+    }
+
+    pub fn set_inputs(&mut self, values: Vec<f64>) {
+        self.input_values = values;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.segment.is_some()
+    }
+
+    /// Why the segment could not be created or resized.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+}
+
+impl Plugin for ShmPublisherPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &[]
+    }
+
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
+        if let Some(segment) = self.segment.as_mut() {
+            segment.publish(ctx.tick, &self.input_values);
+        }
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new().field(
+                ConfigField::text("name", "Segment name")
+                    .default_value(Value::from("rtsyn"))
+                    .max_length(200)
+                    .hint("Readers open it with shm_open(\"/<name>\")"),
+            ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: false,
+            extendable_inputs: ExtendableInputs::Auto {
+                pattern: "in_{}".to_string(),
+            },
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec![],
+            inputs: self.inputs.iter().map(|p| p.id.0.clone()).collect(),
+            variables: Vec::new(),
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "name" => Some(Value::from(self.name.clone())),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        if name == "name" {
+            if let Some(v) = value.as_str() {
+                self.set_config(self.inputs.len(), v.to_string(), self.publishing);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use rtsyn_plugin::prelude::*;
+use shm_publisher_plugin::{object_name, read_snapshot, ShmPublisherPlugin};
+
+fn unique_name(tag: &str) -> String {
+    format!("rtsyn_test_{tag}_{}", std::process::id())
+}
+
+#[test]
+fn object_names_get_a_single_leading_slash() {
+    assert_eq!(object_name("rtsyn"), "/rtsyn");
+    assert_eq!(object_name("//rtsyn "), "/rtsyn");
+}
+
+#[test]
+fn published_values_are_readable_by_name() {
+    let name = unique_name("publish");
+    let mut plugin = ShmPublisherPlugin::new(1);
+    plugin.set_config(2, name.clone(), true);
+    assert!(plugin.is_open(), "{:?}", plugin.last_error());
+    assert_eq!(plugin.inputs().len(), 2);
+
+    let mut ctx = PluginContext {
+        tick: 7,
+        period_seconds: 0.001,
+    };
+    plugin.set_inputs(vec![1.5, -2.0]);
+    plugin.process(&mut ctx).unwrap();
+    let first = read_snapshot(&name).unwrap();
+    assert_eq!(first.tick, 7);
+    assert_eq!(first.values, vec![1.5, -2.0]);
+    assert_eq!(first.sequence % 2, 0);
+
+    // Growing the input count keeps the sequence counter running
+    plugin.set_config(3, name.clone(), true);
+    plugin.set_inputs(vec![1.0, 2.0, 3.0]);
+    ctx.tick = 8;
+    plugin.process(&mut ctx).unwrap();
+    let second = read_snapshot(&name).unwrap();
+    assert_eq!(second.values, vec![1.0, 2.0, 3.0]);
+    assert_eq!(second.sequence, first.sequence + 2);
+
+    // Stopping removes the segment
+    plugin.set_config(3, name.clone(), false);
+    assert!(!plugin.is_open());
+    assert!(read_snapshot(&name).is_err());
+}
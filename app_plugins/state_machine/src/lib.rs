@@ -0,0 +1,649 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+pub use condition::{Condition, Op};
+
+/// Variable available in every condition: seconds since the current state
+/// was entered.
+pub const TIME_IN_STATE: &str = "time_in_state";
+
+/// Condition expressions over the plugin inputs.
+mod condition {
+    /// Binary operators, from lowest to highest precedence: `||`, `&&`,
+    /// comparisons, `+ -`, `* /`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Op {
+        Or,
+        And,
+        Lt,
+        Le,
+        Gt,
+        Ge,
+        Eq,
+        Ne,
+        Add,
+        Sub,
+        Mul,
+        Div,
+    }
+
+    /// A parsed expression. Comparisons and logic evaluate to 1.0 or 0.0,
+    /// and any non-zero value counts as true.
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Condition {
+        Number(f64),
+        /// Index into the values passed to [`Condition::eval`].
+        Variable(usize),
+        Not(Box<Condition>),
+        Neg(Box<Condition>),
+        Binary(Op, Box<Condition>, Box<Condition>),
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Token {
+        Number(f64),
+        Ident(String),
+        Op(&'static str),
+        Open,
+        Close,
+    }
+
+    fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+        const OPERATORS: [&str; 14] = [
+            "||", "&&", "<=", ">=", "==", "!=", "<", ">", "!", "+", "-", "*", "/", "=",
+        ];
+        let mut tokens = Vec::new();
+        let mut rest = text.trim_start();
+        while let Some(c) = rest.chars().next() {
+            if c.is_ascii_digit() || c == '.' {
+                let mut end = 0;
+                let bytes = rest.as_bytes();
+                while end < bytes.len() {
+                    let b = bytes[end];
+                    let exponent_sign = (b == b'+' || b == b'-')
+                        && end > 0
+                        && matches!(bytes[end - 1], b'e' | b'E');
+                    if b.is_ascii_digit() || b == b'.' || b == b'e' || b == b'E' || exponent_sign {
+                        end += 1;
+                    } else {
+                        break;
+                    }
+                }
+                let number = &rest[..end];
+                let value = number
+                    .parse::<f64>()
+                    .map_err(|_| format!("invalid number '{number}'"))?;
+                tokens.push(Token::Number(value));
+                rest = &rest[end..];
+            } else if c.is_ascii_alphabetic() || c == '_' {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                tokens.push(Token::Ident(rest[..end].to_string()));
+                rest = &rest[end..];
+            } else if c == '(' || c == ')' {
+                tokens.push(if c == '(' { Token::Open } else { Token::Close });
+                rest = &rest[1..];
+            } else {
+                let Some(op) = OPERATORS.iter().find(|op| rest.starts_with(**op)) else {
+                    return Err(format!("unexpected '{c}'"));
+                };
+                if *op == "=" {
+                    return Err("use '==' to compare".to_string());
+                }
+                tokens.push(Token::Op(op));
+                rest = &rest[op.len()..];
+            }
+            rest = rest.trim_start();
+        }
+        Ok(tokens)
+    }
+
+    struct Parser<'a, F> {
+        tokens: &'a [Token],
+        pos: usize,
+        variable: F,
+    }
+
+    impl<F: Fn(&str) -> Option<usize>> Parser<'_, F> {
+        fn peek_op(&self, ops: &[(&str, Op)]) -> Option<Op> {
+            match self.tokens.get(self.pos) {
+                Some(Token::Op(text)) => ops
+                    .iter()
+                    .find(|(name, _)| name == text)
+                    .map(|(_, op)| *op),
+                _ => None,
+            }
+        }
+
+        /// Left-associative chain of `level` operators over `next`.
+        fn binary(
+            &mut self,
+            ops: &[(&str, Op)],
+            next: fn(&mut Self) -> Result<Condition, String>,
+        ) -> Result<Condition, String> {
+            let mut left = next(self)?;
+            while let Some(op) = self.peek_op(ops) {
+                self.pos += 1;
+                let right = next(self)?;
+                left = Condition::Binary(op, Box::new(left), Box::new(right));
+            }
+            Ok(left)
+        }
+
+        fn or(&mut self) -> Result<Condition, String> {
+            self.binary(&[("||", Op::Or)], Self::and)
+        }
+
+        fn and(&mut self) -> Result<Condition, String> {
+            self.binary(&[("&&", Op::And)], Self::comparison)
+        }
+
+        fn comparison(&mut self) -> Result<Condition, String> {
+            let left = self.sum()?;
+            let ops = [
+                ("<", Op::Lt),
+                ("<=", Op::Le),
+                (">", Op::Gt),
+                (">=", Op::Ge),
+                ("==", Op::Eq),
+                ("!=", Op::Ne),
+            ];
+            let Some(op) = self.peek_op(&ops) else {
+                return Ok(left);
+            };
+            self.pos += 1;
+            let right = self.sum()?;
+            if self.peek_op(&ops).is_some() {
+                return Err("comparisons cannot be chained".to_string());
+            }
+            Ok(Condition::Binary(op, Box::new(left), Box::new(right)))
+        }
+
+        fn sum(&mut self) -> Result<Condition, String> {
+            self.binary(&[("+", Op::Add), ("-", Op::Sub)], Self::product)
+        }
+
+        fn product(&mut self) -> Result<Condition, String> {
+            self.binary(&[("*", Op::Mul), ("/", Op::Div)], Self::unary)
+        }
+
+        fn unary(&mut self) -> Result<Condition, String> {
+            match self.tokens.get(self.pos) {
+                Some(Token::Op("!")) => {
+                    self.pos += 1;
+                    Ok(Condition::Not(Box::new(self.unary()?)))
+                }
+                Some(Token::Op("-")) => {
+                    self.pos += 1;
+                    Ok(Condition::Neg(Box::new(self.unary()?)))
+                }
+                _ => self.atom(),
+            }
+        }
+
+        fn atom(&mut self) -> Result<Condition, String> {
+            let token = self.tokens.get(self.pos).cloned();
+            self.pos += 1;
+            match token {
+                Some(Token::Number(value)) => Ok(Condition::Number(value)),
+                Some(Token::Ident(name)) => match name.as_str() {
+                    "true" => Ok(Condition::Number(1.0)),
+                    "false" => Ok(Condition::Number(0.0)),
+                    _ => (self.variable)(&name)
+                        .map(Condition::Variable)
+                        .ok_or_else(|| format!("unknown name '{name}'")),
+                },
+                Some(Token::Open) => {
+                    let inner = self.or()?;
+                    match self.tokens.get(self.pos) {
+                        Some(Token::Close) => {
+                            self.pos += 1;
+                            Ok(inner)
+                        }
+                        _ => Err("missing ')'".to_string()),
+                    }
+                }
+                Some(Token::Close) => Err("unexpected ')'".to_string()),
+                Some(Token::Op(op)) => Err(format!("unexpected '{op}'")),
+                None => Err("incomplete condition".to_string()),
+            }
+        }
+    }
+
+    fn truth(value: bool) -> f64 {
+        if value {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    impl Condition {
+        /// Parses `text`, resolving names to value indices with `variable`.
+        pub fn parse(
+            text: &str,
+            variable: impl Fn(&str) -> Option<usize>,
+        ) -> Result<Self, String> {
+            let tokens = tokenize(text)?;
+            if tokens.is_empty() {
+                return Err("empty condition".to_string());
+            }
+            let mut parser = Parser {
+                tokens: &tokens,
+                pos: 0,
+                variable,
+            };
+            let condition = parser.or()?;
+            match tokens.get(parser.pos) {
+                None => Ok(condition),
+                Some(Token::Close) => Err("unexpected ')'".to_string()),
+                Some(_) => Err("expected an operator".to_string()),
+            }
+        }
+
+        pub fn eval(&self, values: &[f64]) -> f64 {
+            match self {
+                Condition::Number(value) => *value,
+                Condition::Variable(idx) => values.get(*idx).copied().unwrap_or(0.0),
+                Condition::Not(inner) => truth(!is_true(inner.eval(values))),
+                Condition::Neg(inner) => -inner.eval(values),
+                Condition::Binary(op, left, right) => {
+                    let a = left.eval(values);
+                    // Logic short-circuits like the operators it mirrors
+                    match op {
+                        Op::Or => return truth(is_true(a) || is_true(right.eval(values))),
+                        Op::And => return truth(is_true(a) && is_true(right.eval(values))),
+                        _ => {}
+                    }
+                    let b = right.eval(values);
+                    match op {
+                        Op::Lt => truth(a < b),
+                        Op::Le => truth(a <= b),
+                        Op::Gt => truth(a > b),
+                        Op::Ge => truth(a >= b),
+                        Op::Eq => truth(a == b),
+                        Op::Ne => truth(a != b),
+                        Op::Add => a + b,
+                        Op::Sub => a - b,
+                        Op::Mul => a * b,
+                        Op::Div => a / b,
+                        Op::Or | Op::And => unreachable!(),
+                    }
+                }
+            }
+        }
+
+        pub fn holds(&self, values: &[f64]) -> bool {
+            is_true(self.eval(values))
+        }
+    }
+
+    /// Non-zero is true; NaN is false.
+    fn is_true(value: f64) -> bool {
+        value != 0.0 && !value.is_nan()
+    }
+}
+
+/// A transition rule, written `from:to:condition`. `from` may be `*` to
+/// leave any state other than `to`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition {
+    /// Source state index, `None` for `*`.
+    pub from: Option<usize>,
+    pub to: usize,
+    pub condition: Condition,
+}
+
+impl Transition {
+    /// `inputs` are the names conditions may use besides
+    /// [`TIME_IN_STATE`], which evaluates from value `inputs.len()`.
+    pub fn parse(spec: &str, states: &[String], inputs: &[String]) -> Result<Self, String> {
+        let mut parts = spec.splitn(3, ':');
+        let (Some(from), Some(to), Some(condition)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err("expected from:to:condition".to_string());
+        };
+        let state = |name: &str| {
+            let name = name.trim();
+            states
+                .iter()
+                .position(|state| state == name)
+                .ok_or_else(|| format!("unknown state '{name}'"))
+        };
+        let from = match from.trim() {
+            "*" => None,
+            name => Some(state(name)?),
+        };
+        let to = state(to)?;
+        let condition = Condition::parse(condition, |name| {
+            if name == TIME_IN_STATE {
+                Some(inputs.len())
+            } else {
+                inputs.iter().position(|input| input == name)
+            }
+        })?;
+        Ok(Self {
+            from,
+            to,
+            condition,
+        })
+    }
+
+    fn applies_to(&self, state: usize) -> bool {
+        match self.from {
+            Some(from) => from == state,
+            None => self.to != state,
+        }
+    }
+}
+
+fn trimmed_names(names: &[String]) -> Vec<String> {
+    names
+        .iter()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Checks a state machine config, naming the first problem found.
+pub fn validate(inputs: &[String], states: &[String], transitions: &[String]) -> Result<(), String> {
+    let inputs = trimmed_names(inputs);
+    let states = trimmed_names(states);
+    for (idx, input) in inputs.iter().enumerate() {
+        if !is_identifier(input) || matches!(input.as_str(), TIME_IN_STATE | "true" | "false") {
+            return Err(format!("'{input}' cannot be used as an input name"));
+        }
+        if inputs[..idx].contains(input) {
+            return Err(format!("input '{input}' is listed twice"));
+        }
+    }
+    for (idx, state) in states.iter().enumerate() {
+        if state == "*" || state.contains(':') {
+            return Err(format!("'{state}' cannot be used as a state name"));
+        }
+        if states[..idx].contains(state) {
+            return Err(format!("state '{state}' is listed twice"));
+        }
+    }
+    for spec in trimmed_names(transitions) {
+        Transition::parse(&spec, &states, &inputs).map_err(|err| format!("{spec}: {err}"))?;
+    }
+    Ok(())
+}
+
+pub struct StateMachinePlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    input_names: Vec<String>,
+    states: Vec<String>,
+    transition_specs: Vec<String>,
+    /// Transitions that parse, in config order.
+    transitions: Vec<Transition>,
+    /// Input values followed by the time in state, as conditions see them.
+    values: Vec<f64>,
+    state: usize,
+    time_in_state: f64,
+    /// State entered on the last tick, for the `on_enter_*` pulses.
+    entered: Option<usize>,
+    started: bool,
+    /// `entered` with the state name, until drained.
+    events: Vec<(String, Value)>,
+}
+
+impl StateMachinePlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "State Machine".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("inputs".to_string(), Value::Array(Vec::new())),
+                    ("states".to_string(), Value::Array(Vec::new())),
+                    ("transitions".to_string(), Value::Array(Vec::new())),
+                ],
+            },
+            inputs: Vec::new(),
+            outputs: vec![Port { id: PortId("state".to_string()) }],
+            input_names: Vec::new(),
+            states: Vec::new(),
+            transition_specs: Vec::new(),
+            transitions: Vec::new(),
+            values: vec![0.0],
+            state: 0,
+            time_in_state: 0.0,
+            entered: None,
+            started: false,
+            events: Vec::new(),
+        }
+    }
+
+    /// Invalid input names and transitions are skipped, see [`validate`].
+    /// Changing the states starts over from the first one.
+    pub fn set_config(&mut self, inputs: &[String], states: &[String], transitions: &[String]) {
+        let inputs: Vec<String> = trimmed_names(inputs)
+            .into_iter()
+            .filter(|name| is_identifier(name) && name != TIME_IN_STATE)
+            .collect();
+        let states = trimmed_names(states);
+        let transitions = trimmed_names(transitions);
+        if inputs == self.input_names && states == self.states && transitions == self.transition_specs {
+            return;
+        }
+        if states != self.states {
+            self.states = states;
+            self.reset();
+        }
+        self.input_names = inputs;
+        self.transition_specs = transitions;
+        self.transitions = self
+            .transition_specs
+            .iter()
+            .filter_map(|spec| Transition::parse(spec, &self.states, &self.input_names).ok())
+            .collect();
+        self.values = vec![0.0; self.input_names.len() + 1];
+        self.inputs = self
+            .input_names
+            .iter()
+            .map(|name| Port { id: PortId(name.clone()) })
+            .collect();
+        self.outputs = self
+            .output_port_names()
+            .into_iter()
+            .map(|name| Port { id: PortId(name) })
+            .collect();
+    }
+
+    pub fn input_port_names(&self) -> Vec<String> {
+        self.input_names.clone()
+    }
+
+    /// `state`, then `on_enter_<state>` for every state.
+    pub fn output_port_names(&self) -> Vec<String> {
+        std::iter::once("state".to_string())
+            .chain(self.states.iter().map(|state| format!("on_enter_{state}")))
+            .collect()
+    }
+
+    pub fn set_input(&mut self, name: &str, value: f64) {
+        if let Some(idx) = self.input_names.iter().position(|input| input == name) {
+            self.values[idx] = value;
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.state = 0;
+        self.time_in_state = 0.0;
+        self.entered = None;
+        self.started = false;
+    }
+
+    /// Index of the current state, in config order.
+    pub fn state(&self) -> usize {
+        self.state
+    }
+
+    pub fn state_name(&self) -> &str {
+        self.states.get(self.state).map(String::as_str).unwrap_or("")
+    }
+
+    pub fn time_in_state(&self) -> f64 {
+        self.time_in_state
+    }
+
+    pub fn get_output(&self, name: &str) -> f64 {
+        if name == "state" {
+            return self.state as f64;
+        }
+        let entered = name
+            .strip_prefix("on_enter_")
+            .and_then(|state| self.states.iter().position(|s| s == state));
+        if entered.is_some() && entered == self.entered {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    /// State changes since the last call, oldest first.
+    pub fn drain_events(&mut self) -> std::vec::Drain<'_, (String, Value)> {
+        self.events.drain(..)
+    }
+
+    fn enter(&mut self, state: usize) {
+        self.state = state;
+        self.time_in_state = 0.0;
+        self.entered = Some(state);
+        self.events
+            .push(("entered".to_string(), Value::from(self.states[state].as_str())));
+    }
+}
+
+impl Plugin for StateMachinePlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    /// The first tick enters the first state. Later ticks take the first
+    /// listed transition out of the current state whose condition holds, at
+    /// most one per tick.
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
+        self.entered = None;
+        if self.states.is_empty() {
+            return Ok(());
+        }
+        if !self.started {
+            self.started = true;
+            self.enter(0);
+            return Ok(());
+        }
+        self.time_in_state += ctx.period_seconds.max(0.0);
+        if let Some(time) = self.values.last_mut() {
+            *time = self.time_in_state;
+        }
+        let next = self
+            .transitions
+            .iter()
+            .find(|transition| {
+                transition.applies_to(self.state) && transition.condition.holds(&self.values)
+            })
+            .map(|transition| transition.to);
+        if let Some(next) = next {
+            self.enter(next);
+        }
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::dynamic_list("inputs", "Inputs")
+                        .hint("Condition signals, one input port each"),
+                )
+                .field(
+                    ConfigField::dynamic_list("states", "States")
+                        .hint("State names; the machine starts in the first"),
+                )
+                .field(
+                    ConfigField::dynamic_list("transitions", "Transitions").hint(
+                        "from:to:condition, e.g. idle:ramp:start > 0.5 or *:stop:abort. \
+                         Conditions use the inputs, time_in_state (s), numbers, \
+                         + - * /, comparisons, && || ! and parentheses",
+                    ),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            inputs: self.input_port_names(),
+            outputs: self.output_port_names(),
+            variables: vec!["state_name".to_string(), TIME_IN_STATE.to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        let names_value =
+            |names: &[String]| Value::Array(names.iter().cloned().map(Value::String).collect());
+        match name {
+            "inputs" => Some(names_value(&self.input_names)),
+            "states" => Some(names_value(&self.states)),
+            "transitions" => Some(names_value(&self.transition_specs)),
+            "state_name" => Some(Value::from(self.state_name())),
+            TIME_IN_STATE => Some(Value::from(self.time_in_state)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        let Some(items) = value.as_array() else {
+            return Ok(());
+        };
+        let names: Vec<String> = items
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect();
+        let (inputs, states, transitions) = (
+            self.input_names.clone(),
+            self.states.clone(),
+            self.transition_specs.clone(),
+        );
+        match name {
+            "inputs" => self.set_config(&names, &states, &transitions),
+            "states" => self.set_config(&inputs, &names, &transitions),
+            "transitions" => self.set_config(&inputs, &states, &names),
+            _ => {}
+        }
+        Ok(())
+    }
+}
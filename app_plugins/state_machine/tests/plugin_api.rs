@@ -0,0 +1,150 @@
+use rtsyn_plugin::prelude::*;
+use state_machine_plugin::{validate, Condition, StateMachinePlugin};
+
+fn names(items: &[&str]) -> Vec<String> {
+    items.iter().map(|item| item.to_string()).collect()
+}
+
+fn eval(text: &str, a: f64, b: f64) -> f64 {
+    let condition = Condition::parse(text, |name| match name {
+        "a" => Some(0),
+        "b" => Some(1),
+        _ => None,
+    })
+    .unwrap();
+    condition.eval(&[a, b])
+}
+
+#[test]
+fn conditions_follow_operator_precedence() {
+    assert_eq!(eval("a + b * 2", 1.0, 3.0), 7.0);
+    assert_eq!(eval("(a + b) * 2", 1.0, 3.0), 8.0);
+    assert_eq!(eval("a > 0.5 && b <= 2 || false", 1.0, 3.0), 0.0);
+    assert_eq!(eval("a > 0.5 && !(b > 2) || b == 3", 1.0, 3.0), 1.0);
+    assert_eq!(eval("-a - -b", 1.0, 3.0), 2.0);
+    assert_eq!(eval("a / b != 0", 0.0, 0.0), 1.0);
+    assert_eq!(eval("1.5e1 >= 15", 0.0, 0.0), 1.0);
+
+    let parse = |text: &str| Condition::parse(text, |name| (name == "a").then_some(0));
+    assert_eq!(parse("c > 1").unwrap_err(), "unknown name 'c'");
+    assert_eq!(parse("a = 1").unwrap_err(), "use '==' to compare");
+    assert!(parse("(a > 1").is_err());
+    assert!(parse("a > 1)").is_err());
+    assert!(parse("a 1").is_err());
+    assert!(parse("1 < a < 2").is_err());
+    assert!(parse("").is_err());
+}
+
+#[test]
+fn sequence_steps_through_states_with_enter_pulses() {
+    let mut plugin = StateMachinePlugin::new(1);
+    plugin.set_config(
+        &names(&["start", "abort"]),
+        &names(&["idle", "ramp", "hold", "stop"]),
+        &names(&[
+            "idle:ramp:start > 0.5",
+            "ramp:hold:time_in_state >= 0.2",
+            "hold:stop:time_in_state >= 0.1",
+            "*:stop:abort",
+            "broken:stop:1",
+        ]),
+    );
+    assert_eq!(plugin.input_port_names(), names(&["start", "abort"]));
+    assert_eq!(
+        plugin.output_port_names(),
+        names(&["state", "on_enter_idle", "on_enter_ramp", "on_enter_hold", "on_enter_stop"])
+    );
+
+    let mut ctx = PluginContext {
+        period_seconds: 0.1,
+        ..PluginContext::default()
+    };
+    let mut step = |plugin: &mut StateMachinePlugin| {
+        plugin.process(&mut ctx).unwrap();
+        plugin.state_name().to_string()
+    };
+
+    assert_eq!(step(&mut plugin), "idle");
+    assert_eq!(plugin.get_output("on_enter_idle"), 1.0);
+    assert_eq!(step(&mut plugin), "idle");
+    assert_eq!(plugin.get_output("on_enter_idle"), 0.0);
+
+    plugin.set_input("start", 1.0);
+    assert_eq!(step(&mut plugin), "ramp");
+    assert_eq!(plugin.get_output("state"), 1.0);
+    assert_eq!(plugin.get_output("on_enter_ramp"), 1.0);
+    assert_eq!(step(&mut plugin), "ramp");
+    assert_eq!(step(&mut plugin), "hold");
+    assert_eq!(step(&mut plugin), "stop");
+    assert_eq!(plugin.get_output("on_enter_stop"), 1.0);
+
+    // A wildcard transition does not re-enter its own target
+    plugin.set_input("abort", 1.0);
+    assert_eq!(step(&mut plugin), "stop");
+    assert_eq!(plugin.get_output("on_enter_stop"), 0.0);
+    assert!((plugin.time_in_state() - 0.1).abs() < 1e-9);
+
+    // Transitions are tried in the listed order
+    plugin.reset();
+    assert_eq!(step(&mut plugin), "idle");
+    assert_eq!(step(&mut plugin), "ramp");
+    plugin.reset();
+    plugin.set_input("start", 0.0);
+    assert_eq!(step(&mut plugin), "idle");
+    assert_eq!(step(&mut plugin), "stop");
+
+    let entered: Vec<String> = plugin
+        .drain_events()
+        .map(|(name, state)| format!("{name} {}", state.as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        entered,
+        [
+            "entered idle",
+            "entered ramp",
+            "entered hold",
+            "entered stop",
+            "entered idle",
+            "entered ramp",
+            "entered idle",
+            "entered stop",
+        ]
+    );
+}
+
+#[test]
+fn changing_the_states_starts_over() {
+    let mut plugin = StateMachinePlugin::new(1);
+    let transitions = names(&["a:b:1"]);
+    plugin.set_config(&[], &names(&["a", "b"]), &transitions);
+    let mut ctx = PluginContext::default();
+    plugin.process(&mut ctx).unwrap();
+    plugin.process(&mut ctx).unwrap();
+    assert_eq!(plugin.state_name(), "b");
+
+    // Unchanged states keep the current state
+    plugin.set_config(&names(&["x"]), &names(&["a", "b"]), &transitions);
+    assert_eq!(plugin.state_name(), "b");
+
+    plugin.set_config(&names(&["x"]), &names(&["a", "b", "c"]), &transitions);
+    assert_eq!(plugin.state(), 0);
+    plugin.process(&mut ctx).unwrap();
+    assert_eq!(plugin.get_output("on_enter_a"), 1.0);
+}
+
+#[test]
+fn validation_names_the_first_problem() {
+    let states = names(&["idle", "run"]);
+    assert!(validate(&names(&["go"]), &states, &names(&["idle:run:go", "*:idle:!go"])).is_ok());
+    assert_eq!(
+        validate(&names(&["go"]), &states, &names(&["idle:walk:go"])).unwrap_err(),
+        "idle:walk:go: unknown state 'walk'"
+    );
+    assert_eq!(
+        validate(&[], &states, &names(&["idle:run"])).unwrap_err(),
+        "idle:run: expected from:to:condition"
+    );
+    assert!(validate(&names(&["time_in_state"]), &states, &[]).is_err());
+    assert!(validate(&names(&["go", "go"]), &states, &[]).is_err());
+    assert!(validate(&[], &names(&["idle", "idle"]), &[]).is_err());
+}
@@ -0,0 +1,214 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+/// Per-input statistics, published as `in_<idx>_<name>` variables.
+pub const STATISTICS: [&str; 5] = ["count", "mean", "variance", "min", "max"];
+
+/// Reset level; the accumulators stay cleared while `reset` is above it.
+const RESET_THRESHOLD: f64 = 0.5;
+
+pub fn variable_name(idx: usize, statistic: &str) -> String {
+    format!("in_{idx}_{statistic}")
+}
+
+/// Welford's online mean and variance, plus the extremes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Accumulator {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for Accumulator {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl Accumulator {
+    pub fn push(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Sample variance; 0 until there are two samples.
+    pub fn variance(&self) -> f64 {
+        if self.count < 2 {
+            0.0
+        } else {
+            self.m2 / (self.count - 1) as f64
+        }
+    }
+
+    /// 0 while empty.
+    pub fn min(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.min
+        }
+    }
+
+    /// 0 while empty.
+    pub fn max(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.max
+        }
+    }
+
+    fn value(&self, statistic: &str) -> Option<Value> {
+        match statistic {
+            "count" => Some(Value::from(self.count)),
+            "mean" => Some(Value::from(self.mean())),
+            "variance" => Some(Value::from(self.variance())),
+            "min" => Some(Value::from(self.min())),
+            "max" => Some(Value::from(self.max())),
+            _ => None,
+        }
+    }
+}
+
+pub struct StatsPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    input_values: Vec<f64>,
+    reset: f64,
+    stats: Vec<Accumulator>,
+}
+
+impl StatsPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Statistics".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![("input_count".to_string(), Value::from(0))],
+            },
+            inputs: vec![Port { id: PortId("reset".to_string()) }],
+            input_values: Vec::new(),
+            reset: 0.0,
+            stats: Vec::new(),
+        }
+    }
+
+    /// Added inputs start empty; removing inputs drops their statistics.
+    pub fn set_config(&mut self, input_count: usize) {
+        if self.stats.len() == input_count {
+            return;
+        }
+        self.inputs = (0..input_count)
+            .map(|idx| Port {
+                id: PortId(format!("in_{idx}")),
+            })
+            .chain(std::iter::once(Port {
+                id: PortId("reset".to_string()),
+            }))
+            .collect();
+        self.input_values.resize(input_count, 0.0);
+        self.stats.resize(input_count, Accumulator::default());
+    }
+
+    pub fn set_inputs(&mut self, values: Vec<f64>, reset: f64) {
+        self.input_values = values;
+        self.reset = reset;
+    }
+
+    pub fn reset(&mut self) {
+        self.stats.fill(Accumulator::default());
+    }
+
+    pub fn stats(&self) -> &[Accumulator] {
+        &self.stats
+    }
+
+    /// `in_<idx>_<statistic>` for every input, in input order.
+    pub fn variable_names(&self) -> Vec<String> {
+        (0..self.stats.len())
+            .flat_map(|idx| STATISTICS.iter().map(move |stat| variable_name(idx, stat)))
+            .collect()
+    }
+}
+
+impl Plugin for StatsPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &[]
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        if self.reset > RESET_THRESHOLD {
+            self.reset();
+            return Ok(());
+        }
+        for (acc, value) in self.stats.iter_mut().zip(&self.input_values) {
+            if value.is_finite() {
+                acc.push(*value);
+            }
+        }
+        Ok(())
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::Auto {
+                pattern: "in_{}".to_string(),
+            },
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec![],
+            inputs: self.inputs.iter().map(|p| p.id.0.clone()).collect(),
+            variables: self.variable_names(),
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        let rest = name.strip_prefix("in_")?;
+        let (idx, statistic) = rest.split_once('_')?;
+        self.stats.get(idx.parse::<usize>().ok()?)?.value(statistic)
+    }
+
+    fn set_variable(&mut self, _name: &str, _value: Value) -> Result<(), PluginError> {
+        Ok(())
+    }
+}
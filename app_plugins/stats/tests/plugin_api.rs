@@ -0,0 +1,49 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+use stats_plugin::StatsPlugin;
+
+fn feed(plugin: &mut StatsPlugin, rows: &[[f64; 2]], reset: f64) {
+    let mut ctx = PluginContext::default();
+    for row in rows {
+        plugin.set_inputs(row.to_vec(), reset);
+        plugin.process(&mut ctx).unwrap();
+    }
+}
+
+#[test]
+fn accumulates_per_input() {
+    let mut plugin = StatsPlugin::new(1);
+    plugin.set_config(2);
+    let ports: Vec<&str> = plugin.inputs().iter().map(|p| p.id.0.as_str()).collect();
+    assert_eq!(ports, ["in_0", "in_1", "reset"]);
+
+    feed(
+        &mut plugin,
+        &[[2.0, 1.0], [4.0, f64::NAN], [4.0, 1.0], [4.0, 1.0], [5.0, 1.0], [5.0, 1.0], [7.0, 1.0], [9.0, 1.0]],
+        0.0,
+    );
+    let first = plugin.stats()[0];
+    assert_eq!(first.count(), 8);
+    assert_eq!(first.mean(), 5.0);
+    assert!((first.variance() - 32.0 / 7.0).abs() < 1e-12);
+    assert_eq!((first.min(), first.max()), (2.0, 9.0));
+
+    // The NaN sample is skipped
+    assert_eq!(plugin.get_variable("in_1_count"), Some(Value::from(7u64)));
+    assert_eq!(plugin.get_variable("in_1_variance"), Some(Value::from(0.0)));
+    assert_eq!(plugin.variable_names().len(), 10);
+}
+
+#[test]
+fn reset_clears_while_high() {
+    let mut plugin = StatsPlugin::new(1);
+    plugin.set_config(2);
+    feed(&mut plugin, &[[1.0, 2.0], [3.0, 4.0]], 0.0);
+    feed(&mut plugin, &[[5.0, 6.0]], 1.0);
+    assert_eq!(plugin.stats()[0].count(), 0);
+    assert_eq!(plugin.get_variable("in_0_max"), Some(Value::from(0.0)));
+
+    feed(&mut plugin, &[[8.0, 8.0]], 0.0);
+    assert_eq!(plugin.stats()[1].mean(), 8.0);
+    assert_eq!(plugin.get_variable("in_2_mean"), None);
+}
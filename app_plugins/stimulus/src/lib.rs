@@ -0,0 +1,291 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+use std::f64::consts::PI;
+
+pub const SHAPES: [&str; 4] = ["step", "impulse", "ramp", "chirp"];
+
+/// Trigger level; a rising edge is a crossing from at-or-below to above it.
+const TRIGGER_THRESHOLD: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Step,
+    Impulse,
+    Ramp,
+    Chirp,
+}
+
+impl Shape {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "impulse" => Shape::Impulse,
+            "ramp" => Shape::Ramp,
+            "chirp" => Shape::Chirp,
+            _ => Shape::Step,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Shape::Step => "step",
+            Shape::Impulse => "impulse",
+            Shape::Ramp => "ramp",
+            Shape::Chirp => "chirp",
+        }
+    }
+}
+
+/// Linear frequency sweep used by the chirp shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Chirp {
+    pub f_start: f64,
+    pub f_end: f64,
+    pub duration: f64,
+}
+
+impl Default for Chirp {
+    fn default() -> Self {
+        Self {
+            f_start: 0.1,
+            f_end: 10.0,
+            duration: 10.0,
+        }
+    }
+}
+
+impl Chirp {
+    /// Sweep value `tau` seconds after it started, or `None` once it is over.
+    pub fn value(&self, tau: f64) -> Option<f64> {
+        if tau < 0.0 || tau > self.duration {
+            return None;
+        }
+        let rate = if self.duration > 0.0 {
+            (self.f_end - self.f_start) / self.duration
+        } else {
+            0.0
+        };
+        let phase = 2.0 * PI * (self.f_start * tau + 0.5 * rate * tau * tau);
+        Some(phase.sin())
+    }
+}
+
+pub struct StimulusPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    shape: Shape,
+    start_time: f64,
+    amplitude: f64,
+    baseline: f64,
+    chirp: Chirp,
+    trigger: f64,
+    prev_trigger: f64,
+    origin_tick: u64,
+    restart_pending: bool,
+    out: f64,
+}
+
+impl StimulusPlugin {
+    pub fn new(id: u64) -> Self {
+        let chirp = Chirp::default();
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Stimulus".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("shape".to_string(), Value::from("step")),
+                    ("start_time".to_string(), Value::from(1.0)),
+                    ("amplitude".to_string(), Value::from(1.0)),
+                    ("baseline".to_string(), Value::from(0.0)),
+                    ("f_start".to_string(), Value::from(chirp.f_start)),
+                    ("f_end".to_string(), Value::from(chirp.f_end)),
+                    ("duration".to_string(), Value::from(chirp.duration)),
+                ],
+            },
+            inputs: vec![Port { id: PortId("trigger".to_string()) }],
+            outputs: vec![Port { id: PortId("out".to_string()) }],
+            shape: Shape::Step,
+            start_time: 1.0,
+            amplitude: 1.0,
+            baseline: 0.0,
+            chirp,
+            trigger: 0.0,
+            prev_trigger: 0.0,
+            origin_tick: 0,
+            restart_pending: false,
+            out: 0.0,
+        }
+    }
+
+    pub fn set_config(&mut self, shape: Shape, start_time: f64, amplitude: f64, baseline: f64) {
+        self.shape = shape;
+        self.start_time = start_time;
+        self.amplitude = amplitude;
+        self.baseline = baseline;
+    }
+
+    pub fn set_chirp(&mut self, chirp: Chirp) {
+        self.chirp = chirp;
+    }
+
+    pub fn set_trigger(&mut self, trigger: f64) {
+        self.trigger = trigger;
+    }
+
+    /// Restarts the stimulus; time is measured from the next processed tick.
+    pub fn reset(&mut self) {
+        self.restart_pending = true;
+        self.out = self.baseline;
+    }
+
+    pub fn out(&self) -> f64 {
+        self.out
+    }
+
+    /// Output `elapsed` seconds after the stimulus (re)started, for a
+    /// runtime stepping every `period` seconds.
+    pub fn value_at(&self, elapsed: f64, period: f64) -> f64 {
+        let tau = elapsed - self.start_time;
+        if tau < 0.0 {
+            return self.baseline;
+        }
+        let shape = match self.shape {
+            Shape::Step => 1.0,
+            // A single sample: the first tick at or after start_time
+            Shape::Impulse if tau < period.max(f64::EPSILON) => 1.0,
+            Shape::Impulse => 0.0,
+            Shape::Ramp => tau,
+            Shape::Chirp => self.chirp.value(tau).unwrap_or(0.0),
+        };
+        self.baseline + self.amplitude * shape
+    }
+}
+
+impl Plugin for StimulusPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
+        if self.prev_trigger <= TRIGGER_THRESHOLD && self.trigger > TRIGGER_THRESHOLD {
+            self.restart_pending = true;
+        }
+        self.prev_trigger = self.trigger;
+        if self.restart_pending {
+            self.origin_tick = ctx.tick;
+            self.restart_pending = false;
+        }
+        let elapsed = ctx.tick.wrapping_sub(self.origin_tick) as f64 * ctx.period_seconds;
+        self.out = self.value_at(elapsed, ctx.period_seconds);
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::text("shape", "Shape")
+                        .default_value(Value::from("step"))
+                        .hint("step, impulse, ramp or chirp"),
+                )
+                .field(
+                    ConfigField::float("start_time", "Start time (s)")
+                        .step_f(0.1)
+                        .default_value(Value::from(1.0))
+                        .hint("Baseline is emitted before this time"),
+                )
+                .field(
+                    ConfigField::float("amplitude", "Amplitude")
+                        .step_f(0.1)
+                        .default_value(Value::from(1.0))
+                        .hint("Step height, impulse height, ramp slope per second or chirp amplitude"),
+                )
+                .field(
+                    ConfigField::float("baseline", "Baseline")
+                        .step_f(0.1)
+                        .default_value(Value::from(0.0)),
+                )
+                .field(
+                    ConfigField::float("f_start", "Chirp start (Hz)")
+                        .step_f(0.1)
+                        .default_value(Value::from(0.1)),
+                )
+                .field(
+                    ConfigField::float("f_end", "Chirp end (Hz)")
+                        .step_f(0.1)
+                        .default_value(Value::from(10.0)),
+                )
+                .field(
+                    ConfigField::float("duration", "Chirp duration (s)")
+                        .step_f(0.1)
+                        .default_value(Value::from(10.0)),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["out".to_string()],
+            inputs: vec!["trigger".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "shape" => Some(Value::from(self.shape.name())),
+            "start_time" => Some(Value::from(self.start_time)),
+            "amplitude" => Some(Value::from(self.amplitude)),
+            "baseline" => Some(Value::from(self.baseline)),
+            "f_start" => Some(Value::from(self.chirp.f_start)),
+            "f_end" => Some(Value::from(self.chirp.f_end)),
+            "duration" => Some(Value::from(self.chirp.duration)),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        if name == "shape" {
+            if let Some(v) = value.as_str() {
+                self.shape = Shape::from_name(v);
+            }
+            return Ok(());
+        }
+        let Some(v) = value.as_f64() else {
+            return Ok(());
+        };
+        match name {
+            "start_time" => self.start_time = v,
+            "amplitude" => self.amplitude = v,
+            "baseline" => self.baseline = v,
+            "f_start" => self.chirp.f_start = v,
+            "f_end" => self.chirp.f_end = v,
+            "duration" => self.chirp.duration = v,
+            _ => {}
+        }
+        Ok(())
+    }
+}
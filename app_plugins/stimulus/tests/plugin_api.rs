@@ -0,0 +1,66 @@
+use rtsyn_plugin::prelude::*;
+use stimulus_plugin::{Chirp, Shape, StimulusPlugin};
+
+/// Runs ticks `from..to` at 0.1 s and collects the output.
+fn run(plugin: &mut StimulusPlugin, from: u64, to: u64) -> Vec<f64> {
+    (from..to)
+        .map(|tick| {
+            let mut ctx = PluginContext {
+                tick,
+                period_seconds: 0.1,
+            };
+            plugin.process(&mut ctx).unwrap();
+            (plugin.out() * 1e9).round() / 1e9
+        })
+        .collect()
+}
+
+#[test]
+fn step_impulse_and_ramp_start_after_baseline() {
+    let mut plugin = StimulusPlugin::new(1);
+    plugin.set_config(Shape::Step, 0.2, 2.0, -1.0);
+    assert_eq!(run(&mut plugin, 0, 5), vec![-1.0, -1.0, 1.0, 1.0, 1.0]);
+
+    let mut plugin = StimulusPlugin::new(1);
+    plugin.set_config(Shape::Impulse, 0.2, 5.0, 0.0);
+    assert_eq!(run(&mut plugin, 0, 5), vec![0.0, 0.0, 5.0, 0.0, 0.0]);
+
+    let mut plugin = StimulusPlugin::new(1);
+    plugin.set_config(Shape::Ramp, 0.1, 10.0, 1.0);
+    assert_eq!(run(&mut plugin, 0, 4), vec![1.0, 1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn chirp_sweeps_for_its_duration() {
+    let chirp = Chirp {
+        f_start: 1.0,
+        f_end: 3.0,
+        duration: 1.0,
+    };
+    assert_eq!(chirp.value(0.0), Some(0.0));
+    // Phase 2*pi*(0.25 + 0.0625) at a quarter of the sweep
+    let expected = (2.0 * std::f64::consts::PI * 0.3125).sin();
+    assert!((chirp.value(0.25).unwrap() - expected).abs() < 1e-12);
+    assert_eq!(chirp.value(1.5), None);
+
+    let mut plugin = StimulusPlugin::new(1);
+    plugin.set_config(Shape::Chirp, 0.0, 2.0, 0.5);
+    plugin.set_chirp(chirp);
+    assert_eq!(plugin.value_at(2.0, 0.1), 0.5);
+}
+
+#[test]
+fn trigger_restarts_the_stimulus() {
+    let mut plugin = StimulusPlugin::new(1);
+    plugin.set_config(Shape::Impulse, 0.0, 1.0, 0.0);
+    assert_eq!(run(&mut plugin, 0, 3), vec![1.0, 0.0, 0.0]);
+
+    plugin.set_trigger(1.0);
+    assert_eq!(run(&mut plugin, 3, 5), vec![1.0, 0.0]);
+    // Held high: no further restarts until the trigger falls and rises again
+    plugin.set_trigger(0.0);
+    assert_eq!(run(&mut plugin, 5, 6), vec![0.0]);
+
+    plugin.reset();
+    assert_eq!(run(&mut plugin, 6, 8), vec![1.0, 0.0]);
+}
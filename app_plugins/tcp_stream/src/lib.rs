@@ -0,0 +1,393 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+use std::io::{ErrorKind, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+pub const FORMATS: [&str; 2] = ["json", "csv"];
+
+const DEFAULT_PORT: u16 = 9000;
+const DEFAULT_RATE_HZ: f64 = 10.0;
+const MAX_RATE_HZ: f64 = 1000.0;
+/// How often the server thread checks for new clients and shutdown.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+/// A client that cannot take a line within this time is dropped.
+const WRITE_TIMEOUT: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "csv" => Format::Csv,
+            _ => Format::Json,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Csv => "csv",
+        }
+    }
+}
+
+/// Header line sent to CSV clients, and again whenever the input count
+/// changes.
+pub fn csv_header(input_count: usize) -> String {
+    let mut columns = vec!["time".to_string()];
+    columns.extend((0..input_count).map(|idx| format!("in_{idx}")));
+    columns.join(",")
+}
+
+/// One streamed line (without the trailing newline).
+pub fn format_line(format: Format, time: f64, values: &[f64]) -> String {
+    match format {
+        Format::Json => serde_json::json!({ "time": time, "values": values }).to_string(),
+        Format::Csv => {
+            let mut fields = vec![time.to_string()];
+            fields.extend(values.iter().map(|value| value.to_string()));
+            fields.join(",")
+        }
+    }
+}
+
+/// State shared with the server thread. The RT side only ever `try_lock`s it
+/// so a slow socket can never stall a tick.
+struct Shared {
+    format: Format,
+    rate_hz: f64,
+    time: f64,
+    values: Vec<f64>,
+    /// Set once `process()` has filled in values; nothing is sent before,
+    /// so clients never see a header for the wrong input count.
+    published: bool,
+}
+
+struct Server {
+    addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Server {
+    fn start(
+        port: u16,
+        shared: Arc<Mutex<Shared>>,
+        clients: Arc<AtomicUsize>,
+    ) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let handle = std::thread::Builder::new()
+            .name(format!("tcp_stream:{}", addr.port()))
+            .spawn(move || serve(listener, shared, clients, thread_stop))?;
+        Ok(Self {
+            addr,
+            stop,
+            handle: Some(handle),
+        })
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+struct Client {
+    stream: TcpStream,
+    /// Last CSV header sent, so a changed input count gets a fresh one.
+    header: Option<String>,
+}
+
+impl Client {
+    fn send(&mut self, format: Format, header: &str, line: &str) -> std::io::Result<()> {
+        if format == Format::Csv && self.header.as_deref() != Some(header) {
+            writeln!(self.stream, "{header}")?;
+            self.header = Some(header.to_string());
+        }
+        writeln!(self.stream, "{line}")
+    }
+}
+
+fn serve(
+    listener: TcpListener,
+    shared: Arc<Mutex<Shared>>,
+    client_count: Arc<AtomicUsize>,
+    stop: Arc<AtomicBool>,
+) {
+    let mut clients: Vec<Client> = Vec::new();
+    let mut next_send = Instant::now();
+    while !stop.load(Ordering::Relaxed) {
+        loop {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    let configured = stream
+                        .set_nonblocking(false)
+                        .and_then(|_| stream.set_write_timeout(Some(WRITE_TIMEOUT)))
+                        .and_then(|_| stream.set_nodelay(true));
+                    if configured.is_ok() {
+                        clients.push(Client {
+                            stream,
+                            header: None,
+                        });
+                    }
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(_) => break,
+            }
+        }
+
+        let now = Instant::now();
+        if now >= next_send {
+            let (format, rate_hz, time, values, published) = match shared.lock() {
+                Ok(shared) => (
+                    shared.format,
+                    shared.rate_hz,
+                    shared.time,
+                    shared.values.clone(),
+                    shared.published,
+                ),
+                Err(_) => return,
+            };
+            next_send = now + Duration::from_secs_f64(1.0 / rate_hz.clamp(0.1, MAX_RATE_HZ));
+            if published && !clients.is_empty() {
+                let header = csv_header(values.len());
+                let line = format_line(format, time, &values);
+                clients.retain_mut(|client| client.send(format, &header, &line).is_ok());
+            }
+        }
+        client_count.store(clients.len(), Ordering::Relaxed);
+        std::thread::sleep(POLL_INTERVAL.min(next_send.saturating_duration_since(Instant::now())));
+    }
+    client_count.store(0, Ordering::Relaxed);
+}
+
+pub struct TcpStreamPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    port: u16,
+    format: Format,
+    rate_hz: f64,
+    streaming: bool,
+    input_values: Vec<f64>,
+    shared: Arc<Mutex<Shared>>,
+    clients: Arc<AtomicUsize>,
+    server: Option<Server>,
+    last_error: Option<String>,
+}
+
+impl TcpStreamPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "TCP Stream".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("port".to_string(), Value::from(DEFAULT_PORT)),
+                    ("format".to_string(), Value::from("json")),
+                    ("rate_hz".to_string(), Value::from(DEFAULT_RATE_HZ)),
+                    ("input_count".to_string(), Value::from(0)),
+                ],
+            },
+            inputs: Vec::new(),
+            port: DEFAULT_PORT,
+            format: Format::Json,
+            rate_hz: DEFAULT_RATE_HZ,
+            streaming: false,
+            input_values: Vec::new(),
+            shared: Arc::new(Mutex::new(Shared {
+                format: Format::Json,
+                rate_hz: DEFAULT_RATE_HZ,
+                time: 0.0,
+                values: Vec::new(),
+                published: false,
+            })),
+            clients: Arc::new(AtomicUsize::new(0)),
+            server: None,
+            last_error: None,
+        }
+    }
+
+    /// The server listens while `streaming` is set and is restarted when the
+    /// port changes. Port 0 picks a free port, see [`Self::local_addr`].
+    pub fn set_config(
+        &mut self,
+        input_count: usize,
+        port: u16,
+        format: Format,
+        rate_hz: f64,
+        streaming: bool,
+    ) {
+        if self.port != port || !streaming {
+            self.server = None;
+            self.last_error = None;
+        }
+        self.port = port;
+        self.format = format;
+        self.rate_hz = rate_hz;
+        self.streaming = streaming;
+        // A failed bind is retried only after the port changes or the plugin
+        // is stopped and started again, not on every tick.
+        if streaming && self.server.is_none() && self.last_error.is_none() {
+            match Server::start(port, self.shared.clone(), self.clients.clone()) {
+                Ok(server) => self.server = Some(server),
+                Err(err) => self.last_error = Some(err.to_string()),
+            }
+        }
+
+        if self.inputs.len() != input_count {
+            self.inputs = (0..input_count)
+                .map(|idx| Port {
+                    id: PortId(format!("in_{idx}")),
+                })
+                .collect();
+        }
+        if self.input_values.len() != input_count {
+            self.input_values.resize(input_count, 0.0);
+        }
+    }
+
+    pub fn set_inputs(&mut self, values: Vec<f64>) {
+        self.input_values = values;
+    }
+
+    /// Address the server is bound to, if it is listening.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.server.as_ref().map(|server| server.addr)
+    }
+
+    pub fn client_count(&self) -> usize {
+        self.clients.load(Ordering::Relaxed)
+    }
+
+    /// Why the server could not bind, e.g. the port is already in use.
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+}
+
+impl Plugin for TcpStreamPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &[]
+    }
+
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
+        if self.server.is_none() {
+            return Ok(());
+        }
+        if let Ok(mut shared) = self.shared.try_lock() {
+            shared.format = self.format;
+            shared.rate_hz = self.rate_hz;
+            shared.time = ctx.tick as f64 * ctx.period_seconds;
+            shared.values.clone_from(&self.input_values);
+            shared.published = true;
+        }
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::integer("port", "Port")
+                        .min(0)
+                        .max(65535)
+                        .default_value(Value::from(DEFAULT_PORT))
+                        .hint("TCP port to listen on"),
+                )
+                .field(
+                    ConfigField::text("format", "Format")
+                        .default_value(Value::from("json"))
+                        .hint("json or csv, one line per sample"),
+                )
+                .field(
+                    ConfigField::float("rate_hz", "Rate (Hz)")
+                        .min_f(0.1)
+                        .max_f(MAX_RATE_HZ)
+                        .default_value(Value::from(DEFAULT_RATE_HZ))
+                        .hint("Lines sent per second to each client"),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: false,
+            extendable_inputs: ExtendableInputs::Auto {
+                pattern: "in_{}".to_string(),
+            },
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec![],
+            inputs: self.inputs.iter().map(|p| p.id.0.clone()).collect(),
+            variables: vec!["clients".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "port" => Some(Value::from(self.port)),
+            "format" => Some(Value::from(self.format.name())),
+            "rate_hz" => Some(Value::from(self.rate_hz)),
+            "clients" => Some(Value::from(self.client_count())),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        let input_count = self.inputs.len();
+        match name {
+            "port" => {
+                if let Some(port) = value.as_u64().and_then(|v| u16::try_from(v).ok()) {
+                    self.set_config(input_count, port, self.format, self.rate_hz, self.streaming);
+                }
+            }
+            "format" => {
+                if let Some(format) = value.as_str() {
+                    self.format = Format::from_name(format);
+                }
+            }
+            "rate_hz" => {
+                if let Some(rate_hz) = value.as_f64() {
+                    self.rate_hz = rate_hz;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use rtsyn_plugin::prelude::*;
+use std::io::{BufRead, BufReader};
+use std::net::TcpStream;
+use std::time::{Duration, Instant};
+use tcp_stream_plugin::{csv_header, format_line, Format, TcpStreamPlugin};
+
+#[test]
+fn lines_are_formatted_as_json_or_csv() {
+    assert_eq!(csv_header(2), "time,in_0,in_1");
+    assert_eq!(format_line(Format::Csv, 0.5, &[1.0, -2.25]), "0.5,1,-2.25");
+    let json: serde_json::Value =
+        serde_json::from_str(&format_line(Format::Json, 0.5, &[1.0, -2.25])).unwrap();
+    assert_eq!(json, serde_json::json!({ "time": 0.5, "values": [1.0, -2.25] }));
+    assert_eq!(Format::from_name("csv"), Format::Csv);
+    assert_eq!(Format::from_name("unknown"), Format::Json);
+}
+
+#[test]
+fn connected_clients_receive_the_latest_inputs() {
+    let mut plugin = TcpStreamPlugin::new(1);
+    plugin.set_config(2, 0, Format::Csv, 100.0, true);
+    let addr = plugin.local_addr().expect("server is listening");
+    assert_eq!(plugin.inputs().len(), 2);
+
+    let stream = TcpStream::connect(("127.0.0.1", addr.port())).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let mut reader = BufReader::new(stream);
+
+    let mut ctx = PluginContext {
+        tick: 20,
+        period_seconds: 0.1,
+    };
+    plugin.set_inputs(vec![1.5, 3.0]);
+    plugin.process(&mut ctx).unwrap();
+
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    assert_eq!(line.trim_end(), "time,in_0,in_1");
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        if line.trim_end() == "2,1.5,3" || Instant::now() > deadline {
+            break;
+        }
+    }
+    assert_eq!(line.trim_end(), "2,1.5,3");
+    assert_eq!(plugin.client_count(), 1);
+
+    plugin.set_config(2, 0, Format::Csv, 100.0, false);
+    assert!(plugin.local_addr().is_none());
+}
+
+#[test]
+fn bind_failures_are_reported() {
+    let mut first = TcpStreamPlugin::new(1);
+    first.set_config(0, 0, Format::Json, 10.0, true);
+    let port = first.local_addr().unwrap().port();
+
+    let mut second = TcpStreamPlugin::new(2);
+    second.set_config(0, port, Format::Json, 10.0, true);
+    assert!(second.local_addr().is_none());
+    assert!(second.last_error().is_some());
+}
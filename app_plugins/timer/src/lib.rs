@@ -0,0 +1,227 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+pub const MODES: [&str; 3] = ["one_shot", "retriggerable", "periodic"];
+
+/// Trigger level; a rising edge is a crossing from at-or-below to above it.
+const TRIGGER_THRESHOLD: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Triggers while the pulse is active are ignored.
+    OneShot,
+    /// A trigger while active restarts the full duration.
+    Retriggerable,
+    /// Ignores the trigger and alternates `duration_ms` on, `duration_ms` off.
+    Periodic,
+}
+
+impl Mode {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "retriggerable" => Mode::Retriggerable,
+            "periodic" => Mode::Periodic,
+            _ => Mode::OneShot,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mode::OneShot => "one_shot",
+            Mode::Retriggerable => "retriggerable",
+            Mode::Periodic => "periodic",
+        }
+    }
+}
+
+pub struct TimerPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    duration_ms: f64,
+    mode: Mode,
+    trigger: f64,
+    prev_trigger: f64,
+    /// Pulse time left in seconds (one-shot and retriggerable).
+    remaining: f64,
+    /// Time into the current on or off phase (periodic), `None` before the
+    /// first tick.
+    phase: Option<f64>,
+    active: bool,
+}
+
+impl TimerPlugin {
+    pub fn new(id: u64) -> Self {
+        Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Timer".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("duration_ms".to_string(), Value::from(1000.0)),
+                    ("mode".to_string(), Value::from("one_shot")),
+                ],
+            },
+            inputs: vec![Port { id: PortId("trigger".to_string()) }],
+            outputs: vec![Port { id: PortId("active".to_string()) }],
+            duration_ms: 1000.0,
+            mode: Mode::OneShot,
+            trigger: 0.0,
+            prev_trigger: 0.0,
+            remaining: 0.0,
+            phase: None,
+            active: false,
+        }
+    }
+
+    /// Changing the mode starts over from an idle timer.
+    pub fn set_config(&mut self, duration_ms: f64, mode: Mode) {
+        self.duration_ms = duration_ms.max(0.0);
+        if mode != self.mode {
+            self.mode = mode;
+            self.reset();
+        }
+    }
+
+    pub fn set_input(&mut self, trigger: f64) {
+        self.trigger = trigger;
+    }
+
+    pub fn reset(&mut self) {
+        self.prev_trigger = 0.0;
+        self.remaining = 0.0;
+        self.phase = None;
+        self.active = false;
+    }
+
+    /// 1.0 while the pulse is on, 0.0 otherwise.
+    pub fn active(&self) -> f64 {
+        if self.active {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Plugin for TimerPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, ctx: &mut PluginContext) -> Result<(), PluginError> {
+        let time_step = ctx.period_seconds.max(0.0);
+        // Durations round to the nearest tick
+        let half_step = time_step * 0.5;
+        let duration = self.duration_ms / 1000.0;
+        let rising = self.prev_trigger <= TRIGGER_THRESHOLD && self.trigger > TRIGGER_THRESHOLD;
+        self.prev_trigger = self.trigger;
+
+        match self.mode {
+            Mode::OneShot | Mode::Retriggerable => {
+                // Account for the tick the pulse was already on
+                if self.active {
+                    self.remaining -= time_step;
+                }
+                let still_active = self.remaining > half_step;
+                if rising && (self.mode == Mode::Retriggerable || !still_active) {
+                    self.remaining = duration;
+                }
+                self.active = self.remaining > half_step;
+            }
+            Mode::Periodic => {
+                if duration <= half_step {
+                    self.active = false;
+                    return Ok(());
+                }
+                match self.phase.as_mut() {
+                    None => {
+                        self.phase = Some(0.0);
+                        self.active = true;
+                    }
+                    Some(phase) => {
+                        *phase += time_step;
+                        if *phase >= duration - half_step {
+                            *phase -= duration;
+                            self.active = !self.active;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::float("duration_ms", "Duration (ms)")
+                        .min_f(0.0)
+                        .step_f(10.0)
+                        .default_value(Value::from(1000.0))
+                        .hint("Pulse length; in periodic mode also the off time"),
+                )
+                .field(
+                    ConfigField::text("mode", "Mode")
+                        .default_value(Value::from("one_shot"))
+                        .hint("one_shot, retriggerable or periodic"),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["active".to_string()],
+            inputs: vec!["trigger".to_string()],
+            variables: vec!["running".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "duration_ms" => Some(Value::from(self.duration_ms)),
+            "mode" => Some(Value::from(self.mode.name())),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        match name {
+            "duration_ms" => {
+                if let Some(v) = value.as_f64() {
+                    self.set_config(v, self.mode);
+                }
+            }
+            "mode" => {
+                if let Some(v) = value.as_str() {
+                    self.set_config(self.duration_ms, Mode::from_name(v));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
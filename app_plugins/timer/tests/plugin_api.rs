@@ -0,0 +1,54 @@
+use rtsyn_plugin::prelude::*;
+use timer_plugin::{Mode, TimerPlugin};
+
+/// Runs one tick per trigger value at 100 ms per tick and returns `active`.
+fn run(plugin: &mut TimerPlugin, triggers: &[f64]) -> Vec<f64> {
+    let mut ctx = PluginContext {
+        period_seconds: 0.1,
+        ..PluginContext::default()
+    };
+    triggers
+        .iter()
+        .map(|trigger| {
+            plugin.set_input(*trigger);
+            plugin.process(&mut ctx).unwrap();
+            plugin.active()
+        })
+        .collect()
+}
+
+#[test]
+fn one_shot_ignores_triggers_while_active() {
+    let mut plugin = TimerPlugin::new(1);
+    plugin.set_config(300.0, Mode::OneShot);
+    assert_eq!(
+        run(&mut plugin, &[0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0]),
+        vec![0.0, 1.0, 1.0, 1.0, 0.0, 0.0, 1.0]
+    );
+}
+
+#[test]
+fn retriggerable_restarts_the_duration() {
+    let mut plugin = TimerPlugin::new(1);
+    plugin.set_config(300.0, Mode::Retriggerable);
+    assert_eq!(
+        run(&mut plugin, &[1.0, 0.0, 1.0, 0.0, 0.0, 0.0]),
+        vec![1.0, 1.0, 1.0, 1.0, 1.0, 0.0]
+    );
+}
+
+#[test]
+fn periodic_alternates_on_its_own() {
+    let mut plugin = TimerPlugin::new(1);
+    plugin.set_config(200.0, Mode::Periodic);
+    assert_eq!(
+        run(&mut plugin, &[0.0; 7]),
+        vec![1.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0]
+    );
+
+    // Switching modes starts from an idle timer
+    plugin.set_config(200.0, Mode::OneShot);
+    assert_eq!(plugin.active(), 0.0);
+    assert_eq!(Mode::from_name("periodic"), Mode::Periodic);
+    assert_eq!(Mode::from_name("bogus"), Mode::OneShot);
+}
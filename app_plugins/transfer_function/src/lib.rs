@@ -0,0 +1,262 @@
+use rtsyn_plugin::prelude::*;
+use serde_json::Value;
+
+/// Checks coefficients for [`TransferFunctionPlugin::set_config`]: `a` must
+/// have a nonzero first entry and every coefficient must be finite.
+pub fn validate(b: &[f64], a: &[f64]) -> Result<(), String> {
+    match a.first() {
+        None => return Err("a needs at least one coefficient".to_string()),
+        Some(a0) if *a0 == 0.0 => return Err("a[0] must not be 0".to_string()),
+        Some(_) => {}
+    }
+    let bad = |name: &str, coefficients: &[f64]| {
+        coefficients
+            .iter()
+            .position(|c| !c.is_finite())
+            .map(|idx| format!("{name}[{idx}] is not a finite number"))
+    };
+    match bad("b", b).or_else(|| bad("a", a)) {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// Discrete transfer function
+/// `H(z) = (b[0] + b[1] z^-1 + ...) / (a[0] + a[1] z^-1 + ...)`, run as the
+/// difference equation `a[0] y[n] = sum b[k] x[n-k] - sum a[k] y[n-k]` in
+/// transposed direct form II. With `a = [1]` it is an FIR filter.
+///
+/// Invalid coefficients leave the output at 0 and are reported in the
+/// `error` variable.
+pub struct TransferFunctionPlugin {
+    id: PluginId,
+    meta: PluginMeta,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    b: Vec<f64>,
+    a: Vec<f64>,
+    /// `b` and `a` divided by `a[0]` and padded to the same length; empty
+    /// while the coefficients are invalid.
+    normalized: Vec<(f64, f64)>,
+    state: Vec<f64>,
+    error: Option<String>,
+    input: f64,
+    out: f64,
+}
+
+impl TransferFunctionPlugin {
+    pub fn new(id: u64) -> Self {
+        let mut plugin = Self {
+            id: PluginId(id),
+            meta: PluginMeta {
+                name: "Transfer Function".to_string(),
+                fixed_vars: Vec::new(),
+                default_vars: vec![
+                    ("b".to_string(), Value::from(vec![1.0])),
+                    ("a".to_string(), Value::from(vec![1.0])),
+                    ("warmup_ticks".to_string(), Value::from(0)),
+                ],
+            },
+            inputs: vec![Port { id: PortId("in".to_string()) }],
+            outputs: vec![Port { id: PortId("out".to_string()) }],
+            b: Vec::new(),
+            a: Vec::new(),
+            normalized: Vec::new(),
+            state: Vec::new(),
+            error: None,
+            input: 0.0,
+            out: 0.0,
+        };
+        plugin.set_config(&[1.0], &[1.0]);
+        plugin
+    }
+
+    /// Changing the coefficients clears the filter state.
+    pub fn set_config(&mut self, b: &[f64], a: &[f64]) {
+        if self.b == b && self.a == a {
+            return;
+        }
+        self.b = b.to_vec();
+        self.a = a.to_vec();
+        self.error = validate(b, a).err();
+        self.normalized = match self.error {
+            Some(_) => Vec::new(),
+            None => {
+                let len = b.len().max(a.len());
+                let coefficient = |list: &[f64], idx: usize| list.get(idx).copied().unwrap_or(0.0) / a[0];
+                (0..len).map(|idx| (coefficient(b, idx), coefficient(a, idx))).collect()
+            }
+        };
+        self.state = vec![0.0; self.normalized.len().saturating_sub(1)];
+        self.out = 0.0;
+    }
+
+    pub fn set_input(&mut self, value: f64) {
+        self.input = value;
+    }
+
+    /// Clears the filter state, as if the input had always been 0.
+    pub fn reset(&mut self) {
+        self.state.fill(0.0);
+        self.out = 0.0;
+    }
+
+    pub fn out(&self) -> f64 {
+        self.out
+    }
+
+    /// Why the coefficients were rejected, if they were.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Checks the coefficients and that a fresh filter built from them
+    /// answers a unit impulse like the difference equation computed
+    /// directly. The running filter's state is not touched.
+    pub fn self_test(&mut self) -> Result<(), String> {
+        validate(&self.b, &self.a)?;
+        let mut filter = TransferFunctionPlugin::new(self.id.0);
+        filter.set_config(&self.b, &self.a);
+        let samples = self.b.len().max(self.a.len()) + SELF_TEST_EXTRA_SAMPLES;
+        let mut expected: Vec<f64> = Vec::with_capacity(samples);
+        for n in 0..samples {
+            let feedforward = self.b.get(n).copied().unwrap_or(0.0);
+            let feedback: f64 = (1..self.a.len().min(n + 1))
+                .map(|k| self.a[k] * expected[n - k])
+                .sum();
+            let y = (feedforward - feedback) / self.a[0];
+            filter.set_input(if n == 0 { 1.0 } else { 0.0 });
+            let _ = filter.process(&mut PluginContext::default());
+            let tolerance = 1e-9 * y.abs().max(1.0);
+            if y.is_finite() && (filter.out() - y).abs() > tolerance {
+                return Err(format!(
+                    "impulse response sample {n} is {}, expected {y}",
+                    filter.out()
+                ));
+            }
+            expected.push(y);
+        }
+        Ok(())
+    }
+}
+
+/// Impulse response samples checked beyond the filter order.
+const SELF_TEST_EXTRA_SAMPLES: usize = 16;
+
+impl Plugin for TransferFunctionPlugin {
+    fn id(&self) -> PluginId {
+        self.id
+    }
+
+    fn meta(&self) -> &PluginMeta {
+        &self.meta
+    }
+
+    fn inputs(&self) -> &[Port] {
+        &self.inputs
+    }
+
+    fn outputs(&self) -> &[Port] {
+        &self.outputs
+    }
+
+    fn process(&mut self, _ctx: &mut PluginContext) -> Result<(), PluginError> {
+        let Some(&(b0, _)) = self.normalized.first() else {
+            self.out = 0.0;
+            return Ok(());
+        };
+        // A non-finite sample would stay in the state forever
+        if !self.input.is_finite() {
+            return Ok(());
+        }
+        let x = self.input;
+        let y = b0 * x + self.state.first().copied().unwrap_or(0.0);
+        let order = self.state.len();
+        for idx in 0..order {
+            let (b, a) = self.normalized[idx + 1];
+            let next = self.state.get(idx + 1).copied().unwrap_or(0.0);
+            self.state[idx] = b * x - a * y + next;
+        }
+        self.out = y;
+        Ok(())
+    }
+
+    fn ui_schema(&self) -> Option<UISchema> {
+        let coefficient = FieldType::Float {
+            min: None,
+            max: None,
+            step: 0.01,
+        };
+        Some(
+            UISchema::new()
+                .field(
+                    ConfigField::dynamic_list("b", "Numerator b")
+                        .item_type(coefficient.clone())
+                        .add_label("Add b")
+                        .hint("b[0] + b[1] z^-1 + ..."),
+                )
+                .field(
+                    ConfigField::dynamic_list("a", "Denominator a")
+                        .item_type(coefficient)
+                        .add_label("Add a")
+                        .hint("a[0] + a[1] z^-1 + ...; a[0] must not be 0"),
+                )
+                .field(
+                    ConfigField::integer("warmup_ticks", "Warmup (ticks)")
+                        .min(0)
+                        .default_value(Value::from(0))
+                        .hint("Outputs are held at 0 for this many ticks after start"),
+                ),
+        )
+    }
+
+    fn behavior(&self) -> PluginBehavior {
+        PluginBehavior {
+            supports_start_stop: true,
+            supports_restart: true,
+            extendable_inputs: ExtendableInputs::None,
+            loads_started: false,
+        }
+    }
+
+    fn display_schema(&self) -> Option<DisplaySchema> {
+        Some(DisplaySchema {
+            outputs: vec!["out".to_string()],
+            inputs: vec!["in".to_string()],
+            variables: vec!["error".to_string()],
+        })
+    }
+
+    fn get_variable(&self, name: &str) -> Option<Value> {
+        match name {
+            "b" => Some(Value::from(self.b.clone())),
+            "a" => Some(Value::from(self.a.clone())),
+            "error" => Some(Value::from(self.error.clone().unwrap_or_default())),
+            _ => None,
+        }
+    }
+
+    fn set_variable(&mut self, name: &str, value: Value) -> Result<(), PluginError> {
+        let list = || {
+            value
+                .as_array()
+                .map(|items| items.iter().map(|v| v.as_f64().unwrap_or(f64::NAN)).collect::<Vec<f64>>())
+        };
+        match name {
+            "b" => {
+                if let Some(b) = list() {
+                    let a = self.a.clone();
+                    self.set_config(&b, &a);
+                }
+            }
+            "a" => {
+                if let Some(a) = list() {
+                    let b = self.b.clone();
+                    self.set_config(&b, &a);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
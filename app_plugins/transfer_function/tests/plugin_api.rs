@@ -0,0 +1,64 @@
+use rtsyn_plugin::prelude::*;
+use transfer_function_plugin::TransferFunctionPlugin;
+
+fn run(plugin: &mut TransferFunctionPlugin, inputs: &[f64]) -> Vec<f64> {
+    inputs
+        .iter()
+        .map(|input| {
+            plugin.set_input(*input);
+            plugin.process(&mut PluginContext::default()).unwrap();
+            plugin.out()
+        })
+        .collect()
+}
+
+#[test]
+fn fir_and_iir_follow_the_difference_equation() {
+    let mut plugin = TransferFunctionPlugin::new(1);
+    // Pass-through by default
+    assert_eq!(run(&mut plugin, &[1.0, -2.0]), [1.0, -2.0]);
+
+    plugin.set_config(&[0.5, 0.5], &[1.0]);
+    assert_eq!(run(&mut plugin, &[1.0, 0.0, 0.0]), [0.5, 0.5, 0.0]);
+
+    // y[n] = 0.5 x[n] + 0.5 y[n-1], given unnormalized
+    plugin.set_config(&[1.0], &[2.0, -1.0]);
+    assert_eq!(run(&mut plugin, &[1.0, 1.0, 1.0]), [0.5, 0.75, 0.875]);
+
+    // Second order with a longer numerator than denominator
+    plugin.set_config(&[1.0, 2.0, 1.0], &[1.0, -0.5]);
+    assert_eq!(run(&mut plugin, &[1.0, 0.0, 0.0, 0.0]), [1.0, 2.5, 2.25, 1.125]);
+
+    plugin.reset();
+    assert_eq!(run(&mut plugin, &[0.0]), [0.0]);
+    assert_eq!(run(&mut plugin, &[f64::NAN, 0.0]), [0.0, 0.0]);
+}
+
+#[test]
+fn invalid_denominator_is_reported_and_silences_the_output() {
+    let mut plugin = TransferFunctionPlugin::new(1);
+    plugin.set_config(&[1.0], &[]);
+    assert_eq!(plugin.error(), Some("a needs at least one coefficient"));
+    assert_eq!(run(&mut plugin, &[1.0]), [0.0]);
+
+    plugin.set_variable("a", serde_json::json!([0.0, 1.0])).unwrap();
+    assert_eq!(plugin.get_variable("error"), Some("a[0] must not be 0".into()));
+
+    plugin.set_variable("a", serde_json::json!([1.0])).unwrap();
+    assert_eq!(plugin.get_variable("error"), Some("".into()));
+    assert_eq!(run(&mut plugin, &[3.0]), [3.0]);
+}
+
+#[test]
+fn self_test_checks_the_impulse_response_without_touching_the_state() {
+    let mut plugin = TransferFunctionPlugin::new(1);
+    plugin.set_config(&[1.0, 2.0, 1.0], &[2.0, -1.0, 0.25]);
+    run(&mut plugin, &[1.0]);
+    let out = plugin.out();
+    assert_eq!(plugin.self_test(), Ok(()));
+    assert_eq!(plugin.out(), out);
+    assert_eq!(run(&mut plugin, &[0.0]), [1.25]);
+
+    plugin.set_config(&[1.0], &[0.0]);
+    assert_eq!(plugin.self_test(), Err("a[0] must not be 0".to_string()));
+}
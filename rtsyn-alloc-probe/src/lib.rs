@@ -0,0 +1,110 @@
+//! Counts the heap allocations of a process by interposing the C allocator.
+//!
+//! `rtsyn benchmark` preloads this library with `LD_PRELOAD` into a child
+//! process. A plugin library allocates through its own copy of std or
+//! straight through `malloc`, both of which end up here, so its allocations
+//! are seen where a `#[global_allocator]` in the host would miss them.
+//! Only the allocations of a thread that switched counting on are counted,
+//! so other threads of the process do not add to a measurement.
+#![cfg(all(target_os = "linux", target_env = "gnu"))]
+
+use std::cell::Cell;
+use std::ffi::{c_int, c_void};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+thread_local! {
+    /// Whether allocations of this thread are counted. A const initializer
+    /// without a destructor, so reading it from `malloc` never allocates.
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+}
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+
+extern "C" {
+    fn __libc_malloc(size: usize) -> *mut c_void;
+    fn __libc_calloc(count: usize, size: usize) -> *mut c_void;
+    fn __libc_realloc(ptr: *mut c_void, size: usize) -> *mut c_void;
+    fn __libc_memalign(align: usize, size: usize) -> *mut c_void;
+}
+
+fn count(bytes: usize) {
+    if COUNTING.try_with(Cell::get).unwrap_or(false) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+}
+
+/// Starts or stops counting the allocations of the calling thread. The
+/// counts are kept across calls.
+#[no_mangle]
+pub extern "C" fn rtsyn_alloc_probe_set_counting(on: bool) {
+    COUNTING.with(|counting| counting.set(on));
+}
+
+/// Writes the allocations and allocated bytes counted so far.
+///
+/// # Safety
+/// Both pointers must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rtsyn_alloc_probe_counts(allocations: *mut u64, bytes: *mut u64) {
+    *allocations = ALLOCATIONS.load(Ordering::SeqCst);
+    *bytes = BYTES.load(Ordering::SeqCst);
+}
+
+/// # Safety
+/// Same contract as the C `malloc`.
+#[no_mangle]
+pub unsafe extern "C" fn malloc(size: usize) -> *mut c_void {
+    count(size);
+    __libc_malloc(size)
+}
+
+/// # Safety
+/// Same contract as the C `calloc`.
+#[no_mangle]
+pub unsafe extern "C" fn calloc(items: usize, size: usize) -> *mut c_void {
+    count(items.saturating_mul(size));
+    __libc_calloc(items, size)
+}
+
+/// # Safety
+/// Same contract as the C `realloc`.
+#[no_mangle]
+pub unsafe extern "C" fn realloc(ptr: *mut c_void, size: usize) -> *mut c_void {
+    count(size);
+    __libc_realloc(ptr, size)
+}
+
+/// # Safety
+/// Same contract as the C `memalign`.
+#[no_mangle]
+pub unsafe extern "C" fn memalign(align: usize, size: usize) -> *mut c_void {
+    count(size);
+    __libc_memalign(align, size)
+}
+
+/// # Safety
+/// Same contract as the C `aligned_alloc`.
+#[no_mangle]
+pub unsafe extern "C" fn aligned_alloc(align: usize, size: usize) -> *mut c_void {
+    count(size);
+    __libc_memalign(align, size)
+}
+
+/// # Safety
+/// Same contract as the C `posix_memalign`.
+#[no_mangle]
+pub unsafe extern "C" fn posix_memalign(out: *mut *mut c_void, align: usize, size: usize) -> c_int {
+    const EINVAL: c_int = 22;
+    const ENOMEM: c_int = 12;
+    if !align.is_power_of_two() || !align.is_multiple_of(std::mem::size_of::<*mut c_void>()) {
+        return EINVAL;
+    }
+    count(size);
+    let ptr = __libc_memalign(align, size);
+    if ptr.is_null() && size != 0 {
+        return ENOMEM;
+    }
+    *out = ptr;
+    0
+}
@@ -94,6 +94,8 @@ impl DaemonState {
             time_label: "time_ms".to_string(),
             ui_hz: 60.0,
             max_integration_steps: 10,
+            missed_deadline_policy: Default::default(),
+            jitter_us: 0.0,
         };
         Self {
             catalog,
@@ -110,7 +112,7 @@ impl DaemonState {
         let _ = self
             .runtime_query
             .logic_tx
-            .send(LogicMessage::UpdateWorkspace(self.workspace_manager.workspace.clone()));
+            .send(LogicMessage::UpdateWorkspace(self.workspace_manager.workspace.resolve_parameters()));
     }
 
     fn drain_logic_states(&mut self) {
@@ -309,6 +311,8 @@ fn handle_client(stream: UnixStream, state: &mut DaemonState) -> Result<(), Stri
                     description: entry.description,
                     plugins: entry.plugins,
                     plugin_kinds: entry.plugin_kinds,
+                    author: entry.metadata.author,
+                    modified_at: entry.metadata.modified_at,
                 })
                 .collect();
             DaemonResponse::WorkspaceList { workspaces }
@@ -348,6 +352,9 @@ fn handle_client(stream: UnixStream, state: &mut DaemonState) -> Result<(), Stri
                         state.logic_settings.period_seconds = runtime_settings.period_seconds;
                         state.logic_settings.time_scale = runtime_settings.time_scale;
                         state.logic_settings.time_label = runtime_settings.time_label;
+                        state.logic_settings.missed_deadline_policy =
+                            runtime_settings.missed_deadline_policy;
+                        state.logic_settings.jitter_us = runtime_settings.jitter_us;
                         let _ = state
                             .runtime_query
                             .logic_tx
@@ -428,6 +435,9 @@ fn handle_client(stream: UnixStream, state: &mut DaemonState) -> Result<(), Stri
                         state.logic_settings.period_seconds = runtime_settings.period_seconds;
                         state.logic_settings.time_scale = runtime_settings.time_scale;
                         state.logic_settings.time_label = runtime_settings.time_label;
+                        state.logic_settings.missed_deadline_policy =
+                            runtime_settings.missed_deadline_policy;
+                        state.logic_settings.jitter_us = runtime_settings.jitter_us;
                         let _ = state
                             .runtime_query
                             .logic_tx
@@ -455,6 +465,7 @@ fn handle_client(stream: UnixStream, state: &mut DaemonState) -> Result<(), Stri
                     to_plugin: conn.to_plugin,
                     to_port: conn.to_port.clone(),
                     kind: conn.kind.clone(),
+                    gain: conn.gain,
                 })
                 .collect();
             DaemonResponse::ConnectionList { connections }
@@ -474,6 +485,7 @@ fn handle_client(stream: UnixStream, state: &mut DaemonState) -> Result<(), Stri
                     to_plugin: conn.to_plugin,
                     to_port: conn.to_port.clone(),
                     kind: conn.kind.clone(),
+                    gain: conn.gain,
                 })
                 .collect();
             DaemonResponse::ConnectionList { connections }
@@ -484,6 +496,7 @@ fn handle_client(stream: UnixStream, state: &mut DaemonState) -> Result<(), Stri
             to_plugin,
             to_port,
             kind,
+            gain,
         } => {
             let from_exists = state
                 .workspace_manager
@@ -556,11 +569,14 @@ fn handle_client(stream: UnixStream, state: &mut DaemonState) -> Result<(), Stri
                             match rtsyn_core::connection::add_connection(
                                 &mut state.workspace_manager.workspace,
                                 &state.catalog.manager.installed_plugins,
-                                from_plugin,
-                                &from_port,
-                                to_plugin,
-                                &to_port,
-                                &kind,
+                                workspace::ConnectionDefinition {
+                                    from_plugin,
+                                    from_port,
+                                    to_plugin,
+                                    to_port,
+                                    kind,
+                                    gain,
+                                },
                             ) {
                                 Ok(()) => {
                                     state.refresh_runtime();
@@ -585,11 +601,14 @@ fn handle_client(stream: UnixStream, state: &mut DaemonState) -> Result<(), Stri
                             match rtsyn_core::connection::add_connection(
                                 &mut state.workspace_manager.workspace,
                                 &state.catalog.manager.installed_plugins,
-                                from_plugin,
-                                &from_port,
-                                to_plugin,
-                                &to_port,
-                                &kind,
+                                workspace::ConnectionDefinition {
+                                    from_plugin,
+                                    from_port,
+                                    to_plugin,
+                                    to_port,
+                                    kind,
+                                    gain,
+                                },
                             ) {
                                 Ok(()) => {
                                     state.refresh_runtime();
@@ -692,6 +711,10 @@ fn handle_client(stream: UnixStream, state: &mut DaemonState) -> Result<(), Stri
                 min_period_value: 1.0,
                 max_integration_steps_min: 1,
                 max_integration_steps_max: 100,
+                missed_deadline_policies: workspace::MissedDeadlinePolicy::ALL
+                    .iter()
+                    .map(|policy| policy.name().to_string())
+                    .collect(),
             },
         },
         DaemonRequest::RuntimeSettingsSet { json } => {
@@ -702,6 +725,9 @@ fn handle_client(stream: UnixStream, state: &mut DaemonState) -> Result<(), Stri
                         state.logic_settings.period_seconds = runtime_settings.period_seconds;
                         state.logic_settings.time_scale = runtime_settings.time_scale;
                         state.logic_settings.time_label = runtime_settings.time_label;
+                        state.logic_settings.missed_deadline_policy =
+                            runtime_settings.missed_deadline_policy;
+                        state.logic_settings.jitter_us = runtime_settings.jitter_us;
                         let _ = state
                             .runtime_query
                             .logic_tx
@@ -986,23 +1012,8 @@ fn build_plotter_series_names(
         }
     }
 
-    let name_by_kind: std::collections::HashMap<String, String> = installed
-        .iter()
-        .map(|plugin| (plugin.manifest.kind.clone(), plugin.manifest.name.clone()))
-        .collect();
     let plugin_display_name = |plugin_id: u64| {
-        manager
-            .workspace
-            .plugins
-            .iter()
-            .find(|plugin| plugin.id == plugin_id)
-            .map(|plugin| {
-                name_by_kind
-                    .get(&plugin.kind)
-                    .cloned()
-                    .unwrap_or_else(|| rtsyn_core::plugin::PluginManager::display_kind(&plugin.kind))
-            })
-            .unwrap_or_else(|| "plugin".to_string())
+        rtsyn_core::plugin::plugin_display_name(installed, &manager.workspace, plugin_id)
     };
 
     let mut names = Vec::with_capacity(input_count);
@@ -18,6 +18,11 @@ pub struct WorkspaceSummary {
     pub description: String,
     pub plugins: usize,
     pub plugin_kinds: Vec<String>,
+    #[serde(default)]
+    pub author: String,
+    /// Unix seconds of the last save, zero when unknown.
+    #[serde(default)]
+    pub modified_at: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +33,12 @@ pub struct ConnectionSummary {
     pub to_plugin: u64,
     pub to_port: String,
     pub kind: String,
+    #[serde(default = "default_gain")]
+    pub gain: f64,
+}
+
+fn default_gain() -> f64 {
+    1.0
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,6 +63,8 @@ pub struct RuntimeSettingsOptions {
     pub min_period_value: f64,
     pub max_integration_steps_min: usize,
     pub max_integration_steps_max: usize,
+    #[serde(default)]
+    pub missed_deadline_policies: Vec<String>,
 }
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -77,6 +90,8 @@ pub enum DaemonRequest {
         to_plugin: u64,
         to_port: String,
         kind: String,
+        #[serde(default = "default_gain")]
+        gain: f64,
     },
     ConnectionRemove {
         from_plugin: u64,
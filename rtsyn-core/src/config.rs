@@ -0,0 +1,148 @@
+use rtsyn_plugin::ui::{FieldType, UISchema};
+use serde_json::Value;
+
+/// A config value that is present but cannot be read as the expected type.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("{key} should be {expected} but is {found}")]
+pub struct ConfigValueError {
+    pub key: String,
+    pub expected: &'static str,
+    pub found: Value,
+}
+
+fn numeric_string(value: &Value) -> Option<f64> {
+    value.as_str()?.trim().parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+/// Reads one config value as a number, the way [`read_f64`] reads a key.
+pub fn parse_f64(value: &Value) -> Option<f64> {
+    value.as_f64().or_else(|| numeric_string(value))
+}
+
+fn parse_u64(value: &Value) -> Option<u64> {
+    value.as_u64().or_else(|| {
+        value
+            .as_f64()
+            .or_else(|| numeric_string(value))
+            .filter(|v| *v >= 0.0 && v.fract() == 0.0 && *v <= u64::MAX as f64)
+            .map(|v| v as u64)
+    })
+}
+
+fn parse_f64_array(value: &Value) -> Option<Vec<f64>> {
+    value.as_array()?.iter().map(parse_f64).collect()
+}
+
+/// Reads `key` as a number. Numeric strings such as `"1.5"` are accepted;
+/// a missing or null key is `Ok(None)`, anything else an error.
+pub fn read_f64(config: &Value, key: &str) -> Result<Option<f64>, ConfigValueError> {
+    let value = match config.get(key) {
+        None | Some(Value::Null) => return Ok(None),
+        Some(value) => value,
+    };
+    parse_f64(value).map(Some).ok_or_else(|| ConfigValueError {
+        key: key.to_string(),
+        expected: "a number",
+        found: value.clone(),
+    })
+}
+
+/// Reads `key` as a non-negative whole number, like [`read_f64`]. Floats
+/// without a fraction such as `8.0` are accepted.
+pub fn read_u64(config: &Value, key: &str) -> Result<Option<u64>, ConfigValueError> {
+    let value = match config.get(key) {
+        None | Some(Value::Null) => return Ok(None),
+        Some(value) => value,
+    };
+    parse_u64(value).map(Some).ok_or_else(|| ConfigValueError {
+        key: key.to_string(),
+        expected: "a whole number of at least 0",
+        found: value.clone(),
+    })
+}
+
+/// Reads `key` as an array of numbers, each read like [`read_f64`]. One
+/// malformed item makes the whole array an error, so a typo cannot turn a
+/// coefficient into 0.
+pub fn read_f64_array(config: &Value, key: &str) -> Result<Option<Vec<f64>>, ConfigValueError> {
+    let value = match config.get(key) {
+        None | Some(Value::Null) => return Ok(None),
+        Some(value) => value,
+    };
+    parse_f64_array(value).map(Some).ok_or_else(|| ConfigValueError {
+        key: key.to_string(),
+        expected: "a list of numbers",
+        found: value.clone(),
+    })
+}
+
+// The `_or` readers run on every tick, so they neither allocate nor log.
+// Malformed values are reported once when the config changes, through
+// [`malformed_numbers`].
+
+/// [`read_f64`] with `default` for missing or malformed keys.
+pub fn f64_or(config: &Value, key: &str, default: f64) -> f64 {
+    config.get(key).and_then(parse_f64).unwrap_or(default)
+}
+
+/// [`read_u64`] with `default` for missing or malformed keys.
+pub fn u64_or(config: &Value, key: &str, default: u64) -> u64 {
+    config.get(key).and_then(parse_u64).unwrap_or(default)
+}
+
+/// [`read_f64_array`] with `default` for missing or malformed keys.
+pub fn f64_array_or(config: &Value, key: &str, default: &[f64]) -> Vec<f64> {
+    config
+        .get(key)
+        .and_then(parse_f64_array)
+        .unwrap_or_else(|| default.to_vec())
+}
+
+/// `value` as the runtime reads a field of type `field_type`: numeric strings
+/// of number fields, and of lists of numbers, become numbers, so config
+/// editors show and write back what the runtime uses.
+pub fn normalize_number(value: Value, field_type: &FieldType) -> Value {
+    match field_type {
+        FieldType::Float { .. } => parse_f64(&value).map(Value::from).unwrap_or(value),
+        FieldType::Integer { .. } => parse_f64(&value)
+            .filter(|v| v.fract() == 0.0)
+            .map(|v| Value::from(v as i64))
+            .unwrap_or(value),
+        FieldType::DynamicList { item_type, .. } if matches!(**item_type, FieldType::Float { .. }) => {
+            parse_f64_array(&value).map(Value::from).unwrap_or(value)
+        }
+        _ => value,
+    }
+}
+
+/// Numeric fields of `schema` whose value in `config` is malformed, in
+/// schema order. Negative integers are allowed where the field allows them,
+/// and lists of numbers must hold numbers only.
+pub fn malformed_numbers(config: &Value, schema: &UISchema) -> Vec<ConfigValueError> {
+    schema
+        .fields
+        .iter()
+        .filter_map(|field| match &field.field_type {
+            FieldType::Float { .. } => read_f64(config, &field.key).err(),
+            FieldType::Integer { min, .. } if !matches!(min, Some(min) if *min >= 0) => {
+                match read_f64(config, &field.key) {
+                    Ok(Some(v)) if v.fract() != 0.0 => Some(ConfigValueError {
+                        key: field.key.clone(),
+                        expected: "a whole number",
+                        found: config[&field.key].clone(),
+                    }),
+                    Ok(_) => None,
+                    Err(err) => Some(ConfigValueError {
+                        expected: "a whole number",
+                        ..err
+                    }),
+                }
+            }
+            FieldType::Integer { .. } => read_u64(config, &field.key).err(),
+            FieldType::DynamicList { item_type, .. } if matches!(**item_type, FieldType::Float { .. }) => {
+                read_f64_array(config, &field.key).err()
+            }
+            _ => None,
+        })
+        .collect()
+}
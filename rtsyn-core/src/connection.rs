@@ -1,4 +1,4 @@
-use crate::plugin::{is_extendable_inputs, plugin_display_name, InstalledPlugin};
+use crate::plugin::{is_extendable_inputs, plugin_display_name, port_unit, with_unit, InstalledPlugin};
 use serde_json::Value;
 use workspace::{ConnectionDefinition, ConnectionRuleError, WorkspaceDefinition};
 
@@ -143,6 +143,52 @@ pub fn sync_extendable_input_count(workspace: &mut WorkspaceDefinition, plugin_i
     }
 }
 
+/// Connections into and out of `plugin_id`, as `(incoming, outgoing)`.
+/// Incoming ones are ordered by input port (extendable inputs by index),
+/// outgoing ones by output port; ties are broken by the other plugin's id.
+pub fn plugin_connections(
+    workspace: &WorkspaceDefinition,
+    plugin_id: u64,
+) -> (Vec<&ConnectionDefinition>, Vec<&ConnectionDefinition>) {
+    let mut incoming: Vec<&ConnectionDefinition> = workspace
+        .connections
+        .iter()
+        .filter(|conn| conn.to_plugin == plugin_id)
+        .collect();
+    incoming.sort_by_key(|conn| {
+        (
+            extendable_input_index(&conn.to_port),
+            conn.to_port.clone(),
+            conn.from_plugin,
+        )
+    });
+    let mut outgoing: Vec<&ConnectionDefinition> = workspace
+        .connections
+        .iter()
+        .filter(|conn| conn.from_plugin == plugin_id)
+        .collect();
+    outgoing.sort_by_key(|conn| (conn.from_port.clone(), conn.to_plugin, conn.to_port.clone()));
+    (incoming, outgoing)
+}
+
+/// Column name template csv_recorder uses when its `column_template` is unset.
+pub const DEFAULT_CSV_COLUMN_TEMPLATE: &str = "{source_name}_{source_id}_{source_port}";
+
+/// Placeholders understood in a csv_recorder `column_template`. Names are
+/// lowercased with spaces replaced by `_`; unknown placeholders are kept
+/// as written.
+pub const CSV_COLUMN_PLACEHOLDERS: [(&str, &str); 7] = [
+    ("{source_name}", "display name of the connected plugin"),
+    ("{source_kind}", "kind of the connected plugin"),
+    ("{source_id}", "id of the connected plugin"),
+    ("{source_port}", "output port the input is fed from"),
+    ("{recorder_name}", "display name of the recorder"),
+    ("{recorder_id}", "id of the recorder"),
+    ("{input}", "recorder input index"),
+];
+
+/// Default name for input `input_idx` of a csv_recorder, from the recorder's
+/// `column_template`. Unconnected inputs are named after the recorder.
 pub fn default_csv_column(
     workspace: &WorkspaceDefinition,
     installed: &[InstalledPlugin],
@@ -150,56 +196,183 @@ pub fn default_csv_column(
     input_idx: usize,
 ) -> String {
     let port = format!("in_{input_idx}");
-    if let Some(conn) = workspace
+    let slug = |plugin_id: u64| {
+        plugin_display_name(installed, workspace, plugin_id)
+            .replace(' ', "_")
+            .to_lowercase()
+    };
+    let recorder_name = slug(recorder_id);
+    let Some(conn) = workspace
         .connections
         .iter()
         .find(|conn| conn.to_plugin == recorder_id && conn.to_port == port)
-    {
-        let source_name = plugin_display_name(installed, workspace, conn.from_plugin)
-            .replace(' ', "_")
-            .to_lowercase();
-        let port = conn.from_port.to_lowercase();
-        return format!("{source_name}_{}_{}", conn.from_plugin, port);
-    }
-    let recorder_name = plugin_display_name(installed, workspace, recorder_id)
-        .replace(' ', "_")
-        .to_lowercase();
-    format!("{recorder_name}_{}_{}", recorder_id, port.to_lowercase())
+    else {
+        return format!("{recorder_name}_{}_{}", recorder_id, port.to_lowercase());
+    };
+    let template = workspace
+        .plugins
+        .iter()
+        .find(|p| p.id == recorder_id)
+        .and_then(|p| p.config.get("column_template"))
+        .and_then(|v| v.as_str())
+        .filter(|v| !v.trim().is_empty())
+        .unwrap_or(DEFAULT_CSV_COLUMN_TEMPLATE);
+    let source_kind = workspace
+        .plugins
+        .iter()
+        .find(|p| p.id == conn.from_plugin)
+        .map(|p| p.kind.to_lowercase())
+        .unwrap_or_default();
+    let values = [
+        slug(conn.from_plugin),
+        source_kind,
+        conn.from_plugin.to_string(),
+        conn.from_port.to_lowercase(),
+        recorder_name,
+        recorder_id.to_string(),
+        input_idx.to_string(),
+    ];
+    // Single pass, so labels containing braces are not expanded again
+    let mut name = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        name.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match CSV_COLUMN_PLACEHOLDERS
+            .iter()
+            .position(|(placeholder, _)| rest.starts_with(placeholder))
+        {
+            Some(idx) => {
+                name.push_str(&values[idx]);
+                rest = &rest[CSV_COLUMN_PLACEHOLDERS[idx].0.len()..];
+            }
+            None => {
+                name.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    name.push_str(rest);
+    with_unit(
+        &name,
+        port_unit(installed, workspace, conn.from_plugin, &conn.from_port),
+    )
 }
 
-pub fn add_connection(
+/// Names the columns of a csv_recorder with [`default_csv_column`]. Columns
+/// are resized to `input_count` first, so column `i` stays the name of
+/// `in_i`. Only blank names are replaced unless `overwrite` is set; returns
+/// how many names changed.
+pub fn fill_csv_columns(
     workspace: &mut WorkspaceDefinition,
     installed: &[InstalledPlugin],
+    recorder_id: u64,
+    overwrite: bool,
+) -> usize {
+    let Some(config) = workspace
+        .plugins
+        .iter()
+        .find(|p| p.id == recorder_id && p.kind == "csv_recorder")
+        .map(|p| &p.config)
+    else {
+        return 0;
+    };
+    let input_count = config
+        .get("input_count")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as usize;
+    let mut columns: Vec<String> = config
+        .get("columns")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .map(|v| v.as_str().unwrap_or("").to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    columns.resize(input_count, String::new());
+    let mut changed = 0;
+    for (idx, column) in columns.iter_mut().enumerate() {
+        if !overwrite && !column.trim().is_empty() {
+            continue;
+        }
+        let name = default_csv_column(workspace, installed, recorder_id, idx);
+        if *column != name {
+            *column = name;
+            changed += 1;
+        }
+    }
+    if let Some(Value::Object(map)) = workspace
+        .plugins
+        .iter_mut()
+        .find(|p| p.id == recorder_id)
+        .map(|p| &mut p.config)
+    {
+        map.insert(
+            "columns".to_string(),
+            Value::Array(columns.into_iter().map(Value::from).collect()),
+        );
+    }
+    changed
+}
+
+/// The connection [`add_connection`] would add: `in` of a plugin with
+/// extendable inputs becomes its next free `in_N`.
+fn candidate_connection(
+    workspace: &WorkspaceDefinition,
+    mut connection: ConnectionDefinition,
+) -> ConnectionDefinition {
+    if let Some(target) = workspace.plugins.iter().find(|p| p.id == connection.to_plugin) {
+        if is_extendable_inputs(&target.kind) && connection.to_port == "in" {
+            let next_idx = next_available_extendable_input_index(workspace, connection.to_plugin);
+            connection.to_port = format!("in_{next_idx}");
+        }
+    }
+    connection
+}
+
+/// Whether [`add_connection`] would accept the connection, without changing
+/// the workspace.
+pub fn can_add_connection(
+    workspace: &WorkspaceDefinition,
     from_plugin: u64,
     from_port: &str,
     to_plugin: u64,
     to_port: &str,
-    kind: &str,
 ) -> Result<(), ConnectionRuleError> {
+    let candidate = candidate_connection(
+        workspace,
+        ConnectionDefinition {
+            from_plugin,
+            from_port: from_port.to_string(),
+            to_plugin,
+            to_port: to_port.to_string(),
+            kind: String::new(),
+            gain: 1.0,
+        },
+    );
+    workspace::can_add_connection(&workspace.connections, &candidate, 1)
+}
+
+/// Adds `connection` to the workspace. A `to_port` of `in` on a plugin with
+/// extendable inputs is taken as its next free `in_N`.
+pub fn add_connection(
+    workspace: &mut WorkspaceDefinition,
+    installed: &[InstalledPlugin],
+    connection: ConnectionDefinition,
+) -> Result<(), ConnectionRuleError> {
+    let (from_plugin, to_plugin) = (connection.from_plugin, connection.to_plugin);
     if from_plugin == to_plugin {
         return Err(ConnectionRuleError::SelfConnection);
     }
 
-    let mut to_port_string = to_port.to_string();
-    if let Some(target) = workspace.plugins.iter().find(|p| p.id == to_plugin) {
-        if is_extendable_inputs(&target.kind) && to_port_string == "in" {
-            let next_idx = next_available_extendable_input_index(workspace, to_plugin);
-            to_port_string = format!("in_{next_idx}");
-        }
-    }
-    let input_idx = to_port_string
+    let connection = candidate_connection(workspace, connection);
+    let input_idx = connection
+        .to_port
         .strip_prefix("in_")
         .and_then(|v| v.parse::<usize>().ok());
-    let default_column = input_idx.map(|idx| default_csv_column(workspace, installed, to_plugin, idx));
-
-    let connection = ConnectionDefinition {
-        from_plugin,
-        from_port: from_port.to_string(),
-        to_plugin,
-        to_port: to_port_string,
-        kind: kind.to_string(),
-    };
     workspace::add_connection(&mut workspace.connections, connection, 1)?;
+    let default_column = input_idx.map(|idx| default_csv_column(workspace, installed, to_plugin, idx));
 
     if let Some(idx) = input_idx {
         if let Some(target) = workspace.plugins.iter().find(|p| p.id == to_plugin) {
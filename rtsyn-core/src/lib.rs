@@ -1,3 +1,5 @@
 pub mod plugin;
 pub mod workspace;
 pub mod connection;
+pub mod scaffold;
+pub mod config;
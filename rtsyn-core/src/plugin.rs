@@ -1,17 +1,52 @@
+use alarm_plugin::AlarmPlugin;
+use audio_out_plugin::AudioOutPlugin;
+use bang_bang_plugin::BangBangPlugin;
+use bit_pack_plugin::BitPackPlugin;
+use bit_unpack_plugin::BitUnpackPlugin;
+use can_io_plugin::CanIoPlugin;
 use csv_recorder_plugin::CsvRecorderedPlugin;
+use delay_plugin::DelayPlugin;
+use differentiator_plugin::DifferentiatorPlugin;
+use error_metrics_plugin::ErrorMetricsPlugin;
+use estop_plugin::EstopPlugin;
+use fault_recorder_plugin::FaultRecorderPlugin;
+use filtered_derivative_plugin::FilteredDerivativePlugin;
+use frequency_counter_plugin::FrequencyCounterPlugin;
+use histogram_plugin::HistogramPlugin;
+use integrator_plugin::IntegratorPlugin;
 use live_plotter_plugin::LivePlotterPlugin;
+use lookup_table_plugin::LookupTablePlugin;
+use modbus_io_plugin::ModbusIoPlugin;
+use noise_generator_plugin::NoiseGeneratorPlugin;
+use peak_hold_plugin::PeakHoldPlugin;
 use performance_monitor_plugin::PerformanceMonitorPlugin;
 #[cfg(feature = "comedi")]
 use comedi_daq_plugin::ComediDaqPlugin;
+#[cfg(feature = "rpi")]
+use gpio_plugin::GpioPlugin;
+use pwm_plugin::PwmPlugin;
+use resampler_plugin::ResamplerPlugin;
+use rms_plugin::RmsPlugin;
 use rtsyn_plugin::ui::{DisplaySchema, PluginBehavior, UISchema};
 use rtsyn_plugin::Plugin;
+use sample_hold_plugin::SampleHoldPlugin;
+use saturation_plugin::SaturationPlugin;
+use scale_plugin::ScalePlugin;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use setpoint_ramp_plugin::SetpointRampPlugin;
+use shm_publisher_plugin::ShmPublisherPlugin;
+use state_machine_plugin::StateMachinePlugin;
+use stats_plugin::StatsPlugin;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::Duration;
+use stimulus_plugin::StimulusPlugin;
+use tcp_stream_plugin::TcpStreamPlugin;
+use timer_plugin::TimerPlugin;
+use transfer_function_plugin::TransferFunctionPlugin;
 use workspace::{PluginDefinition, WorkspaceDefinition};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +56,21 @@ pub struct PluginManifest {
     pub version: Option<String>,
     pub description: Option<String>,
     pub library: Option<String>,
+    /// Unit of each port, e.g. `out = "V"`, from the `[port_units]` table.
+    /// Ports without an entry are unitless.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub port_units: HashMap<String, String>,
+    /// `starts_expanded = false` shows new cards of this kind collapsed.
+    #[serde(default = "default_starts_expanded")]
+    pub starts_expanded: bool,
+    /// Card icon: a FontAwesome codepoint such as `"f1e6"` or a small PNG
+    /// path relative to the plugin folder. See [`plugin_icon`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+}
+
+fn default_starts_expanded() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,19 +113,26 @@ pub struct PluginManager {
     pub detected_plugins: Vec<DetectedPlugin>,
     pub next_plugin_id: u64,
     pub available_plugin_ids: Vec<u64>,
+    /// User directories searched for plugins and their libraries, in order.
+    pub search_paths: Vec<PathBuf>,
     install_db_path: PathBuf,
+    search_paths_db_path: PathBuf,
 }
 
 impl PluginManager {
     pub fn new(install_db_path: PathBuf) -> Self {
+        let search_paths_db_path = install_db_path.with_file_name("plugin_search_paths.json");
         let mut manager = Self {
             installed_plugins: Vec::new(),
             plugin_behaviors: HashMap::new(),
             detected_plugins: Vec::new(),
             next_plugin_id: 1,
             available_plugin_ids: Vec::new(),
+            search_paths: Vec::new(),
             install_db_path,
+            search_paths_db_path,
         };
+        manager.load_search_paths();
         manager.load_installed_plugins();
         manager
     }
@@ -103,8 +160,43 @@ impl PluginManager {
             ("csv_recorder", "CSV Recorder", "Records data to CSV files"),
             ("live_plotter", "Live Plotter", "Real-time data visualization"),
             ("performance_monitor", "Performance Monitor", "Monitors system performance"),
+            ("bang_bang", "Bang-Bang Controller", "Two-position controller with hysteresis"),
+            ("noise_generator", "Noise Generator", "Uniform, gaussian or pink noise source"),
+            ("integrator", "Integrator", "Integrates its input over time"),
+            ("differentiator", "Differentiator", "Rate of change of its input"),
+            ("delay", "Delay", "Transport delay line"),
+            ("alarm", "Alarm", "Dual-limit alarm with latching"),
+            ("sample_hold", "Sample & Hold", "Holds the input captured on a trigger edge"),
+            ("scale", "Scale", "Gain/offset or two-point calibration"),
+            ("can_io", "CAN I/O Device Driver", "SocketCAN signal input/output"),
+            ("stimulus", "Stimulus", "Step, impulse, ramp or chirp test input"),
+            ("saturation", "Saturation", "Clamps the input between min and max"),
+            ("histogram", "Histogram", "Live histogram of recent input samples"),
+            ("tcp_stream", "TCP Stream", "Streams inputs to TCP clients as JSON or CSV"),
+            ("shm_publisher", "Shared Memory Publisher", "Publishes inputs to a POSIX shared memory segment"),
+            ("rms", "RMS", "Moving RMS, peak and crest factor"),
+            ("peak_hold", "Peak Hold", "Holds the input maximum and minimum, with optional decay"),
+            ("timer", "Timer", "One-shot, retriggerable or periodic pulse"),
+            ("setpoint_ramp", "Setpoint Ramp", "Soft setpoint changes at a fixed ramp rate"),
+            ("lookup_table", "Lookup Table", "Breakpoint table with nearest, linear or cubic interpolation"),
+            ("stats", "Statistics", "Running count, mean, variance, min and max per input"),
+            ("resampler", "Resampler", "Converts a signal between sample rates"),
+            ("pwm", "PWM", "Pulse-width modulated digital output"),
+            ("estop", "Emergency Stop", "Puts device drivers in their safe state"),
+            ("audio_out", "Audio Out", "Plays a signal on the audio output, directly or as an AM/FM tone."),
+            ("frequency_counter", "Frequency Counter", "Frequency, period and duty cycle of a pulsing signal from its threshold crossings."),
+            ("modbus_io", "Modbus I/O Device Driver", "Modbus TCP/RTU register input/output"),
+            ("state_machine", "State Machine", "Finite state machine with transitions on input conditions"),
+            ("fault_recorder", "Fault Recorder", "Captures the inputs around each trigger to a file"),
+            ("filtered_derivative", "Filtered Derivative", "Rate of change through a low-pass filter"),
+            ("bit_pack", "Bit Pack", "Packs digital inputs into one integer word"),
+            ("bit_unpack", "Bit Unpack", "Splits an integer word into digital outputs"),
+            ("transfer_function", "Transfer Function", "Discrete filter with arbitrary b/a coefficients"),
+            ("error_metrics", "Error Metrics", "Tracking error with IAE, ISE and windowed RMSE"),
             #[cfg(feature = "comedi")]
             ("comedi_daq", "Comedi DAQ", "Data acquisition via Comedi"),
+            #[cfg(feature = "rpi")]
+            ("gpio", "GPIO Device Driver", "GPIO pin input/output via the Linux GPIO character device"),
         ];
 
         for (kind, name, desc) in bundled {
@@ -121,6 +213,22 @@ impl PluginManager {
                         plugin.ui_schema(),
                     )
                 }
+                "bang_bang" => {
+                    let plugin = BangBangPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (
+                        inputs,
+                        outputs,
+                        vec![
+                            ("hysteresis".to_string(), 0.5),
+                            ("output_high".to_string(), 1.0),
+                            ("output_low".to_string(), 0.0),
+                        ],
+                        plugin.display_schema(),
+                        plugin.ui_schema(),
+                    )
+                }
                 "csv_recorder" => {
                     let plugin = CsvRecorderedPlugin::new(0);
                     let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
@@ -133,6 +241,198 @@ impl PluginManager {
                     let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
                     (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
                 }
+                "noise_generator" => {
+                    let plugin = NoiseGeneratorPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "integrator" => {
+                    let plugin = IntegratorPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "differentiator" => {
+                    let plugin = DifferentiatorPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "delay" => {
+                    let plugin = DelayPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "alarm" => {
+                    let plugin = AlarmPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "sample_hold" => {
+                    let plugin = SampleHoldPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "scale" => {
+                    let plugin = ScalePlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "can_io" => {
+                    let plugin = CanIoPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "stimulus" => {
+                    let plugin = StimulusPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "saturation" => {
+                    let plugin = SaturationPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "histogram" => {
+                    let plugin = HistogramPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "tcp_stream" => {
+                    let plugin = TcpStreamPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "shm_publisher" => {
+                    let plugin = ShmPublisherPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "rms" => {
+                    let plugin = RmsPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "peak_hold" => {
+                    let plugin = PeakHoldPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "timer" => {
+                    let plugin = TimerPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "setpoint_ramp" => {
+                    let plugin = SetpointRampPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "lookup_table" => {
+                    let plugin = LookupTablePlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "stats" => {
+                    let plugin = StatsPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "resampler" => {
+                    let plugin = ResamplerPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "pwm" => {
+                    let plugin = PwmPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "estop" => {
+                    let plugin = EstopPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "audio_out" => {
+                    let plugin = AudioOutPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "frequency_counter" => {
+                    let plugin = FrequencyCounterPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "modbus_io" => {
+                    let plugin = ModbusIoPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "state_machine" => {
+                    let plugin = StateMachinePlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "fault_recorder" => {
+                    let plugin = FaultRecorderPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "filtered_derivative" => {
+                    let plugin = FilteredDerivativePlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "bit_pack" => {
+                    let plugin = BitPackPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "bit_unpack" => {
+                    let plugin = BitUnpackPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "transfer_function" => {
+                    let plugin = TransferFunctionPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
+                "error_metrics" => {
+                    let plugin = ErrorMetricsPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
                 #[cfg(feature = "comedi")]
                 "comedi_daq" => {
                     let plugin = ComediDaqPlugin::new(0);
@@ -140,6 +440,13 @@ impl PluginManager {
                     let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
                     (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
                 }
+                #[cfg(feature = "rpi")]
+                "gpio" => {
+                    let plugin = GpioPlugin::new(0);
+                    let inputs: Vec<String> = plugin.inputs().iter().map(|p| p.id.0.clone()).collect();
+                    let outputs: Vec<String> = plugin.outputs().iter().map(|p| p.id.0.clone()).collect();
+                    (inputs, outputs, Vec::new(), plugin.display_schema(), plugin.ui_schema())
+                }
                 _ => (Vec::new(), Vec::new(), Vec::new(), None, None),
             };
 
@@ -150,6 +457,9 @@ impl PluginManager {
                     description: Some(desc.to_string()),
                     version: Some("1.0.0".to_string()),
                     library: None,
+                    port_units: HashMap::new(),
+                    starts_expanded: true,
+                    icon: None,
                 },
                 path: PathBuf::new(),
                 library_path: None,
@@ -179,16 +489,92 @@ impl PluginManager {
         }
     }
 
+    fn load_search_paths(&mut self) {
+        if let Ok(data) = fs::read(&self.search_paths_db_path) {
+            if let Ok(paths) = serde_json::from_slice::<Vec<PathBuf>>(&data) {
+                self.search_paths = paths;
+            }
+        }
+    }
+
+    fn persist_search_paths(&self) {
+        if let Ok(data) = serde_json::to_vec_pretty(&self.search_paths) {
+            if let Some(parent) = self.search_paths_db_path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            let _ = fs::write(&self.search_paths_db_path, data);
+        }
+    }
+
+    pub fn add_search_path(&mut self, path: PathBuf) -> Result<(), String> {
+        if !path.is_dir() {
+            return Err(format!("{} is not a directory", path.display()));
+        }
+        if self.search_paths.contains(&path) {
+            return Err(format!("{} is already a plugin path", path.display()));
+        }
+        self.search_paths.push(path);
+        self.persist_search_paths();
+        Ok(())
+    }
+
+    pub fn remove_search_path(&mut self, path: &Path) -> bool {
+        let before = self.search_paths.len();
+        self.search_paths.retain(|p| p != path);
+        let removed = self.search_paths.len() != before;
+        if removed {
+            self.persist_search_paths();
+        }
+        removed
+    }
+
     pub fn refresh_library_paths(&mut self) {
         for plugin in &mut self.installed_plugins {
             if plugin.removable {
-                plugin.library_path = Self::resolve_library_path(&plugin.manifest, &plugin.path);
+                plugin.library_path =
+                    Self::resolve_library_path_in(&plugin.manifest, &plugin.path, &self.search_paths);
             }
         }
     }
 
     pub fn resolve_library_path(manifest: &PluginManifest, folder: &Path) -> Option<PathBuf> {
+        Self::resolve_library_path_in(manifest, folder, &[])
+    }
+
+    /// Looks for the plugin library in order: the `library` named in the
+    /// manifest, the user search paths, then the plugin's cargo target dirs.
+    pub fn resolve_library_path_in(
+        manifest: &PluginManifest,
+        folder: &Path,
+        search_paths: &[PathBuf],
+    ) -> Option<PathBuf> {
+        if let Some(library) = manifest.library.as_deref().filter(|l| !l.trim().is_empty()) {
+            let explicit = folder.join(library);
+            if explicit.is_file() {
+                return Some(explicit);
+            }
+        }
+
         let lib_name = manifest.kind.replace('-', "_");
+        let mut file_names = vec![
+            format!("lib{}.so", lib_name),
+            format!("lib{}.dylib", lib_name),
+            format!("{}.dll", lib_name),
+        ];
+        if let Some(library) = manifest
+            .library
+            .as_deref()
+            .and_then(|l| Path::new(l).file_name())
+            .and_then(|n| n.to_str())
+        {
+            file_names.insert(0, library.to_string());
+        }
+        for dir in search_paths {
+            if let Some(found) = file_names.iter().map(|name| dir.join(name)).find(|p| p.is_file()) {
+                return Some(found);
+            }
+        }
+
         let candidates = [
             folder.join("target/release").join(format!("lib{}.so", lib_name)),
             folder.join("target/release").join(format!("lib{}.dylib", lib_name)),
@@ -261,6 +647,56 @@ impl PluginManager {
                 }
             }
         }
+
+        let mut kinds: HashSet<String> =
+            self.detected_plugins.iter().map(|p| p.manifest.kind.clone()).collect();
+        for detected in self.detect_in_search_paths() {
+            if kinds.insert(detected.manifest.kind.clone()) {
+                self.detected_plugins.push(detected);
+            }
+        }
+    }
+
+    /// Plugins found in the user search paths. A path may be a plugin folder
+    /// itself or a directory of plugin folders.
+    fn detect_in_search_paths(&self) -> Vec<DetectedPlugin> {
+        let mut detected = Vec::new();
+        for dir in &self.search_paths {
+            if let Some(plugin) = Self::read_detected_plugin(dir) {
+                detected.push(plugin);
+                continue;
+            }
+            if let Ok(entries) = fs::read_dir(dir) {
+                for entry in entries.flatten() {
+                    if let Some(plugin) = Self::read_detected_plugin(&entry.path()) {
+                        detected.push(plugin);
+                    }
+                }
+            }
+        }
+        detected
+    }
+
+    fn read_detected_plugin(path: &Path) -> Option<DetectedPlugin> {
+        if !path.is_dir() {
+            return None;
+        }
+        let folder_name = path.file_name().and_then(|s| s.to_str()).unwrap_or_default();
+        if folder_name.eq_ignore_ascii_case("template") {
+            return None;
+        }
+        let data = fs::read_to_string(path.join("plugin.toml")).ok()?;
+        let manifest: PluginManifest = toml::from_str(&data).ok()?;
+        if manifest.kind == "comedi_daq" && !cfg!(feature = "comedi") {
+            return None;
+        }
+        if manifest.kind == "gpio" && !cfg!(feature = "rpi") {
+            return None;
+        }
+        Some(DetectedPlugin {
+            manifest,
+            path: path.to_path_buf(),
+        })
     }
 
     pub fn display_kind(kind: &str) -> String {
@@ -294,7 +730,7 @@ impl PluginManager {
         let manifest: PluginManifest =
             toml::from_str(&data).map_err(|err| format!("Failed to parse plugin.toml: {err}"))?;
 
-        let library_path = PluginManager::resolve_library_path(&manifest, path);
+        let library_path = PluginManager::resolve_library_path_in(&manifest, path, &self.search_paths);
 
         if let Some(installed) = self
             .installed_plugins
@@ -369,36 +805,18 @@ impl PluginManager {
     }
 
     pub fn scan_detected_plugins_in(&mut self, bases: &[&str]) {
-        let mut detected = Vec::new();
+        // User paths come first so they can shadow the defaults.
+        let mut detected = self.detect_in_search_paths();
+        let searched_kinds: HashSet<String> =
+            detected.iter().map(|p| p.manifest.kind.clone()).collect();
         for base in bases {
-            let base = PathBuf::from(base);
-            if let Ok(entries) = fs::read_dir(&base) {
+            if let Ok(entries) = fs::read_dir(base) {
                 for entry in entries.flatten() {
-                    let path = entry.path();
-                    if !path.is_dir() {
-                        continue;
-                    }
-                    let folder_name =
-                        path.file_name().and_then(|s| s.to_str()).unwrap_or_default();
-                    if folder_name.eq_ignore_ascii_case("template") {
-                        continue;
-                    }
-                    let manifest_path = path.join("plugin.toml");
-                    if !manifest_path.is_file() {
-                        continue;
-                    }
-                    let data = match fs::read_to_string(&manifest_path) {
-                        Ok(content) => content,
-                        Err(_) => continue,
-                    };
-                    let manifest: PluginManifest = match toml::from_str(&data) {
-                        Ok(parsed) => parsed,
-                        Err(_) => continue,
-                    };
-                    if manifest.kind == "comedi_daq" && !cfg!(feature = "comedi") {
-                        continue;
+                    if let Some(plugin) = Self::read_detected_plugin(&entry.path()) {
+                        if !searched_kinds.contains(&plugin.manifest.kind) {
+                            detected.push(plugin);
+                        }
                     }
-                    detected.push(DetectedPlugin { manifest, path });
                 }
             }
         }
@@ -433,8 +851,11 @@ impl PluginManager {
         if manifest.kind == "comedi_daq" && !cfg!(feature = "comedi") {
             return Err("comedi_daq is not available without the comedi feature".to_string());
         }
+        if manifest.kind == "gpio" && !cfg!(feature = "rpi") {
+            return Err("gpio is not available without the rpi feature".to_string());
+        }
 
-        let library_path = PluginManager::resolve_library_path(&manifest, folder);
+        let library_path = PluginManager::resolve_library_path_in(&manifest, folder, &self.search_paths);
         let (metadata_inputs, metadata_outputs, metadata_variables, mut display_schema, ui_schema) =
             if let Some(ref lib_path) = library_path {
                 let lib_path_str = lib_path.to_string_lossy();
@@ -506,8 +927,11 @@ impl PluginManager {
                 .map(|path| !path.is_file())
                 .unwrap_or(true);
             if needs_update {
-                installed.library_path =
-                    PluginManager::resolve_library_path(&installed.manifest, &installed.path);
+                installed.library_path = PluginManager::resolve_library_path_in(
+                    &installed.manifest,
+                    &installed.path,
+                    &self.search_paths,
+                );
                 changed = true;
             }
         }
@@ -723,51 +1147,8 @@ impl PluginCatalog {
     }
 
     pub fn scan_detected_plugins(&mut self) {
-        let mut detected = Vec::new();
-        for base in ["plugins", "app_plugins", "rtsyn-plugins"] {
-            let base = PathBuf::from(base);
-            if let Ok(entries) = fs::read_dir(&base) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if !path.is_dir() {
-                        continue;
-                    }
-                    let folder_name = path.file_name().and_then(|s| s.to_str()).unwrap_or_default();
-                    if folder_name.eq_ignore_ascii_case("template") {
-                        continue;
-                    }
-                    let manifest_path = path.join("plugin.toml");
-                    if !manifest_path.is_file() {
-                        continue;
-                    }
-                    let data = match fs::read_to_string(&manifest_path) {
-                        Ok(content) => content,
-                        Err(_) => continue,
-                    };
-                    let manifest: PluginManifest = match toml::from_str(&data) {
-                        Ok(parsed) => parsed,
-                        Err(_) => continue,
-                    };
-                    if manifest.kind == "comedi_daq" && !cfg!(feature = "comedi") {
-                        continue;
-                    }
-                    detected.push(DetectedPlugin { manifest, path });
-                }
-            }
-        }
-        let mut detected_kinds: HashSet<String> =
-            detected.iter().map(|p| p.manifest.kind.clone()).collect();
-        for installed in &self.manager.installed_plugins {
-            if detected_kinds.contains(&installed.manifest.kind) {
-                continue;
-            }
-            detected.push(DetectedPlugin {
-                manifest: installed.manifest.clone(),
-                path: installed.path.clone(),
-            });
-            detected_kinds.insert(installed.manifest.kind.clone());
-        }
-        self.manager.detected_plugins = detected;
+        self.manager
+            .scan_detected_plugins_in(&["plugins", "app_plugins", "rtsyn-plugins"]);
     }
 
     pub fn install_plugin_from_folder<P: AsRef<Path>>(
@@ -786,8 +1167,15 @@ impl PluginCatalog {
         if manifest.kind == "comedi_daq" && !cfg!(feature = "comedi") {
             return Err("comedi_daq is not available without the comedi feature".to_string());
         }
+        if manifest.kind == "gpio" && !cfg!(feature = "rpi") {
+            return Err("gpio is not available without the rpi feature".to_string());
+        }
 
-        let library_path = PluginManager::resolve_library_path(&manifest, folder.as_ref());
+        let library_path = PluginManager::resolve_library_path_in(
+            &manifest,
+            folder.as_ref(),
+            &self.manager.search_paths,
+        );
         let (metadata_inputs, metadata_outputs, metadata_variables, mut display_schema, ui_schema) =
             if let Some(ref lib_path) = library_path {
                 let lib_path_str = lib_path.to_string_lossy();
@@ -1055,9 +1443,222 @@ impl PluginCatalog {
 }
 
 pub fn is_extendable_inputs(kind: &str) -> bool {
-    matches!(kind, "csv_recorder" | "live_plotter")
+    matches!(
+        kind,
+        "csv_recorder" | "live_plotter" | "tcp_stream" | "shm_publisher" | "stats" | "fault_recorder"
+    )
+}
+
+/// Named inputs an extendable plugin has besides its `in_N` ports.
+pub fn extendable_fixed_inputs(kind: &str) -> &'static [&'static str] {
+    match kind {
+        "stats" => &["reset"],
+        "fault_recorder" => &["trigger"],
+        _ => &[],
+    }
+}
+
+/// Ports of `bit_pack` and `bit_unpack`: one `bit_N` port per configured
+/// bit on one side and `word` on the other. `None` for other kinds.
+pub fn bit_field_ports(kind: &str, config: &Value, inputs: bool) -> Option<Vec<String>> {
+    let bits_are_inputs = match kind {
+        "bit_pack" => true,
+        "bit_unpack" => false,
+        _ => return None,
+    };
+    if bits_are_inputs != inputs {
+        return Some(vec!["word".to_string()]);
+    }
+    let bits = config
+        .get("bits")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(8)
+        .clamp(1, bit_pack_plugin::MAX_BITS as u64) as usize;
+    Some((0..bits).map(bit_pack_plugin::bit_port).collect())
+}
+
+/// Kinds whose config fields depend on each other. Their config editor
+/// collects edits into a draft that is validated with [`validate_config`]
+/// and applied in one batch, so no intermediate state reaches the runtime.
+pub fn supports_apply(kind: &str) -> bool {
+    matches!(
+        kind,
+        "saturation" | "alarm" | "histogram" | "lookup_table" | "state_machine" | "transfer_function"
+    )
+}
+
+/// Checks the fields of a plugin config that must agree with each other.
+pub fn validate_config(kind: &str, config: &Value) -> Result<(), String> {
+    let number = |key: &str| config.get(key).and_then(|v| v.as_f64());
+    let ordered = |low: &str, high: &str, strict: bool| match (number(low), number(high)) {
+        (Some(lo), Some(hi)) if lo > hi || (strict && lo == hi) => {
+            let relation = if strict { "below" } else { "at most" };
+            Err(format!("{low} ({lo}) must be {relation} {high} ({hi})"))
+        }
+        _ => Ok(()),
+    };
+    match kind {
+        "saturation" => ordered("min", "max", false),
+        "alarm" => ordered("low_limit", "high_limit", false),
+        "histogram" => ordered("range_min", "range_max", true),
+        "lookup_table" => {
+            let len = |key: &str| config.get(key).and_then(|v| v.as_array()).map(Vec::len);
+            match (len("x_points"), len("y_points")) {
+                (Some(x), Some(y)) if x != y => Err(format!(
+                    "x_points has {x} entries but y_points has {y}"
+                )),
+                _ => Ok(()),
+            }
+        }
+        "state_machine" => {
+            let names = |key: &str| -> Vec<String> {
+                config
+                    .get(key)
+                    .and_then(|v| v.as_array())
+                    .map(|items| {
+                        items
+                            .iter()
+                            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            };
+            state_machine_plugin::validate(&names("inputs"), &names("states"), &names("transitions"))
+        }
+        "transfer_function" => {
+            let coefficients = |key: &str| -> Vec<f64> {
+                config
+                    .get(key)
+                    .and_then(|v| v.as_array())
+                    .map(|items| items.iter().map(|v| v.as_f64().unwrap_or(f64::NAN)).collect())
+                    .unwrap_or_default()
+            };
+            transfer_function_plugin::validate(&coefficients("b"), &coefficients("a"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Config key of the user-assigned card label of a plugin instance.
+pub const UI_LABEL_KEY: &str = "_ui_label";
+/// Config key of the user-assigned card accent color, as `#rrggbb`.
+pub const UI_COLOR_KEY: &str = "_ui_color";
+/// Config key of the collapsed state of a plugin card.
+pub const UI_COLLAPSED_KEY: &str = "_ui_collapsed";
+
+/// Custom label of a plugin instance, if one is set and not blank.
+pub fn custom_label(config: &Value) -> Option<&str> {
+    config
+        .get(UI_LABEL_KEY)
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|label| !label.is_empty())
+}
+
+/// Whether a plugin card shows only its header and run controls. Cards the
+/// user never toggled follow the manifest's `starts_expanded`.
+pub fn card_collapsed(config: &Value, starts_expanded: bool) -> bool {
+    config
+        .get(UI_COLLAPSED_KEY)
+        .and_then(Value::as_bool)
+        .unwrap_or(!starts_expanded)
+}
+
+/// Custom accent color of a plugin instance as RGB, if one is set and valid.
+pub fn custom_color(config: &Value) -> Option<[u8; 3]> {
+    let hex = config.get(UI_COLOR_KEY)?.as_str()?.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |idx: usize| u8::from_str_radix(hex.get(idx..idx + 2)?, 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Formats an accent color for [`UI_COLOR_KEY`].
+pub fn format_color([r, g, b]: [u8; 3]) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// FontAwesome puzzle piece, shown for plugins without a usable icon.
+pub const DEFAULT_PLUGIN_ICON: char = '\u{f12e}';
+
+/// Icon of a plugin kind, resolved from its manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginIcon {
+    Glyph(char),
+    Image(PathBuf),
+}
+
+/// Resolves the manifest `icon` of a plugin: a `.png` path, relative to the
+/// plugin folder unless absolute, or a FontAwesome codepoint written as hex
+/// (`f1e6`, `U+f1e6`, `0xf1e6`) or as the character itself. Missing and
+/// unparsable icons give [`DEFAULT_PLUGIN_ICON`].
+pub fn plugin_icon(installed: &InstalledPlugin) -> PluginIcon {
+    let default = PluginIcon::Glyph(DEFAULT_PLUGIN_ICON);
+    let Some(icon) = installed.manifest.icon.as_deref().map(str::trim) else {
+        return default;
+    };
+    if icon.to_ascii_lowercase().ends_with(".png") {
+        return PluginIcon::Image(installed.path.join(icon));
+    }
+    let mut chars = icon.chars();
+    if let (Some(glyph), None) = (chars.next(), chars.next()) {
+        return PluginIcon::Glyph(glyph);
+    }
+    let hex = ["U+", "u+", "0x", "0X"]
+        .iter()
+        .find_map(|prefix| icon.strip_prefix(prefix))
+        .unwrap_or(icon);
+    u32::from_str_radix(hex, 16)
+        .ok()
+        .and_then(char::from_u32)
+        .map_or(default, PluginIcon::Glyph)
+}
+
+/// Config key listing the outputs of a plugin instance that signal a fault.
+/// When set it replaces the naming convention of [`is_fault_output`].
+pub const FAULT_OUTPUTS_KEY: &str = "fault_outputs";
+
+/// Output names treated as fault flags by default: `alarm`, `fault`,
+/// `out_of_range`, and names ending in `_alarm`, `_fault` or `_violation`.
+pub fn is_fault_output(port: &str) -> bool {
+    matches!(port, "alarm" | "fault" | "out_of_range")
+        || ["_alarm", "_fault", "_violation"]
+            .iter()
+            .any(|suffix| port.ends_with(suffix))
 }
 
+/// Whether `port` of the plugin instance with `config` is a fault flag.
+pub fn is_plugin_fault_output(config: &Value, port: &str) -> bool {
+    match config.get(FAULT_OUTPUTS_KEY).and_then(|v| v.as_array()) {
+        Some(ports) => ports.iter().any(|v| v.as_str() == Some(port)),
+        None => is_fault_output(port),
+    }
+}
+
+/// Fault outputs of running plugins that are currently non-zero, as
+/// `(plugin_id, port)` sorted by plugin id and port.
+pub fn active_faults(
+    workspace: &WorkspaceDefinition,
+    outputs: &HashMap<(u64, String), f64>,
+) -> Vec<(u64, String)> {
+    let mut faults: Vec<(u64, String)> = outputs
+        .iter()
+        .filter(|(_, value)| **value != 0.0)
+        .filter(|((plugin_id, port), _)| {
+            workspace.plugins.iter().any(|plugin| {
+                plugin.id == *plugin_id
+                    && plugin.running
+                    && is_plugin_fault_output(&plugin.config, port)
+            })
+        })
+        .map(|(key, _)| key.clone())
+        .collect();
+    faults.sort();
+    faults
+}
+
+/// Custom label of the plugin if it has one, otherwise its kind's name.
 pub fn plugin_display_name(
     installed: &[InstalledPlugin],
     workspace: &WorkspaceDefinition,
@@ -1072,6 +1673,9 @@ pub fn plugin_display_name(
         .iter()
         .find(|plugin| plugin.id == plugin_id)
         .map(|plugin| {
+            if let Some(label) = custom_label(&plugin.config) {
+                return label.to_string();
+            }
             name_by_kind
                 .get(&plugin.kind)
                 .cloned()
@@ -1080,6 +1684,69 @@ pub fn plugin_display_name(
         .unwrap_or_else(|| "plugin".to_string())
 }
 
+/// Unit declared for `port` of the workspace plugin `plugin_id`, if any.
+pub fn port_unit<'a>(
+    installed: &'a [InstalledPlugin],
+    workspace: &WorkspaceDefinition,
+    plugin_id: u64,
+    port: &str,
+) -> Option<&'a str> {
+    let kind = &workspace.plugins.iter().find(|plugin| plugin.id == plugin_id)?.kind;
+    installed
+        .iter()
+        .find(|plugin| &plugin.manifest.kind == kind)?
+        .manifest
+        .port_units
+        .get(port)
+        .map(|unit| unit.trim())
+        .filter(|unit| !unit.is_empty())
+}
+
+/// `label` with the unit appended as ` (unit)`; unitless labels are unchanged.
+pub fn with_unit(label: &str, unit: Option<&str>) -> String {
+    match unit {
+        Some(unit) => format!("{label} ({unit})"),
+        None => label.to_string(),
+    }
+}
+
+/// Result of matching an exported config against an installed plugin.
+#[derive(Debug, Clone, Default)]
+pub struct ImportedConfig {
+    pub accepted: Vec<(String, Value)>,
+    pub skipped: Vec<String>,
+}
+
+/// Picks the entries of an exported plugin config that the installed plugin
+/// knows about, i.e. keys listed in its ui schema, display variables or
+/// metadata variables. Unknown keys are reported as skipped.
+pub fn filter_imported_config(
+    installed: &InstalledPlugin,
+    imported: &Value,
+) -> Result<ImportedConfig, String> {
+    let Value::Object(map) = imported else {
+        return Err("Plugin config must be a JSON object".to_string());
+    };
+    let mut known: HashSet<&str> = HashSet::new();
+    if let Some(schema) = installed.ui_schema.as_ref() {
+        known.extend(schema.fields.iter().map(|field| field.key.as_str()));
+    }
+    if let Some(schema) = installed.display_schema.as_ref() {
+        known.extend(schema.variables.iter().map(String::as_str));
+    }
+    known.extend(installed.metadata_variables.iter().map(|(name, _)| name.as_str()));
+
+    let mut result = ImportedConfig::default();
+    for (key, value) in map {
+        if known.contains(key.as_str()) {
+            result.accepted.push((key.clone(), value.clone()));
+        } else {
+            result.skipped.push(key.clone());
+        }
+    }
+    Ok(result)
+}
+
 pub fn empty_workspace() -> WorkspaceDefinition {
     WorkspaceDefinition {
         name: "cli".to_string(),
@@ -1088,6 +1755,8 @@ pub fn empty_workspace() -> WorkspaceDefinition {
         plugins: Vec::new(),
         connections: Vec::new(),
         settings: workspace::WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
     }
 }
 
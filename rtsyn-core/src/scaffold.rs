@@ -0,0 +1,426 @@
+//! Generates a buildable Rust plugin crate from the fields of the New plugin
+//! dialog.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Rust keywords a field can only use as a raw identifier.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// FFI glue shared by every generated plugin; the `__X__` markers are filled
+/// in by [`PluginScaffold::lib_rs`].
+const FFI_TEMPLATE: &str = r#"fn str_arg<'a>(ptr: *const u8, len: usize) -> Option<&'a str> {
+    if ptr.is_null() {
+        return None;
+    }
+    std::str::from_utf8(unsafe { std::slice::from_raw_parts(ptr, len) }).ok()
+}
+
+fn instance<'a>(handle: *mut c_void) -> Option<&'a mut __TYPE__> {
+    unsafe { (handle as *mut __TYPE__).as_mut() }
+}
+
+extern "C" fn create(_id: u64) -> *mut c_void {
+    Box::into_raw(Box::new(__TYPE__::new())) as *mut c_void
+}
+
+extern "C" fn destroy(handle: *mut c_void) {
+    if !handle.is_null() {
+        unsafe { drop(Box::from_raw(handle as *mut __TYPE__)) };
+    }
+}
+
+extern "C" fn meta_json(_handle: *mut c_void) -> PluginString {
+    let meta = json!({
+        "name": __NAME__,
+        "kind": __KIND__,
+        "variables": [__VARIABLES__],
+    });
+    PluginString::from_string(meta.to_string())
+}
+
+extern "C" fn inputs_json(_handle: *mut c_void) -> PluginString {
+    PluginString::from_string(json!([__INPUTS__]).to_string())
+}
+
+extern "C" fn outputs_json(_handle: *mut c_void) -> PluginString {
+    PluginString::from_string(json!([__OUTPUTS__]).to_string())
+}
+
+extern "C" fn set_config_json(handle: *mut c_void, data: *const u8, len: usize) {
+    let (Some(plugin), Some(text)) = (instance(handle), str_arg(data, len)) else {
+        return;
+    };
+    if let Ok(config) = serde_json::from_str::<Value>(text) {
+        plugin.set_config(&config);
+    }
+}
+
+extern "C" fn set_input(handle: *mut c_void, name: *const u8, len: usize, value: f64) {
+    if let (Some(plugin), Some(name)) = (instance(handle), str_arg(name, len)) {
+        plugin.set_input(name, value);
+    }
+}
+
+extern "C" fn process(handle: *mut c_void, tick: u64, period_seconds: f64) {
+    if let Some(plugin) = instance(handle) {
+        plugin.process(tick, period_seconds);
+    }
+}
+
+extern "C" fn get_output(handle: *mut c_void, name: *const u8, len: usize) -> f64 {
+    match (instance(handle), str_arg(name, len)) {
+        (Some(plugin), Some(name)) => plugin.get_output(name),
+        _ => 0.0,
+    }
+}
+
+extern "C" fn ui_schema_json(_handle: *mut c_void) -> PluginString {
+    let schema = UISchema::new()__UI_FIELDS__;
+    PluginString::from_string(serde_json::to_string(&schema).unwrap_or_default())
+}
+
+#[no_mangle]
+pub extern "C" fn rtsyn_plugin_api() -> *const PluginApi {
+    static API: PluginApi = PluginApi {
+        create,
+        destroy,
+        meta_json,
+        inputs_json,
+        outputs_json,
+        set_config_json,
+        set_input,
+        process,
+        get_output,
+        behavior_json: None,
+        display_schema_json: None,
+        ui_schema_json: Some(ui_schema_json),
+    };
+    &API as *const PluginApi
+}
+"#;
+
+/// Fields of a plugin crate to generate.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PluginScaffold {
+    pub name: String,
+    pub description: String,
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+    /// Config variables with their default values.
+    pub variables: Vec<(String, f64)>,
+}
+
+/// Splits a comma or whitespace separated list of port names.
+pub fn parse_port_list(text: &str) -> Vec<String> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Parses `name[=default]` entries separated by commas; the default is 0.0
+/// when left out.
+pub fn parse_variable_list(text: &str) -> Result<Vec<(String, f64)>, String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| match entry.split_once('=') {
+            Some((name, default)) => default
+                .trim()
+                .parse::<f64>()
+                .map(|default| (name.trim().to_string(), default))
+                .map_err(|_| format!("invalid default '{}' for '{}'", default.trim(), name.trim())),
+            None => Ok((entry.to_string(), 0.0)),
+        })
+        .collect()
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_lowercase() || c == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        && name != "_"
+}
+
+fn field_name(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("r#{name}")
+    } else {
+        name.to_string()
+    }
+}
+
+fn field_label(name: &str) -> String {
+    let label = name.replace('_', " ");
+    let mut chars = label.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => label,
+    }
+}
+
+fn quoted_list<'a>(names: impl Iterator<Item = &'a String>) -> String {
+    names
+        .map(|name| format!("{name:?}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl PluginScaffold {
+    /// Plugin kind and crate name: the name lowercased, with runs of other
+    /// characters turned into `_`.
+    pub fn kind(&self) -> String {
+        let mut kind = String::new();
+        for c in self.name.trim().chars() {
+            if c.is_ascii_alphanumeric() {
+                kind.push(c.to_ascii_lowercase());
+            } else if !kind.is_empty() && !kind.ends_with('_') {
+                kind.push('_');
+            }
+        }
+        kind.trim_end_matches('_').to_string()
+    }
+
+    fn type_name(&self) -> String {
+        let mut name: String = self
+            .kind()
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            })
+            .collect();
+        name.push_str("Plugin");
+        name
+    }
+
+    /// Port and variable names become struct fields, so they must be
+    /// lowercase identifiers and unique across inputs, outputs and variables.
+    pub fn validate(&self) -> Result<(), String> {
+        let kind = self.kind();
+        if !kind.starts_with(|c: char| c.is_ascii_lowercase()) {
+            return Err("The name must start with a letter".to_string());
+        }
+        if self.name.contains(['\n', '\r']) {
+            return Err("The name must be a single line".to_string());
+        }
+        let mut seen: Vec<&str> = Vec::new();
+        let names = self
+            .inputs
+            .iter()
+            .chain(self.outputs.iter())
+            .chain(self.variables.iter().map(|(name, _)| name));
+        for name in names {
+            if !is_identifier(name) || matches!(name.as_str(), "self" | "super" | "crate") {
+                return Err(format!(
+                    "'{name}' is not a valid name: use lowercase letters, digits and '_'"
+                ));
+            }
+            if seen.contains(&name.as_str()) {
+                return Err(format!("'{name}' is used more than once"));
+            }
+            seen.push(name);
+        }
+        if let Some((name, _)) = self
+            .variables
+            .iter()
+            .find(|(_, default)| !default.is_finite())
+        {
+            return Err(format!("The default of '{name}' must be a finite number"));
+        }
+        Ok(())
+    }
+
+    pub fn cargo_toml(&self) -> String {
+        format!(
+            r#"[package]
+name = "{}"
+version = "0.1.0"
+edition = "2021"
+publish = false
+
+[lib]
+crate-type = ["cdylib"]
+
+[dependencies]
+rtsyn_plugin = {{ git = "https://github.com/rtsyn-dev/rtsyn-plugin" }}
+serde_json = "1"
+
+# Built on its own, not as a member of an enclosing cargo workspace
+[workspace]
+"#,
+            self.kind()
+        )
+    }
+
+    pub fn plugin_toml(&self) -> String {
+        let quote = |text: &str| toml::Value::String(text.to_string()).to_string();
+        let mut out = format!(
+            "name = {}\nkind = {}\nversion = \"0.1.0\"\n",
+            quote(self.name.trim()),
+            quote(&self.kind())
+        );
+        if !self.description.trim().is_empty() {
+            let _ = writeln!(out, "description = {}", quote(self.description.trim()));
+        }
+        out
+    }
+
+    /// The plugin struct with one `f64` field per port and variable, stub
+    /// methods to fill in, and the C ABI entry points the runtime loads.
+    pub fn lib_rs(&self) -> String {
+        let ty = self.type_name();
+        let mut out = String::new();
+        let _ = writeln!(out, "//! {} plugin.\n", self.name.trim());
+        if self.variables.is_empty() {
+            out.push_str("use rtsyn_plugin::ui::UISchema;\n");
+        } else {
+            out.push_str("use rtsyn_plugin::ui::{ConfigField, UISchema};\n");
+        }
+        out.push_str("use rtsyn_plugin::{PluginApi, PluginString};\n");
+        out.push_str("use serde_json::{json, Value};\n");
+        out.push_str("use std::ffi::c_void;\n\n");
+
+        let _ = writeln!(out, "#[derive(Debug, Default)]\nstruct {ty} {{");
+        let variable_names: Vec<String> = self
+            .variables
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect();
+        for (comment, names) in [
+            ("Inputs", &self.inputs),
+            ("Outputs", &self.outputs),
+            ("Config variables", &variable_names),
+        ] {
+            if names.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "    // {comment}");
+            for name in names {
+                let _ = writeln!(out, "    {}: f64,", field_name(name));
+            }
+        }
+        out.push_str("}\n\n");
+
+        let _ = writeln!(out, "impl {ty} {{");
+        out.push_str("    fn new() -> Self {\n");
+        if self.variables.is_empty() {
+            out.push_str("        Self::default()\n");
+        } else {
+            out.push_str("        Self {\n");
+            for (name, default) in &self.variables {
+                let _ = writeln!(out, "            {}: {default:?},", field_name(name));
+            }
+            if !self.inputs.is_empty() || !self.outputs.is_empty() {
+                out.push_str("            ..Default::default()\n");
+            }
+            out.push_str("        }\n");
+        }
+        out.push_str("    }\n\n");
+
+        out.push_str("    fn set_config(&mut self, config: &Value) {\n");
+        if self.variables.is_empty() {
+            out.push_str("        let _ = config;\n");
+        }
+        for (name, _) in &self.variables {
+            let _ = writeln!(
+                out,
+                "        if let Some(value) = config.get({name:?}).and_then(Value::as_f64) {{\n            self.{} = value;\n        }}",
+                field_name(name)
+            );
+        }
+        out.push_str("    }\n\n");
+
+        out.push_str("    fn set_input(&mut self, name: &str, value: f64) {\n");
+        if self.inputs.is_empty() {
+            out.push_str("        let _ = (name, value);\n");
+        } else {
+            out.push_str("        match name {\n");
+            for name in &self.inputs {
+                let _ = writeln!(
+                    out,
+                    "            {name:?} => self.{} = value,",
+                    field_name(name)
+                );
+            }
+            out.push_str("            _ => {}\n        }\n");
+        }
+        out.push_str("    }\n\n");
+
+        out.push_str("    /// Called once per tick; `period_seconds` is the tick period.\n");
+        out.push_str("    fn process(&mut self, _tick: u64, _period_seconds: f64) {\n");
+        out.push_str("        // TODO: compute the outputs from the inputs and config variables\n");
+        out.push_str("    }\n\n");
+
+        out.push_str("    fn get_output(&self, name: &str) -> f64 {\n");
+        if self.outputs.is_empty() {
+            out.push_str("        let _ = name;\n        0.0\n");
+        } else {
+            out.push_str("        match name {\n");
+            for name in &self.outputs {
+                let _ = writeln!(out, "            {name:?} => self.{},", field_name(name));
+            }
+            out.push_str("            _ => 0.0,\n        }\n");
+        }
+        out.push_str("    }\n}\n\n");
+
+        let variables = self
+            .variables
+            .iter()
+            .map(|(name, default)| format!("{{ \"name\": {name:?}, \"default\": {default:?} }}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let ui_fields: String = self
+            .variables
+            .iter()
+            .map(|(name, default)| {
+                format!(
+                    "\n        .field(ConfigField::float({name:?}, {:?}).default_value(Value::from({default:?})))",
+                    field_label(name)
+                )
+            })
+            .collect();
+        out.push_str(
+            &FFI_TEMPLATE
+                .replace("__TYPE__", &ty)
+                .replace("__NAME__", &format!("{:?}", self.name.trim()))
+                .replace("__KIND__", &format!("{:?}", self.kind()))
+                .replace("__VARIABLES__", &variables)
+                .replace("__INPUTS__", &quoted_list(self.inputs.iter()))
+                .replace("__OUTPUTS__", &quoted_list(self.outputs.iter()))
+                .replace("__UI_FIELDS__", &ui_fields),
+        );
+        out
+    }
+}
+
+/// Writes the crate into `parent/<kind>` and returns that folder. An existing
+/// folder is never overwritten.
+pub fn write_plugin_crate(parent: &Path, scaffold: &PluginScaffold) -> Result<PathBuf, String> {
+    scaffold.validate()?;
+    let folder = parent.join(scaffold.kind());
+    if folder.exists() {
+        return Err(format!("{} already exists", folder.display()));
+    }
+    fs::create_dir_all(folder.join("src")).map_err(|err| err.to_string())?;
+    let files = [
+        (folder.join("Cargo.toml"), scaffold.cargo_toml()),
+        (folder.join("plugin.toml"), scaffold.plugin_toml()),
+        (folder.join("src").join("lib.rs"), scaffold.lib_rs()),
+    ];
+    for (path, contents) in files {
+        fs::write(&path, contents).map_err(|err| format!("{}: {err}", path.display()))?;
+    }
+    Ok(folder)
+}
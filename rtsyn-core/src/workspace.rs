@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
-use workspace::{WorkspaceDefinition, WorkspaceSettings};
+use workspace::{MissedDeadlinePolicy, WorkspaceDefinition, WorkspaceMetadata, WorkspaceSettings};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkspaceEntry {
@@ -8,6 +8,7 @@ pub struct WorkspaceEntry {
     pub description: String,
     pub plugins: usize,
     pub plugin_kinds: Vec<String>,
+    pub metadata: WorkspaceMetadata,
     pub path: PathBuf,
 }
 
@@ -17,6 +18,20 @@ pub struct WorkspaceManager {
     pub workspace_dirty: bool,
     pub workspace_entries: Vec<WorkspaceEntry>,
     workspace_dir: PathBuf,
+    /// Edits since the last load or save. `workspace_dirty` is cleared as
+    /// soon as the runtime has the new workspace, so it cannot tell.
+    unsaved_changes: bool,
+    /// Every open workspace, in tab order. The active tab's slot is stale:
+    /// its workspace lives in the fields above while it is active.
+    tabs: Vec<WorkspaceTab>,
+    active_tab: usize,
+}
+
+/// A workspace open in a background tab, kept as it was left.
+struct WorkspaceTab {
+    workspace: WorkspaceDefinition,
+    path: PathBuf,
+    unsaved_changes: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -25,6 +40,16 @@ pub struct RuntimeSettings {
     pub period_seconds: f64,
     pub time_scale: f64,
     pub time_label: String,
+    pub missed_deadline_policy: MissedDeadlinePolicy,
+    pub jitter_us: f64,
+}
+
+/// Seconds since the Unix epoch, for workspace save stamps.
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
 }
 
 impl WorkspaceManager {
@@ -44,6 +69,7 @@ impl WorkspaceManager {
                             description: workspace.description,
                             plugins: workspace.plugins.len(),
                             plugin_kinds: workspace.plugins.iter().map(|p| p.kind.clone()).collect(),
+                            metadata: workspace.metadata,
                             path,
                         });
                     }
@@ -67,6 +93,8 @@ impl WorkspaceManager {
             plugins: Vec::new(),
             connections: Vec::new(),
             settings: WorkspaceSettings::default(),
+            parameters: Default::default(),
+            metadata: Default::default(),
         }
     }
 
@@ -75,7 +103,9 @@ impl WorkspaceManager {
             .map_err(|e| format!("Failed to load workspace: {e}"))
     }
 
-    fn save_workspace_file(workspace: &WorkspaceDefinition, path: &Path) -> Result<(), String> {
+    /// Writes `workspace` to `path`, stamping it as modified now.
+    fn save_workspace_file(workspace: &mut WorkspaceDefinition, path: &Path) -> Result<(), String> {
+        workspace.metadata.mark_saved(unix_now());
         workspace
             .save_to_file(path)
             .map_err(|e| format!("Failed to save workspace: {e}"))
@@ -97,6 +127,17 @@ impl WorkspaceManager {
             workspace_dirty: true,
             workspace_entries: Vec::new(),
             workspace_dir,
+            unsaved_changes: false,
+            tabs: vec![Self::empty_tab()],
+            active_tab: 0,
+        }
+    }
+
+    fn empty_tab() -> WorkspaceTab {
+        WorkspaceTab {
+            workspace: Self::empty_workspace("default"),
+            path: PathBuf::new(),
+            unsaved_changes: false,
         }
     }
 
@@ -236,6 +277,22 @@ impl WorkspaceManager {
             }
             settings.selected_cores = cores;
         }
+        if let Some(value) = obj.get("missed_deadline_policy") {
+            let name = value
+                .as_str()
+                .ok_or_else(|| "missed_deadline_policy must be a string".to_string())?;
+            settings.missed_deadline_policy = MissedDeadlinePolicy::from_name(name)
+                .ok_or_else(|| format!("Unknown missed_deadline_policy: {name}"))?;
+        }
+        if let Some(value) = obj.get("jitter_us") {
+            let jitter = value
+                .as_f64()
+                .ok_or_else(|| "jitter_us must be a number".to_string())?;
+            if jitter < 0.0 {
+                return Err("jitter_us must not be negative".to_string());
+            }
+            settings.jitter_us = jitter;
+        }
 
         if settings.selected_cores.is_empty() {
             settings.selected_cores = vec![0];
@@ -283,11 +340,18 @@ impl WorkspaceManager {
             period_seconds,
             time_scale,
             time_label,
+            missed_deadline_policy: settings.missed_deadline_policy,
+            jitter_us: settings.jitter_us,
         })
     }
 
     pub fn mark_dirty(&mut self) {
         self.workspace_dirty = true;
+        self.unsaved_changes = true;
+    }
+
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.unsaved_changes
     }
 
     pub fn workspace_file_path(&self, name: &str) -> PathBuf {
@@ -303,6 +367,7 @@ impl WorkspaceManager {
         self.workspace = loaded;
         self.workspace_path = path.to_path_buf();
         self.workspace_dirty = false;
+        self.unsaved_changes = false;
         Ok(())
     }
 
@@ -310,8 +375,9 @@ impl WorkspaceManager {
         if self.workspace_path.as_os_str().is_empty() {
             return Err("No workspace path set".to_string());
         }
-        Self::save_workspace_file(&self.workspace, &self.workspace_path)?;
+        Self::save_workspace_file(&mut self.workspace, &self.workspace_path)?;
         self.workspace_dirty = false;
+        self.unsaved_changes = false;
         Ok(())
     }
 
@@ -321,12 +387,31 @@ impl WorkspaceManager {
 
         let path = self.workspace_file_path(name);
         let _ = std::fs::create_dir_all(&self.workspace_dir);
-        Self::save_workspace_file(&self.workspace, &path)?;
+        Self::save_workspace_file(&mut self.workspace, &path)?;
         self.workspace_path = path;
         self.workspace_dirty = false;
+        self.unsaved_changes = false;
         Ok(())
     }
 
+    /// Saves a copy of the current workspace under `name` with its plugins
+    /// renumbered from 1, for branching an experiment. The current workspace
+    /// is left as it is; returns the copy's path.
+    pub fn duplicate_workspace(&self, name: &str, description: &str) -> Result<PathBuf, String> {
+        let path = self.workspace_file_path(name);
+        if path.exists() {
+            return Err("Workspace already exists".to_string());
+        }
+        let mut copy = self.workspace.renumbered();
+        copy.name = name.to_string();
+        copy.description = description.to_string();
+        // A new experiment branched off the current one
+        copy.metadata.created_at = 0;
+        let _ = std::fs::create_dir_all(&self.workspace_dir);
+        Self::save_workspace_file(&mut copy, &path)?;
+        Ok(path)
+    }
+
     pub fn create_workspace(&mut self, name: &str, description: &str) -> Result<(), String> {
         let path = self.workspace_file_path(name);
         if path.exists() {
@@ -337,9 +422,10 @@ impl WorkspaceManager {
         self.workspace.description = description.to_string();
 
         let _ = std::fs::create_dir_all(&self.workspace_dir);
-        Self::save_workspace_file(&self.workspace, &path)?;
+        Self::save_workspace_file(&mut self.workspace, &path)?;
         self.workspace_path = path;
         self.workspace_dirty = false;
+        self.unsaved_changes = false;
         Ok(())
     }
 
@@ -363,11 +449,98 @@ impl WorkspaceManager {
         let new_path = self.workspace_file_path(name);
         let mut workspace = self.workspace.clone();
         workspace.name = name.to_string();
-        Self::save_workspace_file(&workspace, &new_path)?;
+        Self::save_workspace_file(&mut workspace, &new_path)?;
         Self::remove_old_workspace_file(&current_path, &new_path)?;
         self.workspace = workspace;
         self.workspace_path = new_path;
         self.workspace_dirty = false;
+        self.unsaved_changes = false;
+        Ok(())
+    }
+
+    /// Name and unsaved state of every open tab, in order.
+    pub fn tabs(&self) -> Vec<(String, bool)> {
+        self.tabs
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| {
+                if index == self.active_tab {
+                    (self.workspace.name.clone(), self.unsaved_changes)
+                } else {
+                    (tab.workspace.name.clone(), tab.unsaved_changes)
+                }
+            })
+            .collect()
+    }
+
+    /// The tab whose workspace is in `workspace`, the one the runtime runs.
+    pub fn active_tab(&self) -> usize {
+        self.active_tab
+    }
+
+    /// A background tab that has `path` open.
+    pub fn tab_for_path(&self, path: &Path) -> Option<usize> {
+        self.tabs
+            .iter()
+            .enumerate()
+            .find(|(index, tab)| *index != self.active_tab && tab.path == path)
+            .map(|(index, _)| index)
+    }
+
+    /// Whether a background tab has edits that were never saved.
+    pub fn other_tab_unsaved(&self) -> Option<usize> {
+        self.tabs
+            .iter()
+            .enumerate()
+            .find(|(index, tab)| *index != self.active_tab && tab.unsaved_changes)
+            .map(|(index, _)| index)
+    }
+
+    /// Opens an empty, unsaved workspace in a new tab and makes it active.
+    pub fn new_tab(&mut self) -> usize {
+        self.tabs.push(Self::empty_tab());
+        let index = self.tabs.len() - 1;
+        self.switch_tab(index);
+        index
+    }
+
+    /// Parks the active workspace in its tab and makes tab `index` active.
+    /// The workspace is marked dirty so it is handed to the runtime.
+    pub fn switch_tab(&mut self, index: usize) {
+        if index == self.active_tab || index >= self.tabs.len() {
+            return;
+        }
+        let parked = WorkspaceTab {
+            workspace: std::mem::replace(&mut self.workspace, Self::empty_workspace("default")),
+            path: std::mem::take(&mut self.workspace_path),
+            unsaved_changes: self.unsaved_changes,
+        };
+        let next = std::mem::replace(&mut self.tabs[index], Self::empty_tab());
+        self.tabs[self.active_tab] = parked;
+        self.workspace = next.workspace;
+        self.workspace_path = next.path;
+        self.unsaved_changes = next.unsaved_changes;
+        self.active_tab = index;
+        self.workspace_dirty = true;
+    }
+
+    /// Closes tab `index`, dropping any unsaved edits in it. Closing the
+    /// active tab activates its neighbour; the last tab cannot be closed.
+    pub fn close_tab(&mut self, index: usize) -> Result<(), String> {
+        if self.tabs.len() <= 1 {
+            return Err("The last workspace tab cannot be closed".to_string());
+        }
+        if index >= self.tabs.len() {
+            return Err("No such workspace tab".to_string());
+        }
+        if index == self.active_tab {
+            let neighbour = if index + 1 < self.tabs.len() { index + 1 } else { index - 1 };
+            self.switch_tab(neighbour);
+        }
+        self.tabs.remove(index);
+        if self.active_tab > index {
+            self.active_tab -= 1;
+        }
         Ok(())
     }
 
@@ -382,6 +555,7 @@ impl WorkspaceManager {
             self.workspace = Self::empty_workspace("default");
             self.workspace_path = PathBuf::new();
             self.workspace_dirty = true;
+            self.unsaved_changes = false;
         }
         Ok(())
     }
@@ -0,0 +1,74 @@
+use rtsyn_core::config::{
+    f64_array_or, f64_or, malformed_numbers, normalize_number, read_f64, read_f64_array, read_u64, u64_or,
+};
+use rtsyn_plugin::ui::{ConfigField, FieldType, UISchema};
+use serde_json::json;
+
+#[test]
+fn missing_values_differ_from_malformed_ones() {
+    let config = json!({ "gain": 2.5, "offset": "0.5", "min": "fast", "bins": 8.0, "window": -3, "none": null });
+
+    assert_eq!(read_f64(&config, "gain"), Ok(Some(2.5)));
+    assert_eq!(read_f64(&config, "offset"), Ok(Some(0.5)));
+    assert_eq!(read_f64(&config, "missing"), Ok(None));
+    assert_eq!(read_f64(&config, "none"), Ok(None));
+    let err = read_f64(&config, "min").unwrap_err();
+    assert_eq!(err.to_string(), "min should be a number but is \"fast\"");
+
+    assert_eq!(read_u64(&config, "bins"), Ok(Some(8)));
+    assert!(read_u64(&config, "window").is_err());
+    assert!(read_u64(&config, "gain").is_err());
+
+    assert_eq!(f64_or(&config, "min", -1.0), -1.0);
+    assert_eq!(f64_or(&config, "gain", -1.0), 2.5);
+    assert_eq!(u64_or(&config, "window", 1000), 1000);
+    assert_eq!(u64_or(&config, "bins", 20), 8);
+}
+
+#[test]
+fn malformed_numbers_follow_the_ui_schema() {
+    let schema = UISchema::new()
+        .field(ConfigField::float("gain", "Gain"))
+        .field(ConfigField::integer("bins", "Bins").min(1))
+        .field(ConfigField::integer("offset", "Offset"))
+        .field(ConfigField::text("name", "Name"));
+    let config = json!({ "gain": "x2", "bins": 2.5, "offset": -4, "name": "scope" });
+
+    let keys: Vec<String> = malformed_numbers(&config, &schema).into_iter().map(|err| err.key).collect();
+    assert_eq!(keys, vec!["gain".to_string(), "bins".to_string()]);
+
+    let config = json!({ "gain": 1.0, "offset": 1.5 });
+    let errors = malformed_numbers(&config, &schema);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].to_string(), "offset should be a whole number but is 1.5");
+}
+
+#[test]
+fn one_malformed_item_rejects_a_number_list() {
+    let config = json!({ "b": [0.5, "0.25"], "a": [1.0, "0,5"] });
+    assert_eq!(read_f64_array(&config, "b"), Ok(Some(vec![0.5, 0.25])));
+    assert_eq!(read_f64_array(&config, "missing"), Ok(None));
+    assert!(read_f64_array(&config, "a").is_err());
+    assert_eq!(f64_array_or(&config, "a", &[1.0]), vec![1.0]);
+
+    let number = FieldType::Float { min: None, max: None, step: 0.01 };
+    let schema = UISchema::new()
+        .field(ConfigField::dynamic_list("b", "b").item_type(number.clone()))
+        .field(ConfigField::dynamic_list("a", "a").item_type(number));
+    let errors = malformed_numbers(&config, &schema);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].to_string(), "a should be a list of numbers but is [1.0,\"0,5\"]");
+}
+
+#[test]
+fn editors_see_numeric_strings_as_numbers() {
+    let float = FieldType::Float { min: None, max: None, step: 0.01 };
+    let integer = FieldType::Integer { min: None, max: None, step: 1 };
+    assert_eq!(normalize_number(json!(" 2.5 "), &float), json!(2.5));
+    assert_eq!(normalize_number(json!("8"), &integer), json!(8));
+    assert_eq!(normalize_number(json!("8.5"), &integer), json!("8.5"));
+    assert_eq!(normalize_number(json!("x2"), &float), json!("x2"));
+
+    let list = ConfigField::dynamic_list("b", "b").item_type(float).field_type;
+    assert_eq!(normalize_number(json!([1, "0.5"]), &list), json!([1.0, 0.5]));
+}
@@ -9,6 +9,7 @@ fn add_connection_rejects_duplicate_between_same_plugins() {
         to_plugin: 2,
         to_port: "in".to_string(),
         kind: "shared_memory".to_string(),
+        gain: 1.0,
     };
     add_connection(&mut connections, first, 1).expect("first connection");
 
@@ -18,6 +19,7 @@ fn add_connection_rejects_duplicate_between_same_plugins() {
         to_plugin: 2,
         to_port: "in".to_string(),
         kind: "shared_memory".to_string(),
+        gain: 1.0,
     };
     let result = add_connection(&mut connections, second, 1);
     assert!(result.is_err());
@@ -32,6 +34,7 @@ fn add_connection_rejects_same_output_to_same_target() {
         to_plugin: 2,
         to_port: "in_0".to_string(),
         kind: "shared_memory".to_string(),
+        gain: 1.0,
     };
     add_connection(&mut connections, first, 1).expect("first connection");
 
@@ -41,7 +44,255 @@ fn add_connection_rejects_same_output_to_same_target() {
         to_plugin: 2,
         to_port: "in_1".to_string(),
         kind: "shared_memory".to_string(),
+        gain: 1.0,
     };
     let result = add_connection(&mut connections, second, 1);
     assert!(result.is_err());
 }
+
+#[test]
+fn csv_columns_follow_the_recorder_template() {
+    use rtsyn_core::connection::add_connection as add_workspace_connection;
+    use workspace::{PluginDefinition, WorkspaceDefinition};
+
+    let plugin = |id: u64, kind: &str, config: serde_json::Value| PluginDefinition {
+        id,
+        kind: kind.to_string(),
+        config,
+        priority: 0,
+        running: false,
+    };
+    let mut workspace = WorkspaceDefinition {
+        name: "test".to_string(),
+        description: String::new(),
+        target_hz: 1000,
+        plugins: vec![
+            plugin(1, "rms", serde_json::json!({ "_ui_label": "Motor Current" })),
+            plugin(2, "csv_recorder", serde_json::json!({ "input_count": 0 })),
+            plugin(
+                3,
+                "csv_recorder",
+                serde_json::json!({ "column_template": "{source_name}/{source_port} ({source_kind}, {unknown})" }),
+            ),
+        ],
+        connections: Vec::new(),
+        settings: Default::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+
+    for recorder in [2, 3] {
+        add_workspace_connection(
+            &mut workspace,
+            &[],
+            ConnectionDefinition {
+                from_plugin: 1,
+                from_port: "out".to_string(),
+                to_plugin: recorder,
+                to_port: "in".to_string(),
+                kind: "shared_memory".to_string(),
+                gain: 1.0,
+            },
+        )
+        .expect("connect recorder");
+    }
+    let columns = |id: u64| workspace.plugins.iter().find(|p| p.id == id).unwrap().config["columns"].clone();
+    assert_eq!(columns(2), serde_json::json!(["motor_current_1_out"]));
+    assert_eq!(columns(3), serde_json::json!(["motor_current/out (rms, {unknown})"]));
+}
+
+#[test]
+fn plugin_connections_split_incoming_and_outgoing() {
+    use rtsyn_core::connection::plugin_connections;
+    use workspace::WorkspaceDefinition;
+
+    let connection = |from_plugin: u64, from_port: &str, to_plugin: u64, to_port: &str| ConnectionDefinition {
+        from_plugin,
+        from_port: from_port.to_string(),
+        to_plugin,
+        to_port: to_port.to_string(),
+        kind: "shared_memory".to_string(),
+        gain: 1.0,
+    };
+    let workspace = WorkspaceDefinition {
+        name: "test".to_string(),
+        description: String::new(),
+        target_hz: 1000,
+        plugins: Vec::new(),
+        connections: vec![
+            connection(3, "out", 2, "in_10"),
+            connection(1, "out", 2, "in_2"),
+            connection(2, "y", 4, "in"),
+            connection(5, "out", 6, "in"),
+            connection(2, "x", 4, "in_0"),
+            connection(2, "x", 3, "in"),
+        ],
+        settings: Default::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+
+    let (incoming, outgoing) = plugin_connections(&workspace, 2);
+    let sources: Vec<(u64, &str)> = incoming.iter().map(|c| (c.from_plugin, c.to_port.as_str())).collect();
+    assert_eq!(sources, vec![(1, "in_2"), (3, "in_10")]);
+    let targets: Vec<(&str, u64)> = outgoing.iter().map(|c| (c.from_port.as_str(), c.to_plugin)).collect();
+    assert_eq!(targets, vec![("x", 3), ("x", 4), ("y", 4)]);
+}
+
+#[test]
+fn csv_columns_append_the_source_port_unit() {
+    use rtsyn_core::connection::default_csv_column;
+    use rtsyn_core::plugin::{port_unit, InstalledPlugin, PluginManifest};
+    use workspace::{PluginDefinition, WorkspaceDefinition};
+
+    let manifest: PluginManifest = toml::from_str(
+        "name = \"Motor\"\nkind = \"motor\"\n\n[port_units]\nspeed = \"rad/s\"\ncurrent = \" \"\n",
+    )
+    .expect("manifest");
+    let installed = vec![InstalledPlugin {
+        manifest,
+        path: Default::default(),
+        library_path: None,
+        removable: true,
+        metadata_inputs: Vec::new(),
+        metadata_outputs: vec!["speed".to_string(), "current".to_string()],
+        metadata_variables: Vec::new(),
+        display_schema: None,
+        ui_schema: None,
+    }];
+    let plugin = |id: u64, kind: &str| PluginDefinition {
+        id,
+        kind: kind.to_string(),
+        config: serde_json::json!({}),
+        priority: 0,
+        running: false,
+    };
+    let connection = |from_port: &str, to_port: &str| ConnectionDefinition {
+        from_plugin: 1,
+        from_port: from_port.to_string(),
+        to_plugin: 2,
+        to_port: to_port.to_string(),
+        kind: "shared_memory".to_string(),
+        gain: 1.0,
+    };
+    let workspace = WorkspaceDefinition {
+        name: "test".to_string(),
+        description: String::new(),
+        target_hz: 1000,
+        plugins: vec![plugin(1, "motor"), plugin(2, "csv_recorder")],
+        connections: vec![connection("speed", "in_0"), connection("current", "in_1")],
+        settings: Default::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+
+    assert_eq!(port_unit(&installed, &workspace, 1, "speed"), Some("rad/s"));
+    // Blank units are treated as untagged
+    assert_eq!(port_unit(&installed, &workspace, 1, "current"), None);
+    assert_eq!(
+        default_csv_column(&workspace, &installed, 2, 0),
+        "motor_1_speed (rad/s)"
+    );
+    assert_eq!(default_csv_column(&workspace, &installed, 2, 1), "motor_1_current");
+}
+
+#[test]
+fn filling_csv_columns_keeps_them_aligned_with_inputs() {
+    use rtsyn_core::connection::fill_csv_columns;
+    use workspace::{PluginDefinition, WorkspaceDefinition};
+
+    let connection = |from_plugin: u64, to_port: &str| ConnectionDefinition {
+        from_plugin,
+        from_port: "out".to_string(),
+        to_plugin: 9,
+        to_port: to_port.to_string(),
+        kind: "shared_memory".to_string(),
+        gain: 1.0,
+    };
+    let plugin = |id: u64, kind: &str, config: serde_json::Value| PluginDefinition {
+        id,
+        kind: kind.to_string(),
+        config,
+        priority: 0,
+        running: false,
+    };
+    let mut workspace = WorkspaceDefinition {
+        name: "test".to_string(),
+        description: String::new(),
+        target_hz: 1000,
+        plugins: vec![
+            plugin(1, "gain", serde_json::json!({})),
+            plugin(2, "gain", serde_json::json!({})),
+            plugin(
+                9,
+                "csv_recorder",
+                serde_json::json!({ "input_count": 3, "columns": ["", "kept"] }),
+            ),
+        ],
+        connections: vec![connection(1, "in_0"), connection(2, "in_2")],
+        settings: Default::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+    let columns = |workspace: &WorkspaceDefinition| workspace.plugins[2].config["columns"].clone();
+
+    assert_eq!(fill_csv_columns(&mut workspace, &[], 9, false), 2);
+    assert_eq!(
+        columns(&workspace),
+        serde_json::json!(["gain_1_out", "kept", "gain_2_out"])
+    );
+    assert_eq!(fill_csv_columns(&mut workspace, &[], 9, false), 0);
+
+    assert_eq!(fill_csv_columns(&mut workspace, &[], 9, true), 1);
+    assert_eq!(
+        columns(&workspace),
+        serde_json::json!(["gain_1_out", "csv_recorder_9_in_1", "gain_2_out"])
+    );
+    assert_eq!(fill_csv_columns(&mut workspace, &[], 1, true), 0);
+}
+
+#[test]
+fn can_add_connection_previews_the_next_extendable_input() {
+    use rtsyn_core::connection::{add_connection as add_workspace_connection, can_add_connection};
+    use workspace::{ConnectionRuleError, PluginDefinition, WorkspaceDefinition};
+
+    let plugin = |id: u64, kind: &str| PluginDefinition {
+        id,
+        kind: kind.to_string(),
+        config: serde_json::json!({}),
+        priority: 0,
+        running: false,
+    };
+    let mut workspace = WorkspaceDefinition {
+        name: "test".to_string(),
+        description: String::new(),
+        target_hz: 1000,
+        plugins: vec![plugin(1, "rms"), plugin(2, "csv_recorder"), plugin(3, "rms")],
+        connections: Vec::new(),
+        settings: Default::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+    add_workspace_connection(
+        &mut workspace,
+        &[],
+        ConnectionDefinition {
+            from_plugin: 1,
+            from_port: "out".to_string(),
+            to_plugin: 2,
+            to_port: "in".to_string(),
+            kind: "shared_memory".to_string(),
+            gain: 1.0,
+        },
+    )
+    .expect("connect recorder");
+
+    // "in" maps to the next free in_N, so another source still fits
+    assert_eq!(can_add_connection(&workspace, 3, "out", 2, "in"), Ok(()));
+    assert_eq!(
+        can_add_connection(&workspace, 3, "out", 2, "in_0"),
+        Err(ConnectionRuleError::InputLimitExceeded)
+    );
+    assert_eq!(can_add_connection(&workspace, 2, "out", 2, "in"), Err(ConnectionRuleError::SelfConnection));
+    assert_eq!(workspace.connections.len(), 1);
+}
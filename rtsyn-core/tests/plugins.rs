@@ -1,7 +1,15 @@
-use rtsyn_core::plugin::{PluginCatalog, PluginMetadataSource};
+use rtsyn_core::plugin::{
+    active_faults, custom_color, filter_imported_config, format_color, is_fault_output,
+    plugin_display_name, InstalledPlugin, PluginCatalog, PluginManager, PluginManifest,
+    PluginMetadataSource, FAULT_OUTPUTS_KEY,
+};
+use rtsyn_core::scaffold::{
+    parse_port_list, parse_variable_list, write_plugin_crate, PluginScaffold,
+};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
-use workspace::WorkspaceDefinition;
+use workspace::{PluginDefinition, WorkspaceDefinition};
 
 struct NoMetadata;
 
@@ -58,6 +66,8 @@ fn install_add_uninstall_plugin() {
         plugins: Vec::new(),
         connections: Vec::new(),
         settings: workspace::WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
     };
 
     let id = catalog
@@ -72,3 +82,336 @@ fn install_add_uninstall_plugin() {
         .expect("uninstall plugin");
     assert_eq!(removed.manifest.kind, "test_plugin");
 }
+
+#[test]
+fn search_paths_are_persisted_and_scanned() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let external = temp.path().join("external");
+    let plugin_dir = external.join("ext-plugin");
+    std::fs::create_dir_all(&plugin_dir).expect("create plugin dir");
+    write_plugin_manifest(&plugin_dir, "ext_plugin", "External Plugin");
+
+    let install_db = temp.path().join("installed_plugins.json");
+    let mut manager = PluginManager::new(install_db.clone());
+    manager
+        .add_search_path(external.clone())
+        .expect("add search path");
+    assert!(manager.add_search_path(external.clone()).is_err());
+    assert!(manager
+        .add_search_path(temp.path().join("missing"))
+        .is_err());
+
+    manager.scan_detected_plugins_in(&[]);
+    assert!(manager
+        .detected_plugins
+        .iter()
+        .any(|p| p.manifest.kind == "ext_plugin" && p.path == plugin_dir));
+
+    let mut reloaded = PluginManager::new(install_db);
+    assert_eq!(reloaded.search_paths, vec![external.clone()]);
+    assert!(reloaded.remove_search_path(&external));
+    assert!(reloaded.search_paths.is_empty());
+}
+
+#[test]
+fn library_resolution_prefers_manifest_then_search_paths() {
+    let temp = tempfile::tempdir().expect("tempdir");
+    let plugin_dir = temp.path().join("plugin");
+    let release = plugin_dir.join("target/release");
+    let user_dir = temp.path().join("libs");
+    std::fs::create_dir_all(&release).expect("create release dir");
+    std::fs::create_dir_all(&user_dir).expect("create user dir");
+    std::fs::write(release.join("libdemo.so"), b"").expect("write default lib");
+    std::fs::write(user_dir.join("libdemo.so"), b"").expect("write user lib");
+
+    let mut manifest = PluginManifest {
+        name: "Demo".to_string(),
+        kind: "demo".to_string(),
+        version: None,
+        description: None,
+        library: None,
+        port_units: HashMap::new(),
+        starts_expanded: true,
+        icon: None,
+    };
+    let search_paths = vec![user_dir.clone()];
+
+    assert_eq!(
+        PluginManager::resolve_library_path_in(&manifest, &plugin_dir, &[]),
+        Some(release.join("libdemo.so"))
+    );
+    assert_eq!(
+        PluginManager::resolve_library_path_in(&manifest, &plugin_dir, &search_paths),
+        Some(user_dir.join("libdemo.so"))
+    );
+
+    std::fs::write(plugin_dir.join("custom.so"), b"").expect("write manifest lib");
+    manifest.library = Some("custom.so".to_string());
+    assert_eq!(
+        PluginManager::resolve_library_path_in(&manifest, &plugin_dir, &search_paths),
+        Some(plugin_dir.join("custom.so"))
+    );
+}
+
+#[test]
+fn imported_config_keeps_only_known_keys() {
+    let installed = InstalledPlugin {
+        manifest: PluginManifest {
+            name: "PID".to_string(),
+            kind: "pid".to_string(),
+            version: None,
+            description: None,
+            library: None,
+            port_units: HashMap::new(),
+            starts_expanded: true,
+            icon: None,
+        },
+        path: PathBuf::new(),
+        library_path: None,
+        removable: true,
+        metadata_inputs: Vec::new(),
+        metadata_outputs: Vec::new(),
+        metadata_variables: vec![("kp".to_string(), 1.0), ("ki".to_string(), 0.0)],
+        display_schema: None,
+        ui_schema: None,
+    };
+    let imported = serde_json::json!({ "kp": 2.5, "ki": 0.1, "library_path": "/tmp/x.so" });
+
+    let result = filter_imported_config(&installed, &imported).expect("object");
+    let mut keys: Vec<&str> = result.accepted.iter().map(|(key, _)| key.as_str()).collect();
+    keys.sort();
+    assert_eq!(keys, vec!["ki", "kp"]);
+    assert_eq!(result.skipped, vec!["library_path".to_string()]);
+
+    assert!(filter_imported_config(&installed, &serde_json::json!([1, 2])).is_err());
+}
+
+#[test]
+fn custom_label_and_color_override_the_kind_defaults() {
+    let installed = vec![InstalledPlugin {
+        manifest: PluginManifest {
+            name: "PID".to_string(),
+            kind: "pid".to_string(),
+            version: None,
+            description: None,
+            library: None,
+            port_units: HashMap::new(),
+            starts_expanded: true,
+            icon: None,
+        },
+        path: PathBuf::new(),
+        library_path: None,
+        removable: true,
+        metadata_inputs: Vec::new(),
+        metadata_outputs: Vec::new(),
+        metadata_variables: Vec::new(),
+        display_schema: None,
+        ui_schema: None,
+    }];
+    let plugin = |id: u64, config: serde_json::Value| PluginDefinition {
+        id,
+        kind: "pid".to_string(),
+        config,
+        priority: 0,
+        running: false,
+    };
+    let workspace = WorkspaceDefinition {
+        name: "test".to_string(),
+        description: String::new(),
+        target_hz: 1000,
+        plugins: vec![
+            plugin(1, serde_json::json!({})),
+            plugin(2, serde_json::json!({ "_ui_label": " Pitch loop ", "_ui_color": "#ff8000" })),
+            plugin(3, serde_json::json!({ "_ui_label": "  ", "_ui_color": "orange" })),
+        ],
+        connections: Vec::new(),
+        settings: Default::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+
+    assert_eq!(plugin_display_name(&installed, &workspace, 1), "PID");
+    assert_eq!(plugin_display_name(&installed, &workspace, 2), "Pitch loop");
+    assert_eq!(plugin_display_name(&installed, &workspace, 3), "PID");
+
+    assert_eq!(custom_color(&workspace.plugins[0].config), None);
+    assert_eq!(custom_color(&workspace.plugins[1].config), Some([255, 128, 0]));
+    assert_eq!(custom_color(&workspace.plugins[2].config), None);
+    assert_eq!(format_color([255, 128, 0]), "#ff8000");
+}
+
+#[test]
+fn active_faults_follow_the_naming_convention_and_overrides() {
+    assert!(is_fault_output("alarm"));
+    assert!(is_fault_output("out_of_range"));
+    assert!(is_fault_output("realtime_violation"));
+    assert!(!is_fault_output("overrun_count"));
+
+    let plugin = |id: u64, running: bool, config: serde_json::Value| PluginDefinition {
+        id,
+        kind: "test".to_string(),
+        config,
+        priority: 0,
+        running,
+    };
+    let workspace = WorkspaceDefinition {
+        name: "test".to_string(),
+        description: String::new(),
+        target_hz: 1000,
+        plugins: vec![
+            plugin(1, true, serde_json::json!({})),
+            plugin(2, true, serde_json::json!({ FAULT_OUTPUTS_KEY: ["active"] })),
+            plugin(3, false, serde_json::json!({})),
+        ],
+        connections: Vec::new(),
+        settings: Default::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+    let outputs: HashMap<(u64, String), f64> = [
+        ((1, "out_of_range"), 1.0),
+        ((1, "alarm"), 0.0),
+        ((1, "out"), 3.0),
+        // The override replaces the convention
+        ((2, "active"), 1.0),
+        ((2, "alarm"), 1.0),
+        // Stopped plugins do not count
+        ((3, "alarm"), 1.0),
+    ]
+    .into_iter()
+    .map(|((id, port), value)| ((id, port.to_string()), value))
+    .collect();
+
+    assert_eq!(
+        active_faults(&workspace, &outputs),
+        vec![(1, "out_of_range".to_string()), (2, "active".to_string())]
+    );
+}
+
+#[test]
+fn batched_config_is_validated_as_a_whole() {
+    use rtsyn_core::plugin::{supports_apply, validate_config};
+    use serde_json::json;
+
+    assert!(supports_apply("saturation"));
+    assert!(!supports_apply("scale"));
+
+    // Raising min above the old max is fine once max moves too
+    assert!(validate_config("saturation", &json!({ "min": 2.0, "max": 1.0 })).is_err());
+    assert!(validate_config("saturation", &json!({ "min": 2.0, "max": 3.0 })).is_ok());
+    assert!(validate_config("alarm", &json!({ "low_limit": 1.0, "high_limit": 1.0 })).is_ok());
+    assert!(validate_config("histogram", &json!({ "range_min": 1.0, "range_max": 1.0 })).is_err());
+    let error = validate_config("lookup_table", &json!({ "x_points": [0.0, 1.0], "y_points": [0.0] }))
+        .unwrap_err();
+    assert!(error.contains("y_points"));
+    assert!(validate_config("transfer_function", &json!({ "b": [1.0], "a": [0.0, 1.0] })).is_err());
+    assert!(validate_config("transfer_function", &json!({ "b": [1.0], "a": [] })).is_err());
+    assert!(validate_config("transfer_function", &json!({ "b": [0.5, 0.5], "a": [1.0] })).is_ok());
+    assert!(validate_config("scale", &json!({ "min": 2.0, "max": 1.0 })).is_ok());
+}
+
+#[test]
+fn scaffold_writes_a_plugin_crate_once() {
+    let scaffold = PluginScaffold {
+        name: "Soft Limiter 2".to_string(),
+        description: "Clamps \"in\" smoothly".to_string(),
+        inputs: parse_port_list("in, enable"),
+        outputs: parse_port_list("out"),
+        variables: parse_variable_list("gain=2.5, type").expect("variables"),
+    };
+    assert_eq!(scaffold.kind(), "soft_limiter_2");
+    assert_eq!(
+        scaffold.variables,
+        vec![("gain".to_string(), 2.5), ("type".to_string(), 0.0)]
+    );
+
+    let temp = tempfile::tempdir().expect("tempdir");
+    let folder = write_plugin_crate(temp.path(), &scaffold).expect("write crate");
+    assert_eq!(folder, temp.path().join("soft_limiter_2"));
+
+    let manifest: PluginManifest =
+        toml::from_str(&std::fs::read_to_string(folder.join("plugin.toml")).unwrap()).unwrap();
+    assert_eq!(manifest.kind, "soft_limiter_2");
+    assert_eq!(manifest.description.as_deref(), Some("Clamps \"in\" smoothly"));
+    let cargo = std::fs::read_to_string(folder.join("Cargo.toml")).unwrap();
+    assert!(cargo.contains("name = \"soft_limiter_2\""));
+    assert!(cargo.contains("crate-type = [\"cdylib\"]"));
+    let lib = std::fs::read_to_string(folder.join("src/lib.rs")).unwrap();
+    assert!(lib.contains("struct SoftLimiter2Plugin"));
+    assert!(lib.contains("\"in\" => self.r#in = value,"));
+    assert!(lib.contains("json!([\"in\", \"enable\"])"));
+    assert!(lib.contains("ConfigField::float(\"gain\", \"Gain\")"));
+
+    // An existing folder is never overwritten
+    assert!(write_plugin_crate(temp.path(), &scaffold).is_err());
+
+    let invalid = |inputs: &str, outputs: &str| PluginScaffold {
+        name: "x".to_string(),
+        inputs: parse_port_list(inputs),
+        outputs: parse_port_list(outputs),
+        ..PluginScaffold::default()
+    };
+    assert!(invalid("a", "a").validate().is_err());
+    assert!(invalid("Bad", "").validate().is_err());
+    assert!(invalid("self", "").validate().is_err());
+    assert!(PluginScaffold::default().validate().is_err());
+    assert!(parse_variable_list("gain=fast").is_err());
+}
+
+#[test]
+fn cards_follow_starts_expanded_until_toggled() {
+    use rtsyn_core::plugin::card_collapsed;
+
+    let manifest: PluginManifest = toml::from_str("name = \"Scope\"\nkind = \"scope\"\n").unwrap();
+    assert!(manifest.starts_expanded);
+    let manifest: PluginManifest =
+        toml::from_str("name = \"Scope\"\nkind = \"scope\"\nstarts_expanded = false\n").unwrap();
+    assert!(!manifest.starts_expanded);
+
+    let untouched = serde_json::json!({ "gain": 1.0 });
+    assert!(!card_collapsed(&untouched, true));
+    assert!(card_collapsed(&untouched, false));
+    let expanded = serde_json::json!({ "_ui_collapsed": false });
+    assert!(!card_collapsed(&expanded, false));
+    let collapsed = serde_json::json!({ "_ui_collapsed": true });
+    assert!(card_collapsed(&collapsed, true));
+}
+
+#[test]
+fn manifest_icons_resolve_to_glyphs_or_images() {
+    use rtsyn_core::plugin::{plugin_icon, PluginIcon, DEFAULT_PLUGIN_ICON};
+
+    let installed = |icon: Option<&str>| {
+        let mut manifest: PluginManifest =
+            toml::from_str("name = \"Scope\"\nkind = \"scope\"\n").unwrap();
+        manifest.icon = icon.map(str::to_string);
+        InstalledPlugin {
+            manifest,
+            path: PathBuf::from("/plugins/scope"),
+            library_path: None,
+            removable: true,
+            metadata_inputs: Vec::new(),
+            metadata_outputs: Vec::new(),
+            metadata_variables: Vec::new(),
+            display_schema: None,
+            ui_schema: None,
+        }
+    };
+    let manifest: PluginManifest =
+        toml::from_str("name = \"Scope\"\nkind = \"scope\"\nicon = \"f1e6\"\n").unwrap();
+    assert_eq!(manifest.icon.as_deref(), Some("f1e6"));
+
+    assert_eq!(plugin_icon(&installed(None)), PluginIcon::Glyph(DEFAULT_PLUGIN_ICON));
+    for hex in ["f1e6", "U+f1e6", "0xF1E6", "\u{f1e6}"] {
+        assert_eq!(plugin_icon(&installed(Some(hex))), PluginIcon::Glyph('\u{f1e6}'), "{hex}");
+    }
+    assert_eq!(
+        plugin_icon(&installed(Some("icons/scope.png"))),
+        PluginIcon::Image(PathBuf::from("/plugins/scope/icons/scope.png"))
+    );
+    assert_eq!(
+        plugin_icon(&installed(Some("/usr/share/scope.PNG"))),
+        PluginIcon::Image(PathBuf::from("/usr/share/scope.PNG"))
+    );
+    assert_eq!(plugin_icon(&installed(Some("wave"))), PluginIcon::Glyph(DEFAULT_PLUGIN_ICON));
+}
@@ -25,4 +25,110 @@ fn save_load_and_scan_workspaces() {
     manager.scan_workspaces();
     assert_eq!(manager.workspace_entries.len(), 1);
     assert_eq!(manager.workspace_entries[0].name, "alpha");
+    // Saving stamps the file; the first save also sets the creation time
+    let metadata = &manager.workspace_entries[0].metadata;
+    assert!(metadata.modified_at > 0);
+    assert_eq!(metadata.created_at, metadata.modified_at);
+}
+
+#[test]
+fn duplicate_workspace_saves_a_renumbered_copy() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let mut manager = WorkspaceManager::new(dir.path().to_path_buf());
+    manager.workspace.name = "base".to_string();
+    manager.workspace.plugins.push(workspace::PluginDefinition {
+        id: 5,
+        kind: "gain".to_string(),
+        config: serde_json::json!({}),
+        priority: 0,
+        running: false,
+    });
+
+    let path = manager
+        .duplicate_workspace("branch", "copy")
+        .expect("duplicate workspace");
+    assert_eq!(manager.workspace.name, "base");
+    assert_eq!(manager.workspace.plugins[0].id, 5);
+
+    manager.load_workspace(&path).expect("load copy");
+    assert_eq!(manager.workspace.name, "branch");
+    assert_eq!(manager.workspace.plugins[0].id, 1);
+    assert!(manager.workspace.metadata.created_at > 0);
+    assert_eq!(manager.workspace.metadata.created_at, manager.workspace.metadata.modified_at);
+    assert!(manager.duplicate_workspace("branch", "").is_err());
+}
+
+#[test]
+fn unsaved_changes_clear_on_save_and_load() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let mut manager = WorkspaceManager::new(dir.path().to_path_buf());
+    assert!(!manager.has_unsaved_changes());
+
+    manager.mark_dirty();
+    assert!(manager.has_unsaved_changes());
+    manager.save_workspace_as("alpha", "").expect("save workspace");
+    assert!(!manager.has_unsaved_changes());
+
+    manager.mark_dirty();
+    // Syncing the runtime does not count as saving
+    manager.workspace_dirty = false;
+    assert!(manager.has_unsaved_changes());
+    let path = manager.workspace_path.clone();
+    manager.load_workspace(&path).expect("load workspace");
+    assert!(!manager.has_unsaved_changes());
+}
+
+#[test]
+fn tabs_park_and_restore_workspaces() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let mut manager = WorkspaceManager::new(dir.path().to_path_buf());
+    manager.save_workspace_as("alpha", "").expect("save workspace");
+    let alpha_path = manager.workspace_path.clone();
+    manager.mark_dirty();
+
+    assert_eq!(manager.new_tab(), 1);
+    assert!(manager.workspace_path.as_os_str().is_empty());
+    assert!(!manager.has_unsaved_changes());
+    manager.workspace.name = "beta".to_string();
+    manager.workspace_dirty = false;
+    assert_eq!(
+        manager.tabs(),
+        vec![("alpha".to_string(), true), ("beta".to_string(), false)]
+    );
+    assert_eq!(manager.tab_for_path(&alpha_path), Some(0));
+    assert_eq!(manager.other_tab_unsaved(), Some(0));
+
+    manager.switch_tab(0);
+    assert_eq!(manager.active_tab(), 0);
+    assert_eq!(manager.workspace.name, "alpha");
+    assert_eq!(manager.workspace_path, alpha_path);
+    assert!(manager.has_unsaved_changes());
+    assert!(manager.workspace_dirty);
+
+    // Closing the active tab activates the one that is left
+    manager.close_tab(0).expect("close tab");
+    assert_eq!(manager.active_tab(), 0);
+    assert_eq!(manager.workspace.name, "beta");
+    assert!(manager.close_tab(0).is_err());
+}
+
+#[test]
+fn runtime_settings_patch_sets_missed_deadline_policy() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let mut manager = WorkspaceManager::new(dir.path().to_path_buf());
+    assert_eq!(
+        manager.runtime_settings().unwrap().missed_deadline_policy,
+        workspace::MissedDeadlinePolicy::BestEffort
+    );
+
+    manager
+        .apply_runtime_settings_json(r#"{"missed_deadline_policy": "catch_up"}"#)
+        .expect("valid policy");
+    assert_eq!(
+        manager.runtime_settings().unwrap().missed_deadline_policy,
+        workspace::MissedDeadlinePolicy::CatchUp
+    );
+    assert!(manager
+        .apply_runtime_settings_json(r#"{"missed_deadline_policy": "later"}"#)
+        .is_err());
 }
@@ -0,0 +1,400 @@
+use eframe::egui::{vec2, Pos2, Rect, Vec2};
+use std::collections::{HashMap, HashSet};
+
+/// Gap between cards placed by [`auto_arrange`].
+const CARD_GAP: f32 = 40.0;
+
+/// Align and distribute actions for a multi-selection of plugin cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CardLayout {
+    AlignLeft,
+    AlignRight,
+    AlignTop,
+    AlignBottom,
+    DistributeHorizontally,
+    DistributeVertically,
+}
+
+impl CardLayout {
+    pub(crate) const ALL: [CardLayout; 6] = [
+        CardLayout::AlignLeft,
+        CardLayout::AlignRight,
+        CardLayout::AlignTop,
+        CardLayout::AlignBottom,
+        CardLayout::DistributeHorizontally,
+        CardLayout::DistributeVertically,
+    ];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            CardLayout::AlignLeft => "Align left",
+            CardLayout::AlignRight => "Align right",
+            CardLayout::AlignTop => "Align top",
+            CardLayout::AlignBottom => "Align bottom",
+            CardLayout::DistributeHorizontally => "Distribute horizontally",
+            CardLayout::DistributeVertically => "Distribute vertically",
+        }
+    }
+
+    /// Cards needed before the action can change anything.
+    pub(crate) fn min_cards(self) -> usize {
+        match self {
+            CardLayout::DistributeHorizontally | CardLayout::DistributeVertically => 3,
+            _ => 2,
+        }
+    }
+}
+
+/// New top-left corners for `cards`, given as canvas-space rects. Aligning
+/// moves every card to the outermost edge of the selection; distributing
+/// keeps the first and last card and spaces the rest with equal gaps.
+pub(crate) fn arrange(cards: &[(u64, Rect)], layout: CardLayout) -> Vec<(u64, Pos2)> {
+    if cards.len() < layout.min_cards() {
+        return Vec::new();
+    }
+    match layout {
+        CardLayout::AlignLeft => {
+            let left = cards.iter().map(|(_, r)| r.min.x).fold(f32::INFINITY, f32::min);
+            cards.iter().map(|(id, r)| (*id, Pos2::new(left, r.min.y))).collect()
+        }
+        CardLayout::AlignRight => {
+            let right = cards.iter().map(|(_, r)| r.max.x).fold(f32::NEG_INFINITY, f32::max);
+            cards
+                .iter()
+                .map(|(id, r)| (*id, Pos2::new(right - r.width(), r.min.y)))
+                .collect()
+        }
+        CardLayout::AlignTop => {
+            let top = cards.iter().map(|(_, r)| r.min.y).fold(f32::INFINITY, f32::min);
+            cards.iter().map(|(id, r)| (*id, Pos2::new(r.min.x, top))).collect()
+        }
+        CardLayout::AlignBottom => {
+            let bottom = cards.iter().map(|(_, r)| r.max.y).fold(f32::NEG_INFINITY, f32::max);
+            cards
+                .iter()
+                .map(|(id, r)| (*id, Pos2::new(r.min.x, bottom - r.height())))
+                .collect()
+        }
+        CardLayout::DistributeHorizontally => distribute(cards, |r| (r.min.x, r.width()), |r, x| {
+            Pos2::new(x, r.min.y)
+        }),
+        CardLayout::DistributeVertically => distribute(cards, |r| (r.min.y, r.height()), |r, y| {
+            Pos2::new(r.min.x, y)
+        }),
+    }
+}
+
+/// Whole-canvas layouts computed from the connection graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AutoLayout {
+    /// Columns by topological rank, so data flows left to right.
+    Layered,
+    /// Connected cards pull together, every pair of cards pushes apart.
+    ForceDirected,
+}
+
+impl AutoLayout {
+    pub(crate) const ALL: [AutoLayout; 2] = [AutoLayout::Layered, AutoLayout::ForceDirected];
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            AutoLayout::Layered => "Layered (data flow)",
+            AutoLayout::ForceDirected => "Force-directed",
+        }
+    }
+}
+
+/// Top-left corners for every card, given its size, so that the layout's
+/// bounding box starts at `origin`. `edges` are (source, target) card ids;
+/// edges to unknown cards are ignored.
+pub(crate) fn auto_arrange(
+    cards: &[(u64, Vec2)],
+    edges: &[(u64, u64)],
+    layout: AutoLayout,
+    origin: Pos2,
+) -> Vec<(u64, Pos2)> {
+    let ids: HashSet<u64> = cards.iter().map(|(id, _)| *id).collect();
+    let mut edges: Vec<(u64, u64)> = edges
+        .iter()
+        .copied()
+        .filter(|(from, to)| from != to && ids.contains(from) && ids.contains(to))
+        .collect();
+    edges.sort_unstable();
+    edges.dedup();
+    let placed = match layout {
+        AutoLayout::Layered => layered(cards, &edges),
+        AutoLayout::ForceDirected => force_directed(cards, &edges),
+    };
+    let min = placed
+        .iter()
+        .fold(Pos2::new(f32::INFINITY, f32::INFINITY), |acc, (_, pos)| acc.min(*pos));
+    placed
+        .into_iter()
+        .map(|(id, pos)| (id, origin + (pos - min)))
+        .collect()
+}
+
+/// Sugiyama-style layering: feedback edges are dropped so the graph is
+/// acyclic, every card goes one column right of its furthest source, and
+/// each column is ordered by the mean row of the cards feeding it.
+fn layered(cards: &[(u64, Vec2)], edges: &[(u64, u64)]) -> Vec<(u64, Pos2)> {
+    let mut ordered: Vec<u64> = cards.iter().map(|(id, _)| *id).collect();
+    ordered.sort_unstable();
+    let forward = acyclic_edges(&ordered, edges);
+
+    // Longest path from the sources, in topological order
+    let mut rank: HashMap<u64, usize> = ordered.iter().map(|id| (*id, 0)).collect();
+    let mut incoming: HashMap<u64, usize> = ordered.iter().map(|id| (*id, 0)).collect();
+    for (_, to) in &forward {
+        *incoming.entry(*to).or_default() += 1;
+    }
+    let mut ready: Vec<u64> = ordered.iter().copied().filter(|id| incoming[id] == 0).rev().collect();
+    while let Some(id) = ready.pop() {
+        for (_, to) in forward.iter().filter(|(from, _)| *from == id) {
+            let next = rank[&id] + 1;
+            let to_rank = rank.entry(*to).or_default();
+            *to_rank = (*to_rank).max(next);
+            let remaining = incoming.entry(*to).or_default();
+            *remaining -= 1;
+            if *remaining == 0 {
+                ready.push(*to);
+            }
+        }
+    }
+
+    let layer_count = rank.values().max().map_or(0, |max| max + 1);
+    let mut layers: Vec<Vec<u64>> = vec![Vec::new(); layer_count];
+    for id in &ordered {
+        layers[rank[id]].push(*id);
+    }
+    let mut row: HashMap<u64, f32> = HashMap::new();
+    for layer in &mut layers {
+        let barycenter = |id: &u64| {
+            let rows: Vec<f32> = forward
+                .iter()
+                .filter(|(_, to)| to == id)
+                .filter_map(|(from, _)| row.get(from).copied())
+                .collect();
+            if rows.is_empty() {
+                f32::INFINITY
+            } else {
+                rows.iter().sum::<f32>() / rows.len() as f32
+            }
+        };
+        let mut keyed: Vec<(f32, u64)> = layer.iter().map(|id| (barycenter(id), *id)).collect();
+        keyed.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        *layer = keyed.into_iter().map(|(_, id)| id).collect();
+        for (index, id) in layer.iter().enumerate() {
+            row.insert(*id, index as f32);
+        }
+    }
+
+    let size: HashMap<u64, Vec2> = cards.iter().copied().collect();
+    let mut positions = Vec::with_capacity(cards.len());
+    let mut x = 0.0;
+    for layer in &layers {
+        let mut y = 0.0;
+        let mut width: f32 = 0.0;
+        for id in layer {
+            positions.push((*id, Pos2::new(x, y)));
+            y += size[id].y + CARD_GAP;
+            width = width.max(size[id].x);
+        }
+        x += width + CARD_GAP;
+    }
+    positions
+}
+
+/// Edges left after dropping those that close a cycle, found by a depth
+/// first search from the lowest ids.
+fn acyclic_edges(ordered: &[u64], edges: &[(u64, u64)]) -> Vec<(u64, u64)> {
+    let mut state: HashMap<u64, bool> = HashMap::new();
+    let mut forward = Vec::with_capacity(edges.len());
+    for root in ordered {
+        if state.contains_key(root) {
+            continue;
+        }
+        // (node, next edge index); `false` in `state` marks nodes on the path
+        let mut stack = vec![(*root, 0usize)];
+        state.insert(*root, false);
+        while let Some((node, next)) = stack.last_mut() {
+            let node = *node;
+            let Some(&(_, to)) = edges.iter().filter(|(from, _)| *from == node).nth(*next) else {
+                state.insert(node, true);
+                stack.pop();
+                continue;
+            };
+            *next += 1;
+            match state.get(&to) {
+                Some(false) => {}
+                Some(true) => forward.push((node, to)),
+                None => {
+                    forward.push((node, to));
+                    state.insert(to, false);
+                    stack.push((to, 0));
+                }
+            }
+        }
+    }
+    forward
+}
+
+/// Fruchterman-Reingold from cards evenly spaced on a circle, so the result
+/// only depends on the graph.
+fn force_directed(cards: &[(u64, Vec2)], edges: &[(u64, u64)]) -> Vec<(u64, Pos2)> {
+    const ITERATIONS: usize = 300;
+    let count = cards.len();
+    if count == 0 {
+        return Vec::new();
+    }
+    let spacing = cards
+        .iter()
+        .map(|(_, size)| size.length())
+        .fold(0.0, f32::max)
+        + CARD_GAP;
+    let index: HashMap<u64, usize> = cards.iter().enumerate().map(|(i, (id, _))| (*id, i)).collect();
+    let radius = spacing * count as f32 / std::f32::consts::TAU;
+    let mut centers: Vec<Vec2> = (0..count)
+        .map(|i| {
+            let angle = std::f32::consts::TAU * i as f32 / count as f32;
+            vec2(angle.cos(), angle.sin()) * radius
+        })
+        .collect();
+    let mut temperature = spacing;
+    for _ in 0..ITERATIONS {
+        let mut shift = vec![Vec2::ZERO; count];
+        for a in 0..count {
+            for b in a + 1..count {
+                let delta = centers[a] - centers[b];
+                let distance = delta.length().max(1.0);
+                let push = delta / distance * (spacing * spacing / distance);
+                shift[a] += push;
+                shift[b] -= push;
+            }
+        }
+        for (from, to) in edges {
+            let (a, b) = (index[from], index[to]);
+            let delta = centers[a] - centers[b];
+            let distance = delta.length().max(1.0);
+            let pull = delta / distance * (distance * distance / spacing);
+            shift[a] -= pull;
+            shift[b] += pull;
+        }
+        for (center, shift) in centers.iter_mut().zip(shift) {
+            let length = shift.length();
+            if length > 0.0 {
+                *center += shift / length * length.min(temperature);
+            }
+        }
+        temperature = (temperature * 0.98).max(1.0);
+    }
+    cards
+        .iter()
+        .zip(centers)
+        .map(|((id, size), center)| (*id, (center - *size / 2.0).to_pos2()))
+        .collect()
+}
+
+fn distribute(
+    cards: &[(u64, Rect)],
+    axis: impl Fn(&Rect) -> (f32, f32),
+    place: impl Fn(&Rect, f32) -> Pos2,
+) -> Vec<(u64, Pos2)> {
+    let mut sorted: Vec<&(u64, Rect)> = cards.iter().collect();
+    sorted.sort_by(|a, b| axis(&a.1).0.total_cmp(&axis(&b.1).0).then(a.0.cmp(&b.0)));
+    let (first_start, _) = axis(&sorted[0].1);
+    let (last_start, last_len) = axis(&sorted[sorted.len() - 1].1);
+    let span = last_start + last_len - first_start;
+    let total: f32 = sorted.iter().map(|(_, r)| axis(r).1).sum();
+    let gap = (span - total) / (sorted.len() - 1) as f32;
+
+    let mut cursor = first_start;
+    sorted
+        .into_iter()
+        .map(|(id, rect)| {
+            let pos = place(rect, cursor);
+            cursor += axis(rect).1 + gap;
+            (*id, pos)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eframe::egui::vec2;
+
+    fn card(x: f32, y: f32, w: f32, h: f32) -> Rect {
+        Rect::from_min_size(Pos2::new(x, y), vec2(w, h))
+    }
+
+    #[test]
+    fn align_uses_the_outermost_edge() {
+        let cards = [(1, card(10.0, 0.0, 100.0, 50.0)), (2, card(40.0, 80.0, 200.0, 30.0))];
+        assert_eq!(
+            arrange(&cards, CardLayout::AlignLeft),
+            vec![(1, Pos2::new(10.0, 0.0)), (2, Pos2::new(10.0, 80.0))]
+        );
+        assert_eq!(
+            arrange(&cards, CardLayout::AlignRight),
+            vec![(1, Pos2::new(140.0, 0.0)), (2, Pos2::new(40.0, 80.0))]
+        );
+        assert_eq!(
+            arrange(&cards, CardLayout::AlignBottom),
+            vec![(1, Pos2::new(10.0, 60.0)), (2, Pos2::new(40.0, 80.0))]
+        );
+        assert!(arrange(&cards[..1], CardLayout::AlignTop).is_empty());
+    }
+
+    #[test]
+    fn distribute_keeps_the_ends_and_equalizes_gaps() {
+        let cards = [
+            (1, card(0.0, 0.0, 100.0, 50.0)),
+            (2, card(400.0, 10.0, 100.0, 50.0)),
+            (3, card(120.0, 20.0, 50.0, 50.0)),
+        ];
+        // Span 0..500 holds 250 of cards: gaps of 125
+        assert_eq!(
+            arrange(&cards, CardLayout::DistributeHorizontally),
+            vec![
+                (1, Pos2::new(0.0, 0.0)),
+                (3, Pos2::new(225.0, 20.0)),
+                (2, Pos2::new(400.0, 10.0)),
+            ]
+        );
+        assert!(arrange(&cards[..2], CardLayout::DistributeVertically).is_empty());
+    }
+
+    #[test]
+    fn layered_layout_puts_cards_right_of_their_sources() {
+        let size = vec2(200.0, 100.0);
+        let cards = [(1, size), (2, size), (3, size), (4, size), (5, size)];
+        // 1 -> 2 -> 3, 1 -> 3, a 3 -> 2 feedback loop, 4 unconnected
+        let edges = [(1, 2), (2, 3), (1, 3), (3, 2), (1, 9)];
+        let placed: HashMap<u64, Pos2> =
+            auto_arrange(&cards, &edges, AutoLayout::Layered, Pos2::new(10.0, 20.0))
+                .into_iter()
+                .collect();
+        assert_eq!(placed[&1], Pos2::new(10.0, 20.0));
+        assert_eq!(placed[&4], Pos2::new(10.0, 160.0));
+        assert_eq!(placed[&2], Pos2::new(250.0, 20.0));
+        assert_eq!(placed[&3], Pos2::new(490.0, 20.0));
+        assert_eq!(placed[&5].x, 10.0);
+    }
+
+    #[test]
+    fn force_directed_layout_separates_cards() {
+        let size = vec2(200.0, 100.0);
+        let cards = [(1, size), (2, size), (3, size), (4, size)];
+        let edges = [(1, 2), (2, 3), (3, 4)];
+        let placed = auto_arrange(&cards, &edges, AutoLayout::ForceDirected, Pos2::ZERO);
+        assert_eq!(placed, auto_arrange(&cards, &edges, AutoLayout::ForceDirected, Pos2::ZERO));
+        let rects: Vec<Rect> = placed.iter().map(|(_, pos)| Rect::from_min_size(*pos, size)).collect();
+        for (i, a) in rects.iter().enumerate() {
+            assert!(a.min.x >= -0.01 && a.min.y >= -0.01);
+            for b in &rects[i + 1..] {
+                assert!(!a.intersects(*b));
+            }
+        }
+    }
+}
@@ -3,10 +3,23 @@ use rtsyn_core::connection as core_connections;
 use rtsyn_runtime::runtime::LogicMessage;
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
-use workspace::{remove_extendable_input, ConnectionDefinition, ConnectionRuleError};
+use workspace::{
+    remove_connection, remove_extendable_input, ConnectionDefinition, ConnectionRuleError,
+};
 
 impl GuiApp {
+    pub(crate) fn connection_rule_message(err: &ConnectionRuleError) -> &'static str {
+        match err {
+            ConnectionRuleError::SelfConnection => "Cannot connect a plugin to itself.",
+            ConnectionRuleError::InputLimitExceeded => "Input already has a connection.",
+            ConnectionRuleError::DuplicateConnection => "Connection between these plugins already exists.",
+        }
+    }
+
     pub(crate) fn add_connection(&mut self) {
+        if self.reject_if_locked() {
+            return;
+        }
         if self.connection_editor.from_idx == self.connection_editor.to_idx {
             self.show_info("Connections", "Cannot connect a plugin to itself");
             return;
@@ -43,18 +56,16 @@ impl GuiApp {
         if let Err(err) = core_connections::add_connection(
             &mut self.workspace_manager.workspace,
             &self.plugin_manager.installed_plugins,
-            from_plugin,
-            from_port,
-            to_plugin,
-            to_port,
-            kind,
+            ConnectionDefinition {
+                from_plugin,
+                from_port: from_port.to_string(),
+                to_plugin,
+                to_port: to_port.to_string(),
+                kind: kind.to_string(),
+                gain: self.connection_editor.gain,
+            },
         ) {
-            let message = match err {
-                ConnectionRuleError::SelfConnection => "Cannot connect a plugin to itself.",
-                ConnectionRuleError::InputLimitExceeded => "Input already has a connection.",
-                ConnectionRuleError::DuplicateConnection => "Connection between these plugins already exists.",
-            };
-            self.show_info("Connections", message);
+            self.show_info("Connections", Self::connection_rule_message(&err));
             return;
         }
         self.status = "Connection added".to_string();
@@ -62,7 +73,10 @@ impl GuiApp {
         self.mark_workspace_dirty();
     }
 
-    pub(crate) fn add_connection_direct(&mut self, from_plugin: u64, from_port: String, to_plugin: u64, to_port: String, kind: String) {
+    pub(crate) fn add_connection_direct(&mut self, from_plugin: u64, from_port: String, to_plugin: u64, to_port: String, kind: String, gain: f64) {
+        if self.reject_if_locked() {
+            return;
+        }
         if from_plugin == to_plugin {
             self.show_info("Connections", "Cannot connect a plugin to itself");
             return;
@@ -74,33 +88,41 @@ impl GuiApp {
         if let Err(err) = core_connections::add_connection(
             &mut self.workspace_manager.workspace,
             &self.plugin_manager.installed_plugins,
-            from_plugin,
-            &from_port,
-            to_plugin,
-            &to_port,
-            &kind,
+            ConnectionDefinition {
+                from_plugin,
+                from_port,
+                to_plugin,
+                to_port,
+                kind,
+                gain,
+            },
         ) {
-            let message = match err {
-                ConnectionRuleError::SelfConnection => "Cannot connect a plugin to itself.",
-                ConnectionRuleError::InputLimitExceeded => "Input already has a connection.",
-                ConnectionRuleError::DuplicateConnection => "Connection between these plugins already exists.",
-            };
-            self.show_info("Connections", message);
+            self.show_info("Connections", Self::connection_rule_message(&err));
             return;
         }
         self.mark_workspace_dirty();
         self.enforce_connection_dependent();
     }
 
+    pub(crate) fn set_connection_gain(&mut self, index: usize, gain: f64) {
+        if self.reject_if_locked() {
+            return;
+        }
+        if let Some(connection) = self.workspace_manager.workspace.connections.get_mut(index) {
+            connection.gain = gain;
+            self.mark_workspace_dirty();
+        }
+    }
+
     pub(crate) fn remove_connection_with_input(&mut self, connection: ConnectionDefinition) {
+        if self.reject_if_locked() {
+            return;
+        }
         if Self::extendable_input_index(&connection.to_port).is_some() {
             let target_kind = self.workspace_manager.workspace.plugins.iter().find(|p| p.id == connection.to_plugin).map(|p| p.kind.clone());
             if let Some(kind) = target_kind {
                 if self.is_extendable_inputs(&kind) {
-                    let matches = |left: &ConnectionDefinition, right: &ConnectionDefinition| {
-                        left.from_plugin == right.from_plugin && left.to_plugin == right.to_plugin && left.from_port == right.from_port && left.to_port == right.to_port && left.kind == right.kind
-                    };
-                    self.workspace_manager.workspace.connections.retain(|conn| !matches(conn, &connection));
+                    remove_connection(&mut self.workspace_manager.workspace.connections, &connection);
                     self.reindex_extendable_inputs(connection.to_plugin);
                     self.mark_workspace_dirty();
                     self.enforce_connection_dependent();
@@ -111,10 +133,7 @@ impl GuiApp {
                 }
             }
         }
-        let matches = |left: &ConnectionDefinition, right: &ConnectionDefinition| {
-            left.from_plugin == right.from_plugin && left.to_plugin == right.to_plugin && left.from_port == right.from_port && left.to_port == right.to_port && left.kind == right.kind
-        };
-        self.workspace_manager.workspace.connections.retain(|conn| !matches(conn, &connection));
+        remove_connection(&mut self.workspace_manager.workspace.connections, &connection);
         self.mark_workspace_dirty();
         self.enforce_connection_dependent();
     }
@@ -185,10 +204,20 @@ impl GuiApp {
                 }
             }
         }
+        if let Some(plugin) = self.workspace_manager.workspace.plugins.iter().find(|p| p.id == plugin_id) {
+            list.extend(
+                rtsyn_core::plugin::extendable_fixed_inputs(&plugin.kind)
+                    .iter()
+                    .map(|port| port.to_string()),
+            );
+        }
         list
     }
 
     pub(crate) fn remove_extendable_input_at(&mut self, plugin_id: u64, remove_idx: usize) {
+        if self.reject_if_locked() {
+            return;
+        }
         let plugin_index = match self.workspace_manager.workspace.plugins.iter().position(|p| p.id == plugin_id) {
             Some(idx) => idx,
             None => return,
@@ -200,7 +229,7 @@ impl GuiApp {
 
         let (current_count, mut columns, is_csv) = {
             let plugin = &self.workspace_manager.workspace.plugins[plugin_index];
-            let mut input_count = plugin.config.get("input_count").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+            let mut input_count = rtsyn_core::config::u64_or(&plugin.config, "input_count", 0) as usize;
             let mut columns = Vec::new();
             let is_csv = plugin.kind == "csv_recorder";
             if is_csv {
@@ -309,6 +338,24 @@ impl GuiApp {
         }
     }
 
+    /// Names a csv_recorder's columns after its connections, see
+    /// [`core_connections::fill_csv_columns`].
+    pub(crate) fn fill_csv_columns(&mut self, plugin_id: u64, overwrite: bool) {
+        if self.reject_if_locked() {
+            return;
+        }
+        let changed = core_connections::fill_csv_columns(
+            &mut self.workspace_manager.workspace,
+            &self.plugin_manager.installed_plugins,
+            plugin_id,
+            overwrite,
+        );
+        if changed > 0 {
+            self.mark_workspace_dirty();
+        }
+        self.status = format!("Renamed {changed} CSV column(s)");
+    }
+
     pub(crate) fn sync_extendable_input_count(&mut self, plugin_id: u64) {
         core_connections::sync_extendable_input_count(&mut self.workspace_manager.workspace, plugin_id);
     }
@@ -0,0 +1,208 @@
+use eframe::egui::{Pos2, Rect, Vec2};
+
+/// Gap kept between a routed line and the cards it bends around.
+const CLEARANCE: f32 = 12.0;
+
+/// Polyline from `start` to `end` that bends around the corners of
+/// `obstacles` (screen-space card rects, without the two connected cards).
+/// Cards containing an endpoint are ignored. Falls back to the straight
+/// line when no detour is found.
+pub(crate) fn route(start: Pos2, end: Pos2, obstacles: &[Rect]) -> Vec<Pos2> {
+    let padded: Vec<Rect> = obstacles
+        .iter()
+        .map(|rect| rect.expand(CLEARANCE))
+        .filter(|rect| !rect.contains(start) && !rect.contains(end))
+        .collect();
+    // Slightly smaller for hit tests, so lines may touch padded corners
+    let blockers: Vec<Rect> = padded.iter().map(|rect| rect.shrink(0.5)).collect();
+    let first_hit = |a: Pos2, b: Pos2| -> Option<usize> {
+        blockers
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, rect)| segment_entry(a, b, rect).map(|t| (idx, t)))
+            .min_by(|x, y| x.1.total_cmp(&y.1))
+            .map(|(idx, _)| idx)
+    };
+    let clear = |a: Pos2, b: Pos2| first_hit(a, b).is_none();
+
+    let mut path = vec![start];
+    let mut visited: Vec<Pos2> = Vec::new();
+    let mut current = start;
+    // Each card can be passed at most once per side
+    for _ in 0..padded.len() * 4 {
+        let Some(hit) = first_hit(current, end) else {
+            path.push(end);
+            return simplify(path, clear);
+        };
+        let rect = padded[hit];
+        let corner = [
+            rect.left_top(),
+            rect.right_top(),
+            rect.left_bottom(),
+            rect.right_bottom(),
+        ]
+        .into_iter()
+        .filter(|corner| !visited.contains(corner) && clear(current, *corner))
+        .min_by(|a, b| {
+            let cost = |c: &Pos2| current.distance(*c) + c.distance(end);
+            cost(a).total_cmp(&cost(b))
+        });
+        let Some(corner) = corner else {
+            break;
+        };
+        visited.push(corner);
+        path.push(corner);
+        current = corner;
+    }
+    vec![start, end]
+}
+
+/// Drops waypoints that can be skipped without hitting a card.
+fn simplify(path: Vec<Pos2>, clear: impl Fn(Pos2, Pos2) -> bool) -> Vec<Pos2> {
+    let mut out = vec![path[0]];
+    let mut idx = 0;
+    while idx + 1 < path.len() {
+        let next = (idx + 1..path.len())
+            .rev()
+            .find(|&j| clear(path[idx], path[j]))
+            .unwrap_or(idx + 1);
+        out.push(path[next]);
+        idx = next;
+    }
+    out
+}
+
+/// Parameter along `a`..`b` where the segment enters `rect`, if it does
+/// (Liang-Barsky clipping).
+fn segment_entry(a: Pos2, b: Pos2, rect: &Rect) -> Option<f32> {
+    let d = b - a;
+    let mut t_enter = 0.0f32;
+    let mut t_exit = 1.0f32;
+    for (delta, low, high, origin) in [
+        (d.x, rect.min.x, rect.max.x, a.x),
+        (d.y, rect.min.y, rect.max.y, a.y),
+    ] {
+        if delta == 0.0 {
+            if origin <= low || origin >= high {
+                return None;
+            }
+            continue;
+        }
+        let t0 = (low - origin) / delta;
+        let t1 = (high - origin) / delta;
+        t_enter = t_enter.max(t0.min(t1));
+        t_exit = t_exit.min(t0.max(t1));
+        if t_enter >= t_exit {
+            return None;
+        }
+    }
+    Some(t_enter)
+}
+
+/// Point halfway along `path` by length, the direction of travel there and
+/// the index of the segment it falls on.
+pub(crate) fn midpoint(path: &[Pos2]) -> (Pos2, Vec2, usize) {
+    let total: f32 = path.windows(2).map(|w| w[0].distance(w[1])).sum();
+    let mut remaining = total * 0.5;
+    for (idx, w) in path.windows(2).enumerate() {
+        let len = w[0].distance(w[1]);
+        if len > 0.0 && remaining <= len {
+            let dir = (w[1] - w[0]) / len;
+            return (w[0] + dir * remaining, dir, idx);
+        }
+        remaining -= len;
+    }
+    let last = path.last().copied().unwrap_or(Pos2::ZERO);
+    (last, Vec2::X, path.len().saturating_sub(2))
+}
+
+/// Dashes of length `dash` separated by `gap` along `path`, shifted by
+/// `phase` in the direction of the path. Advancing `phase` over time makes
+/// the dashes move from the start of the path to its end.
+pub(crate) fn dashes(path: &[Pos2], dash: f32, gap: f32, phase: f32) -> Vec<[Pos2; 2]> {
+    let period = dash + gap;
+    if period <= 0.0 {
+        return Vec::new();
+    }
+    let mut dashes = Vec::new();
+    // Distance along the path where the first dash starts, at most 0
+    let mut dash_start = phase.rem_euclid(period) - period;
+    let mut travelled = 0.0;
+    for w in path.windows(2) {
+        let len = w[0].distance(w[1]);
+        if len <= 0.0 {
+            continue;
+        }
+        let dir = (w[1] - w[0]) / len;
+        let end = travelled + len;
+        while dash_start < end {
+            let from = dash_start.max(travelled);
+            let to = (dash_start + dash).min(end);
+            if to > from {
+                dashes.push([w[0] + dir * (from - travelled), w[0] + dir * (to - travelled)]);
+            }
+            if dash_start + dash > end {
+                // The dash carries on into the next segment
+                break;
+            }
+            dash_start += period;
+        }
+        travelled = end;
+    }
+    dashes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eframe::egui::{pos2, vec2};
+
+    #[test]
+    fn straight_when_clear_and_around_a_card_in_the_way() {
+        let card = Rect::from_min_size(pos2(40.0, -20.0), vec2(20.0, 40.0));
+        assert_eq!(
+            route(pos2(0.0, 100.0), pos2(100.0, 100.0), &[card]),
+            vec![pos2(0.0, 100.0), pos2(100.0, 100.0)]
+        );
+
+        let path = route(pos2(0.0, 0.0), pos2(100.0, 0.0), &[card]);
+        // Over or under the card, along its padded edge
+        assert_eq!(path.len(), 4);
+        assert!(path[1].y.abs() >= 32.0 && path[1].y == path[2].y);
+        let (mid, dir, segment) = midpoint(&path);
+        assert!((mid.x - 50.0).abs() < 1e-3);
+        assert_eq!((dir, segment), (Vec2::X, 1));
+    }
+
+    #[test]
+    fn ignores_cards_around_an_endpoint() {
+        let under_start = Rect::from_center_size(pos2(0.0, 0.0), vec2(20.0, 20.0));
+        assert_eq!(
+            route(pos2(0.0, 0.0), pos2(100.0, 0.0), &[under_start]),
+            vec![pos2(0.0, 0.0), pos2(100.0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn dashes_follow_bends_and_move_with_the_phase() {
+        let path = [pos2(0.0, 0.0), pos2(10.0, 0.0), pos2(10.0, 10.0)];
+        let still = dashes(&path, 4.0, 4.0, 0.0);
+        assert_eq!(still[0], [pos2(0.0, 0.0), pos2(4.0, 0.0)]);
+        // The dash over the bend is split at the corner
+        assert_eq!(still[1], [pos2(8.0, 0.0), pos2(10.0, 0.0)]);
+        assert_eq!(still[2], [pos2(10.0, 0.0), pos2(10.0, 2.0)]);
+        assert_eq!(still.len(), 4);
+
+        let moved = dashes(&path, 4.0, 4.0, 6.0);
+        assert_eq!(
+            moved,
+            vec![
+                [pos2(0.0, 0.0), pos2(2.0, 0.0)],
+                [pos2(6.0, 0.0), pos2(10.0, 0.0)],
+                [pos2(10.0, 4.0), pos2(10.0, 8.0)],
+            ]
+        );
+        // A whole period later it looks the same
+        assert_eq!(dashes(&path, 4.0, 4.0, 8.0), still);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{GuiConfig, GuiError};
-use crate::plotter::LivePlotter;
+use crate::plotter::{LivePlotter, PlotViewMode};
 use eframe::egui;
 use rtsyn_core::plugin::PluginManager;
 use rtsyn_cli::client;
@@ -167,6 +167,16 @@ impl DaemonPluginViewer {
         map
     }
 
+    fn plotter_view_mode(state: &RuntimePluginState) -> PlotViewMode {
+        state
+            .variables
+            .iter()
+            .find(|(key, _)| key == "view_mode")
+            .and_then(|(_, value)| value.as_str())
+            .map(PlotViewMode::from_name)
+            .unwrap_or(PlotViewMode::Time)
+    }
+
     fn plotter_config(
         state: &RuntimePluginState,
         samples: &[(u64, Vec<f64>)],
@@ -446,6 +456,7 @@ impl eframe::App for DaemonPluginViewer {
                 let period_seconds = view.period_seconds;
                 self.plotter
                     .update_config(input_count, refresh_hz, window_ms, amplitude, period_seconds);
+                self.plotter.set_view_mode(Self::plotter_view_mode(&view.state));
                 if !view.series_names.is_empty() {
                     self.plotter.set_series_names(view.series_names.clone());
                 } else {
@@ -1,8 +1,37 @@
 use crate::{BuildAction, BuildResult, GuiApp, spawn_file_dialog_thread};
+use crate::plotter::BitmapTarget;
 use rtsyn_core::plugin::PluginManager;
+use rtsyn_runtime::benchmark::BenchmarkReport;
+use rtsyn_runtime::preflight::{check_device_start, test_device};
 use rtsyn_runtime::LogicMessage;
+use std::path::Path;
+use std::process::Command;
 use std::sync::mpsc;
 
+/// `process()` calls timed by the plugin manager's Benchmark button.
+const BENCHMARK_ITERATIONS: usize = 10_000;
+
+/// Runs `rtsyn benchmark --json` on `library_path` and parses its report.
+fn run_benchmark_child(library_path: &Path) -> Result<BenchmarkReport, String> {
+    let exe = std::env::current_exe().map_err(|e| format!("Failed to get executable path: {e}"))?;
+    let output = Command::new(exe)
+        .arg("benchmark")
+        .arg(library_path)
+        .args(["--iterations", &BENCHMARK_ITERATIONS.to_string(), "--json"])
+        .output()
+        .map_err(|e| format!("Failed to start benchmark: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = stderr.trim().trim_start_matches("[RTSyn][ERROR]:").trim();
+        return Err(if message.is_empty() {
+            format!("Benchmark exited with {}", output.status)
+        } else {
+            message.to_string()
+        });
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| format!("Invalid benchmark output: {e}"))
+}
+
 impl GuiApp {
     pub(crate) fn poll_build_dialog(&mut self) {
         let result = match &self.build_dialog.rx {
@@ -59,6 +88,20 @@ impl GuiApp {
         }
     }
 
+    pub(crate) fn poll_plugin_path_dialog(&mut self) {
+        let result = match &self.file_dialogs.plugin_path_dialog_rx {
+            Some(rx) => rx.try_recv().ok(),
+            None => None,
+        };
+
+        if let Some(selection) = result {
+            self.file_dialogs.plugin_path_dialog_rx = None;
+            if let Some(folder) = selection {
+                self.add_plugin_search_path(folder);
+            }
+        }
+    }
+
     pub(crate) fn poll_import_dialog(&mut self) {
         let result = match &self.file_dialogs.import_dialog_rx {
             Some(rx) => rx.try_recv().ok(),
@@ -80,8 +123,7 @@ impl GuiApp {
         if let Some(selection) = result {
             self.file_dialogs.load_dialog_rx = None;
             if let Some(path) = selection {
-                self.workspace_manager.workspace_path = path;
-                self.load_workspace();
+                self.open_workspace_path(path);
             }
         }
     }
@@ -126,6 +168,25 @@ impl GuiApp {
         }
     }
 
+    pub(crate) fn poll_dot_export_dialog(&mut self) {
+        let result = match &self.file_dialogs.dot_export_dialog_rx {
+            Some(rx) => rx.try_recv().ok(),
+            None => None,
+        };
+        if let Some((dot, dest)) = result {
+            self.file_dialogs.dot_export_dialog_rx = None;
+            if let Some(dest) = dest {
+                match std::fs::write(&dest, dot) {
+                    Ok(()) => self.show_info("Workspace", "Signal graph exported"),
+                    Err(err) => self.show_info(
+                        "Workspace Error",
+                        &format!("Cannot write {}: {err}", dest.display()),
+                    ),
+                }
+            }
+        }
+    }
+
     pub(crate) fn poll_plotter_screenshot_dialog(&mut self) {
         let result = match &self.file_dialogs.plotter_screenshot_rx {
             Some(rx) => rx.try_recv().ok(),
@@ -139,13 +200,14 @@ impl GuiApp {
                 let export_result = self.plotter_manager.plotters.get(&plugin_id)
                     .and_then(|plotter| plotter.lock().ok())
                     .and_then(|mut plotter| {
-                        if let Some((show_axes, show_legend, show_grid, series_names, colors, title, dark_theme, x_axis, y_axis, high_quality, export_svg)) = settings {
+                        if let Some((show_axes, show_legend, show_grid, series_names, color_overrides, styles, title, dark_theme, x_axis, y_axis, high_quality, export_svg, palette)) = settings {
+                            let colors = palette.series_colors(&color_overrides);
                             if export_svg {
-                                plotter.export_svg_with_settings(&path, &self.state_sync.logic_time_label, show_axes, show_legend, show_grid, &title, &series_names, &colors, dark_theme, &x_axis, &y_axis, self.plotter_preview.width, self.plotter_preview.height).err()
+                                plotter.export_svg_with_settings(&path, &self.state_sync.logic_time_label, show_axes, show_legend, show_grid, &title, &series_names, &colors, &styles, dark_theme, &x_axis, &y_axis, self.plotter_preview.width, self.plotter_preview.height).err()
                             } else if high_quality {
-                                plotter.export_png_hq_with_settings(&path, &self.state_sync.logic_time_label, show_axes, show_legend, show_grid, &title, &series_names, &colors, dark_theme, &x_axis, &y_axis).err()
+                                plotter.export_png_hq_with_settings(&path, &self.state_sync.logic_time_label, show_axes, show_legend, show_grid, &title, &series_names, &colors, &styles, dark_theme, &x_axis, &y_axis).err()
                             } else {
-                                plotter.export_png_with_settings(&path, &self.state_sync.logic_time_label, show_axes, show_legend, show_grid, &title, &series_names, &colors, dark_theme, &x_axis, &y_axis, self.plotter_preview.width, self.plotter_preview.height).err()
+                                plotter.export_png_with_settings(BitmapTarget::File(&path), &self.state_sync.logic_time_label, show_axes, show_legend, show_grid, &title, &series_names, &colors, &styles, dark_theme, &x_axis, &y_axis, self.plotter_preview.width, self.plotter_preview.height, self.plotter_preview.supersample).err()
                             }
                         } else {
                             plotter.export_png(&path, &self.state_sync.logic_time_label).err()
@@ -158,6 +220,95 @@ impl GuiApp {
         }
     }
 
+    pub(crate) fn poll_plugin_config_dialogs(&mut self) {
+        let export = match &self.file_dialogs.plugin_config_export_rx {
+            Some(rx) => rx.try_recv().ok(),
+            None => None,
+        };
+        if let Some((plugin_id, selection)) = export {
+            self.file_dialogs.plugin_config_export_rx = None;
+            if let Some(path) = selection {
+                self.export_plugin_config(plugin_id, &path);
+            }
+        }
+
+        let import = match &self.file_dialogs.plugin_config_import_rx {
+            Some(rx) => rx.try_recv().ok(),
+            None => None,
+        };
+        if let Some((plugin_id, selection)) = import {
+            self.file_dialogs.plugin_config_import_rx = None;
+            if let Some(path) = selection {
+                self.import_plugin_config(plugin_id, &path);
+            }
+        }
+
+        let library = match &self.file_dialogs.plugin_library_rx {
+            Some(rx) => rx.try_recv().ok(),
+            None => None,
+        };
+        if let Some((plugin_id, selection)) = library {
+            self.file_dialogs.plugin_library_rx = None;
+            if let Some(path) = selection {
+                self.locate_plugin_library(plugin_id, &path);
+            }
+        }
+    }
+
+    pub(crate) fn request_plugin_config_export(&mut self, plugin_id: u64) {
+        if self.file_dialogs.plugin_config_export_rx.is_some() {
+            return;
+        }
+        let kind = self.workspace_manager.workspace.plugins.iter()
+            .find(|p| p.id == plugin_id)
+            .map(|p| p.kind.clone())
+            .unwrap_or_else(|| "plugin".to_string());
+        let default_name = format!("{kind}_{plugin_id}_config.json");
+
+        let (tx, rx) = mpsc::channel();
+        self.file_dialogs.plugin_config_export_rx = Some(rx);
+        spawn_file_dialog_thread(move || {
+            let file = if crate::has_rt_capabilities() {
+                crate::zenity_file_dialog_with_name("save", Some("*.json"), Some(&default_name))
+            } else {
+                rfd::FileDialog::new().add_filter("JSON", &["json"]).set_file_name(&default_name).save_file()
+            };
+            let _ = tx.send((plugin_id, file));
+        });
+    }
+
+    pub(crate) fn request_plugin_config_import(&mut self, plugin_id: u64) {
+        if self.file_dialogs.plugin_config_import_rx.is_some() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        self.file_dialogs.plugin_config_import_rx = Some(rx);
+        spawn_file_dialog_thread(move || {
+            let file = if crate::has_rt_capabilities() {
+                crate::zenity_file_dialog("open", Some("*.json"))
+            } else {
+                rfd::FileDialog::new().add_filter("JSON", &["json"]).pick_file()
+            };
+            let _ = tx.send((plugin_id, file));
+        });
+    }
+
+    pub(crate) fn request_plugin_library(&mut self, plugin_id: u64) {
+        if self.file_dialogs.plugin_library_rx.is_some() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        self.file_dialogs.plugin_library_rx = Some(rx);
+        spawn_file_dialog_thread(move || {
+            let file = if crate::has_rt_capabilities() {
+                crate::zenity_file_dialog("open", Some("*.so"))
+            } else {
+                rfd::FileDialog::new().add_filter("Plugin library", &["so"]).pick_file()
+            };
+            let _ = tx.send((plugin_id, file));
+        });
+    }
+
     pub(crate) fn start_plugin_build(&mut self, action: BuildAction, label: String) {
         if self.build_dialog.rx.is_some() {
             self.status = "Plugin build already running".to_string();
@@ -202,13 +353,276 @@ impl GuiApp {
         });
     }
 
+    /// Times the installed plugin's `process()` with its default config in a
+    /// child `rtsyn benchmark`, which preloads the allocation probe so the
+    /// plugin's allocations are counted. A plugin that crashes only takes the
+    /// child down.
+    pub(crate) fn start_plugin_benchmark(&mut self, installed_index: usize) {
+        if self.build_dialog.benchmark_rx.is_some() {
+            self.status = "Plugin benchmark already running".to_string();
+            return;
+        }
+        let Some(installed) = self.plugin_manager.installed_plugins.get(installed_index) else {
+            return;
+        };
+        let Some(library_path) = installed.library_path.clone() else {
+            self.show_info("Benchmark", "Plugin has no library to benchmark");
+            return;
+        };
+        let name = installed.manifest.name.clone();
+        let (tx, rx) = mpsc::channel();
+        self.build_dialog.benchmark_rx = Some(rx);
+        self.status = format!("Benchmarking {name}...");
+        std::thread::spawn(move || {
+            let result = run_benchmark_child(&library_path);
+            let _ = tx.send((name, result));
+        });
+    }
+
+    /// Starts a device driver once [`check_device_start`] passes on a
+    /// background thread, since opening the device may block. A failed check
+    /// leaves the plugin stopped and tells the user why.
+    pub(crate) fn start_device_checked(&mut self, plugin_id: u64) {
+        if self.device_start_checks.iter().any(|(id, _)| *id == plugin_id) {
+            return;
+        }
+        // Checked with parameters resolved, as the runtime will run it
+        let Some(plugin) = self
+            .workspace_manager
+            .workspace
+            .resolve_parameters()
+            .plugins
+            .into_iter()
+            .find(|p| p.id == plugin_id)
+        else {
+            return;
+        };
+        let (tx, rx) = mpsc::channel();
+        self.device_start_checks.push((plugin_id, rx));
+        self.status = format!("Checking {}...", self.plugin_display_name(plugin_id));
+        std::thread::spawn(move || {
+            let _ = tx.send(check_device_start(&plugin));
+        });
+    }
+
+    pub(crate) fn poll_device_start_checks(&mut self) {
+        let mut finished = Vec::new();
+        self.device_start_checks.retain(|(plugin_id, rx)| match rx.try_recv() {
+            Ok(result) => {
+                finished.push((*plugin_id, result));
+                false
+            }
+            Err(mpsc::TryRecvError::Empty) => true,
+            Err(mpsc::TryRecvError::Disconnected) => false,
+        });
+        for (plugin_id, result) in finished {
+            let name = self.plugin_display_name(plugin_id);
+            match result {
+                Ok(()) => {
+                    let Some(plugin) = self
+                        .workspace_manager
+                        .workspace
+                        .plugins
+                        .iter_mut()
+                        .find(|p| p.id == plugin_id)
+                    else {
+                        continue;
+                    };
+                    plugin.running = true;
+                    let _ = self
+                        .state_sync
+                        .logic_tx
+                        .send(LogicMessage::SetPluginRunning(plugin_id, true));
+                    self.mark_workspace_dirty();
+                    self.status = format!("Started {name}");
+                }
+                Err(err) => {
+                    self.status = format!("{name} was not started");
+                    self.push_notification(&format!("{name} cannot start"), &err);
+                }
+            }
+        }
+    }
+
+    /// Runs [`test_device`] for the plugin in the device test window on a
+    /// background thread, with its config resolved as the runtime would run
+    /// it.
+    pub(crate) fn start_device_test(&mut self) {
+        if self.device_test.rx.is_some() {
+            return;
+        }
+        let Some(plugin_id) = self.device_test.plugin_id else {
+            return;
+        };
+        let Some(plugin) = self
+            .workspace_manager
+            .workspace
+            .resolve_parameters()
+            .plugins
+            .into_iter()
+            .find(|p| p.id == plugin_id)
+        else {
+            return;
+        };
+        let write_safe = self.device_test.write_safe;
+        let (tx, rx) = mpsc::channel();
+        self.device_test.rx = Some(rx);
+        self.device_test.result = None;
+        self.status = format!("Testing {}...", self.plugin_display_name(plugin_id));
+        std::thread::spawn(move || {
+            let _ = tx.send(test_device(&plugin, write_safe));
+        });
+    }
+
+    pub(crate) fn poll_device_test(&mut self) {
+        let result = match &self.device_test.rx {
+            Some(rx) => match rx.try_recv() {
+                Ok(result) => Some(result),
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => None,
+            },
+            None => return,
+        };
+        self.device_test.rx = None;
+        let Some(result) = result else {
+            return;
+        };
+        if let Some(plugin_id) = self.device_test.plugin_id {
+            let name = self.plugin_display_name(plugin_id);
+            self.status = match &result {
+                Ok(_) => format!("Tested {name}"),
+                Err(_) => format!("{name} test failed"),
+            };
+        }
+        self.device_test.result = Some(result);
+    }
+
+    /// Asks the runtime to run a plugin's self-test on its instance.
+    pub(crate) fn run_self_test(&mut self, plugin_id: u64) {
+        if self.self_tests.iter().any(|(id, _)| *id == plugin_id) {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        if self
+            .state_sync
+            .logic_tx
+            .send(LogicMessage::RunSelfTest(plugin_id, tx))
+            .is_err()
+        {
+            return;
+        }
+        self.self_tests.push((plugin_id, rx));
+    }
+
+    pub(crate) fn poll_self_tests(&mut self) {
+        let mut finished = Vec::new();
+        self.self_tests.retain(|(plugin_id, rx)| match rx.try_recv() {
+            Ok(result) => {
+                finished.push((*plugin_id, result));
+                false
+            }
+            Err(mpsc::TryRecvError::Empty) => true,
+            Err(mpsc::TryRecvError::Disconnected) => false,
+        });
+        for (plugin_id, result) in finished {
+            let name = self.plugin_display_name(plugin_id);
+            match result {
+                Ok(()) => {
+                    self.status = format!("{name} passed its self-test");
+                    self.show_info("Self-test", &format!("{name}: passed"));
+                }
+                Err(err) => {
+                    self.status = format!("{name} failed its self-test");
+                    self.show_info("Self-test", &format!("{name}: failed\n{err}"));
+                }
+            }
+        }
+    }
+
+    pub(crate) fn poll_plugin_benchmark(&mut self) {
+        let result = match &self.build_dialog.benchmark_rx {
+            Some(rx) => rx.try_recv().ok(),
+            None => None,
+        };
+        let Some((name, result)) = result else {
+            return;
+        };
+        self.build_dialog.benchmark_rx = None;
+        match result {
+            Ok(report) => {
+                let allocations = match (report.allocations, report.allocated_bytes) {
+                    (Some(count), Some(bytes)) => format!("{count} ({bytes} bytes)"),
+                    _ => "not measurable for this library".to_string(),
+                };
+                self.status = format!("Benchmarked {name}");
+                self.show_info(
+                    "Benchmark",
+                    &format!(
+                        "{name}: {} process() calls\nmin {:?}, mean {:?}\np99 {:?}, max {:?}\nallocations: {allocations}",
+                        report.iterations, report.min, report.mean, report.p99, report.max
+                    ),
+                );
+            }
+            Err(err) => {
+                self.status = "Plugin benchmark failed".to_string();
+                self.show_info("Benchmark", &err);
+            }
+        }
+    }
+
+    /// Puts the plot on the clipboard as an image, drawn like the PNG export
+    /// with the plotter's preview settings.
+    pub(crate) fn copy_plotter_to_clipboard(&mut self, plugin_id: u64) {
+        let settings = self.plotter_manager.plotter_preview_settings.get(&plugin_id).cloned();
+        let (width, height, supersample) = if settings.is_some() {
+            (self.plotter_preview.width, self.plotter_preview.height, self.plotter_preview.supersample)
+        } else {
+            (1200, 700, 1)
+        };
+        let time_label = self.state_sync.logic_time_label.clone();
+        let mut rgb = vec![0u8; width as usize * height as usize * 3];
+        let Some(rendered) = self.plotter_manager.plotters.get(&plugin_id)
+            .and_then(|plotter| plotter.lock().ok())
+            .map(|mut plotter| {
+                let target = BitmapTarget::Rgb(&mut rgb);
+                if let Some((show_axes, show_legend, show_grid, series_names, color_overrides, styles, title, dark_theme, x_axis, y_axis, _, _, palette)) = settings {
+                    let colors = palette.series_colors(&color_overrides);
+                    plotter.export_png_with_settings(target, &time_label, show_axes, show_legend, show_grid, &title, &series_names, &colors, &styles, dark_theme, &x_axis, &y_axis, width, height, supersample)
+                } else {
+                    let y_axis_name = plotter.value_axis_label();
+                    plotter.export_png_with_settings(target, &time_label, true, true, true, "", &[], &[], &[], true, &time_label, &y_axis_name, width, height, supersample)
+                }
+            })
+        else {
+            return;
+        };
+        let result = rendered.and_then(|()| {
+            let bytes: Vec<u8> = rgb.chunks_exact(3).flat_map(|px| [px[0], px[1], px[2], 255]).collect();
+            let clipboard = match self.clipboard.as_mut() {
+                Some(clipboard) => clipboard,
+                None => self.clipboard.insert(arboard::Clipboard::new().map_err(|err| err.to_string())?),
+            };
+            clipboard
+                .set_image(arboard::ImageData {
+                    width: width as usize,
+                    height: height as usize,
+                    bytes: bytes.into(),
+                })
+                .map_err(|err| err.to_string())
+        });
+        match result {
+            Ok(()) => self.show_info("Plotter", "Plot copied to the clipboard"),
+            Err(err) => self.show_info("Plotter", &format!("Copy to clipboard failed: {err}")),
+        }
+    }
+
     pub(crate) fn request_plotter_screenshot(&mut self, plugin_id: u64) {
         if self.file_dialogs.plotter_screenshot_rx.is_some() {
             return;
         }
         
         let base_name = self.plotter_manager.plotter_preview_settings.get(&plugin_id)
-            .and_then(|(_, _, _, _, _, title, _, _, _, _, _)| {
+            .and_then(|(_, _, _, _, _, _, title, _, _, _, _, _, _)| {
                 if title.trim().is_empty() {
                     None
                 } else {
@@ -228,7 +642,7 @@ impl GuiApp {
         self.file_dialogs.plotter_screenshot_rx = Some(rx);
         self.plotter_screenshot_target = Some(plugin_id);
         
-        let is_svg = self.plotter_manager.plotter_preview_settings.get(&plugin_id).map(|(_, _, _, _, _, _, _, _, _, _, svg)| *svg).unwrap_or(false);
+        let is_svg = self.plotter_manager.plotter_preview_settings.get(&plugin_id).map(|(_, _, _, _, _, _, _, _, _, _, _, svg, _)| *svg).unwrap_or(false);
         let extension = if is_svg { "svg" } else { "png" };
         let filter_name = if is_svg { "SVG" } else { "PNG" };
         
@@ -3,22 +3,34 @@ use std::sync::mpsc::Receiver;
 
 pub struct FileDialogManager {
     pub install_dialog_rx: Option<Receiver<Option<PathBuf>>>,
+    pub plugin_path_dialog_rx: Option<Receiver<Option<PathBuf>>>,
     pub import_dialog_rx: Option<Receiver<Option<PathBuf>>>,
     pub load_dialog_rx: Option<Receiver<Option<PathBuf>>>,
     pub export_dialog_rx: Option<Receiver<(PathBuf, Option<PathBuf>)>>,
+    /// DOT text of the signal graph and where to save it.
+    pub dot_export_dialog_rx: Option<Receiver<(String, Option<PathBuf>)>>,
     pub csv_path_dialog_rx: Option<Receiver<Option<PathBuf>>>,
     pub plotter_screenshot_rx: Option<Receiver<Option<PathBuf>>>,
+    pub plugin_config_export_rx: Option<Receiver<(u64, Option<PathBuf>)>>,
+    pub plugin_config_import_rx: Option<Receiver<(u64, Option<PathBuf>)>>,
+    /// Library picked for a plugin whose library did not load.
+    pub plugin_library_rx: Option<Receiver<(u64, Option<PathBuf>)>>,
 }
 
 impl FileDialogManager {
     pub fn new() -> Self {
         Self {
             install_dialog_rx: None,
+            plugin_path_dialog_rx: None,
             import_dialog_rx: None,
             load_dialog_rx: None,
             export_dialog_rx: None,
+            dot_export_dialog_rx: None,
             csv_path_dialog_rx: None,
             plotter_screenshot_rx: None,
+            plugin_config_export_rx: None,
+            plugin_config_import_rx: None,
+            plugin_library_rx: None,
         }
     }
 }
@@ -1,4 +1,5 @@
-use rtsyn_runtime::runtime::{LogicMessage, LogicSettings, LogicState};
+use rtsyn_runtime::runtime::{LogicMessage, LogicSettings, LogicState, PluginEvent};
+use rtsyn_runtime::logging::{self, Level};
 use rtsyn_runtime::spawn_runtime;
 use eframe::{egui, egui::RichText};
 use serde_json::Value;
@@ -71,8 +72,10 @@ where
 use workspace::{
     input_sum, input_sum_any,
     ConnectionDefinition,
+    MissedDeadlinePolicy,
     WorkspaceSettings,
 };
+use workspace::workspace_edits;
 
 // Operation modules
 mod connection_operations;
@@ -81,6 +84,8 @@ mod plugin_operations;
 mod workspace_operations;
 
 // Core modules
+mod card_layout;
+mod connection_routing;
 mod file_dialogs;
 mod notifications;
 mod plotter;
@@ -91,13 +96,16 @@ mod ui;
 mod ui_state;
 mod utils;
 mod daemon_viewer;
+mod window_layout;
 
 use file_dialogs::FileDialogManager;
-use notifications::Notification;
-use plotter::LivePlotter;
+use notifications::NotificationHandler;
+use window_layout::WindowLayout;
+use plotter::{LivePlotter, PlotViewMode};
 use plotter_manager::PlotterManager;
-use rtsyn_core::plugin::PluginManager;
+use rtsyn_core::plugin::{port_unit, with_unit, PluginManager};
 use state_sync::StateSync;
+use utils::format_hms_millis;
 use rtsyn_core::workspace::WorkspaceManager;
 use state::{
     WorkspaceTimingTab, ConfirmAction,
@@ -105,6 +113,9 @@ use state::{
     TimeUnit, WorkspaceDialogMode,
 };
 
+const PLUGIN_STATES_QUERY_INTERVAL: Duration = Duration::from_secs(1);
+const CONNECTION_LATENCY_QUERY_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Debug, Clone)]
 pub struct GuiConfig {
     pub title: String,
@@ -147,12 +158,16 @@ enum WindowFocus {
     LoadWorkspaces,
     ManageWorkspaces,
     ManagePlugins,
+    NewPlugin,
     Plugins,
     WorkspaceSettings,
     ManageConnections,
+    ConnectionLatency,
     ConnectionEditorAdd,
     ConnectionEditorRemove,
+    ConnectionInspector,
     PluginConfig,
+    DeviceTest,
 }
 
 #[derive(Debug)]
@@ -169,6 +184,8 @@ pub(crate) struct WorkspaceSettingsDraft {
     period_unit: PeriodUnit,
     tab: WorkspaceTimingTab,
     max_integration_steps: usize,
+    missed_deadline_policy: MissedDeadlinePolicy,
+    jitter_us: f64,
 }
 
 pub fn run_gui(config: GuiConfig) -> Result<(), GuiError> {
@@ -236,7 +253,9 @@ struct GuiApp {
     plotter_preview: ui_state::PlotterPreviewState,
     connection_editor: ui_state::ConnectionEditorState,
     workspace_dialog: ui_state::WorkspaceDialogState,
+    new_plugin_dialog: ui_state::NewPluginDialogState,
     build_dialog: ui_state::BuildDialogState,
+    device_test: ui_state::DeviceTestState,
     confirm_dialog: ui_state::ConfirmDialogState,
     workspace_settings: ui_state::WorkspaceSettingsState,
     windows: ui_state::WindowState,
@@ -244,25 +263,63 @@ struct GuiApp {
     // Remaining UI State
     status: String,
     csv_path_target_plugin_id: Option<u64>,
-    notifications: Vec<Notification>,
+    notifications: NotificationHandler,
     plugin_positions: HashMap<u64, egui::Pos2>,
+    /// Card positions of the background workspace tabs, by tab index.
+    parked_card_positions: HashMap<usize, HashMap<u64, egui::Pos2>>,
     plugin_rects: HashMap<u64, egui::Rect>,
+    /// Zoom and pan of the plugin canvas. `plugin_positions` are in canvas
+    /// space, `plugin_rects` in screen space.
+    canvas_transform: egui::emath::TSTransform,
     connections_view_enabled: bool,
+    /// Bend connection lines around cards instead of drawing them straight.
+    route_connections: bool,
+    /// Move dashes along connection lines whose source outputs are active.
+    animate_connections: bool,
+    /// PNG plugin icons by path; `None` for files that failed to load.
+    plugin_icon_textures: HashMap<std::path::PathBuf, Option<egui::TextureHandle>>,
     available_cores: usize,
     selected_cores: Vec<bool>,
     frequency_value: f64,
     frequency_unit: FrequencyUnit,
     period_value: f64,
     period_unit: PeriodUnit,
+    missed_deadline_policy: MissedDeadlinePolicy,
+    jitter_us: f64,
+    log_level: Level,
     output_refresh_hz: f64,
     plotter_screenshot_target: Option<u64>,
     connection_highlight_plugin_id: Option<u64>,
     selected_plugin_id: Option<u64>,
+    /// Cards picked with Ctrl/Shift-click for align and distribute.
+    selected_plugin_ids: HashSet<u64>,
+    /// Canvas positions to force on the next frame, after a layout action.
+    pending_card_positions: HashMap<u64, egui::Pos2>,
+    /// Whole-canvas layout to run once every card has been drawn and sized.
+    pending_auto_layout: Option<card_layout::AutoLayout>,
+    /// Device drivers waiting on their pre-start check before they start.
+    device_start_checks: Vec<(u64, Receiver<Result<(), String>>)>,
+    /// Plugins waiting on the runtime's answer to a self-test.
+    self_tests: Vec<(u64, Receiver<Result<(), String>>)>,
     plugin_context_menu: Option<(u64, egui::Pos2, u64)>,
     connection_context_menu: Option<(Vec<ConnectionDefinition>, egui::Pos2, u64)>,
     number_edit_buffers: HashMap<(u64, String), String>,
+    /// Unapplied config edits of plugins that apply their config as a batch,
+    /// see [`rtsyn_core::plugin::supports_apply`].
+    config_drafts: HashMap<u64, serde_json::Map<String, Value>>,
+    /// Kept open because on X11 copied data is served only while the
+    /// clipboard handle lives.
+    clipboard: Option<arboard::Clipboard>,
     window_rects: Vec<egui::Rect>,
+    /// Sub-window geometry restored from, and saved to, the last session.
+    window_layout: WindowLayout,
+    /// The workspace as last sent to the runtime; later changes are sent as
+    /// edits against it.
+    synced_workspace: Option<WorkspaceDefinition>,
     pending_window_focus: Option<WindowFocus>,
+    /// Set once the user chose Save or Discard for unsaved changes; lets the
+    /// next close request through.
+    close_confirmed: bool,
 }
 
 impl GuiApp {
@@ -277,6 +334,7 @@ impl GuiApp {
             .map(|n| n.get())
             .unwrap_or(1);
 
+        let window_layout = WindowLayout::load(install_db_path.with_file_name("window_layout.json"));
         let mut plugin_manager = PluginManager::new(install_db_path);
         let mut workspace_manager = WorkspaceManager::new(workspace_dir);
         let file_dialogs = FileDialogManager::new();
@@ -306,31 +364,51 @@ impl GuiApp {
             plotter_preview: ui_state::PlotterPreviewState::default(),
             connection_editor: ui_state::ConnectionEditorState::default(),
             workspace_dialog: ui_state::WorkspaceDialogState::default(),
+            new_plugin_dialog: ui_state::NewPluginDialogState::default(),
             build_dialog: ui_state::BuildDialogState::default(),
+            device_test: ui_state::DeviceTestState::default(),
             confirm_dialog: ui_state::ConfirmDialogState::default(),
             workspace_settings: ui_state::WorkspaceSettingsState::default(),
             windows: ui_state::WindowState::default(),
             status: String::new(),
             csv_path_target_plugin_id: None,
-            notifications: Vec::new(),
+            notifications: NotificationHandler::default(),
             plugin_positions: HashMap::new(),
+            parked_card_positions: HashMap::new(),
             plugin_rects: HashMap::new(),
+            canvas_transform: egui::emath::TSTransform::IDENTITY,
             connections_view_enabled: true,
+            route_connections: true,
+            animate_connections: false,
+            plugin_icon_textures: HashMap::new(),
             available_cores,
             selected_cores: (0..available_cores).map(|i| i == 0).collect(),
             frequency_value: 1000.0,
             frequency_unit: FrequencyUnit::Hz,
             period_value: 1.0,
             period_unit: PeriodUnit::Ms,
+            missed_deadline_policy: MissedDeadlinePolicy::default(),
+            jitter_us: 0.0,
+            log_level: logging::level(),
             output_refresh_hz: 1.0,
             plotter_screenshot_target: None,
             connection_highlight_plugin_id: None,
             selected_plugin_id: None,
+            selected_plugin_ids: HashSet::new(),
+            pending_card_positions: HashMap::new(),
+            pending_auto_layout: None,
+            device_start_checks: Vec::new(),
+            self_tests: Vec::new(),
             plugin_context_menu: None,
             connection_context_menu: None,
             number_edit_buffers: HashMap::new(),
+            config_drafts: HashMap::new(),
+            clipboard: None,
             window_rects: Vec::new(),
+            window_layout,
+            synced_workspace: None,
             pending_window_focus: None,
+            close_confirmed: false,
         }
     }
 
@@ -340,6 +418,34 @@ impl GuiApp {
         center - size * 0.5
     }
 
+    /// Where a sub-window opens: the rect it had last time if that is still
+    /// on screen, otherwise `size` centered.
+    fn window_placement(&self, ctx: &egui::Context, key: &str, size: egui::Vec2) -> egui::Rect {
+        self.window_layout
+            .rect(key)
+            .filter(|rect| ctx.screen_rect().intersects(*rect))
+            .unwrap_or_else(|| egui::Rect::from_min_size(Self::center_window(ctx, size), size))
+    }
+
+    /// Records a native viewport's outer position and inner size, the two
+    /// values a `ViewportBuilder` restores.
+    fn remember_viewport(&mut self, ctx: &egui::Context, key: &str, viewport_id: egui::ViewportId) {
+        let rect = ctx.input_for(viewport_id, |i| {
+            let viewport = i.viewport();
+            Some(egui::Rect::from_min_size(viewport.outer_rect?.min, viewport.inner_rect?.size()))
+        });
+        if let Some(rect) = rect {
+            self.window_layout.remember(key, rect);
+        }
+    }
+
+    /// Registers a shown sub-window so canvas clicks under it are ignored and
+    /// its geometry is remembered for the next session.
+    fn track_window(&mut self, key: &str, rect: egui::Rect) {
+        self.window_rects.push(rect);
+        self.window_layout.remember(key, rect);
+    }
+
     fn sync_next_plugin_id(&mut self) {
         let max_id = self.workspace_manager.workspace.plugins.iter().map(|p| p.id).max();
         self.plugin_manager.sync_next_plugin_id(max_id);
@@ -349,10 +455,40 @@ impl GuiApp {
         self.workspace_manager.mark_dirty();
     }
 
+    /// Sends the workspace changes since the last sync to the runtime, as
+    /// incremental edits when possible so untouched plugins keep running
+    /// undisturbed. Parameter references are resolved first, so changing a
+    /// parameter updates the config of every plugin using it.
+    fn sync_workspace_to_runtime(&mut self) {
+        let workspace = self.workspace_manager.workspace.resolve_parameters();
+        let edits = self
+            .synced_workspace
+            .as_ref()
+            .and_then(|synced| workspace_edits(synced, &workspace));
+        match edits {
+            Some(edits) => {
+                for edit in edits {
+                    let _ = self.state_sync.logic_tx.send(LogicMessage::from(edit));
+                }
+            }
+            None => {
+                let _ = self
+                    .state_sync
+                    .logic_tx
+                    .send(LogicMessage::UpdateWorkspace(workspace.clone()));
+            }
+        }
+        self.synced_workspace = Some(workspace);
+    }
+
     fn restart_plugin(&mut self, plugin_id: u64) {
         let _ = self.state_sync.logic_tx.send(LogicMessage::RestartPlugin(plugin_id));
     }
 
+    fn retry_open(&mut self, plugin_id: u64) {
+        let _ = self.state_sync.logic_tx.send(LogicMessage::RetryOpen(plugin_id));
+    }
+
     fn display_kind(kind: &str) -> String {
         PluginManager::display_kind(kind)
     }
@@ -361,13 +497,48 @@ impl GuiApp {
         self.push_notification(title, message);
     }
 
-    fn push_notification(&mut self, title: &str, message: &str) {
-        let notification = Notification {
-            title: title.to_string(),
-            message: message.to_string(),
-            created_at: Instant::now(),
+    /// Returns `true` (and tells the user) when the workspace is locked, so
+    /// editing operations can bail out early.
+    fn reject_if_locked(&mut self) -> bool {
+        if self.workspace_manager.workspace.settings.locked {
+            self.show_info("Workspace", "Workspace is locked. Unlock it to make changes.");
+            return true;
+        }
+        false
+    }
+
+    fn toggle_workspace_lock(&mut self) {
+        let settings = &mut self.workspace_manager.workspace.settings;
+        settings.locked = !settings.locked;
+        self.status = if settings.locked {
+            "Workspace locked".to_string()
+        } else {
+            "Workspace unlocked".to_string()
         };
-        self.notifications.push(notification);
+        self.mark_workspace_dirty();
+    }
+
+    fn push_notification(&mut self, title: &str, message: &str) {
+        self.notifications.push(title, message);
+    }
+
+    fn set_log_level(&mut self, level: Level) {
+        self.log_level = level;
+        let _ = self.state_sync.logic_tx.send(LogicMessage::SetLogLevel(level));
+        self.status = format!("Runtime log level: {level}");
+    }
+
+    /// Moves runtime log lines into the notification history; warnings and
+    /// errors also show as toasts.
+    fn poll_runtime_logs(&mut self) {
+        for record in logging::drain_records() {
+            let title = format!("Runtime {}", record.level);
+            if record.level <= Level::WARN {
+                self.notifications.push(&title, &record.message);
+            } else {
+                self.notifications.record(&title, &record.message);
+            }
+        }
     }
 
     fn show_confirm(
@@ -381,9 +552,54 @@ impl GuiApp {
         self.confirm_dialog.message = message.to_string();
         self.confirm_dialog.action_label = action_label.to_string();
         self.confirm_dialog.action = Some(action);
+        self.confirm_dialog.alternative = None;
         self.confirm_dialog.open = true;
     }
 
+    /// Holds a window close while the workspace has unsaved edits and asks
+    /// whether to save them.
+    fn intercept_close_request(&mut self, ctx: &egui::Context) {
+        if self.close_confirmed {
+            // Background tabs with unsaved changes are asked about in turn
+            if let Some(index) = self.workspace_manager.other_tab_unsaved() {
+                self.close_confirmed = false;
+                self.switch_workspace_tab(index);
+                self.confirm_save_before_close();
+                return;
+            }
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            return;
+        }
+        if !ctx.input(|i| i.viewport().close_requested()) {
+            return;
+        }
+        if !self.workspace_manager.has_unsaved_changes() {
+            match self.workspace_manager.other_tab_unsaved() {
+                Some(index) if !self.confirm_dialog.open => self.switch_workspace_tab(index),
+                Some(_) => {}
+                None => return,
+            }
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+        if self.confirm_dialog.open {
+            return;
+        }
+        self.confirm_save_before_close();
+    }
+
+    fn confirm_save_before_close(&mut self) {
+        let message = if self.workspace_manager.workspace_path.as_os_str().is_empty() {
+            "This workspace has never been saved. Save it before closing?".to_string()
+        } else {
+            format!(
+                "Save changes to '{}' before closing?",
+                self.workspace_manager.workspace.name
+            )
+        };
+        self.show_confirm("Unsaved changes", &message, "Save", ConfirmAction::SaveAndClose);
+        self.confirm_dialog.alternative = Some(("Discard".to_string(), ConfirmAction::DiscardAndClose));
+    }
+
     fn perform_confirm_action(&mut self, action: ConfirmAction) {
         match action {
             ConfirmAction::RemovePlugin(plugin_id) => {
@@ -399,6 +615,25 @@ impl GuiApp {
             ConfirmAction::UninstallPlugin(index) => {
                 self.uninstall_plugin(index);
             }
+            ConfirmAction::SaveAndClose => {
+                // A workspace without a file opens the Save dialog instead;
+                // the window stays open until that is done
+                self.save_workspace_overwrite_current();
+                self.close_confirmed = !self.workspace_manager.has_unsaved_changes();
+            }
+            ConfirmAction::DiscardAndClose => {
+                // Drop the discarded tab so the other tabs can be asked about
+                if self.workspace_manager.tabs().len() > 1 {
+                    self.close_workspace_tab(self.workspace_manager.active_tab());
+                }
+                self.close_confirmed = true;
+            }
+            ConfirmAction::CloseTab(index) => {
+                self.close_workspace_tab(index);
+            }
+            ConfirmAction::RegenerateCsvColumns(plugin_id) => {
+                self.fill_csv_columns(plugin_id, true);
+            }
             ConfirmAction::DeleteWorkspace(path) => {
                 let name = WorkspaceDefinition::load_from_file(&path)
                     .map(|ws| ws.name)
@@ -426,14 +661,31 @@ impl GuiApp {
         }
     }
 
+    /// Plugin events go to the notification history only; they can be too
+    /// frequent for toasts.
+    fn record_plugin_event(&mut self, event: &PluginEvent) {
+        let name = self.plugin_display_name(event.plugin_id);
+        let value = match &event.value {
+            serde_json::Value::String(text) => text.clone(),
+            value => value.to_string(),
+        };
+        self.notifications.record(
+            &name,
+            &format!("{} {value} at t={:.3} s", event.name, event.time_seconds),
+        );
+    }
+
     fn poll_logic_state(&mut self) {
         let mut latest: Option<LogicState> = None;
         let mut merged_samples: HashMap<u64, Vec<(u64, Vec<f64>)>> = HashMap::new();
-        while let Ok(state) = self.state_sync.logic_state_rx.try_recv() {
+        while let Ok(mut state) = self.state_sync.logic_state_rx.try_recv() {
             for (plugin_id, samples) in &state.plotter_samples {
                 let entry = merged_samples.entry(*plugin_id).or_default();
                 entry.extend(samples.iter().cloned());
             }
+            for event in std::mem::take(&mut state.events) {
+                self.record_plugin_event(&event);
+            }
             latest = Some(state);
         }
         if let Some(state) = latest {
@@ -442,6 +694,23 @@ impl GuiApp {
             let internal_variable_values = state.internal_variable_values;
             let viewer_values = state.viewer_values;
             let tick = state.tick;
+            self.state_sync.logic_tick = tick;
+            self.state_sync.step_mode = state.step_mode;
+            self.state_sync.warming_up = state.warming_up;
+            for (plugin_id, error) in &state.open_errors {
+                if self.state_sync.open_errors.get(plugin_id) != Some(error) {
+                    let name = self.plugin_display_name(*plugin_id);
+                    self.push_notification(&format!("{name} failed to open"), error);
+                }
+            }
+            self.state_sync.open_errors = state.open_errors;
+            for (plugin_id, error) in &state.load_errors {
+                if self.state_sync.load_errors.get(plugin_id) != Some(error) {
+                    let name = self.plugin_display_name(*plugin_id);
+                    self.push_notification(&format!("{name} did not load"), error);
+                }
+            }
+            self.state_sync.load_errors = state.load_errors;
             self.update_plotters(tick, &outputs, &merged_samples);
             let output_interval = if self.output_refresh_hz > 0.0 {
                 Duration::from_secs_f64(1.0 / self.output_refresh_hz)
@@ -469,7 +738,8 @@ impl GuiApp {
                     .filter(|((id, _), _)| running_plugins.contains(id))
                     .collect();
                 
-                self.state_sync.computed_outputs = filtered_outputs;
+                self.state_sync.previous_outputs =
+                    std::mem::replace(&mut self.state_sync.computed_outputs, filtered_outputs);
                 self.state_sync.input_values = filtered_inputs;
                 self.state_sync.internal_variable_values = filtered_internals;
                 self.state_sync.viewer_values = viewer_values;
@@ -478,6 +748,59 @@ impl GuiApp {
         }
     }
 
+    /// Starts the run clock when the first plugin starts running and clears it
+    /// once every plugin is stopped.
+    fn update_run_clock(&mut self) {
+        let any_running = self.workspace_manager.workspace.plugins.iter().any(|p| p.running);
+        match (any_running, self.state_sync.run_started) {
+            (true, None) => {
+                self.state_sync.run_started = Some((self.state_sync.logic_tick, Instant::now()));
+            }
+            (false, Some(_)) => self.state_sync.run_started = None,
+            _ => {}
+        }
+    }
+
+    /// Simulated (ticks × period) and wall-clock time since the run started.
+    fn run_elapsed(&self) -> Option<(f64, f64)> {
+        let (start_tick, started_at) = self.state_sync.run_started?;
+        let ticks = self.state_sync.logic_tick.saturating_sub(start_tick);
+        let simulated = ticks as f64 * self.state_sync.logic_period_seconds.max(0.0);
+        Some((simulated, started_at.elapsed().as_secs_f64()))
+    }
+
+    /// Periodically asks the runtime which plugins it is actually running, for
+    /// display. The workspace `running` flags stay as the user saved them.
+    fn reconcile_plugin_states(&mut self) {
+        if let Some(rx) = self.state_sync.plugin_states_rx.as_ref() {
+            match rx.try_recv() {
+                Ok(states) => {
+                    self.state_sync.plugin_states_rx = None;
+                    self.state_sync.plugin_run_states = states;
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => return,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    self.state_sync.plugin_states_rx = None;
+                }
+            }
+        }
+        if self.workspace_manager.workspace_dirty
+            || self.state_sync.last_plugin_states_query.elapsed() < PLUGIN_STATES_QUERY_INTERVAL
+        {
+            return;
+        }
+        let (tx, rx) = std::sync::mpsc::channel();
+        if self
+            .state_sync
+            .logic_tx
+            .send(LogicMessage::QueryPluginStates(tx))
+            .is_ok()
+        {
+            self.state_sync.plugin_states_rx = Some(rx);
+        }
+        self.state_sync.last_plugin_states_query = Instant::now();
+    }
+
     fn ports_for_kind(&self, kind: &str, inputs: bool) -> Vec<String> {
         self.plugin_manager.installed_plugins
             .iter()
@@ -503,7 +826,10 @@ impl GuiApp {
         if let Some(cached) = self.plugin_manager.plugin_behaviors.get(kind) {
             return matches!(cached.extendable_inputs, rtsyn_plugin::ui::ExtendableInputs::Auto { .. });
         }
-        matches!(kind, "csv_recorder" | "live_plotter")
+        matches!(
+            kind,
+            "csv_recorder" | "live_plotter" | "tcp_stream" | "shm_publisher" | "stats" | "fault_recorder"
+        )
     }
     
 
@@ -547,37 +873,147 @@ impl GuiApp {
             let input_count = if columns_len > 0 {
                 columns_len
             } else {
-                plugin
-                    .config
-                    .get("input_count")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(0) as usize
+                rtsyn_core::config::u64_or(&plugin.config, "input_count", 0) as usize
             };
             let mut ports = Vec::new();
             ports.push("in".to_string());
             ports.extend((0..input_count).map(|idx| format!("in_{idx}")));
+            ports.extend(
+                rtsyn_core::plugin::extendable_fixed_inputs(&plugin.kind)
+                    .iter()
+                    .map(|port| port.to_string()),
+            );
             return ports;
         }
+        if plugin.kind == "state_machine" {
+            let names = |key: &str| -> Vec<String> {
+                plugin
+                    .config
+                    .get(key)
+                    .and_then(|v| v.as_array())
+                    .map(|items| {
+                        items
+                            .iter()
+                            .filter_map(|v| v.as_str())
+                            .map(|name| name.trim().to_string())
+                            .filter(|name| !name.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            };
+            if inputs {
+                return names("inputs");
+            }
+            // A pulse output per state besides the state index
+            return std::iter::once("state".to_string())
+                .chain(names("states").iter().map(|state| format!("on_enter_{state}")))
+                .collect();
+        }
+        if let Some(ports) = rtsyn_core::plugin::bit_field_ports(&plugin.kind, &plugin.config, inputs) {
+            return ports;
+        }
+        let mapping_ports = match plugin.kind.as_str() {
+            "can_io" => Some((if inputs { "tx_signals" } else { "rx_signals" }, 4)),
+            "modbus_io" => Some((if inputs { "writes" } else { "reads" }, 3)),
+            "gpio" => Some((if inputs { "output_pins" } else { "input_pins" }, 2)),
+            _ => None,
+        };
+        if let Some((key, min_parts)) = mapping_ports {
+            // One port per mapping, named by the part before the first ':'
+            return plugin
+                .config
+                .get(key)
+                .and_then(|v| v.as_array())
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|v| v.as_str())
+                        .filter(|spec| spec.split(':').count() >= min_parts)
+                        .filter_map(|spec| spec.split(':').next())
+                        .map(|name| name.trim().to_string())
+                        .filter(|name| !name.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default();
+        }
         self.ports_for_kind(&plugin.kind, inputs)
     }
 
     fn plugin_display_name(&self, plugin_id: u64) -> String {
-        let name_by_kind: HashMap<String, String> = self
-            .plugin_manager.installed_plugins
-            .iter()
-            .map(|plugin| (plugin.manifest.kind.clone(), plugin.manifest.name.clone()))
-            .collect();
-        self.workspace_manager.workspace
-            .plugins
-            .iter()
-            .find(|plugin| plugin.id == plugin_id)
-            .map(|plugin| {
-                name_by_kind
-                    .get(&plugin.kind)
-                    .cloned()
-                    .unwrap_or_else(|| Self::display_kind(&plugin.kind))
-            })
-            .unwrap_or_else(|| "plugin".to_string())
+        rtsyn_core::plugin::plugin_display_name(
+            &self.plugin_manager.installed_plugins,
+            &self.workspace_manager.workspace,
+            plugin_id,
+        )
+    }
+
+    /// Bottom strip summarising the fault outputs of all running plugins,
+    /// see [`rtsyn_core::plugin::is_plugin_fault_output`].
+    fn render_fault_strip(&mut self, ctx: &egui::Context) {
+        let faults = rtsyn_core::plugin::active_faults(
+            &self.workspace_manager.workspace,
+            &self.state_sync.computed_outputs,
+        );
+        egui::TopBottomPanel::bottom("fault_strip").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let response = if faults.is_empty() {
+                    ui.label(RichText::new("\u{f058} No faults").color(egui::Color32::from_rgb(120, 200, 120)))
+                } else {
+                    let text = if faults.len() == 1 {
+                        "\u{f071} 1 fault active".to_string()
+                    } else {
+                        format!("\u{f071} {} faults active", faults.len())
+                    };
+                    ui.label(RichText::new(text).color(egui::Color32::from_rgb(230, 80, 80)))
+                };
+                let details = if faults.is_empty() {
+                    "No saturation flags, alarms or realtime violations are active".to_string()
+                } else {
+                    faults
+                        .iter()
+                        .map(|(plugin_id, port)| {
+                            format!("{} #{plugin_id}: {port}", self.plugin_display_name(*plugin_id))
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                };
+                response.on_hover_text(details);
+            });
+        });
+    }
+
+    /// One tab per open workspace. Only the active tab is sent to the
+    /// runtime; the others keep their workspace as it was left.
+    fn render_workspace_tabs(&mut self, ctx: &egui::Context) {
+        let tabs = self.workspace_manager.tabs();
+        let active = self.workspace_manager.active_tab();
+        let mut switch_to = None;
+        let mut close = None;
+        let mut new_tab = false;
+        egui::TopBottomPanel::top("workspace_tabs").show(ctx, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for (index, (name, unsaved)) in tabs.iter().enumerate() {
+                    let label = if *unsaved { format!("{name} *") } else { name.clone() };
+                    if ui.selectable_label(index == active, label).clicked() {
+                        switch_to = Some(index);
+                    }
+                    if tabs.len() > 1 && ui.small_button("\u{f00d}").on_hover_text("Close tab").clicked() {
+                        close = Some(index);
+                    }
+                    ui.separator();
+                }
+                if ui.small_button("+").on_hover_text("Open an empty workspace in a new tab").clicked() {
+                    new_tab = true;
+                }
+            });
+        });
+        if let Some(index) = close {
+            self.request_close_workspace_tab(index);
+        } else if let Some(index) = switch_to {
+            self.switch_workspace_tab(index);
+        } else if new_tab {
+            self.new_workspace_tab();
+        }
     }
 
     fn default_csv_path() -> String {
@@ -599,30 +1035,12 @@ impl GuiApp {
     }
 
     fn plotter_config_from_value(&self, config: &Value) -> (usize, f64, f64, f64) {
-        let input_count = config
-            .get("input_count")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0) as usize;
-        let refresh_hz = config
-            .get("refresh_hz")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(60.0);
-        let window_multiplier = config
-            .get("window_multiplier")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(10000.0);
-        let window_value = config
-            .get("window_value")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(10.0);
-        let window_ms = config
-            .get("window_ms")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(window_multiplier * window_value);
-        let amplitude = config
-            .get("amplitude")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
+        let input_count = rtsyn_core::config::u64_or(config, "input_count", 0) as usize;
+        let refresh_hz = rtsyn_core::config::f64_or(config, "refresh_hz", 60.0);
+        let window_multiplier = rtsyn_core::config::f64_or(config, "window_multiplier", 10000.0);
+        let window_value = rtsyn_core::config::f64_or(config, "window_value", 10.0);
+        let window_ms = rtsyn_core::config::f64_or(config, "window_ms", window_multiplier * window_value);
+        let amplitude = rtsyn_core::config::f64_or(config, "amplitude", 0.0);
         (input_count, refresh_hz, window_ms, amplitude)
     }
 
@@ -637,7 +1055,10 @@ impl GuiApp {
                 .find(|conn| conn.to_plugin == plotter_id && conn.to_port == port)
             {
                 let source_name = self.plugin_display_name(conn.from_plugin);
-                names.push(format!("{source_name}:{}", conn.from_port));
+                names.push(with_unit(
+                    &format!("{source_name}:{}", conn.from_port),
+                    self.source_port_unit(conn.from_plugin, &conn.from_port),
+                ));
             } else {
                 names.push(port);
             }
@@ -645,6 +1066,31 @@ impl GuiApp {
         names
     }
 
+    fn source_port_unit(&self, plugin_id: u64, port: &str) -> Option<&str> {
+        port_unit(
+            &self.plugin_manager.installed_plugins,
+            &self.workspace_manager.workspace,
+            plugin_id,
+            port,
+        )
+    }
+
+    /// Unit shared by every connected plotter input, shown on the value axis.
+    /// Mixed or missing units leave the axis unitless.
+    fn plotter_value_unit(&self, plotter_id: u64) -> Option<String> {
+        let mut units = self
+            .workspace_manager
+            .workspace
+            .connections
+            .iter()
+            .filter(|conn| conn.to_plugin == plotter_id)
+            .map(|conn| self.source_port_unit(conn.from_plugin, &conn.from_port));
+        let first = units.next()??;
+        units
+            .all(|unit| unit == Some(first))
+            .then(|| first.to_string())
+    }
+
     fn plotter_input_values(
         &self,
         plotter_id: u64,
@@ -683,6 +1129,7 @@ impl GuiApp {
             let (input_count, refresh_hz, window_ms, amplitude) =
                 self.plotter_config_from_value(&plugin.config);
             let series_names = self.plotter_series_names(plugin.id, input_count);
+            let value_unit = self.plotter_value_unit(plugin.id);
             let is_open = self
             .plotter_manager.plotters
                 .get(&plugin.id)
@@ -706,6 +1153,10 @@ impl GuiApp {
                     self.state_sync.logic_period_seconds,
                 );
                 plotter.set_series_names(series_names);
+                plotter.set_value_unit(value_unit);
+                plotter.set_view_mode(PlotViewMode::from_name(
+                    plugin.config.get("view_mode").and_then(|v| v.as_str()).unwrap_or("time"),
+                ));
                 if plotter.open && plugin.running {
                     if let Some(samples) = samples.get(&plugin.id) {
                         for (sample_tick, values) in samples {
@@ -758,6 +1209,7 @@ impl GuiApp {
             "shared_memory" => "Shared memory",
             "pipe" => "Pipe",
             "in_process" => "In process",
+            "queue" => "Queue",
             other => other,
         }
     }
@@ -827,6 +1279,8 @@ impl GuiApp {
                 time_label,
                 ui_hz: self.state_sync.logic_ui_hz,
                 max_integration_steps: 10, // Default reasonable limit for real-time performance
+                missed_deadline_policy: self.missed_deadline_policy,
+                jitter_us: self.jitter_us,
             }));
     }
 
@@ -854,6 +1308,9 @@ impl GuiApp {
             period_value: self.period_value,
             period_unit: period_unit.to_string(),
             selected_cores,
+            missed_deadline_policy: self.missed_deadline_policy,
+            jitter_us: self.jitter_us,
+            locked: self.workspace_manager.workspace.settings.locked,
         }
     }
 
@@ -879,6 +1336,8 @@ impl GuiApp {
         if !self.selected_cores.iter().any(|v| *v) && self.available_cores > 0 {
             self.selected_cores[0] = true;
         }
+        self.missed_deadline_policy = settings.missed_deadline_policy;
+        self.jitter_us = settings.jitter_us;
 
         self.send_logic_settings();
     }
@@ -953,31 +1412,43 @@ impl eframe::App for GuiApp {
             style.interaction.selectable_labels = false;
         });
         self.poll_build_dialog();
+        self.poll_plugin_benchmark();
+        self.poll_device_start_checks();
+        self.poll_device_test();
+        self.poll_self_tests();
         self.poll_install_dialog();
+        self.poll_plugin_path_dialog();
         self.poll_import_dialog();
         self.poll_load_dialog();
         self.poll_export_dialog();
+        self.poll_dot_export_dialog();
         self.poll_csv_path_dialog();
         self.poll_plotter_screenshot_dialog();
+        self.poll_plugin_config_dialogs();
         self.poll_logic_state();
-        let mut plotter_refresh = 0.0;
+        self.poll_runtime_logs();
+        self.reconcile_plugin_states();
+        self.update_run_clock();
+        let mut repaint_hz = 0.0;
         for plotter in self.plotter_manager.plotters.values() {
             if let Ok(plotter) = plotter.lock() {
-                if plotter.open && plotter.refresh_hz > plotter_refresh {
-                    plotter_refresh = plotter.refresh_hz;
+                if plotter.open && plotter.refresh_hz > repaint_hz {
+                    repaint_hz = plotter.refresh_hz;
                 }
             }
         }
-        if plotter_refresh > 0.0 {
-            let hz = plotter_refresh.max(1.0);
+        // Values of running plugins refresh with repaints too
+        if self.workspace_manager.workspace.plugins.iter().any(|p| p.running) {
+            repaint_hz = repaint_hz.max(self.output_refresh_hz);
+        }
+        if repaint_hz > 0.0 {
+            let hz = repaint_hz.max(1.0);
             ctx.request_repaint_after(Duration::from_secs_f64(1.0 / hz));
         } else if !ctx.input(|i| i.focused) {
             ctx.request_repaint_after(Duration::from_millis(250));
         }
         if self.workspace_manager.workspace_dirty {
-            let _ = self
-            .state_sync.logic_tx
-                .send(LogicMessage::UpdateWorkspace(self.workspace_manager.workspace.clone()));
+            self.sync_workspace_to_runtime();
             self.workspace_manager.workspace_dirty = false;
         }
         if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
@@ -1016,14 +1487,40 @@ impl eframe::App for GuiApp {
                         ui.close_menu();
                     }
                     let has_workspace = !self.workspace_manager.workspace_path.as_os_str().is_empty();
+                    if ui
+                        .add_enabled(has_workspace, egui::Button::new("Duplicate Workspace"))
+                        .on_hover_text("Save a copy with plugins renumbered from 1 and open it")
+                        .clicked()
+                    {
+                        self.open_workspace_dialog(WorkspaceDialogMode::Duplicate);
+                        ui.close_menu();
+                    }
                     if ui.add_enabled(has_workspace, egui::Button::new("Export Workspace")).clicked() {
                         self.export_workspace_path(&self.workspace_manager.workspace_path.clone());
                         ui.close_menu();
                     }
+                    if ui
+                        .button("Export Graph (DOT)")
+                        .on_hover_text("Save plugins and connections as a Graphviz graph")
+                        .clicked()
+                    {
+                        self.export_workspace_dot();
+                        ui.close_menu();
+                    }
                     if ui.button("Manage Workspaces").clicked() {
                         self.open_manage_workspaces();
                         ui.close_menu();
                     }
+                    ui.separator();
+                    let lock_label = if self.workspace_manager.workspace.settings.locked {
+                        "Unlock Workspace"
+                    } else {
+                        "Lock Workspace"
+                    };
+                    if ui.button(lock_label).clicked() {
+                        self.toggle_workspace_lock();
+                        ui.close_menu();
+                    }
                 });
 
                 ui.menu_button("Plugins", |ui| {
@@ -1035,6 +1532,29 @@ impl eframe::App for GuiApp {
                         self.open_manage_plugins();
                         ui.close_menu();
                     }
+                    if ui.button("New plugin").clicked() {
+                        self.open_new_plugin_dialog();
+                        ui.close_menu();
+                    }
+                    ui.separator();
+                    if ui.button("Compact all cards").clicked() {
+                        self.set_all_cards_collapsed(true);
+                        ui.close_menu();
+                    }
+                    if ui.button("Expand all cards").clicked() {
+                        self.set_all_cards_collapsed(false);
+                        ui.close_menu();
+                    }
+                    ui.menu_button("Auto-arrange cards", |ui| {
+                        for layout in card_layout::AutoLayout::ALL {
+                            if ui.button(layout.label()).clicked() {
+                                if !self.reject_if_locked() {
+                                    self.pending_auto_layout = Some(layout);
+                                }
+                                ui.close_menu();
+                            }
+                        }
+                    });
                 });
 
                 ui.menu_button("Connections", |ui| {
@@ -1051,11 +1571,19 @@ impl eframe::App for GuiApp {
                         self.connections_view_enabled = !self.connections_view_enabled;
                         ui.close_menu();
                     }
+                    ui.checkbox(&mut self.route_connections, "Route lines around cards");
+                    ui.checkbox(&mut self.animate_connections, "Animate data flow")
+                        .on_hover_text("Dashes move along lines whose source is nonzero or changing");
                     if ui.button("Manage connections").clicked() {
                         self.windows.manage_connections_open = true;
                         self.pending_window_focus = Some(WindowFocus::ManageConnections);
                         ui.close_menu();
                     }
+                    if ui.button("Scheduling delay").clicked() {
+                        self.windows.connection_latency_open = true;
+                        self.pending_window_focus = Some(WindowFocus::ConnectionLatency);
+                        ui.close_menu();
+                    }
                 });
 
                 ui.menu_button("Runtime", |ui| {
@@ -1064,26 +1592,103 @@ impl eframe::App for GuiApp {
                         self.pending_window_focus = Some(WindowFocus::WorkspaceSettings);
                         ui.close_menu();
                     }
+                    if ui.button("Parameters").clicked() {
+                        self.windows.parameters_open = true;
+                        ui.close_menu();
+                    }
+                    let signals_label = if self.windows.signals_open {
+                        "Hide signals table"
+                    } else {
+                        "Show signals table"
+                    };
+                    if ui.button(signals_label).clicked() {
+                        self.windows.signals_open = !self.windows.signals_open;
+                        ui.close_menu();
+                    }
+                    ui.menu_button("Log level", |ui| {
+                        for level in logging::levels() {
+                            if ui.radio(self.log_level == *level, level.as_str()).clicked() {
+                                self.set_log_level(*level);
+                                ui.close_menu();
+                            }
+                        }
+                    });
                 });
 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     ui.add_space(6.0);
                     ui.label(RichText::new(format!("RTSyn {}", env!("CARGO_PKG_VERSION"))).weak());
+                    ui.add_space(8.0);
+                    let unread = self.notifications.unread();
+                    let bell = if unread > 0 {
+                        format!("\u{f0f3} {unread}")
+                    } else {
+                        "\u{f0f3}".to_string()
+                    };
+                    if ui
+                        .add(egui::Button::new(bell).frame(false))
+                        .on_hover_text("Notification history")
+                        .clicked()
+                    {
+                        self.windows.notification_history_open = !self.windows.notification_history_open;
+                        self.notifications.mark_read();
+                    }
+                    let locked = self.workspace_manager.workspace.settings.locked;
+                    let (lock_icon, lock_hint) = if locked {
+                        ("\u{f023}", "Workspace locked: click to allow edits")
+                    } else {
+                        ("\u{f09c}", "Lock the workspace against accidental edits")
+                    };
+                    let lock_text = if locked {
+                        RichText::new(format!("{lock_icon} Locked")).color(egui::Color32::from_rgb(255, 170, 80))
+                    } else {
+                        RichText::new(lock_icon).weak()
+                    };
+                    if ui
+                        .add(egui::Button::new(lock_text).frame(false))
+                        .on_hover_text(lock_hint)
+                        .clicked()
+                    {
+                        self.toggle_workspace_lock();
+                    }
+                    ui.add_space(8.0);
+                    self.render_step_controls(ui);
+                    if let Some((simulated, wall)) = self.run_elapsed() {
+                        ui.add_space(12.0);
+                        ui.label(RichText::new(format!("wall {}", format_hms_millis(wall))).monospace())
+                            .on_hover_text("Wall-clock time since the run started");
+                        ui.label(RichText::new(format!("sim {}", format_hms_millis(simulated))).monospace())
+                            .on_hover_text("Simulated time (ticks × period) since the run started");
+                    }
                 });
             });
         });
 
+        self.render_fault_strip(ctx);
+        self.render_workspace_tabs(ctx);
+        self.render_signals_panel(ctx);
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.add_space(8.0);
             let panel_rect = ui.max_rect();
+            self.handle_canvas_navigation(ctx, panel_rect);
+            self.apply_pending_auto_layout(panel_rect);
             self.render_connection_view(ctx, panel_rect);
             self.render_plugin_cards(ctx, panel_rect);
+            self.render_canvas_controls(ctx, panel_rect);
+            self.render_selection_toolbar(ctx, panel_rect);
             if ctx.input(|i| i.pointer.primary_clicked()) {
                 if let Some(pos) = ctx.input(|i| i.pointer.interact_pos()) {
                     let over_plugin = self.plugin_rects.values().any(|rect| rect.contains(pos));
+                    let over_canvas = ctx
+                        .layer_id_at(pos)
+                        .is_none_or(|layer| layer.order == egui::Order::Background);
                     if !over_plugin {
                         self.selected_plugin_id = None;
                     }
+                    if !over_plugin && over_canvas {
+                        self.selected_plugin_ids.clear();
+                    }
                 }
             }
 
@@ -1095,16 +1700,26 @@ impl eframe::App for GuiApp {
         self.render_manage_workspaces_window(ctx);
         self.render_manage_plugins_window(ctx);
         self.render_plugins_window(ctx);
+        self.render_new_plugin_dialog(ctx);
         self.render_manage_connections_window(ctx);
+        self.render_connection_latency_window(ctx);
+        self.render_parameters_window(ctx);
         self.render_connection_editor(ctx);
+        self.render_connection_inspector(ctx);
         self.render_plugin_context_menu(ctx);
         self.render_connection_context_menu(ctx);
         self.render_plugin_config_window(ctx);
+        self.render_device_test_window(ctx);
         self.render_plotter_windows(ctx);
+        self.render_histogram_windows(ctx);
         self.render_workspace_settings_window(ctx);
         self.render_build_dialog(ctx);
         self.render_confirm_remove_dialog(ctx);
+        self.intercept_close_request(ctx);
         self.render_info_dialog(ctx);
+        self.render_notification_history_window(ctx);
         self.render_plotter_preview_dialog(ctx);
+        let closing = self.close_confirmed || ctx.input(|i| i.viewport().close_requested());
+        self.window_layout.save(closing);
     }
 }
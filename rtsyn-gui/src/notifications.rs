@@ -1,8 +1,139 @@
+use std::collections::VecDeque;
 use std::time::Instant;
 
+/// Number of notifications kept for the history panel.
+const MAX_HISTORY: usize = 200;
+
 #[derive(Debug, Clone)]
 pub(crate) struct Notification {
     pub(crate) title: String,
     pub(crate) message: String,
     pub(crate) created_at: Instant,
 }
+
+/// Transient toasts plus a bounded history that outlives them.
+#[derive(Debug, Default)]
+pub(crate) struct NotificationHandler {
+    active: Vec<Notification>,
+    history: VecDeque<Notification>,
+    unread: usize,
+}
+
+impl NotificationHandler {
+    pub(crate) fn push(&mut self, title: &str, message: &str) {
+        let notification = Notification {
+            title: title.to_string(),
+            message: message.to_string(),
+            created_at: Instant::now(),
+        };
+        if self.history.len() == MAX_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(notification.clone());
+        self.active.push(notification);
+        self.unread = (self.unread + 1).min(MAX_HISTORY);
+    }
+
+    /// Adds to the history only: no toast and no unread count, for
+    /// high-volume entries such as runtime log lines.
+    pub(crate) fn record(&mut self, title: &str, message: &str) {
+        if self.history.len() == MAX_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(Notification {
+            title: title.to_string(),
+            message: message.to_string(),
+            created_at: Instant::now(),
+        });
+    }
+
+    pub(crate) fn active(&self) -> &[Notification] {
+        &self.active
+    }
+
+    /// Drops toasts older than `max_age_secs`; they stay in the history.
+    pub(crate) fn prune_active(&mut self, now: Instant, max_age_secs: f32) {
+        self.active
+            .retain(|n| now.duration_since(n.created_at).as_secs_f32() < max_age_secs);
+    }
+
+    /// History entries, newest first, whose title or message contains
+    /// `query` (case-insensitive).
+    pub(crate) fn search(&self, query: &str) -> Vec<&Notification> {
+        let query = query.trim().to_lowercase();
+        self.history
+            .iter()
+            .rev()
+            .filter(|n| {
+                query.is_empty()
+                    || n.title.to_lowercase().contains(&query)
+                    || n.message.to_lowercase().contains(&query)
+            })
+            .collect()
+    }
+
+    pub(crate) fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
+    pub(crate) fn unread(&self) -> usize {
+        self.unread
+    }
+
+    pub(crate) fn mark_read(&mut self) {
+        self.unread = 0;
+    }
+
+    pub(crate) fn clear_history(&mut self) {
+        self.history.clear();
+        self.unread = 0;
+    }
+}
+
+/// Short relative age such as "5s ago" or "3h ago".
+pub(crate) fn format_age(now: Instant, created_at: Instant) -> String {
+    let secs = now.duration_since(created_at).as_secs();
+    if secs < 60 {
+        format!("{secs}s ago")
+    } else if secs < 3600 {
+        format!("{}m ago", secs / 60)
+    } else {
+        format!("{}h ago", secs / 3600)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_is_bounded_and_newest_first() {
+        let mut handler = NotificationHandler::default();
+        for i in 0..(MAX_HISTORY + 5) {
+            handler.push("Info", &format!("message {i}"));
+        }
+        assert_eq!(handler.history_len(), MAX_HISTORY);
+        let all = handler.search("");
+        assert_eq!(all[0].message, format!("message {}", MAX_HISTORY + 4));
+        assert_eq!(all.last().unwrap().message, "message 5");
+    }
+
+    #[test]
+    fn search_matches_title_or_message_and_survives_pruning() {
+        let mut handler = NotificationHandler::default();
+        handler.push("Build failed", "cargo exited with 101");
+        handler.push("Workspace", "Saved");
+        handler.prune_active(Instant::now(), 0.0);
+        assert!(handler.active().is_empty());
+        assert_eq!(handler.search("BUILD").len(), 1);
+        assert_eq!(handler.search("saved").len(), 1);
+        assert_eq!(handler.unread(), 2);
+        handler.record("Runtime DEBUG", "workspace updated");
+        assert!(handler.active().is_empty());
+        assert_eq!(handler.search("runtime").len(), 1);
+        assert_eq!(handler.unread(), 2);
+        handler.clear_history();
+        assert!(handler.search("").is_empty());
+        assert_eq!(handler.unread(), 0);
+    }
+}
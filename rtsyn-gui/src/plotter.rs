@@ -1,12 +1,229 @@
 use egui::Color32;
-use egui_plot::{Line, Plot, PlotPoints};
+use egui_plot::{Line, MarkerShape, Plot, PlotPoints, Points, VLine};
 use plotters::prelude::*;
 use plotters::backend::SVGBackend;
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
 use std::collections::VecDeque;
 use std::path::Path;
+use rtsyn_core::plugin::with_unit;
 
 const MAX_SERIES: usize = 32;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PlotViewMode {
+    Time,
+    Spectrum,
+}
+
+impl PlotViewMode {
+    pub(crate) fn from_name(name: &str) -> Self {
+        match name {
+            "spectrum" => PlotViewMode::Spectrum,
+            _ => PlotViewMode::Time,
+        }
+    }
+}
+
+/// What the plot draws against: time, or one series against another in
+/// X-Y mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum PlotMode {
+    #[default]
+    Time,
+    Xy,
+}
+
+impl PlotMode {
+    pub(crate) const ALL: [PlotMode; 2] = [PlotMode::Time, PlotMode::Xy];
+
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            PlotMode::Time => "Time",
+            PlotMode::Xy => "X-Y",
+        }
+    }
+}
+
+/// Point marker drawn on top of a series line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MarkerStyle {
+    None,
+    Dots,
+    Crosses,
+}
+
+impl MarkerStyle {
+    pub(crate) const ALL: [MarkerStyle; 3] = [MarkerStyle::None, MarkerStyle::Dots, MarkerStyle::Crosses];
+
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            MarkerStyle::None => "None",
+            MarkerStyle::Dots => "Dots",
+            MarkerStyle::Crosses => "Crosses",
+        }
+    }
+}
+
+/// Per-series line width and marker. The default is the thin, marker-less
+/// look; exports scale the width from their own base stroke.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SeriesStyle {
+    pub(crate) line_width: f32,
+    pub(crate) marker: MarkerStyle,
+}
+
+impl Default for SeriesStyle {
+    fn default() -> Self {
+        Self {
+            line_width: 1.0,
+            marker: MarkerStyle::None,
+        }
+    }
+}
+
+impl SeriesStyle {
+    /// Stroke width for an export whose default line is `base` pixels wide.
+    fn export_width(&self, base: u32) -> u32 {
+        ((base as f32 * self.line_width).round() as u32).max(1)
+    }
+}
+
+/// Series colors assigned by index. Okabe-Ito, which stays distinguishable
+/// with the common forms of color blindness, is the default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum PlotPalette {
+    Classic,
+    Viridis,
+    #[default]
+    OkabeIto,
+}
+
+impl PlotPalette {
+    pub(crate) const ALL: [PlotPalette; 3] = [PlotPalette::OkabeIto, PlotPalette::Viridis, PlotPalette::Classic];
+
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            PlotPalette::Classic => "Classic",
+            PlotPalette::Viridis => "Viridis",
+            PlotPalette::OkabeIto => "Colorblind-safe (Okabe-Ito)",
+        }
+    }
+
+    fn colors(&self) -> &'static [Color32] {
+        const CLASSIC: [Color32; 10] = [
+            Color32::from_rgb(86, 156, 214),
+            Color32::from_rgb(220, 122, 95),
+            Color32::from_rgb(181, 206, 168),
+            Color32::from_rgb(197, 134, 192),
+            Color32::from_rgb(220, 220, 170),
+            Color32::from_rgb(156, 220, 254),
+            Color32::from_rgb(255, 204, 102),
+            Color32::from_rgb(206, 145, 120),
+            Color32::from_rgb(78, 201, 176),
+            Color32::from_rgb(214, 157, 133),
+        ];
+        // Darkest stop left out, it vanishes on the dark theme
+        const VIRIDIS: [Color32; 7] = [
+            Color32::from_rgb(70, 50, 126),
+            Color32::from_rgb(54, 92, 141),
+            Color32::from_rgb(39, 127, 142),
+            Color32::from_rgb(31, 161, 135),
+            Color32::from_rgb(74, 193, 109),
+            Color32::from_rgb(160, 218, 57),
+            Color32::from_rgb(253, 231, 37),
+        ];
+        // Black is swapped for grey so the palette works on both themes
+        const OKABE_ITO: [Color32; 8] = [
+            Color32::from_rgb(86, 180, 233),
+            Color32::from_rgb(230, 159, 0),
+            Color32::from_rgb(0, 158, 115),
+            Color32::from_rgb(240, 228, 66),
+            Color32::from_rgb(0, 114, 178),
+            Color32::from_rgb(213, 94, 0),
+            Color32::from_rgb(204, 121, 167),
+            Color32::from_rgb(153, 153, 153),
+        ];
+        match self {
+            PlotPalette::Classic => &CLASSIC,
+            PlotPalette::Viridis => &VIRIDIS,
+            PlotPalette::OkabeIto => &OKABE_ITO,
+        }
+    }
+
+    /// Color of series `idx`, cycling once the palette runs out.
+    pub(crate) fn color(&self, idx: usize) -> Color32 {
+        let colors = self.colors();
+        colors[idx % colors.len()]
+    }
+
+    /// Colors of the series with per-series `overrides`; series without one
+    /// take their palette color.
+    pub(crate) fn series_colors(&self, overrides: &[Option<Color32>]) -> Vec<Color32> {
+        overrides
+            .iter()
+            .enumerate()
+            .map(|(idx, color)| color.unwrap_or_else(|| self.color(idx)))
+            .collect()
+    }
+}
+
+/// Upper bound on markers drawn per series so dense windows stay readable.
+const MAX_MARKERS: usize = 400;
+
+fn draw_markers<DB: DrawingBackend>(
+    chart: &mut ChartContext<DB, Cartesian2d<plotters::coord::types::RangedCoordf64, plotters::coord::types::RangedCoordf64>>,
+    data: &[(f64, f64)],
+    color: RGBColor,
+    marker: MarkerStyle,
+    size: i32,
+) -> Result<(), String> {
+    let step = data.len().div_ceil(MAX_MARKERS).max(1);
+    let points = data.iter().step_by(step).copied();
+    match marker {
+        MarkerStyle::None => {}
+        MarkerStyle::Dots => {
+            chart
+                .draw_series(points.map(|p| Circle::new(p, size, color.filled())))
+                .map_err(|e| e.to_string())?;
+        }
+        MarkerStyle::Crosses => {
+            chart
+                .draw_series(points.map(|p| Cross::new(p, size, color.stroke_width(1))))
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// Draws an X-Y trajectory as one line with its latest point marked.
+fn draw_trajectory<DB: DrawingBackend>(
+    chart: &mut ChartContext<DB, Cartesian2d<plotters::coord::types::RangedCoordf64, plotters::coord::types::RangedCoordf64>>,
+    points: Vec<(f64, f64)>,
+    color: RGBColor,
+    style: SeriesStyle,
+    stroke: u32,
+    marker_size: i32,
+    legend: Option<String>,
+) -> Result<(), String> {
+    draw_markers(chart, &points, color, style.marker, marker_size)?;
+    let latest = points.last().copied();
+    let line = chart
+        .draw_series(LineSeries::new(points, color.stroke_width(stroke)))
+        .map_err(|e| e.to_string())?;
+    if let Some(name) = legend {
+        let length = marker_size * 5;
+        line.label(name)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + length, y)], color.stroke_width(stroke)));
+    }
+    if let Some(latest) = latest {
+        chart
+            .draw_series(std::iter::once(Circle::new(latest, marker_size * 2, color.filled())))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
 pub(crate) struct LivePlotter {
     pub(crate) plugin_id: u64,
     pub(crate) open: bool,
@@ -24,6 +241,28 @@ pub(crate) struct LivePlotter {
     last_time_scale: f64,
     series: Vec<PlotSeries>,
     raw_series: Vec<VecDeque<(f64, f64)>>, // Raw data for smooth exports
+    view_mode: PlotViewMode,
+    period_s: f64,
+    fft_planner: FftPlanner<f64>,
+    /// Unit shared by all series, appended to the value axis label.
+    value_unit: Option<String>,
+    /// Follow the cursor of other plotters with linked cursors.
+    pub(crate) link_cursor: bool,
+    /// Cursor time set by another linked plotter, drawn when this one is
+    /// not hovered.
+    pub(crate) linked_cursor_time: Option<f64>,
+    /// Time under the pointer in the last render, if hovered.
+    pub(crate) hovered_time: Option<f64>,
+    palette: PlotPalette,
+    /// Weight of the previous point when drawing lines, 0 (off) to 1.
+    /// Only the drawing is smoothed; the buffered samples stay raw.
+    pub(crate) display_smoothing: f64,
+    /// Cursor readout of the smoothed lines instead of the raw samples.
+    pub(crate) smoothed_readout: bool,
+    /// Time plot, or the trajectory of `xy_series` over the window.
+    pub(crate) plot_mode: PlotMode,
+    /// Indices of the series on the X and Y axes in X-Y mode.
+    pub(crate) xy_series: (usize, usize),
 }
 
 struct PlotSeries {
@@ -38,6 +277,115 @@ struct SeriesMinMax {
     max: Option<(f64, f64)>,
 }
 
+/// Strongest display smoothing; a weight of 1 would hold the line at its
+/// first sample.
+const MAX_DISPLAY_SMOOTHING: f64 = 0.99;
+
+/// Exponential smoothing of a drawn line: each point keeps `alpha` of the
+/// previous smoothed value. A non-finite value is drawn as is and restarts
+/// the smoothing.
+fn smooth_line(points: impl IntoIterator<Item = (f64, f64)>, alpha: f64) -> Vec<(f64, f64)> {
+    let alpha = alpha.clamp(0.0, MAX_DISPLAY_SMOOTHING);
+    let mut previous: Option<f64> = None;
+    points
+        .into_iter()
+        .map(|(x, y)| {
+            let smoothed = match previous {
+                Some(previous) if y.is_finite() => alpha * previous + (1.0 - alpha) * y,
+                _ => y,
+            };
+            previous = smoothed.is_finite().then_some(smoothed);
+            (x, smoothed)
+        })
+        .collect()
+}
+
+/// `(x, y)` values of the samples taken at the same time in both lines,
+/// within `min_time..=max_time`. Both lines are in time order.
+fn pair_xy(x_line: &[(f64, f64)], y_line: &[(f64, f64)], min_time: f64, max_time: f64) -> Vec<(f64, f64)> {
+    let mut y_points = y_line.iter().peekable();
+    let mut pairs = Vec::new();
+    for &(time, x) in x_line {
+        if time < min_time || time > max_time {
+            continue;
+        }
+        while y_points.next_if(|(y_time, _)| *y_time < time).is_some() {}
+        if let Some(&&(y_time, y)) = y_points.peek() {
+            if y_time == time {
+                pairs.push((x, y));
+            }
+        }
+    }
+    pairs
+}
+
+/// Smallest and largest finite value, padded by 5% like the time plot.
+fn padded_range(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (min, max) = values
+        .filter(|value| value.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| (min.min(value), max.max(value)));
+    if min > max {
+        (-1.0, 1.0)
+    } else if min == max {
+        (min - 1.0, max + 1.0)
+    } else {
+        let pad = (max - min) * 0.05;
+        (min - pad, max + pad)
+    }
+}
+
+/// Value of a line at `x`, linearly interpolated between the points around
+/// it; `None` outside the line.
+fn value_at(points: &VecDeque<(f64, f64)>, x: f64) -> Option<f64> {
+    let (first, last) = (points.front()?, points.back()?);
+    if x < first.0 || x > last.0 {
+        return None;
+    }
+    let after = points.partition_point(|(px, _)| *px < x);
+    let (x1, y1) = points[after.min(points.len() - 1)];
+    if after == 0 || x1 == x {
+        return Some(y1);
+    }
+    let (x0, y0) = points[after - 1];
+    Some(y0 + (y1 - y0) * (x - x0) / (x1 - x0))
+}
+
+/// Largest supersampling factor of bitmap exports.
+pub(crate) const MAX_SUPERSAMPLE: u32 = 4;
+
+/// Averages each `factor` x `factor` block of an RGB image that is `factor`
+/// times `width` x `height` into one pixel.
+fn downsample_rgb(large: &[u8], width: u32, height: u32, factor: u32) -> Vec<u8> {
+    let (width, height, factor) = (width as usize, height as usize, factor as usize);
+    let large_width = width * factor;
+    let mut rgb = Vec::with_capacity(width * height * 3);
+    for y in 0..height {
+        for x in 0..width {
+            let mut sum = [0u32; 3];
+            for dy in 0..factor {
+                let row = (y * factor + dy) * large_width;
+                for dx in 0..factor {
+                    let idx = (row + x * factor + dx) * 3;
+                    for (channel, total) in sum.iter_mut().enumerate() {
+                        *total += u32::from(large[idx + channel]);
+                    }
+                }
+            }
+            let count = (factor * factor) as u32;
+            rgb.extend(sum.map(|total| ((total + count / 2) / count) as u8));
+        }
+    }
+    rgb
+}
+
+/// Where [`LivePlotter::export_png_with_settings`] draws to.
+pub(crate) enum BitmapTarget<'a> {
+    /// A PNG file.
+    File(&'a Path),
+    /// Raw RGB pixels, `width * height * 3` bytes.
+    Rgb(&'a mut [u8]),
+}
+
 impl LivePlotter {
     pub(crate) fn new(plugin_id: u64) -> Self {
         Self {
@@ -57,9 +405,106 @@ impl LivePlotter {
             last_time_scale: 1000.0,
             series: Vec::new(),
             raw_series: Vec::new(),
+            view_mode: PlotViewMode::Time,
+            period_s: 0.0,
+            fft_planner: FftPlanner::new(),
+            value_unit: None,
+            link_cursor: false,
+            linked_cursor_time: None,
+            hovered_time: None,
+            palette: PlotPalette::default(),
+            display_smoothing: 0.0,
+            smoothed_readout: false,
+            plot_mode: PlotMode::Time,
+            xy_series: (0, 1),
+        }
+    }
+
+    /// Value of each series at time `x`, linearly interpolated between the
+    /// samples around it; `None` outside a series' samples. Reads the
+    /// smoothed lines when `smoothed_readout` is set.
+    pub(crate) fn values_at(&self, x: f64) -> Vec<Option<f64>> {
+        let smoothed = self.smoothed_readout && self.display_smoothing > 0.0;
+        self.series
+            .iter()
+            .map(|series| {
+                if smoothed {
+                    value_at(&self.display_line(series.points.iter().copied()).into(), x)
+                } else {
+                    value_at(&series.points, x)
+                }
+            })
+            .collect()
+    }
+
+    /// The points to draw for a line, smoothed by `display_smoothing`.
+    fn display_line(&self, points: impl IntoIterator<Item = (f64, f64)>) -> Vec<(f64, f64)> {
+        if self.display_smoothing > 0.0 {
+            smooth_line(points, self.display_smoothing)
+        } else {
+            points.into_iter().collect()
         }
     }
 
+    /// The trajectory of the X and Y series over `min_time..=max_time` in
+    /// X-Y mode, read from the raw export buffers when `raw` is set; `None`
+    /// in time mode.
+    fn xy_points(&self, raw: bool, min_time: f64, max_time: f64) -> Option<Vec<(f64, f64)>> {
+        if self.plot_mode != PlotMode::Xy {
+            return None;
+        }
+        let line = |index: usize| {
+            let points = match self.raw_series.get(index) {
+                Some(raw_points) if raw && !raw_points.is_empty() => raw_points,
+                _ => &self.series.get(index)?.points,
+            };
+            Some(self.display_line(points.iter().copied()))
+        };
+        let (x_index, y_index) = self.xy_series;
+        Some(match (line(x_index), line(y_index)) {
+            (Some(x_line), Some(y_line)) => pair_xy(&x_line, &y_line, min_time, max_time),
+            _ => Vec::new(),
+        })
+    }
+
+    /// Plot bounds `(min_x, max_x, min_y, max_y)` of an X-Y trajectory; a
+    /// fixed amplitude bounds both axes.
+    fn xy_bounds(&self, points: &[(f64, f64)]) -> (f64, f64, f64, f64) {
+        if self.amplitude > 0.0 {
+            return (-self.amplitude, self.amplitude, -self.amplitude, self.amplitude);
+        }
+        let (min_x, max_x) = padded_range(points.iter().map(|(x, _)| *x));
+        let (min_y, max_y) = padded_range(points.iter().map(|(_, y)| *y));
+        (min_x, max_x, min_y, max_y)
+    }
+
+    /// Name and color of a series, with the caller's overrides.
+    fn series_label(&self, index: usize, names: Option<&[String]>, colors: Option<&[egui::Color32]>) -> (String, Color32) {
+        let series = self.series.get(index);
+        let name = names
+            .and_then(|names| names.get(index).cloned())
+            .or_else(|| series.map(|series| series.name.clone()))
+            .unwrap_or_else(|| format!("Series {}", index + 1));
+        let color = colors
+            .and_then(|colors| colors.get(index).copied())
+            .or_else(|| series.map(|series| series.color))
+            .unwrap_or(Color32::BLUE);
+        (name, color)
+    }
+
+    pub(crate) fn set_value_unit(&mut self, unit: Option<String>) {
+        self.value_unit = unit;
+    }
+
+    /// Default value axis label, e.g. `value (V)`.
+    pub(crate) fn value_axis_label(&self) -> String {
+        with_unit("value", self.value_unit.as_deref())
+    }
+
+    pub(crate) fn set_view_mode(&mut self, view_mode: PlotViewMode) {
+        self.view_mode = view_mode;
+    }
+
     pub(crate) fn update_config(
         &mut self,
         input_count: usize,
@@ -73,6 +518,7 @@ impl LivePlotter {
         self.window_ms = if window_ms <= 0.0 { 1.0 } else { window_ms };
         self.amplitude = if amplitude < 0.0 { 0.0 } else { amplitude };
         let period_s = if period_s <= 0.0 { 0.0 } else { period_s };
+        self.period_s = period_s;
         let expected_points = if period_s > 0.0 {
             (self.window_ms / (period_s * 1000.0)).ceil() as usize
         } else {
@@ -101,7 +547,7 @@ impl LivePlotter {
             self.series = (0..self.input_count)
                 .map(|idx| PlotSeries {
                     name: format!("in_{idx}"),
-                    color: palette_color(idx),
+                    color: self.palette.color(idx),
                     points: VecDeque::new(),
                 })
                 .collect();
@@ -114,6 +560,14 @@ impl LivePlotter {
         }
     }
 
+    /// Recolors every series from `palette`.
+    pub(crate) fn set_palette(&mut self, palette: PlotPalette) {
+        self.palette = palette;
+        for (idx, series) in self.series.iter_mut().enumerate() {
+            series.color = palette.color(idx);
+        }
+    }
+
     pub(crate) fn set_series_names(&mut self, names: Vec<String>) {
         let mut counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
         for (idx, name) in names.into_iter().enumerate() {
@@ -214,7 +668,7 @@ impl LivePlotter {
     }
 
     pub(crate) fn render(&mut self, ui: &mut egui::Ui, title: &str, time_label: &str) {
-        self.render_with_settings(ui, title, time_label, true, true, true, None, None, None, true, None, None);
+        self.render_with_settings(ui, title, time_label, true, true, true, None, None, None, None, true, None, None);
     }
 
     pub(crate) fn render_with_settings(
@@ -228,6 +682,7 @@ impl LivePlotter {
         custom_title: Option<&str>,
         custom_series_names: Option<&[String]>,
         custom_colors: Option<&[egui::Color32]>,
+        custom_styles: Option<&[SeriesStyle]>,
         dark_theme: bool,
         x_axis_name: Option<&str>,
         y_axis_name: Option<&str>,
@@ -248,8 +703,16 @@ impl LivePlotter {
         }
         
         if show_axes {
-            let x_label = x_axis_name.unwrap_or(time_label);
-            let y_label = y_axis_name.unwrap_or("value");
+            // X-Y axes default to the names of their series
+            let (x_label, y_label) = match self.plot_mode {
+                PlotMode::Time => (time_label.to_string(), self.value_axis_label()),
+                PlotMode::Xy => (
+                    self.series_label(self.xy_series.0, custom_series_names, None).0,
+                    self.series_label(self.xy_series.1, custom_series_names, None).0,
+                ),
+            };
+            let x_label = x_axis_name.map_or(x_label, str::to_string);
+            let y_label = y_axis_name.map_or(y_label, str::to_string);
             plot = plot.x_axis_label(x_label).y_axis_label(y_label);
         }
 
@@ -263,7 +726,21 @@ impl LivePlotter {
             ui.style_mut().visuals = egui::Visuals::light();
         }
 
-        plot.show(ui, |plot_ui| {
+        if self.plot_mode == PlotMode::Xy {
+            self.hovered_time = None;
+            self.show_xy(ui, plot, custom_series_names, custom_colors, custom_styles);
+            ui.label(title);
+            return;
+        }
+
+        if self.view_mode == PlotViewMode::Spectrum {
+            self.show_spectrum(ui, show_axes, show_legend, custom_series_names, custom_colors, custom_styles);
+            ui.label(title);
+            return;
+        }
+
+        let cursor_time = self.linked_cursor_time;
+        let response = plot.show(ui, |plot_ui| {
             // Configure grid if needed
             if show_grid && show_axes {
                 // Grid is handled by egui_plot automatically when axes are shown
@@ -273,7 +750,8 @@ impl LivePlotter {
                 if series.points.is_empty() {
                     continue;
                 }
-                let points: PlotPoints = series.points.iter().map(|(x, y)| [*x, *y]).collect();
+                let line_points = self.display_line(series.points.iter().copied());
+                let points: PlotPoints = line_points.iter().map(|(x, y)| [*x, *y]).collect();
                 
                 let series_name = custom_series_names
                     .and_then(|names| names.get(i))
@@ -285,7 +763,24 @@ impl LivePlotter {
                     .copied()
                     .unwrap_or(series.color);
                     
-                let line = Line::new(points).color(series_color).name(series_name);
+                let style = custom_styles
+                    .and_then(|styles| styles.get(i))
+                    .copied()
+                    .unwrap_or_default();
+
+                if style.marker != MarkerStyle::None {
+                    let step = line_points.len().div_ceil(MAX_MARKERS).max(1);
+                    let marker_points: PlotPoints = line_points
+                        .iter()
+                        .step_by(step)
+                        .map(|(x, y)| [*x, *y])
+                        .collect();
+                    plot_ui.points(styled_points(marker_points, style, series_color, series_name));
+                }
+                let line = Line::new(points)
+                    .color(series_color)
+                    .width(style.line_width)
+                    .name(series_name);
                 plot_ui.line(line);
             }
             if min_time.is_finite() && max_time.is_finite() {
@@ -294,14 +789,224 @@ impl LivePlotter {
                     [max_time, max_y],
                 ));
             }
+            let hovered = if plot_ui.response().hovered() {
+                plot_ui.pointer_coordinate().map(|point| point.x)
+            } else {
+                None
+            };
+            // Own hover shows egui's crosshair; a linked cursor needs a line
+            if let (None, Some(time)) = (hovered, cursor_time) {
+                plot_ui.vline(VLine::new(time).color(Color32::from_gray(160)).width(1.0));
+            }
+            hovered
         });
+        self.hovered_time = response.inner;
 
+        if let Some(time) = self.hovered_time.or(cursor_time) {
+            let readout: Vec<String> = self
+                .values_at(time)
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, value)| {
+                    let name = custom_series_names
+                        .and_then(|names| names.get(i))
+                        .unwrap_or(&self.series[i].name);
+                    value.map(|value| format!("{name} = {value:.4}"))
+                })
+                .collect();
+            if !readout.is_empty() {
+                ui.label(
+                    egui::RichText::new(format!("t = {time:.3}   {}", readout.join("   ")))
+                        .monospace(),
+                );
+            }
+        }
         ui.label(title);
     }
 
+    fn show_spectrum(
+        &mut self,
+        ui: &mut egui::Ui,
+        show_axes: bool,
+        show_legend: bool,
+        custom_series_names: Option<&[String]>,
+        custom_colors: Option<&[egui::Color32]>,
+        custom_styles: Option<&[SeriesStyle]>,
+    ) {
+        let spectra = self.compute_spectra();
+        let mut max_freq: f64 = 0.0;
+        let mut max_mag: f64 = 0.0;
+        for spectrum in &spectra {
+            for [freq, mag] in spectrum {
+                max_freq = max_freq.max(*freq);
+                max_mag = max_mag.max(*mag);
+            }
+        }
+
+        let mut plot = Plot::new(format!("spectrum_{}", self.plugin_id))
+            .allow_scroll(false)
+            .allow_zoom(false)
+            .allow_boxed_zoom(false)
+            .allow_drag(false);
+        if show_legend {
+            plot = plot.legend(egui_plot::Legend::default());
+        }
+        if show_axes {
+            plot = plot.x_axis_label("frequency (Hz)").y_axis_label("magnitude");
+        }
+
+        plot.show(ui, |plot_ui| {
+            for (i, (series, spectrum)) in self.series.iter().zip(spectra).enumerate() {
+                if spectrum.is_empty() {
+                    continue;
+                }
+                let series_name = custom_series_names
+                    .and_then(|names| names.get(i))
+                    .map(|s| s.as_str())
+                    .unwrap_or(&series.name);
+                let series_color = custom_colors
+                    .and_then(|colors| colors.get(i))
+                    .copied()
+                    .unwrap_or(series.color);
+                let style = custom_styles
+                    .and_then(|styles| styles.get(i))
+                    .copied()
+                    .unwrap_or_default();
+                if style.marker != MarkerStyle::None {
+                    let marker_points = PlotPoints::from(spectrum.clone());
+                    plot_ui.points(styled_points(marker_points, style, series_color, series_name));
+                }
+                let line = Line::new(PlotPoints::from(spectrum))
+                    .color(series_color)
+                    .width(style.line_width)
+                    .name(series_name);
+                plot_ui.line(line);
+            }
+            if max_freq > 0.0 {
+                let top = if max_mag > 0.0 { max_mag * 1.05 } else { 1.0 };
+                plot_ui.set_plot_bounds(egui_plot::PlotBounds::from_min_max(
+                    [0.0, 0.0],
+                    [max_freq, top],
+                ));
+            }
+        });
+    }
+
+    /// Draws the Y series against the X series over the time window, with
+    /// the latest point marked.
+    fn show_xy(
+        &self,
+        ui: &mut egui::Ui,
+        plot: Plot,
+        custom_series_names: Option<&[String]>,
+        custom_colors: Option<&[egui::Color32]>,
+        custom_styles: Option<&[SeriesStyle]>,
+    ) {
+        let (min_time, max_time, _, _) = self.compute_bounds();
+        let points = self.xy_points(false, min_time, max_time).unwrap_or_default();
+        let (x_index, y_index) = self.xy_series;
+        let (x_name, _) = self.series_label(x_index, custom_series_names, custom_colors);
+        let (y_name, color) = self.series_label(y_index, custom_series_names, custom_colors);
+        let style = custom_styles
+            .and_then(|styles| styles.get(y_index))
+            .copied()
+            .unwrap_or_default();
+
+        let name = format!("{y_name} vs {x_name}");
+        plot.show(ui, |plot_ui| {
+            if style.marker != MarkerStyle::None {
+                let step = points.len().div_ceil(MAX_MARKERS).max(1);
+                let marker_points: PlotPoints = points.iter().step_by(step).map(|(x, y)| [*x, *y]).collect();
+                plot_ui.points(styled_points(marker_points, style, color, &name));
+            }
+            let line_points: PlotPoints = points.iter().map(|(x, y)| [*x, *y]).collect();
+            plot_ui.line(Line::new(line_points).color(color).width(style.line_width).name(&name));
+            if let Some(&(x, y)) = points.last() {
+                plot_ui.points(
+                    Points::new(vec![[x, y]])
+                        .shape(MarkerShape::Circle)
+                        .filled(true)
+                        .radius(style.line_width + 3.0)
+                        .color(color),
+                );
+                let (min_x, max_x, min_y, max_y) = self.xy_bounds(&points);
+                plot_ui.set_plot_bounds(egui_plot::PlotBounds::from_min_max([min_x, min_y], [max_x, max_y]));
+            }
+        });
+    }
+
+    /// Magnitude spectrum of the buffered window for each series.
+    fn compute_spectra(&mut self) -> Vec<Vec<[f64; 2]>> {
+        let mut spectra = Vec::with_capacity(self.raw_series.len());
+        for raw_series in &self.raw_series {
+            let samples: Vec<f64> = raw_series.iter().map(|(_, y)| *y).collect();
+            let time_step = if self.period_s > 0.0 {
+                self.period_s
+            } else {
+                // Without a known period, derive the spacing from the buffered timestamps.
+                match (raw_series.front(), raw_series.back()) {
+                    (Some(first), Some(last)) if raw_series.len() > 1 => {
+                        (last.0 - first.0) / self.last_time_scale / (raw_series.len() - 1) as f64
+                    }
+                    _ => 0.0,
+                }
+            };
+            spectra.push(magnitude_spectrum(&mut self.fft_planner, &samples, time_step));
+        }
+        spectra
+    }
+
+    /// Draws the plot as a `width` x `height` bitmap. A `supersample` factor
+    /// above 1 (at most [`MAX_SUPERSAMPLE`]) draws at that multiple of the
+    /// size and averages each block of pixels down, which smooths lines and
+    /// text; memory and render time grow with the square of the factor.
     pub(crate) fn export_png_with_settings(
         &mut self, 
-        path: &Path, 
+        target: BitmapTarget<'_>, 
+        time_label: &str,
+        show_axes: bool,
+        show_legend: bool,
+        show_grid: bool,
+        title: &str,
+        series_names: &[String],
+        series_colors: &[egui::Color32],
+        series_styles: &[SeriesStyle],
+        dark_theme: bool,
+        x_axis_name: &str,
+        y_axis_name: &str,
+        width: u32,
+        height: u32,
+        supersample: u32,
+    ) -> Result<(), String> {
+        let factor = supersample.clamp(1, MAX_SUPERSAMPLE);
+        if factor == 1 {
+            return self.draw_bitmap(
+                target, time_label, show_axes, show_legend, show_grid, title, series_names, series_colors,
+                series_styles, dark_theme, x_axis_name, y_axis_name, width, height, 1,
+            );
+        }
+        let (large_width, large_height) = (width * factor, height * factor);
+        let mut large = vec![0u8; large_width as usize * large_height as usize * 3];
+        self.draw_bitmap(
+            BitmapTarget::Rgb(&mut large), time_label, show_axes, show_legend, show_grid, title, series_names,
+            series_colors, series_styles, dark_theme, x_axis_name, y_axis_name, large_width, large_height, factor,
+        )?;
+        let rgb = downsample_rgb(&large, width, height, factor);
+        match target {
+            BitmapTarget::Rgb(buffer) => buffer.copy_from_slice(&rgb),
+            BitmapTarget::File(path) => {
+                image::save_buffer_with_format(path, &rgb, width, height, image::ColorType::Rgb8, image::ImageFormat::Png)
+                    .map_err(|e| e.to_string())?
+            }
+        }
+        Ok(())
+    }
+
+    /// Draws the plot like [`Self::export_png_with_settings`] at `width` x
+    /// `height`, with margins, fonts and strokes multiplied by `scale`.
+    fn draw_bitmap(
+        &mut self, 
+        target: BitmapTarget<'_>, 
         _time_label: &str,
         show_axes: bool,
         show_legend: bool,
@@ -309,11 +1014,13 @@ impl LivePlotter {
         title: &str,
         series_names: &[String],
         series_colors: &[egui::Color32],
+        series_styles: &[SeriesStyle],
         dark_theme: bool,
         x_axis_name: &str,
         y_axis_name: &str,
         width: u32,
         height: u32,
+        scale: u32,
     ) -> Result<(), String> {
         // Force flush any pending bucket data first
         self.flush_pending_bucket();
@@ -327,28 +1034,37 @@ impl LivePlotter {
             self.bucket_size = original_bucket_size; // Restore
             return Err("No samples to export.".to_string());
         }
+        let trajectory = self.xy_points(true, min_time, max_time);
+        let (min_x, max_x, min_y, max_y) = match &trajectory {
+            Some(points) => self.xy_bounds(points),
+            None => (min_time, max_time, min_y, max_y),
+        };
         
-        let root = BitMapBackend::new(path, (width, height)).into_drawing_area();
+        let root = match target {
+            BitmapTarget::File(path) => BitMapBackend::new(path, (width, height)),
+            BitmapTarget::Rgb(buffer) => BitMapBackend::with_buffer(buffer, (width, height)),
+        }
+        .into_drawing_area();
         let bg_color = if dark_theme { RGBColor(24, 24, 24) } else { RGBColor(255, 255, 255) };
         let text_color = if dark_theme { RGBColor(220, 220, 220) } else { RGBColor(40, 40, 40) };
         
         root.fill(&bg_color).map_err(|e| e.to_string())?;
         
-        let label_size = if show_axes { 40 } else { 0 };
+        let label_size = if show_axes { 40 * scale } else { 0 };
         let mut chart = if !title.is_empty() {
             ChartBuilder::on(&root)
-                .margin(20)
-                .caption(title, ("sans-serif", 24).into_font().color(&text_color))
+                .margin(20 * scale)
+                .caption(title, ("sans-serif", 24 * scale).into_font().color(&text_color))
                 .set_label_area_size(LabelAreaPosition::Left, label_size)
                 .set_label_area_size(LabelAreaPosition::Bottom, label_size)
-                .build_cartesian_2d(min_time..max_time, min_y..max_y)
+                .build_cartesian_2d(min_x..max_x, min_y..max_y)
                 .map_err(|e| e.to_string())?
         } else {
             ChartBuilder::on(&root)
-                .margin(20)
+                .margin(20 * scale)
                 .set_label_area_size(LabelAreaPosition::Left, label_size)
                 .set_label_area_size(LabelAreaPosition::Bottom, label_size)
-                .build_cartesian_2d(min_time..max_time, min_y..max_y)
+                .build_cartesian_2d(min_x..max_x, min_y..max_y)
                 .map_err(|e| e.to_string())?
         };
             
@@ -358,8 +1074,8 @@ impl LivePlotter {
         if show_axes {
             mesh.x_desc(x_axis_name)
                 .y_desc(y_axis_name)
-                .axis_desc_style(("sans-serif", 16).into_font().color(&text_color))
-                .label_style(("sans-serif", 14).into_font().color(&text_color))
+                .axis_desc_style(("sans-serif", 16 * scale).into_font().color(&text_color))
+                .label_style(("sans-serif", 14 * scale).into_font().color(&text_color))
                 .axis_style(&axis_color);
             if !show_grid {
                 mesh.disable_mesh();
@@ -371,55 +1087,71 @@ impl LivePlotter {
         }
         mesh.draw().map_err(|e| e.to_string())?;
         
-        for (i, raw_series) in self.raw_series.iter().enumerate() {
-            if raw_series.is_empty() {
-                // Fallback to bucketed data if raw data is empty
-                if let Some(series) = self.series.get(i) {
-                    if series.points.is_empty() {
-                        continue;
-                    }
-                    let color = series_colors.get(i)
-                        .map(|c| RGBColor(c.r(), c.g(), c.b()))
-                        .unwrap_or_else(|| RGBColor(series.color.r(), series.color.g(), series.color.b()));
-                    let name = series_names.get(i).cloned().unwrap_or_else(|| series.name.clone());
+        if let Some(points) = trajectory {
+            let (x_index, y_index) = self.xy_series;
+            let (x_name, _) = self.series_label(x_index, Some(series_names), Some(series_colors));
+            let (y_name, color) = self.series_label(y_index, Some(series_names), Some(series_colors));
+            let style = series_styles.get(y_index).copied().unwrap_or_default();
+            let legend = show_legend.then(|| format!("{y_name} vs {x_name}"));
+            draw_trajectory(
+                &mut chart, points, RGBColor(color.r(), color.g(), color.b()), style, style.export_width(scale), 3 * scale as i32, legend,
+            )?;
+        } else {
+            for (i, raw_series) in self.raw_series.iter().enumerate() {
+                if raw_series.is_empty() {
+                    // Fallback to bucketed data if raw data is empty
+                    if let Some(series) = self.series.get(i) {
+                        if series.points.is_empty() {
+                            continue;
+                        }
+                        let color = series_colors.get(i)
+                            .map(|c| RGBColor(c.r(), c.g(), c.b()))
+                            .unwrap_or_else(|| RGBColor(series.color.r(), series.color.g(), series.color.b()));
+                        let name = series_names.get(i).cloned().unwrap_or_else(|| series.name.clone());
                     
-                    let data = series.points.iter()
-                        .filter(|(x, _)| *x >= min_time && *x <= max_time)
-                        .map(|(x, y)| (*x, *y));
+                        let style = series_styles.get(i).copied().unwrap_or_default();
+                        let data: Vec<(f64, f64)> = self.display_line(series.points.iter().copied())
+                            .into_iter()
+                            .filter(|(x, _)| *x >= min_time && *x <= max_time)
+                            .collect();
                     
-                    let series_plot = chart
-                        .draw_series(LineSeries::new(data, color.stroke_width(1)))
-                        .map_err(|e| e.to_string())?;
-                    if show_legend {
-                        series_plot
-                            .label(name)
-                            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &color));
+                        draw_markers(&mut chart, &data, color, style.marker, 3 * scale as i32)?;
+                        let series_plot = chart
+                            .draw_series(LineSeries::new(data, color.stroke_width(style.export_width(scale))))
+                            .map_err(|e| e.to_string())?;
+                        if show_legend {
+                            series_plot
+                                .label(name)
+                                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20 * scale as i32, y)], color.stroke_width(scale)));
+                        }
                     }
+                    continue;
                 }
-                continue;
-            }
-            let color = series_colors.get(i)
-                .map(|c| RGBColor(c.r(), c.g(), c.b()))
-                .unwrap_or_else(|| {
-                    let series_color = self.series.get(i).map(|s| s.color).unwrap_or(egui::Color32::BLUE);
-                    RGBColor(series_color.r(), series_color.g(), series_color.b())
+                let color = series_colors.get(i)
+                    .map(|c| RGBColor(c.r(), c.g(), c.b()))
+                    .unwrap_or_else(|| {
+                        let series_color = self.series.get(i).map(|s| s.color).unwrap_or(egui::Color32::BLUE);
+                        RGBColor(series_color.r(), series_color.g(), series_color.b())
+                    });
+                let name = series_names.get(i).cloned().unwrap_or_else(|| {
+                    self.series.get(i).map(|s| s.name.clone()).unwrap_or_else(|| format!("Series {}", i + 1))
                 });
-            let name = series_names.get(i).cloned().unwrap_or_else(|| {
-                self.series.get(i).map(|s| s.name.clone()).unwrap_or_else(|| format!("Series {}", i + 1))
-            });
             
-            let data: Vec<(f64, f64)> = raw_series.iter()
-                .filter(|(x, _)| *x >= min_time && *x <= max_time)
-                .copied()
-                .collect();
+                let data: Vec<(f64, f64)> = self.display_line(raw_series.iter().copied())
+                    .into_iter()
+                    .filter(|(x, _)| *x >= min_time && *x <= max_time)
+                    .collect();
             
-            let series_plot = chart
-                .draw_series(LineSeries::new(data, color.stroke_width(1)))
-                .map_err(|e| e.to_string())?;
-            if show_legend {
-                series_plot
-                    .label(name)
-                    .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &color));
+                let style = series_styles.get(i).copied().unwrap_or_default();
+                draw_markers(&mut chart, &data, color, style.marker, 3 * scale as i32)?;
+                let series_plot = chart
+                    .draw_series(LineSeries::new(data, color.stroke_width(style.export_width(scale))))
+                    .map_err(|e| e.to_string())?;
+                if show_legend {
+                    series_plot
+                        .label(name)
+                        .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20 * scale as i32, y)], color.stroke_width(scale)));
+                }
             }
         }
         
@@ -428,9 +1160,9 @@ impl LivePlotter {
                 .configure_series_labels()
                 .background_style(if dark_theme { RGBColor(18, 18, 18) } else { RGBColor(240, 240, 240) })
                 .border_style(if dark_theme { RGBColor(80, 80, 80) } else { RGBColor(120, 120, 120) })
-                .label_font(("sans-serif", 16).into_font().color(&text_color))
+                .label_font(("sans-serif", 16 * scale).into_font().color(&text_color))
                 .position(SeriesLabelPosition::UpperRight)
-                .margin(12)
+                .margin(12 * scale)
                 .draw()
                 .map_err(|e| e.to_string())?;
         }
@@ -452,6 +1184,7 @@ impl LivePlotter {
         title: &str,
         series_names: &[String],
         series_colors: &[egui::Color32],
+        series_styles: &[SeriesStyle],
         dark_theme: bool,
         x_axis_name: &str,
         y_axis_name: &str,
@@ -463,6 +1196,11 @@ impl LivePlotter {
         if !min_time.is_finite() || !max_time.is_finite() {
             return Err("No samples to export.".to_string());
         }
+        let trajectory = self.xy_points(true, min_time, max_time);
+        let (min_x, max_x, min_y, max_y) = match &trajectory {
+            Some(points) => self.xy_bounds(points),
+            None => (min_time, max_time, min_y, max_y),
+        };
         
         // Use SVG backend for vector output
         let root = SVGBackend::new(path, (width, height)).into_drawing_area();
@@ -478,14 +1216,14 @@ impl LivePlotter {
                 .caption(title, ("sans-serif", 24).into_font().color(&text_color))
                 .set_label_area_size(LabelAreaPosition::Left, label_size)
                 .set_label_area_size(LabelAreaPosition::Bottom, label_size)
-                .build_cartesian_2d(min_time..max_time, min_y..max_y)
+                .build_cartesian_2d(min_x..max_x, min_y..max_y)
                 .map_err(|e| e.to_string())?
         } else {
             ChartBuilder::on(&root)
                 .margin(20)
                 .set_label_area_size(LabelAreaPosition::Left, label_size)
                 .set_label_area_size(LabelAreaPosition::Bottom, label_size)
-                .build_cartesian_2d(min_time..max_time, min_y..max_y)
+                .build_cartesian_2d(min_x..max_x, min_y..max_y)
                 .map_err(|e| e.to_string())?
         };
             
@@ -508,32 +1246,45 @@ impl LivePlotter {
         }
         mesh.draw().map_err(|e| e.to_string())?;
         
-        for (i, raw_series) in self.raw_series.iter().enumerate() {
-            if raw_series.is_empty() {
-                continue;
-            }
-            let color = series_colors.get(i)
-                .map(|c| RGBColor(c.r(), c.g(), c.b()))
-                .unwrap_or_else(|| {
-                    let series_color = self.series.get(i).map(|s| s.color).unwrap_or(egui::Color32::BLUE);
-                    RGBColor(series_color.r(), series_color.g(), series_color.b())
+        if let Some(points) = trajectory {
+            let (x_index, y_index) = self.xy_series;
+            let (x_name, _) = self.series_label(x_index, Some(series_names), Some(series_colors));
+            let (y_name, color) = self.series_label(y_index, Some(series_names), Some(series_colors));
+            let style = series_styles.get(y_index).copied().unwrap_or_default();
+            let legend = show_legend.then(|| format!("{y_name} vs {x_name}"));
+            draw_trajectory(
+                &mut chart, points, RGBColor(color.r(), color.g(), color.b()), style, style.export_width(3), 4, legend,
+            )?;
+        } else {
+            for (i, raw_series) in self.raw_series.iter().enumerate() {
+                if raw_series.is_empty() {
+                    continue;
+                }
+                let color = series_colors.get(i)
+                    .map(|c| RGBColor(c.r(), c.g(), c.b()))
+                    .unwrap_or_else(|| {
+                        let series_color = self.series.get(i).map(|s| s.color).unwrap_or(egui::Color32::BLUE);
+                        RGBColor(series_color.r(), series_color.g(), series_color.b())
+                    });
+                let name = series_names.get(i).cloned().unwrap_or_else(|| {
+                    self.series.get(i).map(|s| s.name.clone()).unwrap_or_else(|| format!("Series {}", i + 1))
                 });
-            let name = series_names.get(i).cloned().unwrap_or_else(|| {
-                self.series.get(i).map(|s| s.name.clone()).unwrap_or_else(|| format!("Series {}", i + 1))
-            });
             
-            let data: Vec<(f64, f64)> = raw_series.iter()
-                .filter(|(x, _)| *x >= min_time && *x <= max_time)
-                .copied()
-                .collect();
+                let data: Vec<(f64, f64)> = self.display_line(raw_series.iter().copied())
+                    .into_iter()
+                    .filter(|(x, _)| *x >= min_time && *x <= max_time)
+                    .collect();
             
-            let series_plot = chart
-                .draw_series(LineSeries::new(data, color.stroke_width(3)))
-                .map_err(|e| e.to_string())?;
-            if show_legend {
-                series_plot
-                    .label(name)
-                    .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &color));
+                let style = series_styles.get(i).copied().unwrap_or_default();
+                draw_markers(&mut chart, &data, color, style.marker, 4)?;
+                let series_plot = chart
+                    .draw_series(LineSeries::new(data, color.stroke_width(style.export_width(3))))
+                    .map_err(|e| e.to_string())?;
+                if show_legend {
+                    series_plot
+                        .label(name)
+                        .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &color));
+                }
             }
         }
         
@@ -554,8 +1305,9 @@ impl LivePlotter {
     }
 
     pub(crate) fn export_png(&mut self, path: &Path, time_label: &str) -> Result<(), String> {
+        let y_axis_name = self.value_axis_label();
         self.export_png_with_settings(
-            path, time_label, true, true, true, "", &[], &[], true, time_label, "value", 1200, 700
+            BitmapTarget::File(path), time_label, true, true, true, "", &[], &[], &[], true, time_label, &y_axis_name, 1200, 700, 1
         )
     }
 
@@ -570,6 +1322,7 @@ impl LivePlotter {
         title: &str,
         series_names: &[String],
         series_colors: &[egui::Color32],
+        series_styles: &[SeriesStyle],
         dark_theme: bool,
         x_axis_name: &str,
         y_axis_name: &str,
@@ -579,6 +1332,11 @@ impl LivePlotter {
         if !min_time.is_finite() || !max_time.is_finite() {
             return Err("No samples to export.".to_string());
         }
+        let trajectory = self.xy_points(true, min_time, max_time);
+        let (min_x, max_x, min_y, max_y) = match &trajectory {
+            Some(points) => self.xy_bounds(points),
+            None => (min_time, max_time, min_y, max_y),
+        };
         
         // High quality settings: 4K resolution
         let root = BitMapBackend::new(path, (3840, 2160)).into_drawing_area();
@@ -594,14 +1352,14 @@ impl LivePlotter {
                 .caption(title, ("sans-serif", 48).into_font().color(&text_color))
                 .set_label_area_size(LabelAreaPosition::Left, label_size)
                 .set_label_area_size(LabelAreaPosition::Bottom, label_size)
-                .build_cartesian_2d(min_time..max_time, min_y..max_y)
+                .build_cartesian_2d(min_x..max_x, min_y..max_y)
                 .map_err(|e| e.to_string())?
         } else {
             ChartBuilder::on(&root)
                 .margin(40)
                 .set_label_area_size(LabelAreaPosition::Left, label_size)
                 .set_label_area_size(LabelAreaPosition::Bottom, label_size)
-                .build_cartesian_2d(min_time..max_time, min_y..max_y)
+                .build_cartesian_2d(min_x..max_x, min_y..max_y)
                 .map_err(|e| e.to_string())?
         };
             
@@ -624,55 +1382,68 @@ impl LivePlotter {
         }
         mesh.draw().map_err(|e| e.to_string())?;
         
-        for (i, series) in self.series.iter().enumerate() {
-            if series.points.is_empty() {
-                continue;
-            }
-            let color = series_colors.get(i)
-                .map(|c| RGBColor(c.r(), c.g(), c.b()))
-                .unwrap_or_else(|| RGBColor(series.color.r(), series.color.g(), series.color.b()));
-            let name = series_names.get(i).cloned().unwrap_or_else(|| series.name.clone());
+        if let Some(points) = trajectory {
+            let (x_index, y_index) = self.xy_series;
+            let (x_name, _) = self.series_label(x_index, Some(series_names), Some(series_colors));
+            let (y_name, color) = self.series_label(y_index, Some(series_names), Some(series_colors));
+            let style = series_styles.get(y_index).copied().unwrap_or_default();
+            let legend = show_legend.then(|| format!("{y_name} vs {x_name}"));
+            draw_trajectory(
+                &mut chart, points, RGBColor(color.r(), color.g(), color.b()), style, style.export_width(1), 8, legend,
+            )?;
+        } else {
+            for (i, series) in self.series.iter().enumerate() {
+                if series.points.is_empty() {
+                    continue;
+                }
+                let color = series_colors.get(i)
+                    .map(|c| RGBColor(c.r(), c.g(), c.b()))
+                    .unwrap_or_else(|| RGBColor(series.color.r(), series.color.g(), series.color.b()));
+                let name = series_names.get(i).cloned().unwrap_or_else(|| series.name.clone());
             
-            // Filter out min-max artifacts by removing rapid oscillations
-            let filtered_data: Vec<(f64, f64)> = {
-                let points: Vec<(f64, f64)> = series.points.iter()
-                    .filter(|(x, _)| *x >= min_time && *x <= max_time)
-                    .copied()
-                    .collect();
+                // Filter out min-max artifacts by removing rapid oscillations
+                let filtered_data: Vec<(f64, f64)> = {
+                    let points: Vec<(f64, f64)> = self.display_line(series.points.iter().copied())
+                        .into_iter()
+                        .filter(|(x, _)| *x >= min_time && *x <= max_time)
+                        .collect();
                 
-                if points.len() > 3 {
-                    // Remove points that create rapid up-down-up patterns (min-max artifacts)
-                    let mut filtered = Vec::with_capacity(points.len());
-                    filtered.push(points[0]);
+                    if points.len() > 3 {
+                        // Remove points that create rapid up-down-up patterns (min-max artifacts)
+                        let mut filtered = Vec::with_capacity(points.len());
+                        filtered.push(points[0]);
                     
-                    for i in 1..points.len()-1 {
-                        let prev = points[i-1];
-                        let curr = points[i];
-                        let next = points[i+1];
+                        for i in 1..points.len()-1 {
+                            let prev = points[i-1];
+                            let curr = points[i];
+                            let next = points[i+1];
                         
-                        // Skip if this creates a sharp spike (min-max artifact)
-                        let is_spike = (curr.1 - prev.1).abs() > (next.1 - curr.1).abs() * 3.0 &&
-                                      (curr.1 - next.1).abs() > (prev.1 - curr.1).abs() * 3.0;
+                            // Skip if this creates a sharp spike (min-max artifact)
+                            let is_spike = (curr.1 - prev.1).abs() > (next.1 - curr.1).abs() * 3.0 &&
+                                          (curr.1 - next.1).abs() > (prev.1 - curr.1).abs() * 3.0;
                         
-                        if !is_spike {
-                            filtered.push(curr);
+                            if !is_spike {
+                                filtered.push(curr);
+                            }
                         }
+                        filtered.push(points[points.len()-1]);
+                        filtered
+                    } else {
+                        points
                     }
-                    filtered.push(points[points.len()-1]);
-                    filtered
-                } else {
-                    points
-                }
-            };
+                };
             
-            // Thicker lines for high quality export with smoother rendering
-            let series_plot = chart
-                .draw_series(LineSeries::new(filtered_data, color.stroke_width(1)))
-                .map_err(|e| e.to_string())?;
-            if show_legend {
-                series_plot
-                    .label(name)
-                    .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 40, y)], color.stroke_width(6)));
+                // Thicker lines for high quality export with smoother rendering
+                let style = series_styles.get(i).copied().unwrap_or_default();
+                draw_markers(&mut chart, &filtered_data, color, style.marker, 8)?;
+                let series_plot = chart
+                    .draw_series(LineSeries::new(filtered_data, color.stroke_width(style.export_width(1))))
+                    .map_err(|e| e.to_string())?;
+                if show_legend {
+                    series_plot
+                        .label(name)
+                        .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 40, y)], color.stroke_width(6)));
+                }
             }
         }
         
@@ -796,18 +1567,202 @@ impl LivePlotter {
     }
 }
 
-fn palette_color(idx: usize) -> Color32 {
-    const COLORS: [Color32; 10] = [
-        Color32::from_rgb(86, 156, 214),
-        Color32::from_rgb(220, 122, 95),
-        Color32::from_rgb(181, 206, 168),
-        Color32::from_rgb(197, 134, 192),
-        Color32::from_rgb(220, 220, 170),
-        Color32::from_rgb(156, 220, 254),
-        Color32::from_rgb(255, 204, 102),
-        Color32::from_rgb(206, 145, 120),
-        Color32::from_rgb(78, 201, 176),
-        Color32::from_rgb(214, 157, 133),
-    ];
-    COLORS[idx % COLORS.len()]
+/// Single-sided magnitude spectrum as `[frequency_hz, magnitude]` points.
+/// Bin spacing is `1 / (n * time_step)`, so the last bin sits at Nyquist.
+fn magnitude_spectrum(
+    planner: &mut FftPlanner<f64>,
+    samples: &[f64],
+    time_step: f64,
+) -> Vec<[f64; 2]> {
+    let n = samples.len();
+    if n < 2 || time_step <= 0.0 {
+        return Vec::new();
+    }
+    let mut buffer: Vec<Complex<f64>> = samples.iter().map(|v| Complex::new(*v, 0.0)).collect();
+    planner.plan_fft_forward(n).process(&mut buffer);
+    let bin_hz = 1.0 / (n as f64 * time_step);
+    buffer
+        .iter()
+        .take(n / 2 + 1)
+        .enumerate()
+        .map(|(k, value)| {
+            let scale = if k == 0 || 2 * k == n { 1.0 } else { 2.0 };
+            [k as f64 * bin_hz, value.norm() * scale / n as f64]
+        })
+        .collect()
+}
+
+fn styled_points(points: PlotPoints, style: SeriesStyle, color: Color32, name: &str) -> Points {
+    let shape = match style.marker {
+        MarkerStyle::Crosses => MarkerShape::Cross,
+        _ => MarkerShape::Circle,
+    };
+    Points::new(points)
+        .shape(shape)
+        .radius(1.5 + style.line_width)
+        .filled(true)
+        .color(color)
+        .name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn magnitude_spectrum_peaks_at_signal_frequency() {
+        let time_step = 0.001;
+        let samples: Vec<f64> = (0..1000)
+            .map(|i| 2.0 * (2.0 * std::f64::consts::PI * 50.0 * i as f64 * time_step).sin())
+            .collect();
+        let mut planner = FftPlanner::new();
+        let spectrum = magnitude_spectrum(&mut planner, &samples, time_step);
+        assert_eq!(spectrum.len(), 501);
+        assert!((spectrum[500][0] - 500.0).abs() < 1e-9);
+        let peak = spectrum
+            .iter()
+            .max_by(|a, b| a[1].total_cmp(&b[1]))
+            .unwrap();
+        assert!((peak[0] - 50.0).abs() < 1e-9);
+        assert!((peak[1] - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn bitmap_export_renders_into_memory() {
+        let (width, height) = (160, 90);
+        let mut rgb = vec![0u8; width as usize * height as usize * 3];
+        let mut plotter = LivePlotter::new(1);
+        plotter.update_config(1, 60.0, 1000.0, 0.0, 0.001);
+        let export = |plotter: &mut LivePlotter, rgb: &mut Vec<u8>| {
+            plotter.export_png_with_settings(
+                BitmapTarget::Rgb(rgb), "time", false, false, false, "", &[], &[], &[], true,
+                "time", "value", width, height, 1,
+            )
+        };
+        assert_eq!(export(&mut plotter, &mut rgb), Err("No samples to export.".to_string()));
+
+        for tick in 0..100 {
+            plotter.push_sample(tick, tick as f64 * 0.001, 1000.0, &[(tick % 10) as f64]);
+        }
+        export(&mut plotter, &mut rgb).unwrap();
+        // Dark background with the series drawn over it
+        assert_eq!(&rgb[..3], &[24, 24, 24]);
+        assert!(rgb.chunks_exact(3).any(|px| px != [24, 24, 24]));
+    }
+
+    #[test]
+    fn supersampled_export_averages_down_to_the_requested_size() {
+        // A 2x2 block per output pixel: black/white checks average to grey
+        let large = [0, 0, 0, 255, 255, 255, 255, 255, 255, 0, 0, 0];
+        assert_eq!(downsample_rgb(&large, 1, 1, 2), vec![128, 128, 128]);
+
+        let (width, height) = (160, 90);
+        let mut plotter = LivePlotter::new(1);
+        plotter.update_config(1, 60.0, 1000.0, 0.0, 0.001);
+        for tick in 0..100 {
+            plotter.push_sample(tick, tick as f64 * 0.001, 1000.0, &[(tick % 10) as f64]);
+        }
+        let mut rgb = vec![0u8; width as usize * height as usize * 3];
+        plotter
+            .export_png_with_settings(
+                BitmapTarget::Rgb(&mut rgb), "time", false, false, false, "", &[], &[], &[], true,
+                "time", "value", width, height, 3,
+            )
+            .unwrap();
+        assert_eq!(&rgb[..3], &[24, 24, 24]);
+        // The series is still drawn after scaling down
+        assert!(rgb.chunks_exact(3).any(|px| px != [24, 24, 24]));
+    }
+
+    #[test]
+    fn palette_colors_series_without_an_override() {
+        let palette = PlotPalette::OkabeIto;
+        let custom = Color32::from_rgb(1, 2, 3);
+        let colors = palette.series_colors(&[None, Some(custom), None]);
+        assert_eq!(colors, vec![palette.color(0), custom, palette.color(2)]);
+        assert_ne!(colors[0], colors[2]);
+        assert_eq!(palette.color(8), palette.color(0));
+
+        let mut plotter = LivePlotter::new(1);
+        plotter.update_config(2, 60.0, 1000.0, 0.0, 0.001);
+        assert_eq!(plotter.series[1].color, PlotPalette::default().color(1));
+        plotter.set_palette(PlotPalette::Viridis);
+        assert_eq!(plotter.series[1].color, PlotPalette::Viridis.color(1));
+    }
+
+    #[test]
+    fn values_at_interpolates_between_samples() {
+        let mut plotter = LivePlotter::new(1);
+        plotter.update_config(2, 60.0, 1000.0, 0.0, 0.001);
+        for tick in 0..10 {
+            let t = tick as f64 * 0.001;
+            plotter.push_sample(tick, t, 1000.0, &[tick as f64, 2.0]);
+        }
+        plotter.flush_pending_bucket();
+        let first = plotter.series[0].points[0].0;
+        let step = plotter.series[0].points[1].0 - first;
+        let values = plotter.values_at(first + step * 2.5);
+        assert!((values[0].unwrap() - 2.5).abs() < 1e-9);
+        assert_eq!(values[1], Some(2.0));
+        assert_eq!(plotter.values_at(first - 1.0), vec![None, None]);
+    }
+
+    #[test]
+    fn display_smoothing_leaves_samples_raw() {
+        let line = smooth_line([(0.0, 0.0), (1.0, 4.0), (2.0, f64::NAN), (3.0, 2.0)], 0.5);
+        assert_eq!(line[1], (1.0, 2.0));
+        assert!(line[2].1.is_nan());
+        assert_eq!(line[3], (3.0, 2.0));
+
+        let mut plotter = LivePlotter::new(1);
+        plotter.update_config(1, 60.0, 1000.0, 0.0, 0.001);
+        for tick in 0..4 {
+            plotter.push_sample(tick, tick as f64 * 0.001, 1000.0, &[if tick % 2 == 0 { 0.0 } else { 4.0 }]);
+        }
+        plotter.flush_pending_bucket();
+        plotter.display_smoothing = 0.5;
+        let last = plotter.series[0].points[3].0;
+        assert_eq!(plotter.values_at(last), vec![Some(4.0)]);
+        plotter.smoothed_readout = true;
+        // 0, 2, 1, 2.5
+        assert_eq!(plotter.values_at(last), vec![Some(2.5)]);
+        assert_eq!(plotter.series[0].points[3].1, 4.0);
+    }
+
+    #[test]
+    fn xy_mode_pairs_series_by_sample_time() {
+        let x_line = [(0.0, 1.0), (1.0, 2.0), (2.0, 3.0), (3.0, 4.0)];
+        let y_line = [(1.0, 20.0), (2.0, 30.0), (3.0, 40.0)];
+        assert_eq!(pair_xy(&x_line, &y_line, 0.0, 2.0), vec![(2.0, 20.0), (3.0, 30.0)]);
+
+        let mut plotter = LivePlotter::new(1);
+        plotter.update_config(2, 60.0, 1000.0, 0.0, 0.001);
+        for tick in 0..100 {
+            let phase = tick as f64 * 0.1;
+            plotter.push_sample(tick, tick as f64 * 0.001, 1000.0, &[phase.cos(), phase.sin()]);
+        }
+        let (min_time, max_time, _, _) = plotter.compute_bounds();
+        assert_eq!(plotter.xy_points(true, min_time, max_time), None);
+        plotter.plot_mode = PlotMode::Xy;
+        let trajectory = plotter.xy_points(true, min_time, max_time).unwrap();
+        assert!(trajectory.len() > 1);
+        assert!(trajectory.iter().all(|(x, y)| (x * x + y * y - 1.0).abs() < 1e-9));
+
+        let (width, height) = (160, 90);
+        let mut rgb = vec![0u8; width as usize * height as usize * 3];
+        plotter
+            .export_png_with_settings(
+                BitmapTarget::Rgb(&mut rgb), "time", false, false, false, "", &[], &[], &[], true,
+                "x", "y", width, height, 1,
+            )
+            .unwrap();
+        assert!(rgb.chunks_exact(3).any(|px| px != [24, 24, 24]));
+    }
+
+    #[test]
+    fn magnitude_spectrum_needs_samples_and_step() {
+        let mut planner = FftPlanner::new();
+        assert!(magnitude_spectrum(&mut planner, &[1.0], 0.001).is_empty());
+        assert!(magnitude_spectrum(&mut planner, &[1.0, 2.0], 0.0).is_empty());
+    }
 }
@@ -1,21 +1,62 @@
-use crate::plotter::LivePlotter;
+use crate::plotter::{LivePlotter, PlotPalette, SeriesStyle};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
+/// Cursor time of the plotter being hovered, followed by every plotter with
+/// linked cursors.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinkedCursor {
+    pub source: u64,
+    pub time: f64,
+}
+
 pub struct PlotterManager {
     pub plotters: HashMap<u64, Arc<Mutex<LivePlotter>>>,
-    pub plotter_preview_settings: HashMap<u64, (bool, bool, bool, Vec<String>, Vec<egui::Color32>, String, bool, String, String, bool, bool)>,
+    /// Shared with the plotter viewports, which render outside `update`.
+    pub linked_cursor: Arc<Mutex<Option<LinkedCursor>>>,
+    pub plotter_preview_settings: HashMap<u64, (bool, bool, bool, Vec<String>, Vec<Option<egui::Color32>>, Vec<SeriesStyle>, String, bool, String, String, bool, bool, PlotPalette)>,
 }
 
 impl PlotterManager {
     pub fn new() -> Self {
         Self {
             plotters: HashMap::new(),
+            linked_cursor: Arc::new(Mutex::new(None)),
             plotter_preview_settings: HashMap::new(),
         }
     }
 }
 
+/// Renders `plotter` following the shared cursor when its cursors are linked,
+/// and publishes its own hovered time in return.
+pub fn render_linked(
+    plotter: &mut LivePlotter,
+    plugin_id: u64,
+    linked_cursor: &Mutex<Option<LinkedCursor>>,
+    ui: &mut egui::Ui,
+    title: &str,
+    time_label: &str,
+) {
+    let Ok(mut cursor) = linked_cursor.lock() else {
+        plotter.render(ui, title, time_label);
+        return;
+    };
+    plotter.linked_cursor_time = cursor
+        .filter(|cursor| plotter.link_cursor && cursor.source != plugin_id)
+        .map(|cursor| cursor.time);
+    plotter.render(ui, title, time_label);
+    match plotter.hovered_time.filter(|_| plotter.link_cursor) {
+        Some(time) => {
+            *cursor = Some(LinkedCursor {
+                source: plugin_id,
+                time,
+            })
+        }
+        None if cursor.is_some_and(|cursor| cursor.source == plugin_id) => *cursor = None,
+        None => {}
+    }
+}
+
 impl Default for PlotterManager {
     fn default() -> Self {
         Self::new()
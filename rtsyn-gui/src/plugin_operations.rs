@@ -1,7 +1,8 @@
 use crate::GuiApp;
 use rtsyn_runtime::runtime::LogicMessage;
-use rtsyn_core::plugin::PluginMetadataSource;
-use std::path::Path;
+use rtsyn_core::plugin::{filter_imported_config, ImportedConfig, PluginMetadataSource};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc;
 use std::time::Duration;
 
@@ -50,7 +51,100 @@ impl GuiApp {
             .scan_detected_plugins_in(&["plugins", "app_plugins"]);
     }
 
+    pub(crate) fn add_plugin_search_path(&mut self, path: PathBuf) {
+        match self.plugin_manager.add_search_path(path) {
+            Ok(()) => {
+                self.plugin_manager.refresh_library_paths();
+                self.scan_detected_plugins();
+                self.status = "Plugin path added".to_string();
+            }
+            Err(err) => self.show_info("Plugin paths", &err),
+        }
+    }
+
+    pub(crate) fn remove_plugin_search_path(&mut self, path: &Path) {
+        if self.plugin_manager.remove_search_path(path) {
+            self.plugin_manager.refresh_library_paths();
+            self.scan_detected_plugins();
+            self.status = "Plugin path removed".to_string();
+        }
+    }
+
+    pub(crate) fn export_plugin_config(&mut self, plugin_id: u64, path: &Path) {
+        let Some(plugin) = self.workspace_manager.workspace.plugins.iter().find(|p| p.id == plugin_id) else {
+            return;
+        };
+        let result = serde_json::to_string_pretty(&plugin.config)
+            .map_err(|e| e.to_string())
+            .and_then(|data| std::fs::write(path, data).map_err(|e| e.to_string()));
+        match result {
+            Ok(()) => self.show_info("Plugin config", "Plugin config exported"),
+            Err(err) => self.show_info("Plugin config", &format!("Export failed: {err}")),
+        }
+    }
+
+    pub(crate) fn import_plugin_config(&mut self, plugin_id: u64, path: &Path) {
+        if self.reject_if_locked() {
+            return;
+        }
+        let imported = match std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|data| serde_json::from_str::<Value>(&data).map_err(|e| e.to_string()))
+        {
+            Ok(value) => value,
+            Err(err) => {
+                self.show_info("Plugin config", &format!("Import failed: {err}"));
+                return;
+            }
+        };
+        let Some(kind) = self.workspace_manager.workspace.plugins.iter()
+            .find(|p| p.id == plugin_id)
+            .map(|p| p.kind.clone())
+        else {
+            return;
+        };
+        let Some(installed) = self.plugin_manager.installed_plugins.iter().find(|p| p.manifest.kind == kind) else {
+            self.show_info("Plugin config", "Plugin is not installed");
+            return;
+        };
+        let ImportedConfig { accepted, skipped } = match filter_imported_config(installed, &imported) {
+            Ok(result) => result,
+            Err(err) => {
+                self.show_info("Plugin config", &err);
+                return;
+            }
+        };
+        if accepted.is_empty() {
+            self.show_info("Plugin config", "No matching settings found");
+            return;
+        }
+
+        let applied = accepted.len();
+        if let Some(plugin) = self.workspace_manager.workspace.plugins.iter_mut().find(|p| p.id == plugin_id) {
+            if let Value::Object(ref mut map) = plugin.config {
+                for (key, value) in accepted {
+                    let _ = self.state_sync.logic_tx.send(LogicMessage::SetPluginVariable(
+                        plugin_id,
+                        key.clone(),
+                        value.clone(),
+                    ));
+                    map.insert(key, value);
+                }
+            }
+        }
+        self.mark_workspace_dirty();
+        let message = if skipped.is_empty() {
+            format!("Applied {applied} settings")
+        } else {
+            format!("Applied {applied} settings, skipped unknown: {}", skipped.join(", "))
+        };
+        self.show_info("Plugin config", &message);
+    }
+
     pub(crate) fn add_installed_plugin(&mut self, installed_index: usize) {
+        if self.reject_if_locked() {
+            return;
+        }
         let installed = match self.plugin_manager.installed_plugins.get(installed_index) {
             Some(plugin) => plugin.clone(),
             None => {
@@ -78,6 +172,9 @@ impl GuiApp {
     }
 
     pub(crate) fn duplicate_plugin(&mut self, plugin_id: u64) {
+        if self.reject_if_locked() {
+            return;
+        }
         let new_id = match self
             .plugin_manager
             .duplicate_plugin_in_workspace(&mut self.workspace_manager.workspace, plugin_id)
@@ -102,7 +199,24 @@ impl GuiApp {
         self.mark_workspace_dirty();
     }
 
+    /// Collapses or expands every plugin card, e.g. for an overview of a
+    /// large workspace.
+    pub(crate) fn set_all_cards_collapsed(&mut self, collapsed: bool) {
+        for plugin in &mut self.workspace_manager.workspace.plugins {
+            if let Value::Object(map) = &mut plugin.config {
+                map.insert(
+                    rtsyn_core::plugin::UI_COLLAPSED_KEY.to_string(),
+                    Value::Bool(collapsed),
+                );
+            }
+        }
+        self.mark_workspace_dirty();
+    }
+
     pub(crate) fn remove_plugin(&mut self, plugin_index: usize) {
+        if self.reject_if_locked() {
+            return;
+        }
         if plugin_index >= self.workspace_manager.workspace.plugins.len() {
             self.status = "Invalid plugin selection".to_string();
             return;
@@ -211,6 +325,26 @@ impl GuiApp {
         self.status = "Plugin refreshed".to_string();
     }
 
+    /// Points a plugin whose library did not load at `path`, keeping the
+    /// rest of its config and its connections. The runtime tries loading it
+    /// again when the config update reaches it.
+    pub(crate) fn locate_plugin_library(&mut self, plugin_id: u64, path: &Path) {
+        if self.reject_if_locked() {
+            return;
+        }
+        let Some(plugin) = self.workspace_manager.workspace.plugins.iter_mut().find(|p| p.id == plugin_id) else {
+            return;
+        };
+        if let Value::Object(ref mut map) = plugin.config {
+            map.insert(
+                "library_path".to_string(),
+                Value::String(path.to_string_lossy().to_string()),
+            );
+        }
+        self.mark_workspace_dirty();
+        self.status = format!("Plugin library set to {}", path.display());
+    }
+
     pub(crate) fn refresh_installed_library_paths(&mut self) {
         self.plugin_manager.refresh_installed_library_paths();
     }
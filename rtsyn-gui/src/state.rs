@@ -7,6 +7,7 @@ pub(crate) enum WorkspaceDialogMode {
     New,
     Save,
     Edit,
+    Duplicate,
 }
 
 #[derive(Debug, Clone)]
@@ -14,6 +15,10 @@ pub(crate) enum ConfirmAction {
     RemovePlugin(u64),
     UninstallPlugin(usize),
     DeleteWorkspace(PathBuf),
+    RegenerateCsvColumns(u64),
+    SaveAndClose,
+    DiscardAndClose,
+    CloseTab(usize),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -57,9 +62,38 @@ pub(crate) enum ConnectionEditTab {
     Outputs,
 }
 
+/// Selector of the Add connection row that keyboard input acts on, in Tab
+/// order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum ConnectionField {
+    #[default]
+    FromPlugin,
+    FromPort,
+    ToPlugin,
+    ToPort,
+    Kind,
+}
+
+impl ConnectionField {
+    const ORDER: [ConnectionField; 5] = [
+        ConnectionField::FromPlugin,
+        ConnectionField::FromPort,
+        ConnectionField::ToPlugin,
+        ConnectionField::ToPort,
+        ConnectionField::Kind,
+    ];
+
+    /// The next field in Tab order, or the previous one with Shift held.
+    pub(crate) fn step(self, backwards: bool) -> Self {
+        let pos = Self::ORDER.iter().position(|field| *field == self).unwrap_or(0);
+        Self::ORDER[crate::utils::step_index(pos, Self::ORDER.len(), !backwards)]
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum ManageTab {
     Install,
+    Paths,
 }
 
 impl Default for ManageTab {
@@ -1,20 +1,40 @@
-use rtsyn_runtime::runtime::{LogicMessage, LogicState};
-use std::collections::HashMap;
+use rtsyn_runtime::runtime::{LatencyStats, LogicMessage, LogicState, PluginRunState};
+use std::collections::{HashMap, HashSet};
 use std::sync::mpsc::{Receiver, Sender};
 use std::time::Instant;
+use workspace::ConnectionDefinition;
 
 pub struct StateSync {
     pub logic_tx: Sender<LogicMessage>,
     pub logic_state_rx: Receiver<LogicState>,
     pub computed_outputs: HashMap<(u64, String), f64>,
+    /// `computed_outputs` before their last refresh, to tell which changed.
+    pub previous_outputs: HashMap<(u64, String), f64>,
     pub input_values: HashMap<(u64, String), f64>,
     pub internal_variable_values: HashMap<(u64, String), serde_json::Value>,
     pub viewer_values: HashMap<u64, f64>,
+    pub warming_up: HashSet<u64>,
+    pub open_errors: HashMap<u64, String>,
+    /// Plugins whose library is missing or failed to load, shown as
+    /// placeholder cards.
+    pub load_errors: HashMap<u64, String>,
     pub last_output_update: Instant,
     pub logic_period_seconds: f64,
     pub logic_time_scale: f64,
     pub logic_time_label: String,
     pub logic_ui_hz: f64,
+    pub plugin_states_rx: Option<Receiver<HashMap<u64, PluginRunState>>>,
+    /// Run state of each plugin as the runtime last reported it. Display
+    /// only: it can differ from the saved `running` flag, e.g. after an estop.
+    pub plugin_run_states: HashMap<u64, PluginRunState>,
+    pub last_plugin_states_query: Instant,
+    pub connection_latency_rx: Option<Receiver<Vec<(ConnectionDefinition, LatencyStats)>>>,
+    pub connection_latency: Vec<(ConnectionDefinition, LatencyStats)>,
+    pub last_connection_latency_query: Option<Instant>,
+    pub logic_tick: u64,
+    /// Whether the runtime only ticks on request, as last reported.
+    pub step_mode: bool,
+    pub run_started: Option<(u64, Instant)>,
 }
 
 impl StateSync {
@@ -23,14 +43,27 @@ impl StateSync {
             logic_tx,
             logic_state_rx,
             computed_outputs: HashMap::new(),
+            previous_outputs: HashMap::new(),
             input_values: HashMap::new(),
             internal_variable_values: HashMap::new(),
             viewer_values: HashMap::new(),
+            warming_up: HashSet::new(),
+            open_errors: HashMap::new(),
+            load_errors: HashMap::new(),
             last_output_update: Instant::now(),
             logic_period_seconds: 0.001,
             logic_time_scale: 1000.0,
             logic_time_label: "time_ms".to_string(),
             logic_ui_hz: 60.0,
+            plugin_states_rx: None,
+            plugin_run_states: HashMap::new(),
+            last_plugin_states_query: Instant::now(),
+            connection_latency_rx: None,
+            connection_latency: Vec::new(),
+            last_connection_latency_query: None,
+            logic_tick: 0,
+            step_mode: false,
+            run_started: None,
         }
     }
 }
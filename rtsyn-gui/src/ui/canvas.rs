@@ -0,0 +1,211 @@
+use super::*;
+use crate::card_layout::{self, CardLayout};
+use eframe::egui::emath::TSTransform;
+
+const MIN_CANVAS_ZOOM: f32 = 0.25;
+const MAX_CANVAS_ZOOM: f32 = 3.0;
+const FIT_MARGIN: f32 = 24.0;
+
+impl GuiApp {
+    /// Scroll-wheel zoom around the pointer and middle-drag pan over empty
+    /// parts of the canvas.
+    pub(crate) fn handle_canvas_navigation(&mut self, ctx: &egui::Context, panel_rect: egui::Rect) {
+        let over_canvas = |pos: egui::Pos2| {
+            panel_rect.contains(pos)
+                && ctx
+                    .layer_id_at(pos)
+                    .is_none_or(|layer| layer.order == egui::Order::Background)
+        };
+
+        let (hover_pos, scroll, middle_down, press_origin, delta) = ctx.input(|i| {
+            (
+                i.pointer.hover_pos(),
+                i.smooth_scroll_delta.y,
+                i.pointer.middle_down(),
+                i.pointer.press_origin(),
+                i.pointer.delta(),
+            )
+        });
+
+        if middle_down && press_origin.is_some_and(|pos| panel_rect.contains(pos)) {
+            if delta != egui::Vec2::ZERO {
+                self.canvas_transform.translation += delta;
+            }
+            ctx.set_cursor_icon(egui::CursorIcon::Grabbing);
+            return;
+        }
+
+        if let Some(pointer) = hover_pos.filter(|pos| over_canvas(*pos)) {
+            if scroll != 0.0 {
+                let current = self.canvas_transform.scaling;
+                let target = (current * (scroll * 0.002).exp()).clamp(MIN_CANVAS_ZOOM, MAX_CANVAS_ZOOM);
+                self.zoom_canvas_around(pointer, target / current);
+            }
+        }
+    }
+
+    fn zoom_canvas_around(&mut self, pivot: egui::Pos2, factor: f32) {
+        self.canvas_transform = TSTransform::from_translation(pivot.to_vec2())
+            * TSTransform::from_scaling(factor)
+            * TSTransform::from_translation(-pivot.to_vec2())
+            * self.canvas_transform;
+    }
+
+    /// Frames every plugin card in the panel, never zooming in past 100%.
+    pub(crate) fn fit_canvas_to_view(&mut self, panel_rect: egui::Rect) {
+        let to_canvas = self.canvas_transform.inverse();
+        let bounds = self
+            .workspace_manager
+            .workspace
+            .plugins
+            .iter()
+            .filter_map(|plugin| self.plugin_rects.get(&plugin.id))
+            .map(|rect| to_canvas * *rect)
+            .reduce(|acc, rect| acc.union(rect));
+        let Some(bounds) = bounds else {
+            self.canvas_transform = TSTransform::IDENTITY;
+            return;
+        };
+        self.fit_canvas_to_bounds(bounds, panel_rect);
+    }
+
+    /// Frames canvas-space `bounds` in the panel.
+    fn fit_canvas_to_bounds(&mut self, bounds: egui::Rect, panel_rect: egui::Rect) {
+        let available = panel_rect.shrink(FIT_MARGIN);
+        let scaling = (available.width() / bounds.width().max(1.0))
+            .min(available.height() / bounds.height().max(1.0))
+            .clamp(MIN_CANVAS_ZOOM, 1.0);
+        let translation = panel_rect.center().to_vec2() - bounds.center().to_vec2() * scaling;
+        self.canvas_transform = TSTransform::new(translation, scaling);
+    }
+
+    pub(crate) fn render_canvas_controls(&mut self, ctx: &egui::Context, panel_rect: egui::Rect) {
+        let mut fit = false;
+        let mut reset = false;
+        egui::Area::new(egui::Id::new("canvas_controls"))
+            .order(egui::Order::Foreground)
+            .pivot(egui::Align2::RIGHT_BOTTOM)
+            .fixed_pos(panel_rect.right_bottom() - egui::vec2(12.0, 12.0))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        let zoom = self.canvas_transform.scaling * 100.0;
+                        if ui
+                            .button(format!("{zoom:.0}%"))
+                            .on_hover_text("Reset zoom and pan")
+                            .clicked()
+                        {
+                            reset = true;
+                        }
+                        if ui
+                            .button("Fit to view")
+                            .on_hover_text("Scroll to zoom, middle-drag to pan")
+                            .clicked()
+                        {
+                            fit = true;
+                        }
+                    });
+                });
+            });
+        if reset {
+            self.canvas_transform = TSTransform::IDENTITY;
+        } else if fit {
+            self.fit_canvas_to_view(panel_rect);
+        }
+    }
+
+    /// Align and distribute buttons, shown while several cards are selected.
+    pub(crate) fn render_selection_toolbar(&mut self, ctx: &egui::Context, panel_rect: egui::Rect) {
+        let plugins = &self.workspace_manager.workspace.plugins;
+        self.selected_plugin_ids
+            .retain(|id| plugins.iter().any(|plugin| plugin.id == *id));
+        let count = self.selected_plugin_ids.len();
+        if count < 2 {
+            return;
+        }
+        let mut action = None;
+        egui::Area::new(egui::Id::new("card_selection_toolbar"))
+            .order(egui::Order::Foreground)
+            .pivot(egui::Align2::CENTER_TOP)
+            .fixed_pos(panel_rect.center_top() + egui::vec2(0.0, 8.0))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{count} selected"));
+                        ui.separator();
+                        for layout in CardLayout::ALL {
+                            if ui
+                                .add_enabled(count >= layout.min_cards(), egui::Button::new(layout.label()))
+                                .clicked()
+                            {
+                                action = Some(layout);
+                            }
+                        }
+                        ui.separator();
+                        if ui.button("Clear").on_hover_text("Ctrl/Shift-click cards to select").clicked() {
+                            self.selected_plugin_ids.clear();
+                        }
+                    });
+                });
+            });
+        if let Some(layout) = action {
+            self.arrange_selected_cards(layout);
+        }
+    }
+
+    /// Runs the requested auto-layout once every card has a size, i.e. a
+    /// frame after a workspace is loaded. Zooms out when the result does not
+    /// fit the panel.
+    pub(crate) fn apply_pending_auto_layout(&mut self, panel_rect: egui::Rect) {
+        let Some(layout) = self.pending_auto_layout else {
+            return;
+        };
+        let plugins = &self.workspace_manager.workspace.plugins;
+        if !plugins.iter().all(|plugin| self.plugin_rects.contains_key(&plugin.id)) {
+            return;
+        }
+        self.pending_auto_layout = None;
+        let to_canvas = self.canvas_transform.inverse();
+        let cards: Vec<(u64, egui::Vec2)> = plugins
+            .iter()
+            .map(|plugin| (plugin.id, (to_canvas * self.plugin_rects[&plugin.id]).size()))
+            .collect();
+        let edges: Vec<(u64, u64)> = self
+            .workspace_manager
+            .workspace
+            .connections
+            .iter()
+            .map(|conn| (conn.from_plugin, conn.to_plugin))
+            .collect();
+        let origin = to_canvas * (panel_rect.min + egui::vec2(12.0, 12.0));
+        let sizes: HashMap<u64, egui::Vec2> = cards.iter().copied().collect();
+        let mut bounds = egui::Rect::NOTHING;
+        for (id, pos) in card_layout::auto_arrange(&cards, &edges, layout, origin) {
+            let rect = egui::Rect::from_min_size(pos, sizes[&id]);
+            bounds = bounds.union(rect);
+            self.plugin_positions.insert(id, pos);
+            self.pending_card_positions.insert(id, pos);
+            self.plugin_rects.insert(id, self.canvas_transform * rect);
+        }
+        if bounds.is_positive() && !(to_canvas * panel_rect).contains_rect(bounds) {
+            self.fit_canvas_to_bounds(bounds, panel_rect);
+        }
+    }
+
+    fn arrange_selected_cards(&mut self, layout: CardLayout) {
+        if self.reject_if_locked() {
+            return;
+        }
+        let to_canvas = self.canvas_transform.inverse();
+        let mut cards: Vec<(u64, egui::Rect)> = self
+            .selected_plugin_ids
+            .iter()
+            .filter_map(|id| self.plugin_rects.get(id).map(|rect| (*id, to_canvas * *rect)))
+            .collect();
+        cards.sort_by_key(|(id, _)| *id);
+        for (id, pos) in card_layout::arrange(&cards, layout) {
+            self.plugin_positions.insert(id, pos);
+            self.pending_card_positions.insert(id, pos);
+        }
+    }
+}
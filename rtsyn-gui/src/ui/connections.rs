@@ -1,7 +1,43 @@
 use super::*;
-use crate::WindowFocus;
+use crate::connection_routing;
+use crate::utils::step_index;
+use crate::{WindowFocus, CONNECTION_LATENCY_QUERY_INTERVAL};
+use rtsyn_runtime::runtime::LatencyStats;
+use std::time::{Duration, Instant};
+
+/// Dash and gap lengths of the data flow animation, in points.
+const FLOW_DASH: f32 = 6.0;
+const FLOW_GAP: f32 = 10.0;
+/// Dash speed along an active line, in points per second, from the
+/// weakest signal to the strongest.
+const FLOW_SPEED: std::ops::RangeInclusive<f64> = 20.0..=120.0;
+
+/// Focus target of keyboard entry in the Add connection row.
+const CONNECTION_KEYBOARD_ID: &str = "conn_keyboard";
 
 impl GuiApp {
+    /// Activity of the outputs feeding a connection line: `None` while they
+    /// are all zero and unchanged, else their largest magnitude mapped to
+    /// 0..1, reaching 0.5 at a magnitude of 1.
+    fn connection_flow(&self, plugin_id: u64, ports: &[String]) -> Option<f32> {
+        let mut active = false;
+        let mut magnitude = 0.0_f64;
+        for port in ports {
+            let key = (plugin_id, port.clone());
+            let Some(value) = self.state_sync.computed_outputs.get(&key).filter(|v| v.is_finite()) else {
+                continue;
+            };
+            let changed = self
+                .state_sync
+                .previous_outputs
+                .get(&key)
+                .is_some_and(|previous| previous != value);
+            active |= *value != 0.0 || changed;
+            magnitude = magnitude.max(value.abs());
+        }
+        active.then(|| (magnitude / (1.0 + magnitude)) as f32)
+    }
+
     pub(crate) fn open_connection_editor(&mut self, plugin_id: u64, mode: ConnectionEditMode) {
         self.connection_editor.open = true;
         self.connection_editor.mode = mode;
@@ -19,6 +55,14 @@ impl GuiApp {
         });
     }
 
+    pub(crate) fn open_connection_inspector(&mut self, plugin_id: u64) {
+        self.windows.connection_inspector_id = Some(plugin_id);
+        if !self.connection_editor.open {
+            self.connection_highlight_plugin_id = None;
+        }
+        self.pending_window_focus = Some(WindowFocus::ConnectionInspector);
+    }
+
     pub(crate) fn render_manage_connections_window(&mut self, ctx: &egui::Context) {
         if !self.windows.manage_connections_open {
             return;
@@ -29,8 +73,38 @@ impl GuiApp {
             .iter()
             .map(|plugin| (plugin.manifest.kind.clone(), plugin.manifest.name.clone()))
             .collect();
+        let keys_id = egui::Id::new(CONNECTION_KEYBOARD_ID);
+        let keyboard = ctx.memory(|m| m.has_focus(keys_id));
+        if keyboard {
+            // Keep Tab and the arrows from moving egui's focus away
+            ctx.memory_mut(|m| {
+                m.set_focus_lock_filter(
+                    keys_id,
+                    egui::EventFilter {
+                        tab: true,
+                        horizontal_arrows: true,
+                        vertical_arrows: true,
+                        escape: false,
+                    },
+                )
+            });
+        }
+        let (tab_pressed, backwards, step, enter_pressed) = if keyboard {
+            ctx.input(|i| {
+                let step = if i.key_pressed(egui::Key::ArrowDown) || i.key_pressed(egui::Key::ArrowRight) {
+                    Some(true)
+                } else if i.key_pressed(egui::Key::ArrowUp) || i.key_pressed(egui::Key::ArrowLeft) {
+                    Some(false)
+                } else {
+                    None
+                };
+                (i.key_pressed(egui::Key::Tab), i.modifiers.shift, step, i.key_pressed(egui::Key::Enter))
+            })
+        } else {
+            (false, false, None, false)
+        };
         let window_size = egui::vec2(420.0, 360.0);
-        let default_pos = Self::center_window(ctx, window_size);
+        let default_pos = self.window_placement(ctx, "ManageConnections", window_size).min;
         let response = egui::Window::new("Manage connections")
             .open(&mut open)
             .resizable(false)
@@ -42,6 +116,7 @@ impl GuiApp {
                 if self.workspace_manager.workspace.connections.is_empty() {
                     ui.label("No connections yet.");
                 } else {
+                    let mut pending_gain: Option<(usize, f64)> = None;
                     egui::ScrollArea::vertical()
                         .max_height(140.0)
                         .show(ui, |ui| {
@@ -55,6 +130,13 @@ impl GuiApp {
                                     connection.to_port,
                                     Self::display_connection_kind(&connection.kind)
                                 ));
+                                ui.horizontal(|ui| {
+                                    ui.label("Gain");
+                                    let mut gain = connection.gain;
+                                    if ui.add(egui::DragValue::new(&mut gain).speed(0.01)).changed() {
+                                        pending_gain = Some((idx, gain));
+                                    }
+                                });
                                 if ui.button(format!("Remove #{display_idx}")).clicked() {
                                     let connection = connection.clone();
                                     self.remove_connection_with_input(connection);
@@ -62,22 +144,76 @@ impl GuiApp {
                                 }
                             }
                         });
+                    if let Some((idx, gain)) = pending_gain {
+                        self.set_connection_gain(idx, gain);
+                    }
                 }
 
                 ui.separator();
-                ui.label("Add connection");
+                ui.horizontal(|ui| {
+                    ui.label("Add connection");
+                    let (rect, _) = ui.allocate_exact_size(egui::vec2(20.0, 18.0), egui::Sense::hover());
+                    let response = ui
+                        .interact(rect, keys_id, egui::Sense::click())
+                        .on_hover_text(
+                            "Keyboard entry: Tab and Shift+Tab move between the selectors, \
+                             arrow keys change the selection, Enter adds the connection",
+                        );
+                    if response.clicked() {
+                        response.request_focus();
+                    }
+                    let color = if keyboard {
+                        ui.visuals().selection.stroke.color
+                    } else {
+                        ui.visuals().weak_text_color()
+                    };
+                    ui.painter().text(
+                        rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        "\u{f11c}",
+                        egui::FontId::proportional(14.0),
+                        color,
+                    );
+                });
                 if self.workspace_manager.workspace.plugins.len() < 2 {
                     ui.label("Add at least two plugins.");
                     return;
                 }
 
                 let plugin_len = self.workspace_manager.workspace.plugins.len();
+                // Keep the selection on the same plugins when the list changes
+                let position = |id: Option<u64>| {
+                    id.and_then(|id| self.workspace_manager.workspace.plugins.iter().position(|p| p.id == id))
+                };
+                if let Some(idx) = position(self.connection_editor.from_plugin) {
+                    self.connection_editor.from_idx = idx;
+                }
+                if let Some(idx) = position(self.connection_editor.to_plugin) {
+                    self.connection_editor.to_idx = idx;
+                }
                 if self.connection_editor.from_idx >= plugin_len {
                     self.connection_editor.from_idx = 0;
                 }
                 if self.connection_editor.to_idx >= plugin_len {
                     self.connection_editor.to_idx = 0;
                 }
+                if tab_pressed {
+                    self.connection_editor.field = self.connection_editor.field.step(backwards);
+                }
+                match (self.connection_editor.field, step) {
+                    (ConnectionField::FromPlugin, Some(forward)) => {
+                        self.connection_editor.from_idx =
+                            step_index(self.connection_editor.from_idx, plugin_len, forward);
+                    }
+                    (ConnectionField::ToPlugin, Some(forward)) => {
+                        let mut to_idx = step_index(self.connection_editor.to_idx, plugin_len, forward);
+                        if to_idx == self.connection_editor.from_idx {
+                            to_idx = step_index(to_idx, plugin_len, forward);
+                        }
+                        self.connection_editor.to_idx = to_idx;
+                    }
+                    _ => {}
+                }
                 if self.connection_editor.from_idx == self.connection_editor.to_idx && plugin_len > 1 {
                     self.connection_editor.to_idx = (self.connection_editor.from_idx + 1) % plugin_len;
                 }
@@ -148,10 +284,42 @@ impl GuiApp {
                         self.connection_editor.to_port = default_port;
                     }
                 }
+                if let Some(forward) = step {
+                    let step_in = |options: &[String], current: &str| -> Option<String> {
+                        let pos = options.iter().position(|option| option == current).unwrap_or(0);
+                        options.get(step_index(pos, options.len(), forward)).cloned()
+                    };
+                    let editor = &mut self.connection_editor;
+                    match editor.field {
+                        ConnectionField::FromPort => {
+                            if let Some(port) = step_in(&from_ports, &editor.from_port) {
+                                editor.from_port = port;
+                            }
+                        }
+                        ConnectionField::ToPort => {
+                            if let Some(port) = step_in(&display_to_ports, &editor.to_port) {
+                                editor.to_port = port;
+                            }
+                        }
+                        ConnectionField::Kind => {
+                            if let Some(kind) = step_in(&editor.kind_options, &editor.kind) {
+                                editor.kind = kind;
+                            }
+                        }
+                        ConnectionField::FromPlugin | ConnectionField::ToPlugin => {}
+                    }
+                }
+                let active_field = keyboard.then_some(self.connection_editor.field);
+                let highlight = |ui: &egui::Ui, response: &egui::Response, field: ConnectionField| {
+                    if active_field == Some(field) {
+                        ui.painter()
+                            .rect_stroke(response.rect.expand(2.0), 3.0, ui.visuals().selection.stroke);
+                    }
+                };
 
                 ui.horizontal(|ui| {
                     ui.label("From");
-                    egui::ComboBox::from_id_source("conn_from_plugin")
+                    let response = egui::ComboBox::from_id_source("conn_from_plugin")
                         .selected_text({
                             let name = name_by_kind
                                 .get(&from_kind)
@@ -168,9 +336,11 @@ impl GuiApp {
                                 let label = format!("#{} {}", plugin.id, name);
                                 ui.selectable_value(&mut self.connection_editor.from_idx, idx, label);
                             }
-                        });
+                        })
+                        .response;
+                    highlight(ui, &response, ConnectionField::FromPlugin);
                     ui.label("Port");
-                    egui::ComboBox::from_id_source("conn_from_port")
+                    let response = egui::ComboBox::from_id_source("conn_from_port")
                         .selected_text(self.connection_editor.from_port.clone())
                         .show_ui(ui, |ui| {
                             for port in &from_ports {
@@ -180,12 +350,14 @@ impl GuiApp {
                                     port,
                                 );
                             }
-                        });
+                        })
+                        .response;
+                    highlight(ui, &response, ConnectionField::FromPort);
                 });
 
                 ui.horizontal(|ui| {
                     ui.label("To");
-                    egui::ComboBox::from_id_source("conn_to_plugin")
+                    let response = egui::ComboBox::from_id_source("conn_to_plugin")
                         .selected_text({
                             let name = name_by_kind
                                 .get(&to_kind)
@@ -206,9 +378,11 @@ impl GuiApp {
                                     ui.selectable_value(&mut self.connection_editor.to_idx, idx, label);
                                 }
                             }
-                        });
+                        })
+                        .response;
+                    highlight(ui, &response, ConnectionField::ToPlugin);
                     ui.label("Port");
-                    egui::ComboBox::from_id_source("conn_to_port")
+                    let response = egui::ComboBox::from_id_source("conn_to_port")
                         .selected_text(self.connection_editor.to_port.clone())
                         .show_ui(ui, |ui| {
                     for port in &display_to_ports {
@@ -218,12 +392,14 @@ impl GuiApp {
                                     port,
                                 );
                             }
-                        });
+                        })
+                        .response;
+                    highlight(ui, &response, ConnectionField::ToPort);
                 });
 
                 ui.horizontal(|ui| {
                     ui.label("Kind");
-                    egui::ComboBox::from_id_source("conn_kind")
+                    let response = egui::ComboBox::from_id_source("conn_kind")
                         .selected_text(Self::display_connection_kind(&self.connection_editor.kind))
                         .show_ui(ui, |ui| {
                             for kind in &self.connection_editor.kind_options {
@@ -233,7 +409,11 @@ impl GuiApp {
                                     Self::display_connection_kind(kind),
                                 );
                             }
-                        });
+                        })
+                        .response;
+                    highlight(ui, &response, ConnectionField::Kind);
+                    ui.label("Gain");
+                    ui.add(egui::DragValue::new(&mut self.connection_editor.gain).speed(0.01));
                     if let Some(connection) = exact_connection.clone() {
                         if ui
                             .add_sized([160.0, 28.0], egui::Button::new("Remove connection"))
@@ -245,13 +425,17 @@ impl GuiApp {
                     if ui
                         .add_enabled(!has_duplicate, egui::Button::new("Add connection"))
                         .clicked()
+                        || (enter_pressed && !has_duplicate)
                     {
                         self.add_connection();
                     }
                 });
+                let plugins = &self.workspace_manager.workspace.plugins;
+                self.connection_editor.from_plugin = plugins.get(self.connection_editor.from_idx).map(|p| p.id);
+                self.connection_editor.to_plugin = plugins.get(self.connection_editor.to_idx).map(|p| p.id);
             });
         if let Some(response) = response {
-            self.window_rects.push(response.response.rect);
+            self.track_window("ManageConnections", response.response.rect);
             if !self.confirm_dialog.open
                 && (response.response.clicked() || response.response.dragged())
             {
@@ -259,6 +443,7 @@ impl GuiApp {
             }
             if self.pending_window_focus == Some(WindowFocus::ManageConnections) {
                 ctx.move_to_top(response.response.layer_id);
+                ctx.memory_mut(|m| m.request_focus(keys_id));
                 self.pending_window_focus = None;
             }
         }
@@ -266,6 +451,132 @@ impl GuiApp {
         self.windows.manage_connections_open = open;
     }
 
+    /// Scheduling delay per connection, refreshed from the runtime about
+    /// once a second while the window is open. The runtime only times
+    /// transfers while the window asks it to.
+    pub(crate) fn render_connection_latency_window(&mut self, ctx: &egui::Context) {
+        if !self.windows.connection_latency_open {
+            if self.state_sync.last_connection_latency_query.take().is_some() {
+                let _ = self
+                    .state_sync
+                    .logic_tx
+                    .send(LogicMessage::TrackConnectionLatency(false));
+                self.state_sync.connection_latency.clear();
+            }
+            self.state_sync.connection_latency_rx = None;
+            return;
+        }
+        if self.state_sync.last_connection_latency_query.is_none() {
+            let _ = self
+                .state_sync
+                .logic_tx
+                .send(LogicMessage::TrackConnectionLatency(true));
+        }
+        if let Some(rx) = self.state_sync.connection_latency_rx.as_ref() {
+            match rx.try_recv() {
+                Ok(stats) => {
+                    self.state_sync.connection_latency = stats;
+                    self.state_sync.connection_latency_rx = None;
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => {}
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    self.state_sync.connection_latency_rx = None;
+                }
+            }
+        }
+        let query_due = self
+            .state_sync
+            .last_connection_latency_query
+            .map(|last| last.elapsed() >= CONNECTION_LATENCY_QUERY_INTERVAL)
+            .unwrap_or(true);
+        if self.state_sync.connection_latency_rx.is_none() && query_due {
+            let (tx, rx) = std::sync::mpsc::channel();
+            if self
+                .state_sync
+                .logic_tx
+                .send(LogicMessage::QueryConnectionLatency(tx))
+                .is_ok()
+            {
+                self.state_sync.connection_latency_rx = Some(rx);
+            }
+            self.state_sync.last_connection_latency_query = Some(Instant::now());
+        }
+        ctx.request_repaint_after(CONNECTION_LATENCY_QUERY_INTERVAL);
+
+        let port_label = |plugin_id: u64, port: &str| format!("{}.{port}", self.plugin_display_name(plugin_id));
+        let mut rows: Vec<(String, &str, &LatencyStats)> = self
+            .state_sync
+            .connection_latency
+            .iter()
+            .map(|(connection, stats)| {
+                let label = format!(
+                    "{} \u{2192} {}",
+                    port_label(connection.from_plugin, &connection.from_port),
+                    port_label(connection.to_plugin, &connection.to_port),
+                );
+                (label, Self::display_connection_kind(&connection.kind), stats)
+            })
+            .collect();
+        rows.sort_by(|a, b| a.0.cmp(&b.0));
+        let micros = |duration: Duration| format!("{:.1} \u{b5}s", duration.as_secs_f64() * 1e6);
+
+        let mut open = self.windows.connection_latency_open;
+        let window_size = egui::vec2(560.0, 200.0);
+        let default_pos = self.window_placement(ctx, "ConnectionLatency", window_size).min;
+        let response = egui::Window::new("Connection scheduling delay")
+            .open(&mut open)
+            .resizable(false)
+            .default_pos(default_pos)
+            .default_size(window_size)
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(
+                        "Time from a plugin writing its outputs to a connected plugin reading \
+                         them, over recent transfers. It comes from the plugins run in between, \
+                         not from the connection kind. Feedback connections include the rest \
+                         of the tick.",
+                    )
+                    .weak(),
+                );
+                ui.separator();
+                if rows.is_empty() {
+                    ui.label("No transfers measured yet.");
+                    return;
+                }
+                egui::Grid::new("connection_latency_grid")
+                    .num_columns(6)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in ["Connection", "Kind", "Transfers", "Mean", "p99", "Max"] {
+                            ui.label(RichText::new(header).strong());
+                        }
+                        ui.end_row();
+                        for (label, kind, stats) in &rows {
+                            ui.label(label);
+                            ui.label(*kind);
+                            ui.label(stats.samples.to_string());
+                            ui.label(micros(stats.mean));
+                            ui.label(micros(stats.p99));
+                            ui.label(micros(stats.max));
+                            ui.end_row();
+                        }
+                    });
+            });
+        if let Some(response) = response {
+            self.track_window("ConnectionLatency", response.response.rect);
+            if !self.confirm_dialog.open
+                && (response.response.clicked() || response.response.dragged())
+            {
+                ctx.move_to_top(response.response.layer_id);
+            }
+            if self.pending_window_focus == Some(WindowFocus::ConnectionLatency) {
+                ctx.move_to_top(response.response.layer_id);
+                self.pending_window_focus = None;
+            }
+        }
+        self.windows.connection_latency_open = open;
+    }
+
     pub(crate) fn render_connection_editor(&mut self, ctx: &egui::Context) {
         if !self.connection_editor.open {
             return;
@@ -273,7 +584,11 @@ impl GuiApp {
 
         let mut open = self.connection_editor.open;
         let window_size = egui::vec2(520.0, 360.0);
-        let default_pos = Self::center_window(ctx, window_size);
+        let layout_key = match self.connection_editor.mode {
+            ConnectionEditMode::Add => "ConnectionEditorAdd",
+            ConnectionEditMode::Remove => "ConnectionEditorRemove",
+        };
+        let default_pos = self.window_placement(ctx, layout_key, window_size).min;
         let Some(current_id) = self.connection_editor.plugin_id else {
             self.connection_highlight_plugin_id = None;
             self.connection_editor.open = false;
@@ -576,14 +891,32 @@ impl GuiApp {
                                                         .clone(),
                                                 )
                                                 .show_ui(ui, |ui| {
+                                                    let from_port = from_ports
+                                                        .get(self.connection_editor.from_port_idx)
+                                                        .map(String::as_str)
+                                                        .unwrap_or_default();
                                                     for (idx, port) in
                                                         display_to_ports.iter().enumerate()
                                                     {
-                                                        ui.selectable_value(
-                                                            &mut self.connection_editor.to_port_idx,
-                                                            idx,
+                                                        // Gray out inputs that would reject the connection
+                                                        let check = rtsyn_core::connection::can_add_connection(
+                                                            &self.workspace_manager.workspace,
+                                                            from_plugin,
+                                                            from_port,
+                                                            to_plugin,
                                                             port,
                                                         );
+                                                        let selected = self.connection_editor.to_port_idx == idx;
+                                                        let response = ui.add_enabled(
+                                                            check.is_ok() || selected,
+                                                            egui::SelectableLabel::new(selected, port),
+                                                        );
+                                                        if let Err(err) = &check {
+                                                            response.clone().on_hover_text(Self::connection_rule_message(err));
+                                                        }
+                                                        if response.clicked() {
+                                                            self.connection_editor.to_port_idx = idx;
+                                                        }
                                                     }
                                                 });
                                         }
@@ -604,6 +937,14 @@ impl GuiApp {
                                                 }
                                             });
                                     });
+                                    ui.horizontal(|ui| {
+                                        ui.label("Gain");
+                                        ui.add(
+                                            egui::DragValue::new(&mut self.connection_editor.gain)
+                                                .speed(0.01),
+                                        )
+                                        .on_hover_text("Source output is multiplied by this factor");
+                                    });
                                     if missing_ports {
                                         ui.label(
                                             RichText::new("Add inputs/outputs to connect.")
@@ -625,15 +966,30 @@ impl GuiApp {
                                             },
                                         );
                                         let has_duplicate = exact_idx.is_some();
+                                        let check = rtsyn_core::connection::can_add_connection(
+                                            &self.workspace_manager.workspace,
+                                            from_plugin,
+                                            &from_port,
+                                            to_plugin,
+                                            &to_port,
+                                        );
                                         ui.horizontal(|ui| {
-                                            ui.add_enabled_ui(!has_duplicate, |ui| {
-                                                if ui.button("Add connection").clicked() {
+                                            ui.add_enabled_ui(!has_duplicate && check.is_ok(), |ui| {
+                                                let button = ui.button("Add connection");
+                                                let button = match &check {
+                                                    Err(err) if !has_duplicate => {
+                                                        button.on_disabled_hover_text(Self::connection_rule_message(err))
+                                                    }
+                                                    _ => button,
+                                                };
+                                                if button.clicked() {
                                                     self.add_connection_direct(
                                                         from_plugin,
                                                         from_port.clone(),
                                                         to_plugin,
                                                         to_port.clone(),
                                                         self.connection_editor.kind.clone(),
+                                                        self.connection_editor.gain,
                                                     );
                                                 }
                                             });
@@ -698,7 +1054,7 @@ impl GuiApp {
                 });
             });
         if let Some(response) = response {
-            self.window_rects.push(response.response.rect);
+            self.track_window(layout_key, response.response.rect);
             if !self.confirm_dialog.open
                 && (response.response.clicked() || response.response.dragged())
             {
@@ -721,6 +1077,126 @@ impl GuiApp {
         }
     }
 
+    /// Read-only list of the connections into and out of one plugin.
+    /// Clicking a row highlights the plugin on the other end.
+    pub(crate) fn render_connection_inspector(&mut self, ctx: &egui::Context) {
+        let Some(plugin_id) = self.windows.connection_inspector_id else {
+            return;
+        };
+        if !self
+            .workspace_manager
+            .workspace
+            .plugins
+            .iter()
+            .any(|plugin| plugin.id == plugin_id)
+        {
+            self.close_connection_inspector();
+            return;
+        }
+
+        let (incoming, outgoing) =
+            rtsyn_core::connection::plugin_connections(&self.workspace_manager.workspace, plugin_id);
+        let incoming: Vec<ConnectionDefinition> = incoming.into_iter().cloned().collect();
+        let outgoing: Vec<ConnectionDefinition> = outgoing.into_iter().cloned().collect();
+        let plugin_name = self.plugin_display_name(plugin_id);
+        let mut names: HashMap<u64, String> = HashMap::new();
+        for conn in incoming.iter().chain(outgoing.iter()) {
+            for id in [conn.from_plugin, conn.to_plugin] {
+                if id != plugin_id && !names.contains_key(&id) {
+                    names.insert(id, self.plugin_display_name(id));
+                }
+            }
+        }
+        let details = |conn: &ConnectionDefinition| {
+            let mut text = Self::display_connection_kind(&conn.kind).to_string();
+            if conn.gain != 1.0 {
+                text.push_str(&format!(", gain {}", conn.gain));
+            }
+            text
+        };
+
+        let mut open = true;
+        let mut highlight = self.connection_highlight_plugin_id;
+        let window_size = egui::vec2(420.0, 320.0);
+        let default_pos = self.window_placement(ctx, "ConnectionInspector", window_size).min;
+        let response = egui::Window::new("Connection inspector")
+            .open(&mut open)
+            .resizable(false)
+            .default_pos(default_pos)
+            .default_size(window_size)
+            .fixed_size(window_size)
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(format!("#{plugin_id} {plugin_name}"))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.separator();
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    let mut row = |ui: &mut egui::Ui, other_id: u64, text: String, conn: &ConnectionDefinition| {
+                        let response = ui
+                            .selectable_label(highlight == Some(other_id), text)
+                            .on_hover_text(details(conn));
+                        if response.clicked() {
+                            highlight = if highlight == Some(other_id) {
+                                None
+                            } else {
+                                Some(other_id)
+                            };
+                        }
+                    };
+                    ui.label(RichText::new(format!("Incoming ({})", incoming.len())).strong());
+                    if incoming.is_empty() {
+                        ui.label("No incoming connections.");
+                    }
+                    for conn in &incoming {
+                        let text = format!(
+                            "#{} {}:{} -> {}",
+                            conn.from_plugin, names[&conn.from_plugin], conn.from_port, conn.to_port
+                        );
+                        row(ui, conn.from_plugin, text, conn);
+                    }
+                    ui.add_space(8.0);
+                    ui.label(RichText::new(format!("Outgoing ({})", outgoing.len())).strong());
+                    if outgoing.is_empty() {
+                        ui.label("No outgoing connections.");
+                    }
+                    for conn in &outgoing {
+                        let text = format!(
+                            "{} -> #{} {}:{}",
+                            conn.from_port, conn.to_plugin, names[&conn.to_plugin], conn.to_port
+                        );
+                        row(ui, conn.to_plugin, text, conn);
+                    }
+                });
+            });
+        self.connection_highlight_plugin_id = highlight;
+
+        if let Some(response) = response {
+            self.track_window("ConnectionInspector", response.response.rect);
+            if !self.confirm_dialog.open
+                && (response.response.clicked() || response.response.dragged())
+            {
+                ctx.move_to_top(response.response.layer_id);
+            }
+            if self.pending_window_focus == Some(WindowFocus::ConnectionInspector) {
+                ctx.move_to_top(response.response.layer_id);
+                self.pending_window_focus = None;
+            }
+        }
+
+        if !open {
+            self.close_connection_inspector();
+        }
+    }
+
+    fn close_connection_inspector(&mut self) {
+        self.windows.connection_inspector_id = None;
+        if !self.connection_editor.open {
+            self.connection_highlight_plugin_id = None;
+        }
+    }
+
     pub(crate) fn render_connection_view(&mut self, ctx: &egui::Context, panel_rect: egui::Rect) {
         if !self.connections_view_enabled {
             self.connection_context_menu = None;
@@ -764,6 +1240,18 @@ impl GuiApp {
             egui::Color32::from_rgba_premultiplied(color.r(), color.g(), color.b(), alpha)
         };
 
+        let animate = self.animate_connections;
+        let time = ctx.input(|i| i.time);
+        let mut animating = false;
+        let draw_flow = |path: &[egui::Pos2], painter: &egui::Painter, intensity: f32, stroke: f32| {
+            let speed = FLOW_SPEED.start() + (FLOW_SPEED.end() - FLOW_SPEED.start()) * f64::from(intensity);
+            let phase = ((time * speed) % f64::from(FLOW_DASH + FLOW_GAP)) as f32;
+            let color = egui::Color32::from_white_alpha((90.0 + 140.0 * intensity) as u8);
+            for dash in connection_routing::dashes(path, FLOW_DASH, FLOW_GAP, phase) {
+                painter.line_segment(dash, (stroke, color));
+            }
+        };
+
         let pointer_pos = ctx.input(|i| i.pointer.hover_pos());
         let pointer_over_plugin = pointer_pos
             .map(|pos| self.plugin_rects.values().any(|rect| rect.contains(pos)))
@@ -798,6 +1286,15 @@ impl GuiApp {
 
             let start = from_rect.center();
             let end = to_rect.center();
+            let obstacles: Vec<egui::Rect> = if self.route_connections {
+                self.plugin_rects
+                    .iter()
+                    .filter(|(id, _)| **id != from_id && **id != to_id)
+                    .map(|(_, rect)| *rect)
+                    .collect()
+            } else {
+                Vec::new()
+            };
             let dir = (end - start).normalized();
             let perp = egui::vec2(-dir.y, dir.x);
             let offset = if reverse_ports.is_some() {
@@ -819,17 +1316,19 @@ impl GuiApp {
                 (out_color, in_color, 2.0)
             };
 
-            let draw_line = |start: egui::Pos2,
-                             end: egui::Pos2,
+            let draw_line = |path: &[egui::Pos2],
                              painter: &egui::Painter,
                              out_line: egui::Color32,
                              in_line: egui::Color32,
                              stroke: f32| {
-                let mid = egui::pos2((start.x + end.x) * 0.5, (start.y + end.y) * 0.5);
-                painter.line_segment([start, mid], (stroke, out_line));
-                painter.line_segment([mid, end], (stroke, in_line));
+                let (mid, dir, segment) = connection_routing::midpoint(path);
+                let mut out_half = path[..=segment].to_vec();
+                out_half.push(mid);
+                let mut in_half = vec![mid];
+                in_half.extend_from_slice(&path[segment + 1..]);
+                painter.add(egui::Shape::line(out_half, (stroke, out_line)));
+                painter.add(egui::Shape::line(in_half, (stroke, in_line)));
 
-                let dir = (end - start).normalized();
                 let arrow_len = 8.0;
                 let arrow_width = 5.0;
                 let tip = mid + dir * arrow_len;
@@ -843,24 +1342,45 @@ impl GuiApp {
                 mid
             };
 
+            let route = |start: egui::Pos2, end: egui::Pos2| {
+                if self.route_connections {
+                    connection_routing::route(start, end, &obstacles)
+                } else {
+                    vec![start, end]
+                }
+            };
+            let path_primary = route(start + offset, end + offset);
+            let path_reverse = route(end - offset, start - offset);
+            let distance_to_path = |pointer: egui::Pos2, path: &[egui::Pos2]| {
+                path.windows(2)
+                    .map(|w| distance_to_segment(pointer, w[0], w[1]))
+                    .fold(f32::INFINITY, f32::min)
+            };
+
             let mid_primary = draw_line(
-                start + offset,
-                end + offset,
+                &path_primary,
                 &painter,
                 out_line,
                 in_line,
                 stroke,
             );
+            if let Some(intensity) = animate.then(|| self.connection_flow(from_id, &unique_outputs)).flatten() {
+                draw_flow(&path_primary, &painter, intensity, stroke);
+                animating = true;
+            }
             let (mid_reverse, reverse_outputs, reverse_inputs, reverse_index) =
                 if let Some((rev_out, rev_in, rev_indices)) = reverse_ports {
                     let mid = draw_line(
-                        end - offset,
-                        start - offset,
+                        &path_reverse,
                         &painter,
                         out_line,
                         in_line,
                         stroke,
                     );
+                    if let Some(intensity) = animate.then(|| self.connection_flow(to_id, rev_out)).flatten() {
+                        draw_flow(&path_reverse, &painter, intensity, stroke);
+                        animating = true;
+                    }
                     let rev_index = rev_indices.iter().min().copied().unwrap_or(0);
                     (
                         Some(mid),
@@ -880,7 +1400,7 @@ impl GuiApp {
                     continue;
                 }
                 let hover_pad = 10.0;
-                let dist_primary = distance_to_segment(pointer, start + offset, end + offset);
+                let dist_primary = distance_to_path(pointer, &path_primary);
                 if dist_primary <= hover_pad {
                     let replace = best_hover
                         .as_ref()
@@ -899,7 +1419,7 @@ impl GuiApp {
                     }
                 }
                 if let Some(mid) = mid_reverse {
-                    let dist_reverse = distance_to_segment(pointer, end - offset, start - offset);
+                    let dist_reverse = distance_to_path(pointer, &path_reverse);
                     if dist_reverse <= hover_pad {
                         let replace = best_hover
                             .as_ref()
@@ -920,6 +1440,9 @@ impl GuiApp {
                 }
             }
         }
+        if animating {
+            ctx.request_repaint();
+        }
         if self.confirm_dialog.open {
             best_hover = None;
         }
@@ -1083,7 +1606,7 @@ impl GuiApp {
                             self.workspace_manager.workspace.connections.retain(|conn| {
                                 !remove_direct.iter().any(|remove| matches(conn, remove))
                             });
-                            self.workspace_manager.workspace_dirty = true;
+                            self.mark_workspace_dirty();
                             self.enforce_connection_dependent();
                         }
                         close_menu = true;
@@ -0,0 +1,153 @@
+use super::*;
+use egui_plot::{Bar, BarChart, Plot};
+
+struct HistogramView {
+    plugin_id: u64,
+    title: String,
+    range_min: f64,
+    range_max: f64,
+    counts: Vec<u64>,
+}
+
+impl HistogramView {
+    fn render(&self, ui: &mut egui::Ui) -> bool {
+        let total: u64 = self.counts.iter().sum();
+        let mut reset = false;
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "Samples: {total}   Range: [{}, {}]",
+                format_f64_6(self.range_min),
+                format_f64_6(self.range_max)
+            ));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                reset = ui.button("Reset").clicked();
+            });
+        });
+        ui.separator();
+
+        let bins = self.counts.len().max(1);
+        let width = (self.range_max - self.range_min) / bins as f64;
+        let bars: Vec<Bar> = self
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(idx, count)| {
+                let center = self.range_min + (idx as f64 + 0.5) * width;
+                Bar::new(center, *count as f64).width(width)
+            })
+            .collect();
+        Plot::new(("histogram_plot", self.plugin_id))
+            .allow_drag(false)
+            .allow_scroll(false)
+            .allow_zoom(false)
+            .include_y(0.0)
+            .show(ui, |plot_ui| plot_ui.bar_chart(BarChart::new(bars)));
+        reset
+    }
+}
+
+impl GuiApp {
+    /// One window per running histogram plugin, fed by the bin counts the
+    /// runtime publishes. Closing a window stops its plugin.
+    pub(crate) fn render_histogram_windows(&mut self, ctx: &egui::Context) {
+        let views: Vec<HistogramView> = self
+            .workspace_manager
+            .workspace
+            .plugins
+            .iter()
+            .filter(|plugin| plugin.kind == "histogram" && plugin.running)
+            .map(|plugin| {
+                let config_f64 = |key: &str, default: f64| {
+                    rtsyn_core::config::f64_or(&plugin.config, key, default)
+                };
+                let counts = self
+                    .state_sync
+                    .internal_variable_values
+                    .get(&(plugin.id, "counts".to_string()))
+                    .and_then(|v| v.as_array())
+                    .map(|values| values.iter().filter_map(|v| v.as_u64()).collect())
+                    .unwrap_or_default();
+                let (range_min, range_max) = {
+                    let (a, b) = (config_f64("range_min", -1.0), config_f64("range_max", 1.0));
+                    (a.min(b), a.max(b))
+                };
+                HistogramView {
+                    plugin_id: plugin.id,
+                    title: format!(
+                        "Histogram #{} {}",
+                        plugin.id,
+                        self.plugin_display_name(plugin.id)
+                    ),
+                    range_min,
+                    range_max,
+                    counts,
+                }
+            })
+            .collect();
+
+        let app_closing = self.close_confirmed || ctx.input(|i| i.viewport().close_requested());
+        let mut closed = Vec::new();
+        let mut reset = Vec::new();
+        for view in views {
+            let viewport_id = egui::ViewportId::from_hash_of(("histogram", view.plugin_id));
+            let layout_key = WindowLayout::key("Histogram", view.plugin_id);
+            let mut builder = egui::ViewportBuilder::default()
+                .with_title(view.title.clone())
+                .with_inner_size([640.0, 400.0]);
+            if let Some(rect) = self.window_layout.rect(&layout_key) {
+                builder = builder.with_position(rect.min).with_inner_size(rect.size());
+            }
+
+            ctx.show_viewport_immediate(viewport_id, builder, |ctx, class| {
+                if class == egui::ViewportClass::Embedded {
+                    let mut open = true;
+                    egui::Window::new(view.title.clone())
+                        .id(egui::Id::new(("histogram_window", view.plugin_id)))
+                        .open(&mut open)
+                        .resizable(true)
+                        .default_size(egui::vec2(640.0, 400.0))
+                        .show(ctx, |ui| {
+                            if view.render(ui) {
+                                reset.push(view.plugin_id);
+                            }
+                        });
+                    if !open {
+                        closed.push(view.plugin_id);
+                    }
+                    return;
+                }
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    if view.render(ui) {
+                        reset.push(view.plugin_id);
+                    }
+                });
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    closed.push(view.plugin_id);
+                }
+            });
+            if app_closing || closed.last() == Some(&view.plugin_id) {
+                self.remember_viewport(ctx, &layout_key, viewport_id);
+            }
+        }
+
+        for id in reset {
+            self.restart_plugin(id);
+        }
+        for id in closed {
+            if let Some(plugin) = self
+                .workspace_manager
+                .workspace
+                .plugins
+                .iter_mut()
+                .find(|p| p.id == id)
+            {
+                plugin.running = false;
+                let _ = self
+                    .state_sync
+                    .logic_tx
+                    .send(LogicMessage::SetPluginRunning(id, false));
+                self.mark_workspace_dirty();
+            }
+        }
+    }
+}
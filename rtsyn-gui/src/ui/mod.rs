@@ -8,12 +8,20 @@ use std::sync::mpsc;
 
 use crate::state::*;
 use crate::utils::{distance_to_segment, format_f64_6, truncate_f64};
+use crate::window_layout::WindowLayout;
 use crate::{WorkspaceSettingsDraft, GuiApp};
-use rtsyn_runtime::runtime::LogicMessage;
+use rtsyn_runtime::runtime::{LogicMessage, PluginRunState};
 use std::time::{Duration, Instant};
-use workspace::{prune_extendable_inputs_plugin_connections, ConnectionDefinition};
+use workspace::{
+    prune_extendable_inputs_plugin_connections, swap_extendable_inputs, ConnectionDefinition,
+};
 
+mod canvas;
 mod connections;
+mod histograms;
+mod parameters;
 mod plotters;
 mod plugins;
+mod signals;
+mod stepping;
 mod workspaces;
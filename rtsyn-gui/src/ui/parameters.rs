@@ -0,0 +1,238 @@
+use super::*;
+use workspace::parameter_reference;
+
+enum ParameterAction {
+    Add(String),
+    Set(String, f64),
+    Remove(String),
+    Bind(u64, String, String),
+    Unbind(u64, String),
+}
+
+impl GuiApp {
+    /// Config fields of a plugin that can take a parameter: numbers and
+    /// existing references, without the card's own `_ui_*` settings.
+    fn bindable_fields(config: &Value) -> Vec<String> {
+        let Some(map) = config.as_object() else {
+            return Vec::new();
+        };
+        map.iter()
+            .filter(|(key, value)| {
+                !key.starts_with('_') && (value.is_number() || parameter_reference(value).is_some())
+            })
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    fn apply_parameter_action(&mut self, action: ParameterAction) {
+        let workspace = &mut self.workspace_manager.workspace;
+        match action {
+            ParameterAction::Add(name) => {
+                workspace.parameters.entry(name).or_insert(0.0);
+            }
+            ParameterAction::Set(name, value) => {
+                workspace.parameters.insert(name, value);
+            }
+            ParameterAction::Remove(name) => {
+                // Referencing fields keep the last value as a literal
+                let Some(value) = workspace.parameters.remove(&name) else {
+                    return;
+                };
+                for (plugin_id, key) in workspace.parameter_uses(&name) {
+                    if let Some(map) = workspace
+                        .plugins
+                        .iter_mut()
+                        .find(|p| p.id == plugin_id)
+                        .and_then(|p| p.config.as_object_mut())
+                    {
+                        map.insert(key, Value::from(value));
+                    }
+                }
+            }
+            ParameterAction::Bind(plugin_id, key, name) => {
+                if let Some(map) = workspace
+                    .plugins
+                    .iter_mut()
+                    .find(|p| p.id == plugin_id)
+                    .and_then(|p| p.config.as_object_mut())
+                {
+                    map.insert(key, Value::String(format!("${name}")));
+                }
+            }
+            ParameterAction::Unbind(plugin_id, key) => {
+                let Some(plugin) = workspace.plugins.iter_mut().find(|p| p.id == plugin_id) else {
+                    return;
+                };
+                let value = parameter_reference(&plugin.config[&key])
+                    .and_then(|name| workspace.parameters.get(name))
+                    .copied()
+                    .unwrap_or(0.0);
+                if let Some(map) = plugin.config.as_object_mut() {
+                    map.insert(key, Value::from(value));
+                }
+            }
+        }
+        self.mark_workspace_dirty();
+    }
+
+    pub(crate) fn render_parameters_window(&mut self, ctx: &egui::Context) {
+        if !self.windows.parameters_open {
+            return;
+        }
+
+        let locked = self.workspace_manager.workspace.settings.locked;
+        let parameters: Vec<(String, f64)> = self
+            .workspace_manager
+            .workspace
+            .parameters
+            .iter()
+            .map(|(name, value)| (name.clone(), *value))
+            .collect();
+        let plugin_names: Vec<(u64, String)> = self
+            .workspace_manager
+            .workspace
+            .plugins
+            .iter()
+            .map(|p| (p.id, format!("#{} {}", p.id, self.plugin_display_name(p.id))))
+            .collect();
+        let mut actions = Vec::new();
+        let mut open = self.windows.parameters_open;
+        let window_size = egui::vec2(460.0, 360.0);
+        let default_pos = self.window_placement(ctx, "Parameters", window_size).min;
+        let response = egui::Window::new("Parameters")
+            .open(&mut open)
+            .resizable(false)
+            .default_pos(default_pos)
+            .default_size(window_size)
+            .show(ctx, |ui| {
+                ui.set_enabled(!locked);
+                ui.label(
+                    RichText::new(
+                        "Shared values for plugin config fields. A field bound to a \
+                         parameter stores \"$name\" and follows the parameter's value.",
+                    )
+                    .weak(),
+                );
+                ui.separator();
+                egui::ScrollArea::vertical().max_height(160.0).show(ui, |ui| {
+                    if parameters.is_empty() {
+                        ui.label("No parameters.");
+                    }
+                    for (name, value) in &parameters {
+                        let uses = self.workspace_manager.workspace.parameter_uses(name);
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(format!("${name}")).monospace().strong());
+                            let mut edited = *value;
+                            if ui.add(egui::DragValue::new(&mut edited).speed(0.01)).changed() {
+                                actions.push(ParameterAction::Set(name.clone(), edited));
+                            }
+                            if ui.small_button("\u{f1f8}").on_hover_text("Remove parameter").clicked() {
+                                actions.push(ParameterAction::Remove(name.clone()));
+                            }
+                        });
+                        for (plugin_id, key) in uses {
+                            ui.horizontal(|ui| {
+                                ui.add_space(16.0);
+                                let plugin = plugin_names
+                                    .iter()
+                                    .find(|(id, _)| *id == plugin_id)
+                                    .map(|(_, label)| label.as_str())
+                                    .unwrap_or("");
+                                ui.label(RichText::new(format!("{plugin}: {key}")).weak());
+                                if ui.small_button("Unbind").clicked() {
+                                    actions.push(ParameterAction::Unbind(plugin_id, key.clone()));
+                                }
+                            });
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut self.windows.parameter_name);
+                    let name = self.windows.parameter_name.trim().trim_start_matches('$').to_string();
+                    let valid = !name.is_empty()
+                        && !name.chars().any(char::is_whitespace)
+                        && !parameters.iter().any(|(existing, _)| *existing == name);
+                    if ui.add_enabled(valid, egui::Button::new("Add parameter")).clicked() {
+                        actions.push(ParameterAction::Add(name));
+                        self.windows.parameter_name.clear();
+                    }
+                });
+
+                ui.separator();
+                ui.label(RichText::new("Bind a config field").strong());
+                let (bind_plugin, bind_key, bind_parameter) = &mut self.windows.parameter_binding;
+                let fields = bind_plugin
+                    .and_then(|id| self.workspace_manager.workspace.plugins.iter().find(|p| p.id == id))
+                    .map(|p| Self::bindable_fields(&p.config))
+                    .unwrap_or_default();
+                ui.horizontal(|ui| {
+                    let selected_plugin = plugin_names
+                        .iter()
+                        .find(|(id, _)| Some(*id) == *bind_plugin)
+                        .map(|(_, label)| label.clone())
+                        .unwrap_or_else(|| "Plugin".to_string());
+                    egui::ComboBox::from_id_source("parameter_bind_plugin")
+                        .selected_text(selected_plugin)
+                        .width(150.0)
+                        .show_ui(ui, |ui| {
+                            for (id, label) in &plugin_names {
+                                if ui.selectable_label(*bind_plugin == Some(*id), label).clicked() {
+                                    *bind_plugin = Some(*id);
+                                    bind_key.clear();
+                                }
+                            }
+                        });
+                    let selected_key = if bind_key.is_empty() { "Field" } else { bind_key.as_str() };
+                    egui::ComboBox::from_id_source("parameter_bind_key")
+                        .selected_text(selected_key.to_string())
+                        .width(110.0)
+                        .show_ui(ui, |ui| {
+                            for key in &fields {
+                                if ui.selectable_label(bind_key == key, key).clicked() {
+                                    *bind_key = key.clone();
+                                }
+                            }
+                        });
+                    let selected_parameter = if bind_parameter.is_empty() {
+                        "Parameter".to_string()
+                    } else {
+                        format!("${bind_parameter}")
+                    };
+                    egui::ComboBox::from_id_source("parameter_bind_parameter")
+                        .selected_text(selected_parameter)
+                        .width(90.0)
+                        .show_ui(ui, |ui| {
+                            for (name, _) in &parameters {
+                                if ui.selectable_label(bind_parameter == name, format!("${name}")).clicked() {
+                                    *bind_parameter = name.clone();
+                                }
+                            }
+                        });
+                    let ready = bind_plugin.is_some()
+                        && fields.contains(bind_key)
+                        && parameters.iter().any(|(name, _)| name == bind_parameter);
+                    if ui.add_enabled(ready, egui::Button::new("Bind")).clicked() {
+                        if let Some(plugin_id) = *bind_plugin {
+                            actions.push(ParameterAction::Bind(
+                                plugin_id,
+                                bind_key.clone(),
+                                bind_parameter.clone(),
+                            ));
+                        }
+                    }
+                });
+            });
+        if let Some(response) = response {
+            self.track_window("Parameters", response.response.rect);
+            if !self.confirm_dialog.open
+                && (response.response.clicked() || response.response.dragged())
+            {
+                ctx.move_to_top(response.response.layer_id);
+            }
+        }
+        for action in actions {
+            self.apply_parameter_action(action);
+        }
+        self.windows.parameters_open = open;
+    }
+}
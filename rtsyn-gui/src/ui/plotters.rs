@@ -1,10 +1,14 @@
 use super::*;
+use crate::plotter::{MarkerStyle, PlotMode, PlotPalette, SeriesStyle};
+use crate::plotter_manager::render_linked;
+use rtsyn_core::plugin::with_unit;
 use std::time::Duration;
 
 impl GuiApp {
     pub(crate) fn render_plotter_windows(&mut self, ctx: &egui::Context) {
         let mut closed = Vec::new();
         let mut capture_requested = Vec::new();
+        let mut copy_requested = Vec::new();
         let name_by_id: HashMap<u64, String> = self
             .workspace_manager.workspace
             .plugins
@@ -22,6 +26,8 @@ impl GuiApp {
             })
             .collect();
 
+        let app_closing = self.close_confirmed || ctx.input(|i| i.viewport().close_requested());
+
         for plugin_id in plotter_ids {
             let display_name = name_by_id
                 .get(&plugin_id)
@@ -29,10 +35,15 @@ impl GuiApp {
                 .unwrap_or_else(|| "plotter".to_string());
             let title = format!("Plotter #{} {}", plugin_id, display_name);
             let viewport_id = egui::ViewportId::from_hash_of(("plotter", plugin_id));
-            let builder = egui::ViewportBuilder::default()
+            let layout_key = WindowLayout::key("Plotter", plugin_id);
+            let saved_rect = self.window_layout.rect(&layout_key);
+            let mut builder = egui::ViewportBuilder::default()
                 .with_title(title.clone())
                 .with_inner_size([900.0, 520.0])
                 .with_close_button(false);
+            if let Some(rect) = saved_rect {
+                builder = builder.with_position(rect.min).with_inner_size(rect.size());
+            }
 
             let plotter = self
             .plotter_manager.plotters
@@ -40,6 +51,7 @@ impl GuiApp {
                 .cloned()
                 .expect("plotter exists");
             let plotter_for_viewport = plotter.clone();
+            let linked_cursor = self.plotter_manager.linked_cursor.clone();
             let time_label = self.state_sync.logic_time_label.clone();
 
             ctx.show_viewport_deferred(viewport_id, builder, move |ctx, class| {
@@ -52,14 +64,20 @@ impl GuiApp {
                         ui.horizontal(|ui| {
                             let label = format!("Inputs: {}", plotter.input_count);
                             ui.label(&label);
+                            ui.checkbox(&mut plotter.link_cursor, "Link cursors").on_hover_text(
+                                "Follow the cursor of other plotters with linked cursors",
+                            );
                             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                                 if ui.button("Capture").clicked() {
                                     ctx.data_mut(|d| d.insert_temp(egui::Id::new(("capture_request", plugin_id)), true));
                                 }
+                                if ui.button("Copy").on_hover_text("Copy the plot to the clipboard").clicked() {
+                                    ctx.data_mut(|d| d.insert_temp(egui::Id::new(("copy_request", plugin_id)), true));
+                                }
                             });
                         });
                         ui.separator();
-                        plotter.render(ui, "", &time_label);
+                        render_linked(&mut plotter, plugin_id, &linked_cursor, ui, "", &time_label);
                         let refresh_hz = plotter.refresh_hz.max(1.0);
                         ctx.request_repaint_after(Duration::from_secs_f64(1.0 / refresh_hz));
                     }
@@ -71,19 +89,40 @@ impl GuiApp {
                 capture_requested.push(plugin_id);
                 ctx.data_mut(|d| d.remove::<bool>(egui::Id::new(("capture_request", plugin_id))));
             }
+            if ctx.data(|d| d.get_temp::<bool>(egui::Id::new(("copy_request", plugin_id))).unwrap_or(false)) {
+                copy_requested.push(plugin_id);
+                ctx.data_mut(|d| d.remove::<bool>(egui::Id::new(("copy_request", plugin_id))));
+            }
 
             if ctx.embed_viewports() {
+                let placement = self.window_placement(ctx, &layout_key, egui::vec2(900.0, 520.0));
+                let mut content_size = placement.size();
                 let response = egui::Window::new(title)
                     .resizable(true)
-                    .default_size(egui::vec2(900.0, 520.0))
+                    .default_pos(placement.min)
+                    .default_size(placement.size())
                     .show(ctx, |ui| {
+                        content_size = ui.max_rect().size();
                         if let Ok(mut plotter) = plotter.lock() {
                             let label = format!("Inputs: {}", plotter.input_count);
-                            plotter.render(ui, &label, &self.state_sync.logic_time_label);
+                            ui.checkbox(&mut plotter.link_cursor, "Link cursors");
+                            render_linked(
+                                &mut plotter,
+                                plugin_id,
+                                &self.plotter_manager.linked_cursor,
+                                ui,
+                                &label,
+                                &self.state_sync.logic_time_label,
+                            );
                         }
                     });
                 if let Some(response) = response {
                     self.window_rects.push(response.response.rect);
+                    // Store the content size, which is what `default_size` restores
+                    self.window_layout.remember(
+                        &layout_key,
+                        egui::Rect::from_min_size(response.response.rect.min, content_size),
+                    );
                     if !self.confirm_dialog.open
                         && (response.response.clicked() || response.response.dragged())
                     {
@@ -93,6 +132,9 @@ impl GuiApp {
             }
 
             let close_requested = ctx.input_for(viewport_id, |i| i.viewport().close_requested());
+            if close_requested || app_closing {
+                self.remember_viewport(ctx, &layout_key, viewport_id);
+            }
             if close_requested {
                 closed.push(plugin_id);
             }
@@ -118,6 +160,9 @@ impl GuiApp {
         for plugin_id in capture_requested {
             self.open_plotter_preview(plugin_id);
         }
+        for plugin_id in copy_requested {
+            self.copy_plotter_to_clipboard(plugin_id);
+        }
     }
 
     fn open_plotter_preview(&mut self, plugin_id: u64) {
@@ -125,12 +170,14 @@ impl GuiApp {
         self.plotter_preview.open = true;
         
         // Load existing settings or create defaults
-        if let Some((show_axes, show_legend, show_grid, series_names, colors, title, dark_theme, x_axis, y_axis, high_quality, export_svg)) = self.plotter_manager.plotter_preview_settings.get(&plugin_id).cloned() {
+        if let Some((show_axes, show_legend, show_grid, series_names, color_overrides, series_styles, title, dark_theme, x_axis, y_axis, high_quality, export_svg, palette)) = self.plotter_manager.plotter_preview_settings.get(&plugin_id).cloned() {
             self.plotter_preview.show_axes = show_axes;
             self.plotter_preview.show_legend = show_legend;
             self.plotter_preview.show_grid = show_grid;
             self.plotter_preview.series_names = series_names;
-            self.plotter_preview.colors = colors;
+            self.plotter_preview.palette = palette;
+            self.plotter_preview.color_overrides = color_overrides;
+            self.plotter_preview.series_styles = series_styles;
             self.plotter_preview.title = title;
             self.plotter_preview.dark_theme = dark_theme;
             self.plotter_preview.x_axis_name = x_axis;
@@ -146,7 +193,12 @@ impl GuiApp {
                     self.workspace_manager.workspace.plugins
                         .iter()
                         .find(|p| p.id == conn.from_plugin)
-                        .map(|p| self.plugin_display_name(p.id))
+                        .map(|p| {
+                            with_unit(
+                                &self.plugin_display_name(p.id),
+                                self.source_port_unit(conn.from_plugin, &conn.from_port),
+                            )
+                        })
                 })
                 .collect();
                 
@@ -158,7 +210,7 @@ impl GuiApp {
                     self.plotter_preview.title = String::new(); // Empty by default
                     self.plotter_preview.dark_theme = true;
                     self.plotter_preview.x_axis_name = self.state_sync.logic_time_label.clone();
-                    self.plotter_preview.y_axis_name = "value".to_string();
+                    self.plotter_preview.y_axis_name = plotter.value_axis_label();
                     self.plotter_preview.high_quality = false;
                     
                     self.plotter_preview.series_names = (0..plotter.input_count)
@@ -168,24 +220,20 @@ impl GuiApp {
                                 .unwrap_or_else(|| format!("Series {}", i + 1))
                         })
                         .collect();
-                    self.plotter_preview.colors = (0..plotter.input_count)
-                        .map(|i| {
-                            // Use the same palette as live plotter
-                            match i % 8 {
-                                0 => egui::Color32::from_rgb(86, 156, 214),
-                                1 => egui::Color32::from_rgb(220, 122, 95),
-                                2 => egui::Color32::from_rgb(181, 206, 168),
-                                3 => egui::Color32::from_rgb(220, 220, 170),
-                                4 => egui::Color32::from_rgb(197, 134, 192),
-                                5 => egui::Color32::from_rgb(78, 201, 176),
-                                6 => egui::Color32::from_rgb(156, 220, 254),
-                                _ => egui::Color32::from_rgb(255, 206, 84),
-                            }
-                        })
-                        .collect();
+                    self.plotter_preview.palette = PlotPalette::default();
+                    self.plotter_preview.color_overrides = vec![None; plotter.input_count];
+                    self.plotter_preview.series_styles = vec![SeriesStyle::default(); plotter.input_count];
                 }
             }
         }
+        if let Some(plotter) = self.plotter_manager.plotters.get(&plugin_id) {
+            if let Ok(plotter) = plotter.lock() {
+                self.plotter_preview.display_smoothing = plotter.display_smoothing;
+                self.plotter_preview.smoothed_readout = plotter.smoothed_readout;
+                self.plotter_preview.plot_mode = plotter.plot_mode;
+                (self.plotter_preview.xy_x, self.plotter_preview.xy_y) = plotter.xy_series;
+            }
+        }
     }
 
     pub(crate) fn render_plotter_preview_dialog(&mut self, ctx: &egui::Context) {
@@ -198,11 +246,16 @@ impl GuiApp {
         };
 
         let mut save_requested = false;
+        let mut copy_requested = false;
         
-        egui::Window::new("Plot Preview & Export")
+        let placement = self.window_placement(ctx, "PlotPreview", egui::vec2(600.0, 500.0));
+        let mut content_size = placement.size();
+        let response = egui::Window::new("Plot Preview & Export")
             .resizable(true)
-            .default_size(egui::vec2(600.0, 500.0))
+            .default_pos(placement.min)
+            .default_size(placement.size())
             .show(ctx, |ui| {
+                content_size = ui.max_rect().size();
                 ui.horizontal(|ui| {
                     ui.label("Title:");
                     ui.text_edit_singleline(&mut self.plotter_preview.title);
@@ -223,18 +276,98 @@ impl GuiApp {
                 });
 
                 ui.separator();
-                ui.label("Series customization:");
+                ui.horizontal(|ui| {
+                    ui.label("Series customization:");
+                    ui.add_space(8.0);
+                    ui.label("Palette:");
+                    egui::ComboBox::from_id_source("plot_preview_palette")
+                        .selected_text(self.plotter_preview.palette.label())
+                        .show_ui(ui, |ui| {
+                            for palette in PlotPalette::ALL {
+                                ui.selectable_value(&mut self.plotter_preview.palette, palette, palette.label());
+                            }
+                        })
+                        .response
+                        .on_hover_text("Colors of the series without a color of their own");
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Display smoothing:");
+                    ui.add(egui::Slider::new(&mut self.plotter_preview.display_smoothing, 0.0..=1.0).step_by(0.01))
+                        .on_hover_text("Low-pass on the drawn lines of this plot, 0 is off. The buffered samples and recordings stay raw");
+                    ui.add_enabled(
+                        self.plotter_preview.display_smoothing > 0.0,
+                        egui::Checkbox::new(&mut self.plotter_preview.smoothed_readout, "Smoothed readout"),
+                    )
+                    .on_hover_text("Cursor values of the smoothed lines instead of the raw samples");
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Plot mode:");
+                    egui::ComboBox::from_id_source("plot_preview_mode")
+                        .selected_text(self.plotter_preview.plot_mode.label())
+                        .show_ui(ui, |ui| {
+                            for mode in PlotMode::ALL {
+                                ui.selectable_value(&mut self.plotter_preview.plot_mode, mode, mode.label());
+                            }
+                        })
+                        .response
+                        .on_hover_text("X-Y draws one series against another over the window instead of against time");
+                    if self.plotter_preview.plot_mode == PlotMode::Xy {
+                        let names = &self.plotter_preview.series_names;
+                        for (label, index) in [
+                            ("X:", &mut self.plotter_preview.xy_x),
+                            ("Y:", &mut self.plotter_preview.xy_y),
+                        ] {
+                            ui.label(label);
+                            egui::ComboBox::from_id_source(("plot_preview_xy", label))
+                                .selected_text(names.get(*index).map(String::as_str).unwrap_or("-"))
+                                .show_ui(ui, |ui| {
+                                    for (i, name) in names.iter().enumerate() {
+                                        ui.selectable_value(index, i, name);
+                                    }
+                                });
+                        }
+                    }
+                });
 
+                let series_count = self.plotter_preview.series_names.len();
+                self.plotter_preview.series_styles.resize(series_count, SeriesStyle::default());
+                self.plotter_preview.color_overrides.resize(series_count, None);
+                let palette = self.plotter_preview.palette;
                 egui::ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
-                    for (i, (name, color)) in self.plotter_preview.series_names
+                    for (i, ((name, color_override), style)) in self.plotter_preview.series_names
                         .iter_mut()
-                        .zip(self.plotter_preview.colors.iter_mut())
+                        .zip(self.plotter_preview.color_overrides.iter_mut())
+                        .zip(self.plotter_preview.series_styles.iter_mut())
                         .enumerate()
                     {
                         ui.horizontal(|ui| {
                             ui.label(format!("Series {}:", i + 1));
                             ui.text_edit_singleline(name);
-                            ui.color_edit_button_srgba(color);
+                            let mut color = color_override.unwrap_or_else(|| palette.color(i));
+                            if ui.color_edit_button_srgba(&mut color).changed() {
+                                *color_override = Some(color);
+                            }
+                            if color_override.is_some()
+                                && ui
+                                    .small_button("\u{f0e2}")
+                                    .on_hover_text("Use the palette color")
+                                    .clicked()
+                            {
+                                *color_override = None;
+                            }
+                            ui.label("Width:");
+                            ui.add(
+                                egui::DragValue::new(&mut style.line_width)
+                                    .clamp_range(0.5..=8.0)
+                                    .speed(0.1),
+                            );
+                            egui::ComboBox::from_id_source(("series_marker", i))
+                                .selected_text(style.marker.label())
+                                .show_ui(ui, |ui| {
+                                    for marker in MarkerStyle::ALL {
+                                        ui.selectable_value(&mut style.marker, marker, marker.label());
+                                    }
+                                });
                         });
                     }
                 });
@@ -248,6 +381,27 @@ impl GuiApp {
                 
                 if let Some(plotter) = self.plotter_manager.plotters.get(&plugin_id) {
                     if let Ok(mut plotter) = plotter.lock() {
+                        // Smoothing is kept on the plotter, so its window shows it too
+                        plotter.display_smoothing = self.plotter_preview.display_smoothing;
+                        plotter.smoothed_readout = self.plotter_preview.smoothed_readout;
+                        let xy_series = (self.plotter_preview.xy_x, self.plotter_preview.xy_y);
+                        if plotter.plot_mode != self.plotter_preview.plot_mode || plotter.xy_series != xy_series {
+                            // Axis names follow the mode until the user edits them again
+                            let names = &self.plotter_preview.series_names;
+                            (self.plotter_preview.x_axis_name, self.plotter_preview.y_axis_name) =
+                                match self.plotter_preview.plot_mode {
+                                    PlotMode::Time => (
+                                        self.state_sync.logic_time_label.clone(),
+                                        plotter.value_axis_label(),
+                                    ),
+                                    PlotMode::Xy => (
+                                        names.get(xy_series.0).cloned().unwrap_or_default(),
+                                        names.get(xy_series.1).cloned().unwrap_or_default(),
+                                    ),
+                                };
+                            plotter.plot_mode = self.plotter_preview.plot_mode;
+                            plotter.xy_series = xy_series;
+                        }
                         ui.allocate_ui(preview_size, |ui| {
                             plotter.render_with_settings(
                                 ui, 
@@ -258,7 +412,8 @@ impl GuiApp {
                                 self.plotter_preview.show_grid,
                                 Some(&self.plotter_preview.title),
                                 Some(&self.plotter_preview.series_names),
-                                Some(&self.plotter_preview.colors),
+                                Some(&self.plotter_preview.palette.series_colors(&self.plotter_preview.color_overrides)),
+                                Some(&self.plotter_preview.series_styles),
                                 self.plotter_preview.dark_theme,
                                 Some(&self.plotter_preview.x_axis_name),
                                 Some(&self.plotter_preview.y_axis_name),
@@ -302,18 +457,47 @@ impl GuiApp {
                     }
                 });
 
+                ui.horizontal(|ui| {
+                    ui.label("Supersample:");
+                    let bitmap = !self.plotter_preview.export_svg && !self.plotter_preview.high_quality;
+                    ui.add_enabled_ui(bitmap, |ui| {
+                        egui::ComboBox::from_id_source("plot_preview_supersample")
+                            .selected_text(format!("{}x", self.plotter_preview.supersample))
+                            .show_ui(ui, |ui| {
+                                for factor in 1..=crate::plotter::MAX_SUPERSAMPLE {
+                                    ui.selectable_value(&mut self.plotter_preview.supersample, factor, format!("{factor}x"));
+                                }
+                            });
+                    })
+                    .response
+                    .on_hover_text("Draw the PNG at 2-4x the resolution and scale it down for smoother lines and text. Export time and memory grow with the square of the factor: 4x renders 16 times the pixels");
+                });
+
                 ui.horizontal(|ui| {
                     if ui.button("Save").clicked() {
                         save_requested = true;
                     }
+                    if ui
+                        .button("Copy to clipboard")
+                        .on_hover_text("Copy the image at the chosen resolution")
+                        .clicked()
+                    {
+                        copy_requested = true;
+                    }
                     if ui.button("Cancel").clicked() {
                         self.plotter_preview.open = false;
                     }
                 });
             });
+        if let Some(response) = response {
+            self.window_layout.remember(
+                "PlotPreview",
+                egui::Rect::from_min_size(response.response.rect.min, content_size),
+            );
+        }
 
         // Save settings when dialog closes
-        if save_requested || !self.plotter_preview.open {
+        if save_requested || copy_requested || !self.plotter_preview.open {
             if let Some(plugin_id) = self.plotter_preview.target {
                 self.plotter_manager.plotter_preview_settings.insert(
                     plugin_id,
@@ -322,18 +506,29 @@ impl GuiApp {
                         self.plotter_preview.show_legend,
                         self.plotter_preview.show_grid,
                         self.plotter_preview.series_names.clone(),
-                        self.plotter_preview.colors.clone(),
+                        self.plotter_preview.color_overrides.clone(),
+                        self.plotter_preview.series_styles.clone(),
                         self.plotter_preview.title.clone(),
                         self.plotter_preview.dark_theme,
                         self.plotter_preview.x_axis_name.clone(),
                         self.plotter_preview.y_axis_name.clone(),
                         self.plotter_preview.high_quality,
                         self.plotter_preview.export_svg,
+                        self.plotter_preview.palette,
                     ),
                 );
+                if let Some(plotter) = self.plotter_manager.plotters.get(&plugin_id) {
+                    if let Ok(mut plotter) = plotter.lock() {
+                        plotter.set_palette(self.plotter_preview.palette);
+                    }
+                }
             }
         }
 
+        if copy_requested {
+            self.copy_plotter_to_clipboard(plugin_id);
+        }
+
         if save_requested {
             self.request_plotter_screenshot(plugin_id);
             // Keep dialog open after saving
@@ -1,6 +1,7 @@
 use super::*;
 use crate::utils::{format_f64_with_input, normalize_numeric_input, parse_f64_input};
 use crate::WindowFocus;
+use rtsyn_core::scaffold::{parse_port_list, parse_variable_list, write_plugin_crate, PluginScaffold};
 use crate::{BuildAction, LivePlotter, has_rt_capabilities, spawn_file_dialog_thread, zenity_file_dialog};
 use std::sync::{Arc, Mutex};
 use std::sync::mpsc;
@@ -32,7 +33,167 @@ fn kv_row_wrapped(
     });
 }
 
+/// Bundled plugins whose config is driven by their ui_schema through
+/// Get/SetPluginVariable instead of the generic metadata editor.
+fn is_app_plugin_kind(kind: &str) -> bool {
+    matches!(
+        kind,
+        "csv_recorder"
+            | "live_plotter"
+            | "performance_monitor"
+            | "comedi_daq"
+            | "bang_bang"
+            | "noise_generator"
+            | "integrator"
+            | "differentiator"
+            | "delay"
+            | "alarm"
+            | "sample_hold"
+            | "scale"
+            | "can_io"
+            | "gpio"
+            | "stimulus"
+            | "saturation"
+            | "histogram"
+            | "tcp_stream"
+            | "shm_publisher"
+            | "rms"
+            | "peak_hold"
+            | "timer"
+            | "setpoint_ramp"
+            | "lookup_table"
+            | "stats"
+            | "resampler"
+            | "pwm"
+            | "estop"
+            | "audio_out"
+            | "frequency_counter"
+            | "modbus_io"
+            | "state_machine"
+            | "fault_recorder"
+            | "filtered_derivative"
+            | "bit_pack"
+            | "bit_unpack"
+            | "transfer_function"
+            | "error_metrics"
+    )
+}
+
+/// Icon shown before a plugin name, resolved once per frame.
+#[derive(Clone)]
+enum KindIcon {
+    Glyph(char),
+    Texture(egui::TextureHandle),
+}
+
+/// Draws `icon` in a `size` square; `None` draws the default icon.
+fn paint_kind_icon(ui: &mut egui::Ui, icon: Option<&KindIcon>, size: f32) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(size, size), egui::Sense::hover());
+    match icon {
+        Some(KindIcon::Texture(texture)) => {
+            let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+            ui.painter().image(texture.id(), rect, uv, egui::Color32::WHITE);
+        }
+        icon => {
+            let glyph = match icon {
+                Some(KindIcon::Glyph(glyph)) => *glyph,
+                _ => rtsyn_core::plugin::DEFAULT_PLUGIN_ICON,
+            };
+            ui.painter().text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                glyph,
+                egui::FontId::proportional(size * 0.8),
+                egui::Color32::from_rgb(200, 200, 210),
+            );
+        }
+    }
+}
+
+/// Text fields that only accept a fixed set of values and are shown as a
+/// dropdown.
+fn config_choices(kind: &str, key: &str) -> Option<&'static [&'static str]> {
+    match (kind, key) {
+        ("noise_generator", "distribution") => Some(&["uniform", "gaussian", "pink"]),
+        ("live_plotter", "view_mode") => Some(&["time", "spectrum"]),
+        ("scale", "mode") => Some(&["gain_offset", "two_point"]),
+        ("stimulus", "shape") => Some(&["step", "impulse", "ramp", "chirp"]),
+        ("tcp_stream", "format") => Some(&["json", "csv"]),
+        ("timer", "mode") => Some(&["one_shot", "retriggerable", "periodic"]),
+        ("csv_recorder", "write_mode") => Some(&["overwrite", "append"]),
+        ("lookup_table", "interpolation") => Some(&["nearest", "linear", "cubic"]),
+        ("resampler", "interpolation") => Some(&["hold", "linear"]),
+        ("resampler", "downsampling") => Some(&["decimate", "average"]),
+        ("audio_out", "mode") => Some(&["direct", "am", "fm"]),
+        ("frequency_counter", "edge") => Some(&["rising", "falling"]),
+        ("modbus_io", "mode") => Some(&["tcp", "rtu"]),
+        _ => None,
+    }
+}
+
+/// Fields that only apply in some modes and are left out of the editor
+/// otherwise.
+fn config_field_hidden(kind: &str, key: &str, config: &Value) -> bool {
+    match kind {
+        "scale" => {
+            matches!(key, "in_low" | "out_low" | "in_high" | "out_high")
+                && config.get("mode").and_then(|v| v.as_str()) != Some("two_point")
+        }
+        "stimulus" => {
+            matches!(key, "f_start" | "f_end" | "duration")
+                && config.get("shape").and_then(|v| v.as_str()) != Some("chirp")
+        }
+        "modbus_io" => {
+            let rtu = config.get("mode").and_then(|v| v.as_str()) == Some("rtu");
+            match key {
+                "address" => rtu,
+                "device" | "baud_rate" => !rtu,
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
 impl GuiApp {
+    /// Icons of the installed plugin kinds. PNG icons are loaded on first use
+    /// and kinds whose image failed to load fall back to the default glyph.
+    fn kind_icons(&mut self, ctx: &egui::Context) -> HashMap<String, KindIcon> {
+        let mut icons = HashMap::new();
+        for installed in &self.plugin_manager.installed_plugins {
+            let icon = match rtsyn_core::plugin::plugin_icon(installed) {
+                rtsyn_core::plugin::PluginIcon::Glyph(glyph) => Some(KindIcon::Glyph(glyph)),
+                rtsyn_core::plugin::PluginIcon::Image(path) => self
+                    .plugin_icon_textures
+                    .entry(path.clone())
+                    .or_insert_with(|| Self::load_icon_texture(ctx, &path))
+                    .clone()
+                    .map(KindIcon::Texture),
+            };
+            if let Some(icon) = icon {
+                icons.insert(installed.manifest.kind.clone(), icon);
+            }
+        }
+        icons
+    }
+
+    fn load_icon_texture(ctx: &egui::Context, path: &std::path::Path) -> Option<egui::TextureHandle> {
+        let image = match image::open(path) {
+            Ok(image) => image.to_rgba8(),
+            Err(err) => {
+                log::warn!("Failed to load plugin icon {}: {err}", path.display());
+                return None;
+            }
+        };
+        let size = [image.width() as usize, image.height() as usize];
+        let pixels = egui::ColorImage::from_rgba_unmultiplied(size, image.as_raw());
+        Some(ctx.load_texture(
+            format!("plugin_icon:{}", path.display()),
+            pixels,
+            egui::TextureOptions::LINEAR,
+        ))
+    }
+
     fn open_install_dialog(&mut self) {
         if self.file_dialogs.install_dialog_rx.is_some() {
             self.status = "Plugin dialog already open".to_string();
@@ -53,6 +214,26 @@ impl GuiApp {
         });
     }
 
+    fn open_plugin_path_dialog(&mut self) {
+        if self.file_dialogs.plugin_path_dialog_rx.is_some() {
+            self.status = "Plugin dialog already open".to_string();
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        self.file_dialogs.plugin_path_dialog_rx = Some(rx);
+        self.status = "Opening plugin path dialog...".to_string();
+
+        crate::spawn_file_dialog_thread(move || {
+            let folder = if crate::has_rt_capabilities() {
+                crate::zenity_file_dialog("folder", None)
+            } else {
+                rfd::FileDialog::new().pick_folder()
+            };
+            let _ = tx.send(folder);
+        });
+    }
+
     pub(crate) fn open_manage_plugins(&mut self) {
         self.windows.manage_plugins_open = true;
         self.scan_detected_plugins();
@@ -66,6 +247,131 @@ impl GuiApp {
         self.pending_window_focus = Some(WindowFocus::Plugins);
     }
 
+    pub(crate) fn open_new_plugin_dialog(&mut self) {
+        self.new_plugin_dialog = crate::ui_state::NewPluginDialogState {
+            open: true,
+            ..Default::default()
+        };
+        self.pending_window_focus = Some(WindowFocus::NewPlugin);
+    }
+
+    pub(crate) fn render_new_plugin_dialog(&mut self, ctx: &egui::Context) {
+        if !self.new_plugin_dialog.open {
+            return;
+        }
+
+        let scaffold = self.new_plugin_scaffold();
+        let mut folder_display = match &scaffold {
+            Ok(scaffold) => Path::new("plugins").join(scaffold.kind()).display().to_string(),
+            Err(_) => String::new(),
+        };
+        let mut open = self.new_plugin_dialog.open;
+        let window_size = egui::vec2(420.0, 380.0);
+        let default_pos = self.window_placement(ctx, "NewPlugin", window_size).min;
+        let mut create = false;
+        let mut cancel = false;
+        let response = egui::Window::new("New plugin")
+            .open(&mut open)
+            .resizable(false)
+            .default_pos(default_pos)
+            .default_size(window_size)
+            .show(ctx, |ui| {
+                let dialog = &mut self.new_plugin_dialog;
+                ui.label("Name");
+                ui.text_edit_singleline(&mut dialog.name);
+                ui.label("Description");
+                ui.text_edit_singleline(&mut dialog.description);
+                ui.label("Inputs");
+                ui.add(egui::TextEdit::singleline(&mut dialog.inputs).hint_text("in, enable"));
+                ui.label("Outputs");
+                ui.add(egui::TextEdit::singleline(&mut dialog.outputs).hint_text("out"));
+                ui.label("Variables");
+                ui.add(
+                    egui::TextEdit::singleline(&mut dialog.variables).hint_text("gain=1.0, offset"),
+                );
+                ui.add_space(6.0);
+                ui.label("Folder");
+                ui.add_enabled(false, egui::TextEdit::singleline(&mut folder_display));
+                ui.checkbox(&mut dialog.build, "Build and install after creating");
+                if let Err(err) = &scaffold {
+                    if !dialog.name.trim().is_empty() {
+                        ui.colored_label(ui.visuals().error_fg_color, err);
+                    }
+                }
+                ui.add_space(6.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Cancel").clicked() {
+                        cancel = true;
+                    }
+                    if ui
+                        .add_enabled(scaffold.is_ok(), egui::Button::new("Create"))
+                        .clicked()
+                    {
+                        create = true;
+                    }
+                });
+            });
+        if let Some(response) = response {
+            self.track_window("NewPlugin", response.response.rect);
+            if !self.confirm_dialog.open
+                && (response.response.clicked() || response.response.dragged())
+            {
+                ctx.move_to_top(response.response.layer_id);
+            }
+            if self.pending_window_focus == Some(WindowFocus::NewPlugin) {
+                ctx.move_to_top(response.response.layer_id);
+                self.pending_window_focus = None;
+            }
+        }
+
+        self.new_plugin_dialog.open = open && !cancel;
+        if let (true, Ok(scaffold)) = (create, scaffold) {
+            self.create_plugin_crate(&scaffold);
+        }
+    }
+
+    fn new_plugin_scaffold(&self) -> Result<PluginScaffold, String> {
+        let dialog = &self.new_plugin_dialog;
+        let scaffold = PluginScaffold {
+            name: dialog.name.trim().to_string(),
+            description: dialog.description.trim().to_string(),
+            inputs: parse_port_list(&dialog.inputs),
+            outputs: parse_port_list(&dialog.outputs),
+            variables: parse_variable_list(&dialog.variables)?,
+        };
+        scaffold.validate()?;
+        Ok(scaffold)
+    }
+
+    fn create_plugin_crate(&mut self, scaffold: &PluginScaffold) {
+        match write_plugin_crate(Path::new("plugins"), scaffold) {
+            Ok(folder) => {
+                self.new_plugin_dialog.open = false;
+                if self.new_plugin_dialog.build {
+                    self.start_plugin_build(
+                        BuildAction::Install {
+                            path: folder,
+                            removable: true,
+                            persist: true,
+                        },
+                        scaffold.name.clone(),
+                    );
+                } else {
+                    self.scan_detected_plugins();
+                    self.show_info(
+                        "Plugin",
+                        &format!(
+                            "Created {}; edit src/lib.rs and install it from Manage plugins",
+                            folder.display()
+                        ),
+                    );
+                }
+            }
+            Err(err) => self.show_info("Plugin", &format!("Failed to create plugin: {err}")),
+        }
+    }
+
     pub(crate) fn render_plugin_cards(&mut self, ctx: &egui::Context, panel_rect: egui::Rect) {
         let mut pending_info: Option<String> = None;
         let incoming_connections: HashSet<u64> = self
@@ -89,15 +395,34 @@ impl GuiApp {
             .iter()
             .map(|plugin| (plugin.manifest.kind.clone(), plugin.metadata_variables.clone()))
             .collect();
+        let starts_expanded_by_kind: HashMap<String, bool> = self
+            .plugin_manager.installed_plugins
+            .iter()
+            .map(|plugin| (plugin.manifest.kind.clone(), plugin.manifest.starts_expanded))
+            .collect();
+        let icons = self.kind_icons(ctx);
         let computed_outputs = self.state_sync.computed_outputs.clone();
         let input_values = self.state_sync.input_values.clone();
         let internal_variable_values = self.state_sync.internal_variable_values.clone();
         let viewer_values = self.state_sync.viewer_values.clone();
+        let connections = self.workspace_manager.workspace.connections.clone();
+        let warming_up = self.state_sync.warming_up.clone();
+        let open_errors = self.state_sync.open_errors.clone();
+        let load_errors = self.state_sync.load_errors.clone();
+        let plugin_run_states = self.state_sync.plugin_run_states.clone();
+        // Drafts of removed plugins must not carry over to a reused id
+        let plugin_ids: HashSet<u64> = self.workspace_manager.workspace.plugins.iter().map(|p| p.id).collect();
+        self.config_drafts.retain(|id, _| plugin_ids.contains(id));
         let mut remove_id: Option<u64> = None;
         let mut pending_running: Vec<(u64, bool)> = Vec::new();
+        let mut pending_device_starts: Vec<u64> = Vec::new();
         let mut pending_restart: Vec<u64> = Vec::new();
+        let mut pending_retry_open: Vec<u64> = Vec::new();
+        let mut pending_locate_library: Option<u64> = None;
         let mut pending_workspace_update = false;
         let mut pending_prune: Option<(u64, usize)> = None;
+        let mut pending_input_swap: Option<(u64, usize, usize)> = None;
+        let mut pending_fill_columns: Option<(u64, bool)> = None;
         let mut pending_enforce_connection = false;
 
         let mut index = 0usize;
@@ -105,6 +430,8 @@ impl GuiApp {
         let mut workspace_changed = false;
         let mut recompute_plotter_needed = false;
         let right_down = ctx.input(|i| i.pointer.secondary_down());
+        let canvas_transform = self.canvas_transform;
+        let locked = self.workspace_manager.workspace.settings.locked;
         for plugin in &mut self.workspace_manager.workspace.plugins {
             let col = index % max_per_row;
             let row = index / max_per_row;
@@ -117,6 +444,13 @@ impl GuiApp {
                 .unwrap_or(default_pos);
             let area_id = egui::Id::new(("plugin_window", plugin.id));
             let mut plugin_changed = false;
+            // Plugins whose library did not load keep their card, greyed
+            // out, so their config and connections are not lost
+            let load_error = load_errors.get(&plugin.id);
+            let collapsed = rtsyn_core::plugin::card_collapsed(
+                &plugin.config,
+                starts_expanded_by_kind.get(&plugin.kind).copied().unwrap_or(true),
+            );
             let current_id = self.connection_editor.plugin_id;
             let selected_id = self.connection_highlight_plugin_id;
             let tab_primary = match self.connection_editor.tab {
@@ -142,20 +476,36 @@ impl GuiApp {
                 .outer_margin(egui::Margin::ZERO);
             if let Some(color) = highlight_color {
                 frame = frame.stroke(egui::Stroke::new(2.0, color));
+            } else if self.selected_plugin_ids.contains(&plugin.id) {
+                frame = frame.stroke(egui::Stroke::new(2.0, egui::Color32::from_rgb(110, 160, 255)));
             }
-            let response = egui::Area::new(area_id)
+            let mut area = egui::Area::new(area_id)
                 .order(egui::Order::Middle)
                 .default_pos(pos)
-                .movable(!right_down)
-                .constrain_to(panel_rect)
+                .movable(!right_down && !locked);
+            if let Some(pos) = self.pending_card_positions.remove(&plugin.id) {
+                area = area.current_pos(pos);
+            }
+            // Keep cards on screen only while the canvas is untransformed;
+            // otherwise they would follow every pan.
+            area = if canvas_transform == egui::emath::TSTransform::IDENTITY {
+                area.constrain_to(panel_rect)
+            } else {
+                area.constrain(false)
+            };
+            let response = area
                 .show(ctx, |ui| {
                     let card_width = 280.0;
                     ui.set_width(card_width);
                     
                     frame.show(ui, |ui| {
+                        if load_error.is_some() {
+                            ui.set_opacity(0.55);
+                        }
                         ui.vertical(|ui| {
-                            // Header
-                            ui.horizontal(|ui| {
+                            // Header, tinted with the plugin's accent color
+                            let header_tint = ui.painter().add(egui::Shape::Noop);
+                            let header = ui.horizontal(|ui| {
                                 // ID badge
                                 let (id_rect, _) = ui.allocate_exact_size(
                                     egui::vec2(24.0, 24.0),
@@ -175,13 +525,49 @@ impl GuiApp {
                                 );
                                 
                                 ui.add_space(8.0);
+                                paint_kind_icon(ui, icons.get(&plugin.kind), 18.0);
+                                ui.add_space(4.0);
                                 
                                 // Plugin name
-                                let display_name = name_by_kind
+                                let kind_name = name_by_kind
                                     .get(&plugin.kind)
                                     .cloned()
                                     .unwrap_or_else(|| Self::display_kind(&plugin.kind));
-                                ui.label(RichText::new(display_name).size(15.0).strong());
+                                match rtsyn_core::plugin::custom_label(&plugin.config) {
+                                    Some(label) => {
+                                        ui.label(RichText::new(label).size(15.0).strong())
+                                            .on_hover_text(kind_name);
+                                    }
+                                    None => {
+                                        ui.label(RichText::new(kind_name).size(15.0).strong());
+                                    }
+                                }
+                                if warming_up.contains(&plugin.id) {
+                                    ui.label(
+                                        RichText::new("warming up")
+                                            .size(11.0)
+                                            .color(egui::Color32::from_rgb(230, 180, 80)),
+                                    )
+                                    .on_hover_text("Outputs are held at zero until the warmup elapses");
+                                }
+                                if plugin.running
+                                    && plugin_run_states.get(&plugin.id) == Some(&PluginRunState::Stopped)
+                                {
+                                    ui.label(
+                                        RichText::new("stopped by runtime")
+                                            .size(11.0)
+                                            .color(egui::Color32::from_rgb(230, 180, 80)),
+                                    )
+                                    .on_hover_text("The runtime is not running this plugin; the workspace still starts it");
+                                }
+                                if let Some(error) = open_errors.get(&plugin.id) {
+                                    ui.label(
+                                        RichText::new("failed to open")
+                                            .size(11.0)
+                                            .color(egui::Color32::from_rgb(230, 90, 90)),
+                                    )
+                                    .on_hover_text(error);
+                                }
                                 
                                 // Close button
                                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
@@ -204,531 +590,714 @@ impl GuiApp {
                                     if close_resp.clicked() {
                                         remove_id = Some(plugin.id);
                                     }
+                                    let (chevron, hint) = if collapsed {
+                                        ("\u{f078}", "Expand card")
+                                    } else {
+                                        ("\u{f077}", "Collapse card")
+                                    };
+                                    if ui
+                                        .add(egui::Button::new(RichText::new(chevron).size(12.0)).frame(false))
+                                        .on_hover_text(hint)
+                                        .clicked()
+                                    {
+                                        if let Value::Object(ref mut map) = plugin.config {
+                                            map.insert(
+                                                rtsyn_core::plugin::UI_COLLAPSED_KEY.to_string(),
+                                                Value::Bool(!collapsed),
+                                            );
+                                            plugin_changed = true;
+                                        }
+                                    }
                                 });
                             });
+                            if let Some([r, g, b]) = rtsyn_core::plugin::custom_color(&plugin.config) {
+                                ui.painter().set(
+                                    header_tint,
+                                    egui::Shape::rect_filled(
+                                        header.response.rect.expand(6.0),
+                                        egui::Rounding::same(4.0),
+                                        egui::Color32::from_rgb(r, g, b).gamma_multiply(0.45),
+                                    ),
+                                );
+                            }
+
+                            if let Some(error) = load_error {
+                                ui.add_space(6.0);
+                                ui.label(
+                                    RichText::new(error)
+                                        .size(11.0)
+                                        .color(egui::Color32::from_rgb(230, 90, 90)),
+                                );
+                                ui.add_space(4.0);
+                                if ui
+                                    .add_enabled(!locked, egui::Button::new("\u{f07c}  Locate library"))
+                                    .on_hover_text("Pick the rebuilt library; the plugin's config and connections are kept")
+                                    .clicked()
+                                {
+                                    pending_locate_library = Some(plugin.id);
+                                }
+                            }
+
+                            if !collapsed && load_error.is_none() {
+                                ui.add_space(8.0);
+                                ui.separator();
+                                ui.add_space(4.0);
                             
-                            ui.add_space(8.0);
-                            ui.separator();
-                            ui.add_space(4.0);
-                            
-                            // Body with sections
-                            ui.scope(|ui| {
-                                // Set thin scrollbar BEFORE creating ScrollArea
-                                let mut scroll_style = egui::style::ScrollStyle::solid();
-                                scroll_style.bar_width = 4.0;
-                                scroll_style.floating = true;  // Only show on hover
-                                scroll_style.floating_width = 2.0;  // Thinner when not hovered
-                                scroll_style.floating_allocated_width = 2.0;
-                                ui.style_mut().spacing.scroll = scroll_style;
+                                // Body with sections, hidden on collapsed cards
+                                ui.scope(|ui| {
+                                    // Set thin scrollbar BEFORE creating ScrollArea
+                                    let mut scroll_style = egui::style::ScrollStyle::solid();
+                                    scroll_style.bar_width = 4.0;
+                                    scroll_style.floating = true;  // Only show on hover
+                                    scroll_style.floating_width = 2.0;  // Thinner when not hovered
+                                    scroll_style.floating_allocated_width = 2.0;
+                                    ui.style_mut().spacing.scroll = scroll_style;
                                 
-                                egui::ScrollArea::vertical()
-                                    .max_height(400.0)
-                                    .drag_to_scroll(false)
-                                    .show(ui, |ui| {
-                                        ui.push_id(("plugin_content", plugin.id), |ui| {
-                                        ui.style_mut().spacing.item_spacing = egui::vec2(0.0, 6.0);
+                                    egui::ScrollArea::vertical()
+                                        .max_height(400.0)
+                                        .drag_to_scroll(false)
+                                        .show(ui, |ui| {
+                                            ui.push_id(("plugin_content", plugin.id), |ui| {
+                                            ui.style_mut().spacing.item_spacing = egui::vec2(0.0, 6.0);
                                     
-                                    let is_app_plugin = matches!(
-                                        plugin.kind.as_str(),
-                                        "csv_recorder" | "live_plotter" | "performance_monitor" | "comedi_daq"
-                                    );
+                                        let is_app_plugin = is_app_plugin_kind(&plugin.kind);
 
-                                    if !is_app_plugin {
-                                        match plugin.config {
-                                            Value::Object(ref mut map) => {
-                                                let vars = metadata_by_kind
-                                                    .get(&plugin.kind)
-                                                    .cloned()
-                                                    .unwrap_or_default();
-                                                if !vars.is_empty() {
-                                                    egui::CollapsingHeader::new(
-                                                        RichText::new("\u{f013}  Variables").size(13.0).strong()  // gear icon
-                                                    )
-                                                    .default_open(true)
-                                                    .show(ui, |ui| {
-                                                        ui.add_space(4.0);
-                                                        for (name, __default_value) in vars {
-                                                            let key = &name;
-                                                            if let Some(value) = map.get_mut(key) {
-                                                                // Special handling for max_latency_us
-                                                                if key == "max_latency_us" {
-                                                                    let us_value = value.as_f64().unwrap_or(1000.0);
-                                                                    let value_key = (plugin.id, "max_latency_value".to_string());
-                                                                    let unit_key = (plugin.id, "max_latency_unit".to_string());
-                                                                    
-                                                                    // Determine display value and unit
-                                                                    let (display_value, default_unit) = if us_value >= 1000.0 {
-                                                                        (us_value / 1000.0, "ms")
-                                                                    } else if us_value >= 1.0 {
-                                                                        (us_value, "us")
-                                                                    } else {
-                                                                        (us_value * 1000.0, "ns")
-                                                                    };
-                                                                    
-                                                                    if !self.number_edit_buffers.contains_key(&value_key) {
-                                                                        self.number_edit_buffers.insert(value_key.clone(), display_value.to_string());
-                                                                    }
-                                                                    if !self.number_edit_buffers.contains_key(&unit_key) {
-                                                                        self.number_edit_buffers.insert(unit_key.clone(), default_unit.to_string());
-                                                                    }
+                                        if !is_app_plugin {
+                                            match plugin.config {
+                                                Value::Object(ref mut map) => {
+                                                    let vars = metadata_by_kind
+                                                        .get(&plugin.kind)
+                                                        .cloned()
+                                                        .unwrap_or_default();
+                                                    if !vars.is_empty() {
+                                                        egui::CollapsingHeader::new(
+                                                            RichText::new("\u{f013}  Variables").size(13.0).strong()  // gear icon
+                                                        )
+                                                        .default_open(true)
+                                                        .show(ui, |ui| {
+                                                            ui.add_space(4.0);
+                                                            ui.set_enabled(!locked);
+                                                            for (name, __default_value) in vars {
+                                                                let key = &name;
+                                                                if let Some(value) = map.get_mut(key) {
+                                                                    // Special handling for max_latency_us
+                                                                    if key == "max_latency_us" {
+                                                                        let us_value = rtsyn_core::config::parse_f64(value).unwrap_or(1000.0);
+                                                                        let value_key = (plugin.id, "max_latency_value".to_string());
+                                                                        let unit_key = (plugin.id, "max_latency_unit".to_string());
                                                                     
-                                                                    let mut drag_value = self.number_edit_buffers[&value_key].parse::<f64>().unwrap_or(display_value);
-                                                                    let mut unit_clone = self.number_edit_buffers[&unit_key].clone();
+                                                                        // Determine display value and unit
+                                                                        let (display_value, default_unit) = if us_value >= 1000.0 {
+                                                                            (us_value / 1000.0, "ms")
+                                                                        } else if us_value >= 1.0 {
+                                                                            (us_value, "us")
+                                                                        } else {
+                                                                            (us_value * 1000.0, "ns")
+                                                                        };
                                                                     
-                                                                    kv_row_wrapped(ui, "max_latency", 140.0, |ui| {
-                                                                        let mut changed = false;
-                                                                        if ui.add(egui::DragValue::new(&mut drag_value).speed(10.0).clamp_range(1.0..=f64::INFINITY).fixed_decimals(0)).changed() {
-                                                                            changed = true;
+                                                                        if !self.number_edit_buffers.contains_key(&value_key) {
+                                                                            self.number_edit_buffers.insert(value_key.clone(), display_value.to_string());
                                                                         }
-                                                                        ui.add_space(4.0);
-                                                                        egui::ComboBox::from_id_source((plugin.id, "max_latency_unit"))
-                                                                            .selected_text(&unit_clone)
-                                                                            .width(40.0)
-                                                                            .show_ui(ui, |ui| {
-                                                                                if ui.selectable_label(unit_clone == "ns", "ns").clicked() {
-                                                                                    unit_clone = "ns".to_string();
-                                                                                    changed = true;
-                                                                                }
-                                                                                if ui.selectable_label(unit_clone == "us", "us").clicked() {
-                                                                                    unit_clone = "us".to_string();
-                                                                                    changed = true;
-                                                                                }
-                                                                                if ui.selectable_label(unit_clone == "ms", "ms").clicked() {
-                                                                                    unit_clone = "ms".to_string();
-                                                                                    changed = true;
-                                                                                }
-                                                                            });
-                                                                        
-                                                                        if changed {
-                                                                            let us_val = match unit_clone.as_str() {
-                                                                                "ms" => drag_value * 1000.0,
-                                                                                "us" => drag_value,
-                                                                                "ns" => drag_value / 1000.0,
-                                                                                _ => drag_value,
-                                                                            };
-                                                                            *value = Value::from(us_val);
-                                                                            plugin_changed = true;
+                                                                        if !self.number_edit_buffers.contains_key(&unit_key) {
+                                                                            self.number_edit_buffers.insert(unit_key.clone(), default_unit.to_string());
                                                                         }
-                                                                    });
                                                                     
-                                                                    self.number_edit_buffers.insert(value_key, drag_value.to_string());
-                                                                    self.number_edit_buffers.insert(unit_key, unit_clone);
-                                                                } else {
-                                                                    let buffer_key = (plugin.id, key.clone());
-                                                                    let buffer = self
-                                                                        .number_edit_buffers
-                                                                        .entry(buffer_key)
-                                                                        .or_insert_with(|| {
-                                                                            format_f64_6(
-                                                                                value.as_f64().unwrap_or(0.0),
-                                                                            )
-                                                                        });
-                                                                    kv_row_wrapped(ui, key, 140.0, |ui| {
-                                                                        ui.add_sized(
-                                                                            [80.0, 0.0],
-                                                                            egui::TextEdit::singleline(buffer)
-                                                                        ).changed().then(|| {
-                                                                            let _ = normalize_numeric_input(buffer);
-                                                                            if let Some(parsed) = parse_f64_input(buffer) {
-                                                                                let truncated = truncate_f64(parsed);
-                                                                                *value = Value::from(truncated);
-                                                                                *buffer = format_f64_with_input(buffer, truncated);
+                                                                        let mut drag_value = self.number_edit_buffers[&value_key].parse::<f64>().unwrap_or(display_value);
+                                                                        let mut unit_clone = self.number_edit_buffers[&unit_key].clone();
+                                                                    
+                                                                        kv_row_wrapped(ui, "max_latency", 140.0, |ui| {
+                                                                            let mut changed = false;
+                                                                            if ui.add(egui::DragValue::new(&mut drag_value).speed(10.0).clamp_range(1.0..=f64::INFINITY).fixed_decimals(0)).changed() {
+                                                                                changed = true;
+                                                                            }
+                                                                            ui.add_space(4.0);
+                                                                            egui::ComboBox::from_id_source((plugin.id, "max_latency_unit"))
+                                                                                .selected_text(&unit_clone)
+                                                                                .width(40.0)
+                                                                                .show_ui(ui, |ui| {
+                                                                                    if ui.selectable_label(unit_clone == "ns", "ns").clicked() {
+                                                                                        unit_clone = "ns".to_string();
+                                                                                        changed = true;
+                                                                                    }
+                                                                                    if ui.selectable_label(unit_clone == "us", "us").clicked() {
+                                                                                        unit_clone = "us".to_string();
+                                                                                        changed = true;
+                                                                                    }
+                                                                                    if ui.selectable_label(unit_clone == "ms", "ms").clicked() {
+                                                                                        unit_clone = "ms".to_string();
+                                                                                        changed = true;
+                                                                                    }
+                                                                                });
+                                                                        
+                                                                            if changed {
+                                                                                let us_val = match unit_clone.as_str() {
+                                                                                    "ms" => drag_value * 1000.0,
+                                                                                    "us" => drag_value,
+                                                                                    "ns" => drag_value / 1000.0,
+                                                                                    _ => drag_value,
+                                                                                };
+                                                                                *value = Value::from(us_val);
                                                                                 plugin_changed = true;
                                                                             }
                                                                         });
-                                                                    });
+                                                                    
+                                                                        self.number_edit_buffers.insert(value_key, drag_value.to_string());
+                                                                        self.number_edit_buffers.insert(unit_key, unit_clone);
+                                                                    } else {
+                                                                        let buffer_key = (plugin.id, key.clone());
+                                                                        let buffer = self
+                                                                            .number_edit_buffers
+                                                                            .entry(buffer_key)
+                                                                            .or_insert_with(|| {
+                                                                                format_f64_6(
+                                                                                    rtsyn_core::config::parse_f64(value).unwrap_or(0.0),
+                                                                                )
+                                                                            });
+                                                                        kv_row_wrapped(ui, key, 140.0, |ui| {
+                                                                            ui.add_sized(
+                                                                                [80.0, 0.0],
+                                                                                egui::TextEdit::singleline(buffer)
+                                                                            ).changed().then(|| {
+                                                                                let _ = normalize_numeric_input(buffer);
+                                                                                if let Some(parsed) = parse_f64_input(buffer) {
+                                                                                    let truncated = truncate_f64(parsed);
+                                                                                    *value = Value::from(truncated);
+                                                                                    *buffer = format_f64_with_input(buffer, truncated);
+                                                                                    plugin_changed = true;
+                                                                                }
+                                                                            });
+                                                                        });
+                                                                    }
                                                                 }
                                                             }
-                                                        }
-                                                    });
+                                                        });
+                                                    }
+                                                }
+                                                _ => {
+                                                    ui.label("Config is not an object.");
                                                 }
-                                            }
-                                            _ => {
-                                                ui.label("Config is not an object.");
                                             }
                                         }
-                                    }
 
-                                        let (display_schema, ui_schema) = self.plugin_manager.installed_plugins
-                                            .iter()
-                                            .find(|p| p.manifest.kind == plugin.kind)
-                                            .map(|p| (p.display_schema.clone(), p.ui_schema.clone()))
-                                            .unwrap_or((None, None));
-                                        if let Some(schema) = display_schema.as_ref() {
-                                                // Variables section for app plugins
-                                                let vars: Vec<String> = if is_app_plugin {
-                                                    ui_schema
-                                                        .as_ref()
-                                                        .map(|schema| {
-                                                            schema.fields.iter().map(|f| f.key.clone()).collect()
-                                                        })
-                                                        .unwrap_or_default()
-                                                } else {
-                                                    schema.variables.clone()
-                                                };
-                                                if !vars.is_empty() && is_app_plugin {
-                                                    egui::CollapsingHeader::new(
-                                                        RichText::new("\u{f0ae}  Variables").size(13.0).strong()
-                                                    )
-                                                    .default_open(true)
-                                                    .show(ui, |ui| {
-                                                        ui.add_space(4.0);
-                                                        let label_w = 140.0;
-                                                        let value_w = (ui.available_width() - label_w - 8.0).max(80.0);
+                                            let (display_schema, ui_schema, port_units) = self.plugin_manager.installed_plugins
+                                                .iter()
+                                                .find(|p| p.manifest.kind == plugin.kind)
+                                                .map(|p| (p.display_schema.clone(), p.ui_schema.clone(), p.manifest.port_units.clone()))
+                                                .unwrap_or_default();
+                                            let unit_label = |ui: &mut egui::Ui, port: &str| {
+                                                if let Some(unit) = port_units.get(port).map(|unit| unit.trim()).filter(|unit| !unit.is_empty()) {
+                                                    ui.label(RichText::new(unit).size(11.0).color(egui::Color32::from_gray(170)));
+                                                }
+                                            };
+                                            if let Some(schema) = display_schema.as_ref() {
+                                                    // Variables section for app plugins
+                                                    let vars: Vec<String> = if is_app_plugin {
+                                                        ui_schema
+                                                            .as_ref()
+                                                            .map(|schema| {
+                                                                schema.fields.iter().map(|f| f.key.clone()).collect()
+                                                            })
+                                                            .unwrap_or_default()
+                                                    } else {
+                                                        schema.variables.clone()
+                                                    };
+                                                    if !vars.is_empty() && is_app_plugin {
+                                                        egui::CollapsingHeader::new(
+                                                            RichText::new("\u{f0ae}  Variables").size(13.0).strong()
+                                                        )
+                                                        .default_open(true)
+                                                        .show(ui, |ui| {
+                                                            ui.add_space(4.0);
+                                                            ui.set_enabled(!locked);
+                                                            let label_w = 140.0;
+                                                            let value_w = (ui.available_width() - label_w - 8.0).max(80.0);
                                                         
-                                                        for var_name in &vars {
-                                                            let (tx, rx) = mpsc::channel();
-                                                            let _ = self.state_sync.logic_tx.send(LogicMessage::GetPluginVariable(plugin.id, var_name.clone(), tx));
-                                                            
-                                                            if let Ok(Some(value)) = rx.recv() {
-                                                                if plugin.kind == "csv_recorder"
-                                                                    && var_name == "columns"
-                                                                    && matches!(value, Value::Array(ref arr) if arr.is_empty())
-                                                                {
+                                                            let apply_mode = rtsyn_core::plugin::supports_apply(&plugin.kind);
+                                                            let mut var_edits: Vec<(String, Value)> = Vec::new();
+                                                            for var_name in &vars {
+                                                                if config_field_hidden(&plugin.kind, var_name, &plugin.config) {
                                                                     continue;
                                                                 }
-                                                                let field_info = ui_schema.as_ref()
-                                                                    .and_then(|schema| schema.fields.iter().find(|f| f.key == *var_name));
-                                                                let label = field_info
-                                                                    .map(|field| field.label.as_str())
-                                                                    .unwrap_or(var_name.as_str());
-                                                                let is_filepath = field_info
-                                                                    .map(|field| matches!(field.field_type, rtsyn_plugin::ui::FieldType::FilePath { .. }))
-                                                                    .unwrap_or(false);
+                                                                let (tx, rx) = mpsc::channel();
+                                                                let _ = self.state_sync.logic_tx.send(LogicMessage::GetPluginVariable(plugin.id, var_name.clone(), tx));
+                                                            
+                                                                if let Ok(Some(value)) = rx.recv() {
+                                                                    // Unapplied edits show in place of the running values
+                                                                    let value = match self.config_drafts.get(&plugin.id).and_then(|draft| draft.get(var_name)) {
+                                                                        Some(draft_value) => draft_value.clone(),
+                                                                        None => value,
+                                                                    };
+                                                                    if plugin.kind == "csv_recorder"
+                                                                        && var_name == "columns"
+                                                                        && matches!(value, Value::Array(ref arr) if arr.is_empty())
+                                                                    {
+                                                                        continue;
+                                                                    }
+                                                                    let field_info = ui_schema.as_ref()
+                                                                        .and_then(|schema| schema.fields.iter().find(|f| f.key == *var_name));
+                                                                    let value = match field_info {
+                                                                        Some(field) => rtsyn_core::config::normalize_number(value, &field.field_type),
+                                                                        None => value,
+                                                                    };
+                                                                    let label = field_info
+                                                                        .map(|field| field.label.as_str())
+                                                                        .unwrap_or(var_name.as_str());
+                                                                    let is_filepath = field_info
+                                                                        .map(|field| matches!(field.field_type, rtsyn_plugin::ui::FieldType::FilePath { .. }))
+                                                                        .unwrap_or(false);
                                                                 
-                                                                kv_row_wrapped(ui, label, label_w, |ui| {
-                                                                    match &value {
-                                                                        Value::String(s) => {
-                                                                            let mut text = s.clone();
-                                                                            if is_filepath {
-                                                                                if text.trim().is_empty() {
-                                                                                    text = Self::default_csv_path();
-                                                                                    let _ = self.state_sync.logic_tx.send(
-                                                                                        LogicMessage::SetPluginVariable(
-                                                                                            plugin.id,
-                                                                                            var_name.clone(),
-                                                                                            Value::String(text.clone()),
-                                                                                        ),
-                                                                                    );
-                                                                                    if let Value::Object(ref mut map) = plugin.config {
-                                                                                        map.insert("path".to_string(), Value::String(text.clone()));
-                                                                                        map.insert("path_autogen".to_string(), Value::Bool(true));
-                                                                                        plugin_changed = true;
-                                                                                    }
-                                                                                }
-                                                                                ui.vertical(|ui| {
-                                                                                    ui.add_enabled_ui(false, |ui| {
-                                                                                        ui.add_sized(
-                                                                                            [value_w, 0.0],
-                                                                                            egui::TextEdit::singleline(&mut text),
+                                                                    kv_row_wrapped(ui, label, label_w, |ui| {
+                                                                        match &value {
+                                                                            Value::String(s) => {
+                                                                                let mut text = s.clone();
+                                                                                if is_filepath {
+                                                                                    if text.trim().is_empty() {
+                                                                                        text = Self::default_csv_path();
+                                                                                        let _ = self.state_sync.logic_tx.send(
+                                                                                            LogicMessage::SetPluginVariable(
+                                                                                                plugin.id,
+                                                                                                var_name.clone(),
+                                                                                                Value::String(text.clone()),
+                                                                                            ),
                                                                                         );
+                                                                                        if let Value::Object(ref mut map) = plugin.config {
+                                                                                            map.insert("path".to_string(), Value::String(text.clone()));
+                                                                                            map.insert("path_autogen".to_string(), Value::Bool(true));
+                                                                                            plugin_changed = true;
+                                                                                        }
+                                                                                    }
+                                                                                    ui.vertical(|ui| {
+                                                                                        ui.add_enabled_ui(false, |ui| {
+                                                                                            ui.add_sized(
+                                                                                                [value_w, 0.0],
+                                                                                                egui::TextEdit::singleline(&mut text),
+                                                                                            );
+                                                                                        });
+                                                                                        if ui.add_sized([value_w, 0.0], egui::Button::new("Browse")).clicked() {
+                                                                                            self.csv_path_target_plugin_id = Some(plugin.id);
+                                                                                            let (tx, rx) = mpsc::channel();
+                                                                                            self.file_dialogs.csv_path_dialog_rx = Some(rx);
+                                                                                            spawn_file_dialog_thread(move || {
+                                                                                                let file = if has_rt_capabilities() {
+                                                                                                    zenity_file_dialog("save", None)
+                                                                                                } else {
+                                                                                                    rfd::FileDialog::new().save_file()
+                                                                                                };
+                                                                                                let _ = tx.send(file);
+                                                                                            });
+                                                                                        }
                                                                                     });
-                                                                                    if ui.add_sized([value_w, 0.0], egui::Button::new("Browse")).clicked() {
-                                                                                        self.csv_path_target_plugin_id = Some(plugin.id);
-                                                                                        let (tx, rx) = mpsc::channel();
-                                                                                        self.file_dialogs.csv_path_dialog_rx = Some(rx);
-                                                                                        spawn_file_dialog_thread(move || {
-                                                                                            let file = if has_rt_capabilities() {
-                                                                                                zenity_file_dialog("save", None)
-                                                                                            } else {
-                                                                                                rfd::FileDialog::new().save_file()
-                                                                                            };
-                                                                                            let _ = tx.send(file);
+                                                                                } else if let Some(choices) = config_choices(&plugin.kind, var_name) {
+                                                                                    let mut selected = text.clone();
+                                                                                    egui::ComboBox::from_id_source(("config_choice", plugin.id, var_name.as_str()))
+                                                                                        .width(value_w)
+                                                                                        .selected_text(selected.clone())
+                                                                                        .show_ui(ui, |ui| {
+                                                                                            for choice in choices {
+                                                                                                ui.selectable_value(&mut selected, choice.to_string(), *choice);
+                                                                                            }
                                                                                         });
+                                                                                    if selected != text {
+                                                                                        var_edits.push((var_name.clone(), Value::String(selected)));
                                                                                     }
-                                                                                });
-                                                                            } else if ui.add_sized([value_w, 0.0], egui::TextEdit::singleline(&mut text)).changed() {
-                                                                                let new_text = text.clone();
-                                                                                let _ = self.state_sync.logic_tx.send(LogicMessage::SetPluginVariable(
-                                                                                    plugin.id,
-                                                                                    var_name.clone(),
-                                                                                    Value::String(new_text.clone())
-                                                                                ));
-                                                                                if let Value::Object(ref mut map) = plugin.config {
-                                                                                    map.insert(var_name.clone(), Value::String(new_text));
+                                                                                } else if ui.add_sized([value_w, 0.0], egui::TextEdit::singleline(&mut text)).changed() {
+                                                                                    var_edits.push((var_name.clone(), Value::String(text.clone())));
                                                                                     if var_name == "path" {
-                                                                                        map.insert("path_autogen".to_string(), Value::from(false));
+                                                                                        if let Value::Object(ref mut map) = plugin.config {
+                                                                                            map.insert("path_autogen".to_string(), Value::from(false));
+                                                                                        }
                                                                                     }
-                                                                                    plugin_changed = true;
                                                                                 }
                                                                             }
-                                                                        }
-                                                                        Value::Bool(b) => {
-                                                                            let mut checked = *b;
-                                                                            if ui.add_sized([value_w, 0.0], egui::Checkbox::new(&mut checked, "")).changed() {
-                                                                                let _ = self.state_sync.logic_tx.send(LogicMessage::SetPluginVariable(plugin.id, var_name.clone(), Value::Bool(checked)));
-                                                                                if let Value::Object(ref mut map) = plugin.config {
-                                                                                    map.insert(var_name.clone(), Value::Bool(checked));
-                                                                                    plugin_changed = true;
+                                                                            Value::Bool(b) => {
+                                                                                let mut checked = *b;
+                                                                                if ui.add_sized([value_w, 0.0], egui::Checkbox::new(&mut checked, "")).changed() {
+                                                                                    var_edits.push((var_name.clone(), Value::Bool(checked)));
                                                                                 }
                                                                             }
-                                                                        }
-                                                                        Value::Number(n) => {
-                                                                            let field_info = ui_schema
-                                                                                .as_ref()
-                                                                                .and_then(|schema| schema.fields.iter().find(|f| f.key == *var_name));
-
-                                                                            let mut handled = false;
-                                                                            if let Some(field) = field_info {
-                                                                                match &field.field_type {
-                                                                                    rtsyn_plugin::ui::FieldType::Integer { min, max, step } => {
-                                                                                        let min = *min;
-                                                                                        let max = *max;
-                                                                                        let mut val = n.as_i64().unwrap_or_else(|| n.as_f64().unwrap_or(0.0).round() as i64);
-                                                                                        let range = match (min, max) {
-                                                                                            (Some(mn), Some(mx)) => mn..=mx,
-                                                                                            (Some(mn), None) => mn..=i64::MAX,
-                                                                                            (None, Some(mx)) => i64::MIN..=mx,
-                                                                                            (None, None) => i64::MIN..=i64::MAX,
-                                                                                        };
-                                                                                        if ui.add_sized([value_w, 0.0], egui::DragValue::new(&mut val).speed(*step as f64).clamp_range(range)).changed() {
-                                                                                            let _ = self.state_sync.logic_tx.send(LogicMessage::SetPluginVariable(plugin.id, var_name.clone(), Value::from(val)));
-                                                                                            if let Value::Object(ref mut map) = plugin.config {
-                                                                                                map.insert(var_name.clone(), Value::from(val));
-                                                                                                plugin_changed = true;
+                                                                            Value::Number(n) => {
+                                                                                let field_info = ui_schema
+                                                                                    .as_ref()
+                                                                                    .and_then(|schema| schema.fields.iter().find(|f| f.key == *var_name));
+
+                                                                                let mut handled = false;
+                                                                                if let Some(field) = field_info {
+                                                                                    match &field.field_type {
+                                                                                        rtsyn_plugin::ui::FieldType::Integer { min, max, step } => {
+                                                                                            let min = *min;
+                                                                                            let max = *max;
+                                                                                            let mut val = n.as_i64().unwrap_or_else(|| rtsyn_core::config::parse_f64(&value).unwrap_or(0.0).round() as i64);
+                                                                                            let range = match (min, max) {
+                                                                                                (Some(mn), Some(mx)) => mn..=mx,
+                                                                                                (Some(mn), None) => mn..=i64::MAX,
+                                                                                                (None, Some(mx)) => i64::MIN..=mx,
+                                                                                                (None, None) => i64::MIN..=i64::MAX,
+                                                                                            };
+                                                                                            if ui.add_sized([value_w, 0.0], egui::DragValue::new(&mut val).speed(*step as f64).clamp_range(range)).changed() {
+                                                                                                var_edits.push((var_name.clone(), Value::from(val)));
                                                                                             }
+                                                                                            handled = true;
                                                                                         }
-                                                                                        handled = true;
-                                                                                    }
-                                                                                    rtsyn_plugin::ui::FieldType::Float { min, max, step } => {
-                                                                                        let min = *min;
-                                                                                        let max = *max;
-                                                                                        let mut val = n.as_f64().unwrap_or(0.0);
-                                                                                        let range = match (min, max) {
-                                                                                            (Some(mn), Some(mx)) => mn..=mx,
-                                                                                            (Some(mn), None) => mn..=f64::INFINITY,
-                                                                                            (None, Some(mx)) => f64::NEG_INFINITY..=mx,
-                                                                                            (None, None) => f64::NEG_INFINITY..=f64::INFINITY,
-                                                                                        };
-                                                                                        if ui.add_sized([value_w, 0.0], egui::DragValue::new(&mut val).speed(*step).clamp_range(range)).changed() {
-                                                                                            let _ = self.state_sync.logic_tx.send(LogicMessage::SetPluginVariable(plugin.id, var_name.clone(), Value::from(val)));
-                                                                                            if let Value::Object(ref mut map) = plugin.config {
-                                                                                                map.insert(var_name.clone(), Value::from(val));
-                                                                                                plugin_changed = true;
-                                                                                            }
-                                                                                            if var_name == "refresh_hz" {
-                                                                                                recompute_plotter_needed = true;
+                                                                                        rtsyn_plugin::ui::FieldType::Float { min, max, step } => {
+                                                                                            let min = *min;
+                                                                                            let max = *max;
+                                                                                            let mut val = rtsyn_core::config::parse_f64(&value).unwrap_or(0.0);
+                                                                                            let range = match (min, max) {
+                                                                                                (Some(mn), Some(mx)) => mn..=mx,
+                                                                                                (Some(mn), None) => mn..=f64::INFINITY,
+                                                                                                (None, Some(mx)) => f64::NEG_INFINITY..=mx,
+                                                                                                (None, None) => f64::NEG_INFINITY..=f64::INFINITY,
+                                                                                            };
+                                                                                            if ui.add_sized([value_w, 0.0], egui::DragValue::new(&mut val).speed(*step).clamp_range(range)).changed() {
+                                                                                                var_edits.push((var_name.clone(), Value::from(val)));
+                                                                                                if var_name == "refresh_hz" {
+                                                                                                    recompute_plotter_needed = true;
+                                                                                                }
                                                                                             }
+                                                                                            handled = true;
                                                                                         }
-                                                                                        handled = true;
+                                                                                        _ => {}
                                                                                     }
-                                                                                    _ => {}
                                                                                 }
-                                                                            }
 
-                                                                            if !handled {
-                                                                                if let Some(f) = n.as_f64() {
-                                                                                    let mut val = f;
-                                                                                    if ui.add_sized([value_w, 0.0], egui::DragValue::new(&mut val)).changed() {
-                                                                                        let _ = self.state_sync.logic_tx.send(LogicMessage::SetPluginVariable(plugin.id, var_name.clone(), Value::from(val)));
-                                                                                        if let Value::Object(ref mut map) = plugin.config {
-                                                                                            map.insert(var_name.clone(), Value::from(val));
-                                                                                            plugin_changed = true;
+                                                                                if !handled {
+                                                                                    if let Some(f) = n.as_f64() {
+                                                                                        let mut val = f;
+                                                                                        if ui.add_sized([value_w, 0.0], egui::DragValue::new(&mut val)).changed() {
+                                                                                            var_edits.push((var_name.clone(), Value::from(val)));
                                                                                         }
                                                                                     }
                                                                                 }
                                                                             }
-                                                                        }
-                                                                        Value::Array(arr) => {
-                                                                            if let Some(field) = field_info {
-                                                                                if let rtsyn_plugin::ui::FieldType::DynamicList { item_type, add_label } = &field.field_type {
-                                                                                    let mut items: Vec<String> = arr
-                                                                                        .iter()
-                                                                                        .map(|v| v.as_str().unwrap_or("").to_string())
-                                                                                        .collect();
-                                                                                    let mut list_changed = false;
+                                                                            Value::Array(arr) => {
+                                                                                if let Some(field) = field_info {
+                                                                                    if let rtsyn_plugin::ui::FieldType::DynamicList { item_type, add_label } = &field.field_type {
+                                                                                        let float_items = matches!(**item_type, rtsyn_plugin::ui::FieldType::Float { .. });
+                                                                                        let mut items: Vec<Value> = arr
+                                                                                            .iter()
+                                                                                            .map(|v| {
+                                                                                                if float_items {
+                                                                                                    Value::from(rtsyn_core::config::parse_f64(v).unwrap_or(0.0))
+                                                                                                } else {
+                                                                                                    Value::String(v.as_str().unwrap_or("").to_string())
+                                                                                                }
+                                                                                            })
+                                                                                            .collect();
+                                                                                        let mut list_changed = false;
 
-                                                                                    ui.vertical(|ui| {
-                                                                                        let mut idx = 0usize;
-                                                                                        while idx < items.len() {
-                                                                                            let mut value = items[idx].clone();
-                                                                                            let mut remove_row = false;
-                                                                                            ui.horizontal(|ui| {
-                                                                                                match &**item_type {
-                                                                                                    rtsyn_plugin::ui::FieldType::Text { .. } => {
-                                                                                                        if ui.add_sized([value_w, 0.0], egui::TextEdit::singleline(&mut value)).changed() {
-                                                                                                            items[idx] = value.clone();
-                                                                                                            list_changed = true;
+                                                                                        ui.vertical(|ui| {
+                                                                                            let mut idx = 0usize;
+                                                                                            while idx < items.len() {
+                                                                                                let mut swap_with: Option<usize> = None;
+                                                                                                let mut remove_row = false;
+                                                                                                let item_count = items.len();
+                                                                                                ui.horizontal(|ui| {
+                                                                                                    match &**item_type {
+                                                                                                        rtsyn_plugin::ui::FieldType::Text { .. } => {
+                                                                                                            let mut value = items[idx].as_str().unwrap_or("").to_string();
+                                                                                                            if ui.add_sized([value_w, 0.0], egui::TextEdit::singleline(&mut value)).changed() {
+                                                                                                                items[idx] = Value::String(value);
+                                                                                                                list_changed = true;
+                                                                                                            }
+                                                                                                        }
+                                                                                                        rtsyn_plugin::ui::FieldType::Float { step, .. } => {
+                                                                                                            let mut value = rtsyn_core::config::parse_f64(&items[idx]).unwrap_or(0.0);
+                                                                                                            if ui.add(egui::DragValue::new(&mut value).speed(*step)).changed() {
+                                                                                                                items[idx] = Value::from(value);
+                                                                                                                list_changed = true;
+                                                                                                            }
+                                                                                                        }
+                                                                                                        _ => {
+                                                                                                            ui.label("Unsupported list item type");
                                                                                                         }
                                                                                                     }
-                                                                                                    _ => {
-                                                                                                        ui.label("Unsupported list item type");
+                                                                                                    if ui.add_enabled(idx > 0, egui::Button::new("\u{f062}").small()).clicked() {
+                                                                                                        swap_with = Some(idx - 1);
+                                                                                                    }
+                                                                                                    if ui.add_enabled(idx + 1 < item_count, egui::Button::new("\u{f063}").small()).clicked() {
+                                                                                                        swap_with = Some(idx + 1);
+                                                                                                    }
+                                                                                                    if ui.small_button("X").clicked() {
+                                                                                                        remove_row = true;
                                                                                                     }
+                                                                                                });
+                                                                                                if remove_row {
+                                                                                                    items.remove(idx);
+                                                                                                    list_changed = true;
+                                                                                                } else if let Some(target) = swap_with {
+                                                                                                    items.swap(idx, target);
+                                                                                                    list_changed = true;
+                                                                                                    if plugin.kind == "csv_recorder" && var_name == "columns" {
+                                                                                                        pending_input_swap = Some((plugin.id, idx, target));
+                                                                                                    }
+                                                                                                    idx += 1;
+                                                                                                } else {
+                                                                                                    idx += 1;
                                                                                                 }
-                                                                                                if ui.small_button("X").clicked() {
-                                                                                                    remove_row = true;
+                                                                                            }
+                                                                                            if !(plugin.kind == "csv_recorder" && var_name == "columns") {
+                                                                                                if ui.small_button(add_label).clicked() {
+                                                                                                    items.push(if float_items { Value::from(0.0) } else { Value::String(String::new()) });
+                                                                                                    list_changed = true;
                                                                                                 }
-                                                                                            });
-                                                                                            if remove_row {
-                                                                                                items.remove(idx);
-                                                                                                list_changed = true;
                                                                                             } else {
-                                                                                                idx += 1;
-                                                                                            }
-                                                                                        }
-                                                                                        if !(plugin.kind == "csv_recorder" && var_name == "columns") {
-                                                                                            if ui.small_button(add_label).clicked() {
-                                                                                                items.push(String::new());
-                                                                                                list_changed = true;
+                                                                                                ui.horizontal(|ui| {
+                                                                                                    if ui
+                                                                                                        .small_button("Fill blank names")
+                                                                                                        .on_hover_text("Name blank columns after their connections")
+                                                                                                        .clicked()
+                                                                                                    {
+                                                                                                        pending_fill_columns = Some((plugin.id, false));
+                                                                                                    }
+                                                                                                    if ui
+                                                                                                        .small_button("Regenerate all")
+                                                                                                        .on_hover_text("Rename every column after its connection")
+                                                                                                        .clicked()
+                                                                                                    {
+                                                                                                        pending_fill_columns = Some((plugin.id, true));
+                                                                                                    }
+                                                                                                });
                                                                                             }
-                                                                                        }
-                                                                                    });
+                                                                                        });
 
-                                                                                    if list_changed {
-                                                                                        let new_value = Value::Array(
-                                                                                            items.iter().cloned().map(Value::String).collect()
-                                                                                        );
-                                                                                        let _ = self.state_sync.logic_tx.send(
-                                                                                            LogicMessage::SetPluginVariable(plugin.id, var_name.clone(), new_value.clone())
-                                                                                        );
-                                                                                        if let Value::Object(ref mut map) = plugin.config {
-                                                                                            map.insert(var_name.clone(), new_value);
+                                                                                        if list_changed {
+                                                                                            let new_value = Value::Array(
+                                                                                                items.clone()
+                                                                                            );
+                                                                                            var_edits.push((var_name.clone(), new_value));
                                                                                             if var_name == "columns" {
-                                                                                                map.insert("input_count".to_string(), Value::from(items.len() as u64));
-                                                                                                pending_prune = Some((plugin.id, items.len()));
-                                                                                                pending_enforce_connection = true;
+                                                                                                if let Value::Object(ref mut map) = plugin.config {
+                                                                                                    map.insert("input_count".to_string(), Value::from(items.len() as u64));
+                                                                                                    pending_prune = Some((plugin.id, items.len()));
+                                                                                                    pending_enforce_connection = true;
+                                                                                                }
                                                                                             }
-                                                                                            plugin_changed = true;
                                                                                         }
-                                                                                    }
+                                                                                }
+                                                                            }
+                                                                            }
+                                                                            _ => {}
+                                                                        }
+                                                                    });
+                                                                    ui.add_space(4.0);
+                                                                }
+                                                            }
+                                                            if apply_mode {
+                                                                if !var_edits.is_empty() {
+                                                                    self.config_drafts.entry(plugin.id).or_default().extend(var_edits);
+                                                                }
+                                                                if let Some(draft) = self.config_drafts.get(&plugin.id).cloned() {
+                                                                    let mut merged = plugin.config.clone();
+                                                                    if let Value::Object(ref mut map) = merged {
+                                                                        map.extend(draft.clone());
+                                                                    }
+                                                                    let validation = rtsyn_core::plugin::validate_config(&plugin.kind, &merged)
+                                                                        .and_then(|()| {
+                                                                            let malformed = ui_schema
+                                                                                .as_ref()
+                                                                                .map(|schema| rtsyn_core::config::malformed_numbers(&merged, schema))
+                                                                                .unwrap_or_default();
+                                                                            match malformed.first() {
+                                                                                Some(err) => Err(err.to_string()),
+                                                                                None => Ok(()),
                                                                             }
+                                                                        });
+                                                                    if let Err(err) = &validation {
+                                                                        ui.label(RichText::new(err).color(egui::Color32::from_rgb(230, 80, 80)));
+                                                                    }
+                                                                    ui.horizontal(|ui| {
+                                                                        if ui
+                                                                            .add_enabled(validation.is_ok(), egui::Button::new("Apply"))
+                                                                            .on_hover_text("Send all edits to the plugin at once")
+                                                                            .clicked()
+                                                                        {
+                                                                            let _ = self.state_sync.logic_tx.send(LogicMessage::SetPluginVariables(
+                                                                                plugin.id,
+                                                                                draft.into_iter().collect(),
+                                                                            ));
+                                                                            plugin.config = merged;
+                                                                            plugin_changed = true;
+                                                                            self.config_drafts.remove(&plugin.id);
                                                                         }
+                                                                        if ui.button("Discard").clicked() {
+                                                                            self.config_drafts.remove(&plugin.id);
                                                                         }
-                                                                        _ => {}
+                                                                    });
+                                                                }
+                                                            } else {
+                                                                for (var_name, value) in var_edits {
+                                                                    let _ = self.state_sync.logic_tx.send(LogicMessage::SetPluginVariable(
+                                                                        plugin.id,
+                                                                        var_name.clone(),
+                                                                        value.clone(),
+                                                                    ));
+                                                                    if let Value::Object(ref mut map) = plugin.config {
+                                                                        map.insert(var_name, value);
+                                                                        plugin_changed = true;
                                                                     }
+                                                                }
+                                                            }
+                                                        });
+                                                    }
+                                                
+                                                    // Inputs second
+                                                    if !schema.inputs.is_empty() {
+                                                        egui::CollapsingHeader::new(
+                                                            RichText::new("\u{f090}  Inputs").size(13.0).strong()  // sign-in icon with space
+                                                        )
+                                                        .default_open(true)
+                                                        .show(ui, |ui| {
+                                                            ui.add_space(4.0);
+                                                            for input_name in &schema.inputs {
+                                                                let value = input_values
+                                                                    .get(&(plugin.id, input_name.clone()))
+                                                                    .copied()
+                                                                    .unwrap_or(0.0);
+                                                                let mut value_text = format!("{value:.4}");
+                                                                kv_row_wrapped(ui, input_name, 140.0, |ui| {
+                                                                    ui.add_enabled_ui(false, |ui| {
+                                                                        ui.add_sized(
+                                                                            [80.0, 0.0],
+                                                                            egui::TextEdit::singleline(&mut value_text)
+                                                                        );
+                                                                    });
+                                                                    unit_label(ui, input_name);
                                                                 });
                                                                 ui.add_space(4.0);
                                                             }
-                                                        }
-                                                    });
-                                                }
+                                                        });
+                                                    }
                                                 
-                                                // Inputs second
-                                                if !schema.inputs.is_empty() {
-                                                    egui::CollapsingHeader::new(
-                                                        RichText::new("\u{f090}  Inputs").size(13.0).strong()  // sign-in icon with space
-                                                    )
-                                                    .default_open(true)
-                                                    .show(ui, |ui| {
-                                                        ui.add_space(4.0);
-                                                        for input_name in &schema.inputs {
-                                                            let value = input_values
-                                                                .get(&(plugin.id, input_name.clone()))
-                                                                .copied()
-                                                                .unwrap_or(0.0);
-                                                            let mut value_text = format!("{value:.4}");
-                                                            kv_row_wrapped(ui, input_name, 140.0, |ui| {
-                                                                ui.add_enabled_ui(false, |ui| {
-                                                                    ui.add_sized(
-                                                                        [80.0, 0.0],
-                                                                        egui::TextEdit::singleline(&mut value_text)
-                                                                    );
-                                                                });
-                                                            });
+                                                    // Outputs third
+                                                    if !schema.outputs.is_empty() {
+                                                        egui::CollapsingHeader::new(
+                                                            RichText::new("\u{f08b}  Outputs").size(13.0).strong()  // sign-out icon with space
+                                                        )
+                                                        .default_open(true)
+                                                        .show(ui, |ui| {
                                                             ui.add_space(4.0);
-                                                        }
-                                                    });
-                                                }
-                                                
-                                                // Outputs third
-                                                if !schema.outputs.is_empty() {
-                                                    egui::CollapsingHeader::new(
-                                                        RichText::new("\u{f08b}  Outputs").size(13.0).strong()  // sign-out icon with space
-                                                    )
-                                                    .default_open(true)
-                                                    .show(ui, |ui| {
-                                                        ui.add_space(4.0);
-                                                        for output_name in &schema.outputs {
-                                                            let value = computed_outputs
-                                                                .get(&(plugin.id, output_name.clone()))
-                                                                .copied()
-                                                                .unwrap_or(0.0);
-                                                            let mut value_text = if (value.fract() - 0.0).abs() < f64::EPSILON {
-                                                                format!("{value:.0}")
-                                                            } else {
-                                                                format!("{value:.4}")
-                                                            };
-                                                            kv_row_wrapped(ui, output_name, 140.0, |ui| {
-                                                                ui.add_enabled_ui(false, |ui| {
-                                                                    ui.add_sized(
-                                                                        [80.0, 0.0],
-                                                                        egui::TextEdit::singleline(&mut value_text)
-                                                                    );
+                                                            for output_name in &schema.outputs {
+                                                                let value = computed_outputs
+                                                                    .get(&(plugin.id, output_name.clone()))
+                                                                    .copied()
+                                                                    .unwrap_or(0.0);
+                                                                let mut value_text = if (value.fract() - 0.0).abs() < f64::EPSILON {
+                                                                    format!("{value:.0}")
+                                                                } else {
+                                                                    format!("{value:.4}")
+                                                                };
+                                                                let branches: Vec<&ConnectionDefinition> =
+                                                                    workspace::fan_out(&connections, plugin.id, output_name).collect();
+                                                                kv_row_wrapped(ui, output_name, 140.0, |ui| {
+                                                                    ui.add_enabled_ui(false, |ui| {
+                                                                        ui.add_sized(
+                                                                            [80.0, 0.0],
+                                                                            egui::TextEdit::singleline(&mut value_text)
+                                                                        );
+                                                                    });
+                                                                    unit_label(ui, output_name);
+                                                                    if branches.len() > 1 {
+                                                                        let targets: Vec<String> = branches
+                                                                            .iter()
+                                                                            .map(|conn| format!("#{} {}", conn.to_plugin, conn.to_port))
+                                                                            .collect();
+                                                                        ui.label(
+                                                                            RichText::new(format!("\u{f126} {}", branches.len()))
+                                                                                .size(11.0)
+                                                                                .color(egui::Color32::from_gray(170)),
+                                                                        )
+                                                                        .on_hover_text(format!(
+                                                                            "Feeds {} inputs:\n{}",
+                                                                            branches.len(),
+                                                                            targets.join("\n")
+                                                                        ));
+                                                                    }
                                                                 });
-                                                            });
-                                                            ui.add_space(4.0);
-                                                        }
-                                                    });
-                                                }
+                                                                ui.add_space(4.0);
+                                                            }
+                                                        });
+                                                    }
 
-                                                if !schema.variables.is_empty() {
-                                                    egui::CollapsingHeader::new(
-                                                        RichText::new("\u{f085}  Internal variables").size(13.0).strong()
-                                                    )
-                                                    .default_open(true)
-                                                    .show(ui, |ui| {
-                                                        ui.add_space(4.0);
-                                                        for var_name in &schema.variables {
-                                                            let value = internal_variable_values
-                                                                .get(&(plugin.id, var_name.clone()))
-                                                                .cloned()
-                                                                .unwrap_or_else(|| {
-                                                                    if matches!(plugin.kind.as_str(), "csv_recorder" | "live_plotter") {
-                                                                        match var_name.as_str() {
-                                                                            "input_count" => serde_json::Value::from(0),
-                                                                            "running" => serde_json::Value::from(false),
-                                                                            _ => serde_json::Value::from(0.0),
+                                                    let mut internal_variables = schema.variables.clone();
+                                                    if plugin.kind == "stats" {
+                                                        // One set per input, which the static schema cannot list
+                                                        let input_count = rtsyn_core::config::u64_or(&plugin.config, "input_count", 0);
+                                                        internal_variables.extend((0..input_count).flat_map(|idx| {
+                                                            ["count", "mean", "variance", "min", "max"]
+                                                                .into_iter()
+                                                                .map(move |stat| format!("in_{idx}_{stat}"))
+                                                        }));
+                                                    }
+                                                    if !internal_variables.is_empty() {
+                                                        egui::CollapsingHeader::new(
+                                                            RichText::new("\u{f085}  Internal variables").size(13.0).strong()
+                                                        )
+                                                        .default_open(true)
+                                                        .show(ui, |ui| {
+                                                            ui.add_space(4.0);
+                                                            for var_name in &internal_variables {
+                                                                let value = internal_variable_values
+                                                                    .get(&(plugin.id, var_name.clone()))
+                                                                    .cloned()
+                                                                    .unwrap_or_else(|| {
+                                                                        if matches!(plugin.kind.as_str(), "csv_recorder" | "live_plotter") {
+                                                                            match var_name.as_str() {
+                                                                                "input_count" => serde_json::Value::from(0),
+                                                                                "running" => serde_json::Value::from(false),
+                                                                                _ => serde_json::Value::from(0.0),
+                                                                            }
+                                                                        } else {
+                                                                            serde_json::Value::from(0.0)
+                                                                        }
+                                                                    });
+                                                                let mut value_text = match value {
+                                                                    serde_json::Value::Bool(v) => v.to_string(),
+                                                                    serde_json::Value::String(ref text) => text.clone(),
+                                                                    serde_json::Value::Number(ref num) => {
+                                                                        if let Some(i) = num.as_i64() {
+                                                                            i.to_string()
+                                                                        } else if let Some(u) = num.as_u64() {
+                                                                            u.to_string()
+                                                                        } else {
+                                                                            num.as_f64()
+                                                                                .map(|v| format!("{:.4}", v))
+                                                                                .unwrap_or_else(|| value.to_string())
                                                                         }
-                                                                    } else {
-                                                                        serde_json::Value::from(0.0)
-                                                                    }
-                                                                });
-                                                            let mut value_text = match value {
-                                                                serde_json::Value::Bool(v) => v.to_string(),
-                                                                serde_json::Value::Number(ref num) => {
-                                                                    if let Some(i) = num.as_i64() {
-                                                                        i.to_string()
-                                                                    } else if let Some(u) = num.as_u64() {
-                                                                        u.to_string()
-                                                                    } else {
-                                                                        num.as_f64()
-                                                                            .map(|v| format!("{:.4}", v))
-                                                                            .unwrap_or_else(|| value.to_string())
                                                                     }
-                                                                }
-                                                                _ => value.to_string(),
-                                                            };
-                                                            kv_row_wrapped(ui, var_name, 140.0, |ui| {
-                                                                ui.add_enabled_ui(false, |ui| {
-                                                                    ui.add_sized(
-                                                                        [80.0, 0.0],
-                                                                        egui::TextEdit::singleline(&mut value_text)
-                                                                    );
+                                                                    _ => value.to_string(),
+                                                                };
+                                                                kv_row_wrapped(ui, var_name, 140.0, |ui| {
+                                                                    ui.add_enabled_ui(false, |ui| {
+                                                                        ui.add_sized(
+                                                                            [80.0, 0.0],
+                                                                            egui::TextEdit::singleline(&mut value_text)
+                                                                        );
+                                                                    });
                                                                 });
-                                                            });
-                                                            ui.add_space(4.0);
-                                                        }
-                                                    });
+                                                                ui.add_space(4.0);
+                                                            }
+                                                        });
+                                                    }
                                                 }
-                                            }
 
-                                        if plugin.kind == "value_viewer" {
-                                            let value =
-                                                viewer_values.get(&plugin.id).copied().unwrap_or(0.0);
-                                            ui.add_space(4.0);
-                                            ui.separator();
-                                            ui.label(RichText::new("Last value").strong());
-                                            ui.add_space(4.0);
-                                            let mut value_text = format!("{value:.4}");
-                                            ui.add_enabled(
-                                                false,
-                                                egui::TextEdit::singleline(&mut value_text)
-                                                    .desired_width(80.0),
-                                            );
-                                        }
-                                        });  // close push_id
-                                    });  // close ScrollArea.show
-                            });  // close scope
+                                            if plugin.kind == "value_viewer" {
+                                                let value =
+                                                    viewer_values.get(&plugin.id).copied().unwrap_or(0.0);
+                                                ui.add_space(4.0);
+                                                ui.separator();
+                                                ui.label(RichText::new("Last value").strong());
+                                                ui.add_space(4.0);
+                                                let mut value_text = format!("{value:.4}");
+                                                ui.add_enabled(
+                                                    false,
+                                                    egui::TextEdit::singleline(&mut value_text)
+                                                        .desired_width(80.0),
+                                                );
+                                            }
+                                            });  // close push_id
+                                        });  // close ScrollArea.show
+                                });  // close scope
+                            }
                             
                             // Controls at bottom
                             ui.add_space(8.0);
@@ -778,6 +1347,14 @@ impl GuiApp {
                                                         map.insert("path".to_string(), Value::String(path));
                                                     }
                                                 }
+                                                if !blocked_start
+                                                    && !plugin.running
+                                                    && rtsyn_runtime::preflight::needs_start_check(&plugin.kind)
+                                                {
+                                                    // Started once the device check passes
+                                                    pending_device_starts.push(plugin.id);
+                                                    blocked_start = true;
+                                                }
                                                 if !blocked_start {
                                                     plugin.running = !plugin.running;
                                                     pending_running.push((plugin.id, plugin.running));
@@ -808,6 +1385,11 @@ impl GuiApp {
                                                 pending_restart.push(plugin.id);
                                             }
                                         }
+                                        if open_errors.contains_key(&plugin.id)
+                                            && ui.button("Retry open").clicked()
+                                        {
+                                            pending_retry_open.push(plugin.id);
+                                        }
                                     });
                                     
                                     if controls_changed {
@@ -817,9 +1399,11 @@ impl GuiApp {
                     });
                 });
 
+            ctx.set_transform_layer(response.response.layer_id, canvas_transform);
             self.plugin_positions
                 .insert(plugin.id, response.response.rect.min);
-            self.plugin_rects.insert(plugin.id, response.response.rect);
+            self.plugin_rects
+                .insert(plugin.id, canvas_transform * response.response.rect);
             if ctx.input(|i| i.pointer.button_double_clicked(egui::PointerButton::Primary)) {
                 if response.response.hovered() && !self.confirm_dialog.open {
                     // Toggle selection
@@ -833,6 +1417,12 @@ impl GuiApp {
             if response.response.clicked() || response.response.dragged() {
                 ctx.move_to_top(response.response.layer_id);
             }
+            if response.response.clicked()
+                && ctx.input(|i| i.modifiers.command || i.modifiers.shift)
+                && !self.selected_plugin_ids.remove(&plugin.id)
+            {
+                self.selected_plugin_ids.insert(plugin.id);
+            }
             if ctx.input(|i| i.pointer.button_released(egui::PointerButton::Secondary)) {
                 if let Some(pos) = ctx.input(|i| i.pointer.interact_pos()) {
                     if response.response.rect.contains(pos) && response.response.hovered() {
@@ -850,9 +1440,10 @@ impl GuiApp {
             index += 1;
         }
         if pending_workspace_update {
-            let _ = self
-            .state_sync.logic_tx
-                .send(LogicMessage::UpdateWorkspace(self.workspace_manager.workspace.clone()));
+            self.sync_workspace_to_runtime();
+        }
+        for plugin_id in pending_device_starts {
+            self.start_device_checked(plugin_id);
         }
         for (plugin_id, running) in pending_running {
             // Mark plugin as stopped BEFORE sending message to prevent one more update
@@ -872,6 +1463,12 @@ impl GuiApp {
         for plugin_id in pending_restart {
             self.restart_plugin(plugin_id);
         }
+        for plugin_id in pending_retry_open {
+            self.retry_open(plugin_id);
+        }
+        if let Some(plugin_id) = pending_locate_library {
+            self.request_plugin_library(plugin_id);
+        }
         if let Some((plugin_id, count)) = pending_prune {
             prune_extendable_inputs_plugin_connections(
                 &mut self.workspace_manager.workspace.connections,
@@ -879,6 +1476,19 @@ impl GuiApp {
                 count,
             );
         }
+        if let Some((plugin_id, a, b)) = pending_input_swap {
+            swap_extendable_inputs(&mut self.workspace_manager.workspace.connections, plugin_id, a, b);
+        }
+        match pending_fill_columns {
+            Some((plugin_id, false)) => self.fill_csv_columns(plugin_id, false),
+            Some((plugin_id, true)) => self.show_confirm(
+                "Regenerate column names",
+                "Replace every column name, including edited ones, with the default for its connection?",
+                "Regenerate",
+                ConfirmAction::RegenerateCsvColumns(plugin_id),
+            ),
+            None => {}
+        }
         if pending_enforce_connection {
             self.enforce_connection_dependent();
         }
@@ -886,7 +1496,7 @@ impl GuiApp {
             self.mark_workspace_dirty();
         }
 
-        if let Some(id) = remove_id {
+        if let Some(id) = remove_id.filter(|_| !self.reject_if_locked()) {
             let name_by_kind: HashMap<String, String> = self
             .plugin_manager.installed_plugins
                 .iter()
@@ -956,7 +1566,10 @@ impl GuiApp {
                         .unwrap_or_default()
                 });
                 let mut inputs_label = inputs.join(", ");
-                let is_extendable = matches!(plugin_kind, "csv_recorder" | "live_plotter");
+                let is_extendable = matches!(
+                    plugin_kind,
+                    "csv_recorder" | "live_plotter" | "tcp_stream" | "shm_publisher" | "stats" | "fault_recorder"
+                );
                 if is_extendable {
                     if inputs_label.is_empty() {
                         inputs_label = "incremental".to_string();
@@ -1008,11 +1621,7 @@ impl GuiApp {
             .plugins
             .iter()
             .find(|p| p.kind == "live_plotter")?;
-        let count = plugin
-            .config
-            .get("input_count")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(1) as usize;
+        let count = rtsyn_core::config::u64_or(&plugin.config, "input_count", 1) as usize;
         Some((0..count).map(|idx| format!("in_{idx}")).collect())
     }
 
@@ -1073,10 +1682,11 @@ impl GuiApp {
             .iter()
             .map(|plugin| (plugin.manifest.kind.clone(), plugin.metadata_variables.clone()))
             .collect();
+        let icons = self.kind_icons(ctx);
 
         let mut window_open = self.windows.plugins_open;
         let window_size = egui::vec2(700.0, 420.0);
-        let default_pos = Self::center_window(ctx, window_size);
+        let default_pos = self.window_placement(ctx, "Plugins", window_size).min;
         let response = egui::Window::new("Add plugins")
             .open(&mut window_open)
             .resizable(false)
@@ -1120,15 +1730,18 @@ impl GuiApp {
                                         {
                                             continue;
                                         }
-                                        if ui
-                                            .selectable_label(
-                                                self.windows.plugin_selected_index == Some(idx),
-                                                label,
-                                            )
-                                            .clicked()
-                                        {
-                                            selected = Some(idx);
-                                        }
+                                        ui.horizontal(|ui| {
+                                            paint_kind_icon(ui, icons.get(&installed.manifest.kind), 16.0);
+                                            if ui
+                                                .selectable_label(
+                                                    self.windows.plugin_selected_index == Some(idx),
+                                                    label,
+                                                )
+                                                .clicked()
+                                            {
+                                                selected = Some(idx);
+                                            }
+                                        });
                                     }
                                 });
                             if let Some(idx) = selected {
@@ -1158,6 +1771,7 @@ impl GuiApp {
                     }
                     PluginTab::Organize => {
                         let mut pending_csv_prune: Option<(u64, usize)> = None;
+                        let mut pending_csv_swap: Option<(u64, usize, usize)> = None;
                         ui.columns(2, |columns| {
                             columns[0].label("Search");
                             columns[0].text_edit_singleline(&mut self.windows.organize_search);
@@ -1195,6 +1809,7 @@ impl GuiApp {
                             }
 
                             columns[1].label("Edit");
+                            columns[1].set_enabled(!self.workspace_manager.workspace.settings.locked);
                             if let Some(idx) = self.windows.organize_selected_index {
                                 let mut plugin_changed = false;
                                 if let Some(plugin) = self.workspace_manager.workspace.plugins.get_mut(idx) {
@@ -1203,10 +1818,7 @@ impl GuiApp {
                                         .cloned()
                                         .unwrap_or_else(|| Self::display_kind(&plugin.kind));
                                     columns[1].label(format!("#{} {}", plugin.id, display_name));
-                                    let is_app_plugin = matches!(
-                                        plugin.kind.as_str(),
-                                        "csv_recorder" | "live_plotter" | "performance_monitor" | "comedi_daq"
-                                    );
+                                    let is_app_plugin = is_app_plugin_kind(&plugin.kind);
                                     if is_app_plugin {
                                         if let Some(installed) = self.plugin_manager.installed_plugins.iter().find(|p| p.manifest.kind == plugin.kind) {
                                             if let Some(schema) = &installed.display_schema {
@@ -1222,6 +1834,10 @@ impl GuiApp {
                                                         if let Ok(Some(value)) = rx.recv() {
                                                             let field_info = installed.ui_schema.as_ref()
                                                                 .and_then(|schema| schema.fields.iter().find(|f| f.key == *var_name));
+                                                            let value = match field_info {
+                                                                Some(field) => rtsyn_core::config::normalize_number(value, &field.field_type),
+                                                                None => value,
+                                                            };
                                                             let label = field_info
                                                                 .map(|field| field.label.as_str())
                                                                 .unwrap_or(var_name.as_str());
@@ -1233,7 +1849,26 @@ impl GuiApp {
                                                                     Value::String(s) => {
                                                                         let mut text = s.clone();
                                                                         let width = if is_filepath { 80.0 } else { 80.0 };
-                                                                        if ui.add(egui::TextEdit::singleline(&mut text).desired_width(width)).changed() {
+                                                                        if let Some(choices) = config_choices(&plugin.kind, var_name) {
+                                                                            let mut selected = text.clone();
+                                                                            egui::ComboBox::from_id_source(("organize_choice", plugin.id, var_name.as_str()))
+                                                                                .width(width)
+                                                                                .selected_text(selected.clone())
+                                                                                .show_ui(ui, |ui| {
+                                                                                    for choice in choices {
+                                                                                        ui.selectable_value(&mut selected, choice.to_string(), *choice);
+                                                                                    }
+                                                                                });
+                                                                            if selected != text {
+                                                                                let _ = self.state_sync.logic_tx.send(
+                                                                                    LogicMessage::SetPluginVariable(plugin.id, var_name.clone(), Value::String(selected.clone())),
+                                                                                );
+                                                                                if let Value::Object(ref mut map) = plugin.config {
+                                                                                    map.insert(var_name.clone(), Value::String(selected));
+                                                                                    plugin_changed = true;
+                                                                                }
+                                                                            }
+                                                                        } else if ui.add(egui::TextEdit::singleline(&mut text).desired_width(width)).changed() {
                                                                             let new_text = text.clone();
                                                                             let _ = self.state_sync.logic_tx.send(
                                                                                 LogicMessage::SetPluginVariable(plugin.id, var_name.clone(), Value::String(new_text.clone())),
@@ -1335,22 +1970,38 @@ impl GuiApp {
                                                                     Value::Array(arr) => {
                                                                         if let Some(field) = field_info {
                                                                             if let rtsyn_plugin::ui::FieldType::DynamicList { item_type, add_label } = &field.field_type {
-                                                                                let mut items: Vec<String> = arr
+                                                                                let float_items = matches!(**item_type, rtsyn_plugin::ui::FieldType::Float { .. });
+                                                                                let mut items: Vec<Value> = arr
                                                                                     .iter()
-                                                                                    .map(|v| v.as_str().unwrap_or("").to_string())
+                                                                                    .map(|v| {
+                                                                                        if float_items {
+                                                                                            Value::from(rtsyn_core::config::parse_f64(v).unwrap_or(0.0))
+                                                                                        } else {
+                                                                                            Value::String(v.as_str().unwrap_or("").to_string())
+                                                                                        }
+                                                                                    })
                                                                                     .collect();
                                                                                 let mut list_changed = false;
 
                                                                                 ui.vertical(|ui| {
                                                                                     let mut idx = 0usize;
                                                                                     while idx < items.len() {
-                                                                                        let mut value = items[idx].clone();
+                                                                                        let mut swap_with: Option<usize> = None;
                                                                                         let mut remove_row = false;
+                                                                                        let item_count = items.len();
                                                                                         ui.horizontal(|ui| {
                                                                                             match &**item_type {
                                                                                                 rtsyn_plugin::ui::FieldType::Text { .. } => {
+                                                                                                    let mut value = items[idx].as_str().unwrap_or("").to_string();
                                                                                                     if ui.add(egui::TextEdit::singleline(&mut value).desired_width(140.0)).changed() {
-                                                                                                        items[idx] = value.clone();
+                                                                                                        items[idx] = Value::String(value);
+                                                                                                        list_changed = true;
+                                                                                                    }
+                                                                                                }
+                                                                                                rtsyn_plugin::ui::FieldType::Float { step, .. } => {
+                                                                                                    let mut value = rtsyn_core::config::parse_f64(&items[idx]).unwrap_or(0.0);
+                                                                                                    if ui.add(egui::DragValue::new(&mut value).speed(*step)).changed() {
+                                                                                                        items[idx] = Value::from(value);
                                                                                                         list_changed = true;
                                                                                                     }
                                                                                                 }
@@ -1358,6 +2009,12 @@ impl GuiApp {
                                                                                                     ui.label("Unsupported list item type");
                                                                                                 }
                                                                                             }
+                                                                                            if ui.add_enabled(idx > 0, egui::Button::new("\u{f062}").small()).clicked() {
+                                                                                                swap_with = Some(idx - 1);
+                                                                                            }
+                                                                                            if ui.add_enabled(idx + 1 < item_count, egui::Button::new("\u{f063}").small()).clicked() {
+                                                                                                swap_with = Some(idx + 1);
+                                                                                            }
                                                                                             if ui.small_button("X").clicked() {
                                                                                                 remove_row = true;
                                                                                             }
@@ -1365,19 +2022,26 @@ impl GuiApp {
                                                                                         if remove_row {
                                                                                             items.remove(idx);
                                                                                             list_changed = true;
+                                                                                        } else if let Some(target) = swap_with {
+                                                                                            items.swap(idx, target);
+                                                                                            list_changed = true;
+                                                                                            if plugin.kind == "csv_recorder" && var_name == "columns" {
+                                                                                                pending_csv_swap = Some((plugin.id, idx, target));
+                                                                                            }
+                                                                                            idx += 1;
                                                                                         } else {
                                                                                             idx += 1;
                                                                                         }
                                                                                     }
                                                                                     if ui.small_button(add_label).clicked() {
-                                                                                        items.push(String::new());
+                                                                                        items.push(if float_items { Value::from(0.0) } else { Value::String(String::new()) });
                                                                                         list_changed = true;
                                                                                     }
                                                                                 });
 
                                                                                 if list_changed {
                                                                                     let new_value = Value::Array(
-                                                                                        items.clone().into_iter().map(Value::String).collect()
+                                                                                        items.clone()
                                                                                     );
                                                                                     let _ = self.state_sync.logic_tx.send(
                                                                                         LogicMessage::SetPluginVariable(plugin.id, var_name.clone(), new_value.clone())
@@ -1427,7 +2091,7 @@ impl GuiApp {
                                                                         .entry(buffer_key)
                                                                         .or_insert_with(|| {
                                                                             format_f64_6(
-                                                                                value.as_f64().unwrap_or(0.0),
+                                                                                rtsyn_core::config::parse_f64(value).unwrap_or(0.0),
                                                                             )
                                                                         });
                                                                     let resp = ui.add(
@@ -1483,6 +2147,14 @@ impl GuiApp {
                                 columns[1].label("Select a plugin to edit.");
                             }
                         });
+                        if let Some((id, a, b)) = pending_csv_swap {
+                            swap_extendable_inputs(
+                                &mut self.workspace_manager.workspace.connections,
+                                id,
+                                a,
+                                b,
+                            );
+                        }
                         if let Some((id, count)) = pending_csv_prune {
                             prune_extendable_inputs_plugin_connections(
                                 &mut self.workspace_manager.workspace.connections,
@@ -1495,7 +2167,7 @@ impl GuiApp {
                 }
             });
         if let Some(response) = response {
-            self.window_rects.push(response.response.rect);
+            self.track_window("Plugins", response.response.rect);
             if !self.confirm_dialog.open
                 && (response.response.clicked() || response.response.dragged())
             {
@@ -1516,14 +2188,31 @@ impl GuiApp {
 
         let mut window_open = self.windows.manage_plugins_open;
         let window_size = egui::vec2(700.0, 400.0);
-        let default_pos = Self::center_window(ctx, window_size);
+        let default_pos = self.window_placement(ctx, "ManagePlugins", window_size).min;
         let response = egui::Window::new("Manage plugins")
             .open(&mut window_open)
             .resizable(false)
             .default_pos(default_pos)
             .default_size(window_size)
             .fixed_size(window_size)
-            .show(ctx, |ui| match self.windows.manage_plugins_tab {
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui
+                        .selectable_label(matches!(self.windows.manage_plugins_tab, ManageTab::Install), "Install")
+                        .clicked()
+                    {
+                        self.windows.manage_plugins_tab = ManageTab::Install;
+                    }
+                    if ui
+                        .selectable_label(matches!(self.windows.manage_plugins_tab, ManageTab::Paths), "Plugin paths")
+                        .clicked()
+                    {
+                        self.windows.manage_plugins_tab = ManageTab::Paths;
+                    }
+                });
+                ui.separator();
+
+                match self.windows.manage_plugins_tab {
                 ManageTab::Install => {
                     let mut rescan = false;
                     let installed_kinds: HashSet<String> = self
@@ -1594,6 +2283,7 @@ impl GuiApp {
 
                         let mut install_selected: Option<(BuildAction, String)> = None;
                         let mut uninstall_selected: Option<usize> = None;
+                        let mut benchmark_selected: Option<usize> = None;
                         let mut reinstall_selected: Option<(BuildAction, String)> = None;
                         if let Some(idx) = self.windows.manage_selected_index {
                             if let Some(detected) = self.plugin_manager.detected_plugins.get(idx) {
@@ -1665,6 +2355,21 @@ impl GuiApp {
                                         {
                                             uninstall_selected = Some(installed_idx);
                                         }
+                                        let has_library = self
+                                            .plugin_manager
+                                            .installed_plugins
+                                            .get(installed_idx)
+                                            .is_some_and(|p| p.library_path.is_some());
+                                        if ui
+                                            .add_enabled(
+                                                has_library && self.build_dialog.benchmark_rx.is_none(),
+                                                egui::Button::new("Benchmark"),
+                                            )
+                                            .on_hover_text("Time 10000 process() calls with the default config")
+                                            .clicked()
+                                        {
+                                            benchmark_selected = Some(installed_idx);
+                                        }
                                     });
                                 }
                             }
@@ -1677,6 +2382,9 @@ impl GuiApp {
                         if let Some((action, label)) = reinstall_selected {
                             self.start_plugin_build(action, label);
                         }
+                        if let Some(idx) = benchmark_selected {
+                            self.start_plugin_benchmark(idx);
+                        }
                         if let Some(idx) = uninstall_selected {
                             self.show_confirm(
                                 "Uninstall plugin",
@@ -1692,9 +2400,38 @@ impl GuiApp {
                         self.scan_detected_plugins();
                     }
                 }
+                ManageTab::Paths => {
+                    ui.label("Folders searched for plugins and their libraries, in order.");
+                    ui.add_space(6.0);
+                    let mut remove_path = None;
+                    let list_height = (ui.available_height() - 40.0).max(40.0);
+                    egui::ScrollArea::vertical()
+                        .max_height(list_height)
+                        .show(ui, |ui| {
+                            if self.plugin_manager.search_paths.is_empty() {
+                                ui.label("No plugin paths configured.");
+                            }
+                            for path in &self.plugin_manager.search_paths {
+                                ui.horizontal(|ui| {
+                                    if ui.button("Remove").clicked() {
+                                        remove_path = Some(path.clone());
+                                    }
+                                    ui.label(path.display().to_string());
+                                });
+                            }
+                        });
+                    ui.separator();
+                    if ui.button("Add folder...").clicked() {
+                        self.open_plugin_path_dialog();
+                    }
+                    if let Some(path) = remove_path {
+                        self.remove_plugin_search_path(&path);
+                    }
+                }
+                }
             });
         if let Some(response) = response {
-            self.window_rects.push(response.response.rect);
+            self.track_window("ManagePlugins", response.response.rect);
             if !self.confirm_dialog.open
                 && (response.response.clicked() || response.response.dragged())
             {
@@ -1714,6 +2451,14 @@ impl GuiApp {
             return;
         };
 
+        let is_device = self
+            .workspace_manager
+            .workspace
+            .plugins
+            .iter()
+            .find(|p| p.id == plugin_id)
+            .is_some_and(|p| rtsyn_runtime::preflight::needs_start_check(&p.kind));
+
         let mut close_menu = false;
         let menu_response = egui::Area::new(egui::Id::new("plugin_context_menu"))
             .order(egui::Order::Foreground)
@@ -1750,6 +2495,20 @@ impl GuiApp {
                         self.open_connection_editor(plugin_id, ConnectionEditMode::Remove);
                         close_menu = true;
                     }
+                    let inspect_clicked = ui
+                        .allocate_ui_with_layout(
+                            egui::vec2(menu_width, row_height),
+                            egui::Layout::left_to_right(egui::Align::Center),
+                            |ui| {
+                                ui.add(egui::SelectableLabel::new(false, "Inspect connections"))
+                                    .clicked()
+                            },
+                        )
+                        .inner;
+                    if inspect_clicked {
+                        self.open_connection_inspector(plugin_id);
+                        close_menu = true;
+                    }
                     let config_clicked = ui
                         .allocate_ui_with_layout(
                             egui::vec2(menu_width, row_height),
@@ -1766,6 +2525,36 @@ impl GuiApp {
                         close_menu = true;
                         self.pending_window_focus = Some(WindowFocus::PluginConfig);
                     }
+                    let self_test_clicked = ui
+                        .allocate_ui_with_layout(
+                            egui::vec2(menu_width, row_height),
+                            egui::Layout::left_to_right(egui::Align::Center),
+                            |ui| {
+                                ui.add(egui::SelectableLabel::new(false, "Self-test"))
+                                    .clicked()
+                            },
+                        )
+                        .inner;
+                    if self_test_clicked {
+                        self.run_self_test(plugin_id);
+                        close_menu = true;
+                    }
+                    if is_device {
+                        let test_clicked = ui
+                            .allocate_ui_with_layout(
+                                egui::vec2(menu_width, row_height),
+                                egui::Layout::left_to_right(egui::Align::Center),
+                                |ui| {
+                                    ui.add(egui::SelectableLabel::new(false, "Test device"))
+                                        .clicked()
+                                },
+                            )
+                            .inner;
+                        if test_clicked {
+                            self.open_device_test(plugin_id);
+                            close_menu = true;
+                        }
+                    }
                     let duplicate_clicked = ui
                         .allocate_ui_with_layout(
                             egui::vec2(menu_width, row_height),
@@ -1799,6 +2588,123 @@ impl GuiApp {
         }
     }
 
+    fn open_device_test(&mut self, plugin_id: u64) {
+        if self.device_test.plugin_id != Some(plugin_id) {
+            self.device_test = Default::default();
+            self.device_test.plugin_id = Some(plugin_id);
+        }
+        self.pending_window_focus = Some(WindowFocus::DeviceTest);
+    }
+
+    /// Opens the device of a driver plugin once, outside of the graph, and
+    /// lists what every channel read back, so wiring can be checked before
+    /// the plugin is started.
+    pub(crate) fn render_device_test_window(&mut self, ctx: &egui::Context) {
+        let Some(plugin_id) = self.device_test.plugin_id else {
+            return;
+        };
+        let Some(running) = self
+            .workspace_manager
+            .workspace
+            .plugins
+            .iter()
+            .find(|p| p.id == plugin_id)
+            .map(|p| p.running)
+        else {
+            self.device_test = Default::default();
+            return;
+        };
+        let plugin_name = self.plugin_display_name(plugin_id);
+        let testing = self.device_test.rx.is_some();
+
+        let mut open = true;
+        let mut run_clicked = false;
+        let window_size = egui::vec2(380.0, 300.0);
+        let default_pos = self.window_placement(ctx, "DeviceTest", window_size).min;
+        let response = egui::Window::new("Test device")
+            .open(&mut open)
+            .resizable(false)
+            .default_pos(default_pos)
+            .default_size(window_size)
+            .fixed_size(window_size)
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(format!("#{plugin_id} {plugin_name}"))
+                        .strong()
+                        .size(16.0),
+                );
+                ui.separator();
+                ui.checkbox(&mut self.device_test.write_safe, "Write safe value to outputs");
+                ui.horizontal(|ui| {
+                    let response = ui.add_enabled(
+                        !running && !testing,
+                        egui::Button::new("Run test"),
+                    );
+                    run_clicked = response.clicked();
+                    if running {
+                        response.on_disabled_hover_text("Stop the plugin to test its device");
+                    }
+                    if testing {
+                        ui.spinner();
+                    }
+                });
+                ui.separator();
+                match &self.device_test.result {
+                    None => {
+                        ui.label("Opens the device, reads every input once and closes it.");
+                    }
+                    Some(Err(err)) => {
+                        ui.label(RichText::new(err).color(egui::Color32::from_rgb(230, 80, 80)));
+                    }
+                    Some(Ok(results)) if results.is_empty() => {
+                        ui.label("No channels are mapped.");
+                    }
+                    Some(Ok(results)) => {
+                        egui::ScrollArea::vertical().show(ui, |ui| {
+                            egui::Grid::new(("device_test_grid", plugin_id))
+                                .num_columns(2)
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for (channel, value) in results {
+                                        ui.label(channel);
+                                        match value {
+                                            Ok(value) => ui.monospace(format!("{value}")),
+                                            Err(err) => ui.label(
+                                                RichText::new(err)
+                                                    .color(egui::Color32::from_rgb(230, 80, 80)),
+                                            ),
+                                        };
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                    }
+                }
+            });
+
+        if let Some(response) = response {
+            self.track_window("DeviceTest", response.response.rect);
+            if !self.confirm_dialog.open
+                && (response.response.clicked() || response.response.dragged())
+            {
+                ctx.move_to_top(response.response.layer_id);
+            }
+            if self.pending_window_focus == Some(WindowFocus::DeviceTest) {
+                ctx.move_to_top(response.response.layer_id);
+                self.pending_window_focus = None;
+            }
+        }
+
+        if run_clicked {
+            self.start_device_test();
+        }
+        if !open {
+            // A test still running finishes on its own thread; its result
+            // is dropped
+            self.device_test = Default::default();
+        }
+    }
+
     pub(crate) fn render_plugin_config_window(&mut self, ctx: &egui::Context) {
         if !self.windows.plugin_config_open {
             return;
@@ -1826,19 +2732,23 @@ impl GuiApp {
                 } else if plugin.kind == "live_plotter" {
                     egui::vec2(420.0, 240.0)
                 } else {
-                    egui::vec2(320.0, 180.0)
+                    egui::vec2(320.0, 220.0)
                 }
             } else {
                 egui::vec2(320.0, 180.0)
             };
-        let default_pos = Self::center_window(ctx, window_size);
+        // Each plugin's config window keeps its own place
+        let layout_key = WindowLayout::key("PluginConfig", plugin_id);
+        let default_pos = self.window_placement(ctx, &layout_key, window_size).min;
         let response = egui::Window::new("Plugin config")
+            .id(egui::Id::new(("plugin_config", plugin_id)))
             .open(&mut open)
             .resizable(false)
             .default_pos(default_pos)
             .default_size(window_size)
             .fixed_size(window_size)
             .show(ctx, |ui| {
+                ui.set_enabled(!self.workspace_manager.workspace.settings.locked);
                 let plugin_index = self
             .workspace_manager.workspace
                     .plugins
@@ -1851,7 +2761,7 @@ impl GuiApp {
                         .cloned()
                         .unwrap_or_else(|| Self::display_kind(&plugin_kind));
                     let mut priority = self.workspace_manager.workspace.plugins[plugin_index].priority;
-                    let config = self.workspace_manager.workspace.plugins[plugin_index].config.clone();
+                    let mut config = self.workspace_manager.workspace.plugins[plugin_index].config.clone();
                     let mut config_changed = false;
                     let pending_start: Option<bool> = None;
 
@@ -1870,7 +2780,7 @@ impl GuiApp {
                             egui::FontId::proportional(12.0),
                             egui::Color32::from_rgb(200, 200, 210),
                         );
-                        ui.label(RichText::new(display_name).strong().size(16.0));
+                        ui.label(RichText::new(display_name.clone()).strong().size(16.0));
                     });
                     ui.add_space(6.0);
                     let label_w = 140.0;
@@ -1878,6 +2788,7 @@ impl GuiApp {
                     kv_row_wrapped(ui, "Priority", label_w, |ui| {
                         if ui
                             .add_sized([value_w, 0.0], egui::DragValue::new(&mut priority).speed(1))
+                            .on_hover_text("Lower priorities run first within a tick; equal priorities follow connections, then plugin id")
                             .changed()
                         {
                             config_changed = true;
@@ -1890,6 +2801,108 @@ impl GuiApp {
                         priority = 99;
                         config_changed = true;
                     }
+                    // Edited untrimmed so spaces can be typed; blank means no label
+                    let mut label = config
+                        .get(rtsyn_core::plugin::UI_LABEL_KEY)
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default()
+                        .to_string();
+                    let mut color = rtsyn_core::plugin::custom_color(&config);
+                    let mut appearance_changed = false;
+                    kv_row_wrapped(ui, "Label", label_w, |ui| {
+                        if ui
+                            .add_sized(
+                                [160.0, 0.0],
+                                egui::TextEdit::singleline(&mut label).hint_text(display_name.as_str()),
+                            )
+                            .on_hover_text("Name shown on the card instead of the plugin name")
+                            .changed()
+                        {
+                            appearance_changed = true;
+                        }
+                    });
+                    kv_row_wrapped(ui, "Accent color", label_w, |ui| {
+                        let mut rgb = color.unwrap_or([90, 90, 100]);
+                        if ui.color_edit_button_srgb(&mut rgb).changed() {
+                            color = Some(rgb);
+                            appearance_changed = true;
+                        }
+                        if color.is_some() && ui.small_button("Reset").clicked() {
+                            color = None;
+                            appearance_changed = true;
+                        }
+                    });
+                    if appearance_changed {
+                        if let Value::Object(ref mut map) = config {
+                            if label.trim().is_empty() {
+                                map.remove(rtsyn_core::plugin::UI_LABEL_KEY);
+                            } else {
+                                map.insert(
+                                    rtsyn_core::plugin::UI_LABEL_KEY.to_string(),
+                                    Value::from(label),
+                                );
+                            }
+                            match color {
+                                Some(rgb) => map.insert(
+                                    rtsyn_core::plugin::UI_COLOR_KEY.to_string(),
+                                    Value::from(rtsyn_core::plugin::format_color(rgb)),
+                                ),
+                                None => map.remove(rtsyn_core::plugin::UI_COLOR_KEY),
+                            };
+                            config_changed = true;
+                        }
+                    }
+                    let outputs = self
+                        .plugin_manager
+                        .installed_plugins
+                        .iter()
+                        .find(|installed| installed.manifest.kind == plugin_kind)
+                        .map(|installed| installed.metadata_outputs.clone())
+                        .unwrap_or_default();
+                    if !outputs.is_empty() {
+                        let mut faults: Vec<String> = outputs
+                            .iter()
+                            .filter(|port| rtsyn_core::plugin::is_plugin_fault_output(&config, port))
+                            .cloned()
+                            .collect();
+                        let mut faults_changed = false;
+                        kv_row_wrapped(ui, "Fault outputs", label_w, |ui| {
+                            ui.horizontal_wrapped(|ui| {
+                                for port in &outputs {
+                                    let mut is_fault = faults.contains(port);
+                                    if ui
+                                        .checkbox(&mut is_fault, port.as_str())
+                                        .on_hover_text("Shown in the fault strip while non-zero")
+                                        .changed()
+                                    {
+                                        faults.retain(|fault| fault != port);
+                                        if is_fault {
+                                            faults.push(port.clone());
+                                        }
+                                        faults_changed = true;
+                                    }
+                                }
+                            });
+                        });
+                        if faults_changed {
+                            if let Value::Object(ref mut map) = config {
+                                map.insert(
+                                    rtsyn_core::plugin::FAULT_OUTPUTS_KEY.to_string(),
+                                    Value::from(faults),
+                                );
+                                config_changed = true;
+                            }
+                        }
+                    }
+                    ui.add_space(6.0);
+                    ui.horizontal(|ui| {
+                        if ui.button("Export config").clicked() {
+                            self.request_plugin_config_export(plugin_id);
+                        }
+                        if ui.button("Import config").clicked() {
+                            self.request_plugin_config_import(plugin_id);
+                        }
+                    });
 
                     if config_changed {
                         self.workspace_manager.workspace.plugins[plugin_index].priority = priority;
@@ -1907,7 +2920,7 @@ impl GuiApp {
                 }
             });
         if let Some(response) = response {
-            self.window_rects.push(response.response.rect);
+            self.track_window(&layout_key, response.response.rect);
             if !self.confirm_dialog.open
                 && (response.response.clicked() || response.response.dragged())
             {
@@ -0,0 +1,141 @@
+use super::*;
+
+struct SignalRow {
+    plugin: String,
+    port: String,
+    direction: &'static str,
+    value: f64,
+    unit: Option<String>,
+}
+
+impl GuiApp {
+    /// Unit of an input: its own declared unit, else the unit of the first
+    /// output feeding it.
+    fn input_port_unit(&self, plugin_id: u64, port: &str) -> Option<String> {
+        let own = rtsyn_core::plugin::port_unit(
+            &self.plugin_manager.installed_plugins,
+            &self.workspace_manager.workspace,
+            plugin_id,
+            port,
+        );
+        own.or_else(|| {
+            let conn = self
+                .workspace_manager
+                .workspace
+                .connections
+                .iter()
+                .find(|conn| conn.to_plugin == plugin_id && conn.to_port == port)?;
+            rtsyn_core::plugin::port_unit(
+                &self.plugin_manager.installed_plugins,
+                &self.workspace_manager.workspace,
+                conn.from_plugin,
+                &conn.from_port,
+            )
+        })
+        .map(str::to_string)
+    }
+
+    /// Every output and input value of the running plugins, in workspace
+    /// order, matching `filter` on the plugin name or port.
+    fn signal_rows(&self, filter: &str) -> Vec<SignalRow> {
+        let filter = filter.trim().to_lowercase();
+        let mut rows = Vec::new();
+        for plugin in &self.workspace_manager.workspace.plugins {
+            let name = self.plugin_display_name(plugin.id);
+            let name_matches = name.to_lowercase().contains(&filter);
+            let ports = |values: &HashMap<(u64, String), f64>| {
+                let mut ports: Vec<(String, f64)> = values
+                    .iter()
+                    .filter(|((id, port), _)| {
+                        *id == plugin.id
+                            && (name_matches || port.to_lowercase().contains(&filter))
+                    })
+                    .map(|((_, port), value)| (port.clone(), *value))
+                    .collect();
+                ports.sort_by(|a, b| a.0.cmp(&b.0));
+                ports
+            };
+            for (port, value) in ports(&self.state_sync.computed_outputs) {
+                let unit = rtsyn_core::plugin::port_unit(
+                    &self.plugin_manager.installed_plugins,
+                    &self.workspace_manager.workspace,
+                    plugin.id,
+                    &port,
+                )
+                .map(str::to_string);
+                rows.push(SignalRow {
+                    plugin: name.clone(),
+                    port,
+                    direction: "out",
+                    value,
+                    unit,
+                });
+            }
+            for (port, value) in ports(&self.state_sync.input_values) {
+                let unit = self.input_port_unit(plugin.id, &port);
+                rows.push(SignalRow {
+                    plugin: name.clone(),
+                    port,
+                    direction: "in",
+                    value,
+                    unit,
+                });
+            }
+        }
+        rows
+    }
+
+    /// Read-only table of live signal values, docked under the canvas.
+    /// Values refresh with the rest of the outputs, at `output_refresh_hz`.
+    pub(crate) fn render_signals_panel(&mut self, ctx: &egui::Context) {
+        if !self.windows.signals_open {
+            return;
+        }
+        let rows = self.signal_rows(&self.windows.signals_filter);
+        let total = self.state_sync.computed_outputs.len() + self.state_sync.input_values.len();
+        let mut open = true;
+        egui::TopBottomPanel::bottom("signals")
+            .resizable(true)
+            .default_height(220.0)
+            .min_height(80.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Signals").strong());
+                    ui.label("Filter");
+                    ui.text_edit_singleline(&mut self.windows.signals_filter);
+                    ui.label(RichText::new(format!("{} of {total}", rows.len())).weak());
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.small_button("\u{f00d}").on_hover_text("Close").clicked() {
+                            open = false;
+                        }
+                    });
+                });
+                ui.separator();
+                if rows.is_empty() {
+                    ui.label("No signals. Start plugins to see their values.");
+                    return;
+                }
+                egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                    egui::Grid::new("signals_table")
+                        .striped(true)
+                        .num_columns(5)
+                        .min_col_width(60.0)
+                        .show(ui, |ui| {
+                            for header in ["Plugin", "Port", "Direction", "Value", "Unit"] {
+                                ui.label(RichText::new(header).strong());
+                            }
+                            ui.end_row();
+                            for row in &rows {
+                                ui.label(&row.plugin);
+                                ui.label(&row.port);
+                                ui.label(row.direction);
+                                ui.label(RichText::new(format_f64_6(row.value)).monospace());
+                                ui.label(row.unit.as_deref().unwrap_or(""));
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+        self.windows.signals_open = open;
+    }
+}
@@ -0,0 +1,51 @@
+use super::*;
+
+/// Ticks run by the "Step 10" button.
+const STEP_BURST: u64 = 10;
+
+impl GuiApp {
+    /// Pause, play and step buttons for advancing the runtime one tick at a
+    /// time, e.g. to follow a value around a feedback loop.
+    pub(crate) fn render_step_controls(&mut self, ui: &mut egui::Ui) {
+        let step_mode = self.state_sync.step_mode;
+        let mut message = None;
+        // Laid out right to left
+        if step_mode {
+            if ui
+                .add(egui::Button::new(format!("+{STEP_BURST}")).small())
+                .on_hover_text(format!("Run {STEP_BURST} ticks"))
+                .clicked()
+            {
+                message = Some(LogicMessage::Step(STEP_BURST));
+            }
+            if ui
+                .add(egui::Button::new("\u{f051}").small())
+                .on_hover_text("Run one tick")
+                .clicked()
+            {
+                message = Some(LogicMessage::Step(1));
+            }
+            if ui
+                .add(egui::Button::new("\u{f04b}").small())
+                .on_hover_text("Resume timed execution")
+                .clicked()
+            {
+                message = Some(LogicMessage::SetStepMode(false));
+            }
+            ui.label(
+                RichText::new(format!("Step mode · tick {}", self.state_sync.logic_tick))
+                    .monospace()
+                    .color(egui::Color32::from_rgb(255, 170, 80)),
+            );
+        } else if ui
+            .add(egui::Button::new("\u{f04c}").small().frame(false))
+            .on_hover_text("Pause and step the runtime tick by tick")
+            .clicked()
+        {
+            message = Some(LogicMessage::SetStepMode(true));
+        }
+        if let Some(message) = message {
+            let _ = self.state_sync.logic_tx.send(message);
+        }
+    }
+}
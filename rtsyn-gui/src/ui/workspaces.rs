@@ -1,5 +1,8 @@
 use super::*;
+use crate::notifications::format_age;
 use crate::WindowFocus;
+use crate::utils::format_unix_utc;
+use workspace::{MissedDeadlinePolicy, WorkspaceMetadata};
 use rtsyn_runtime::LogicSettings;
 
 impl GuiApp {
@@ -43,14 +46,25 @@ impl GuiApp {
             WorkspaceDialogMode::New => {
                 self.workspace_dialog.name_input.clear();
                 self.workspace_dialog.description_input.clear();
+                self.workspace_dialog.author_input.clear();
+                self.workspace_dialog.notes_input.clear();
                 self.workspace_dialog.edit_path = None;
             }
             WorkspaceDialogMode::Save => {
-                self.workspace_dialog.name_input = self.workspace_manager.workspace.name.clone();
-                self.workspace_dialog.description_input = self.workspace_manager.workspace.description.clone();
+                let workspace = &self.workspace_manager.workspace;
+                self.workspace_dialog.name_input = workspace.name.clone();
+                self.workspace_dialog.description_input = workspace.description.clone();
+                self.workspace_dialog.author_input = workspace.metadata.author.clone();
+                self.workspace_dialog.notes_input = workspace.metadata.notes.clone();
                 self.workspace_dialog.edit_path = None;
             }
             WorkspaceDialogMode::Edit => {}
+            WorkspaceDialogMode::Duplicate => {
+                self.workspace_dialog.name_input =
+                    format!("{} copy", self.workspace_manager.workspace.name);
+                self.workspace_dialog.description_input = self.workspace_manager.workspace.description.clone();
+                self.workspace_dialog.edit_path = None;
+            }
         }
         self.workspace_dialog.open = true;
         self.pending_window_focus = Some(WindowFocus::WorkspaceDialog);
@@ -78,8 +92,10 @@ impl GuiApp {
         let path_preview = self.workspace_file_path(self.workspace_dialog.name_input.trim());
         let mut path_display = path_preview.display().to_string();
         let mut open = self.workspace_dialog.open;
-        let window_size = egui::vec2(420.0, 260.0);
-        let default_pos = Self::center_window(ctx, window_size);
+        // A duplicate keeps the author and notes of the workspace it copies
+        let edits_metadata = !matches!(self.workspace_dialog.mode, WorkspaceDialogMode::Duplicate);
+        let window_size = egui::vec2(420.0, if edits_metadata { 400.0 } else { 260.0 });
+        let default_pos = self.window_placement(ctx, "WorkspaceDialog", window_size).min;
         let mut action = None;
         let response = egui::Window::new("Workspace")
             .open(&mut open)
@@ -91,6 +107,16 @@ impl GuiApp {
                 ui.text_edit_singleline(&mut self.workspace_dialog.name_input);
                 ui.label("Description");
                 ui.text_edit_multiline(&mut self.workspace_dialog.description_input);
+                if edits_metadata {
+                    ui.label("Author");
+                    ui.text_edit_singleline(&mut self.workspace_dialog.author_input);
+                    ui.label("Notes");
+                    ui.add(
+                        egui::TextEdit::multiline(&mut self.workspace_dialog.notes_input)
+                            .desired_rows(4)
+                            .hint_text("Setup, sample, conditions..."),
+                    );
+                }
                 ui.add_space(6.0);
                 ui.label("Path");
                 ui.add_enabled(false, egui::TextEdit::singleline(&mut path_display));
@@ -106,7 +132,7 @@ impl GuiApp {
                 });
             });
         if let Some(response) = response {
-            self.window_rects.push(response.response.rect);
+            self.track_window("WorkspaceDialog", response.response.rect);
             if !self.confirm_dialog.open
                 && (response.response.clicked() || response.response.dragged())
             {
@@ -127,6 +153,7 @@ impl GuiApp {
                     let saved = match self.workspace_dialog.mode {
                         WorkspaceDialogMode::New => self.create_workspace_from_dialog(),
                         WorkspaceDialogMode::Save => self.save_workspace_as(),
+                        WorkspaceDialogMode::Duplicate => self.duplicate_workspace(),
                         WorkspaceDialogMode::Edit => {
                             if let Some(path) = self.workspace_dialog.edit_path.clone() {
                                 self.update_workspace_metadata(&path)
@@ -151,7 +178,7 @@ impl GuiApp {
 
         let mut open = self.windows.manage_workspace_open;
         let window_size = egui::vec2(360.0, 520.0);
-        let default_pos = Self::center_window(ctx, window_size);
+        let default_pos = self.window_placement(ctx, "ManageWorkspaces", window_size).min;
         let mut action_load: Option<PathBuf> = None;
         let mut action_export: Option<PathBuf> = None;
         let mut action_delete: Option<PathBuf> = None;
@@ -199,6 +226,8 @@ impl GuiApp {
                         if let Some(entry) = self.workspace_manager.workspace_entries.get(idx) {
                             self.workspace_dialog.name_input = entry.name.clone();
                             self.workspace_dialog.description_input = entry.description.clone();
+                            self.workspace_dialog.author_input = entry.metadata.author.clone();
+                            self.workspace_dialog.notes_input = entry.metadata.notes.clone();
                         }
                     }
 
@@ -220,6 +249,7 @@ impl GuiApp {
                                     ui.label(egui::RichText::new("Types:").strong());
                                     ui.label(egui::RichText::new(entry.plugin_kinds.join(", ")).size(12.0).color(egui::Color32::from_gray(180)));
                                 }
+                                workspace_metadata_rows(ui, &entry.metadata);
                                 ui.add_space(12.0);
                                 if ui.button("Load").clicked() {
                                     action_load = Some(entry.path.clone());
@@ -253,7 +283,7 @@ impl GuiApp {
                 });
             });
         if let Some(response) = response {
-            self.window_rects.push(response.response.rect);
+            self.track_window("ManageWorkspaces", response.response.rect);
             if !self.confirm_dialog.open
                 && (response.response.clicked() || response.response.dragged())
             {
@@ -268,8 +298,7 @@ impl GuiApp {
         self.windows.manage_workspace_open = open;
 
         if let Some(path) = action_load {
-            self.workspace_manager.workspace_path = path;
-            self.load_workspace();
+            self.open_workspace_path(path);
             self.windows.manage_workspace_open = false;
         }
         if let Some(path) = action_edit {
@@ -297,7 +326,7 @@ impl GuiApp {
 
         let mut open = self.windows.load_workspace_open;
         let window_size = egui::vec2(360.0, 520.0);
-        let default_pos = Self::center_window(ctx, window_size);
+        let default_pos = self.window_placement(ctx, "LoadWorkspaces", window_size).min;
         let mut action_load: Option<PathBuf> = None;
         let response = egui::Window::new("Load workspaces")
             .open(&mut open)
@@ -339,6 +368,8 @@ impl GuiApp {
                         if let Some(entry) = self.workspace_manager.workspace_entries.get(idx) {
                             self.workspace_dialog.name_input = entry.name.clone();
                             self.workspace_dialog.description_input = entry.description.clone();
+                            self.workspace_dialog.author_input = entry.metadata.author.clone();
+                            self.workspace_dialog.notes_input = entry.metadata.notes.clone();
                         }
                     }
 
@@ -360,6 +391,7 @@ impl GuiApp {
                                     ui.label(egui::RichText::new("Types:").strong());
                                     ui.label(egui::RichText::new(entry.plugin_kinds.join(", ")).size(12.0).color(egui::Color32::from_gray(180)));
                                 }
+                                workspace_metadata_rows(ui, &entry.metadata);
                                 ui.add_space(12.0);
                                 if ui.button("Load").clicked() {
                                     action_load = Some(entry.path.clone());
@@ -385,7 +417,7 @@ impl GuiApp {
                 });
             });
         if let Some(response) = response {
-            self.window_rects.push(response.response.rect);
+            self.track_window("LoadWorkspaces", response.response.rect);
             if !self.confirm_dialog.open
                 && (response.response.clicked() || response.response.dragged())
             {
@@ -400,8 +432,7 @@ impl GuiApp {
         self.windows.load_workspace_open = open;
 
         if let Some(path) = action_load {
-            self.workspace_manager.workspace_path = path;
-            self.load_workspace();
+            self.open_workspace_path(path);
             self.windows.load_workspace_open = false;
         }
     }
@@ -412,8 +443,8 @@ impl GuiApp {
         }
 
         let mut open = self.workspace_settings.open;
-        let window_size = egui::vec2(420.0, 240.0);
-        let default_pos = Self::center_window(ctx, window_size);
+        let window_size = egui::vec2(420.0, 380.0);
+        let default_pos = self.window_placement(ctx, "WorkspaceSettings", window_size).min;
         let mut draft = self
             .workspace_settings.draft
             .unwrap_or(WorkspaceSettingsDraft {
@@ -423,6 +454,8 @@ impl GuiApp {
                 period_unit: self.period_unit,
                 tab: self.workspace_settings.tab,
                 max_integration_steps: 10, // Default reasonable limit
+                missed_deadline_policy: self.missed_deadline_policy,
+                jitter_us: self.jitter_us,
             });
         let mut apply_clicked = false;
         let response = egui::Window::new("Runtime settings")
@@ -638,13 +671,60 @@ impl GuiApp {
                 });
                 ui.label("Lower values improve real-time performance but may reduce numerical accuracy.");
 
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.label("On missed deadline");
+                    egui::ComboBox::from_id_source("missed_deadline_policy")
+                        .selected_text(missed_deadline_label(draft.missed_deadline_policy))
+                        .show_ui(ui, |ui| {
+                            for policy in MissedDeadlinePolicy::ALL {
+                                ui.selectable_value(
+                                    &mut draft.missed_deadline_policy,
+                                    policy,
+                                    missed_deadline_label(policy),
+                                )
+                                .on_hover_text(missed_deadline_hint(policy));
+                            }
+                        });
+                });
+                ui.label(missed_deadline_hint(draft.missed_deadline_policy));
+
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.label("Injected jitter");
+                    ui.add(
+                        egui::DragValue::new(&mut draft.jitter_us)
+                            .speed(1.0)
+                            .clamp_range(0.0..=f64::MAX)
+                            .suffix(" us"),
+                    );
+                    ui.label("(testing only)");
+                })
+                .response
+                .on_hover_text(
+                    "Offsets every tick by a random amount up to this bound. \
+                     The performance monitor should report the added jitter.",
+                );
+
+                ui.add_space(10.0);
+                // A display preference, so it applies as soon as it changes
+                ui.horizontal(|ui| {
+                    ui.label("Value refresh");
+                    ui.add(
+                        egui::Slider::new(&mut self.output_refresh_hz, 0.5..=30.0)
+                            .logarithmic(true)
+                            .suffix(" Hz"),
+                    )
+                    .on_hover_text("How often numeric values on cards and in the signals table update");
+                });
+
                 ui.separator();
                 if ui.button("Apply").clicked() {
                     apply_clicked = true;
                 }
             });
         if let Some(response) = response {
-            self.window_rects.push(response.response.rect);
+            self.track_window("WorkspaceSettings", response.response.rect);
             if !self.confirm_dialog.open
                 && (response.response.clicked() || response.response.dragged())
             {
@@ -662,6 +742,8 @@ impl GuiApp {
             self.period_value = draft.period_value;
             self.period_unit = draft.period_unit;
             self.workspace_settings.tab = draft.tab;
+            self.missed_deadline_policy = draft.missed_deadline_policy;
+            self.jitter_us = draft.jitter_us;
             
             // Update the logic settings with the new max integration steps
             let period_seconds = self.compute_period_seconds();
@@ -691,6 +773,8 @@ impl GuiApp {
                     time_label,
                     ui_hz: self.state_sync.logic_ui_hz,
                     max_integration_steps: draft.max_integration_steps,
+                    missed_deadline_policy: draft.missed_deadline_policy,
+                    jitter_us: draft.jitter_us,
                 }));
             
             self.show_info("Runtime settings", "Sampling rate updated");
@@ -735,6 +819,13 @@ impl GuiApp {
                                 self.confirm_dialog.open = false;
                                 self.confirm_dialog.action = None;
                             }
+                            if let Some((label, action)) = self.confirm_dialog.alternative.clone() {
+                                if ui.button(label).clicked() {
+                                    self.perform_confirm_action(action);
+                                    self.confirm_dialog.open = false;
+                                    self.confirm_dialog.action = None;
+                                }
+                            }
                             if ui.button(&self.confirm_dialog.action_label).clicked() {
                                 if let Some(action) = self.confirm_dialog.action.clone() {
                                     self.perform_confirm_action(action);
@@ -748,7 +839,7 @@ impl GuiApp {
     }
 
     pub(crate) fn render_info_dialog(&mut self, ctx: &egui::Context) {
-        if self.notifications.is_empty() {
+        if self.notifications.active().is_empty() {
             return;
         }
 
@@ -759,7 +850,7 @@ impl GuiApp {
         let x = screen_rect.max.x - 4.0;
         let total = 2.8;
         let mut idx = 0usize;
-        for notification in &self.notifications {
+        for notification in self.notifications.active() {
             let age = now.duration_since(notification.created_at).as_secs_f32();
             if age >= total {
                 idx += 1;
@@ -810,11 +901,70 @@ impl GuiApp {
             y += 66.0;
             idx += 1;
         }
-        self.notifications
-            .retain(|n| now.duration_since(n.created_at).as_secs_f32() < total);
+        self.notifications.prune_active(now, total);
         ctx.request_repaint_after(Duration::from_millis(16));
     }
 
+    pub(crate) fn render_notification_history_window(&mut self, ctx: &egui::Context) {
+        if !self.windows.notification_history_open {
+            return;
+        }
+
+        let mut open = self.windows.notification_history_open;
+        let mut clear_requested = false;
+        let now = Instant::now();
+        let placement = self.window_placement(ctx, "Notifications", egui::vec2(420.0, 360.0));
+        let mut content_size = placement.size();
+        let response = egui::Window::new("Notifications")
+            .open(&mut open)
+            .resizable(true)
+            .default_pos(placement.min)
+            .default_size(placement.size())
+            .show(ctx, |ui| {
+                content_size = ui.max_rect().size();
+                ui.horizontal(|ui| {
+                    ui.label("Search");
+                    ui.text_edit_singleline(&mut self.windows.notification_search);
+                    if ui.button("Clear").clicked() {
+                        clear_requested = true;
+                    }
+                });
+                ui.separator();
+                let entries = self.notifications.search(&self.windows.notification_search);
+                ui.label(
+                    RichText::new(format!("{} of {}", entries.len(), self.notifications.history_len()))
+                        .weak(),
+                );
+                if entries.is_empty() {
+                    ui.label("No notifications.");
+                    return;
+                }
+                egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                    for notification in entries {
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(&notification.title).strong());
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                ui.label(RichText::new(format_age(now, notification.created_at)).weak());
+                            });
+                        });
+                        ui.label(&notification.message);
+                        ui.separator();
+                    }
+                });
+            });
+        if let Some(response) = response {
+            self.window_layout.remember(
+                "Notifications",
+                egui::Rect::from_min_size(response.response.rect.min, content_size),
+            );
+        }
+        if clear_requested {
+            self.notifications.clear_history();
+        }
+        self.notifications.mark_read();
+        self.windows.notification_history_open = open;
+    }
+
     pub(crate) fn render_build_dialog(&mut self, ctx: &egui::Context) {
         if !self.build_dialog.open {
             return;
@@ -855,3 +1005,49 @@ impl GuiApp {
             });
     }
 }
+
+/// Author, save times and notes of a workspace list entry.
+fn workspace_metadata_rows(ui: &mut egui::Ui, metadata: &WorkspaceMetadata) {
+    ui.add_space(4.0);
+    if !metadata.author.is_empty() {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Author:").strong());
+            ui.label(&metadata.author);
+        });
+    }
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("Created:").strong());
+        ui.label(format_unix_utc(metadata.created_at));
+    });
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new("Modified:").strong());
+        ui.label(format_unix_utc(metadata.modified_at));
+    });
+    if !metadata.notes.is_empty() {
+        ui.add_space(4.0);
+        ui.label(egui::RichText::new("Notes:").strong());
+        ui.label(egui::RichText::new(&metadata.notes).size(12.0).color(egui::Color32::from_gray(180)));
+    }
+}
+
+fn missed_deadline_label(policy: MissedDeadlinePolicy) -> &'static str {
+    match policy {
+        MissedDeadlinePolicy::Skip => "Skip",
+        MissedDeadlinePolicy::CatchUp => "Catch up",
+        MissedDeadlinePolicy::BestEffort => "Best effort",
+    }
+}
+
+fn missed_deadline_hint(policy: MissedDeadlinePolicy) -> &'static str {
+    match policy {
+        MissedDeadlinePolicy::Skip => {
+            "Drops late ticks to stay aligned with the wall clock; recorded time stays exact."
+        }
+        MissedDeadlinePolicy::CatchUp => {
+            "Runs late ticks back to back so no simulated step is lost."
+        }
+        MissedDeadlinePolicy::BestEffort => {
+            "Keeps to the tick deadlines; after a late tick the schedule restarts from it, so simulated time falls behind only by overruns."
+        }
+    }
+}
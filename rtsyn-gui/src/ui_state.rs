@@ -1,7 +1,10 @@
 // UI state management structs
 
-use crate::state::{ConnectionEditMode, ConnectionEditTab, ManageTab, PluginTab, WorkspaceDialogMode, WorkspaceTimingTab, ConfirmAction};
+use crate::state::{ConnectionEditMode, ConnectionEditTab, ConnectionField, ManageTab, PluginTab, WorkspaceDialogMode, WorkspaceTimingTab, ConfirmAction};
+use crate::plotter::{PlotMode, PlotPalette, SeriesStyle};
 use crate::WorkspaceSettingsDraft;
+use rtsyn_runtime::benchmark::BenchmarkReport;
+use rtsyn_runtime::preflight::ChannelResults;
 use std::sync::mpsc::Receiver;
 use std::path::PathBuf;
 
@@ -12,7 +15,10 @@ pub struct PlotterPreviewState {
     pub show_legend: bool,
     pub show_grid: bool,
     pub series_names: Vec<String>,
-    pub colors: Vec<egui::Color32>,
+    pub palette: PlotPalette,
+    /// Colors the user picked, per series; the others follow `palette`.
+    pub color_overrides: Vec<Option<egui::Color32>>,
+    pub series_styles: Vec<SeriesStyle>,
     pub title: String,
     pub dark_theme: bool,
     pub x_axis_name: String,
@@ -21,6 +27,14 @@ pub struct PlotterPreviewState {
     pub export_svg: bool,
     pub width: u32,
     pub height: u32,
+    /// Supersampling factor of PNG exports, 1 to `MAX_SUPERSAMPLE`.
+    pub supersample: u32,
+    pub display_smoothing: f64,
+    pub smoothed_readout: bool,
+    pub plot_mode: PlotMode,
+    /// Series on the X and Y axes in X-Y mode.
+    pub xy_x: usize,
+    pub xy_y: usize,
 }
 
 impl Default for PlotterPreviewState {
@@ -32,7 +46,9 @@ impl Default for PlotterPreviewState {
             show_legend: true,
             show_grid: true,
             series_names: Vec::new(),
-            colors: Vec::new(),
+            palette: PlotPalette::default(),
+            color_overrides: Vec::new(),
+            series_styles: Vec::new(),
             title: String::new(),
             dark_theme: false,
             x_axis_name: "Time".to_string(),
@@ -41,6 +57,12 @@ impl Default for PlotterPreviewState {
             export_svg: false,
             width: 1920,
             height: 1080,
+            supersample: 1,
+            display_smoothing: 0.0,
+            smoothed_readout: false,
+            plot_mode: PlotMode::Time,
+            xy_x: 0,
+            xy_y: 1,
         }
     }
 }
@@ -48,10 +70,16 @@ impl Default for PlotterPreviewState {
 pub struct ConnectionEditorState {
     pub from_idx: usize,
     pub to_idx: usize,
+    /// Ids of the plugins at `from_idx` and `to_idx`, so the selection
+    /// follows them when plugins are added or removed.
+    pub from_plugin: Option<u64>,
+    pub to_plugin: Option<u64>,
+    pub field: ConnectionField,
     pub from_port: String,
     pub to_port: String,
     pub kind: String,
     pub kind_options: Vec<String>,
+    pub gain: f64,
     pub open: bool,
     pub mode: ConnectionEditMode,
     pub tab: ConnectionEditTab,
@@ -68,6 +96,9 @@ impl Default for ConnectionEditorState {
         Self {
             from_idx: 0,
             to_idx: 0,
+            from_plugin: None,
+            to_plugin: None,
+            field: ConnectionField::default(),
             from_port: String::new(),
             to_port: String::new(),
             kind: "shared_memory".to_string(),
@@ -75,7 +106,9 @@ impl Default for ConnectionEditorState {
                 "shared_memory".to_string(),
                 "pipe".to_string(),
                 "in_process".to_string(),
+                "queue".to_string(),
             ],
+            gain: 1.0,
             open: false,
             mode: ConnectionEditMode::Add,
             tab: ConnectionEditTab::Outputs,
@@ -94,6 +127,8 @@ pub struct WorkspaceDialogState {
     pub mode: WorkspaceDialogMode,
     pub name_input: String,
     pub description_input: String,
+    pub author_input: String,
+    pub notes_input: String,
     pub edit_path: Option<PathBuf>,
 }
 
@@ -104,17 +139,47 @@ impl Default for WorkspaceDialogState {
             mode: WorkspaceDialogMode::New,
             name_input: String::new(),
             description_input: String::new(),
+            author_input: String::new(),
+            notes_input: String::new(),
             edit_path: None,
         }
     }
 }
 
+/// Fields of the New plugin dialog; ports are comma separated and variables
+/// are `name=default` entries.
+pub struct NewPluginDialogState {
+    pub open: bool,
+    pub name: String,
+    pub description: String,
+    pub inputs: String,
+    pub outputs: String,
+    pub variables: String,
+    pub build: bool,
+}
+
+impl Default for NewPluginDialogState {
+    fn default() -> Self {
+        Self {
+            open: false,
+            name: String::new(),
+            description: String::new(),
+            inputs: String::new(),
+            outputs: String::new(),
+            variables: String::new(),
+            build: true,
+        }
+    }
+}
+
 pub struct BuildDialogState {
     pub open: bool,
     pub in_progress: bool,
     pub message: String,
     pub title: String,
     pub rx: Option<Receiver<super::BuildResult>>,
+    /// Plugin name and result of the running benchmark.
+    pub benchmark_rx: Option<Receiver<(String, Result<BenchmarkReport, String>)>>,
 }
 
 impl Default for BuildDialogState {
@@ -125,16 +190,29 @@ impl Default for BuildDialogState {
             message: String::new(),
             title: String::new(),
             rx: None,
+            benchmark_rx: None,
         }
     }
 }
 
+/// The device test window of one driver plugin.
+#[derive(Default)]
+pub struct DeviceTestState {
+    /// Plugin under test; the window is open while set.
+    pub plugin_id: Option<u64>,
+    pub write_safe: bool,
+    pub rx: Option<Receiver<Result<ChannelResults, String>>>,
+    pub result: Option<Result<ChannelResults, String>>,
+}
+
 pub struct ConfirmDialogState {
     pub open: bool,
     pub title: String,
     pub message: String,
     pub action_label: String,
     pub action: Option<ConfirmAction>,
+    /// Extra choice shown between Cancel and the main action.
+    pub alternative: Option<(String, ConfirmAction)>,
 }
 
 impl Default for ConfirmDialogState {
@@ -145,6 +223,7 @@ impl Default for ConfirmDialogState {
             message: String::new(),
             action_label: String::new(),
             action: None,
+            alternative: None,
         }
     }
 }
@@ -181,8 +260,20 @@ pub struct WindowState {
     pub organize_search: String,
     pub organize_selected_index: Option<usize>,
     pub manage_connections_open: bool,
+    pub connection_latency_open: bool,
     pub plugin_config_open: bool,
     pub plugin_config_id: Option<u64>,
+    /// Plugin whose connections the inspector lists; the window is open while set.
+    pub connection_inspector_id: Option<u64>,
+    pub notification_history_open: bool,
+    pub notification_search: String,
+    pub signals_open: bool,
+    pub signals_filter: String,
+    pub parameters_open: bool,
+    /// Name typed for a new workspace parameter.
+    pub parameter_name: String,
+    /// Plugin, config field and parameter picked for a new binding.
+    pub parameter_binding: (Option<u64>, String, String),
 }
 
 impl Default for WindowState {
@@ -203,8 +294,17 @@ impl Default for WindowState {
             organize_search: String::new(),
             organize_selected_index: None,
             manage_connections_open: false,
+            connection_latency_open: false,
             plugin_config_open: false,
             plugin_config_id: None,
+            connection_inspector_id: None,
+            notification_history_open: false,
+            notification_search: String::new(),
+            signals_open: false,
+            signals_filter: String::new(),
+            parameters_open: false,
+            parameter_name: String::new(),
+            parameter_binding: (None, String::new(), String::new()),
         }
     }
 }
@@ -78,6 +78,57 @@ pub(crate) fn distance_to_segment(point: egui::Pos2, a: egui::Pos2, b: egui::Pos
     (point - closest).length()
 }
 
+/// Formats a duration in seconds as `HH:MM:SS.mmm`.
+pub(crate) fn format_hms_millis(seconds: f64) -> String {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    let ms = total_ms % 1000;
+    let total_s = total_ms / 1000;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        total_s / 3600,
+        (total_s / 60) % 60,
+        total_s % 60,
+        ms
+    )
+}
+
+/// Moves a list selection one entry forward or back, wrapping at the ends.
+pub(crate) fn step_index(current: usize, len: usize, forward: bool) -> usize {
+    if len == 0 {
+        return 0;
+    }
+    let current = current.min(len - 1);
+    if forward {
+        (current + 1) % len
+    } else {
+        (current + len - 1) % len
+    }
+}
+
+/// Formats Unix seconds as `YYYY-MM-DD HH:MM UTC`, or `unknown` for zero.
+pub(crate) fn format_unix_utc(secs: u64) -> String {
+    if secs == 0 {
+        return "unknown".to_string();
+    }
+    // Days to a civil date, counting years from March so the leap day ends
+    // the year
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+        secs % 86_400 / 3600,
+        secs % 3600 / 60
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,6 +140,23 @@ mod tests {
         assert_eq!(format_f64_6(1.2345), "1.2345");
     }
 
+    #[test]
+    fn step_index_wraps_and_clamps() {
+        assert_eq!(step_index(0, 3, true), 1);
+        assert_eq!(step_index(2, 3, true), 0);
+        assert_eq!(step_index(0, 3, false), 2);
+        // A stale index past the end steps from the last entry
+        assert_eq!(step_index(7, 3, false), 1);
+        assert_eq!(step_index(0, 0, true), 0);
+    }
+
+    #[test]
+    fn unix_seconds_format_as_a_utc_date() {
+        assert_eq!(format_unix_utc(0), "unknown");
+        assert_eq!(format_unix_utc(1_700_000_000), "2023-11-14 22:13 UTC");
+        assert_eq!(format_unix_utc(951_782_400), "2000-02-29 00:00 UTC");
+    }
+
     #[test]
     fn parse_f64_input_accepts_commas() {
         assert_eq!(parse_f64_input("1,25"), Some(1.25));
@@ -114,6 +182,14 @@ mod tests {
         assert_eq!(value, "-0,01");
     }
 
+    #[test]
+    fn format_hms_millis_pads_fields() {
+        assert_eq!(format_hms_millis(0.0), "00:00:00.000");
+        assert_eq!(format_hms_millis(61.0125), "00:01:01.013");
+        assert_eq!(format_hms_millis(3723.5), "01:02:03.500");
+        assert_eq!(format_hms_millis(-1.0), "00:00:00.000");
+    }
+
     #[test]
     fn distance_to_segment_returns_zero_on_segment() {
         let a = egui::pos2(0.0, 0.0);
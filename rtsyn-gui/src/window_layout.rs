@@ -0,0 +1,108 @@
+use eframe::egui::{Pos2, Rect, Vec2};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Saved windows are written at most this often while they move.
+const SAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Last position and size of each sub-window, persisted between sessions.
+///
+/// Windows are keyed by name, with `#<plugin id>` appended for windows that
+/// belong to a single plugin (plugin config, plotters).
+pub(crate) struct WindowLayout {
+    path: PathBuf,
+    windows: BTreeMap<String, [f32; 4]>,
+    dirty: bool,
+    last_save: Instant,
+}
+
+impl WindowLayout {
+    pub(crate) fn load(path: PathBuf) -> Self {
+        let windows = fs::read(&path)
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            windows,
+            dirty: false,
+            last_save: Instant::now(),
+        }
+    }
+
+    pub(crate) fn key(name: &str, plugin_id: u64) -> String {
+        format!("{name}#{plugin_id}")
+    }
+
+    pub(crate) fn rect(&self, key: &str) -> Option<Rect> {
+        let [x, y, w, h] = *self.windows.get(key)?;
+        Some(Rect::from_min_size(Pos2::new(x, y), Vec2::new(w, h)))
+    }
+
+    /// Record where a window is this frame. Sub-pixel jitter is ignored so a
+    /// still window never marks the layout dirty.
+    pub(crate) fn remember(&mut self, key: &str, rect: Rect) {
+        if !rect.is_finite() || rect.width() < 1.0 || rect.height() < 1.0 {
+            return;
+        }
+        let geometry = [rect.min.x, rect.min.y, rect.width(), rect.height()];
+        let unchanged = self.windows.get(key).is_some_and(|saved| {
+            saved
+                .iter()
+                .zip(geometry)
+                .all(|(a, b)| (a - b).abs() < 0.5)
+        });
+        if !unchanged {
+            self.windows.insert(key.to_string(), geometry);
+            self.dirty = true;
+        }
+    }
+
+    /// Write the layout if it changed, at most once per `SAVE_INTERVAL`
+    /// unless `force` is set (the app is closing).
+    pub(crate) fn save(&mut self, force: bool) {
+        if !self.dirty || (!force && self.last_save.elapsed() < SAVE_INTERVAL) {
+            return;
+        }
+        if let Ok(data) = serde_json::to_vec_pretty(&self.windows) {
+            if let Some(parent) = self.path.parent() {
+                let _ = fs::create_dir_all(parent);
+            }
+            let _ = fs::write(&self.path, data);
+        }
+        self.dirty = false;
+        self.last_save = Instant::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_round_trips_through_its_file() {
+        let path = std::env::temp_dir()
+            .join(format!("rtsyn_window_layout_{}", std::process::id()))
+            .join("window_layout.json");
+        let rect = Rect::from_min_size(Pos2::new(40.0, 60.0), Vec2::new(520.0, 360.0));
+
+        let mut layout = WindowLayout::load(path.clone());
+        assert!(layout.rect("ManageConnections").is_none());
+        layout.remember("ManageConnections", rect);
+        layout.remember(&WindowLayout::key("PluginConfig", 3), rect.translate(Vec2::X * 10.0));
+        // Degenerate rects from a collapsed or not yet laid out window are skipped
+        layout.remember("Notifications", Rect::NOTHING);
+        layout.save(true);
+
+        let mut reloaded = WindowLayout::load(path.clone());
+        assert_eq!(reloaded.rect("ManageConnections"), Some(rect));
+        assert_eq!(reloaded.rect("PluginConfig#3").map(|r| r.min.x), Some(50.0));
+        assert!(reloaded.rect("Notifications").is_none());
+
+        reloaded.remember("ManageConnections", rect.translate(Vec2::splat(0.2)));
+        assert!(!reloaded.dirty);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -1,9 +1,12 @@
 use crate::{GuiApp, spawn_file_dialog_thread};
-use crate::state::WorkspaceDialogMode;
+use crate::state::{ConfirmAction, WorkspaceDialogMode};
+use crate::ui_state::WorkspaceDialogState;
+use rtsyn_runtime::LogicMessage;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc;
-use workspace::WorkspaceDefinition;
+use workspace::{WorkspaceDefinition, WorkspaceMetadata};
 
 impl GuiApp {
     pub(crate) fn load_workspace(&mut self) {
@@ -15,16 +18,14 @@ impl GuiApp {
         match self.workspace_manager.load_workspace(&self.workspace_manager.workspace_path.clone()) {
             Ok(()) => {
                 let name = self.workspace_manager.workspace.name.clone();
-                self.refresh_installed_library_paths();
-                self.inject_library_paths_into_workspace();
-                self.apply_loads_started_on_load();
-                self.open_running_plotters();
-                self.enforce_connection_dependent();
-                self.apply_workspace_settings();
-                self.sync_next_plugin_id();
-                self.plugin_manager.available_plugin_ids.clear();
-                self.mark_workspace_dirty();
+                self.prepare_loaded_workspace(None);
+                // Hand the workspace to the runtime; nothing to save yet
+                self.workspace_manager.workspace_dirty = true;
                 self.show_info("Workspace", &format!("Workspace '{}' loaded", name));
+                let malformed = self.malformed_config_numbers();
+                if !malformed.is_empty() {
+                    self.push_notification("Plugin config", &malformed.join("\n"));
+                }
             }
             Err(err) => {
                 self.show_info("Workspace", &format!("Load failed: {err}"));
@@ -32,6 +33,146 @@ impl GuiApp {
         }
     }
 
+    /// Loads the workspace at `path` into the active tab, or switches to the
+    /// tab that already has it open.
+    pub(crate) fn open_workspace_path(&mut self, path: PathBuf) {
+        if let Some(index) = self.workspace_manager.tab_for_path(&path) {
+            self.switch_workspace_tab(index);
+            return;
+        }
+        self.workspace_manager.workspace_path = path;
+        self.load_workspace();
+    }
+
+    /// Brings the GUI in line with a workspace that has just become active.
+    /// Cards are put back at `card_positions`, or laid out by data flow when
+    /// the workspace was never shown.
+    fn prepare_loaded_workspace(&mut self, card_positions: Option<HashMap<u64, egui::Pos2>>) {
+        // A different graph: the next sync hands the runtime the whole
+        // workspace. `UpdateWorkspace` keeps the instances of plugin ids it
+        // already has, and ids repeat across workspaces, so the runtime is
+        // emptied first and no plugin state carries over
+        self.synced_workspace = None;
+        let _ = self.state_sync.logic_tx.send(LogicMessage::UpdateWorkspace(WorkspaceDefinition {
+            plugins: Vec::new(),
+            connections: Vec::new(),
+            ..self.workspace_manager.workspace.clone()
+        }));
+        self.refresh_installed_library_paths();
+        self.inject_library_paths_into_workspace();
+        self.apply_loads_started_on_load();
+        self.open_running_plotters();
+        self.enforce_connection_dependent();
+        self.apply_workspace_settings();
+        self.sync_next_plugin_id();
+        self.plugin_manager.available_plugin_ids.clear();
+        self.plugin_rects.clear();
+        self.state_sync.plugin_run_states.clear();
+        match card_positions {
+            Some(positions) => {
+                self.pending_card_positions = positions.clone();
+                self.plugin_positions = positions;
+                self.pending_auto_layout = None;
+            }
+            None => {
+                // Workspaces carry no card positions: lay the cards out by
+                // data flow once they have been drawn
+                self.plugin_positions.clear();
+                self.pending_auto_layout = Some(crate::card_layout::AutoLayout::Layered);
+            }
+        }
+    }
+
+    /// Keeps the active tab's card positions for when it is shown again.
+    fn park_card_positions(&mut self) {
+        let positions = std::mem::take(&mut self.plugin_positions);
+        self.parked_card_positions
+            .insert(self.workspace_manager.active_tab(), positions);
+    }
+
+    /// Makes tab `index` active. Only the active tab runs, so the runtime
+    /// is handed its workspace as on a load.
+    pub(crate) fn switch_workspace_tab(&mut self, index: usize) {
+        if index == self.workspace_manager.active_tab() {
+            return;
+        }
+        self.park_card_positions();
+        self.workspace_manager.switch_tab(index);
+        // Plugin ids are per workspace: plotters of the parked tab would
+        // pick up samples of unrelated plugins
+        self.plotter_manager.plotters.clear();
+        let positions = self.parked_card_positions.remove(&index);
+        self.prepare_loaded_workspace(positions);
+    }
+
+    pub(crate) fn new_workspace_tab(&mut self) {
+        self.park_card_positions();
+        self.workspace_manager.new_tab();
+        self.plotter_manager.plotters.clear();
+        self.prepare_loaded_workspace(None);
+    }
+
+    /// Closes tab `index`, asking first when it has unsaved changes.
+    pub(crate) fn request_close_workspace_tab(&mut self, index: usize) {
+        let Some((name, unsaved)) = self.workspace_manager.tabs().get(index).cloned() else {
+            return;
+        };
+        if unsaved {
+            self.show_confirm(
+                "Unsaved changes",
+                &format!("Close '{name}' and discard its unsaved changes?"),
+                "Close",
+                ConfirmAction::CloseTab(index),
+            );
+        } else {
+            self.close_workspace_tab(index);
+        }
+    }
+
+    pub(crate) fn close_workspace_tab(&mut self, index: usize) {
+        let active = self.workspace_manager.active_tab();
+        if let Err(err) = self.workspace_manager.close_tab(index) {
+            self.show_info("Workspace", &err);
+            return;
+        }
+        // Later tabs moved down by one
+        self.parked_card_positions.remove(&index);
+        self.parked_card_positions = std::mem::take(&mut self.parked_card_positions)
+            .into_iter()
+            .map(|(tab, positions)| (if tab > index { tab - 1 } else { tab }, positions))
+            .collect();
+        if index == active {
+            self.plotter_manager.plotters.clear();
+            let positions = self
+                .parked_card_positions
+                .remove(&self.workspace_manager.active_tab());
+            self.prepare_loaded_workspace(positions);
+        }
+    }
+
+    /// Numeric config values of the workspace plugins that are present but
+    /// malformed, which the runtime replaces with defaults.
+    fn malformed_config_numbers(&self) -> Vec<String> {
+        let workspace = &self.workspace_manager.workspace;
+        let installed = &self.plugin_manager.installed_plugins;
+        workspace
+            .plugins
+            .iter()
+            .flat_map(|plugin| {
+                let schema = installed
+                    .iter()
+                    .find(|p| p.manifest.kind == plugin.kind)
+                    .and_then(|p| p.ui_schema.as_ref());
+                let name = rtsyn_core::plugin::plugin_display_name(installed, workspace, plugin.id);
+                schema
+                    .map(|schema| rtsyn_core::config::malformed_numbers(&plugin.config, schema))
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(move |err| format!("{name} #{}: {err}", plugin.id))
+            })
+            .collect()
+    }
+
     pub(crate) fn scan_workspaces(&mut self) {
         self.workspace_manager.scan_workspaces();
     }
@@ -51,6 +192,12 @@ impl GuiApp {
             self.show_info("Workspace Error", &e);
             return false;
         }
+        if Self::apply_dialog_metadata(&self.workspace_dialog, &mut self.workspace_manager.workspace.metadata) {
+            if let Err(e) = self.workspace_manager.save_workspace_overwrite_current() {
+                self.show_info("Workspace Error", &e);
+                return false;
+            }
+        }
         
         self.plugin_manager.next_plugin_id = 1;
         self.plugin_manager.available_plugin_ids.clear();
@@ -59,6 +206,22 @@ impl GuiApp {
         true
     }
 
+    /// Copies the dialog's author and notes into `metadata`; true when
+    /// either changed.
+    fn apply_dialog_metadata(
+        dialog: &WorkspaceDialogState,
+        metadata: &mut WorkspaceMetadata,
+    ) -> bool {
+        let author = dialog.author_input.trim();
+        let notes = dialog.notes_input.trim();
+        if metadata.author == author && metadata.notes == notes {
+            return false;
+        }
+        metadata.author = author.to_string();
+        metadata.notes = notes.to_string();
+        true
+    }
+
     pub(crate) fn save_workspace_as(&mut self) -> bool {
         let name = self.workspace_dialog.name_input.trim();
         if name.is_empty() {
@@ -66,6 +229,7 @@ impl GuiApp {
             return false;
         }
         
+        Self::apply_dialog_metadata(&self.workspace_dialog, &mut self.workspace_manager.workspace.metadata);
         if let Err(e) = self.workspace_manager.save_workspace_as(name, self.workspace_dialog.description_input.trim()) {
             self.show_info("Workspace Error", &e);
             return false;
@@ -76,6 +240,30 @@ impl GuiApp {
         true
     }
 
+    /// Saves a renumbered copy of the current workspace under the dialog's
+    /// name and opens it.
+    pub(crate) fn duplicate_workspace(&mut self) -> bool {
+        let name = self.workspace_dialog.name_input.trim().to_string();
+        if name.is_empty() {
+            self.show_info("Workspace", "Workspace name is required");
+            return false;
+        }
+        self.workspace_manager.workspace.settings = self.current_workspace_settings();
+        let description = self.workspace_dialog.description_input.trim().to_string();
+        let path = match self.workspace_manager.duplicate_workspace(&name, &description) {
+            Ok(path) => path,
+            Err(e) => {
+                self.show_info("Workspace Error", &e);
+                return false;
+            }
+        };
+        self.workspace_manager.workspace_path = path;
+        self.load_workspace();
+        self.show_info("Workspace", &format!("Workspace duplicated as '{name}'"));
+        self.scan_workspaces();
+        true
+    }
+
     pub(crate) fn save_workspace_overwrite_current(&mut self) {
         if self.workspace_manager.workspace_path.as_os_str().is_empty() {
             self.open_workspace_dialog(WorkspaceDialogMode::Save);
@@ -103,7 +291,17 @@ impl GuiApp {
             if let Ok(mut workspace) = serde_json::from_slice::<WorkspaceDefinition>(&data) {
                 workspace.name = name.to_string();
                 workspace.description = self.workspace_dialog.description_input.trim().to_string();
+                Self::apply_dialog_metadata(&self.workspace_dialog, &mut workspace.metadata);
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                workspace.metadata.mark_saved(now);
                 let _ = workspace.save_to_file(&new_path);
+                if self.workspace_manager.workspace_path == path {
+                    // Keep the open copy from overwriting the edit on its next save
+                    self.workspace_manager.workspace.metadata = workspace.metadata.clone();
+                }
                 if new_path != path {
                     let _ = fs::remove_file(path);
                 }
@@ -146,6 +344,31 @@ impl GuiApp {
         });
     }
 
+    /// Saves the signal graph of the open workspace as Graphviz DOT, with
+    /// plugins named as on their cards.
+    pub(crate) fn export_workspace_dot(&mut self) {
+        if self.file_dialogs.dot_export_dialog_rx.is_some() {
+            self.show_info("Workspace", "Dialog already open");
+            return;
+        }
+        let workspace = &self.workspace_manager.workspace;
+        let dot = workspace.to_dot_labeled(|plugin| self.plugin_display_name(plugin.id));
+        let filename = format!("{}.dot", workspace.name);
+        let (tx, rx) = mpsc::channel();
+        self.file_dialogs.dot_export_dialog_rx = Some(rx);
+        spawn_file_dialog_thread(move || {
+            let dest = if crate::has_rt_capabilities() {
+                crate::zenity_file_dialog_with_name("save", None, Some(filename.as_str()))
+            } else {
+                rfd::FileDialog::new()
+                    .add_filter("Graphviz", &["dot", "gv"])
+                    .set_file_name(&filename)
+                    .save_file()
+            };
+            let _ = tx.send((dot, dest));
+        });
+    }
+
     pub(crate) fn import_workspace_from_path(&mut self, path: &Path) {
         match self.workspace_manager.import_workspace(path) {
             Ok(()) => {
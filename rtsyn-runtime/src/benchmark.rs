@@ -0,0 +1,153 @@
+use crate::latency::summarize;
+use crate::runtime::DynamicPluginInstance;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// File name of the allocation probe, built next to the `rtsyn` binary by the
+/// `rtsyn-alloc-probe` crate. Preloaded with `LD_PRELOAD`, it lets
+/// [`benchmark_plugin`] count the allocations a plugin library makes.
+pub const ALLOC_PROBE_LIBRARY: &str = "librtsyn_alloc_probe.so";
+
+/// Entry points of the allocation probe preloaded into this process.
+struct AllocationProbe {
+    set_counting: extern "C" fn(bool),
+    counts: unsafe extern "C" fn(*mut u64, *mut u64),
+}
+
+impl AllocationProbe {
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    fn find() -> Option<Self> {
+        unsafe {
+            let set_counting =
+                libc::dlsym(libc::RTLD_DEFAULT, c"rtsyn_alloc_probe_set_counting".as_ptr());
+            let counts = libc::dlsym(libc::RTLD_DEFAULT, c"rtsyn_alloc_probe_counts".as_ptr());
+            if set_counting.is_null() || counts.is_null() {
+                return None;
+            }
+            Some(Self {
+                set_counting: std::mem::transmute::<*mut libc::c_void, extern "C" fn(bool)>(
+                    set_counting,
+                ),
+                counts: std::mem::transmute::<
+                    *mut libc::c_void,
+                    unsafe extern "C" fn(*mut u64, *mut u64),
+                >(counts),
+            })
+        }
+    }
+
+    #[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+    fn find() -> Option<Self> {
+        None
+    }
+
+    fn counts(&self) -> (u64, u64) {
+        let (mut allocations, mut bytes) = (0, 0);
+        unsafe { (self.counts)(&mut allocations, &mut bytes) };
+        (allocations, bytes)
+    }
+}
+
+/// Whether the allocation probe is preloaded into this process.
+pub fn allocation_probe_loaded() -> bool {
+    AllocationProbe::find().is_some()
+}
+
+/// The allocation probe next to the running executable, if it was built.
+pub fn allocation_probe_path() -> Option<PathBuf> {
+    let path = std::env::current_exe()
+        .ok()?
+        .parent()?
+        .join(ALLOC_PROBE_LIBRARY);
+    path.is_file().then_some(path)
+}
+
+/// `process()` timings of one [`benchmark_plugin`] run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchmarkReport {
+    pub iterations: usize,
+    pub min: Duration,
+    pub mean: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+    /// Allocations during the `process()` calls, or `None` when the
+    /// allocation probe is not preloaded (see [`ALLOC_PROBE_LIBRARY`]).
+    pub allocations: Option<u64>,
+    pub allocated_bytes: Option<u64>,
+}
+
+/// Loads the plugin library at `library_path`, applies `config` and times
+/// `iterations` calls of its `process()`, feeding every input a slow sine.
+/// `period_seconds` in `config` sets the tick period passed to the plugin,
+/// 1 ms by default.
+pub fn benchmark_plugin(
+    library_path: &str,
+    config: &Value,
+    iterations: usize,
+) -> Result<BenchmarkReport, String> {
+    if iterations == 0 {
+        return Err("iterations must be at least 1".to_string());
+    }
+    let config = config
+        .as_object()
+        .ok_or_else(|| "config must be a JSON object".to_string())?;
+    let plugin = unsafe { DynamicPluginInstance::load(library_path, 0) }
+        .ok_or_else(|| format!("Failed to load plugin library {library_path}"))?;
+    let api = unsafe { &*plugin.api };
+
+    let period_seconds = config
+        .get("period_seconds")
+        .and_then(Value::as_f64)
+        .unwrap_or(0.001);
+    let mut config = config.clone();
+    config
+        .entry("period_seconds")
+        .or_insert(Value::from(period_seconds));
+    config
+        .entry("max_integration_steps")
+        .or_insert(Value::from(10.0));
+    let json = Value::Object(config).to_string();
+    (api.set_config_json)(plugin.handle, json.as_ptr(), json.len());
+
+    let probe = AllocationProbe::find();
+    let before = probe.as_ref().map(AllocationProbe::counts);
+    let mut durations = VecDeque::with_capacity(iterations);
+    for tick in 0..iterations {
+        for (idx, bytes) in plugin.input_bytes.iter().enumerate() {
+            let value = (tick as f64 * period_seconds * (idx + 1) as f64).sin();
+            (api.set_input)(plugin.handle, bytes.as_ptr(), bytes.len(), value);
+        }
+        if let Some(probe) = &probe {
+            (probe.set_counting)(true);
+        }
+        let start = Instant::now();
+        (api.process)(plugin.handle, tick as u64, period_seconds);
+        let elapsed = start.elapsed();
+        if let Some(probe) = &probe {
+            (probe.set_counting)(false);
+        }
+        durations.push_back(elapsed);
+        for bytes in &plugin.output_bytes {
+            (api.get_output)(plugin.handle, bytes.as_ptr(), bytes.len());
+        }
+    }
+    (api.destroy)(plugin.handle);
+
+    let allocations = probe.zip(before).map(|(probe, before)| {
+        let after = probe.counts();
+        (after.0 - before.0, after.1 - before.1)
+    });
+    let stats = summarize(&durations);
+    Ok(BenchmarkReport {
+        iterations,
+        min: durations.iter().min().copied().unwrap_or_default(),
+        mean: stats.mean,
+        p99: stats.p99,
+        max: stats.max,
+        allocations: allocations.map(|counts| counts.0),
+        allocated_bytes: allocations.map(|counts| counts.1),
+    })
+}
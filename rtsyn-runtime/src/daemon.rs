@@ -15,7 +15,9 @@ impl DaemonService {
     }
 
     pub fn load_workspace(&self, workspace: WorkspaceDefinition) {
-        let _ = self.logic_tx.send(LogicMessage::UpdateWorkspace(workspace));
+        let _ = self
+            .logic_tx
+            .send(LogicMessage::UpdateWorkspace(workspace.resolve_parameters()));
     }
 
     pub fn update_settings(&self, settings: LogicSettings) {
@@ -30,6 +32,10 @@ impl DaemonService {
         let _ = self.logic_tx.send(LogicMessage::RestartPlugin(plugin_id));
     }
 
+    pub fn set_log_level(&self, level: crate::logging::Level) {
+        let _ = self.logic_tx.send(LogicMessage::SetLogLevel(level));
+    }
+
     pub fn poll_state(&self) -> Option<LogicState> {
         self.logic_state_rx.try_recv().ok()
     }
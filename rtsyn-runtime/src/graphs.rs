@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Sender};
+
+use crate::rt_thread::RuntimeThread;
+use crate::runtime::{run_runtime_loop, LogicMessage, LogicSettings, LogicState};
+
+/// Named graphs hosted next to the main workspace. Each graph runs its own
+/// tick loop on its own runtime thread, so plugin ids only need to be unique
+/// within a graph. Settings sent to the host apply to every graph, so graph
+/// threads take the host's cores and realtime priority. Graphs cannot host
+/// graphs of their own.
+#[derive(Default)]
+pub(crate) struct Graphs {
+    graphs: HashMap<String, Sender<LogicMessage>>,
+}
+
+impl Graphs {
+    /// Starts graph `name`, publishing its state on `state_tx`. An existing
+    /// graph with the same name is replaced.
+    pub(crate) fn create(
+        &mut self,
+        name: String,
+        settings: &LogicSettings,
+        state_tx: Sender<LogicState>,
+    ) {
+        let (graph_tx, graph_rx) = mpsc::channel::<LogicMessage>();
+        let _ = graph_tx.send(LogicMessage::UpdateSettings(settings.clone()));
+        let graph_name = name.clone();
+        let spawned = RuntimeThread::spawn(format!("rtsyn-graph-{name}"), move || {
+            if let Err(err) = run_runtime_loop(graph_rx, state_tx, true) {
+                tracing::warn!(graph = %graph_name, "graph runtime failed: {err}");
+            }
+        });
+        match spawned {
+            Ok(_) => {
+                tracing::info!(graph = %name, "graph created");
+                self.graphs.insert(name, graph_tx);
+            }
+            Err(err) => tracing::warn!(graph = %name, "failed to start graph: {err}"),
+        }
+    }
+
+    /// Stops graph `name`; its tick loop exits once it sees the channel close.
+    pub(crate) fn remove(&mut self, name: &str) {
+        if self.graphs.remove(name).is_some() {
+            tracing::info!(graph = %name, "graph removed");
+        }
+    }
+
+    /// Forwards `message` to graph `name`, dropping graphs whose loop has exited.
+    pub(crate) fn send(&mut self, name: &str, message: LogicMessage) {
+        let Some(graph_tx) = self.graphs.get(name) else {
+            tracing::warn!(graph = %name, "message for unknown graph");
+            return;
+        };
+        if graph_tx.send(message).is_err() {
+            self.graphs.remove(name);
+        }
+    }
+
+    pub(crate) fn update_settings(&mut self, settings: &LogicSettings) {
+        self.graphs
+            .retain(|_, graph_tx| graph_tx.send(LogicMessage::UpdateSettings(settings.clone())).is_ok());
+    }
+}
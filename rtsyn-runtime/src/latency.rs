@@ -0,0 +1,192 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+use workspace::ConnectionDefinition;
+
+/// Most recent transfers kept per connection.
+const WINDOW: usize = 4096;
+
+/// Scheduling delay of one connection over its most recent transfers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LatencyStats {
+    /// Transfers the figures are computed from.
+    pub samples: usize,
+    pub mean: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+/// Measures the scheduling delay of each connection: from the source
+/// writing its outputs to the target reading its inputs. Every connection
+/// kind hands values over through the same output table, so the delay
+/// depends on the plugins run in between, not on the kind. A target that
+/// runs before its source in the tick (a feedback edge) reads the previous
+/// tick's value, so its transfers include the rest of that tick.
+///
+/// Nothing is timed until tracking is turned on, so the tick loop pays
+/// nothing while no one is looking at the figures.
+#[derive(Default)]
+pub(crate) struct ConnectionLatency {
+    enabled: bool,
+    written: HashMap<u64, Instant>,
+    /// Samples per connection, at the connection's index in the workspace.
+    /// A slot whose connection changed starts over.
+    samples: Vec<(ConnectionDefinition, VecDeque<Duration>)>,
+}
+
+impl ConnectionLatency {
+    /// Turns tracking on or off; turning it off drops what was measured.
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.written.clear();
+            self.samples.clear();
+        }
+    }
+
+    /// Called after a plugin's tick. Stopped plugins produce no new values,
+    /// so transfers out of them are not measured.
+    pub(crate) fn outputs_written(&mut self, plugin_id: u64, running: bool) {
+        if !self.enabled {
+            return;
+        }
+        if running {
+            self.written.insert(plugin_id, Instant::now());
+        } else {
+            self.written.remove(&plugin_id);
+        }
+    }
+
+    /// Called before a running plugin reads its inputs. Records one transfer
+    /// for every connection into it whose source has written outputs.
+    pub(crate) fn inputs_read(&mut self, connections: &[ConnectionDefinition], plugin_id: u64) {
+        if !self.enabled {
+            return;
+        }
+        let now = Instant::now();
+        self.samples.truncate(connections.len());
+        for (index, connection) in connections.iter().enumerate() {
+            if connection.to_plugin != plugin_id {
+                continue;
+            }
+            let Some(written) = self.written.get(&connection.from_plugin) else {
+                continue;
+            };
+            let latency = now.saturating_duration_since(*written);
+            while self.samples.len() <= index {
+                self.samples.push((connection.clone(), VecDeque::new()));
+            }
+            let (tracked, samples) = &mut self.samples[index];
+            if !same_connection(tracked, connection) {
+                *tracked = connection.clone();
+                samples.clear();
+            }
+            if samples.len() == WINDOW {
+                samples.pop_front();
+            }
+            samples.push_back(latency);
+        }
+    }
+
+    /// Drops what was measured on connections into or out of `plugin_id`,
+    /// e.g. when it is removed or restarted.
+    pub(crate) fn forget(&mut self, plugin_id: u64) {
+        self.written.remove(&plugin_id);
+        for (connection, samples) in &mut self.samples {
+            if connection.from_plugin == plugin_id || connection.to_plugin == plugin_id {
+                samples.clear();
+            }
+        }
+    }
+
+    /// Statistics of every connection with measured transfers.
+    pub(crate) fn stats(&self) -> Vec<(ConnectionDefinition, LatencyStats)> {
+        self.samples
+            .iter()
+            .filter(|(_, samples)| !samples.is_empty())
+            .map(|(connection, samples)| (connection.clone(), summarize(samples)))
+            .collect()
+    }
+}
+
+fn same_connection(a: &ConnectionDefinition, b: &ConnectionDefinition) -> bool {
+    a.from_plugin == b.from_plugin
+        && a.to_plugin == b.to_plugin
+        && a.from_port == b.from_port
+        && a.to_port == b.to_port
+        && a.kind == b.kind
+}
+
+pub(crate) fn summarize(samples: &VecDeque<Duration>) -> LatencyStats {
+    let mut sorted: Vec<Duration> = samples.iter().copied().collect();
+    sorted.sort();
+    let Some(max) = sorted.last().copied() else {
+        return LatencyStats::default();
+    };
+    let total: Duration = sorted.iter().sum();
+    // Nearest-rank percentile
+    let p99_rank = (sorted.len() * 99).div_ceil(100);
+    LatencyStats {
+        samples: sorted.len(),
+        mean: total / sorted.len() as u32,
+        p99: sorted[p99_rank - 1],
+        max,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_uses_nearest_rank_percentile() {
+        let samples: VecDeque<Duration> = (1..=200).map(Duration::from_micros).collect();
+        let stats = summarize(&samples);
+        assert_eq!(stats.samples, 200);
+        assert_eq!(stats.mean, Duration::from_nanos(100_500));
+        assert_eq!(stats.p99, Duration::from_micros(198));
+        assert_eq!(stats.max, Duration::from_micros(200));
+        assert_eq!(summarize(&VecDeque::new()), LatencyStats::default());
+    }
+
+    #[test]
+    fn transfers_are_recorded_per_connection_while_enabled() {
+        let connection = |from_plugin: u64, kind: &str| ConnectionDefinition {
+            from_plugin,
+            from_port: "out".to_string(),
+            to_plugin: 3,
+            to_port: "in".to_string(),
+            kind: kind.to_string(),
+            gain: 1.0,
+        };
+        let mut connections = vec![connection(1, "pipe"), connection(2, "shared_memory")];
+        let mut latency = ConnectionLatency::default();
+        latency.outputs_written(1, true);
+        latency.inputs_read(&connections, 3);
+        assert!(latency.stats().is_empty());
+
+        latency.set_enabled(true);
+        latency.outputs_written(1, true);
+        latency.outputs_written(2, false);
+        latency.inputs_read(&connections, 3);
+        latency.inputs_read(&connections, 3);
+        let stats = latency.stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].0.from_plugin, 1);
+        assert_eq!(stats[0].1.samples, 2);
+
+        // A different connection at the same index starts over
+        connections[0] = connection(1, "in_process");
+        latency.inputs_read(&connections, 3);
+        assert_eq!(latency.stats()[0].1.samples, 1);
+
+        latency.forget(1);
+        assert!(latency.stats().is_empty());
+        latency.inputs_read(&connections, 3);
+        assert!(latency.stats().is_empty());
+
+        latency.outputs_written(1, true);
+        latency.inputs_read(&connections, 3);
+        latency.set_enabled(false);
+        assert!(latency.stats().is_empty());
+    }
+}
@@ -1,7 +1,12 @@
 use rtsyn_plugin::{Plugin, PluginContext, PluginError};
 use workspace::WorkspaceDefinition;
 
+mod graphs;
+mod latency;
+mod queue;
 mod rt_thread;
+mod scheduler;
+mod warmup;
 
 #[derive(Debug, Clone, Copy)]
 pub struct PluginSchedule {
@@ -26,10 +31,16 @@ pub struct Runtime {
     ctx: PluginContext,
 }
 
+pub mod benchmark;
 pub mod daemon;
+pub mod logging;
+pub mod preflight;
 pub mod runtime;
 
-pub use runtime::{run_runtime_current, spawn_runtime, LogicMessage, LogicSettings, LogicState};
+pub use runtime::{
+    run_runtime_current, spawn_runtime, LatencyStats, LogicMessage, LogicSettings, LogicState,
+    PluginRunState,
+};
 
 #[cfg(test)]
 mod tests {
@@ -45,6 +56,8 @@ mod tests {
             plugins: Vec::new(),
             connections: Vec::new(),
             settings: WorkspaceSettings::default(),
+            parameters: Default::default(),
+            metadata: Default::default(),
         };
         
         let runtime = Runtime::new(workspace);
@@ -61,6 +74,8 @@ mod tests {
             plugins: Vec::new(),
             connections: Vec::new(),
             settings: WorkspaceSettings::default(),
+            parameters: Default::default(),
+            metadata: Default::default(),
         };
         
         let mut runtime = Runtime::new(workspace);
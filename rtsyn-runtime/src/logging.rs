@@ -0,0 +1,352 @@
+//! Runtime logging on top of `tracing`.
+//!
+//! The runtime emits spans around each tick and plugin `process()` call and
+//! events for workspace, settings and connection changes. [`init`] installs a
+//! small global subscriber that filters by a level adjustable at runtime
+//! (`RTSYN_LOG` picks the initial one, default `warn`), echoes lines to
+//! stderr and keeps the most recent ones for the GUI to pick up with
+//! [`drain_records`].
+
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Write as _};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Event, Metadata, Subscriber};
+
+pub use tracing::Level;
+
+/// Lines kept for [`drain_records`]; older ones are dropped first.
+const MAX_RECORDS: usize = 512;
+
+const LEVELS: [Level; 5] = [
+    Level::ERROR,
+    Level::WARN,
+    Level::INFO,
+    Level::DEBUG,
+    Level::TRACE,
+];
+
+fn level_index(level: Level) -> usize {
+    LEVELS.iter().position(|l| *l == level).unwrap_or(1)
+}
+
+/// Parses `error`, `warn`, `info`, `debug` or `trace`, case-insensitively.
+pub fn parse_level(name: &str) -> Option<Level> {
+    name.trim().parse().ok()
+}
+
+/// All levels from least to most verbose, for selectors.
+pub fn levels() -> &'static [Level] {
+    &LEVELS
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRecord {
+    pub level: Level,
+    pub target: String,
+    /// Enclosing spans, the message and its fields, e.g.
+    /// `tick{tick=42}:process{plugin=3}: no runtime instance`.
+    pub message: String,
+}
+
+struct SpanData {
+    name: &'static str,
+    fields: String,
+    refs: usize,
+}
+
+thread_local! {
+    static SPAN_STACK: RefCell<Vec<u64>> = const { RefCell::new(Vec::new()) };
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: String,
+}
+
+impl FieldVisitor {
+    fn push_field(&mut self, field: &Field, value: fmt::Arguments<'_>) {
+        if !self.fields.is_empty() {
+            self.fields.push(' ');
+        }
+        let _ = write!(self.fields, "{}={}", field.name(), value);
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.push_field(field, format_args!("{value}"));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            self.push_field(field, format_args!("{value:?}"));
+        }
+    }
+}
+
+/// Subscriber filtering on one adjustable level and buffering recent lines.
+pub struct RuntimeSubscriber {
+    max_level: AtomicUsize,
+    next_span: AtomicU64,
+    spans: Mutex<HashMap<u64, SpanData>>,
+    records: Mutex<VecDeque<LogRecord>>,
+    echo_stderr: bool,
+}
+
+impl RuntimeSubscriber {
+    pub fn new(level: Level, echo_stderr: bool) -> Self {
+        Self {
+            max_level: AtomicUsize::new(level_index(level)),
+            next_span: AtomicU64::new(1),
+            spans: Mutex::new(HashMap::new()),
+            records: Mutex::new(VecDeque::with_capacity(MAX_RECORDS)),
+            echo_stderr,
+        }
+    }
+
+    pub fn level(&self) -> Level {
+        LEVELS[self.max_level.load(Ordering::Relaxed)]
+    }
+
+    pub fn set_level(&self, level: Level) {
+        self.max_level.store(level_index(level), Ordering::Relaxed);
+        // Callsites cache whether they are enabled; make them ask again.
+        tracing::callsite::rebuild_interest_cache();
+    }
+
+    /// Takes the buffered lines, oldest first.
+    pub fn drain(&self) -> Vec<LogRecord> {
+        self.records
+            .lock()
+            .map(|mut records| records.drain(..).collect())
+            .unwrap_or_default()
+    }
+
+    fn allows(&self, level: &Level) -> bool {
+        level_index(*level) <= self.max_level.load(Ordering::Relaxed)
+    }
+
+    fn span_context(&self) -> String {
+        let Ok(spans) = self.spans.lock() else {
+            return String::new();
+        };
+        SPAN_STACK.with(|stack| {
+            let mut context = String::new();
+            for id in stack.borrow().iter() {
+                let Some(span) = spans.get(id) else { continue };
+                if !context.is_empty() {
+                    context.push(':');
+                }
+                context.push_str(span.name);
+                if !span.fields.is_empty() {
+                    let _ = write!(context, "{{{}}}", span.fields);
+                }
+            }
+            context
+        })
+    }
+}
+
+impl Subscriber for RuntimeSubscriber {
+    fn register_callsite(&self, metadata: &'static Metadata<'static>) -> Interest {
+        if self.allows(metadata.level()) {
+            Interest::always()
+        } else {
+            Interest::never()
+        }
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.allows(metadata.level())
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(LevelFilter::from_level(self.level()))
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let id = self.next_span.fetch_add(1, Ordering::Relaxed);
+        let mut visitor = FieldVisitor::default();
+        span.record(&mut visitor);
+        if let Ok(mut spans) = self.spans.lock() {
+            spans.insert(
+                id,
+                SpanData {
+                    name: span.metadata().name(),
+                    fields: visitor.fields,
+                    refs: 1,
+                },
+            );
+        }
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut visitor = FieldVisitor::default();
+        values.record(&mut visitor);
+        if let Ok(mut spans) = self.spans.lock() {
+            if let Some(data) = spans.get_mut(&span.into_u64()) {
+                if !data.fields.is_empty() && !visitor.fields.is_empty() {
+                    data.fields.push(' ');
+                }
+                data.fields.push_str(&visitor.fields);
+            }
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let metadata = event.metadata();
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        let mut message = self.span_context();
+        if !message.is_empty() {
+            message.push_str(": ");
+        }
+        message.push_str(&visitor.message);
+        if !visitor.fields.is_empty() {
+            if !visitor.message.is_empty() {
+                message.push(' ');
+            }
+            message.push_str(&visitor.fields);
+        }
+
+        if self.echo_stderr {
+            eprintln!("{:>5} {}: {}", metadata.level(), metadata.target(), message);
+        }
+        if let Ok(mut records) = self.records.lock() {
+            if records.len() == MAX_RECORDS {
+                records.pop_front();
+            }
+            records.push_back(LogRecord {
+                level: *metadata.level(),
+                target: metadata.target().to_string(),
+                message,
+            });
+        }
+    }
+
+    fn enter(&self, span: &Id) {
+        SPAN_STACK.with(|stack| stack.borrow_mut().push(span.into_u64()));
+    }
+
+    fn exit(&self, span: &Id) {
+        let id = span.into_u64();
+        SPAN_STACK.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            if let Some(pos) = stack.iter().rposition(|entered| *entered == id) {
+                stack.remove(pos);
+            }
+        });
+    }
+
+    fn clone_span(&self, span: &Id) -> Id {
+        if let Ok(mut spans) = self.spans.lock() {
+            if let Some(data) = spans.get_mut(&span.into_u64()) {
+                data.refs += 1;
+            }
+        }
+        span.clone()
+    }
+
+    fn try_close(&self, span: Id) -> bool {
+        let Ok(mut spans) = self.spans.lock() else {
+            return false;
+        };
+        let id = span.into_u64();
+        match spans.get_mut(&id) {
+            Some(data) if data.refs > 1 => {
+                data.refs -= 1;
+                false
+            }
+            Some(_) => {
+                spans.remove(&id);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+static SUBSCRIBER: OnceLock<Arc<RuntimeSubscriber>> = OnceLock::new();
+
+/// Installs the runtime subscriber as the global default, once. If the host
+/// application already installed its own, that one keeps receiving events.
+pub fn init() -> &'static RuntimeSubscriber {
+    SUBSCRIBER.get_or_init(|| {
+        let level = std::env::var("RTSYN_LOG")
+            .ok()
+            .and_then(|value| parse_level(&value))
+            .unwrap_or(Level::WARN);
+        let subscriber = Arc::new(RuntimeSubscriber::new(level, true));
+        let _ = tracing::subscriber::set_global_default(subscriber.clone());
+        subscriber
+    })
+}
+
+pub fn set_level(level: Level) {
+    init().set_level(level);
+}
+
+pub fn level() -> Level {
+    init().level()
+}
+
+/// Takes the lines logged since the last call, oldest first.
+pub fn drain_records() -> Vec<LogRecord> {
+    init().drain()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_respect_the_level_and_carry_span_context() {
+        let subscriber = Arc::new(RuntimeSubscriber::new(Level::INFO, false));
+        tracing::subscriber::with_default(subscriber.clone(), || {
+            tracing::debug!("hidden");
+            let tick = tracing::info_span!("tick", tick = 3);
+            let _tick = tick.enter();
+            tracing::warn!(plugin = 2, "late");
+        });
+        let records = subscriber.drain();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].level, Level::WARN);
+        assert_eq!(records[0].message, "tick{tick=3}: late plugin=2");
+        assert!(subscriber.drain().is_empty());
+
+        subscriber.set_level(Level::DEBUG);
+        tracing::subscriber::with_default(subscriber.clone(), || tracing::debug!("shown"));
+        assert_eq!(subscriber.drain()[0].message, "shown");
+    }
+
+    #[test]
+    fn buffer_keeps_the_most_recent_lines() {
+        let subscriber = Arc::new(RuntimeSubscriber::new(Level::INFO, false));
+        tracing::subscriber::with_default(subscriber.clone(), || {
+            for idx in 0..MAX_RECORDS + 10 {
+                tracing::info!(idx);
+            }
+        });
+        let records = subscriber.drain();
+        assert_eq!(records.len(), MAX_RECORDS);
+        assert_eq!(records[0].message, "idx=10");
+        assert_eq!(parse_level(" Debug "), Some(Level::DEBUG));
+    }
+}
@@ -0,0 +1,206 @@
+use can_io_plugin::CanIoPlugin;
+use modbus_io_plugin::{ConnectionConfig as ModbusConnectionConfig, Mode as ModbusMode, ModbusIoPlugin};
+use rtsyn_core::config;
+use serde_json::Value;
+use workspace::PluginDefinition;
+
+/// Whether starting a plugin of `kind` talks to hardware and so goes through
+/// [`check_device_start`] first.
+pub fn needs_start_check(kind: &str) -> bool {
+    match kind {
+        "can_io" | "modbus_io" => true,
+        #[cfg(feature = "comedi")]
+        "comedi_daq" => true,
+        #[cfg(feature = "rpi")]
+        "gpio" => true,
+        _ => false,
+    }
+}
+
+fn config_str<'a>(config: &'a Value, key: &str, default: &'a str) -> &'a str {
+    config.get(key).and_then(Value::as_str).unwrap_or(default)
+}
+
+fn config_specs(config: &Value, key: &str) -> Vec<String> {
+    config
+        .get(key)
+        .and_then(Value::as_array)
+        .map(|items| items.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+        .unwrap_or_default()
+}
+
+/// A device driver built from a plugin config the way the runtime builds it.
+enum Driver {
+    Can(CanIoPlugin),
+    Modbus(ModbusIoPlugin),
+    #[cfg(feature = "comedi")]
+    Comedi(comedi_daq_plugin::ComediDaqPlugin),
+    #[cfg(feature = "rpi")]
+    Gpio(gpio_plugin::GpioPlugin),
+}
+
+fn driver(plugin: &PluginDefinition) -> Option<Driver> {
+    let config = &plugin.config;
+    let safe_value = config::f64_or(config, "safe_value", 0.0);
+    match plugin.kind.as_str() {
+        "can_io" => {
+            let mut driver = CanIoPlugin::new(plugin.id);
+            driver.set_config(
+                config_str(config, "interface", "can0"),
+                &config_specs(config, "tx_signals"),
+                &config_specs(config, "rx_signals"),
+            );
+            driver.set_safe_value(safe_value);
+            Some(Driver::Can(driver))
+        }
+        "modbus_io" => {
+            let connection = ModbusConnectionConfig {
+                mode: ModbusMode::from_name(config_str(config, "mode", "tcp")),
+                address: config_str(config, "address", "127.0.0.1:502").to_string(),
+                device: config_str(config, "device", "/dev/ttyUSB0").to_string(),
+                baud_rate: u32::try_from(config::u64_or(config, "baud_rate", 9600)).unwrap_or(9600),
+                unit_id: u8::try_from(config::u64_or(config, "unit_id", 1)).unwrap_or(1),
+                timeout_ms: config::u64_or(config, "timeout_ms", 100),
+            };
+            let mut driver = ModbusIoPlugin::new(plugin.id);
+            driver.set_config(
+                connection,
+                config::u64_or(config, "poll_ms", 100),
+                &config_specs(config, "writes"),
+                &config_specs(config, "reads"),
+            );
+            driver.set_safe_value(safe_value);
+            Some(Driver::Modbus(driver))
+        }
+        #[cfg(feature = "comedi")]
+        "comedi_daq" => {
+            let mut driver = comedi_daq_plugin::ComediDaqPlugin::new(plugin.id);
+            driver.set_config(
+                config_str(config, "device_path", "/dev/comedi0").to_string(),
+                false,
+                0,
+            );
+            driver.set_safe_value(safe_value);
+            Some(Driver::Comedi(driver))
+        }
+        #[cfg(feature = "rpi")]
+        "gpio" => {
+            let mut driver = gpio_plugin::GpioPlugin::new(plugin.id);
+            driver.set_config(
+                config_str(config, "chip", "/dev/gpiochip0"),
+                &config_specs(config, "output_pins"),
+                &config_specs(config, "input_pins"),
+            );
+            driver.set_safe_value(safe_value);
+            Some(Driver::Gpio(driver))
+        }
+        _ => None,
+    }
+}
+
+/// Checks that a device driver plugin can start with its current config:
+/// the channel or signal specs are valid and the device can be opened. The
+/// check uses a fresh driver instance, read from the config the way the
+/// runtime does, and closes the device again before returning.
+///
+/// Opening may block up to the driver's connect timeout, so call this off
+/// the UI thread. Plugins that are not device drivers always pass.
+pub fn check_device_start(plugin: &PluginDefinition) -> Result<(), String> {
+    match driver(plugin) {
+        Some(Driver::Can(driver)) => driver.check_start(),
+        Some(Driver::Modbus(driver)) => driver.check_start(),
+        #[cfg(feature = "comedi")]
+        Some(Driver::Comedi(driver)) => driver.check_start(),
+        #[cfg(feature = "rpi")]
+        Some(Driver::Gpio(driver)) => driver.check_start(),
+        None => Ok(()),
+    }
+}
+
+/// Per-channel outcome of [`test_device`]: the channel name and its value
+/// or error, writes first when the safe value was written.
+pub type ChannelResults = Vec<(String, Result<f64, String>)>;
+
+/// Opens the device of a driver plugin with a fresh instance, optionally
+/// writes its safe value to every output, reads every input once and closes
+/// it again, outside of any running graph. Fails as a whole only when the
+/// device cannot be opened, or when `plugin` is not a device driver.
+///
+/// Like [`check_device_start`] this blocks on the device, so call it off the
+/// UI thread.
+pub fn test_device(plugin: &PluginDefinition, write_safe: bool) -> Result<ChannelResults, String> {
+    match driver(plugin) {
+        Some(Driver::Can(driver)) => driver.test_channels(write_safe),
+        Some(Driver::Modbus(driver)) => driver.test_channels(write_safe),
+        #[cfg(feature = "comedi")]
+        Some(Driver::Comedi(driver)) => driver.test_channels(write_safe),
+        #[cfg(feature = "rpi")]
+        Some(Driver::Gpio(driver)) => driver.test_channels(write_safe),
+        None => Err(format!("'{}' is not a device driver", plugin.kind)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn definition(kind: &str, config: Value) -> PluginDefinition {
+        PluginDefinition {
+            id: 1,
+            kind: kind.to_string(),
+            config,
+            priority: 0,
+            running: false,
+        }
+    }
+
+    #[test]
+    fn invalid_specs_fail_before_the_device_is_opened() {
+        let plugin = definition(
+            "can_io",
+            json!({"interface": "can0", "tx_signals": ["speed:0x100:60:8"]}),
+        );
+        let err = check_device_start(&plugin).unwrap_err();
+        assert!(err.contains("tx signal 'speed:0x100:60:8'"), "{err}");
+
+        let plugin = definition("modbus_io", json!({"writes": ["level:input:0"]}));
+        let err = check_device_start(&plugin).unwrap_err();
+        assert!(err.contains("read-only"), "{err}");
+
+        let plugin = definition("modbus_io", json!({"reads": []}));
+        assert!(check_device_start(&plugin).unwrap_err().contains("no write or read"));
+    }
+
+    #[test]
+    fn unreachable_device_is_reported_and_other_kinds_pass() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        drop(listener);
+        let plugin = definition(
+            "modbus_io",
+            json!({"address": address, "reads": ["level:input:0"], "timeout_ms": 50}),
+        );
+        let err = check_device_start(&plugin).unwrap_err();
+        assert!(err.starts_with(&format!("cannot connect to {address}")), "{err}");
+
+        assert!(!needs_start_check("scale"));
+        assert!(check_device_start(&definition("scale", json!({}))).is_ok());
+    }
+
+    #[test]
+    fn device_test_fails_as_a_whole_only_without_a_device() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        drop(listener);
+        let plugin = definition(
+            "modbus_io",
+            json!({"address": address, "reads": ["level:input:0"], "timeout_ms": 50}),
+        );
+        let err = test_device(&plugin, false).unwrap_err();
+        assert!(err.starts_with(&format!("cannot connect to {address}")), "{err}");
+
+        let err = test_device(&definition("scale", json!({})), false).unwrap_err();
+        assert_eq!(err, "'scale' is not a device driver");
+    }
+}
@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+use std::sync::atomic::{fence, AtomicU64, Ordering};
+use workspace::ConnectionDefinition;
+
+/// Connection kind that delivers every sample in order instead of the
+/// latest value.
+pub(crate) const QUEUE_KIND: &str = "queue";
+
+/// Samples a `queue` connection holds before it drops the oldest.
+pub(crate) const QUEUE_CAPACITY: usize = 1024;
+
+/// Bounded lock-free single-producer single-consumer ring of samples.
+///
+/// The producer never waits: when the ring is full it overwrites the oldest
+/// sample, and the consumer counts the overwritten samples as dropped.
+/// Blocking is not an option on the runtime thread, and for recording the
+/// newest data is the more useful to keep.
+pub(crate) struct SampleQueue {
+    slots: Box<[AtomicU64]>,
+    /// Samples ever pushed; written by the producer only.
+    head: AtomicU64,
+    /// Samples ever consumed or dropped; written by the consumer only.
+    tail: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl SampleQueue {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            slots: (0..capacity.max(2)).map(|_| AtomicU64::new(0)).collect(),
+            head: AtomicU64::new(0),
+            tail: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    fn capacity(&self) -> u64 {
+        self.slots.len() as u64
+    }
+
+    /// Producer side.
+    pub(crate) fn push(&self, value: f64) {
+        let head = self.head.load(Ordering::Relaxed);
+        // Pairs with the consumer's fence: a reader that sees this slot
+        // write also sees `head`, and so knows the slot may be overwritten
+        fence(Ordering::Release);
+        self.slots[(head % self.capacity()) as usize].store(value.to_bits(), Ordering::Relaxed);
+        self.head.store(head + 1, Ordering::Release);
+    }
+
+    /// Consumer side: the oldest sample not consumed yet.
+    pub(crate) fn pop(&self) -> Option<f64> {
+        let mut tail = self.tail.load(Ordering::Relaxed);
+        loop {
+            let head = self.head.load(Ordering::Acquire);
+            if tail == head {
+                self.tail.store(tail, Ordering::Relaxed);
+                return None;
+            }
+            // The slot at `head - capacity` may be being overwritten
+            let oldest_safe = (head + 1).saturating_sub(self.capacity());
+            if tail < oldest_safe {
+                self.dropped.fetch_add(oldest_safe - tail, Ordering::Relaxed);
+                tail = oldest_safe;
+                continue;
+            }
+            let bits = self.slots[(tail % self.capacity()) as usize].load(Ordering::Relaxed);
+            fence(Ordering::Acquire);
+            if tail < (self.head.load(Ordering::Relaxed) + 1).saturating_sub(self.capacity()) {
+                // Overwritten while it was read
+                continue;
+            }
+            self.tail.store(tail + 1, Ordering::Relaxed);
+            return Some(f64::from_bits(bits));
+        }
+    }
+
+    pub(crate) fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+type ConnectionKey = (u64, String, u64, String);
+
+fn key(connection: &ConnectionDefinition) -> ConnectionKey {
+    (
+        connection.from_plugin,
+        connection.from_port.clone(),
+        connection.to_plugin,
+        connection.to_port.clone(),
+    )
+}
+
+/// Queues of the workspace's `queue` connections.
+///
+/// Inputs are read from the shared outputs map, so while a target runs the
+/// source outputs it reads through a queue are swapped for the queued
+/// samples, then put back. Every plugin runs once per tick with sources
+/// before targets, so a queue only holds samples while its target is not
+/// running; it then replays them one per tick, oldest first.
+#[derive(Default)]
+pub(crate) struct ConnectionQueues {
+    queues: HashMap<ConnectionKey, SampleQueue>,
+}
+
+impl ConnectionQueues {
+    /// Called after a running plugin's tick: queues its outputs on every
+    /// `queue` connection out of it.
+    pub(crate) fn outputs_written(
+        &mut self,
+        connections: &[ConnectionDefinition],
+        outputs: &HashMap<(u64, String), f64>,
+        plugin_id: u64,
+    ) {
+        for connection in connections
+            .iter()
+            .filter(|conn| conn.from_plugin == plugin_id && conn.kind == QUEUE_KIND)
+        {
+            let Some(value) = outputs.get(&(plugin_id, connection.from_port.clone())) else {
+                continue;
+            };
+            self.queues
+                .entry(key(connection))
+                .or_insert_with(|| SampleQueue::new(QUEUE_CAPACITY))
+                .push(*value);
+        }
+    }
+
+    /// Called before a running plugin reads its inputs. Returns the outputs
+    /// that were replaced, for [`ConnectionQueues::restore`].
+    pub(crate) fn inputs_read(
+        &mut self,
+        connections: &[ConnectionDefinition],
+        outputs: &mut HashMap<(u64, String), f64>,
+        plugin_id: u64,
+    ) -> Vec<((u64, String), Option<f64>)> {
+        let mut replaced = Vec::new();
+        for connection in connections
+            .iter()
+            .filter(|conn| conn.to_plugin == plugin_id && conn.kind == QUEUE_KIND)
+        {
+            let Some(queue) = self.queues.get(&key(connection)) else {
+                continue;
+            };
+            let dropped = queue.dropped();
+            let Some(sample) = queue.pop() else {
+                continue;
+            };
+            if queue.dropped() > dropped {
+                tracing::warn!(
+                    from = connection.from_plugin,
+                    to = plugin_id,
+                    dropped = queue.dropped() - dropped,
+                    "queue connection full, dropped the oldest samples"
+                );
+            }
+            let source = (connection.from_plugin, connection.from_port.clone());
+            let value = outputs.insert(source.clone(), sample);
+            replaced.push((source, value));
+        }
+        replaced
+    }
+
+    /// Puts back the outputs replaced by [`ConnectionQueues::inputs_read`].
+    pub(crate) fn restore(
+        outputs: &mut HashMap<(u64, String), f64>,
+        replaced: Vec<((u64, String), Option<f64>)>,
+    ) {
+        // Reversed, so a port replaced twice ends with its own value
+        for (source, value) in replaced.into_iter().rev() {
+            match value {
+                Some(value) => outputs.insert(source, value),
+                None => outputs.remove(&source),
+            };
+        }
+    }
+
+    /// Drops the backlog into `plugin_id`, e.g. when it restarts.
+    pub(crate) fn clear_into(&mut self, plugin_id: u64) {
+        self.queues.retain(|(_, _, to, _), _| *to != plugin_id);
+    }
+
+    /// Drops the queues of connections no longer in the workspace, so a
+    /// connection added again starts empty.
+    pub(crate) fn retain(&mut self, connections: &[ConnectionDefinition]) {
+        self.queues.retain(|queue_key, _| {
+            connections
+                .iter()
+                .any(|conn| conn.kind == QUEUE_KIND && key(conn) == *queue_key)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_queue_drops_the_oldest_samples() {
+        let queue = SampleQueue::new(4);
+        assert_eq!(queue.pop(), None);
+        for value in 0..3 {
+            queue.push(value as f64);
+        }
+        assert_eq!(queue.pop(), Some(0.0));
+        for value in 3..10 {
+            queue.push(value as f64);
+        }
+        // One slot is kept free for the sample being written
+        let drained: Vec<f64> = std::iter::from_fn(|| queue.pop()).collect();
+        assert_eq!(drained, vec![7.0, 8.0, 9.0]);
+        assert_eq!(queue.dropped(), 6);
+    }
+
+    #[test]
+    fn samples_cross_threads_in_order() {
+        let queue = std::sync::Arc::new(SampleQueue::new(64));
+        let producer = {
+            let queue = queue.clone();
+            std::thread::spawn(move || {
+                for value in 0..100_000 {
+                    queue.push(value as f64);
+                }
+            })
+        };
+        let mut last = -1.0;
+        let mut received = 0u64;
+        while !producer.is_finished() || last < 99_999.0 {
+            if let Some(value) = queue.pop() {
+                assert!(value > last);
+                last = value;
+                received += 1;
+            }
+        }
+        producer.join().unwrap();
+        assert_eq!(received + queue.dropped(), 100_000);
+    }
+
+    #[test]
+    fn queued_samples_replace_the_source_output_while_read() {
+        let connection = ConnectionDefinition {
+            from_plugin: 1,
+            from_port: "out".to_string(),
+            to_plugin: 2,
+            to_port: "in".to_string(),
+            kind: QUEUE_KIND.to_string(),
+            gain: 1.0,
+        };
+        let connections = vec![connection];
+        let mut queues = ConnectionQueues::default();
+        let mut outputs = HashMap::new();
+        for value in [1.0, 2.0, 3.0] {
+            outputs.insert((1, "out".to_string()), value);
+            queues.outputs_written(&connections, &outputs, 1);
+        }
+
+        let replaced = queues.inputs_read(&connections, &mut outputs, 2);
+        assert_eq!(outputs[&(1, "out".to_string())], 1.0);
+        ConnectionQueues::restore(&mut outputs, replaced);
+        assert_eq!(outputs[&(1, "out".to_string())], 3.0);
+
+        queues.clear_into(2);
+        assert!(queues.inputs_read(&connections, &mut outputs, 2).is_empty());
+    }
+}
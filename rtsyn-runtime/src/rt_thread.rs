@@ -5,10 +5,10 @@ compile_error!("`preempt_rt` and `xenomai` features cannot be enabled simultaneo
 mod preempt_rt {
     use libc::{
         clock_gettime, clock_nanosleep, sched_param, sched_setscheduler, syscall, timespec,
-        CLOCK_MONOTONIC, SCHED_FIFO, SYS_gettid, TIMER_ABSTIME,
+        CLOCK_MONOTONIC, EINTR, SCHED_FIFO, SYS_gettid, TIMER_ABSTIME,
     };
     use std::ptr;
-    use std::time::Duration;
+    use std::time::{Duration, Instant};
 
     pub struct PreemptRt;
 
@@ -20,7 +20,7 @@ mod preempt_rt {
                 let ret = sched_setscheduler(tid, SCHED_FIFO, &param);
                 if ret != 0 {
                     let err = std::io::Error::last_os_error();
-                    eprintln!(
+                    tracing::warn!(
                         "Realtime scheduling unavailable ({err}); running without RT priority."
                     );
                     return Ok(());
@@ -29,39 +29,45 @@ mod preempt_rt {
             Ok(())
         }
 
-        pub fn init_sleep(period: Duration) -> timespec {
-            let mut now = timespec {
+        /// Sleeps until `deadline` with an absolute `clock_nanosleep`, so
+        /// ticks paced by the scheduler's deadlines do not drift.
+        pub fn sleep_until(deadline: Instant) {
+            let now = Instant::now();
+            if deadline <= now {
+                return;
+            }
+            // `Instant` follows CLOCK_MONOTONIC; only the offset is converted
+            let mut target = timespec {
                 tv_sec: 0,
                 tv_nsec: 0,
             };
             unsafe {
-                clock_gettime(CLOCK_MONOTONIC, &mut now);
+                clock_gettime(CLOCK_MONOTONIC, &mut target);
             }
-            add_duration(&mut now, period);
-            now
+            add_duration(&mut target, deadline - now);
+            while unsafe { clock_nanosleep(CLOCK_MONOTONIC, TIMER_ABSTIME, &target, ptr::null_mut()) }
+                == EINTR
+            {}
         }
 
-        pub fn sleep(period: Duration, next: &mut timespec) {
-            if period.is_zero() {
+        /// Sleeps for `duration` on the monotonic clock.
+        pub fn sleep(duration: Duration) {
+            if duration.is_zero() {
                 return;
             }
+            let request = timespec {
+                tv_sec: duration.as_secs() as _,
+                tv_nsec: duration.subsec_nanos() as _,
+            };
             unsafe {
-                clock_nanosleep(
-                    CLOCK_MONOTONIC,
-                    TIMER_ABSTIME,
-                    next,
-                    ptr::null_mut(),
-                );
+                clock_nanosleep(CLOCK_MONOTONIC, 0, &request, ptr::null_mut());
             }
-            add_duration(next, period);
         }
     }
 
     fn add_duration(target: &mut timespec, duration: Duration) {
-        let secs = duration.as_secs() as i64;
-        let nanos = duration.subsec_nanos() as i64;
-        target.tv_sec += secs;
-        target.tv_nsec += nanos;
+        target.tv_sec += duration.as_secs() as libc::time_t;
+        target.tv_nsec += duration.subsec_nanos() as libc::c_long;
         if target.tv_nsec >= 1_000_000_000 {
             target.tv_sec += target.tv_nsec / 1_000_000_000;
             target.tv_nsec %= 1_000_000_000;
@@ -71,8 +77,10 @@ mod preempt_rt {
 
 #[cfg(all(not(feature = "preempt_rt"), not(feature = "xenomai")))]
 mod normal_thread {
-    use libc::timespec;
-    use std::{thread, time::Duration};
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
 
     pub struct NormalThread;
 
@@ -81,25 +89,22 @@ mod normal_thread {
             Ok(())
         }
 
-        pub fn init_sleep(_period: Duration) -> timespec {
-            timespec {
-                tv_sec: 0,
-                tv_nsec: 0,
-            }
+        pub fn sleep_until(deadline: Instant) {
+            Self::sleep(deadline.saturating_duration_since(Instant::now()));
         }
 
-        pub fn sleep(period: Duration, _next: &mut timespec) {
-            if period.is_zero() {
+        pub fn sleep(duration: Duration) {
+            if duration.is_zero() {
                 return;
             }
-            // For very short periods, use spin-wait for better precision
-            if period.as_nanos() < 500_000 { // Less than 500μs
+            // For very short waits, use spin-wait for better precision
+            if duration.as_nanos() < 500_000 { // Less than 500μs
                 let start = std::time::Instant::now();
-                while start.elapsed() < period {
+                while start.elapsed() < duration {
                     std::hint::spin_loop();
                 }
             } else {
-                thread::sleep(period);
+                thread::sleep(duration);
             }
         }
     }
@@ -107,8 +112,7 @@ mod normal_thread {
 
 #[cfg(all(not(feature = "preempt_rt"), feature = "xenomai"))]
 mod xenomai {
-    use libc::timespec;
-    use std::time::Duration;
+    use std::time::{Duration, Instant};
 
     pub struct XenomaiRt;
 
@@ -117,14 +121,11 @@ mod xenomai {
             Err("Xenomai backend is not implemented yet.".to_string())
         }
 
-        pub fn init_sleep(_period: Duration) -> timespec {
-            timespec {
-                tv_sec: 0,
-                tv_nsec: 0,
-            }
+        pub fn sleep_until(_deadline: Instant) {
+            // Placeholder; default to no-op
         }
 
-        pub fn sleep(_period: Duration, _next: &mut timespec) {
+        pub fn sleep(_duration: Duration) {
             // Placeholder; default to no-op
         }
     }
@@ -142,19 +143,22 @@ use std::{sync::mpsc, thread};
 pub(crate) struct RuntimeThread;
 
 impl RuntimeThread {
-    pub(crate) fn spawn<F>(f: F) -> Result<thread::JoinHandle<()>, String>
+    pub(crate) fn spawn<F>(name: String, f: F) -> Result<thread::JoinHandle<()>, String>
     where
         F: FnOnce() + Send + 'static,
     {
         let (status_tx, status_rx) = mpsc::sync_channel(1);
-        let handle = thread::spawn(move || {
-            let status = ActiveRtBackend::prepare();
-            let _ = status_tx.send(status.clone());
-            if status.is_err() {
-                return;
-            }
-            f();
-        });
+        let handle = thread::Builder::new()
+            .name(name)
+            .spawn(move || {
+                let status = ActiveRtBackend::prepare();
+                let _ = status_tx.send(status.clone());
+                if status.is_err() {
+                    return;
+                }
+                f();
+            })
+            .map_err(|err| format!("Failed to spawn runtime thread: {err}"))?;
 
         match status_rx.recv() {
             Ok(Ok(())) => Ok(handle),
@@ -169,3 +173,23 @@ impl RuntimeThread {
         }
     }
 }
+
+/// Pins the calling thread to `cores`, the workspace's selected cores. An
+/// empty selection lets it run on any core.
+pub(crate) fn pin_to_cores(cores: &[usize]) {
+    #[cfg(target_os = "linux")]
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        for core in 0..libc::CPU_SETSIZE as usize {
+            if cores.is_empty() || cores.contains(&core) {
+                libc::CPU_SET(core, &mut set);
+            }
+        }
+        if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+            let err = std::io::Error::last_os_error();
+            tracing::warn!(?cores, "could not pin the runtime thread to its cores ({err})");
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = cores;
+}
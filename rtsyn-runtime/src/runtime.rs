@@ -1,18 +1,66 @@
-use csv_recorder_plugin::{normalize_path, CsvRecorderedPlugin};
+use alarm_plugin::AlarmPlugin;
+use audio_out_plugin::{AudioOutPlugin, Mode as AudioOutMode};
+use bang_bang_plugin::BangBangPlugin;
+use bit_pack_plugin::{bit_port as bit_pack_port, BitPackPlugin};
+use bit_unpack_plugin::{bit_port as bit_unpack_port, BitUnpackPlugin};
+use can_io_plugin::CanIoPlugin;
+use csv_recorder_plugin::{
+    normalize_path, CsvRecorderedPlugin, WallTimeFormat as CsvWallTimeFormat,
+    WriteMode as CsvWriteMode,
+};
+use delay_plugin::DelayPlugin;
+use differentiator_plugin::DifferentiatorPlugin;
+use error_metrics_plugin::ErrorMetricsPlugin;
+use estop_plugin::EstopPlugin;
+use fault_recorder_plugin::FaultRecorderPlugin;
+use filtered_derivative_plugin::FilteredDerivativePlugin;
+use frequency_counter_plugin::{Edge as FrequencyCounterEdge, FrequencyCounterPlugin};
+use histogram_plugin::HistogramPlugin;
+use integrator_plugin::IntegratorPlugin;
 use libloading::Library;
 use live_plotter_plugin::LivePlotterPlugin;
+use lookup_table_plugin::{Interpolation, LookupTablePlugin};
+use modbus_io_plugin::{
+    ConnectionConfig as ModbusConnectionConfig, Mode as ModbusMode, ModbusIoPlugin,
+};
+use noise_generator_plugin::{Distribution, NoiseGeneratorPlugin};
+use peak_hold_plugin::PeakHoldPlugin;
 use performance_monitor_plugin::PerformanceMonitorPlugin;
-#[cfg(feature = "comedi")]
+use pwm_plugin::PwmPlugin;
+use resampler_plugin::{Downsampling, Interpolation as ResamplerInterpolation, ResamplerPlugin};
+use rms_plugin::RmsPlugin;
+use rtsyn_core::config;
 use rtsyn_plugin::DeviceDriver;
 use rtsyn_plugin::{Plugin, PluginApi, PluginContext, PluginString, RTSYN_PLUGIN_API_SYMBOL};
 use rtsyn_plugin::ui::DisplaySchema;
+use sample_hold_plugin::SampleHoldPlugin;
+use saturation_plugin::SaturationPlugin;
+use scale_plugin::{Calibration, ScaleMode, ScalePlugin};
 use serde_json::Value;
+use setpoint_ramp_plugin::SetpointRampPlugin;
+use shm_publisher_plugin::ShmPublisherPlugin;
+use state_machine_plugin::StateMachinePlugin;
+use stats_plugin::StatsPlugin;
 use std::collections::{HashMap, HashSet};
 use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
 use std::time::{Duration, Instant};
-use workspace::{input_sum, input_sum_any, order_plugins_for_execution, WorkspaceDefinition};
+use stimulus_plugin::{Chirp, Shape, StimulusPlugin};
+use tcp_stream_plugin::{Format as TcpStreamFormat, TcpStreamPlugin};
+use timer_plugin::{Mode as TimerMode, TimerPlugin};
+use transfer_function_plugin::TransferFunctionPlugin;
+use workspace::{
+    input_value, input_value_any, order_plugins_for_execution, sort_connections_for_evaluation,
+    ConnectionDefinition, MissedDeadlinePolicy, PluginDefinition, WorkspaceDefinition,
+    WorkspaceEdit,
+};
 
-use crate::rt_thread::{ActiveRtBackend, RuntimeThread};
+use crate::graphs::Graphs;
+use crate::latency::ConnectionLatency;
+pub use crate::latency::LatencyStats;
+use crate::queue::ConnectionQueues;
+use crate::rt_thread::{pin_to_cores, ActiveRtBackend, RuntimeThread};
+use crate::scheduler::{StepMode, TickScheduler};
+use crate::warmup::{warmup_ticks, Warmup};
 
 #[derive(Debug, Clone)]
 pub struct LogicSettings {
@@ -22,6 +70,10 @@ pub struct LogicSettings {
     pub time_label: String,
     pub ui_hz: f64,
     pub max_integration_steps: usize, // Maximum integration steps per plugin per tick
+    pub missed_deadline_policy: MissedDeadlinePolicy,
+    /// Bound in microseconds of a random offset added to every tick, for
+    /// testing robustness to imperfect timing. Zero in normal use.
+    pub jitter_us: f64,
 }
 
 #[derive(Debug, Clone)]
@@ -32,37 +84,333 @@ pub struct LogicState {
     pub viewer_values: HashMap<u64, f64>,
     pub tick: u64,
     pub plotter_samples: HashMap<u64, Vec<(u64, Vec<f64>)>>,
+    /// Plugins whose outputs are held at zero until their warmup elapses.
+    pub warming_up: HashSet<u64>,
+    /// Device drivers that failed to open, with the reason.
+    pub open_errors: HashMap<u64, String>,
+    /// Plugins whose library is missing or failed to load, with the reason.
+    /// They stay in the workspace but do not run.
+    pub load_errors: HashMap<u64, String>,
+    /// Plugin events since the previous state, oldest first.
+    pub events: Vec<PluginEvent>,
+    /// Whether ticks run only on `LogicMessage::Step`.
+    pub step_mode: bool,
+}
+
+/// A discrete event a plugin reported during a tick, such as an alarm
+/// tripping or a state machine entering a state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluginEvent {
+    pub plugin_id: u64,
+    pub tick: u64,
+    /// Runtime time of the tick, `tick * period`.
+    pub time_seconds: f64,
+    pub name: String,
+    pub value: serde_json::Value,
+}
+
+/// Events held for the next `LogicState`; later ones are dropped (and
+/// logged) until it is sent.
+const MAX_PENDING_EVENTS: usize = 256;
+
+/// How often a loop paused in step mode checks for messages.
+const STEP_MODE_POLL: Duration = Duration::from_millis(1);
+
+/// Queues the events a plugin reported this tick and logs each at info
+/// level.
+fn queue_plugin_events(
+    pending: &mut Vec<PluginEvent>,
+    plugin_id: u64,
+    tick: u64,
+    period_seconds: f64,
+    events: impl Iterator<Item = (String, serde_json::Value)>,
+) {
+    for (name, value) in events {
+        if pending.len() >= MAX_PENDING_EVENTS {
+            tracing::warn!(plugin = plugin_id, event = %name, "event queue full, dropping event");
+            continue;
+        }
+        tracing::info!(plugin = plugin_id, tick, event = %name, %value, "plugin event");
+        pending.push(PluginEvent {
+            plugin_id,
+            tick,
+            time_seconds: tick as f64 * period_seconds,
+            name,
+            value,
+        });
+    }
+}
+
+/// Scheduling state of a plugin as seen by the runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginRunState {
+    Running,
+    /// Enabled, but held by step mode between requested ticks.
+    Paused,
+    Stopped,
+    /// Present in the workspace but without a runtime instance (e.g. the library failed to load).
+    Failed,
 }
 
 #[derive(Debug, Clone)]
 pub enum LogicMessage {
     UpdateSettings(LogicSettings),
     UpdateWorkspace(WorkspaceDefinition),
+    /// Incremental workspace changes: only the named plugin or connection is
+    /// touched, every other instance keeps running with its state.
+    AddPlugin(PluginDefinition),
+    RemovePlugin(u64),
+    UpdatePluginConfig(u64, serde_json::Value),
+    AddConnection(ConnectionDefinition),
+    RemoveConnection(ConnectionDefinition),
     SetPluginRunning(u64, bool),
     RestartPlugin(u64),
     QueryPluginBehavior(String, Option<String>, Sender<Option<rtsyn_plugin::ui::PluginBehavior>>),
     QueryPluginMetadata(String, Sender<Option<(Vec<String>, Vec<String>, Vec<(String, f64)>, Option<rtsyn_plugin::ui::DisplaySchema>, Option<rtsyn_plugin::ui::UISchema>)>>),
     GetPluginVariable(u64, String, Sender<Option<serde_json::Value>>),
     SetPluginVariable(u64, String, serde_json::Value),
+    /// Applies several config changes to one plugin in order, all before
+    /// its next tick.
+    SetPluginVariables(u64, Vec<(String, serde_json::Value)>),
+    QueryPluginStates(Sender<HashMap<u64, PluginRunState>>),
+    /// Turns scheduling delay tracking of connections on or off. It is off
+    /// by default so the tick loop does not time transfers no one reads.
+    TrackConnectionLatency(bool),
+    /// Scheduling delay of recent reads of each connection, while tracked.
+    QueryConnectionLatency(Sender<Vec<(ConnectionDefinition, LatencyStats)>>),
+    SetLogLevel(crate::logging::Level),
+    /// Starts a named graph with its own tick loop; its state is sent on the
+    /// given channel.
+    CreateGraph(String, Sender<LogicState>),
+    RemoveGraph(String),
+    /// Delivers a message, e.g. `UpdateWorkspace`, to a named graph. Plugin
+    /// ids in it refer to that graph's workspace.
+    ToGraph(String, Box<LogicMessage>),
+    /// Lets a device driver that failed to open try again.
+    RetryOpen(u64),
+    /// In step mode ticks stop following the timer and run only when
+    /// requested; leaving it resumes timed execution.
+    SetStepMode(bool),
+    /// Runs this many ticks, back to back, while in step mode.
+    Step(u64),
+    /// Runs a plugin's self-test between ticks and sends its outcome.
+    RunSelfTest(u64, Sender<Result<(), String>>),
+}
+
+impl From<WorkspaceEdit> for LogicMessage {
+    fn from(edit: WorkspaceEdit) -> Self {
+        match edit {
+            WorkspaceEdit::AddPlugin(plugin) => LogicMessage::AddPlugin(plugin),
+            WorkspaceEdit::RemovePlugin(id) => LogicMessage::RemovePlugin(id),
+            WorkspaceEdit::UpdatePluginConfig(id, config) => {
+                LogicMessage::UpdatePluginConfig(id, config)
+            }
+            WorkspaceEdit::SetPluginRunning(id, running) => {
+                LogicMessage::SetPluginRunning(id, running)
+            }
+            WorkspaceEdit::AddConnection(connection) => LogicMessage::AddConnection(connection),
+            WorkspaceEdit::RemoveConnection(connection) => {
+                LogicMessage::RemoveConnection(connection)
+            }
+        }
+    }
 }
 
 enum RuntimePlugin {
     CsvRecorder(CsvRecorderedPlugin),
     LivePlotter(LivePlotterPlugin),
     PerformanceMonitor(PerformanceMonitorPlugin),
+    BangBang(BangBangPlugin),
+    NoiseGenerator(NoiseGeneratorPlugin),
+    Integrator(IntegratorPlugin),
+    Differentiator(DifferentiatorPlugin),
+    Delay(DelayPlugin),
+    Alarm(AlarmPlugin),
+    SampleHold(SampleHoldPlugin),
+    Scale(ScalePlugin),
+    CanIo(CanIoPlugin),
+    Stimulus(StimulusPlugin),
+    Saturation(SaturationPlugin),
+    Histogram(HistogramPlugin),
+    TcpStream(TcpStreamPlugin),
+    ShmPublisher(ShmPublisherPlugin),
+    Rms(RmsPlugin),
+    PeakHold(PeakHoldPlugin),
+    Timer(TimerPlugin),
+    SetpointRamp(SetpointRampPlugin),
+    LookupTable(LookupTablePlugin),
+    Stats(StatsPlugin),
+    Resampler(ResamplerPlugin),
+    Pwm(PwmPlugin),
+    Estop(EstopPlugin),
+    AudioOut(AudioOutPlugin),
+    FrequencyCounter(FrequencyCounterPlugin),
+    ModbusIo(ModbusIoPlugin),
+    StateMachine(StateMachinePlugin),
+    FaultRecorder(FaultRecorderPlugin),
+    FilteredDerivative(FilteredDerivativePlugin),
+    BitPack(BitPackPlugin),
+    BitUnpack(BitUnpackPlugin),
+    TransferFunction(TransferFunctionPlugin),
+    ErrorMetrics(ErrorMetricsPlugin),
     #[cfg(feature = "comedi")]
     ComediDaq(comedi_daq_plugin::ComediDaqPlugin),
+    #[cfg(feature = "rpi")]
+    Gpio(gpio_plugin::GpioPlugin),
     Dynamic(DynamicPluginInstance),
 }
 
-struct DynamicPluginInstance {
+/// Applies one config change to a running plugin instance.
+fn set_runtime_variable(instance: &mut RuntimePlugin, name: &str, value: serde_json::Value) {
+    let _ = match instance {
+        RuntimePlugin::CsvRecorder(p) => p.set_variable(name, value),
+        RuntimePlugin::LivePlotter(p) => p.set_variable(name, value),
+        RuntimePlugin::PerformanceMonitor(p) => p.set_variable(name, value),
+        RuntimePlugin::BangBang(p) => p.set_variable(name, value),
+        RuntimePlugin::NoiseGenerator(p) => p.set_variable(name, value),
+        RuntimePlugin::Integrator(p) => p.set_variable(name, value),
+        RuntimePlugin::Differentiator(p) => p.set_variable(name, value),
+        RuntimePlugin::Delay(p) => p.set_variable(name, value),
+        RuntimePlugin::Alarm(p) => p.set_variable(name, value),
+        RuntimePlugin::SampleHold(p) => p.set_variable(name, value),
+        RuntimePlugin::Scale(p) => p.set_variable(name, value),
+        RuntimePlugin::CanIo(p) => p.set_variable(name, value),
+        RuntimePlugin::Stimulus(p) => p.set_variable(name, value),
+        RuntimePlugin::Saturation(p) => p.set_variable(name, value),
+        RuntimePlugin::Histogram(p) => p.set_variable(name, value),
+        RuntimePlugin::TcpStream(p) => p.set_variable(name, value),
+        RuntimePlugin::ShmPublisher(p) => p.set_variable(name, value),
+        RuntimePlugin::Rms(p) => p.set_variable(name, value),
+        RuntimePlugin::PeakHold(p) => p.set_variable(name, value),
+        RuntimePlugin::Timer(p) => p.set_variable(name, value),
+        RuntimePlugin::SetpointRamp(p) => p.set_variable(name, value),
+        RuntimePlugin::LookupTable(p) => p.set_variable(name, value),
+        RuntimePlugin::Stats(p) => p.set_variable(name, value),
+        RuntimePlugin::Resampler(p) => p.set_variable(name, value),
+        RuntimePlugin::Pwm(p) => p.set_variable(name, value),
+        RuntimePlugin::Estop(p) => p.set_variable(name, value),
+        RuntimePlugin::AudioOut(p) => p.set_variable(name, value),
+        RuntimePlugin::FrequencyCounter(p) => p.set_variable(name, value),
+        RuntimePlugin::ModbusIo(p) => p.set_variable(name, value),
+        RuntimePlugin::StateMachine(p) => p.set_variable(name, value),
+        RuntimePlugin::FaultRecorder(p) => p.set_variable(name, value),
+        RuntimePlugin::FilteredDerivative(p) => p.set_variable(name, value),
+        RuntimePlugin::BitPack(p) => p.set_variable(name, value),
+        RuntimePlugin::BitUnpack(p) => p.set_variable(name, value),
+        RuntimePlugin::TransferFunction(p) => p.set_variable(name, value),
+        RuntimePlugin::ErrorMetrics(p) => p.set_variable(name, value),
+        #[cfg(feature = "comedi")]
+        RuntimePlugin::ComediDaq(p) => p.set_variable(name, value),
+        #[cfg(feature = "rpi")]
+        RuntimePlugin::Gpio(p) => p.set_variable(name, value),
+        RuntimePlugin::Dynamic(_) => Ok(()),
+    };
+}
+
+/// UI schema of a plugin instance, which says how its config values are
+/// typed.
+fn runtime_ui_schema(instance: &RuntimePlugin) -> Option<rtsyn_plugin::ui::UISchema> {
+    match instance {
+    RuntimePlugin::CsvRecorder(p) => p.ui_schema(),
+    RuntimePlugin::LivePlotter(p) => p.ui_schema(),
+    RuntimePlugin::PerformanceMonitor(p) => p.ui_schema(),
+    RuntimePlugin::BangBang(p) => p.ui_schema(),
+    RuntimePlugin::NoiseGenerator(p) => p.ui_schema(),
+    RuntimePlugin::Integrator(p) => p.ui_schema(),
+    RuntimePlugin::Differentiator(p) => p.ui_schema(),
+    RuntimePlugin::Delay(p) => p.ui_schema(),
+    RuntimePlugin::Alarm(p) => p.ui_schema(),
+    RuntimePlugin::SampleHold(p) => p.ui_schema(),
+    RuntimePlugin::Scale(p) => p.ui_schema(),
+    RuntimePlugin::CanIo(p) => p.ui_schema(),
+    RuntimePlugin::Stimulus(p) => p.ui_schema(),
+    RuntimePlugin::Saturation(p) => p.ui_schema(),
+    RuntimePlugin::Histogram(p) => p.ui_schema(),
+    RuntimePlugin::TcpStream(p) => p.ui_schema(),
+    RuntimePlugin::ShmPublisher(p) => p.ui_schema(),
+    RuntimePlugin::Rms(p) => p.ui_schema(),
+    RuntimePlugin::PeakHold(p) => p.ui_schema(),
+    RuntimePlugin::Timer(p) => p.ui_schema(),
+    RuntimePlugin::SetpointRamp(p) => p.ui_schema(),
+    RuntimePlugin::LookupTable(p) => p.ui_schema(),
+    RuntimePlugin::Stats(p) => p.ui_schema(),
+    RuntimePlugin::Resampler(p) => p.ui_schema(),
+    RuntimePlugin::Pwm(p) => p.ui_schema(),
+    RuntimePlugin::Estop(p) => p.ui_schema(),
+    RuntimePlugin::AudioOut(p) => p.ui_schema(),
+    RuntimePlugin::FrequencyCounter(p) => p.ui_schema(),
+    RuntimePlugin::ModbusIo(p) => p.ui_schema(),
+    RuntimePlugin::StateMachine(p) => p.ui_schema(),
+    RuntimePlugin::FaultRecorder(p) => p.ui_schema(),
+    RuntimePlugin::FilteredDerivative(p) => p.ui_schema(),
+    RuntimePlugin::BitPack(p) => p.ui_schema(),
+    RuntimePlugin::BitUnpack(p) => p.ui_schema(),
+    RuntimePlugin::TransferFunction(p) => p.ui_schema(),
+    RuntimePlugin::ErrorMetrics(p) => p.ui_schema(),
+    #[cfg(feature = "comedi")]
+    RuntimePlugin::ComediDaq(p) => p.ui_schema(),
+    #[cfg(feature = "rpi")]
+    RuntimePlugin::Gpio(p) => p.ui_schema(),
+    RuntimePlugin::Dynamic(dynamic) => {
+            let schema_fn = unsafe { (*dynamic.api).ui_schema_json }?;
+            let schema_str = schema_fn(dynamic.handle);
+            if schema_str.ptr.is_null() || schema_str.len == 0 {
+                return None;
+            }
+            let json = unsafe { schema_str.into_string() };
+            serde_json::from_str(&json).ok()
+        }
+    }
+}
+
+/// Warns about config values a plugin cannot read, which the tick loop
+/// replaces with defaults. Called when the config changes, not per tick.
+fn warn_malformed_config(instance: Option<&RuntimePlugin>, plugin: &PluginDefinition) {
+    let Some(schema) = instance.and_then(runtime_ui_schema) else {
+        return;
+    };
+    for err in config::malformed_numbers(&plugin.config, &schema) {
+        tracing::warn!(plugin = plugin.id, kind = %plugin.kind, "config {err}, using the default");
+    }
+}
+
+/// A device driver is healthy when it is open, or, while stopped, when its
+/// pre-start check passes; a failed open is reported as it is.
+fn driver_self_test(
+    is_open: bool,
+    open_error: Option<&str>,
+    check_start: impl FnOnce() -> Result<(), String>,
+) -> Result<(), String> {
+    match open_error {
+        Some(err) => Err(err.to_string()),
+        None if is_open => Ok(()),
+        None => check_start(),
+    }
+}
+
+/// Runs the self-test of plugins that have one. Every other plugin passes,
+/// dynamic ones included since the plugin API has no self-test entry yet.
+fn run_self_test(instance: &mut RuntimePlugin) -> Result<(), String> {
+    match instance {
+        RuntimePlugin::TransferFunction(p) => p.self_test(),
+        RuntimePlugin::CanIo(p) => driver_self_test(p.is_open(), p.open_error(), || p.check_start()),
+        RuntimePlugin::ModbusIo(p) => driver_self_test(p.is_open(), p.open_error(), || p.check_start()),
+        #[cfg(feature = "comedi")]
+        RuntimePlugin::ComediDaq(p) => driver_self_test(p.is_open(), p.open_error(), || p.check_start()),
+        #[cfg(feature = "rpi")]
+        RuntimePlugin::Gpio(p) => driver_self_test(p.is_open(), p.open_error(), || p.check_start()),
+        _ => Ok(()),
+    }
+}
+
+pub(crate) struct DynamicPluginInstance {
     _lib: Library,
-    api: *const PluginApi,
-    handle: *mut std::ffi::c_void,
+    pub(crate) api: *const PluginApi,
+    pub(crate) handle: *mut std::ffi::c_void,
     inputs: Vec<String>,
     outputs: Vec<String>,
-    input_bytes: Vec<Vec<u8>>,
-    output_bytes: Vec<Vec<u8>>,
+    pub(crate) input_bytes: Vec<Vec<u8>>,
+    pub(crate) output_bytes: Vec<Vec<u8>>,
     internal_variables: Vec<String>,
     internal_variable_bytes: Vec<Vec<u8>>,
     last_config: Option<String>,
@@ -70,7 +418,7 @@ struct DynamicPluginInstance {
 }
 
 impl DynamicPluginInstance {
-    unsafe fn load(path: &str, id: u64) -> Option<Self> {
+    pub(crate) unsafe fn load(path: &str, id: u64) -> Option<Self> {
         let lib = Library::new(path).ok()?;
         let symbol: libloading::Symbol<unsafe extern "C" fn() -> *const PluginApi> =
             lib.get(RTSYN_PLUGIN_API_SYMBOL.as_bytes()).ok()?;
@@ -134,11 +482,263 @@ impl DynamicPluginInstance {
     }
 }
 
+/// Run state of every workspace plugin. `paused` is set while step mode
+/// holds the loop, so enabled plugins report [`PluginRunState::Paused`].
+fn plugin_run_states(
+    workspace: Option<&WorkspaceDefinition>,
+    plugin_instances: &HashMap<u64, RuntimePlugin>,
+    plugin_running: &HashMap<u64, bool>,
+    paused: bool,
+) -> HashMap<u64, PluginRunState> {
+    let Some(ws) = workspace else {
+        return HashMap::new();
+    };
+    ws.plugins
+        .iter()
+        .map(|plugin| {
+            let running = plugin_running.get(&plugin.id).copied().unwrap_or(plugin.running);
+            let state = if !plugin_instances.contains_key(&plugin.id) {
+                PluginRunState::Failed
+            } else if running && paused {
+                PluginRunState::Paused
+            } else if running {
+                PluginRunState::Running
+            } else {
+                PluginRunState::Stopped
+            };
+            (plugin.id, state)
+        })
+        .collect()
+}
+
+/// Plugins an emergency stop leaves running: the device drivers it puts in
+/// their safe state, the estops themselves and passive observers.
+fn is_estop_essential(kind: &str) -> bool {
+    matches!(
+        kind,
+        "estop"
+            | "can_io"
+            | "modbus_io"
+            | "comedi_daq"
+            | "gpio"
+            | "csv_recorder"
+            | "fault_recorder"
+            | "live_plotter"
+            | "performance_monitor"
+    )
+}
+
+/// Records a driver's open error for `LogicState::open_errors`, logging each
+/// new reason once.
+fn track_open_error(open_errors: &mut HashMap<u64, String>, plugin_id: u64, error: Option<&str>) {
+    let Some(error) = error else {
+        open_errors.remove(&plugin_id);
+        return;
+    };
+    if open_errors.get(&plugin_id).map(String::as_str) != Some(error) {
+        tracing::info!(plugin = plugin_id, error, "device failed to open");
+        open_errors.insert(plugin_id, error.to_string());
+    }
+}
+
+/// Creates the runtime instance for a workspace plugin, or says why it cannot
+/// be created: its kind is unknown or its library is missing or fails to load.
+fn instantiate_plugin(plugin: &PluginDefinition) -> Result<RuntimePlugin, String> {
+    let instance = match plugin.kind.as_str() {
+        "csv_recorder" => RuntimePlugin::CsvRecorder(
+            CsvRecorderedPlugin::new(plugin.id),
+        ),
+        "live_plotter" => RuntimePlugin::LivePlotter(
+            LivePlotterPlugin::new(plugin.id),
+        ),
+        "performance_monitor" => RuntimePlugin::PerformanceMonitor(
+            PerformanceMonitorPlugin::new(plugin.id),
+        ),
+        "bang_bang" => RuntimePlugin::BangBang(BangBangPlugin::new(plugin.id)),
+        "noise_generator" => RuntimePlugin::NoiseGenerator(
+            NoiseGeneratorPlugin::new(plugin.id),
+        ),
+        "integrator" => RuntimePlugin::Integrator(
+            IntegratorPlugin::new(plugin.id),
+        ),
+        "differentiator" => RuntimePlugin::Differentiator(
+            DifferentiatorPlugin::new(plugin.id),
+        ),
+        "delay" => RuntimePlugin::Delay(
+            DelayPlugin::new(plugin.id),
+        ),
+        "alarm" => RuntimePlugin::Alarm(
+            AlarmPlugin::new(plugin.id),
+        ),
+        "sample_hold" => RuntimePlugin::SampleHold(
+            SampleHoldPlugin::new(plugin.id),
+        ),
+        "scale" => RuntimePlugin::Scale(
+            ScalePlugin::new(plugin.id),
+        ),
+        "can_io" => RuntimePlugin::CanIo(
+            CanIoPlugin::new(plugin.id),
+        ),
+        "stimulus" => RuntimePlugin::Stimulus(
+            StimulusPlugin::new(plugin.id),
+        ),
+        "saturation" => RuntimePlugin::Saturation(
+            SaturationPlugin::new(plugin.id),
+        ),
+        "histogram" => RuntimePlugin::Histogram(
+            HistogramPlugin::new(plugin.id),
+        ),
+        "tcp_stream" => RuntimePlugin::TcpStream(
+            TcpStreamPlugin::new(plugin.id),
+        ),
+        "shm_publisher" => RuntimePlugin::ShmPublisher(
+            ShmPublisherPlugin::new(plugin.id),
+        ),
+        "rms" => RuntimePlugin::Rms(
+            RmsPlugin::new(plugin.id),
+        ),
+        "peak_hold" => RuntimePlugin::PeakHold(
+            PeakHoldPlugin::new(plugin.id),
+        ),
+        "timer" => RuntimePlugin::Timer(
+            TimerPlugin::new(plugin.id),
+        ),
+        "setpoint_ramp" => RuntimePlugin::SetpointRamp(
+            SetpointRampPlugin::new(plugin.id),
+        ),
+        "lookup_table" => RuntimePlugin::LookupTable(
+            LookupTablePlugin::new(plugin.id),
+        ),
+        "stats" => RuntimePlugin::Stats(
+            StatsPlugin::new(plugin.id),
+        ),
+        "resampler" => RuntimePlugin::Resampler(
+            ResamplerPlugin::new(plugin.id),
+        ),
+        "pwm" => RuntimePlugin::Pwm(
+            PwmPlugin::new(plugin.id),
+        ),
+        "estop" => RuntimePlugin::Estop(
+            EstopPlugin::new(plugin.id),
+        ),
+        "audio_out" => RuntimePlugin::AudioOut(
+            AudioOutPlugin::new(plugin.id),
+        ),
+        "frequency_counter" => RuntimePlugin::FrequencyCounter(
+            FrequencyCounterPlugin::new(plugin.id),
+        ),
+        "modbus_io" => RuntimePlugin::ModbusIo(
+            ModbusIoPlugin::new(plugin.id),
+        ),
+        "state_machine" => RuntimePlugin::StateMachine(
+            StateMachinePlugin::new(plugin.id),
+        ),
+        "fault_recorder" => RuntimePlugin::FaultRecorder(
+            FaultRecorderPlugin::new(plugin.id),
+        ),
+        "filtered_derivative" => RuntimePlugin::FilteredDerivative(
+            FilteredDerivativePlugin::new(plugin.id),
+        ),
+        "bit_pack" => RuntimePlugin::BitPack(BitPackPlugin::new(plugin.id)),
+        "bit_unpack" => RuntimePlugin::BitUnpack(BitUnpackPlugin::new(plugin.id)),
+        "transfer_function" => RuntimePlugin::TransferFunction(TransferFunctionPlugin::new(plugin.id)),
+        "error_metrics" => RuntimePlugin::ErrorMetrics(ErrorMetricsPlugin::new(plugin.id)),
+        #[cfg(feature = "comedi")]
+        "comedi_daq" => RuntimePlugin::ComediDaq(
+            comedi_daq_plugin::ComediDaqPlugin::new(plugin.id),
+        ),
+        #[cfg(feature = "rpi")]
+        "gpio" => RuntimePlugin::Gpio(gpio_plugin::GpioPlugin::new(plugin.id)),
+        _ => {
+            let library_path = plugin
+                .config
+                .get("library_path")
+                .and_then(|v| v.as_str());
+            if let Some(path) = library_path {
+                unsafe {
+                    if let Some(dynamic) =
+                        DynamicPluginInstance::load(path, plugin.id)
+                    {
+                        RuntimePlugin::Dynamic(dynamic)
+                    } else {
+                        tracing::error!(
+                            plugin = plugin.id,
+                            path,
+                            "failed to load plugin library"
+                        );
+                        if !std::path::Path::new(path).is_file() {
+                            return Err(format!("library missing: {path}"));
+                        }
+                        return Err(format!("library failed to load: {path}"));
+                    }
+                }
+            } else {
+                tracing::warn!(
+                    plugin = plugin.id,
+                    kind = %plugin.kind,
+                    "unknown plugin kind without library_path"
+                );
+                return Err(format!("no library for plugin kind '{}'", plugin.kind));
+            }
+        }
+    };
+    Ok(instance)
+}
+
+/// Instantiates `plugin` into `plugin_instances`, or records why it could not
+/// be for `LogicState::load_errors`.
+fn load_plugin_instance(
+    plugin_instances: &mut HashMap<u64, RuntimePlugin>,
+    load_errors: &mut HashMap<u64, String>,
+    plugin: &PluginDefinition,
+) {
+    match instantiate_plugin(plugin) {
+        Ok(instance) => {
+            plugin_instances.insert(plugin.id, instance);
+            load_errors.remove(&plugin.id);
+        }
+        Err(err) => {
+            load_errors.insert(plugin.id, err);
+        }
+    }
+}
+
+/// What the tick loop tracks per plugin beyond its values: warmup, connection
+/// latency and queued samples.
+#[derive(Default)]
+struct PluginTracking {
+    warmup: Warmup,
+    latency: ConnectionLatency,
+    queues: ConnectionQueues,
+}
+
+/// Drops everything the tick loop keeps for `plugin_id`, so a removed plugin
+/// leaves nothing behind and a restarted one starts from scratch.
+fn clear_plugin_state(
+    plugin_id: u64,
+    tracking: &mut PluginTracking,
+    outputs: &mut HashMap<(u64, String), f64>,
+    input_values: &mut HashMap<(u64, String), f64>,
+    internal_variable_values: &mut HashMap<(u64, String), serde_json::Value>,
+    viewer_values: &mut HashMap<u64, f64>,
+    plotter_samples: &mut HashMap<u64, Vec<(u64, Vec<f64>)>>,
+) {
+    tracking.warmup.restart(plugin_id);
+    tracking.latency.forget(plugin_id);
+    tracking.queues.clear_into(plugin_id);
+    outputs.retain(|(pid, _), _| *pid != plugin_id);
+    input_values.retain(|(pid, _), _| *pid != plugin_id);
+    internal_variable_values.retain(|(pid, _), _| *pid != plugin_id);
+    viewer_values.remove(&plugin_id);
+    plotter_samples.remove(&plugin_id);
+}
+
 pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), String> {
     let (logic_tx, logic_rx) = mpsc::channel::<LogicMessage>();
     let (logic_state_tx, logic_state_rx) = mpsc::channel::<LogicState>();
 
-    RuntimeThread::spawn(move || {
+    crate::logging::init();
+    RuntimeThread::spawn("rtsyn-runtime".to_string(), move || {
         let mut settings = LogicSettings {
             cores: vec![0],
             period_seconds: 0.001,
@@ -146,12 +746,24 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
             time_label: "time_ms".to_string(),
             ui_hz: 60.0,
             max_integration_steps: 10, // Reasonable default for real-time performance
+            missed_deadline_policy: MissedDeadlinePolicy::default(),
+            jitter_us: 0.0,
         };
-        let mut period_duration = Duration::from_secs_f64(settings.period_seconds.max(0.0));
-        let mut sleep_deadline = ActiveRtBackend::init_sleep(period_duration);
+        let mut scheduler = TickScheduler::new(
+            settings.missed_deadline_policy,
+            Duration::from_secs_f64(settings.period_seconds.max(0.0)),
+        );
         let mut workspace: Option<WorkspaceDefinition> = None;
+        // Recomputed when the workspace changes, not every tick
+        let mut execution_order: Vec<PluginDefinition> = Vec::new();
         let mut plugin_instances: HashMap<u64, RuntimePlugin> = HashMap::new();
         let mut plugin_running: HashMap<u64, bool> = HashMap::new();
+        let mut tracking = PluginTracking::default();
+        let mut open_errors: HashMap<u64, String> = HashMap::new();
+        let mut load_errors: HashMap<u64, String> = HashMap::new();
+        let mut estop_active = false;
+        let mut step_mode = StepMode::default();
+        let mut graphs = Graphs::default();
         let mut plugin_ctx = PluginContext {
             period_seconds: settings.period_seconds,
             ..Default::default()
@@ -161,6 +773,7 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
         let mut internal_variable_values: HashMap<(u64, String), serde_json::Value> = HashMap::new();
         let mut viewer_values: HashMap<u64, f64> = HashMap::new();
         let mut plotter_samples: HashMap<u64, Vec<(u64, Vec<f64>)>> = HashMap::new();
+        let mut plugin_events: Vec<PluginEvent> = Vec::new();
         let mut runtime = crate::Runtime::new(workspace::WorkspaceDefinition {
             name: "test".to_string(),
             description: String::new(),
@@ -168,6 +781,8 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
             plugins: Vec::new(),
             connections: Vec::new(),
             settings: workspace::WorkspaceSettings::default(),
+            parameters: Default::default(),
+            metadata: Default::default(),
         });
         let mut last_state = Instant::now();
 
@@ -178,54 +793,39 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
                     Ok(message) => match message {
                     LogicMessage::UpdateSettings(new_settings) => {
                         settings = new_settings;
-                        period_duration = Duration::from_secs_f64(settings.period_seconds.max(0.0));
-                        sleep_deadline = ActiveRtBackend::init_sleep(period_duration);
+                        pin_to_cores(&settings.cores);
+                        scheduler.reconfigure(
+                            settings.missed_deadline_policy,
+                            Duration::from_secs_f64(settings.period_seconds.max(0.0)),
+                        );
+                        scheduler.set_jitter(Duration::from_secs_f64(settings.jitter_us.max(0.0) / 1e6));
                         plugin_ctx.period_seconds = settings.period_seconds;
+                        graphs.update_settings(&settings);
+                        tracing::info!(
+                            period_seconds = settings.period_seconds,
+                            policy = ?settings.missed_deadline_policy,
+                            jitter_us = settings.jitter_us,
+                            "runtime settings updated"
+                        );
                     }
-                    LogicMessage::UpdateWorkspace(new_workspace) => {
+                    LogicMessage::UpdateWorkspace(mut new_workspace) => {
                         let mut new_ids: HashSet<u64> = HashSet::new();
                         for plugin in &new_workspace.plugins {
                             new_ids.insert(plugin.id);
                             plugin_running.insert(plugin.id, plugin.running);
                             if !plugin_instances.contains_key(&plugin.id) {
-                                let instance = match plugin.kind.as_str() {
-                                    "csv_recorder" => RuntimePlugin::CsvRecorder(
-                                        CsvRecorderedPlugin::new(plugin.id),
-                                    ),
-                                    "live_plotter" => RuntimePlugin::LivePlotter(
-                                        LivePlotterPlugin::new(plugin.id),
-                                    ),
-                                    "performance_monitor" => RuntimePlugin::PerformanceMonitor(
-                                        PerformanceMonitorPlugin::new(plugin.id),
-                                    ),
-                                    #[cfg(feature = "comedi")]
-                                    "comedi_daq" => RuntimePlugin::ComediDaq(
-                                        comedi_daq_plugin::ComediDaqPlugin::new(plugin.id),
-                                    ),
-                                    _ => {
-                                        let library_path = plugin
-                                            .config
-                                            .get("library_path")
-                                            .and_then(|v| v.as_str());
-                                        if let Some(path) = library_path {
-                                            unsafe {
-                                                if let Some(dynamic) =
-                                                    DynamicPluginInstance::load(path, plugin.id)
-                                                {
-                                                    RuntimePlugin::Dynamic(dynamic)
-                                                } else {
-                                                    continue;
-                                                }
-                                            }
-                                        } else {
-                                            continue;
-                                        }
-                                    }
-                                };
-                                plugin_instances.insert(plugin.id, instance);
+                                load_plugin_instance(&mut plugin_instances, &mut load_errors, plugin);
+                            }
+                            let previous = workspace
+                                .as_ref()
+                                .and_then(|ws| ws.plugins.iter().find(|p| p.id == plugin.id));
+                            if !matches!(previous, Some(previous) if previous.config == plugin.config) {
+                                warn_malformed_config(plugin_instances.get(&plugin.id), plugin);
                             }
                         }
 
+                        open_errors.retain(|id, _| new_ids.contains(id));
+                        load_errors.retain(|id, _| new_ids.contains(id));
                         let removed_ids: Vec<u64> = plugin_instances
                             .keys()
                             .filter(|id| !new_ids.contains(id))
@@ -238,24 +838,217 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
                                 }
                             }
                             plugin_running.remove(&id);
-                            viewer_values.remove(&id);
-                            outputs.retain(|(pid, _), _| *pid != id);
-                            input_values.retain(|(pid, _), _| *pid != id);
-                            internal_variable_values.retain(|(pid, _), _| *pid != id);
-                            plotter_samples.remove(&id);
+                            clear_plugin_state(
+                                id,
+                                &mut tracking,
+                                &mut outputs,
+                                &mut input_values,
+                                &mut internal_variable_values,
+                                &mut viewer_values,
+                                &mut plotter_samples,
+                            );
+                        }
+                        for conn in &new_workspace.connections {
+                            if new_ids.contains(&conn.from_plugin) && new_ids.contains(&conn.to_plugin) {
+                                tracing::debug!(
+                                    "connection {}:{} -> {}:{}",
+                                    conn.from_plugin,
+                                    conn.from_port,
+                                    conn.to_plugin,
+                                    conn.to_port
+                                );
+                            } else {
+                                tracing::warn!(
+                                    from = conn.from_plugin,
+                                    to = conn.to_plugin,
+                                    "connection references a missing plugin"
+                                );
+                            }
                         }
+                        tracing::info!(
+                            plugins = new_workspace.plugins.len(),
+                            connections = new_workspace.connections.len(),
+                            "workspace updated"
+                        );
+                        sort_connections_for_evaluation(&mut new_workspace.connections);
+                        execution_order = order_plugins_for_execution(
+                            &new_workspace.plugins,
+                            &new_workspace.connections,
+                        );
+                        tracking.queues.retain(&new_workspace.connections);
                         workspace = Some(new_workspace);
                     }
+                    LogicMessage::AddPlugin(plugin) => {
+                        let Some(ws) = workspace.as_mut() else {
+                            continue;
+                        };
+                        tracing::debug!(plugin = plugin.id, kind = %plugin.kind, "plugin added");
+                        ws.plugins.retain(|p| p.id != plugin.id);
+                        plugin_running.insert(plugin.id, plugin.running);
+                        if !plugin_instances.contains_key(&plugin.id) {
+                            load_plugin_instance(&mut plugin_instances, &mut load_errors, &plugin);
+                        }
+                        warn_malformed_config(plugin_instances.get(&plugin.id), &plugin);
+                        ws.plugins.push(plugin);
+                        execution_order = order_plugins_for_execution(&ws.plugins, &ws.connections);
+                    }
+                    LogicMessage::RemovePlugin(plugin_id) => {
+                        let Some(ws) = workspace.as_mut() else {
+                            continue;
+                        };
+                        tracing::debug!(plugin = plugin_id, "plugin removed");
+                        ws.plugins.retain(|p| p.id != plugin_id);
+                        ws.connections
+                            .retain(|c| c.from_plugin != plugin_id && c.to_plugin != plugin_id);
+                        if let Some(RuntimePlugin::Dynamic(dynamic)) = plugin_instances.remove(&plugin_id) {
+                            (unsafe { &*dynamic.api }.destroy)(dynamic.handle);
+                        }
+                        open_errors.remove(&plugin_id);
+                        load_errors.remove(&plugin_id);
+                        plugin_running.remove(&plugin_id);
+                        clear_plugin_state(
+                            plugin_id,
+                            &mut tracking,
+                            &mut outputs,
+                            &mut input_values,
+                            &mut internal_variable_values,
+                            &mut viewer_values,
+                            &mut plotter_samples,
+                        );
+                        execution_order = order_plugins_for_execution(&ws.plugins, &ws.connections);
+                        tracking.queues.retain(&ws.connections);
+                    }
+                    LogicMessage::UpdatePluginConfig(plugin_id, config) => {
+                        let Some(ws) = workspace.as_mut() else {
+                            continue;
+                        };
+                        let changed = ws.plugins.iter().any(|p| p.id == plugin_id && p.config != config);
+                        // Plugins read their config every tick, so updating
+                        // both copies is enough
+                        for plugin in ws
+                            .plugins
+                            .iter_mut()
+                            .chain(execution_order.iter_mut())
+                            .filter(|p| p.id == plugin_id)
+                        {
+                            plugin.config = config.clone();
+                        }
+                        // A plugin whose library failed to load gets another try,
+                        // e.g. after its library_path was pointed at a rebuilt one
+                        if load_errors.contains_key(&plugin_id) {
+                            if let Some(plugin) = ws.plugins.iter().find(|p| p.id == plugin_id) {
+                                load_plugin_instance(&mut plugin_instances, &mut load_errors, plugin);
+                            }
+                        }
+                        if changed {
+                            if let Some(plugin) = ws.plugins.iter().find(|p| p.id == plugin_id) {
+                                warn_malformed_config(plugin_instances.get(&plugin_id), plugin);
+                            }
+                        }
+                    }
+                    LogicMessage::AddConnection(connection) => {
+                        let Some(ws) = workspace.as_mut() else {
+                            continue;
+                        };
+                        tracing::debug!(
+                            "connection {}:{} -> {}:{} added",
+                            connection.from_plugin,
+                            connection.from_port,
+                            connection.to_plugin,
+                            connection.to_port
+                        );
+                        ws.connections.push(connection);
+                        sort_connections_for_evaluation(&mut ws.connections);
+                        execution_order = order_plugins_for_execution(&ws.plugins, &ws.connections);
+                    }
+                    LogicMessage::RemoveConnection(connection) => {
+                        let Some(ws) = workspace.as_mut() else {
+                            continue;
+                        };
+                        if workspace::remove_connection(&mut ws.connections, &connection) {
+                            execution_order =
+                                order_plugins_for_execution(&ws.plugins, &ws.connections);
+                            tracking.queues.retain(&ws.connections);
+                        }
+                    }
                     LogicMessage::SetPluginRunning(plugin_id, running) => {
+                        tracing::debug!(plugin = plugin_id, running, "plugin run state changed");
                         plugin_running.insert(plugin_id, running);
                     }
+                    LogicMessage::SetLogLevel(level) => {
+                        crate::logging::set_level(level);
+                        tracing::info!(%level, "log level changed");
+                    }
+                    LogicMessage::CreateGraph(name, state_tx) => {
+                        graphs.create(name, &settings, state_tx);
+                    }
+                    LogicMessage::RemoveGraph(name) => graphs.remove(&name),
+                    LogicMessage::ToGraph(name, message) => graphs.send(&name, *message),
+                    LogicMessage::SetStepMode(enabled) => {
+                        if step_mode.enabled() != enabled {
+                            tracing::info!(enabled, "step mode");
+                        }
+                        step_mode.set_enabled(enabled);
+                    }
+                    LogicMessage::Step(ticks) => step_mode.request(ticks),
+                    LogicMessage::RetryOpen(plugin_id) => match plugin_instances.get_mut(&plugin_id) {
+                        Some(RuntimePlugin::CanIo(p)) => p.retry_open(),
+                        Some(RuntimePlugin::ModbusIo(p)) => p.retry_open(),
+                        #[cfg(feature = "comedi")]
+                        Some(RuntimePlugin::ComediDaq(p)) => p.retry_open(),
+                        #[cfg(feature = "rpi")]
+                        Some(RuntimePlugin::Gpio(p)) => p.retry_open(),
+                        _ => {}
+                    },
+                    LogicMessage::RunSelfTest(plugin_id, response_tx) => {
+                        let result = match plugin_instances.get_mut(&plugin_id) {
+                            Some(instance) => run_self_test(instance),
+                            None => Err(format!("plugin {plugin_id} is not loaded")),
+                        };
+                        let _ = response_tx.send(result);
+                    }
                     LogicMessage::QueryPluginBehavior(kind, library_path, response_tx) => {
                         let behavior = match kind.as_str() {
                             "csv_recorder" => Some(CsvRecorderedPlugin::new(0).behavior()),
                             "live_plotter" => Some(LivePlotterPlugin::new(0).behavior()),
                             "performance_monitor" => Some(PerformanceMonitorPlugin::new(0).behavior()),
+                            "bang_bang" => Some(BangBangPlugin::new(0).behavior()),
+                            "noise_generator" => Some(NoiseGeneratorPlugin::new(0).behavior()),
+                            "integrator" => Some(IntegratorPlugin::new(0).behavior()),
+                            "differentiator" => Some(DifferentiatorPlugin::new(0).behavior()),
+                            "delay" => Some(DelayPlugin::new(0).behavior()),
+                            "alarm" => Some(AlarmPlugin::new(0).behavior()),
+                            "sample_hold" => Some(SampleHoldPlugin::new(0).behavior()),
+                            "scale" => Some(ScalePlugin::new(0).behavior()),
+                            "can_io" => Some(CanIoPlugin::new(0).behavior()),
+                            "stimulus" => Some(StimulusPlugin::new(0).behavior()),
+                            "saturation" => Some(SaturationPlugin::new(0).behavior()),
+                            "histogram" => Some(HistogramPlugin::new(0).behavior()),
+                            "tcp_stream" => Some(TcpStreamPlugin::new(0).behavior()),
+                            "shm_publisher" => Some(ShmPublisherPlugin::new(0).behavior()),
+                            "rms" => Some(RmsPlugin::new(0).behavior()),
+                            "peak_hold" => Some(PeakHoldPlugin::new(0).behavior()),
+                            "timer" => Some(TimerPlugin::new(0).behavior()),
+                            "setpoint_ramp" => Some(SetpointRampPlugin::new(0).behavior()),
+                            "lookup_table" => Some(LookupTablePlugin::new(0).behavior()),
+                            "stats" => Some(StatsPlugin::new(0).behavior()),
+                            "resampler" => Some(ResamplerPlugin::new(0).behavior()),
+                            "pwm" => Some(PwmPlugin::new(0).behavior()),
+                            "estop" => Some(EstopPlugin::new(0).behavior()),
+                            "audio_out" => Some(AudioOutPlugin::new(0).behavior()),
+                            "frequency_counter" => Some(FrequencyCounterPlugin::new(0).behavior()),
+                            "modbus_io" => Some(ModbusIoPlugin::new(0).behavior()),
+                            "state_machine" => Some(StateMachinePlugin::new(0).behavior()),
+                            "fault_recorder" => Some(FaultRecorderPlugin::new(0).behavior()),
+                            "filtered_derivative" => Some(FilteredDerivativePlugin::new(0).behavior()),
+                            "bit_pack" => Some(BitPackPlugin::new(0).behavior()),
+                            "bit_unpack" => Some(BitUnpackPlugin::new(0).behavior()),
+                            "transfer_function" => Some(TransferFunctionPlugin::new(0).behavior()),
+                            "error_metrics" => Some(ErrorMetricsPlugin::new(0).behavior()),
                             #[cfg(feature = "comedi")]
                             "comedi_daq" => Some(comedi_daq_plugin::ComediDaqPlugin::new(0).behavior()),
+                            #[cfg(feature = "rpi")]
+                            "gpio" => Some(gpio_plugin::GpioPlugin::new(0).behavior()),
 
                             _ => {
                                 // Try to load behavior from dynamic plugin
@@ -350,43 +1143,34 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
                         let _ = response_tx.send(metadata);
                     }
                     LogicMessage::RestartPlugin(plugin_id) => {
+                        tracing::debug!(plugin = plugin_id, "restarting plugin");
                         let Some(ws) = workspace.as_ref() else {
                             continue;
                         };
                         let Some(plugin) = ws.plugins.iter().find(|p| p.id == plugin_id) else {
                             continue;
                         };
-                        let instance = match plugin.kind.as_str() {
-                            "csv_recorder" => {
-                                RuntimePlugin::CsvRecorder(CsvRecorderedPlugin::new(plugin.id))
-                            }
-                            #[cfg(feature = "comedi")]
-                            "comedi_daq" => RuntimePlugin::ComediDaq(
-                                comedi_daq_plugin::ComediDaqPlugin::new(plugin.id),
-                            ),
-                            _ => {
-                                let library_path =
-                                    plugin.config.get("library_path").and_then(|v| v.as_str());
-                                if let Some(path) = library_path {
-                                    unsafe {
-                                        if let Some(dynamic) =
-                                            DynamicPluginInstance::load(path, plugin.id)
-                                        {
-                                            RuntimePlugin::Dynamic(dynamic)
-                                        } else {
-                                            continue;
-                                        }
-                                    }
-                                } else {
-                                    continue;
-                                }
+                        let instance = match instantiate_plugin(plugin) {
+                            Ok(instance) => instance,
+                            Err(err) => {
+                                tracing::warn!(plugin = plugin_id, %err, "plugin restart failed");
+                                load_errors.insert(plugin_id, err);
+                                continue;
                             }
                         };
-                        plugin_instances.insert(plugin.id, instance);
-                        viewer_values.remove(&plugin.id);
-                        outputs.retain(|(pid, _), _| *pid != plugin.id);
-                        input_values.retain(|(pid, _), _| *pid != plugin.id);
-                        internal_variable_values.retain(|(pid, _), _| *pid != plugin.id);
+                        load_errors.remove(&plugin_id);
+                        if let Some(RuntimePlugin::Dynamic(dynamic)) = plugin_instances.insert(plugin_id, instance) {
+                            (unsafe { &*dynamic.api }.destroy)(dynamic.handle);
+                        }
+                        clear_plugin_state(
+                            plugin_id,
+                            &mut tracking,
+                            &mut outputs,
+                            &mut input_values,
+                            &mut internal_variable_values,
+                            &mut viewer_values,
+                            &mut plotter_samples,
+                        );
                     }
                     LogicMessage::GetPluginVariable(plugin_id, var_name, response_tx) => {
                         let value = plugin_instances.get(&plugin_id).and_then(|instance| {
@@ -394,23 +1178,73 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
                                 RuntimePlugin::CsvRecorder(p) => p.get_variable(&var_name),
                                 RuntimePlugin::LivePlotter(p) => p.get_variable(&var_name),
                                 RuntimePlugin::PerformanceMonitor(p) => p.get_variable(&var_name),
+                                RuntimePlugin::BangBang(p) => p.get_variable(&var_name),
+                                RuntimePlugin::NoiseGenerator(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Integrator(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Differentiator(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Delay(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Alarm(p) => p.get_variable(&var_name),
+                                RuntimePlugin::SampleHold(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Scale(p) => p.get_variable(&var_name),
+                                RuntimePlugin::CanIo(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Stimulus(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Saturation(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Histogram(p) => p.get_variable(&var_name),
+                                RuntimePlugin::TcpStream(p) => p.get_variable(&var_name),
+                                RuntimePlugin::ShmPublisher(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Rms(p) => p.get_variable(&var_name),
+                                RuntimePlugin::PeakHold(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Timer(p) => p.get_variable(&var_name),
+                                RuntimePlugin::SetpointRamp(p) => p.get_variable(&var_name),
+                                RuntimePlugin::LookupTable(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Stats(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Resampler(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Pwm(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Estop(p) => p.get_variable(&var_name),
+                                RuntimePlugin::AudioOut(p) => p.get_variable(&var_name),
+                                RuntimePlugin::FrequencyCounter(p) => p.get_variable(&var_name),
+                                RuntimePlugin::ModbusIo(p) => p.get_variable(&var_name),
+                                RuntimePlugin::StateMachine(p) => p.get_variable(&var_name),
+                                RuntimePlugin::FaultRecorder(p) => p.get_variable(&var_name),
+                                RuntimePlugin::FilteredDerivative(p) => p.get_variable(&var_name),
+                                RuntimePlugin::BitPack(p) => p.get_variable(&var_name),
+                                RuntimePlugin::BitUnpack(p) => p.get_variable(&var_name),
+                                RuntimePlugin::TransferFunction(p) => p.get_variable(&var_name),
+                                RuntimePlugin::ErrorMetrics(p) => p.get_variable(&var_name),
                                 #[cfg(feature = "comedi")]
                                 RuntimePlugin::ComediDaq(p) => p.get_variable(&var_name),
+                                #[cfg(feature = "rpi")]
+                                RuntimePlugin::Gpio(p) => p.get_variable(&var_name),
                                 RuntimePlugin::Dynamic(_) => None,
                             }
                         });
                         let _ = response_tx.send(value);
                     }
+                    LogicMessage::QueryPluginStates(response_tx) => {
+                        let states = plugin_run_states(
+                            workspace.as_ref(),
+                            &plugin_instances,
+                            &plugin_running,
+                            step_mode.enabled(),
+                        );
+                        let _ = response_tx.send(states);
+                    }
+                    LogicMessage::TrackConnectionLatency(enabled) => {
+                        tracking.latency.set_enabled(enabled);
+                    }
+                    LogicMessage::QueryConnectionLatency(response_tx) => {
+                        let _ = response_tx.send(tracking.latency.stats());
+                    }
                     LogicMessage::SetPluginVariable(plugin_id, var_name, value) => {
                         if let Some(instance) = plugin_instances.get_mut(&plugin_id) {
-                            let _ = match instance {
-                                RuntimePlugin::CsvRecorder(p) => p.set_variable(&var_name, value),
-                                RuntimePlugin::LivePlotter(p) => p.set_variable(&var_name, value),
-                                RuntimePlugin::PerformanceMonitor(p) => p.set_variable(&var_name, value),
-                                #[cfg(feature = "comedi")]
-                                RuntimePlugin::ComediDaq(p) => p.set_variable(&var_name, value),
-                                RuntimePlugin::Dynamic(_) => Ok(()),
-                            };
+                            set_runtime_variable(instance, &var_name, value);
+                        }
+                    }
+                    LogicMessage::SetPluginVariables(plugin_id, changes) => {
+                        if let Some(instance) = plugin_instances.get_mut(&plugin_id) {
+                            for (var_name, value) in changes {
+                                set_runtime_variable(instance, &var_name, value);
+                            }
                         }
                     }
                     },
@@ -424,19 +1258,33 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
             if disconnected {
                 return;
             }
+            let run_tick = step_mode.take_tick();
 
             if let Some(ws) = workspace.as_ref() {
-                let plugins = order_plugins_for_execution(&ws.plugins, &ws.connections);
-
-                for plugin in plugins {
+                let _tick_span = tracing::trace_span!("tick", tick = plugin_ctx.tick).entered();
+                let mut estop_seen = false;
+                // Paused in step mode: no plugin runs, but the state is still sent
+                let due: &[PluginDefinition] = if run_tick { &execution_order } else { &[] };
+                for plugin in due {
                     let is_running = plugin_running
                         .get(&plugin.id)
                         .copied()
-                        .unwrap_or(plugin.running);
+                        .unwrap_or(plugin.running)
+                        && (!estop_active || is_estop_essential(&plugin.kind));
                     let instance = match plugin_instances.get_mut(&plugin.id) {
                         Some(instance) => instance,
-                        None => continue,
+                        None => {
+                            tracing::trace!(plugin = plugin.id, "no runtime instance, skipping");
+                            continue;
+                        }
                     };
+                    let mut queued_inputs = Vec::new();
+                    if is_running {
+                        tracking.latency.inputs_read(&ws.connections, plugin.id);
+                        queued_inputs = tracking.queues.inputs_read(&ws.connections, &mut outputs, plugin.id);
+                    }
+                    let _process_span =
+                        tracing::trace_span!("process", plugin = plugin.id, kind = %plugin.kind).entered();
                     match instance {
                         RuntimePlugin::Dynamic(plugin_instance) => {
                             let api = unsafe { &*plugin_instance.api };
@@ -467,7 +1315,7 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
                             }
                             for (idx, input_name) in plugin_instance.inputs.iter().enumerate() {
                                 let value =
-                                    input_sum(&ws.connections, &outputs, plugin.id, input_name);
+                                    input_value(&ws.connections, &outputs, plugin, input_name);
                                 input_values.insert((plugin.id, input_name.clone()), value);
                                 let bits = value.to_bits();
                                 if plugin_instance.last_inputs[idx] != bits {
@@ -512,12 +1360,7 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
                             }
                         }
                         RuntimePlugin::CsvRecorder(plugin_instance) => {
-                            let config_input_count = plugin
-                                .config
-                                .get("input_count")
-                                .and_then(|v| v.as_u64())
-                                .unwrap_or(0)
-                                as usize;
+                            let config_input_count = config::u64_or(&plugin.config, "input_count", 0) as usize;
                             let separator = plugin
                                 .config
                                 .get("separator")
@@ -533,6 +1376,14 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
                                 .get("include_time")
                                 .and_then(|v| v.as_bool())
                                 .unwrap_or(true);
+                            let write_mode = plugin
+                                .config
+                                .get("write_mode")
+                                .and_then(|v| v.as_str())
+                                .map(CsvWriteMode::from_name)
+                                .unwrap_or_default();
+                            let rotate_mb = config::f64_or(&plugin.config, "rotate_mb", 0.0);
+                            let rotate_minutes = config::f64_or(&plugin.config, "rotate_minutes", 0.0);
                             let mut columns: Vec<String> = plugin
                                 .config
                                 .get("columns")
@@ -559,13 +1410,29 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
                                 let value = if idx == 0 {
                                     let mut ports = vec![port.clone()];
                                     ports.push("in".to_string());
-                                    input_sum_any(&ws.connections, &outputs, plugin.id, &ports)
+                                    input_value_any(&ws.connections, &outputs, plugin, &ports)
                                 } else {
-                                    input_sum(&ws.connections, &outputs, plugin.id, &port)
+                                    input_value(&ws.connections, &outputs, plugin, &port)
                                 };
                                 input_values.insert((plugin.id, port), value);
                                 inputs.push(value);
                             }
+                            plugin_instance.set_write_mode(write_mode);
+                            let wall_time = plugin
+                                .config
+                                .get("include_wall_time")
+                                .and_then(|v| v.as_bool())
+                                .unwrap_or(false)
+                                .then(|| {
+                                    plugin
+                                        .config
+                                        .get("wall_time_format")
+                                        .and_then(|v| v.as_str())
+                                        .map(CsvWallTimeFormat::from_name)
+                                        .unwrap_or_default()
+                                });
+                            plugin_instance.set_wall_time(wall_time);
+                            plugin_instance.set_rotation(rotate_mb, rotate_minutes);
                             plugin_instance.set_config(
                                 input_count,
                                 separator.to_string(),
@@ -586,7 +1453,24 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
                                 serde_json::Value::from(is_running),
                             );
                             plugin_instance.set_inputs(inputs);
-                            let _ = plugin_instance.process(&mut plugin_ctx);
+                            let skip_warmup = plugin
+                                .config
+                                .get("skip_warmup")
+                                .and_then(|v| v.as_bool())
+                                .unwrap_or(false);
+                            // Rows start once every connected source has warmed up
+                            let sources_warming_up = skip_warmup
+                                && ws
+                                    .connections
+                                    .iter()
+                                    .any(|conn| conn.to_plugin == plugin.id && tracking.warmup.is_warming_up(conn.from_plugin));
+                            if !sources_warming_up {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            internal_variable_values.insert(
+                                (plugin.id, "error".to_string()),
+                                serde_json::Value::from(plugin_instance.last_error().unwrap_or_default()),
+                            );
                         }
                         #[cfg(feature = "comedi")]
                         RuntimePlugin::ComediDaq(plugin_instance) => {
@@ -600,11 +1484,7 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
                                 .get("scan_devices")
                                 .and_then(|v| v.as_bool())
                                 .unwrap_or(false);
-                            let scan_nonce = plugin
-                                .config
-                                .get("scan_nonce")
-                                .and_then(|v| v.as_u64())
-                                .unwrap_or(0);
+                            let scan_nonce = config::u64_or(&plugin.config, "scan_nonce", 0);
 
                             let mut active_inputs: HashSet<String> = HashSet::new();
                             let mut active_outputs: HashSet<String> = HashSet::new();
@@ -626,19 +1506,26 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
 
                             let has_active =
                                 !active_inputs.is_empty() || !active_outputs.is_empty();
-                            if has_active && !plugin_instance.is_open() {
+                            if has_active && !plugin_instance.is_open() && plugin_instance.can_retry_open() {
                                 let _ = plugin_instance.open();
                             } else if !has_active && plugin_instance.is_open() {
                                 let _ = plugin_instance.close();
                             }
+                            track_open_error(&mut open_errors, plugin.id, plugin_instance.open_error());
 
                             let input_ports: Vec<String> =
                                 plugin_instance.input_port_names().iter().cloned().collect();
                             for port in input_ports {
-                                let value = input_sum(&ws.connections, &outputs, plugin.id, &port);
+                                let value = input_value(&ws.connections, &outputs, plugin, &port);
                                 input_values.insert((plugin.id, port.clone()), value);
                                 plugin_instance.set_input(&port, value);
                             }
+                            plugin_instance.set_safe_value(
+                                config::f64_or(&plugin.config, "safe_value", 0.0),
+                            );
+                            if estop_active {
+                                plugin_instance.safe_state();
+                            }
 
                             let _ = plugin_instance.process(&mut plugin_ctx);
 
@@ -653,12 +1540,7 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
                             }
                         }
                         RuntimePlugin::LivePlotter(plugin_instance) => {
-                            let config_input_count = plugin
-                                .config
-                                .get("input_count")
-                                .and_then(|v| v.as_u64())
-                                .unwrap_or(0)
-                                as usize;
+                            let config_input_count = config::u64_or(&plugin.config, "input_count", 0) as usize;
                             let input_count = config_input_count;
                             plugin_instance.set_config(input_count, is_running);
                             internal_variable_values.insert(
@@ -675,9 +1557,9 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
                                 let value = if idx == 0 {
                                     let mut ports = vec![port.clone()];
                                     ports.push("in".to_string());
-                                    input_sum_any(&ws.connections, &outputs, plugin.id, &ports)
+                                    input_value_any(&ws.connections, &outputs, plugin, &ports)
                                 } else {
-                                    input_sum(&ws.connections, &outputs, plugin.id, &port)
+                                    input_value(&ws.connections, &outputs, plugin, &port)
                                 };
                                 input_values.insert((plugin.id, port), value);
                                 inputs.push(value);
@@ -691,645 +1573,2727 @@ pub fn spawn_runtime() -> Result<(Sender<LogicMessage>, Receiver<LogicState>), S
                             }
                         }
                         RuntimePlugin::PerformanceMonitor(plugin_instance) => {
-                            let max_latency_us = plugin
-                                .config
-                                .get("max_latency_us")
-                                .and_then(|v| v.as_f64())
-                                .unwrap_or(1000.0);
+                            let max_latency_us = config::f64_or(&plugin.config, "max_latency_us", 1000.0);
 
                             let workspace_period_us = settings.period_seconds * 1_000_000.0;
                             plugin_instance.set_config(max_latency_us, workspace_period_us);
                             let _ = plugin_instance.process(&mut plugin_ctx);
 
-                            // Output the performance values
-                            for (idx, output_name) in
-                                ["period_us", "latency_us", "jitter_us", "realtime_violation"]
-                                    .iter()
-                                    .enumerate()
+                            // Output the performance values, including the tick they were taken on
+                            for (port, value) in plugin_instance
+                                .outputs()
+                                .iter()
+                                .zip(plugin_instance.get_output_values())
                             {
-                                let value = plugin_instance.get_output_values()[idx];
-                                outputs.insert((plugin.id, output_name.to_string()), value);
+                                outputs.insert((plugin.id, port.id.0.clone()), *value);
                             }
                         }
-                    }
-                }
-                plugin_ctx.tick = plugin_ctx.tick.wrapping_add(1);
-                let ui_interval = if settings.ui_hz > 0.0 {
-                    Duration::from_secs_f64(1.0 / settings.ui_hz)
-                } else {
-                    Duration::from_secs(1)
-                };
-                if last_state.elapsed() >= ui_interval {
-                    // Limit plotter samples to prevent memory issues
-                    let mut limited_plotter_samples = HashMap::new();
-                    for (plugin_id, samples) in &plotter_samples {
-                        let mut limited_samples = samples.clone();
-                        // Keep only the last 1000 samples per plugin to prevent memory overflow
-                        if limited_samples.len() > 1000 {
-                            limited_samples.drain(0..limited_samples.len() - 1000);
+                        RuntimePlugin::BangBang(plugin_instance) => {
+                            let hysteresis = config::f64_or(&plugin.config, "hysteresis", 0.5);
+                            let output_high = config::f64_or(&plugin.config, "output_high", 1.0);
+                            let output_low = config::f64_or(&plugin.config, "output_low", 0.0);
+                            plugin_instance.set_config(hysteresis, output_high, output_low);
+
+                            let setpoint = input_value(&ws.connections, &outputs, plugin, "setpoint");
+                            let process_variable =
+                                input_value(&ws.connections, &outputs, plugin, "process_variable");
+                            input_values.insert((plugin.id, "setpoint".to_string()), setpoint);
+                            input_values.insert((plugin.id, "process_variable".to_string()), process_variable);
+                            plugin_instance.set_inputs(setpoint, process_variable);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "control".to_string()), plugin_instance.control());
                         }
-                        limited_plotter_samples.insert(*plugin_id, limited_samples);
-                    }
+                        RuntimePlugin::NoiseGenerator(plugin_instance) => {
+                            let distribution = plugin
+                                .config
+                                .get("distribution")
+                                .and_then(|v| v.as_str())
+                                .map(Distribution::from_name)
+                                .unwrap_or(Distribution::Uniform);
+                            let amplitude = config::f64_or(&plugin.config, "amplitude", 1.0);
+                            let mean = config::f64_or(&plugin.config, "mean", 0.0);
+                            let seed = config::u64_or(&plugin.config, "seed", 0);
+                            plugin_instance.set_config(distribution, amplitude, mean, seed);
 
-                    let _ = logic_state_tx.send(LogicState {
-                        outputs: outputs.clone(),
-                        input_values: input_values.clone(),
-                        internal_variable_values: internal_variable_values.clone(),
-                        viewer_values: viewer_values.clone(),
-                        tick: plugin_ctx.tick,
-                        plotter_samples: limited_plotter_samples,
-                    });
-                    plotter_samples.clear();
-                    last_state = Instant::now();
-                }
-            }
-            let _ = runtime.tick();
-            let _ = settings.cores.len();
-            ActiveRtBackend::sleep(period_duration, &mut sleep_deadline);
-        }
-    })?;
+                            let input = input_value(&ws.connections, &outputs, plugin, "in");
+                            input_values.insert((plugin.id, "in".to_string()), input);
+                            plugin_instance.set_input(input);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                        }
+                        RuntimePlugin::Integrator(plugin_instance) => {
+                            let gain = config::f64_or(&plugin.config, "gain", 1.0);
+                            let initial_value = config::f64_or(&plugin.config, "initial_value", 0.0);
+                            let clamp = plugin
+                                .config
+                                .get("clamp")
+                                .and_then(|v| v.as_bool())
+                                .unwrap_or(false);
+                            let min = config::f64_or(&plugin.config, "min", -1.0);
+                            let max = config::f64_or(&plugin.config, "max", 1.0);
+                            plugin_instance.set_config(gain, initial_value, clamp, min, max);
 
-    Ok((logic_tx, logic_state_rx))
-}
+                            let input = input_value(&ws.connections, &outputs, plugin, "in");
+                            input_values.insert((plugin.id, "in".to_string()), input);
+                            plugin_instance.set_input(input);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                        }
+                        RuntimePlugin::Differentiator(plugin_instance) => {
+                            let gain = config::f64_or(&plugin.config, "gain", 1.0);
+                            plugin_instance.set_config(gain);
 
-pub fn run_runtime_current(
-    logic_rx: Receiver<LogicMessage>,
-    logic_state_tx: Sender<LogicState>,
-) -> Result<(), String> {
-    ActiveRtBackend::prepare()?;
-    let mut settings = LogicSettings {
-        cores: vec![0],
-        period_seconds: 0.001,
-        time_scale: 1000.0,
-        time_label: "time_ms".to_string(),
-        ui_hz: 60.0,
-        max_integration_steps: 10, // Reasonable default for real-time performance
-    };
-    let mut period_duration = Duration::from_secs_f64(settings.period_seconds.max(0.0));
-    let mut sleep_deadline = ActiveRtBackend::init_sleep(period_duration);
-    let mut workspace: Option<WorkspaceDefinition> = None;
-    let mut plugin_instances: HashMap<u64, RuntimePlugin> = HashMap::new();
-    let mut plugin_running: HashMap<u64, bool> = HashMap::new();
-    let mut plugin_ctx = PluginContext {
-        period_seconds: settings.period_seconds,
-        ..Default::default()
-    };
-    let mut outputs: HashMap<(u64, String), f64> = HashMap::new();
-    let mut input_values: HashMap<(u64, String), f64> = HashMap::new();
-    let mut internal_variable_values: HashMap<(u64, String), serde_json::Value> = HashMap::new();
-    let mut viewer_values: HashMap<u64, f64> = HashMap::new();
-    let mut plotter_samples: HashMap<u64, Vec<(u64, Vec<f64>)>> = HashMap::new();
-    let mut runtime = crate::Runtime::new(workspace::WorkspaceDefinition {
-        name: "test".to_string(),
-        description: String::new(),
-        target_hz: 1000,
-        plugins: Vec::new(),
-        connections: Vec::new(),
-        settings: workspace::WorkspaceSettings::default(),
-    });
-    let mut last_state = Instant::now();
+                            let input = input_value(&ws.connections, &outputs, plugin, "in");
+                            input_values.insert((plugin.id, "in".to_string()), input);
+                            plugin_instance.set_input(input);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                        }
+                        RuntimePlugin::Delay(plugin_instance) => {
+                            let delay_ms = config::f64_or(&plugin.config, "delay_ms", 100.0);
+                            let initial_value = config::f64_or(&plugin.config, "initial_value", 0.0);
+                            plugin_instance.set_config(delay_ms, initial_value, plugin_ctx.period_seconds);
 
-    loop {
-        let mut disconnected = false;
-        loop {
-            match logic_rx.try_recv() {
-                Ok(message) => match message {
-                    LogicMessage::UpdateSettings(new_settings) => {
-                        settings = new_settings;
-                        period_duration = Duration::from_secs_f64(settings.period_seconds.max(0.0));
-                        sleep_deadline = ActiveRtBackend::init_sleep(period_duration);
-                        plugin_ctx.period_seconds = settings.period_seconds;
-                    }
-                    LogicMessage::UpdateWorkspace(new_workspace) => {
-                        let mut new_ids: HashSet<u64> = HashSet::new();
-                        for plugin in &new_workspace.plugins {
-                            new_ids.insert(plugin.id);
-                            plugin_running.insert(plugin.id, plugin.running);
-                            if !plugin_instances.contains_key(&plugin.id) {
-                                let instance = match plugin.kind.as_str() {
-                                    "csv_recorder" => RuntimePlugin::CsvRecorder(
-                                        CsvRecorderedPlugin::new(plugin.id),
-                                    ),
-                                    "live_plotter" => RuntimePlugin::LivePlotter(
-                                        LivePlotterPlugin::new(plugin.id),
-                                    ),
-                                    "performance_monitor" => RuntimePlugin::PerformanceMonitor(
-                                        PerformanceMonitorPlugin::new(plugin.id),
-                                    ),
-                                    #[cfg(feature = "comedi")]
-                                    "comedi_daq" => RuntimePlugin::ComediDaq(
-                                        comedi_daq_plugin::ComediDaqPlugin::new(plugin.id),
-                                    ),
-                                    _ => {
-                                        let library_path = plugin
-                                            .config
-                                            .get("library_path")
-                                            .and_then(|v| v.as_str());
-                                        if let Some(path) = library_path {
-                                            unsafe {
-                                                if let Some(dynamic) =
-                                                    DynamicPluginInstance::load(path, plugin.id)
-                                                {
-                                                    RuntimePlugin::Dynamic(dynamic)
-                                                } else {
-                                                    continue;
-                                                }
-                                            }
-                                        } else {
-                                            continue;
-                                        }
-                                    }
-                                };
-                                plugin_instances.insert(plugin.id, instance);
+                            let input = input_value(&ws.connections, &outputs, plugin, "in");
+                            input_values.insert((plugin.id, "in".to_string()), input);
+                            plugin_instance.set_input(input);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
                             }
+                            outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
                         }
+                        RuntimePlugin::Alarm(plugin_instance) => {
+                            let high_limit = config::f64_or(&plugin.config, "high_limit", 1.0);
+                            let low_limit = config::f64_or(&plugin.config, "low_limit", -1.0);
+                            let latching = plugin
+                                .config
+                                .get("latching")
+                                .and_then(|v| v.as_bool())
+                                .unwrap_or(false);
+                            plugin_instance.set_config(high_limit, low_limit, latching);
 
-                        let removed_ids: Vec<u64> = plugin_instances
-                            .keys()
-                            .filter(|id| !new_ids.contains(id))
-                            .copied()
-                            .collect();
-                        for id in removed_ids {
-                            if let Some(instance) = plugin_instances.remove(&id) {
-                                if let RuntimePlugin::Dynamic(dynamic) = instance {
-                                    (unsafe { &*dynamic.api }.destroy)(dynamic.handle);
-                                }
+                            let signal = input_value(&ws.connections, &outputs, plugin, "signal");
+                            let reset = input_value(&ws.connections, &outputs, plugin, "reset");
+                            input_values.insert((plugin.id, "signal".to_string()), signal);
+                            input_values.insert((plugin.id, "reset".to_string()), reset);
+                            plugin_instance.set_inputs(signal, reset);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                                queue_plugin_events(
+                                    &mut plugin_events,
+                                    plugin.id,
+                                    plugin_ctx.tick,
+                                    settings.period_seconds,
+                                    plugin_instance.drain_events(),
+                                );
                             }
-                            plugin_running.remove(&id);
-                            viewer_values.remove(&id);
-                            outputs.retain(|(pid, _), _| *pid != id);
-                            input_values.retain(|(pid, _), _| *pid != id);
-                            internal_variable_values.retain(|(pid, _), _| *pid != id);
-                            plotter_samples.remove(&id);
+                            outputs.insert((plugin.id, "alarm".to_string()), plugin_instance.alarm());
                         }
-                        workspace = Some(new_workspace);
-                    }
-                    LogicMessage::SetPluginRunning(plugin_id, running) => {
-                        plugin_running.insert(plugin_id, running);
-                    }
-                    LogicMessage::QueryPluginBehavior(kind, library_path, response_tx) => {
-                        let behavior = match kind.as_str() {
-                            "csv_recorder" => Some(CsvRecorderedPlugin::new(0).behavior()),
-                            "live_plotter" => Some(LivePlotterPlugin::new(0).behavior()),
-                            "performance_monitor" => Some(PerformanceMonitorPlugin::new(0).behavior()),
-                            #[cfg(feature = "comedi")]
-                            "comedi_daq" => Some(comedi_daq_plugin::ComediDaqPlugin::new(0).behavior()),
+                        RuntimePlugin::SampleHold(plugin_instance) => {
+                            let initial_value = config::f64_or(&plugin.config, "initial_value", 0.0);
+                            plugin_instance.set_config(initial_value);
 
-                            _ => {
-                                // Try to load behavior from dynamic plugin
-                                if let Some(path) = library_path.as_ref() {
-                                    if let Some(dynamic) = unsafe { DynamicPluginInstance::load(path, 0) } {
-                                        if let Some(behavior_json_fn) = unsafe { (*dynamic.api).behavior_json } {
-                                            let json_str = behavior_json_fn(dynamic.handle);
-                                            if !json_str.ptr.is_null() && json_str.len > 0 {
-                                                let json = unsafe { json_str.into_string() };
-                                                if let Ok(behavior) = serde_json::from_str(&json) {
-                                                    unsafe { ((*dynamic.api).destroy)(dynamic.handle); }
-                                                    let _ = response_tx.send(Some(behavior));
-                                                    continue;
-                                                }
-                                            }
-                                            unsafe { ((*dynamic.api).destroy)(dynamic.handle); }
-                                        } else {
-                                            unsafe { ((*dynamic.api).destroy)(dynamic.handle); }
-                                        }
-                                    }
-                                }
-                                None
+                            let input = input_value(&ws.connections, &outputs, plugin, "in");
+                            let trigger = input_value(&ws.connections, &outputs, plugin, "trigger");
+                            input_values.insert((plugin.id, "in".to_string()), input);
+                            input_values.insert((plugin.id, "trigger".to_string()), trigger);
+                            plugin_instance.set_inputs(input, trigger);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
                             }
-                        };
-                        let _ = response_tx.send(behavior);
-                    }
-                    LogicMessage::QueryPluginMetadata(library_path, response_tx) => {
-                        let metadata = if let Some(dynamic) = unsafe { DynamicPluginInstance::load(&library_path, 0) } {
-                            let inputs_str = unsafe { ((*dynamic.api).inputs_json)(dynamic.handle) };
-                            let outputs_str = unsafe { ((*dynamic.api).outputs_json)(dynamic.handle) };
-                            let meta_str = unsafe { ((*dynamic.api).meta_json)(dynamic.handle) };
-                            let inputs: Vec<String> = if inputs_str.ptr.is_null() || inputs_str.len == 0 {
-                                Vec::new()
-                            } else {
-                                let json = unsafe { inputs_str.into_string() };
-                                serde_json::from_str(&json).unwrap_or_default()
-                            };
-                            let outputs: Vec<String> = if outputs_str.ptr.is_null() || outputs_str.len == 0 {
-                                Vec::new()
-                            } else {
-                                let json = unsafe { outputs_str.into_string() };
-                                serde_json::from_str(&json).unwrap_or_default()
-                            };
-                            let meta: serde_json::Value = if meta_str.ptr.is_null() || meta_str.len == 0 {
-                                serde_json::json!({})
-                            } else {
-                                let json = unsafe { meta_str.into_string() };
-                                serde_json::from_str(&json).unwrap_or(serde_json::json!({}))
+                            outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                        }
+                        RuntimePlugin::Scale(plugin_instance) => {
+                            let config_f64 = |key: &str, default: f64| {
+                                config::f64_or(&plugin.config, key, default)
                             };
-                            let variables: Vec<(String, f64)> = meta.get("default_vars")
-                                .and_then(|v| v.as_array())
-                                .map(|arr| {
-                                    arr.iter().filter_map(|item| {
-                                        if let Some(arr) = item.as_array() {
-                                            if arr.len() == 2 {
-                                                let name = arr[0].as_str()?.to_string();
-                                                let value = arr[1].as_f64()?;
-                                                return Some((name, value));
-                                            }
-                                        }
-                                        None
-                                    }).collect()
-                                })
-                                .unwrap_or_default();
-                            let display_schema = if let Some(schema_fn) = unsafe { (*dynamic.api).display_schema_json } {
-                                let schema_str = schema_fn(dynamic.handle);
-                                if schema_str.ptr.is_null() || schema_str.len == 0 {
-                                    None
-                                } else {
-                                    let json = unsafe { schema_str.into_string() };
-                                    serde_json::from_str(&json).ok()
-                                }
-                            } else {
-                                None
+                            let mode = plugin
+                                .config
+                                .get("mode")
+                                .and_then(|v| v.as_str())
+                                .map(ScaleMode::from_name)
+                                .unwrap_or(ScaleMode::GainOffset);
+                            plugin_instance.set_config(mode, config_f64("gain", 1.0), config_f64("offset", 0.0));
+                            plugin_instance.set_calibration(Calibration {
+                                in_low: config_f64("in_low", 0.0),
+                                out_low: config_f64("out_low", 0.0),
+                                in_high: config_f64("in_high", 1.0),
+                                out_high: config_f64("out_high", 1.0),
+                            });
+
+                            let input = input_value(&ws.connections, &outputs, plugin, "in");
+                            input_values.insert((plugin.id, "in".to_string()), input);
+                            plugin_instance.set_input(input);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                        }
+                        RuntimePlugin::CanIo(plugin_instance) => {
+                            let interface = plugin
+                                .config
+                                .get("interface")
+                                .and_then(|v| v.as_str())
+                                .unwrap_or("can0");
+                            let specs = |key: &str| -> Vec<String> {
+                                plugin
+                                    .config
+                                    .get(key)
+                                    .and_then(|v| v.as_array())
+                                    .map(|items| {
+                                        items
+                                            .iter()
+                                            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                            .collect()
+                                    })
+                                    .unwrap_or_default()
                             };
-                            let ui_schema: Option<rtsyn_plugin::ui::UISchema> = if let Some(ui_schema_fn) = unsafe { (*dynamic.api).ui_schema_json } {
-                                let schema_str = ui_schema_fn(dynamic.handle);
-                                if schema_str.ptr.is_null() || schema_str.len == 0 {
-                                    None
-                                } else {
-                                    let json = unsafe { schema_str.into_string() };
-                                    serde_json::from_str(&json).ok()
+                            plugin_instance.set_config(interface, &specs("tx_signals"), &specs("rx_signals"));
+
+                            if !is_running {
+                                if plugin_instance.is_open() || !plugin_instance.can_retry_open() {
+                                    let _ = plugin_instance.close();
                                 }
-                            } else {
-                                None
-                            };
-                            unsafe { ((*dynamic.api).destroy)(dynamic.handle); }
-                            Some((inputs, outputs, variables, display_schema, ui_schema))
-                        } else {
-                            None
-                        };
-                        let _ = response_tx.send(metadata);
-                    }
-                    LogicMessage::RestartPlugin(plugin_id) => {
-                        let Some(ws) = workspace.as_ref() else {
-                            continue;
-                        };
-                        let Some(plugin) = ws.plugins.iter().find(|p| p.id == plugin_id) else {
-                            continue;
-                        };
-                        let instance = match plugin.kind.as_str() {
-                            "csv_recorder" => {
-                                RuntimePlugin::CsvRecorder(CsvRecorderedPlugin::new(plugin.id))
+                            } else if !plugin_instance.is_open() && plugin_instance.can_retry_open() {
+                                let _ = plugin_instance.open();
                             }
-                            #[cfg(feature = "comedi")]
-                            "comedi_daq" => RuntimePlugin::ComediDaq(
-                                comedi_daq_plugin::ComediDaqPlugin::new(plugin.id),
-                            ),
-                            _ => {
-                                let library_path =
-                                    plugin.config.get("library_path").and_then(|v| v.as_str());
-                                if let Some(path) = library_path {
-                                    unsafe {
-                                        if let Some(dynamic) =
-                                            DynamicPluginInstance::load(path, plugin.id)
-                                        {
-                                            RuntimePlugin::Dynamic(dynamic)
-                                        } else {
-                                            continue;
-                                        }
-                                    }
-                                } else {
-                                    continue;
-                                }
+                            track_open_error(&mut open_errors, plugin.id, plugin_instance.open_error());
+
+                            for port in plugin_instance.input_port_names() {
+                                let value = input_value(&ws.connections, &outputs, plugin, &port);
+                                input_values.insert((plugin.id, port.clone()), value);
+                                plugin_instance.set_input(&port, value);
                             }
-                        };
-                        plugin_instances.insert(plugin.id, instance);
-                        viewer_values.remove(&plugin.id);
-                        outputs.retain(|(pid, _), _| *pid != plugin.id);
-                        input_values.retain(|(pid, _), _| *pid != plugin.id);
-                        internal_variable_values.retain(|(pid, _), _| *pid != plugin.id);
-                    }
-                    LogicMessage::GetPluginVariable(plugin_id, var_name, response_tx) => {
-                        let value = plugin_instances.get(&plugin_id).and_then(|instance| {
-                            match instance {
-                                RuntimePlugin::CsvRecorder(p) => p.get_variable(&var_name),
-                                RuntimePlugin::LivePlotter(p) => p.get_variable(&var_name),
-                                RuntimePlugin::PerformanceMonitor(p) => p.get_variable(&var_name),
-                                #[cfg(feature = "comedi")]
-                                RuntimePlugin::ComediDaq(p) => p.get_variable(&var_name),
-                                RuntimePlugin::Dynamic(_) => None,
+                            plugin_instance.set_safe_value(
+                                config::f64_or(&plugin.config, "safe_value", 0.0),
+                            );
+                            if estop_active {
+                                plugin_instance.safe_state();
+                            }
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            for port in plugin_instance.output_port_names() {
+                                let value = plugin_instance.get_output(&port);
+                                outputs.insert((plugin.id, port), value);
                             }
-                        });
-                        let _ = response_tx.send(value);
-                    }
-                    LogicMessage::SetPluginVariable(plugin_id, var_name, value) => {
-                        if let Some(instance) = plugin_instances.get_mut(&plugin_id) {
-                            let _ = match instance {
-                                RuntimePlugin::CsvRecorder(p) => p.set_variable(&var_name, value),
-                                RuntimePlugin::LivePlotter(p) => p.set_variable(&var_name, value),
-                                RuntimePlugin::PerformanceMonitor(p) => p.set_variable(&var_name, value),
-                                #[cfg(feature = "comedi")]
-                                RuntimePlugin::ComediDaq(p) => p.set_variable(&var_name, value),
-                                RuntimePlugin::Dynamic(_) => Ok(()),
-                            };
                         }
-                    }
-                },
-                Err(TryRecvError::Empty) => break,
-                Err(TryRecvError::Disconnected) => {
-                    disconnected = true;
-                    break;
-                }
-            }
-        }
-        if disconnected {
-            break;
-        }
+                        #[cfg(feature = "rpi")]
+                        RuntimePlugin::Gpio(plugin_instance) => {
+                            let chip = plugin
+                                .config
+                                .get("chip")
+                                .and_then(|v| v.as_str())
+                                .unwrap_or("/dev/gpiochip0");
+                            let specs = |key: &str| -> Vec<String> {
+                                plugin
+                                    .config
+                                    .get(key)
+                                    .and_then(|v| v.as_array())
+                                    .map(|items| {
+                                        items
+                                            .iter()
+                                            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                            .collect()
+                                    })
+                                    .unwrap_or_default()
+                            };
+                            plugin_instance.set_config(chip, &specs("output_pins"), &specs("input_pins"));
 
-        if let Some(ws) = workspace.as_ref() {
-            let plugins = order_plugins_for_execution(&ws.plugins, &ws.connections);
+                            if !is_running {
+                                if plugin_instance.is_open() || !plugin_instance.can_retry_open() {
+                                    let _ = plugin_instance.close();
+                                }
+                            } else if !plugin_instance.is_open() && plugin_instance.can_retry_open() {
+                                let _ = plugin_instance.open();
+                            }
+                            track_open_error(&mut open_errors, plugin.id, plugin_instance.open_error());
 
-            for plugin in plugins {
-                let is_running = plugin_running
-                    .get(&plugin.id)
-                    .copied()
-                    .unwrap_or(plugin.running);
-                let instance = match plugin_instances.get_mut(&plugin.id) {
-                    Some(instance) => instance,
-                    None => continue,
-                };
-                match instance {
-                    RuntimePlugin::Dynamic(plugin_instance) => {
-                        let api = unsafe { &*plugin_instance.api };
-                        if let Value::Object(map) = plugin.config.clone() {
-                            let mut map = map;
-                            map.insert(
-                                "period_seconds".to_string(),
-                                Value::from(settings.period_seconds),
+                            for port in plugin_instance.input_port_names() {
+                                let value = input_value(&ws.connections, &outputs, plugin, &port);
+                                input_values.insert((plugin.id, port.clone()), value);
+                                plugin_instance.set_input(&port, value);
+                            }
+                            plugin_instance.set_safe_value(
+                                config::f64_or(&plugin.config, "safe_value", 0.0),
                             );
-                            map.insert(
-                                "max_integration_steps".to_string(),
-                                Value::from(settings.max_integration_steps as f64),
+                            if estop_active {
+                                plugin_instance.safe_state();
+                            }
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            for port in plugin_instance.output_port_names() {
+                                let value = plugin_instance.get_output(&port);
+                                outputs.insert((plugin.id, port), value);
+                            }
+                        }
+                        RuntimePlugin::Stimulus(plugin_instance) => {
+                            let config_f64 = |key: &str, default: f64| {
+                                config::f64_or(&plugin.config, key, default)
+                            };
+                            let shape = plugin
+                                .config
+                                .get("shape")
+                                .and_then(|v| v.as_str())
+                                .map(Shape::from_name)
+                                .unwrap_or(Shape::Step);
+                            plugin_instance.set_config(
+                                shape,
+                                config_f64("start_time", 1.0),
+                                config_f64("amplitude", 1.0),
+                                config_f64("baseline", 0.0),
                             );
-                            let json = Value::Object(map).to_string();
-                            if plugin_instance
-                                .last_config
-                                .as_ref()
-                                .map(|last| last != &json)
-                                .unwrap_or(true)
-                            {
-                                (api.set_config_json)(
-                                    plugin_instance.handle,
-                                    json.as_bytes().as_ptr(),
-                                    json.as_bytes().len(),
-                                );
-                                plugin_instance.last_config = Some(json);
+                            plugin_instance.set_chirp(Chirp {
+                                f_start: config_f64("f_start", 0.1),
+                                f_end: config_f64("f_end", 10.0),
+                                duration: config_f64("duration", 10.0),
+                            });
+
+                            let trigger = input_value(&ws.connections, &outputs, plugin, "trigger");
+                            input_values.insert((plugin.id, "trigger".to_string()), trigger);
+                            plugin_instance.set_trigger(trigger);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
                             }
+                            outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
                         }
-                        for (idx, input_name) in plugin_instance.inputs.iter().enumerate() {
-                            let value =
-                                input_sum(&ws.connections, &outputs, plugin.id, input_name);
-                            input_values.insert((plugin.id, input_name.clone()), value);
-                            let bits = value.to_bits();
-                            if plugin_instance.last_inputs[idx] != bits {
-                                plugin_instance.last_inputs[idx] = bits;
-                                let bytes = &plugin_instance.input_bytes[idx];
-                                (api.set_input)(
-                                    plugin_instance.handle,
-                                    bytes.as_ptr(),
-                                    bytes.len(),
-                                    value,
-                                );
+                        RuntimePlugin::Saturation(plugin_instance) => {
+                            let min = config::f64_or(&plugin.config, "min", -1.0);
+                            let max = config::f64_or(&plugin.config, "max", 1.0);
+                            plugin_instance.set_config(min, max);
+
+                            let input = input_value(&ws.connections, &outputs, plugin, "in");
+                            input_values.insert((plugin.id, "in".to_string()), input);
+                            plugin_instance.set_input(input);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
                             }
+                            outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                            outputs.insert(
+                                (plugin.id, "out_of_range".to_string()),
+                                plugin_instance.out_of_range(),
+                            );
                         }
-                        if is_running {
-                            (api.process)(
-                                plugin_instance.handle,
-                                plugin_ctx.tick,
-                                plugin_ctx.period_seconds,
+                        RuntimePlugin::Histogram(plugin_instance) => {
+                            let bins = config::u64_or(&plugin.config, "bins", 20);
+                            let range_min = config::f64_or(&plugin.config, "range_min", -1.0);
+                            let range_max = config::f64_or(&plugin.config, "range_max", 1.0);
+                            let window = config::u64_or(&plugin.config, "window", 1000);
+                            plugin_instance.set_config(bins as usize, range_min, range_max, window as usize);
+
+                            let input = input_value(&ws.connections, &outputs, plugin, "in");
+                            input_values.insert((plugin.id, "in".to_string()), input);
+                            plugin_instance.set_input(input);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            internal_variable_values.insert(
+                                (plugin.id, "counts".to_string()),
+                                serde_json::Value::from(plugin_instance.counts().to_vec()),
                             );
-                            for (idx, output_name) in plugin_instance.outputs.iter().enumerate() {
-                                let bytes = &plugin_instance.output_bytes[idx];
-                                let value = (api.get_output)(
-                                    plugin_instance.handle,
-                                    bytes.as_ptr(),
-                                    bytes.len(),
+                            outputs.insert((plugin.id, "count_total".to_string()), plugin_instance.count_total());
+                            outputs.insert((plugin.id, "mode_bin".to_string()), plugin_instance.mode_bin());
+                        }
+                        RuntimePlugin::TcpStream(plugin_instance) => {
+                            let input_count = config::u64_or(&plugin.config, "input_count", 0) as usize;
+                            let port = u16::try_from(config::u64_or(&plugin.config, "port", 9000)).unwrap_or(9000);
+                            let format = plugin
+                                .config
+                                .get("format")
+                                .and_then(|v| v.as_str())
+                                .map(TcpStreamFormat::from_name)
+                                .unwrap_or(TcpStreamFormat::Json);
+                            let rate_hz = config::f64_or(&plugin.config, "rate_hz", 10.0);
+                            plugin_instance.set_config(input_count, port, format, rate_hz, is_running);
+
+                            let mut inputs = Vec::with_capacity(input_count);
+                            for idx in 0..input_count {
+                                let port = format!("in_{idx}");
+                                let value = if idx == 0 {
+                                    let mut ports = vec![port.clone()];
+                                    ports.push("in".to_string());
+                                    input_value_any(&ws.connections, &outputs, plugin, &ports)
+                                } else {
+                                    input_value(&ws.connections, &outputs, plugin, &port)
+                                };
+                                input_values.insert((plugin.id, port), value);
+                                inputs.push(value);
+                            }
+                            plugin_instance.set_inputs(inputs);
+                            internal_variable_values.insert(
+                                (plugin.id, "input_count".to_string()),
+                                serde_json::Value::from(input_count as i64),
+                            );
+                            internal_variable_values.insert(
+                                (plugin.id, "clients".to_string()),
+                                serde_json::Value::from(plugin_instance.client_count() as i64),
+                            );
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                        }
+                        RuntimePlugin::ShmPublisher(plugin_instance) => {
+                            let input_count = config::u64_or(&plugin.config, "input_count", 0) as usize;
+                            let name = plugin
+                                .config
+                                .get("name")
+                                .and_then(|v| v.as_str())
+                                .unwrap_or("rtsyn");
+                            plugin_instance.set_config(input_count, name.to_string(), is_running);
+
+                            let mut inputs = Vec::with_capacity(input_count);
+                            for idx in 0..input_count {
+                                let port = format!("in_{idx}");
+                                let value = if idx == 0 {
+                                    let mut ports = vec![port.clone()];
+                                    ports.push("in".to_string());
+                                    input_value_any(&ws.connections, &outputs, plugin, &ports)
+                                } else {
+                                    input_value(&ws.connections, &outputs, plugin, &port)
+                                };
+                                input_values.insert((plugin.id, port), value);
+                                inputs.push(value);
+                            }
+                            plugin_instance.set_inputs(inputs);
+                            internal_variable_values.insert(
+                                (plugin.id, "input_count".to_string()),
+                                serde_json::Value::from(input_count as i64),
+                            );
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                        }
+                        RuntimePlugin::Rms(plugin_instance) => {
+                            let window_ms = config::f64_or(&plugin.config, "window_ms", 100.0);
+                            plugin_instance.set_config(window_ms, plugin_ctx.period_seconds);
+
+                            let input = input_value(&ws.connections, &outputs, plugin, "in");
+                            input_values.insert((plugin.id, "in".to_string()), input);
+                            plugin_instance.set_input(input);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "rms".to_string()), plugin_instance.rms());
+                            outputs.insert((plugin.id, "peak".to_string()), plugin_instance.peak());
+                            outputs.insert(
+                                (plugin.id, "crest_factor".to_string()),
+                                plugin_instance.crest_factor(),
+                            );
+                        }
+                        RuntimePlugin::PeakHold(plugin_instance) => {
+                            let decay_rate = config::f64_or(&plugin.config, "decay_rate", 0.0);
+                            plugin_instance.set_config(decay_rate);
+
+                            let input = input_value(&ws.connections, &outputs, plugin, "in");
+                            let reset = input_value(&ws.connections, &outputs, plugin, "reset");
+                            input_values.insert((plugin.id, "in".to_string()), input);
+                            input_values.insert((plugin.id, "reset".to_string()), reset);
+                            plugin_instance.set_inputs(input, reset);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "max_hold".to_string()), plugin_instance.max_hold());
+                            outputs.insert((plugin.id, "min_hold".to_string()), plugin_instance.min_hold());
+                        }
+                        RuntimePlugin::Timer(plugin_instance) => {
+                            let duration_ms = config::f64_or(&plugin.config, "duration_ms", 1000.0);
+                            let mode = plugin
+                                .config
+                                .get("mode")
+                                .and_then(|v| v.as_str())
+                                .map(TimerMode::from_name)
+                                .unwrap_or(TimerMode::OneShot);
+                            plugin_instance.set_config(duration_ms, mode);
+
+                            let trigger = input_value(&ws.connections, &outputs, plugin, "trigger");
+                            input_values.insert((plugin.id, "trigger".to_string()), trigger);
+                            plugin_instance.set_input(trigger);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "active".to_string()), plugin_instance.active());
+                        }
+                        RuntimePlugin::SetpointRamp(plugin_instance) => {
+                            let ramp_rate = config::f64_or(&plugin.config, "ramp_rate", 1.0);
+                            plugin_instance.set_config(ramp_rate);
+
+                            let target = input_value(&ws.connections, &outputs, plugin, "target");
+                            input_values.insert((plugin.id, "target".to_string()), target);
+                            // An unconnected enable input leaves the ramp running
+                            let enable_connected = ws
+                                .connections
+                                .iter()
+                                .any(|conn| conn.to_plugin == plugin.id && conn.to_port == "enable");
+                            let enabled = if enable_connected {
+                                let enable = input_value(&ws.connections, &outputs, plugin, "enable");
+                                input_values.insert((plugin.id, "enable".to_string()), enable);
+                                enable > 0.5
+                            } else {
+                                true
+                            };
+                            plugin_instance.set_inputs(target, enabled);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                        }
+                        RuntimePlugin::LookupTable(plugin_instance) => {
+                            let points = |key: &str| config::f64_array_or(&plugin.config, key, &[]);
+                            let interpolation = plugin
+                                .config
+                                .get("interpolation")
+                                .and_then(|v| v.as_str())
+                                .map(Interpolation::from_name)
+                                .unwrap_or_default();
+                            let extrapolate = plugin
+                                .config
+                                .get("extrapolate")
+                                .and_then(|v| v.as_bool())
+                                .unwrap_or(false);
+                            plugin_instance.set_config(&points("x_points"), &points("y_points"), interpolation, extrapolate);
+
+                            let input = input_value(&ws.connections, &outputs, plugin, "in");
+                            input_values.insert((plugin.id, "in".to_string()), input);
+                            plugin_instance.set_input(input);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                        }
+                        RuntimePlugin::Stats(plugin_instance) => {
+                            let input_count = config::u64_or(&plugin.config, "input_count", 0) as usize;
+                            plugin_instance.set_config(input_count);
+
+                            let mut inputs = Vec::with_capacity(input_count);
+                            for idx in 0..input_count {
+                                let port = format!("in_{idx}");
+                                let value = if idx == 0 {
+                                    let mut ports = vec![port.clone()];
+                                    ports.push("in".to_string());
+                                    input_value_any(&ws.connections, &outputs, plugin, &ports)
+                                } else {
+                                    input_value(&ws.connections, &outputs, plugin, &port)
+                                };
+                                input_values.insert((plugin.id, port), value);
+                                inputs.push(value);
+                            }
+                            let reset = input_value(&ws.connections, &outputs, plugin, "reset");
+                            input_values.insert((plugin.id, "reset".to_string()), reset);
+                            plugin_instance.set_inputs(inputs, reset);
+                            internal_variable_values.insert(
+                                (plugin.id, "input_count".to_string()),
+                                serde_json::Value::from(input_count as i64),
+                            );
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            for name in plugin_instance.variable_names() {
+                                if let Some(value) = plugin_instance.get_variable(&name) {
+                                    internal_variable_values.insert((plugin.id, name), value);
+                                }
+                            }
+                        }
+                        RuntimePlugin::Resampler(plugin_instance) => {
+                            let rate = |key: &str, default: f64| -> f64 {
+                                config::f64_or(&plugin.config, key, default)
+                            };
+                            let interpolation = plugin
+                                .config
+                                .get("interpolation")
+                                .and_then(|v| v.as_str())
+                                .map(ResamplerInterpolation::from_name)
+                                .unwrap_or_default();
+                            let downsampling = plugin
+                                .config
+                                .get("downsampling")
+                                .and_then(|v| v.as_str())
+                                .map(Downsampling::from_name)
+                                .unwrap_or_default();
+                            plugin_instance.set_config(
+                                rate("input_rate_hz", 100.0),
+                                rate("output_rate_hz", 1000.0),
+                                interpolation,
+                                downsampling,
+                            );
+
+                            let input = input_value(&ws.connections, &outputs, plugin, "in");
+                            input_values.insert((plugin.id, "in".to_string()), input);
+                            plugin_instance.set_input(input);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                        }
+                        RuntimePlugin::Pwm(plugin_instance) => {
+                            let frequency_hz = config::f64_or(&plugin.config, "frequency_hz", 10.0);
+                            let deadband = config::f64_or(&plugin.config, "deadband", 0.0);
+                            let invert = plugin
+                                .config
+                                .get("invert")
+                                .and_then(|v| v.as_bool())
+                                .unwrap_or(false);
+                            plugin_instance.set_config(frequency_hz, deadband, invert);
+
+                            let duty = input_value(&ws.connections, &outputs, plugin, "duty");
+                            input_values.insert((plugin.id, "duty".to_string()), duty);
+                            plugin_instance.set_input(duty);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                        }
+                        RuntimePlugin::Estop(plugin_instance) => {
+                            let latching = plugin
+                                .config
+                                .get("latching")
+                                .and_then(|v| v.as_bool())
+                                .unwrap_or(true);
+                            plugin_instance.set_config(latching);
+
+                            let trigger = input_value(&ws.connections, &outputs, plugin, "trigger");
+                            let reset = input_value(&ws.connections, &outputs, plugin, "reset");
+                            input_values.insert((plugin.id, "trigger".to_string()), trigger);
+                            input_values.insert((plugin.id, "reset".to_string()), reset);
+                            plugin_instance.set_inputs(trigger > 0.5, reset > 0.5);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "active".to_string()), plugin_instance.active());
+                            if is_running && plugin_instance.is_active() {
+                                estop_seen = true;
+                                if !estop_active {
+                                    tracing::warn!(plugin = plugin.id, "emergency stop asserted");
+                                    estop_active = true;
+                                    // Stopped plugins stay stopped once the estop is released
+                                    for other in &ws.plugins {
+                                        if !is_estop_essential(&other.kind) {
+                                            plugin_running.insert(other.id, false);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        RuntimePlugin::AudioOut(plugin_instance) => {
+                            let sample_rate = u32::try_from(config::u64_or(&plugin.config, "sample_rate", 44_100)).unwrap_or(44_100);
+                            let gain = config::f64_or(&plugin.config, "gain", 1.0);
+                            let mode = plugin
+                                .config
+                                .get("mode")
+                                .and_then(|v| v.as_str())
+                                .map(AudioOutMode::from_name)
+                                .unwrap_or_default();
+                            let carrier_hz = config::f64_or(&plugin.config, "carrier_hz", 440.0);
+                            let deviation_hz = config::f64_or(&plugin.config, "deviation_hz", 220.0);
+                            plugin_instance.set_config(sample_rate, gain, mode, carrier_hz, deviation_hz, is_running);
+
+                            let value = input_value(&ws.connections, &outputs, plugin, "in");
+                            input_values.insert((plugin.id, "in".to_string()), value);
+                            plugin_instance.set_input(value);
+                            internal_variable_values.insert(
+                                (plugin.id, "dropped".to_string()),
+                                serde_json::Value::from(plugin_instance.dropped()),
+                            );
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                        }
+                        RuntimePlugin::FrequencyCounter(plugin_instance) => {
+                            let threshold = config::f64_or(&plugin.config, "threshold", 0.5);
+                            let edge = plugin
+                                .config
+                                .get("edge")
+                                .and_then(|v| v.as_str())
+                                .map(FrequencyCounterEdge::from_name)
+                                .unwrap_or_default();
+                            let periods = config::u64_or(&plugin.config, "periods", 4) as usize;
+                            plugin_instance.set_config(threshold, edge, periods);
+
+                            let value = input_value(&ws.connections, &outputs, plugin, "in");
+                            input_values.insert((plugin.id, "in".to_string()), value);
+                            plugin_instance.set_input(value);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "frequency_hz".to_string()), plugin_instance.frequency_hz());
+                            outputs.insert((plugin.id, "period_ms".to_string()), plugin_instance.period_ms());
+                            outputs.insert((plugin.id, "duty_cycle".to_string()), plugin_instance.duty_cycle());
+                        }
+                        RuntimePlugin::ModbusIo(plugin_instance) => {
+                            let config_str = |key: &str, default: &str| {
+                                plugin
+                                    .config
+                                    .get(key)
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or(default)
+                                    .to_string()
+                            };
+                            let config_u64 = |key: &str, default: u64| {
+                                config::u64_or(&plugin.config, key, default)
+                            };
+                            let specs = |key: &str| -> Vec<String> {
+                                plugin
+                                    .config
+                                    .get(key)
+                                    .and_then(|v| v.as_array())
+                                    .map(|items| {
+                                        items
+                                            .iter()
+                                            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                            .collect()
+                                    })
+                                    .unwrap_or_default()
+                            };
+                            let connection = ModbusConnectionConfig {
+                                mode: ModbusMode::from_name(&config_str("mode", "tcp")),
+                                address: config_str("address", "127.0.0.1:502"),
+                                device: config_str("device", "/dev/ttyUSB0"),
+                                baud_rate: u32::try_from(config_u64("baud_rate", 9600)).unwrap_or(9600),
+                                unit_id: u8::try_from(config_u64("unit_id", 1)).unwrap_or(1),
+                                timeout_ms: config_u64("timeout_ms", 100),
+                            };
+                            plugin_instance.set_config(
+                                connection,
+                                config_u64("poll_ms", 100),
+                                &specs("writes"),
+                                &specs("reads"),
+                            );
+
+                            if !is_running {
+                                if plugin_instance.is_open() || !plugin_instance.can_retry_open() {
+                                    let _ = plugin_instance.close();
+                                }
+                            } else if !plugin_instance.is_open() && plugin_instance.can_retry_open() {
+                                let _ = plugin_instance.open();
+                            }
+                            track_open_error(&mut open_errors, plugin.id, plugin_instance.open_error());
+
+                            for port in plugin_instance.input_port_names() {
+                                let value = input_value(&ws.connections, &outputs, plugin, &port);
+                                input_values.insert((plugin.id, port.clone()), value);
+                                plugin_instance.set_input(&port, value);
+                            }
+                            plugin_instance.set_safe_value(
+                                config::f64_or(&plugin.config, "safe_value", 0.0),
+                            );
+                            if estop_active {
+                                plugin_instance.safe_state();
+                            }
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            for port in plugin_instance.output_port_names() {
+                                let value = plugin_instance.get_output(&port);
+                                outputs.insert((plugin.id, port), value);
+                            }
+                        }
+                        RuntimePlugin::StateMachine(plugin_instance) => {
+                            let names = |key: &str| -> Vec<String> {
+                                plugin
+                                    .config
+                                    .get(key)
+                                    .and_then(|v| v.as_array())
+                                    .map(|items| {
+                                        items
+                                            .iter()
+                                            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                            .collect()
+                                    })
+                                    .unwrap_or_default()
+                            };
+                            plugin_instance.set_config(&names("inputs"), &names("states"), &names("transitions"));
+
+                            for port in plugin_instance.input_port_names() {
+                                let value = input_value(&ws.connections, &outputs, plugin, &port);
+                                input_values.insert((plugin.id, port.clone()), value);
+                                plugin_instance.set_input(&port, value);
+                            }
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                                queue_plugin_events(
+                                    &mut plugin_events,
+                                    plugin.id,
+                                    plugin_ctx.tick,
+                                    settings.period_seconds,
+                                    plugin_instance.drain_events(),
                                 );
-                                outputs.insert((plugin.id, output_name.clone()), value);
                             }
+                            for port in plugin_instance.output_port_names() {
+                                let value = plugin_instance.get_output(&port);
+                                outputs.insert((plugin.id, port), value);
+                            }
+                            internal_variable_values.insert(
+                                (plugin.id, "state_name".to_string()),
+                                serde_json::Value::from(plugin_instance.state_name()),
+                            );
+                            internal_variable_values.insert(
+                                (plugin.id, "time_in_state".to_string()),
+                                serde_json::Value::from(plugin_instance.time_in_state()),
+                            );
                         }
-                        for (idx, var_name) in plugin_instance.internal_variables.iter().enumerate() {
-                            let bytes = &plugin_instance.internal_variable_bytes[idx];
-                            let value = (api.get_output)(
-                                plugin_instance.handle,
-                                bytes.as_ptr(),
-                                bytes.len(),
+                        RuntimePlugin::FaultRecorder(plugin_instance) => {
+                            let input_count = config::u64_or(&plugin.config, "input_count", 0) as usize;
+                            let path = plugin
+                                .config
+                                .get("path")
+                                .and_then(|v| v.as_str())
+                                .and_then(normalize_path);
+                            let seconds = |key: &str| config::f64_or(&plugin.config, key, 1.0);
+                            plugin_instance.set_config(
+                                input_count,
+                                path,
+                                seconds("pre_trigger_s"),
+                                seconds("post_trigger_s"),
+                                settings.period_seconds,
                             );
+
+                            let mut inputs = Vec::with_capacity(input_count);
+                            for idx in 0..input_count {
+                                let port = format!("in_{idx}");
+                                let value = if idx == 0 {
+                                    let mut ports = vec![port.clone()];
+                                    ports.push("in".to_string());
+                                    input_value_any(&ws.connections, &outputs, plugin, &ports)
+                                } else {
+                                    input_value(&ws.connections, &outputs, plugin, &port)
+                                };
+                                input_values.insert((plugin.id, port), value);
+                                inputs.push(value);
+                            }
+                            let trigger = input_value(&ws.connections, &outputs, plugin, "trigger");
+                            input_values.insert((plugin.id, "trigger".to_string()), trigger);
+                            plugin_instance.set_inputs(inputs, trigger);
                             internal_variable_values.insert(
-                                (plugin.id, var_name.clone()),
-                                serde_json::Value::from(value),
+                                (plugin.id, "input_count".to_string()),
+                                serde_json::Value::from(input_count as i64),
                             );
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            for name in ["state", "captures", "last_capture", "error"] {
+                                if let Some(value) = plugin_instance.get_variable(name) {
+                                    internal_variable_values.insert((plugin.id, name.to_string()), value);
+                                }
+                            }
                         }
-                    }
-                    RuntimePlugin::CsvRecorder(plugin_instance) => {
-                        let config_input_count = plugin
-                            .config
-                            .get("input_count")
-                            .and_then(|v| v.as_u64())
-                            .unwrap_or(0)
-                            as usize;
-                        let separator = plugin
+                        RuntimePlugin::FilteredDerivative(plugin_instance) => {
+                            let tau = config::f64_or(&plugin.config, "tau", 0.01);
+                            let gain = config::f64_or(&plugin.config, "gain", 1.0);
+                            plugin_instance.set_config(tau, gain);
+
+                            let input = input_value(&ws.connections, &outputs, plugin, "in");
+                            input_values.insert((plugin.id, "in".to_string()), input);
+                            plugin_instance.set_input(input);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                        }
+                        RuntimePlugin::BitPack(plugin_instance) => {
+                            let bits = config::u64_or(&plugin.config, "bits", 8) as usize;
+                            plugin_instance.set_config(bits);
+
+                            for idx in 0..plugin_instance.bits() {
+                                let port = bit_pack_port(idx);
+                                let value = input_value(&ws.connections, &outputs, plugin, &port);
+                                input_values.insert((plugin.id, port), value);
+                                plugin_instance.set_bit(idx, value);
+                            }
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "word".to_string()), plugin_instance.word());
+                        }
+                        RuntimePlugin::BitUnpack(plugin_instance) => {
+                            let bits = config::u64_or(&plugin.config, "bits", 8) as usize;
+                            plugin_instance.set_config(bits);
+
+                            let word = input_value(&ws.connections, &outputs, plugin, "word");
+                            input_values.insert((plugin.id, "word".to_string()), word);
+                            plugin_instance.set_input(word);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            for idx in 0..plugin_instance.bits() {
+                                outputs.insert((plugin.id, bit_unpack_port(idx)), plugin_instance.bit(idx));
+                            }
+                        }
+                        RuntimePlugin::TransferFunction(plugin_instance) => {
+                            let coefficients = |key: &str| config::f64_array_or(&plugin.config, key, &[]);
+                            plugin_instance.set_config(&coefficients("b"), &coefficients("a"));
+
+                            let input = input_value(&ws.connections, &outputs, plugin, "in");
+                            input_values.insert((plugin.id, "in".to_string()), input);
+                            plugin_instance.set_input(input);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                            internal_variable_values.insert(
+                                (plugin.id, "error".to_string()),
+                                serde_json::Value::from(plugin_instance.error().unwrap_or_default()),
+                            );
+                        }
+                        RuntimePlugin::ErrorMetrics(plugin_instance) => {
+                            let window_ms = config::f64_or(&plugin.config, "window_ms", 1000.0);
+                            plugin_instance.set_config(window_ms, plugin_ctx.period_seconds);
+
+                            let reference = input_value(&ws.connections, &outputs, plugin, "reference");
+                            let actual = input_value(&ws.connections, &outputs, plugin, "actual");
+                            input_values.insert((plugin.id, "reference".to_string()), reference);
+                            input_values.insert((plugin.id, "actual".to_string()), actual);
+                            plugin_instance.set_inputs(reference, actual);
+                            if is_running {
+                                let _ = plugin_instance.process(&mut plugin_ctx);
+                            }
+                            outputs.insert((plugin.id, "error".to_string()), plugin_instance.error());
+                            outputs.insert((plugin.id, "abs_error".to_string()), plugin_instance.abs_error());
+                            outputs.insert((plugin.id, "iae".to_string()), plugin_instance.iae());
+                            outputs.insert((plugin.id, "ise".to_string()), plugin_instance.ise());
+                            outputs.insert((plugin.id, "rmse".to_string()), plugin_instance.rmse());
+                        }
+                    }
+                    ConnectionQueues::restore(&mut outputs, queued_inputs);
+                    if tracking.warmup.tick(plugin.id, is_running, warmup_ticks(&plugin.config)) {
+                        for ((id, _), value) in outputs.iter_mut() {
+                            if *id == plugin.id {
+                                *value = 0.0;
+                            }
+                        }
+                    }
+                    tracking.latency.outputs_written(plugin.id, is_running);
+                    if is_running {
+                        tracking.queues.outputs_written(&ws.connections, &outputs, plugin.id);
+                    }
+                }
+                if run_tick {
+                    estop_active = estop_seen;
+                    plugin_ctx.tick = plugin_ctx.tick.wrapping_add(1);
+                }
+                let ui_interval = if settings.ui_hz > 0.0 {
+                    Duration::from_secs_f64(1.0 / settings.ui_hz)
+                } else {
+                    Duration::from_secs(1)
+                };
+                if last_state.elapsed() >= ui_interval {
+                    // Limit plotter samples to prevent memory issues
+                    let mut limited_plotter_samples = HashMap::new();
+                    for (plugin_id, samples) in &plotter_samples {
+                        let mut limited_samples = samples.clone();
+                        // Keep only the last 1000 samples per plugin to prevent memory overflow
+                        if limited_samples.len() > 1000 {
+                            limited_samples.drain(0..limited_samples.len() - 1000);
+                        }
+                        limited_plotter_samples.insert(*plugin_id, limited_samples);
+                    }
+
+                    let _ = logic_state_tx.send(LogicState {
+                        outputs: outputs.clone(),
+                        input_values: input_values.clone(),
+                        internal_variable_values: internal_variable_values.clone(),
+                        viewer_values: viewer_values.clone(),
+                        tick: plugin_ctx.tick,
+                        plotter_samples: limited_plotter_samples,
+                        warming_up: tracking.warmup.warming_up().clone(),
+                        open_errors: open_errors.clone(),
+                        load_errors: load_errors.clone(),
+                        events: std::mem::take(&mut plugin_events),
+                        step_mode: step_mode.enabled(),
+                    });
+                    plotter_samples.clear();
+                    last_state = Instant::now();
+                }
+            }
+            let _ = runtime.tick();
+            let _ = settings.cores.len();
+            if step_mode.enabled() {
+                // Requested ticks run back to back; the timer starts over when
+                // step mode is left
+                scheduler.restart();
+                if step_mode.pending() == 0 {
+                    ActiveRtBackend::sleep(STEP_MODE_POLL);
+                }
+                continue;
+            }
+            // Skipped ticks still count, so tick * period follows the wall clock
+            let (wake, skipped) = scheduler.after_tick(Instant::now());
+            if skipped > 0 {
+                tracing::debug!(skipped, "missed deadline, skipping ticks");
+            }
+            plugin_ctx.tick = plugin_ctx.tick.wrapping_add(skipped);
+            ActiveRtBackend::sleep_until(wake);
+        }
+    })?;
+
+    Ok((logic_tx, logic_state_rx))
+}
+
+pub fn run_runtime_current(
+    logic_rx: Receiver<LogicMessage>,
+    logic_state_tx: Sender<LogicState>,
+) -> Result<(), String> {
+    crate::logging::init();
+    ActiveRtBackend::prepare()?;
+    run_runtime_loop(logic_rx, logic_state_tx, false)
+}
+
+/// The tick loop of [`run_runtime_current`], on a thread that is already
+/// prepared. Graphs run it with `in_graph` set, which refuses nested graphs.
+pub(crate) fn run_runtime_loop(
+    logic_rx: Receiver<LogicMessage>,
+    logic_state_tx: Sender<LogicState>,
+    in_graph: bool,
+) -> Result<(), String> {
+    let mut settings = LogicSettings {
+        cores: vec![0],
+        period_seconds: 0.001,
+        time_scale: 1000.0,
+        time_label: "time_ms".to_string(),
+        ui_hz: 60.0,
+        max_integration_steps: 10, // Reasonable default for real-time performance
+        missed_deadline_policy: MissedDeadlinePolicy::default(),
+        jitter_us: 0.0,
+    };
+    let mut scheduler = TickScheduler::new(
+        settings.missed_deadline_policy,
+        Duration::from_secs_f64(settings.period_seconds.max(0.0)),
+    );
+    let mut workspace: Option<WorkspaceDefinition> = None;
+    // Recomputed when the workspace changes, not every tick
+    let mut execution_order: Vec<PluginDefinition> = Vec::new();
+    let mut plugin_instances: HashMap<u64, RuntimePlugin> = HashMap::new();
+    let mut plugin_running: HashMap<u64, bool> = HashMap::new();
+    let mut tracking = PluginTracking::default();
+    let mut open_errors: HashMap<u64, String> = HashMap::new();
+    let mut load_errors: HashMap<u64, String> = HashMap::new();
+    let mut estop_active = false;
+    let mut step_mode = StepMode::default();
+    let mut graphs = Graphs::default();
+    let mut plugin_ctx = PluginContext {
+        period_seconds: settings.period_seconds,
+        ..Default::default()
+    };
+    let mut outputs: HashMap<(u64, String), f64> = HashMap::new();
+    let mut input_values: HashMap<(u64, String), f64> = HashMap::new();
+    let mut internal_variable_values: HashMap<(u64, String), serde_json::Value> = HashMap::new();
+    let mut viewer_values: HashMap<u64, f64> = HashMap::new();
+    let mut plotter_samples: HashMap<u64, Vec<(u64, Vec<f64>)>> = HashMap::new();
+    let mut plugin_events: Vec<PluginEvent> = Vec::new();
+    let mut runtime = crate::Runtime::new(workspace::WorkspaceDefinition {
+        name: "test".to_string(),
+        description: String::new(),
+        target_hz: 1000,
+        plugins: Vec::new(),
+        connections: Vec::new(),
+        settings: workspace::WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    });
+    let mut last_state = Instant::now();
+
+    loop {
+        let mut disconnected = false;
+        loop {
+            match logic_rx.try_recv() {
+                Ok(message) => match message {
+                    LogicMessage::UpdateSettings(new_settings) => {
+                        settings = new_settings;
+                        pin_to_cores(&settings.cores);
+                        scheduler.reconfigure(
+                            settings.missed_deadline_policy,
+                            Duration::from_secs_f64(settings.period_seconds.max(0.0)),
+                        );
+                        scheduler.set_jitter(Duration::from_secs_f64(settings.jitter_us.max(0.0) / 1e6));
+                        plugin_ctx.period_seconds = settings.period_seconds;
+                        graphs.update_settings(&settings);
+                        tracing::info!(
+                            period_seconds = settings.period_seconds,
+                            policy = ?settings.missed_deadline_policy,
+                            jitter_us = settings.jitter_us,
+                            "runtime settings updated"
+                        );
+                    }
+                    LogicMessage::UpdateWorkspace(mut new_workspace) => {
+                        let mut new_ids: HashSet<u64> = HashSet::new();
+                        for plugin in &new_workspace.plugins {
+                            new_ids.insert(plugin.id);
+                            plugin_running.insert(plugin.id, plugin.running);
+                            if !plugin_instances.contains_key(&plugin.id) {
+                                load_plugin_instance(&mut plugin_instances, &mut load_errors, plugin);
+                            }
+                            let previous = workspace
+                                .as_ref()
+                                .and_then(|ws| ws.plugins.iter().find(|p| p.id == plugin.id));
+                            if !matches!(previous, Some(previous) if previous.config == plugin.config) {
+                                warn_malformed_config(plugin_instances.get(&plugin.id), plugin);
+                            }
+                        }
+
+                        open_errors.retain(|id, _| new_ids.contains(id));
+                        load_errors.retain(|id, _| new_ids.contains(id));
+                        let removed_ids: Vec<u64> = plugin_instances
+                            .keys()
+                            .filter(|id| !new_ids.contains(id))
+                            .copied()
+                            .collect();
+                        for id in removed_ids {
+                            if let Some(instance) = plugin_instances.remove(&id) {
+                                if let RuntimePlugin::Dynamic(dynamic) = instance {
+                                    (unsafe { &*dynamic.api }.destroy)(dynamic.handle);
+                                }
+                            }
+                            plugin_running.remove(&id);
+                            clear_plugin_state(
+                                id,
+                                &mut tracking,
+                                &mut outputs,
+                                &mut input_values,
+                                &mut internal_variable_values,
+                                &mut viewer_values,
+                                &mut plotter_samples,
+                            );
+                        }
+                        for conn in &new_workspace.connections {
+                            if new_ids.contains(&conn.from_plugin) && new_ids.contains(&conn.to_plugin) {
+                                tracing::debug!(
+                                    "connection {}:{} -> {}:{}",
+                                    conn.from_plugin,
+                                    conn.from_port,
+                                    conn.to_plugin,
+                                    conn.to_port
+                                );
+                            } else {
+                                tracing::warn!(
+                                    from = conn.from_plugin,
+                                    to = conn.to_plugin,
+                                    "connection references a missing plugin"
+                                );
+                            }
+                        }
+                        tracing::info!(
+                            plugins = new_workspace.plugins.len(),
+                            connections = new_workspace.connections.len(),
+                            "workspace updated"
+                        );
+                        sort_connections_for_evaluation(&mut new_workspace.connections);
+                        execution_order = order_plugins_for_execution(
+                            &new_workspace.plugins,
+                            &new_workspace.connections,
+                        );
+                        tracking.queues.retain(&new_workspace.connections);
+                        workspace = Some(new_workspace);
+                    }
+                    LogicMessage::AddPlugin(plugin) => {
+                        let Some(ws) = workspace.as_mut() else {
+                            continue;
+                        };
+                        tracing::debug!(plugin = plugin.id, kind = %plugin.kind, "plugin added");
+                        ws.plugins.retain(|p| p.id != plugin.id);
+                        plugin_running.insert(plugin.id, plugin.running);
+                        if !plugin_instances.contains_key(&plugin.id) {
+                            load_plugin_instance(&mut plugin_instances, &mut load_errors, &plugin);
+                        }
+                        warn_malformed_config(plugin_instances.get(&plugin.id), &plugin);
+                        ws.plugins.push(plugin);
+                        execution_order = order_plugins_for_execution(&ws.plugins, &ws.connections);
+                    }
+                    LogicMessage::RemovePlugin(plugin_id) => {
+                        let Some(ws) = workspace.as_mut() else {
+                            continue;
+                        };
+                        tracing::debug!(plugin = plugin_id, "plugin removed");
+                        ws.plugins.retain(|p| p.id != plugin_id);
+                        ws.connections
+                            .retain(|c| c.from_plugin != plugin_id && c.to_plugin != plugin_id);
+                        if let Some(RuntimePlugin::Dynamic(dynamic)) = plugin_instances.remove(&plugin_id) {
+                            (unsafe { &*dynamic.api }.destroy)(dynamic.handle);
+                        }
+                        open_errors.remove(&plugin_id);
+                        load_errors.remove(&plugin_id);
+                        plugin_running.remove(&plugin_id);
+                        clear_plugin_state(
+                            plugin_id,
+                            &mut tracking,
+                            &mut outputs,
+                            &mut input_values,
+                            &mut internal_variable_values,
+                            &mut viewer_values,
+                            &mut plotter_samples,
+                        );
+                        execution_order = order_plugins_for_execution(&ws.plugins, &ws.connections);
+                        tracking.queues.retain(&ws.connections);
+                    }
+                    LogicMessage::UpdatePluginConfig(plugin_id, config) => {
+                        let Some(ws) = workspace.as_mut() else {
+                            continue;
+                        };
+                        let changed = ws.plugins.iter().any(|p| p.id == plugin_id && p.config != config);
+                        // Plugins read their config every tick, so updating
+                        // both copies is enough
+                        for plugin in ws
+                            .plugins
+                            .iter_mut()
+                            .chain(execution_order.iter_mut())
+                            .filter(|p| p.id == plugin_id)
+                        {
+                            plugin.config = config.clone();
+                        }
+                        // A plugin whose library failed to load gets another try,
+                        // e.g. after its library_path was pointed at a rebuilt one
+                        if load_errors.contains_key(&plugin_id) {
+                            if let Some(plugin) = ws.plugins.iter().find(|p| p.id == plugin_id) {
+                                load_plugin_instance(&mut plugin_instances, &mut load_errors, plugin);
+                            }
+                        }
+                        if changed {
+                            if let Some(plugin) = ws.plugins.iter().find(|p| p.id == plugin_id) {
+                                warn_malformed_config(plugin_instances.get(&plugin_id), plugin);
+                            }
+                        }
+                    }
+                    LogicMessage::AddConnection(connection) => {
+                        let Some(ws) = workspace.as_mut() else {
+                            continue;
+                        };
+                        tracing::debug!(
+                            "connection {}:{} -> {}:{} added",
+                            connection.from_plugin,
+                            connection.from_port,
+                            connection.to_plugin,
+                            connection.to_port
+                        );
+                        ws.connections.push(connection);
+                        sort_connections_for_evaluation(&mut ws.connections);
+                        execution_order = order_plugins_for_execution(&ws.plugins, &ws.connections);
+                    }
+                    LogicMessage::RemoveConnection(connection) => {
+                        let Some(ws) = workspace.as_mut() else {
+                            continue;
+                        };
+                        if workspace::remove_connection(&mut ws.connections, &connection) {
+                            execution_order =
+                                order_plugins_for_execution(&ws.plugins, &ws.connections);
+                            tracking.queues.retain(&ws.connections);
+                        }
+                    }
+                    LogicMessage::SetPluginRunning(plugin_id, running) => {
+                        tracing::debug!(plugin = plugin_id, running, "plugin run state changed");
+                        plugin_running.insert(plugin_id, running);
+                    }
+                    LogicMessage::SetLogLevel(level) => {
+                        crate::logging::set_level(level);
+                        tracing::info!(%level, "log level changed");
+                    }
+                    LogicMessage::CreateGraph(name, state_tx) => {
+                        if in_graph {
+                            // Dropping `state_tx` tells the caller no graph was created
+                            tracing::warn!(graph = %name, "graphs cannot create graphs");
+                        } else {
+                            graphs.create(name, &settings, state_tx);
+                        }
+                    }
+                    LogicMessage::RemoveGraph(name) => graphs.remove(&name),
+                    LogicMessage::ToGraph(name, message) => graphs.send(&name, *message),
+                    LogicMessage::SetStepMode(enabled) => {
+                        if step_mode.enabled() != enabled {
+                            tracing::info!(enabled, "step mode");
+                        }
+                        step_mode.set_enabled(enabled);
+                    }
+                    LogicMessage::Step(ticks) => step_mode.request(ticks),
+                    LogicMessage::RetryOpen(plugin_id) => match plugin_instances.get_mut(&plugin_id) {
+                        Some(RuntimePlugin::CanIo(p)) => p.retry_open(),
+                        Some(RuntimePlugin::ModbusIo(p)) => p.retry_open(),
+                        #[cfg(feature = "comedi")]
+                        Some(RuntimePlugin::ComediDaq(p)) => p.retry_open(),
+                        #[cfg(feature = "rpi")]
+                        Some(RuntimePlugin::Gpio(p)) => p.retry_open(),
+                        _ => {}
+                    },
+                    LogicMessage::RunSelfTest(plugin_id, response_tx) => {
+                        let result = match plugin_instances.get_mut(&plugin_id) {
+                            Some(instance) => run_self_test(instance),
+                            None => Err(format!("plugin {plugin_id} is not loaded")),
+                        };
+                        let _ = response_tx.send(result);
+                    }
+                    LogicMessage::QueryPluginBehavior(kind, library_path, response_tx) => {
+                        let behavior = match kind.as_str() {
+                            "csv_recorder" => Some(CsvRecorderedPlugin::new(0).behavior()),
+                            "live_plotter" => Some(LivePlotterPlugin::new(0).behavior()),
+                            "performance_monitor" => Some(PerformanceMonitorPlugin::new(0).behavior()),
+                            "bang_bang" => Some(BangBangPlugin::new(0).behavior()),
+                            "noise_generator" => Some(NoiseGeneratorPlugin::new(0).behavior()),
+                            "integrator" => Some(IntegratorPlugin::new(0).behavior()),
+                            "differentiator" => Some(DifferentiatorPlugin::new(0).behavior()),
+                            "delay" => Some(DelayPlugin::new(0).behavior()),
+                            "alarm" => Some(AlarmPlugin::new(0).behavior()),
+                            "sample_hold" => Some(SampleHoldPlugin::new(0).behavior()),
+                            "scale" => Some(ScalePlugin::new(0).behavior()),
+                            "can_io" => Some(CanIoPlugin::new(0).behavior()),
+                            "stimulus" => Some(StimulusPlugin::new(0).behavior()),
+                            "saturation" => Some(SaturationPlugin::new(0).behavior()),
+                            "histogram" => Some(HistogramPlugin::new(0).behavior()),
+                            "tcp_stream" => Some(TcpStreamPlugin::new(0).behavior()),
+                            "shm_publisher" => Some(ShmPublisherPlugin::new(0).behavior()),
+                            "rms" => Some(RmsPlugin::new(0).behavior()),
+                            "peak_hold" => Some(PeakHoldPlugin::new(0).behavior()),
+                            "timer" => Some(TimerPlugin::new(0).behavior()),
+                            "setpoint_ramp" => Some(SetpointRampPlugin::new(0).behavior()),
+                            "lookup_table" => Some(LookupTablePlugin::new(0).behavior()),
+                            "stats" => Some(StatsPlugin::new(0).behavior()),
+                            "resampler" => Some(ResamplerPlugin::new(0).behavior()),
+                            "pwm" => Some(PwmPlugin::new(0).behavior()),
+                            "estop" => Some(EstopPlugin::new(0).behavior()),
+                            "audio_out" => Some(AudioOutPlugin::new(0).behavior()),
+                            "frequency_counter" => Some(FrequencyCounterPlugin::new(0).behavior()),
+                            "modbus_io" => Some(ModbusIoPlugin::new(0).behavior()),
+                            "state_machine" => Some(StateMachinePlugin::new(0).behavior()),
+                            "fault_recorder" => Some(FaultRecorderPlugin::new(0).behavior()),
+                            "filtered_derivative" => Some(FilteredDerivativePlugin::new(0).behavior()),
+                            "bit_pack" => Some(BitPackPlugin::new(0).behavior()),
+                            "bit_unpack" => Some(BitUnpackPlugin::new(0).behavior()),
+                            "transfer_function" => Some(TransferFunctionPlugin::new(0).behavior()),
+                            "error_metrics" => Some(ErrorMetricsPlugin::new(0).behavior()),
+                            #[cfg(feature = "comedi")]
+                            "comedi_daq" => Some(comedi_daq_plugin::ComediDaqPlugin::new(0).behavior()),
+                            #[cfg(feature = "rpi")]
+                            "gpio" => Some(gpio_plugin::GpioPlugin::new(0).behavior()),
+
+                            _ => {
+                                // Try to load behavior from dynamic plugin
+                                if let Some(path) = library_path.as_ref() {
+                                    if let Some(dynamic) = unsafe { DynamicPluginInstance::load(path, 0) } {
+                                        if let Some(behavior_json_fn) = unsafe { (*dynamic.api).behavior_json } {
+                                            let json_str = behavior_json_fn(dynamic.handle);
+                                            if !json_str.ptr.is_null() && json_str.len > 0 {
+                                                let json = unsafe { json_str.into_string() };
+                                                if let Ok(behavior) = serde_json::from_str(&json) {
+                                                    unsafe { ((*dynamic.api).destroy)(dynamic.handle); }
+                                                    let _ = response_tx.send(Some(behavior));
+                                                    continue;
+                                                }
+                                            }
+                                            unsafe { ((*dynamic.api).destroy)(dynamic.handle); }
+                                        } else {
+                                            unsafe { ((*dynamic.api).destroy)(dynamic.handle); }
+                                        }
+                                    }
+                                }
+                                None
+                            }
+                        };
+                        let _ = response_tx.send(behavior);
+                    }
+                    LogicMessage::QueryPluginMetadata(library_path, response_tx) => {
+                        let metadata = if let Some(dynamic) = unsafe { DynamicPluginInstance::load(&library_path, 0) } {
+                            let inputs_str = unsafe { ((*dynamic.api).inputs_json)(dynamic.handle) };
+                            let outputs_str = unsafe { ((*dynamic.api).outputs_json)(dynamic.handle) };
+                            let meta_str = unsafe { ((*dynamic.api).meta_json)(dynamic.handle) };
+                            let inputs: Vec<String> = if inputs_str.ptr.is_null() || inputs_str.len == 0 {
+                                Vec::new()
+                            } else {
+                                let json = unsafe { inputs_str.into_string() };
+                                serde_json::from_str(&json).unwrap_or_default()
+                            };
+                            let outputs: Vec<String> = if outputs_str.ptr.is_null() || outputs_str.len == 0 {
+                                Vec::new()
+                            } else {
+                                let json = unsafe { outputs_str.into_string() };
+                                serde_json::from_str(&json).unwrap_or_default()
+                            };
+                            let meta: serde_json::Value = if meta_str.ptr.is_null() || meta_str.len == 0 {
+                                serde_json::json!({})
+                            } else {
+                                let json = unsafe { meta_str.into_string() };
+                                serde_json::from_str(&json).unwrap_or(serde_json::json!({}))
+                            };
+                            let variables: Vec<(String, f64)> = meta.get("default_vars")
+                                .and_then(|v| v.as_array())
+                                .map(|arr| {
+                                    arr.iter().filter_map(|item| {
+                                        if let Some(arr) = item.as_array() {
+                                            if arr.len() == 2 {
+                                                let name = arr[0].as_str()?.to_string();
+                                                let value = arr[1].as_f64()?;
+                                                return Some((name, value));
+                                            }
+                                        }
+                                        None
+                                    }).collect()
+                                })
+                                .unwrap_or_default();
+                            let display_schema = if let Some(schema_fn) = unsafe { (*dynamic.api).display_schema_json } {
+                                let schema_str = schema_fn(dynamic.handle);
+                                if schema_str.ptr.is_null() || schema_str.len == 0 {
+                                    None
+                                } else {
+                                    let json = unsafe { schema_str.into_string() };
+                                    serde_json::from_str(&json).ok()
+                                }
+                            } else {
+                                None
+                            };
+                            let ui_schema: Option<rtsyn_plugin::ui::UISchema> = if let Some(ui_schema_fn) = unsafe { (*dynamic.api).ui_schema_json } {
+                                let schema_str = ui_schema_fn(dynamic.handle);
+                                if schema_str.ptr.is_null() || schema_str.len == 0 {
+                                    None
+                                } else {
+                                    let json = unsafe { schema_str.into_string() };
+                                    serde_json::from_str(&json).ok()
+                                }
+                            } else {
+                                None
+                            };
+                            unsafe { ((*dynamic.api).destroy)(dynamic.handle); }
+                            Some((inputs, outputs, variables, display_schema, ui_schema))
+                        } else {
+                            None
+                        };
+                        let _ = response_tx.send(metadata);
+                    }
+                    LogicMessage::RestartPlugin(plugin_id) => {
+                        tracing::debug!(plugin = plugin_id, "restarting plugin");
+                        let Some(ws) = workspace.as_ref() else {
+                            continue;
+                        };
+                        let Some(plugin) = ws.plugins.iter().find(|p| p.id == plugin_id) else {
+                            continue;
+                        };
+                        let instance = match instantiate_plugin(plugin) {
+                            Ok(instance) => instance,
+                            Err(err) => {
+                                tracing::warn!(plugin = plugin_id, %err, "plugin restart failed");
+                                load_errors.insert(plugin_id, err);
+                                continue;
+                            }
+                        };
+                        load_errors.remove(&plugin_id);
+                        if let Some(RuntimePlugin::Dynamic(dynamic)) = plugin_instances.insert(plugin_id, instance) {
+                            (unsafe { &*dynamic.api }.destroy)(dynamic.handle);
+                        }
+                        clear_plugin_state(
+                            plugin_id,
+                            &mut tracking,
+                            &mut outputs,
+                            &mut input_values,
+                            &mut internal_variable_values,
+                            &mut viewer_values,
+                            &mut plotter_samples,
+                        );
+                    }
+                    LogicMessage::GetPluginVariable(plugin_id, var_name, response_tx) => {
+                        let value = plugin_instances.get(&plugin_id).and_then(|instance| {
+                            match instance {
+                                RuntimePlugin::CsvRecorder(p) => p.get_variable(&var_name),
+                                RuntimePlugin::LivePlotter(p) => p.get_variable(&var_name),
+                                RuntimePlugin::PerformanceMonitor(p) => p.get_variable(&var_name),
+                                RuntimePlugin::BangBang(p) => p.get_variable(&var_name),
+                                RuntimePlugin::NoiseGenerator(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Integrator(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Differentiator(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Delay(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Alarm(p) => p.get_variable(&var_name),
+                                RuntimePlugin::SampleHold(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Scale(p) => p.get_variable(&var_name),
+                                RuntimePlugin::CanIo(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Stimulus(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Saturation(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Histogram(p) => p.get_variable(&var_name),
+                                RuntimePlugin::TcpStream(p) => p.get_variable(&var_name),
+                                RuntimePlugin::ShmPublisher(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Rms(p) => p.get_variable(&var_name),
+                                RuntimePlugin::PeakHold(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Timer(p) => p.get_variable(&var_name),
+                                RuntimePlugin::SetpointRamp(p) => p.get_variable(&var_name),
+                                RuntimePlugin::LookupTable(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Stats(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Resampler(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Pwm(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Estop(p) => p.get_variable(&var_name),
+                                RuntimePlugin::AudioOut(p) => p.get_variable(&var_name),
+                                RuntimePlugin::FrequencyCounter(p) => p.get_variable(&var_name),
+                                RuntimePlugin::ModbusIo(p) => p.get_variable(&var_name),
+                                RuntimePlugin::StateMachine(p) => p.get_variable(&var_name),
+                                RuntimePlugin::FaultRecorder(p) => p.get_variable(&var_name),
+                                RuntimePlugin::FilteredDerivative(p) => p.get_variable(&var_name),
+                                RuntimePlugin::BitPack(p) => p.get_variable(&var_name),
+                                RuntimePlugin::BitUnpack(p) => p.get_variable(&var_name),
+                                RuntimePlugin::TransferFunction(p) => p.get_variable(&var_name),
+                                RuntimePlugin::ErrorMetrics(p) => p.get_variable(&var_name),
+                                #[cfg(feature = "comedi")]
+                                RuntimePlugin::ComediDaq(p) => p.get_variable(&var_name),
+                                #[cfg(feature = "rpi")]
+                                RuntimePlugin::Gpio(p) => p.get_variable(&var_name),
+                                RuntimePlugin::Dynamic(_) => None,
+                            }
+                        });
+                        let _ = response_tx.send(value);
+                    }
+                    LogicMessage::QueryPluginStates(response_tx) => {
+                        let states = plugin_run_states(
+                            workspace.as_ref(),
+                            &plugin_instances,
+                            &plugin_running,
+                            step_mode.enabled(),
+                        );
+                        let _ = response_tx.send(states);
+                    }
+                    LogicMessage::TrackConnectionLatency(enabled) => {
+                        tracking.latency.set_enabled(enabled);
+                    }
+                    LogicMessage::QueryConnectionLatency(response_tx) => {
+                        let _ = response_tx.send(tracking.latency.stats());
+                    }
+                    LogicMessage::SetPluginVariable(plugin_id, var_name, value) => {
+                        if let Some(instance) = plugin_instances.get_mut(&plugin_id) {
+                            set_runtime_variable(instance, &var_name, value);
+                        }
+                    }
+                    LogicMessage::SetPluginVariables(plugin_id, changes) => {
+                        if let Some(instance) = plugin_instances.get_mut(&plugin_id) {
+                            for (var_name, value) in changes {
+                                set_runtime_variable(instance, &var_name, value);
+                            }
+                        }
+                    }
+                },
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    disconnected = true;
+                    break;
+                }
+            }
+        }
+        if disconnected {
+            break;
+        }
+        let run_tick = step_mode.take_tick();
+
+        if let Some(ws) = workspace.as_ref() {
+            let _tick_span = tracing::trace_span!("tick", tick = plugin_ctx.tick).entered();
+            let mut estop_seen = false;
+            // Paused in step mode: no plugin runs, but the state is still sent
+            let due: &[PluginDefinition] = if run_tick { &execution_order } else { &[] };
+            for plugin in due {
+                let is_running = plugin_running
+                    .get(&plugin.id)
+                    .copied()
+                    .unwrap_or(plugin.running)
+                    && (!estop_active || is_estop_essential(&plugin.kind));
+                let instance = match plugin_instances.get_mut(&plugin.id) {
+                    Some(instance) => instance,
+                    None => {
+                        tracing::trace!(plugin = plugin.id, "no runtime instance, skipping");
+                        continue;
+                    }
+                };
+                let mut queued_inputs = Vec::new();
+                if is_running {
+                    tracking.latency.inputs_read(&ws.connections, plugin.id);
+                    queued_inputs = tracking.queues.inputs_read(&ws.connections, &mut outputs, plugin.id);
+                }
+                let _process_span =
+                    tracing::trace_span!("process", plugin = plugin.id, kind = %plugin.kind).entered();
+                match instance {
+                    RuntimePlugin::Dynamic(plugin_instance) => {
+                        let api = unsafe { &*plugin_instance.api };
+                        if let Value::Object(map) = plugin.config.clone() {
+                            let mut map = map;
+                            map.insert(
+                                "period_seconds".to_string(),
+                                Value::from(settings.period_seconds),
+                            );
+                            map.insert(
+                                "max_integration_steps".to_string(),
+                                Value::from(settings.max_integration_steps as f64),
+                            );
+                            let json = Value::Object(map).to_string();
+                            if plugin_instance
+                                .last_config
+                                .as_ref()
+                                .map(|last| last != &json)
+                                .unwrap_or(true)
+                            {
+                                (api.set_config_json)(
+                                    plugin_instance.handle,
+                                    json.as_bytes().as_ptr(),
+                                    json.as_bytes().len(),
+                                );
+                                plugin_instance.last_config = Some(json);
+                            }
+                        }
+                        for (idx, input_name) in plugin_instance.inputs.iter().enumerate() {
+                            let value =
+                                input_value(&ws.connections, &outputs, plugin, input_name);
+                            input_values.insert((plugin.id, input_name.clone()), value);
+                            let bits = value.to_bits();
+                            if plugin_instance.last_inputs[idx] != bits {
+                                plugin_instance.last_inputs[idx] = bits;
+                                let bytes = &plugin_instance.input_bytes[idx];
+                                (api.set_input)(
+                                    plugin_instance.handle,
+                                    bytes.as_ptr(),
+                                    bytes.len(),
+                                    value,
+                                );
+                            }
+                        }
+                        if is_running {
+                            (api.process)(
+                                plugin_instance.handle,
+                                plugin_ctx.tick,
+                                plugin_ctx.period_seconds,
+                            );
+                            for (idx, output_name) in plugin_instance.outputs.iter().enumerate() {
+                                let bytes = &plugin_instance.output_bytes[idx];
+                                let value = (api.get_output)(
+                                    plugin_instance.handle,
+                                    bytes.as_ptr(),
+                                    bytes.len(),
+                                );
+                                outputs.insert((plugin.id, output_name.clone()), value);
+                            }
+                        }
+                        for (idx, var_name) in plugin_instance.internal_variables.iter().enumerate() {
+                            let bytes = &plugin_instance.internal_variable_bytes[idx];
+                            let value = (api.get_output)(
+                                plugin_instance.handle,
+                                bytes.as_ptr(),
+                                bytes.len(),
+                            );
+                            internal_variable_values.insert(
+                                (plugin.id, var_name.clone()),
+                                serde_json::Value::from(value),
+                            );
+                        }
+                    }
+                    RuntimePlugin::CsvRecorder(plugin_instance) => {
+                        let config_input_count = config::u64_or(&plugin.config, "input_count", 0) as usize;
+                        let separator = plugin
+                            .config
+                            .get("separator")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or(",");
+                        let path = plugin
+                            .config
+                            .get("path")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("");
+                        let include_time = plugin
+                            .config
+                            .get("include_time")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(true);
+                        let write_mode = plugin
+                            .config
+                            .get("write_mode")
+                            .and_then(|v| v.as_str())
+                            .map(CsvWriteMode::from_name)
+                            .unwrap_or_default();
+                        let rotate_mb = config::f64_or(&plugin.config, "rotate_mb", 0.0);
+                        let rotate_minutes = config::f64_or(&plugin.config, "rotate_minutes", 0.0);
+                        let mut columns: Vec<String> = plugin
+                            .config
+                            .get("columns")
+                            .and_then(|v| v.as_array())
+                            .map(|arr| {
+                                arr.iter()
+                                    .map(|value| value.as_str().unwrap_or("").to_string())
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+                        let mut input_count = columns.len();
+                        if input_count < config_input_count {
+                            columns.resize(config_input_count, String::new());
+                            input_count = config_input_count;
+                        }
+                        for idx in 0..input_count {
+                            if columns.get(idx).map(|v| v.is_empty()).unwrap_or(true) {
+                                columns[idx] = "empty".to_string();
+                            }
+                        }
+                        let mut inputs = Vec::with_capacity(input_count);
+                        for idx in 0..input_count {
+                            let port = format!("in_{idx}");
+                            let value = if idx == 0 {
+                                let mut ports = vec![port.clone()];
+                                ports.push("in".to_string());
+                                input_value_any(&ws.connections, &outputs, plugin, &ports)
+                            } else {
+                                input_value(&ws.connections, &outputs, plugin, &port)
+                            };
+                            input_values.insert((plugin.id, port), value);
+                            inputs.push(value);
+                        }
+                        plugin_instance.set_write_mode(write_mode);
+                        let wall_time = plugin
+                            .config
+                            .get("include_wall_time")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false)
+                            .then(|| {
+                                plugin
+                                    .config
+                                    .get("wall_time_format")
+                                    .and_then(|v| v.as_str())
+                                    .map(CsvWallTimeFormat::from_name)
+                                    .unwrap_or_default()
+                            });
+                        plugin_instance.set_wall_time(wall_time);
+                        plugin_instance.set_rotation(rotate_mb, rotate_minutes);
+                        plugin_instance.set_config(
+                            input_count,
+                            separator.to_string(),
+                            columns,
+                            normalize_path(path),
+                            is_running,
+                            include_time,
+                            settings.time_scale,
+                            settings.time_label.clone(),
+                            settings.period_seconds,
+                        );
+                        internal_variable_values.insert(
+                            (plugin.id, "input_count".to_string()),
+                            serde_json::Value::from(input_count as i64),
+                        );
+                        internal_variable_values.insert(
+                            (plugin.id, "running".to_string()),
+                            serde_json::Value::from(is_running),
+                        );
+                        plugin_instance.set_inputs(inputs);
+                        let skip_warmup = plugin
+                            .config
+                            .get("skip_warmup")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false);
+                        // Rows start once every connected source has warmed up
+                        let sources_warming_up = skip_warmup
+                            && ws
+                                .connections
+                                .iter()
+                                .any(|conn| conn.to_plugin == plugin.id && tracking.warmup.is_warming_up(conn.from_plugin));
+                        if !sources_warming_up {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        internal_variable_values.insert(
+                            (plugin.id, "error".to_string()),
+                            serde_json::Value::from(plugin_instance.last_error().unwrap_or_default()),
+                        );
+                    }
+                    #[cfg(feature = "comedi")]
+                    RuntimePlugin::ComediDaq(plugin_instance) => {
+                        let device_path = plugin
+                            .config
+                            .get("device_path")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("/dev/comedi0");
+                        let scan_devices = plugin
+                            .config
+                            .get("scan_devices")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false);
+                        let scan_nonce = config::u64_or(&plugin.config, "scan_nonce", 0);
+
+                        let mut active_inputs: HashSet<String> = HashSet::new();
+                        let mut active_outputs: HashSet<String> = HashSet::new();
+                        for conn in &ws.connections {
+                            if conn.to_plugin == plugin.id {
+                                active_inputs.insert(conn.to_port.clone());
+                            }
+                            if conn.from_plugin == plugin.id {
+                                active_outputs.insert(conn.from_port.clone());
+                            }
+                        }
+
+                        plugin_instance.set_active_ports(&active_inputs, &active_outputs);
+                        plugin_instance.set_config(
+                            device_path.to_string(),
+                            scan_devices,
+                            scan_nonce,
+                        );
+
+                        let has_active = !active_inputs.is_empty() || !active_outputs.is_empty();
+                        if has_active && !plugin_instance.is_open() && plugin_instance.can_retry_open() {
+                            let _ = plugin_instance.open();
+                        } else if !has_active && plugin_instance.is_open() {
+                            let _ = plugin_instance.close();
+                        }
+                        track_open_error(&mut open_errors, plugin.id, plugin_instance.open_error());
+
+                        let input_ports: Vec<String> =
+                            plugin_instance.input_port_names().iter().cloned().collect();
+                        for port in input_ports {
+                            let value = input_value(&ws.connections, &outputs, plugin, &port);
+                            input_values.insert((plugin.id, port.clone()), value);
+                            plugin_instance.set_input(&port, value);
+                        }
+                        plugin_instance.set_safe_value(
+                            config::f64_or(&plugin.config, "safe_value", 0.0),
+                        );
+                        if estop_active {
+                            plugin_instance.safe_state();
+                        }
+
+                        let _ = plugin_instance.process(&mut plugin_ctx);
+
+                        let output_ports: Vec<String> = plugin_instance
+                            .output_port_names()
+                            .iter()
+                            .cloned()
+                            .collect();
+                        for port in output_ports {
+                            let value = plugin_instance.get_output(&port);
+                            outputs.insert((plugin.id, port), value);
+                        }
+                    }
+                    RuntimePlugin::LivePlotter(plugin_instance) => {
+                        let config_input_count = config::u64_or(&plugin.config, "input_count", 0) as usize;
+                        let input_count = config_input_count;
+                        plugin_instance.set_config(input_count, is_running);
+                        internal_variable_values.insert(
+                            (plugin.id, "input_count".to_string()),
+                            serde_json::Value::from(input_count as i64),
+                        );
+                        internal_variable_values.insert(
+                            (plugin.id, "running".to_string()),
+                            serde_json::Value::from(is_running),
+                        );
+                        let mut inputs = Vec::with_capacity(input_count);
+                        for idx in 0..input_count {
+                            let port = format!("in_{idx}");
+                            let value = if idx == 0 {
+                                let mut ports = vec![port.clone()];
+                                ports.push("in".to_string());
+                                input_value_any(&ws.connections, &outputs, plugin, &ports)
+                            } else {
+                                input_value(&ws.connections, &outputs, plugin, &port)
+                            };
+                            input_values.insert((plugin.id, port), value);
+                            inputs.push(value);
+                        }
+                        plugin_instance.set_inputs(inputs.clone());
+                        if plugin_instance.is_running() {
+                            plotter_samples
+                                .entry(plugin.id)
+                                .or_default()
+                                .push((plugin_ctx.tick, inputs));
+                        }
+                    }
+                    RuntimePlugin::PerformanceMonitor(plugin_instance) => {
+                        let max_latency_us = config::f64_or(&plugin.config, "max_latency_us", 1000.0);
+
+                        let workspace_period_us = settings.period_seconds * 1_000_000.0;
+                        plugin_instance.set_config(max_latency_us, workspace_period_us);
+                        let _ = plugin_instance.process(&mut plugin_ctx);
+
+                        // Output the performance values, including the tick they were taken on
+                        for (port, value) in plugin_instance
+                            .outputs()
+                            .iter()
+                            .zip(plugin_instance.get_output_values())
+                        {
+                            outputs.insert((plugin.id, port.id.0.clone()), *value);
+                        }
+                    }
+                    RuntimePlugin::BangBang(plugin_instance) => {
+                        let hysteresis = config::f64_or(&plugin.config, "hysteresis", 0.5);
+                        let output_high = config::f64_or(&plugin.config, "output_high", 1.0);
+                        let output_low = config::f64_or(&plugin.config, "output_low", 0.0);
+                        plugin_instance.set_config(hysteresis, output_high, output_low);
+
+                        let setpoint = input_value(&ws.connections, &outputs, plugin, "setpoint");
+                        let process_variable =
+                            input_value(&ws.connections, &outputs, plugin, "process_variable");
+                        input_values.insert((plugin.id, "setpoint".to_string()), setpoint);
+                        input_values.insert((plugin.id, "process_variable".to_string()), process_variable);
+                        plugin_instance.set_inputs(setpoint, process_variable);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "control".to_string()), plugin_instance.control());
+                    }
+                    RuntimePlugin::NoiseGenerator(plugin_instance) => {
+                        let distribution = plugin
+                            .config
+                            .get("distribution")
+                            .and_then(|v| v.as_str())
+                            .map(Distribution::from_name)
+                            .unwrap_or(Distribution::Uniform);
+                        let amplitude = config::f64_or(&plugin.config, "amplitude", 1.0);
+                        let mean = config::f64_or(&plugin.config, "mean", 0.0);
+                        let seed = config::u64_or(&plugin.config, "seed", 0);
+                        plugin_instance.set_config(distribution, amplitude, mean, seed);
+
+                        let input = input_value(&ws.connections, &outputs, plugin, "in");
+                        input_values.insert((plugin.id, "in".to_string()), input);
+                        plugin_instance.set_input(input);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                    }
+                    RuntimePlugin::Integrator(plugin_instance) => {
+                        let gain = config::f64_or(&plugin.config, "gain", 1.0);
+                        let initial_value = config::f64_or(&plugin.config, "initial_value", 0.0);
+                        let clamp = plugin
+                            .config
+                            .get("clamp")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false);
+                        let min = config::f64_or(&plugin.config, "min", -1.0);
+                        let max = config::f64_or(&plugin.config, "max", 1.0);
+                        plugin_instance.set_config(gain, initial_value, clamp, min, max);
+
+                        let input = input_value(&ws.connections, &outputs, plugin, "in");
+                        input_values.insert((plugin.id, "in".to_string()), input);
+                        plugin_instance.set_input(input);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                    }
+                    RuntimePlugin::Differentiator(plugin_instance) => {
+                        let gain = config::f64_or(&plugin.config, "gain", 1.0);
+                        plugin_instance.set_config(gain);
+
+                        let input = input_value(&ws.connections, &outputs, plugin, "in");
+                        input_values.insert((plugin.id, "in".to_string()), input);
+                        plugin_instance.set_input(input);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                    }
+                    RuntimePlugin::Delay(plugin_instance) => {
+                        let delay_ms = config::f64_or(&plugin.config, "delay_ms", 100.0);
+                        let initial_value = config::f64_or(&plugin.config, "initial_value", 0.0);
+                        plugin_instance.set_config(delay_ms, initial_value, plugin_ctx.period_seconds);
+
+                        let input = input_value(&ws.connections, &outputs, plugin, "in");
+                        input_values.insert((plugin.id, "in".to_string()), input);
+                        plugin_instance.set_input(input);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                    }
+                    RuntimePlugin::Alarm(plugin_instance) => {
+                        let high_limit = config::f64_or(&plugin.config, "high_limit", 1.0);
+                        let low_limit = config::f64_or(&plugin.config, "low_limit", -1.0);
+                        let latching = plugin
+                            .config
+                            .get("latching")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false);
+                        plugin_instance.set_config(high_limit, low_limit, latching);
+
+                        let signal = input_value(&ws.connections, &outputs, plugin, "signal");
+                        let reset = input_value(&ws.connections, &outputs, plugin, "reset");
+                        input_values.insert((plugin.id, "signal".to_string()), signal);
+                        input_values.insert((plugin.id, "reset".to_string()), reset);
+                        plugin_instance.set_inputs(signal, reset);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                            queue_plugin_events(
+                                &mut plugin_events,
+                                plugin.id,
+                                plugin_ctx.tick,
+                                settings.period_seconds,
+                                plugin_instance.drain_events(),
+                            );
+                        }
+                        outputs.insert((plugin.id, "alarm".to_string()), plugin_instance.alarm());
+                    }
+                    RuntimePlugin::SampleHold(plugin_instance) => {
+                        let initial_value = config::f64_or(&plugin.config, "initial_value", 0.0);
+                        plugin_instance.set_config(initial_value);
+
+                        let input = input_value(&ws.connections, &outputs, plugin, "in");
+                        let trigger = input_value(&ws.connections, &outputs, plugin, "trigger");
+                        input_values.insert((plugin.id, "in".to_string()), input);
+                        input_values.insert((plugin.id, "trigger".to_string()), trigger);
+                        plugin_instance.set_inputs(input, trigger);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                    }
+                    RuntimePlugin::Scale(plugin_instance) => {
+                        let config_f64 = |key: &str, default: f64| {
+                            config::f64_or(&plugin.config, key, default)
+                        };
+                        let mode = plugin
+                            .config
+                            .get("mode")
+                            .and_then(|v| v.as_str())
+                            .map(ScaleMode::from_name)
+                            .unwrap_or(ScaleMode::GainOffset);
+                        plugin_instance.set_config(mode, config_f64("gain", 1.0), config_f64("offset", 0.0));
+                        plugin_instance.set_calibration(Calibration {
+                            in_low: config_f64("in_low", 0.0),
+                            out_low: config_f64("out_low", 0.0),
+                            in_high: config_f64("in_high", 1.0),
+                            out_high: config_f64("out_high", 1.0),
+                        });
+
+                        let input = input_value(&ws.connections, &outputs, plugin, "in");
+                        input_values.insert((plugin.id, "in".to_string()), input);
+                        plugin_instance.set_input(input);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                    }
+                    RuntimePlugin::CanIo(plugin_instance) => {
+                        let interface = plugin
+                            .config
+                            .get("interface")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("can0");
+                        let specs = |key: &str| -> Vec<String> {
+                            plugin
+                                .config
+                                .get(key)
+                                .and_then(|v| v.as_array())
+                                .map(|items| {
+                                    items
+                                        .iter()
+                                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                        .collect()
+                                })
+                                .unwrap_or_default()
+                        };
+                        plugin_instance.set_config(interface, &specs("tx_signals"), &specs("rx_signals"));
+
+                        if !is_running {
+                            if plugin_instance.is_open() || !plugin_instance.can_retry_open() {
+                                let _ = plugin_instance.close();
+                            }
+                        } else if !plugin_instance.is_open() && plugin_instance.can_retry_open() {
+                            let _ = plugin_instance.open();
+                        }
+                        track_open_error(&mut open_errors, plugin.id, plugin_instance.open_error());
+
+                        for port in plugin_instance.input_port_names() {
+                            let value = input_value(&ws.connections, &outputs, plugin, &port);
+                            input_values.insert((plugin.id, port.clone()), value);
+                            plugin_instance.set_input(&port, value);
+                        }
+                        plugin_instance.set_safe_value(
+                            config::f64_or(&plugin.config, "safe_value", 0.0),
+                        );
+                        if estop_active {
+                            plugin_instance.safe_state();
+                        }
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        for port in plugin_instance.output_port_names() {
+                            let value = plugin_instance.get_output(&port);
+                            outputs.insert((plugin.id, port), value);
+                        }
+                    }
+                    #[cfg(feature = "rpi")]
+                    RuntimePlugin::Gpio(plugin_instance) => {
+                        let chip = plugin
+                            .config
+                            .get("chip")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("/dev/gpiochip0");
+                        let specs = |key: &str| -> Vec<String> {
+                            plugin
+                                .config
+                                .get(key)
+                                .and_then(|v| v.as_array())
+                                .map(|items| {
+                                    items
+                                        .iter()
+                                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                        .collect()
+                                })
+                                .unwrap_or_default()
+                        };
+                        plugin_instance.set_config(chip, &specs("output_pins"), &specs("input_pins"));
+
+                        if !is_running {
+                            if plugin_instance.is_open() || !plugin_instance.can_retry_open() {
+                                let _ = plugin_instance.close();
+                            }
+                        } else if !plugin_instance.is_open() && plugin_instance.can_retry_open() {
+                            let _ = plugin_instance.open();
+                        }
+                        track_open_error(&mut open_errors, plugin.id, plugin_instance.open_error());
+
+                        for port in plugin_instance.input_port_names() {
+                            let value = input_value(&ws.connections, &outputs, plugin, &port);
+                            input_values.insert((plugin.id, port.clone()), value);
+                            plugin_instance.set_input(&port, value);
+                        }
+                        plugin_instance.set_safe_value(
+                            config::f64_or(&plugin.config, "safe_value", 0.0),
+                        );
+                        if estop_active {
+                            plugin_instance.safe_state();
+                        }
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        for port in plugin_instance.output_port_names() {
+                            let value = plugin_instance.get_output(&port);
+                            outputs.insert((plugin.id, port), value);
+                        }
+                    }
+                    RuntimePlugin::Stimulus(plugin_instance) => {
+                        let config_f64 = |key: &str, default: f64| {
+                            config::f64_or(&plugin.config, key, default)
+                        };
+                        let shape = plugin
+                            .config
+                            .get("shape")
+                            .and_then(|v| v.as_str())
+                            .map(Shape::from_name)
+                            .unwrap_or(Shape::Step);
+                        plugin_instance.set_config(
+                            shape,
+                            config_f64("start_time", 1.0),
+                            config_f64("amplitude", 1.0),
+                            config_f64("baseline", 0.0),
+                        );
+                        plugin_instance.set_chirp(Chirp {
+                            f_start: config_f64("f_start", 0.1),
+                            f_end: config_f64("f_end", 10.0),
+                            duration: config_f64("duration", 10.0),
+                        });
+
+                        let trigger = input_value(&ws.connections, &outputs, plugin, "trigger");
+                        input_values.insert((plugin.id, "trigger".to_string()), trigger);
+                        plugin_instance.set_trigger(trigger);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                    }
+                    RuntimePlugin::Saturation(plugin_instance) => {
+                        let min = config::f64_or(&plugin.config, "min", -1.0);
+                        let max = config::f64_or(&plugin.config, "max", 1.0);
+                        plugin_instance.set_config(min, max);
+
+                        let input = input_value(&ws.connections, &outputs, plugin, "in");
+                        input_values.insert((plugin.id, "in".to_string()), input);
+                        plugin_instance.set_input(input);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                        outputs.insert(
+                            (plugin.id, "out_of_range".to_string()),
+                            plugin_instance.out_of_range(),
+                        );
+                    }
+                    RuntimePlugin::Histogram(plugin_instance) => {
+                        let bins = config::u64_or(&plugin.config, "bins", 20);
+                        let range_min = config::f64_or(&plugin.config, "range_min", -1.0);
+                        let range_max = config::f64_or(&plugin.config, "range_max", 1.0);
+                        let window = config::u64_or(&plugin.config, "window", 1000);
+                        plugin_instance.set_config(bins as usize, range_min, range_max, window as usize);
+
+                        let input = input_value(&ws.connections, &outputs, plugin, "in");
+                        input_values.insert((plugin.id, "in".to_string()), input);
+                        plugin_instance.set_input(input);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        internal_variable_values.insert(
+                            (plugin.id, "counts".to_string()),
+                            serde_json::Value::from(plugin_instance.counts().to_vec()),
+                        );
+                        outputs.insert((plugin.id, "count_total".to_string()), plugin_instance.count_total());
+                        outputs.insert((plugin.id, "mode_bin".to_string()), plugin_instance.mode_bin());
+                    }
+                    RuntimePlugin::TcpStream(plugin_instance) => {
+                        let input_count = config::u64_or(&plugin.config, "input_count", 0) as usize;
+                        let port = u16::try_from(config::u64_or(&plugin.config, "port", 9000)).unwrap_or(9000);
+                        let format = plugin
+                            .config
+                            .get("format")
+                            .and_then(|v| v.as_str())
+                            .map(TcpStreamFormat::from_name)
+                            .unwrap_or(TcpStreamFormat::Json);
+                        let rate_hz = config::f64_or(&plugin.config, "rate_hz", 10.0);
+                        plugin_instance.set_config(input_count, port, format, rate_hz, is_running);
+
+                        let mut inputs = Vec::with_capacity(input_count);
+                        for idx in 0..input_count {
+                            let port = format!("in_{idx}");
+                            let value = if idx == 0 {
+                                let mut ports = vec![port.clone()];
+                                ports.push("in".to_string());
+                                input_value_any(&ws.connections, &outputs, plugin, &ports)
+                            } else {
+                                input_value(&ws.connections, &outputs, plugin, &port)
+                            };
+                            input_values.insert((plugin.id, port), value);
+                            inputs.push(value);
+                        }
+                        plugin_instance.set_inputs(inputs);
+                        internal_variable_values.insert(
+                            (plugin.id, "input_count".to_string()),
+                            serde_json::Value::from(input_count as i64),
+                        );
+                        internal_variable_values.insert(
+                            (plugin.id, "clients".to_string()),
+                            serde_json::Value::from(plugin_instance.client_count() as i64),
+                        );
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                    }
+                    RuntimePlugin::ShmPublisher(plugin_instance) => {
+                        let input_count = config::u64_or(&plugin.config, "input_count", 0) as usize;
+                        let name = plugin
+                            .config
+                            .get("name")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("rtsyn");
+                        plugin_instance.set_config(input_count, name.to_string(), is_running);
+
+                        let mut inputs = Vec::with_capacity(input_count);
+                        for idx in 0..input_count {
+                            let port = format!("in_{idx}");
+                            let value = if idx == 0 {
+                                let mut ports = vec![port.clone()];
+                                ports.push("in".to_string());
+                                input_value_any(&ws.connections, &outputs, plugin, &ports)
+                            } else {
+                                input_value(&ws.connections, &outputs, plugin, &port)
+                            };
+                            input_values.insert((plugin.id, port), value);
+                            inputs.push(value);
+                        }
+                        plugin_instance.set_inputs(inputs);
+                        internal_variable_values.insert(
+                            (plugin.id, "input_count".to_string()),
+                            serde_json::Value::from(input_count as i64),
+                        );
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                    }
+                    RuntimePlugin::Rms(plugin_instance) => {
+                        let window_ms = config::f64_or(&plugin.config, "window_ms", 100.0);
+                        plugin_instance.set_config(window_ms, plugin_ctx.period_seconds);
+
+                        let input = input_value(&ws.connections, &outputs, plugin, "in");
+                        input_values.insert((plugin.id, "in".to_string()), input);
+                        plugin_instance.set_input(input);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "rms".to_string()), plugin_instance.rms());
+                        outputs.insert((plugin.id, "peak".to_string()), plugin_instance.peak());
+                        outputs.insert(
+                            (plugin.id, "crest_factor".to_string()),
+                            plugin_instance.crest_factor(),
+                        );
+                    }
+                    RuntimePlugin::PeakHold(plugin_instance) => {
+                        let decay_rate = config::f64_or(&plugin.config, "decay_rate", 0.0);
+                        plugin_instance.set_config(decay_rate);
+
+                        let input = input_value(&ws.connections, &outputs, plugin, "in");
+                        let reset = input_value(&ws.connections, &outputs, plugin, "reset");
+                        input_values.insert((plugin.id, "in".to_string()), input);
+                        input_values.insert((plugin.id, "reset".to_string()), reset);
+                        plugin_instance.set_inputs(input, reset);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "max_hold".to_string()), plugin_instance.max_hold());
+                        outputs.insert((plugin.id, "min_hold".to_string()), plugin_instance.min_hold());
+                    }
+                    RuntimePlugin::Timer(plugin_instance) => {
+                        let duration_ms = config::f64_or(&plugin.config, "duration_ms", 1000.0);
+                        let mode = plugin
                             .config
-                            .get("separator")
+                            .get("mode")
                             .and_then(|v| v.as_str())
-                            .unwrap_or(",");
-                        let path = plugin
+                            .map(TimerMode::from_name)
+                            .unwrap_or(TimerMode::OneShot);
+                        plugin_instance.set_config(duration_ms, mode);
+
+                        let trigger = input_value(&ws.connections, &outputs, plugin, "trigger");
+                        input_values.insert((plugin.id, "trigger".to_string()), trigger);
+                        plugin_instance.set_input(trigger);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "active".to_string()), plugin_instance.active());
+                    }
+                    RuntimePlugin::SetpointRamp(plugin_instance) => {
+                        let ramp_rate = config::f64_or(&plugin.config, "ramp_rate", 1.0);
+                        plugin_instance.set_config(ramp_rate);
+
+                        let target = input_value(&ws.connections, &outputs, plugin, "target");
+                        input_values.insert((plugin.id, "target".to_string()), target);
+                        // An unconnected enable input leaves the ramp running
+                        let enable_connected = ws
+                            .connections
+                            .iter()
+                            .any(|conn| conn.to_plugin == plugin.id && conn.to_port == "enable");
+                        let enabled = if enable_connected {
+                            let enable = input_value(&ws.connections, &outputs, plugin, "enable");
+                            input_values.insert((plugin.id, "enable".to_string()), enable);
+                            enable > 0.5
+                        } else {
+                            true
+                        };
+                        plugin_instance.set_inputs(target, enabled);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                    }
+                    RuntimePlugin::LookupTable(plugin_instance) => {
+                        let points = |key: &str| config::f64_array_or(&plugin.config, key, &[]);
+                        let interpolation = plugin
                             .config
-                            .get("path")
+                            .get("interpolation")
                             .and_then(|v| v.as_str())
-                            .unwrap_or("");
-                        let include_time = plugin
+                            .map(Interpolation::from_name)
+                            .unwrap_or_default();
+                        let extrapolate = plugin
                             .config
-                            .get("include_time")
+                            .get("extrapolate")
                             .and_then(|v| v.as_bool())
-                            .unwrap_or(true);
-                        let mut columns: Vec<String> = plugin
-                            .config
-                            .get("columns")
-                            .and_then(|v| v.as_array())
-                            .map(|arr| {
-                                arr.iter()
-                                    .map(|value| value.as_str().unwrap_or("").to_string())
-                                    .collect()
-                            })
-                            .unwrap_or_default();
-                        let mut input_count = columns.len();
-                        if input_count < config_input_count {
-                            columns.resize(config_input_count, String::new());
-                            input_count = config_input_count;
-                        }
-                        for idx in 0..input_count {
-                            if columns.get(idx).map(|v| v.is_empty()).unwrap_or(true) {
-                                columns[idx] = "empty".to_string();
-                            }
+                            .unwrap_or(false);
+                        plugin_instance.set_config(&points("x_points"), &points("y_points"), interpolation, extrapolate);
+
+                        let input = input_value(&ws.connections, &outputs, plugin, "in");
+                        input_values.insert((plugin.id, "in".to_string()), input);
+                        plugin_instance.set_input(input);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
                         }
+                        outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                    }
+                    RuntimePlugin::Stats(plugin_instance) => {
+                        let input_count = config::u64_or(&plugin.config, "input_count", 0) as usize;
+                        plugin_instance.set_config(input_count);
+
                         let mut inputs = Vec::with_capacity(input_count);
                         for idx in 0..input_count {
                             let port = format!("in_{idx}");
                             let value = if idx == 0 {
                                 let mut ports = vec![port.clone()];
                                 ports.push("in".to_string());
-                                input_sum_any(&ws.connections, &outputs, plugin.id, &ports)
+                                input_value_any(&ws.connections, &outputs, plugin, &ports)
                             } else {
-                                input_sum(&ws.connections, &outputs, plugin.id, &port)
+                                input_value(&ws.connections, &outputs, plugin, &port)
                             };
                             input_values.insert((plugin.id, port), value);
                             inputs.push(value);
                         }
-                        plugin_instance.set_config(
-                            input_count,
-                            separator.to_string(),
-                            columns,
-                            normalize_path(path),
-                            is_running,
-                            include_time,
-                            settings.time_scale,
-                            settings.time_label.clone(),
-                            settings.period_seconds,
-                        );
+                        let reset = input_value(&ws.connections, &outputs, plugin, "reset");
+                        input_values.insert((plugin.id, "reset".to_string()), reset);
+                        plugin_instance.set_inputs(inputs, reset);
                         internal_variable_values.insert(
                             (plugin.id, "input_count".to_string()),
                             serde_json::Value::from(input_count as i64),
                         );
-                        internal_variable_values.insert(
-                            (plugin.id, "running".to_string()),
-                            serde_json::Value::from(is_running),
-                        );
-                        plugin_instance.set_inputs(inputs);
-                        let _ = plugin_instance.process(&mut plugin_ctx);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        for name in plugin_instance.variable_names() {
+                            if let Some(value) = plugin_instance.get_variable(&name) {
+                                internal_variable_values.insert((plugin.id, name), value);
+                            }
+                        }
                     }
-                    #[cfg(feature = "comedi")]
-                    RuntimePlugin::ComediDaq(plugin_instance) => {
-                        let device_path = plugin
+                    RuntimePlugin::Resampler(plugin_instance) => {
+                        let rate = |key: &str, default: f64| -> f64 {
+                            config::f64_or(&plugin.config, key, default)
+                        };
+                        let interpolation = plugin
                             .config
-                            .get("device_path")
+                            .get("interpolation")
                             .and_then(|v| v.as_str())
-                            .unwrap_or("/dev/comedi0");
-                        let scan_devices = plugin
+                            .map(ResamplerInterpolation::from_name)
+                            .unwrap_or_default();
+                        let downsampling = plugin
                             .config
-                            .get("scan_devices")
+                            .get("downsampling")
+                            .and_then(|v| v.as_str())
+                            .map(Downsampling::from_name)
+                            .unwrap_or_default();
+                        plugin_instance.set_config(
+                            rate("input_rate_hz", 100.0),
+                            rate("output_rate_hz", 1000.0),
+                            interpolation,
+                            downsampling,
+                        );
+
+                        let input = input_value(&ws.connections, &outputs, plugin, "in");
+                        input_values.insert((plugin.id, "in".to_string()), input);
+                        plugin_instance.set_input(input);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                    }
+                    RuntimePlugin::Pwm(plugin_instance) => {
+                        let frequency_hz = config::f64_or(&plugin.config, "frequency_hz", 10.0);
+                        let deadband = config::f64_or(&plugin.config, "deadband", 0.0);
+                        let invert = plugin
+                            .config
+                            .get("invert")
                             .and_then(|v| v.as_bool())
                             .unwrap_or(false);
-                        let scan_nonce = plugin
+                        plugin_instance.set_config(frequency_hz, deadband, invert);
+
+                        let duty = input_value(&ws.connections, &outputs, plugin, "duty");
+                        input_values.insert((plugin.id, "duty".to_string()), duty);
+                        plugin_instance.set_input(duty);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                    }
+                    RuntimePlugin::Estop(plugin_instance) => {
+                        let latching = plugin
                             .config
-                            .get("scan_nonce")
-                            .and_then(|v| v.as_u64())
-                            .unwrap_or(0);
+                            .get("latching")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(true);
+                        plugin_instance.set_config(latching);
 
-                        let mut active_inputs: HashSet<String> = HashSet::new();
-                        let mut active_outputs: HashSet<String> = HashSet::new();
-                        for conn in &ws.connections {
-                            if conn.to_plugin == plugin.id {
-                                active_inputs.insert(conn.to_port.clone());
-                            }
-                            if conn.from_plugin == plugin.id {
-                                active_outputs.insert(conn.from_port.clone());
+                        let trigger = input_value(&ws.connections, &outputs, plugin, "trigger");
+                        let reset = input_value(&ws.connections, &outputs, plugin, "reset");
+                        input_values.insert((plugin.id, "trigger".to_string()), trigger);
+                        input_values.insert((plugin.id, "reset".to_string()), reset);
+                        plugin_instance.set_inputs(trigger > 0.5, reset > 0.5);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "active".to_string()), plugin_instance.active());
+                        if is_running && plugin_instance.is_active() {
+                            estop_seen = true;
+                            if !estop_active {
+                                tracing::warn!(plugin = plugin.id, "emergency stop asserted");
+                                estop_active = true;
+                                // Stopped plugins stay stopped once the estop is released
+                                for other in &ws.plugins {
+                                    if !is_estop_essential(&other.kind) {
+                                        plugin_running.insert(other.id, false);
+                                    }
+                                }
                             }
                         }
+                    }
+                    RuntimePlugin::AudioOut(plugin_instance) => {
+                        let sample_rate = u32::try_from(config::u64_or(&plugin.config, "sample_rate", 44_100)).unwrap_or(44_100);
+                        let gain = config::f64_or(&plugin.config, "gain", 1.0);
+                        let mode = plugin
+                            .config
+                            .get("mode")
+                            .and_then(|v| v.as_str())
+                            .map(AudioOutMode::from_name)
+                            .unwrap_or_default();
+                        let carrier_hz = config::f64_or(&plugin.config, "carrier_hz", 440.0);
+                        let deviation_hz = config::f64_or(&plugin.config, "deviation_hz", 220.0);
+                        plugin_instance.set_config(sample_rate, gain, mode, carrier_hz, deviation_hz, is_running);
+
+                        let value = input_value(&ws.connections, &outputs, plugin, "in");
+                        input_values.insert((plugin.id, "in".to_string()), value);
+                        plugin_instance.set_input(value);
+                        internal_variable_values.insert(
+                            (plugin.id, "dropped".to_string()),
+                            serde_json::Value::from(plugin_instance.dropped()),
+                        );
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                    }
+                    RuntimePlugin::FrequencyCounter(plugin_instance) => {
+                        let threshold = config::f64_or(&plugin.config, "threshold", 0.5);
+                        let edge = plugin
+                            .config
+                            .get("edge")
+                            .and_then(|v| v.as_str())
+                            .map(FrequencyCounterEdge::from_name)
+                            .unwrap_or_default();
+                        let periods = config::u64_or(&plugin.config, "periods", 4) as usize;
+                        plugin_instance.set_config(threshold, edge, periods);
 
-                        plugin_instance.set_active_ports(&active_inputs, &active_outputs);
+                        let value = input_value(&ws.connections, &outputs, plugin, "in");
+                        input_values.insert((plugin.id, "in".to_string()), value);
+                        plugin_instance.set_input(value);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "frequency_hz".to_string()), plugin_instance.frequency_hz());
+                        outputs.insert((plugin.id, "period_ms".to_string()), plugin_instance.period_ms());
+                        outputs.insert((plugin.id, "duty_cycle".to_string()), plugin_instance.duty_cycle());
+                    }
+                    RuntimePlugin::ModbusIo(plugin_instance) => {
+                        let config_str = |key: &str, default: &str| {
+                            plugin
+                                .config
+                                .get(key)
+                                .and_then(|v| v.as_str())
+                                .unwrap_or(default)
+                                .to_string()
+                        };
+                        let config_u64 = |key: &str, default: u64| {
+                            config::u64_or(&plugin.config, key, default)
+                        };
+                        let specs = |key: &str| -> Vec<String> {
+                            plugin
+                                .config
+                                .get(key)
+                                .and_then(|v| v.as_array())
+                                .map(|items| {
+                                    items
+                                        .iter()
+                                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                        .collect()
+                                })
+                                .unwrap_or_default()
+                        };
+                        let connection = ModbusConnectionConfig {
+                            mode: ModbusMode::from_name(&config_str("mode", "tcp")),
+                            address: config_str("address", "127.0.0.1:502"),
+                            device: config_str("device", "/dev/ttyUSB0"),
+                            baud_rate: u32::try_from(config_u64("baud_rate", 9600)).unwrap_or(9600),
+                            unit_id: u8::try_from(config_u64("unit_id", 1)).unwrap_or(1),
+                            timeout_ms: config_u64("timeout_ms", 100),
+                        };
                         plugin_instance.set_config(
-                            device_path.to_string(),
-                            scan_devices,
-                            scan_nonce,
+                            connection,
+                            config_u64("poll_ms", 100),
+                            &specs("writes"),
+                            &specs("reads"),
                         );
 
-                        let has_active = !active_inputs.is_empty() || !active_outputs.is_empty();
-                        if has_active && !plugin_instance.is_open() {
+                        if !is_running {
+                            if plugin_instance.is_open() || !plugin_instance.can_retry_open() {
+                                let _ = plugin_instance.close();
+                            }
+                        } else if !plugin_instance.is_open() && plugin_instance.can_retry_open() {
                             let _ = plugin_instance.open();
-                        } else if !has_active && plugin_instance.is_open() {
-                            let _ = plugin_instance.close();
                         }
+                        track_open_error(&mut open_errors, plugin.id, plugin_instance.open_error());
 
-                        let input_ports: Vec<String> =
-                            plugin_instance.input_port_names().iter().cloned().collect();
-                        for port in input_ports {
-                            let value = input_sum(&ws.connections, &outputs, plugin.id, &port);
+                        for port in plugin_instance.input_port_names() {
+                            let value = input_value(&ws.connections, &outputs, plugin, &port);
                             input_values.insert((plugin.id, port.clone()), value);
                             plugin_instance.set_input(&port, value);
                         }
-
-                        let _ = plugin_instance.process(&mut plugin_ctx);
-
-                        let output_ports: Vec<String> = plugin_instance
-                            .output_port_names()
-                            .iter()
-                            .cloned()
-                            .collect();
-                        for port in output_ports {
+                        plugin_instance.set_safe_value(
+                            config::f64_or(&plugin.config, "safe_value", 0.0),
+                        );
+                        if estop_active {
+                            plugin_instance.safe_state();
+                        }
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        for port in plugin_instance.output_port_names() {
                             let value = plugin_instance.get_output(&port);
                             outputs.insert((plugin.id, port), value);
                         }
                     }
-                    RuntimePlugin::LivePlotter(plugin_instance) => {
-                        let config_input_count = plugin
-                            .config
-                            .get("input_count")
-                            .and_then(|v| v.as_u64())
-                            .unwrap_or(0)
-                            as usize;
-                        let input_count = config_input_count;
-                        plugin_instance.set_config(input_count, is_running);
+                    RuntimePlugin::StateMachine(plugin_instance) => {
+                        let names = |key: &str| -> Vec<String> {
+                            plugin
+                                .config
+                                .get(key)
+                                .and_then(|v| v.as_array())
+                                .map(|items| {
+                                    items
+                                        .iter()
+                                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                                        .collect()
+                                })
+                                .unwrap_or_default()
+                        };
+                        plugin_instance.set_config(&names("inputs"), &names("states"), &names("transitions"));
+
+                        for port in plugin_instance.input_port_names() {
+                            let value = input_value(&ws.connections, &outputs, plugin, &port);
+                            input_values.insert((plugin.id, port.clone()), value);
+                            plugin_instance.set_input(&port, value);
+                        }
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                            queue_plugin_events(
+                                &mut plugin_events,
+                                plugin.id,
+                                plugin_ctx.tick,
+                                settings.period_seconds,
+                                plugin_instance.drain_events(),
+                            );
+                        }
+                        for port in plugin_instance.output_port_names() {
+                            let value = plugin_instance.get_output(&port);
+                            outputs.insert((plugin.id, port), value);
+                        }
                         internal_variable_values.insert(
-                            (plugin.id, "input_count".to_string()),
-                            serde_json::Value::from(input_count as i64),
+                            (plugin.id, "state_name".to_string()),
+                            serde_json::Value::from(plugin_instance.state_name()),
                         );
                         internal_variable_values.insert(
-                            (plugin.id, "running".to_string()),
-                            serde_json::Value::from(is_running),
+                            (plugin.id, "time_in_state".to_string()),
+                            serde_json::Value::from(plugin_instance.time_in_state()),
+                        );
+                    }
+                    RuntimePlugin::FaultRecorder(plugin_instance) => {
+                        let input_count = config::u64_or(&plugin.config, "input_count", 0) as usize;
+                        let path = plugin
+                            .config
+                            .get("path")
+                            .and_then(|v| v.as_str())
+                            .and_then(normalize_path);
+                        let seconds = |key: &str| config::f64_or(&plugin.config, key, 1.0);
+                        plugin_instance.set_config(
+                            input_count,
+                            path,
+                            seconds("pre_trigger_s"),
+                            seconds("post_trigger_s"),
+                            settings.period_seconds,
                         );
+
                         let mut inputs = Vec::with_capacity(input_count);
                         for idx in 0..input_count {
                             let port = format!("in_{idx}");
                             let value = if idx == 0 {
                                 let mut ports = vec![port.clone()];
                                 ports.push("in".to_string());
-                                input_sum_any(&ws.connections, &outputs, plugin.id, &ports)
+                                input_value_any(&ws.connections, &outputs, plugin, &ports)
                             } else {
-                                input_sum(&ws.connections, &outputs, plugin.id, &port)
+                                input_value(&ws.connections, &outputs, plugin, &port)
                             };
                             input_values.insert((plugin.id, port), value);
                             inputs.push(value);
                         }
-                        plugin_instance.set_inputs(inputs.clone());
-                        if plugin_instance.is_running() {
-                            plotter_samples
-                                .entry(plugin.id)
-                                .or_default()
-                                .push((plugin_ctx.tick, inputs));
+                        let trigger = input_value(&ws.connections, &outputs, plugin, "trigger");
+                        input_values.insert((plugin.id, "trigger".to_string()), trigger);
+                        plugin_instance.set_inputs(inputs, trigger);
+                        internal_variable_values.insert(
+                            (plugin.id, "input_count".to_string()),
+                            serde_json::Value::from(input_count as i64),
+                        );
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        for name in ["state", "captures", "last_capture", "error"] {
+                            if let Some(value) = plugin_instance.get_variable(name) {
+                                internal_variable_values.insert((plugin.id, name.to_string()), value);
+                            }
                         }
                     }
-                    RuntimePlugin::PerformanceMonitor(plugin_instance) => {
-                        let max_latency_us = plugin
-                            .config
-                            .get("max_latency_us")
-                            .and_then(|v| v.as_f64())
-                            .unwrap_or(1000.0);
+                    RuntimePlugin::FilteredDerivative(plugin_instance) => {
+                        let tau = config::f64_or(&plugin.config, "tau", 0.01);
+                        let gain = config::f64_or(&plugin.config, "gain", 1.0);
+                        plugin_instance.set_config(tau, gain);
 
-                        let workspace_period_us = settings.period_seconds * 1_000_000.0;
-                        plugin_instance.set_config(max_latency_us, workspace_period_us);
-                        let _ = plugin_instance.process(&mut plugin_ctx);
+                        let input = input_value(&ws.connections, &outputs, plugin, "in");
+                        input_values.insert((plugin.id, "in".to_string()), input);
+                        plugin_instance.set_input(input);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                    }
+                    RuntimePlugin::BitPack(plugin_instance) => {
+                        let bits = config::u64_or(&plugin.config, "bits", 8) as usize;
+                        plugin_instance.set_config(bits);
 
-                        // Output the performance values
-                        for (idx, output_name) in
-                            ["period_us", "latency_us", "jitter_us", "realtime_violation"]
-                                .iter()
-                                .enumerate()
-                        {
-                            let value = plugin_instance.get_output_values()[idx];
-                            outputs.insert((plugin.id, output_name.to_string()), value);
+                        for idx in 0..plugin_instance.bits() {
+                            let port = bit_pack_port(idx);
+                            let value = input_value(&ws.connections, &outputs, plugin, &port);
+                            input_values.insert((plugin.id, port), value);
+                            plugin_instance.set_bit(idx, value);
+                        }
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "word".to_string()), plugin_instance.word());
+                    }
+                    RuntimePlugin::BitUnpack(plugin_instance) => {
+                        let bits = config::u64_or(&plugin.config, "bits", 8) as usize;
+                        plugin_instance.set_config(bits);
+
+                        let word = input_value(&ws.connections, &outputs, plugin, "word");
+                        input_values.insert((plugin.id, "word".to_string()), word);
+                        plugin_instance.set_input(word);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        for idx in 0..plugin_instance.bits() {
+                            outputs.insert((plugin.id, bit_unpack_port(idx)), plugin_instance.bit(idx));
+                        }
+                    }
+                    RuntimePlugin::TransferFunction(plugin_instance) => {
+                        let coefficients = |key: &str| config::f64_array_or(&plugin.config, key, &[]);
+                        plugin_instance.set_config(&coefficients("b"), &coefficients("a"));
+
+                        let input = input_value(&ws.connections, &outputs, plugin, "in");
+                        input_values.insert((plugin.id, "in".to_string()), input);
+                        plugin_instance.set_input(input);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "out".to_string()), plugin_instance.out());
+                        internal_variable_values.insert(
+                            (plugin.id, "error".to_string()),
+                            serde_json::Value::from(plugin_instance.error().unwrap_or_default()),
+                        );
+                    }
+                    RuntimePlugin::ErrorMetrics(plugin_instance) => {
+                        let window_ms = config::f64_or(&plugin.config, "window_ms", 1000.0);
+                        plugin_instance.set_config(window_ms, plugin_ctx.period_seconds);
+
+                        let reference = input_value(&ws.connections, &outputs, plugin, "reference");
+                        let actual = input_value(&ws.connections, &outputs, plugin, "actual");
+                        input_values.insert((plugin.id, "reference".to_string()), reference);
+                        input_values.insert((plugin.id, "actual".to_string()), actual);
+                        plugin_instance.set_inputs(reference, actual);
+                        if is_running {
+                            let _ = plugin_instance.process(&mut plugin_ctx);
+                        }
+                        outputs.insert((plugin.id, "error".to_string()), plugin_instance.error());
+                        outputs.insert((plugin.id, "abs_error".to_string()), plugin_instance.abs_error());
+                        outputs.insert((plugin.id, "iae".to_string()), plugin_instance.iae());
+                        outputs.insert((plugin.id, "ise".to_string()), plugin_instance.ise());
+                        outputs.insert((plugin.id, "rmse".to_string()), plugin_instance.rmse());
+                    }
+                }
+                ConnectionQueues::restore(&mut outputs, queued_inputs);
+                if tracking.warmup.tick(plugin.id, is_running, warmup_ticks(&plugin.config)) {
+                    for ((id, _), value) in outputs.iter_mut() {
+                        if *id == plugin.id {
+                            *value = 0.0;
                         }
                     }
                 }
+                tracking.latency.outputs_written(plugin.id, is_running);
+                if is_running {
+                    tracking.queues.outputs_written(&ws.connections, &outputs, plugin.id);
+                }
+            }
+            if run_tick {
+                estop_active = estop_seen;
+                plugin_ctx.tick = plugin_ctx.tick.wrapping_add(1);
             }
-            plugin_ctx.tick = plugin_ctx.tick.wrapping_add(1);
             let ui_interval = if settings.ui_hz > 0.0 {
                 Duration::from_secs_f64(1.0 / settings.ui_hz)
             } else {
@@ -1354,6 +4318,11 @@ pub fn run_runtime_current(
                     viewer_values: viewer_values.clone(),
                     tick: plugin_ctx.tick,
                     plotter_samples: limited_plotter_samples,
+                    warming_up: tracking.warmup.warming_up().clone(),
+                    open_errors: open_errors.clone(),
+                    load_errors: load_errors.clone(),
+                    events: std::mem::take(&mut plugin_events),
+                    step_mode: step_mode.enabled(),
                 });
                 plotter_samples.clear();
                 last_state = Instant::now();
@@ -1361,7 +4330,22 @@ pub fn run_runtime_current(
         }
         let _ = runtime.tick();
         let _ = settings.cores.len();
-        ActiveRtBackend::sleep(period_duration, &mut sleep_deadline);
+        if step_mode.enabled() {
+            // Requested ticks run back to back; the timer starts over when
+            // step mode is left
+            scheduler.restart();
+            if step_mode.pending() == 0 {
+                ActiveRtBackend::sleep(STEP_MODE_POLL);
+            }
+            continue;
+        }
+        // Skipped ticks still count, so tick * period follows the wall clock
+        let (wake, skipped) = scheduler.after_tick(Instant::now());
+        if skipped > 0 {
+            tracing::debug!(skipped, "missed deadline, skipping ticks");
+        }
+        plugin_ctx.tick = plugin_ctx.tick.wrapping_add(skipped);
+        ActiveRtBackend::sleep_until(wake);
     }
 
     Ok(())
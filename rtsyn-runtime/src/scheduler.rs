@@ -0,0 +1,241 @@
+use std::time::{Duration, Instant};
+use workspace::MissedDeadlinePolicy;
+
+/// Paces the runtime loop according to a [`MissedDeadlinePolicy`].
+pub(crate) struct TickScheduler {
+    policy: MissedDeadlinePolicy,
+    period: Duration,
+    /// Deadline of the tick that just ran; `None` until the first tick.
+    deadline: Option<Instant>,
+    /// Bound of the random offset added to each sleep, for testing how the
+    /// workspace copes with timing jitter; zero leaves the timing alone.
+    jitter: Duration,
+    /// xorshift64 state for the jitter offsets.
+    rng: u64,
+}
+
+impl TickScheduler {
+    pub(crate) fn new(policy: MissedDeadlinePolicy, period: Duration) -> Self {
+        Self {
+            policy,
+            period,
+            deadline: None,
+            jitter: Duration::ZERO,
+            rng: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    /// Sets the jitter bound. Deadlines are not moved by the offsets, so a
+    /// late wake-up shortens the next sleep and the ticks do not drift.
+    pub(crate) fn set_jitter(&mut self, jitter: Duration) {
+        self.jitter = jitter;
+    }
+
+    /// Forgets the last deadline, so the next tick is not counted as late,
+    /// e.g. after the loop was paused in step mode.
+    pub(crate) fn restart(&mut self) {
+        self.deadline = None;
+    }
+
+    /// Applies new settings; deadlines restart from the next tick.
+    pub(crate) fn reconfigure(&mut self, policy: MissedDeadlinePolicy, period: Duration) {
+        self.policy = policy;
+        self.period = period;
+        self.deadline = None;
+    }
+
+    /// Called once a tick finished at `now`. Returns when to wake for the
+    /// next tick and how many ticks were skipped, which the caller adds to
+    /// the tick counter so that tick * period keeps following the wall clock.
+    /// The wake-up is an absolute deadline, so processing time does not
+    /// delay the ticks after it.
+    pub(crate) fn after_tick(&mut self, now: Instant) -> (Instant, u64) {
+        let (wake, skipped) = self.next_deadline(now);
+        (self.jittered(wake), skipped)
+    }
+
+    fn jittered(&mut self, wake: Instant) -> Instant {
+        if self.jitter.is_zero() {
+            return wake;
+        }
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        // Uniform in [-1, 1)
+        let unit = (self.rng >> 11) as f64 / (1u64 << 52) as f64 - 1.0;
+        let offset = Duration::from_secs_f64(self.jitter.as_secs_f64() * unit.abs());
+        if unit < 0.0 {
+            wake.checked_sub(offset).unwrap_or(wake)
+        } else {
+            wake + offset
+        }
+    }
+
+    fn next_deadline(&mut self, now: Instant) -> (Instant, u64) {
+        if self.period.is_zero() {
+            return (now, 0);
+        }
+        let next = self.deadline.unwrap_or(now) + self.period;
+        if now < next {
+            self.deadline = Some(next);
+            return (next, 0);
+        }
+        match self.policy {
+            MissedDeadlinePolicy::CatchUp => {
+                self.deadline = Some(next);
+                (next, 0)
+            }
+            MissedDeadlinePolicy::BestEffort => {
+                let next = now + self.period;
+                self.deadline = Some(next);
+                (next, 0)
+            }
+            MissedDeadlinePolicy::Skip => {
+                let period_ns = self.period.as_nanos();
+                let skipped = (now - next).as_nanos() / period_ns + 1;
+                let aligned = next + Duration::from_nanos((skipped * period_ns) as u64);
+                self.deadline = Some(aligned);
+                (aligned, skipped as u64)
+            }
+        }
+    }
+}
+
+/// Manual ticking for debugging. While enabled, the loop runs only the
+/// ticks requested with `LogicMessage::Step`, back to back, instead of
+/// following the timer.
+#[derive(Debug, Default)]
+pub(crate) struct StepMode {
+    enabled: bool,
+    pending: u64,
+}
+
+impl StepMode {
+    /// Leaving step mode drops the ticks still requested.
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.pending = 0;
+    }
+
+    pub(crate) fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Requests `ticks` more ticks; ignored outside step mode.
+    pub(crate) fn request(&mut self, ticks: u64) {
+        if self.enabled {
+            self.pending = self.pending.saturating_add(ticks);
+        }
+    }
+
+    /// Ticks requested and not run yet.
+    pub(crate) fn pending(&self) -> u64 {
+        self.pending
+    }
+
+    /// Whether the loop runs a tick now; uses up one requested tick.
+    pub(crate) fn take_tick(&mut self) -> bool {
+        if !self.enabled {
+            return true;
+        }
+        if self.pending == 0 {
+            return false;
+        }
+        self.pending -= 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PERIOD: Duration = Duration::from_millis(10);
+
+    fn ms(value: u64) -> Duration {
+        Duration::from_millis(value)
+    }
+
+    #[test]
+    fn on_time_ticks_sleep_until_the_next_deadline() {
+        for policy in MissedDeadlinePolicy::ALL {
+            let start = Instant::now();
+            let mut scheduler = TickScheduler::new(policy, PERIOD);
+            assert_eq!(scheduler.after_tick(start + ms(2)), (start + ms(12), 0));
+            // Processing time does not push the next deadline back
+            assert_eq!(scheduler.after_tick(start + ms(15)), (start + ms(22), 0));
+        }
+    }
+
+    #[test]
+    fn skip_realigns_and_reports_dropped_ticks() {
+        let start = Instant::now();
+        let mut scheduler = TickScheduler::new(MissedDeadlinePolicy::Skip, PERIOD);
+        scheduler.after_tick(start);
+        assert_eq!(scheduler.after_tick(start + ms(10)), (start + ms(20), 0));
+        // The tick due at 20 ms ran until 43 ms: the 30 and 40 ms slots are dropped
+        assert_eq!(scheduler.after_tick(start + ms(43)), (start + ms(50), 2));
+        assert_eq!(scheduler.after_tick(start + ms(51)), (start + ms(60), 0));
+    }
+
+    #[test]
+    fn catch_up_runs_missed_ticks_back_to_back() {
+        let start = Instant::now();
+        let mut scheduler = TickScheduler::new(MissedDeadlinePolicy::CatchUp, PERIOD);
+        scheduler.after_tick(start);
+        assert_eq!(scheduler.after_tick(start + ms(35)), (start + ms(20), 0));
+        assert_eq!(scheduler.after_tick(start + ms(36)), (start + ms(30), 0));
+        assert_eq!(scheduler.after_tick(start + ms(37)), (start + ms(40), 0));
+    }
+
+    #[test]
+    fn best_effort_restarts_the_schedule_after_an_overrun() {
+        let start = Instant::now();
+        let mut scheduler = TickScheduler::new(MissedDeadlinePolicy::BestEffort, PERIOD);
+        scheduler.after_tick(start);
+        assert_eq!(scheduler.after_tick(start + ms(35)), (start + ms(45), 0));
+        assert_eq!(scheduler.after_tick(start + ms(47)), (start + ms(55), 0));
+    }
+
+    #[test]
+    fn jitter_stays_within_its_bound_without_drifting() {
+        let start = Instant::now();
+        let mut scheduler = TickScheduler::new(MissedDeadlinePolicy::Skip, PERIOD);
+        scheduler.set_jitter(ms(2));
+        scheduler.after_tick(start);
+        let mut now = start;
+        let mut waits = Vec::new();
+        for tick in 2..200 {
+            let (wake, skipped) = scheduler.after_tick(now);
+            assert_eq!(skipped, 0);
+            waits.push(wake.saturating_duration_since(now));
+            now = now.max(wake);
+            // Each wake-up is within 2 ms of its deadline, never accumulating
+            let deadline = start + PERIOD * tick;
+            let error = if now > deadline { now - deadline } else { deadline - now };
+            assert!(error <= ms(2), "tick {tick} off by {error:?}");
+        }
+        assert!(waits.iter().any(|wait| *wait < PERIOD));
+        assert!(waits.iter().any(|wait| *wait > PERIOD));
+    }
+
+    #[test]
+    fn step_mode_runs_only_requested_ticks() {
+        let mut step = StepMode::default();
+        step.request(3);
+        assert!(step.take_tick());
+        assert_eq!(step.pending(), 0);
+
+        step.set_enabled(true);
+        assert!(!step.take_tick());
+        step.request(2);
+        assert!(step.take_tick());
+        assert!(step.take_tick());
+        assert!(!step.take_tick());
+
+        step.request(5);
+        step.set_enabled(false);
+        assert_eq!(step.pending(), 0);
+        assert!(step.take_tick());
+    }
+}
@@ -0,0 +1,82 @@
+use rtsyn_core::config;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// Ticks a plugin's outputs are held at zero after it starts. Plugins declare
+/// it with a `warmup_ticks` config field; without one there is no warmup.
+pub(crate) fn warmup_ticks(plugin_config: &Value) -> u64 {
+    config::u64_or(plugin_config, "warmup_ticks", 0)
+}
+
+/// Tracks how far each running plugin is into its warmup.
+#[derive(Default)]
+pub(crate) struct Warmup {
+    elapsed: HashMap<u64, u64>,
+    warming_up: HashSet<u64>,
+}
+
+impl Warmup {
+    /// Called after a plugin's tick. Returns whether the plugin is still
+    /// warming up, in which case the caller suppresses its outputs. Stopped
+    /// plugins start their warmup over once they run again.
+    pub(crate) fn tick(&mut self, plugin_id: u64, running: bool, warmup_ticks: u64) -> bool {
+        if !running || warmup_ticks == 0 {
+            self.restart(plugin_id);
+            return false;
+        }
+        let elapsed = self.elapsed.entry(plugin_id).or_insert(0);
+        if *elapsed < warmup_ticks {
+            *elapsed += 1;
+            self.warming_up.insert(plugin_id);
+            true
+        } else {
+            self.warming_up.remove(&plugin_id);
+            false
+        }
+    }
+
+    /// Starts the plugin's warmup over from its next tick.
+    pub(crate) fn restart(&mut self, plugin_id: u64) {
+        self.elapsed.remove(&plugin_id);
+        self.warming_up.remove(&plugin_id);
+    }
+
+    pub(crate) fn is_warming_up(&self, plugin_id: u64) -> bool {
+        self.warming_up.contains(&plugin_id)
+    }
+
+    pub(crate) fn warming_up(&self) -> &HashSet<u64> {
+        &self.warming_up
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn warms_up_for_the_declared_ticks_after_each_start() {
+        let mut warmup = Warmup::default();
+        let ticks = warmup_ticks(&json!({ "warmup_ticks": 2 }));
+        assert_eq!(ticks, 2);
+
+        let run: Vec<bool> = (0..3).map(|_| warmup.tick(1, true, ticks)).collect();
+        assert_eq!(run, vec![true, true, false]);
+        assert!(!warmup.is_warming_up(1));
+
+        // Stopping and starting again warms up again
+        assert!(!warmup.tick(1, false, ticks));
+        assert!(warmup.tick(1, true, ticks));
+        assert_eq!(warmup.warming_up(), &HashSet::from([1]));
+    }
+
+    #[test]
+    fn no_warmup_without_a_declaration() {
+        let mut warmup = Warmup::default();
+        let ticks = warmup_ticks(&json!({}));
+        assert_eq!(ticks, 0);
+        assert!(!warmup.tick(1, true, ticks));
+        assert!(warmup.warming_up().is_empty());
+    }
+}
@@ -58,6 +58,8 @@ fn tick_orders_by_priority() {
         plugins: Vec::new(),
         connections: Vec::new(),
         settings: workspace::WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
     });
     let log = Arc::new(Mutex::new(Vec::new()));
     let ticks = Arc::new(Mutex::new(Vec::new()));
@@ -94,6 +96,8 @@ fn tick_increments_context() {
         plugins: Vec::new(),
         connections: Vec::new(),
         settings: workspace::WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
     });
     let log = Arc::new(Mutex::new(Vec::new()));
     let ticks = Arc::new(Mutex::new(Vec::new()));
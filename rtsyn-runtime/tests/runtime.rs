@@ -1,11 +1,15 @@
-use rtsyn_runtime::runtime::{spawn_runtime, LogicMessage, LogicSettings};
+use rtsyn_runtime::benchmark::benchmark_plugin;
+use rtsyn_runtime::runtime::{spawn_runtime, LogicMessage, LogicSettings, PluginRunState};
 use serde_json::json;
 use std::{
     fs,
     path::PathBuf,
     time::{Duration, Instant},
 };
-use workspace::{PluginDefinition, WorkspaceDefinition, WorkspaceSettings};
+use workspace::{
+    ConnectionDefinition, MissedDeadlinePolicy, PluginDefinition, WorkspaceDefinition,
+    WorkspaceSettings,
+};
 
 fn find_cdylib(crate_name: &str) -> PathBuf {
     // Workspace root = CARGO_MANIFEST_DIR/..
@@ -52,6 +56,8 @@ fn runtime_executes_dynamic_mock_plugin_and_emits_outputs() {
         time_label: "time_ms".to_string(),
         max_integration_steps: 50,
         ui_hz: 500.0,
+        missed_deadline_policy: MissedDeadlinePolicy::BestEffort,
+        jitter_us: 0.0,
     };
     logic_tx
         .send(LogicMessage::UpdateSettings(settings))
@@ -77,6 +83,8 @@ fn runtime_executes_dynamic_mock_plugin_and_emits_outputs() {
         plugins,
         connections: vec![],
         settings: WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
     };
 
     logic_tx
@@ -110,3 +118,556 @@ fn runtime_executes_dynamic_mock_plugin_and_emits_outputs() {
         last_tick
     );
 }
+
+#[test]
+fn runtime_reports_plugin_run_states() {
+    let (logic_tx, _logic_state_rx) = spawn_runtime().expect("failed to spawn runtime");
+
+    let plugin = |id: u64, kind: &str, running: bool| PluginDefinition {
+        id,
+        kind: kind.to_string(),
+        config: json!({}),
+        priority: 0,
+        running,
+    };
+    let workspace = WorkspaceDefinition {
+        name: "test".to_string(),
+        description: "".to_string(),
+        target_hz: 1000,
+        plugins: vec![
+            plugin(1, "performance_monitor", true),
+            plugin(2, "performance_monitor", false),
+            // No library_path: the runtime cannot instantiate it
+            plugin(3, "missing_dynamic_plugin", true),
+        ],
+        connections: vec![],
+        settings: WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+    logic_tx
+        .send(LogicMessage::UpdateWorkspace(workspace))
+        .unwrap();
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    logic_tx.send(LogicMessage::QueryPluginStates(tx)).unwrap();
+    let states = rx
+        .recv_timeout(Duration::from_secs(2))
+        .expect("did not receive plugin states in time");
+
+    assert_eq!(states.get(&1), Some(&PluginRunState::Running));
+    assert_eq!(states.get(&2), Some(&PluginRunState::Stopped));
+    assert_eq!(states.get(&3), Some(&PluginRunState::Failed));
+
+    logic_tx.send(LogicMessage::SetPluginRunning(2, true)).unwrap();
+    let (tx, rx) = std::sync::mpsc::channel();
+    logic_tx.send(LogicMessage::QueryPluginStates(tx)).unwrap();
+    let states = rx.recv_timeout(Duration::from_secs(2)).unwrap();
+    assert_eq!(states.get(&2), Some(&PluginRunState::Running));
+
+    // Step mode holds enabled plugins; stopped and failed ones stay as they are
+    logic_tx.send(LogicMessage::SetPluginRunning(2, false)).unwrap();
+    logic_tx.send(LogicMessage::SetStepMode(true)).unwrap();
+    let (tx, rx) = std::sync::mpsc::channel();
+    logic_tx.send(LogicMessage::QueryPluginStates(tx)).unwrap();
+    let states = rx.recv_timeout(Duration::from_secs(2)).unwrap();
+    assert_eq!(states.get(&1), Some(&PluginRunState::Paused));
+    assert_eq!(states.get(&2), Some(&PluginRunState::Stopped));
+    assert_eq!(states.get(&3), Some(&PluginRunState::Failed));
+}
+
+#[test]
+fn missing_library_is_reported_until_the_config_points_at_one() {
+    let (logic_tx, logic_state_rx) = spawn_runtime().expect("failed to spawn runtime");
+    let settings = LogicSettings {
+        cores: vec![0],
+        period_seconds: 0.001,
+        time_scale: 1_000.0,
+        time_label: "time_ms".to_string(),
+        max_integration_steps: 50,
+        ui_hz: 500.0,
+        missed_deadline_policy: MissedDeadlinePolicy::BestEffort,
+        jitter_us: 0.0,
+    };
+    logic_tx.send(LogicMessage::UpdateSettings(settings)).unwrap();
+    let missing = "/nonexistent/libmock_out_5_rs_runtime.so";
+    let workspace = WorkspaceDefinition {
+        name: "test".to_string(),
+        description: "".to_string(),
+        target_hz: 1000,
+        plugins: vec![PluginDefinition {
+            id: 1,
+            kind: "mock_out_5_rs_runtime".to_string(),
+            config: json!({ "library_path": missing }),
+            priority: 0,
+            running: true,
+        }],
+        connections: Vec::new(),
+        settings: WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+    logic_tx.send(LogicMessage::UpdateWorkspace(workspace)).unwrap();
+    let deadline = Instant::now() + Duration::from_secs(2);
+    loop {
+        let state = logic_state_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("did not receive runtime state in time");
+        if let Some(error) = state.load_errors.get(&1) {
+            assert_eq!(error, &format!("library missing: {missing}"));
+            break;
+        }
+        assert!(Instant::now() < deadline, "missing library was not reported");
+    }
+
+    let lib_path = find_cdylib("mock_out_5_rs_runtime");
+    logic_tx
+        .send(LogicMessage::UpdatePluginConfig(
+            1,
+            json!({ "library_path": lib_path.to_string_lossy().to_string() }),
+        ))
+        .unwrap();
+    let deadline = Instant::now() + Duration::from_secs(2);
+    loop {
+        let state = logic_state_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("did not receive runtime state in time");
+        if state.load_errors.is_empty() && state.outputs.get(&(1, "out".to_string())) == Some(&5.0) {
+            break;
+        }
+        assert!(Instant::now() < deadline, "plugin did not load from the located library");
+    }
+}
+
+#[test]
+fn feedforward_chain_propagates_within_one_tick() {
+    let (logic_tx, logic_state_rx) = spawn_runtime().expect("failed to spawn runtime");
+    // Publish a state after every tick
+    let settings = LogicSettings {
+        cores: vec![0],
+        period_seconds: 0.001,
+        time_scale: 1_000.0,
+        time_label: "time_ms".to_string(),
+        max_integration_steps: 50,
+        ui_hz: 1e9,
+        missed_deadline_policy: MissedDeadlinePolicy::BestEffort,
+        jitter_us: 0.0,
+    };
+    logic_tx.send(LogicMessage::UpdateSettings(settings)).unwrap();
+
+    let lib_path = find_cdylib("mock_out_5_rs_runtime");
+    let scale = |id: u64, gain: f64| PluginDefinition {
+        id,
+        kind: "scale".to_string(),
+        config: json!({ "mode": "gain_offset", "gain": gain, "offset": 0.0 }),
+        priority: 0,
+        running: true,
+    };
+    // Ids run against the data flow: source 3 -> scale 2 -> scale 1
+    let plugins = vec![
+        scale(1, 3.0),
+        scale(2, 2.0),
+        PluginDefinition {
+            id: 3,
+            kind: "mock_out_5_rs_runtime".to_string(),
+            config: json!({ "library_path": lib_path.to_string_lossy().to_string() }),
+            priority: 0,
+            running: true,
+        },
+    ];
+    let connection = |from: u64, to: u64| ConnectionDefinition {
+        from_plugin: from,
+        from_port: "out".to_string(),
+        to_plugin: to,
+        to_port: "in".to_string(),
+        kind: "shared_memory".to_string(),
+        gain: 1.0,
+    };
+    let workspace = WorkspaceDefinition {
+        name: "test".to_string(),
+        description: "".to_string(),
+        target_hz: 1000,
+        plugins,
+        connections: vec![connection(2, 1), connection(3, 2)],
+        settings: WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+    logic_tx.send(LogicMessage::UpdateWorkspace(workspace)).unwrap();
+
+    let state = logic_state_rx
+        .recv_timeout(Duration::from_secs(2))
+        .expect("did not receive runtime state in time");
+    assert_eq!(state.tick, 1);
+    assert_eq!(state.outputs.get(&(3, "out".to_string())), Some(&5.0));
+    assert_eq!(state.outputs.get(&(2, "out".to_string())), Some(&10.0));
+    assert_eq!(state.outputs.get(&(1, "out".to_string())), Some(&30.0));
+}
+
+#[test]
+fn step_mode_runs_only_requested_ticks() {
+    let (logic_tx, logic_state_rx) = spawn_runtime().expect("failed to spawn runtime");
+    let settings = LogicSettings {
+        cores: vec![0],
+        period_seconds: 0.001,
+        time_scale: 1_000.0,
+        time_label: "time_ms".to_string(),
+        max_integration_steps: 50,
+        ui_hz: 1e9,
+        missed_deadline_policy: MissedDeadlinePolicy::BestEffort,
+        jitter_us: 0.0,
+    };
+    logic_tx.send(LogicMessage::UpdateSettings(settings)).unwrap();
+    logic_tx.send(LogicMessage::SetStepMode(true)).unwrap();
+    let lib_path = find_cdylib("mock_out_5_rs_runtime");
+    let workspace = WorkspaceDefinition {
+        name: "test".to_string(),
+        description: "".to_string(),
+        target_hz: 1000,
+        plugins: vec![PluginDefinition {
+            id: 1,
+            kind: "mock_out_5_rs_runtime".to_string(),
+            config: json!({ "library_path": lib_path.to_string_lossy().to_string() }),
+            priority: 0,
+            running: true,
+        }],
+        connections: Vec::new(),
+        settings: WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+    logic_tx.send(LogicMessage::UpdateWorkspace(workspace)).unwrap();
+    let next_state = || {
+        logic_state_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("did not receive runtime state in time")
+    };
+
+    // Paused: states keep coming, the tick does not move
+    let state = next_state();
+    assert!(state.step_mode);
+    assert_eq!(state.tick, 0);
+    assert!(state.outputs.is_empty());
+
+    logic_tx.send(LogicMessage::Step(3)).unwrap();
+    while next_state().tick < 3 {}
+    std::thread::sleep(Duration::from_millis(50));
+    let state = logic_state_rx.try_iter().last().unwrap_or_else(next_state);
+    assert_eq!(state.tick, 3);
+    assert_eq!(state.outputs.get(&(1, "out".to_string())), Some(&5.0));
+
+    logic_tx.send(LogicMessage::SetStepMode(false)).unwrap();
+    let deadline = Instant::now() + Duration::from_secs(2);
+    loop {
+        let state = next_state();
+        if !state.step_mode && state.tick > 3 {
+            break;
+        }
+        assert!(Instant::now() < deadline, "timed execution did not resume");
+    }
+}
+
+#[test]
+fn estop_stops_non_essential_plugins() {
+    let (logic_tx, logic_state_rx) = spawn_runtime().expect("failed to spawn runtime");
+    let lib_path = find_cdylib("mock_out_5_rs_runtime");
+    let plugin = |id: u64, kind: &str, config: serde_json::Value| PluginDefinition {
+        id,
+        kind: kind.to_string(),
+        config,
+        priority: 0,
+        running: true,
+    };
+    let workspace = WorkspaceDefinition {
+        name: "test".to_string(),
+        description: "".to_string(),
+        target_hz: 1000,
+        plugins: vec![
+            plugin(1, "scale", json!({})),
+            plugin(2, "estop", json!({ "latching": true })),
+            plugin(
+                3,
+                "mock_out_5_rs_runtime",
+                json!({ "library_path": lib_path.to_string_lossy().to_string() }),
+            ),
+        ],
+        connections: vec![ConnectionDefinition {
+            from_plugin: 3,
+            from_port: "out".to_string(),
+            to_plugin: 2,
+            to_port: "trigger".to_string(),
+            kind: "shared_memory".to_string(),
+            gain: 1.0,
+        }],
+        settings: WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+    logic_tx.send(LogicMessage::UpdateWorkspace(workspace)).unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(2);
+    loop {
+        let state = logic_state_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("did not receive runtime state in time");
+        if state.outputs.get(&(2, "active".to_string())) == Some(&1.0) {
+            break;
+        }
+        assert!(Instant::now() < deadline, "estop never became active");
+    }
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    logic_tx.send(LogicMessage::QueryPluginStates(tx)).unwrap();
+    let states = rx.recv_timeout(Duration::from_secs(2)).unwrap();
+    assert_eq!(states.get(&1), Some(&PluginRunState::Stopped));
+    assert_eq!(states.get(&2), Some(&PluginRunState::Running));
+    assert_eq!(states.get(&3), Some(&PluginRunState::Stopped));
+}
+
+#[test]
+fn graphs_run_independently_with_their_own_plugin_ids() {
+    let (logic_tx, _logic_state_rx) = spawn_runtime().expect("failed to spawn runtime");
+    let lib_path = find_cdylib("mock_out_5_rs_runtime");
+    let workspace = |kind: &str, config: serde_json::Value| WorkspaceDefinition {
+        name: kind.to_string(),
+        description: "".to_string(),
+        target_hz: 1000,
+        plugins: vec![PluginDefinition {
+            id: 1,
+            kind: kind.to_string(),
+            config,
+            priority: 0,
+            running: true,
+        }],
+        connections: vec![],
+        settings: WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+
+    let (a_tx, a_rx) = std::sync::mpsc::channel();
+    let (b_tx, b_rx) = std::sync::mpsc::channel();
+    logic_tx.send(LogicMessage::CreateGraph("a".to_string(), a_tx)).unwrap();
+    logic_tx.send(LogicMessage::CreateGraph("b".to_string(), b_tx)).unwrap();
+    let mock = workspace(
+        "mock_out_5_rs_runtime",
+        json!({ "library_path": lib_path.to_string_lossy().to_string() }),
+    );
+    let update = |graph: &str, workspace| {
+        LogicMessage::ToGraph(graph.to_string(), Box::new(LogicMessage::UpdateWorkspace(workspace)))
+    };
+    logic_tx.send(update("a", mock)).unwrap();
+    logic_tx.send(update("b", workspace("scale", json!({})))).unwrap();
+
+    // Plugin 1 is the mock in graph a and a scale in graph b
+    for (rx, expected) in [(&a_rx, 5.0), (&b_rx, 0.0)] {
+        let deadline = Instant::now() + Duration::from_secs(2);
+        loop {
+            let state = rx
+                .recv_timeout(Duration::from_secs(2))
+                .expect("did not receive graph state in time");
+            if state.outputs.get(&(1, "out".to_string())) == Some(&expected) {
+                break;
+            }
+            assert!(Instant::now() < deadline, "graph never produced out={expected}");
+        }
+    }
+
+    // Removing a graph ends its tick loop, which closes its state channel
+    logic_tx.send(LogicMessage::RemoveGraph("a".to_string())).unwrap();
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while a_rx.recv_timeout(Duration::from_secs(2)).is_ok() {
+        assert!(Instant::now() < deadline, "removed graph kept running");
+    }
+    assert!(b_rx.recv_timeout(Duration::from_secs(2)).is_ok());
+}
+
+#[test]
+fn graphs_cannot_create_graphs() {
+    let (logic_tx, _logic_state_rx) = spawn_runtime().expect("failed to spawn runtime");
+    let (graph_tx, graph_rx) = std::sync::mpsc::channel();
+    logic_tx.send(LogicMessage::CreateGraph("outer".to_string(), graph_tx)).unwrap();
+    let (nested_tx, nested_rx) = std::sync::mpsc::channel();
+    let nested = LogicMessage::CreateGraph("inner".to_string(), nested_tx);
+    logic_tx
+        .send(LogicMessage::ToGraph("outer".to_string(), Box::new(nested)))
+        .unwrap();
+
+    assert_eq!(
+        nested_rx.recv_timeout(Duration::from_secs(2)).err(),
+        Some(std::sync::mpsc::RecvTimeoutError::Disconnected)
+    );
+
+    // The outer graph keeps running
+    let workspace = WorkspaceDefinition {
+        name: "outer".to_string(),
+        description: "".to_string(),
+        target_hz: 1000,
+        plugins: vec![],
+        connections: vec![],
+        settings: WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+    logic_tx
+        .send(LogicMessage::ToGraph(
+            "outer".to_string(),
+            Box::new(LogicMessage::UpdateWorkspace(workspace)),
+        ))
+        .unwrap();
+    assert!(graph_rx.recv_timeout(Duration::from_secs(2)).is_ok());
+}
+
+#[test]
+fn restarted_plugin_warms_up_again() {
+    let (logic_tx, logic_state_rx) = spawn_runtime().expect("failed to spawn runtime");
+    let workspace = WorkspaceDefinition {
+        name: "test".to_string(),
+        description: "".to_string(),
+        target_hz: 1000,
+        plugins: vec![PluginDefinition {
+            id: 1,
+            kind: "scale".to_string(),
+            config: json!({ "warmup_ticks": 20 }),
+            priority: 0,
+            running: true,
+        }],
+        connections: vec![],
+        settings: WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+    logic_tx.send(LogicMessage::UpdateWorkspace(workspace)).unwrap();
+    let wait_for = |warming_up: bool| {
+        let deadline = Instant::now() + Duration::from_secs(2);
+        loop {
+            let state = logic_state_rx
+                .recv_timeout(Duration::from_secs(2))
+                .expect("did not receive runtime state in time");
+            if state.warming_up.contains(&1) == warming_up {
+                break;
+            }
+            assert!(Instant::now() < deadline, "warming_up never became {warming_up}");
+        }
+    };
+
+    wait_for(true);
+    wait_for(false);
+    logic_tx.send(LogicMessage::RestartPlugin(1)).unwrap();
+    wait_for(true);
+}
+
+#[test]
+fn connection_latency_is_reported_per_connection_while_tracked() {
+    let (logic_tx, logic_state_rx) = spawn_runtime().expect("failed to spawn runtime");
+    let scale = |id: u64| PluginDefinition {
+        id,
+        kind: "scale".to_string(),
+        config: json!({ "mode": "gain_offset", "gain": 1.0, "offset": 0.0 }),
+        priority: 0,
+        running: true,
+    };
+    let connection = |from: u64, to: u64, kind: &str| ConnectionDefinition {
+        from_plugin: from,
+        from_port: "out".to_string(),
+        to_plugin: to,
+        to_port: "in".to_string(),
+        kind: kind.to_string(),
+        gain: 1.0,
+    };
+    let workspace = WorkspaceDefinition {
+        name: "test".to_string(),
+        description: "".to_string(),
+        target_hz: 1000,
+        plugins: vec![scale(1), scale(2), scale(3)],
+        connections: vec![connection(1, 2, "pipe"), connection(2, 3, "shared_memory")],
+        settings: WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+    logic_tx.send(LogicMessage::UpdateWorkspace(workspace)).unwrap();
+    let wait_ticks = || {
+        for _ in 0..2 {
+            logic_state_rx
+                .recv_timeout(Duration::from_secs(2))
+                .expect("did not receive runtime state in time");
+        }
+    };
+    let query = || {
+        let (tx, rx) = std::sync::mpsc::channel();
+        logic_tx.send(LogicMessage::QueryConnectionLatency(tx)).unwrap();
+        rx.recv_timeout(Duration::from_secs(2)).unwrap()
+    };
+    wait_ticks();
+    assert!(query().is_empty(), "measured without tracking");
+
+    logic_tx.send(LogicMessage::TrackConnectionLatency(true)).unwrap();
+    wait_ticks();
+    let stats = query();
+    assert_eq!(stats.len(), 2);
+    for (connection, stats) in &stats {
+        assert!(connection.to_plugin == connection.from_plugin + 1);
+        assert!(stats.samples > 0);
+        assert!(stats.mean <= stats.max && stats.p99 <= stats.max);
+    }
+}
+
+#[test]
+fn benchmark_times_process_calls_of_a_plugin_library() {
+    let lib_path = find_cdylib("mock_out_5_rs_runtime");
+    let lib_path = lib_path.to_string_lossy();
+    let report = benchmark_plugin(&lib_path, &json!({}), 200).expect("benchmark");
+    assert_eq!(report.iterations, 200);
+    assert!(report.min <= report.mean && report.mean <= report.max);
+    assert!(report.p99 <= report.max);
+    // The allocation probe is not preloaded into the test binary
+    assert_eq!(report.allocations, None);
+
+    assert!(benchmark_plugin(&lib_path, &json!({}), 0).is_err());
+    assert!(benchmark_plugin(&lib_path, &json!([]), 10).is_err());
+    assert!(benchmark_plugin("/nonexistent/libplugin.so", &json!({}), 10).is_err());
+}
+
+#[test]
+fn self_test_reports_per_plugin_outcome() {
+    let (logic_tx, logic_state_rx) = spawn_runtime().expect("failed to spawn runtime");
+    let filter = |id: u64, a: serde_json::Value| PluginDefinition {
+        id,
+        kind: "transfer_function".to_string(),
+        config: json!({ "b": [0.5, 0.5], "a": a }),
+        priority: 0,
+        running: true,
+    };
+    let workspace = WorkspaceDefinition {
+        name: "test".to_string(),
+        description: "".to_string(),
+        target_hz: 1000,
+        plugins: vec![filter(1, json!([1.0, -0.5])), filter(2, json!([0.0]))],
+        connections: Vec::new(),
+        settings: WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+    logic_tx.send(LogicMessage::UpdateWorkspace(workspace)).unwrap();
+    // Configs are applied on the first tick
+    let deadline = Instant::now() + Duration::from_secs(2);
+    loop {
+        let state = logic_state_rx
+            .recv_timeout(Duration::from_secs(2))
+            .expect("did not receive runtime state in time");
+        if state.outputs.contains_key(&(1, "out".to_string())) {
+            break;
+        }
+        assert!(Instant::now() < deadline, "filters did not tick");
+    }
+
+    let self_test = |plugin_id: u64| {
+        let (tx, rx) = std::sync::mpsc::channel();
+        logic_tx.send(LogicMessage::RunSelfTest(plugin_id, tx)).unwrap();
+        rx.recv_timeout(Duration::from_secs(2))
+            .expect("self-test did not answer in time")
+    };
+    assert_eq!(self_test(1), Ok(()));
+    assert_eq!(self_test(2), Err("a[0] must not be 0".to_string()));
+    assert_eq!(self_test(9), Err("plugin 9 is not loaded".to_string()));
+}
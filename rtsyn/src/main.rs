@@ -1,7 +1,12 @@
 use clap::{Parser, Subcommand};
 use rtsyn_gui::{run_gui, GuiConfig};
 use rtsyn_cli::{client, daemon, protocol::{DaemonRequest, DaemonResponse, DEFAULT_SOCKET_PATH}};
-use std::process::{Command, Stdio};
+use rtsyn_runtime::benchmark::{allocation_probe_loaded, allocation_probe_path, benchmark_plugin};
+use std::process::{Command, ExitStatus, Stdio};
+
+/// Set on the benchmark child started with the allocation probe preloaded,
+/// so it never starts another one.
+const ALLOC_PROBE_CHILD_ENV: &str = "RTSYN_ALLOC_PROBE_CHILD";
 
 #[derive(Parser)]
 #[command(name = "rtsyn", version, about = "RTSyn MVP CLI")]
@@ -16,6 +21,17 @@ enum Commands {
         #[command(subcommand)]
         command: DaemonCommands,
     },
+    /// Times a plugin library's process() without a workspace.
+    Benchmark {
+        library: String,
+        #[arg(long, default_value = "{}")]
+        config: String,
+        #[arg(long, default_value_t = 10_000)]
+        iterations: usize,
+        /// Prints the report as JSON.
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -83,6 +99,8 @@ enum ConnectionCommands {
         to_port: String,
         #[arg(long, default_value = "shared_memory")]
         kind: String,
+        #[arg(long, default_value_t = 1.0)]
+        gain: f64,
     },
     Remove {
         #[arg(long)]
@@ -145,6 +163,49 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         None => {
             run_gui(GuiConfig::default())?;
         }
+        Some(Commands::Benchmark {
+            library,
+            config,
+            iterations,
+            json,
+        }) => {
+            if let Some(status) = rerun_with_allocation_probe() {
+                std::process::exit(status.code().unwrap_or(1));
+            }
+            let config = match serde_json::from_str(&config) {
+                Ok(config) => config,
+                Err(err) => {
+                    eprintln!("[RTSyn][ERROR]: Invalid config JSON: {err}");
+                    std::process::exit(1);
+                }
+            };
+            match benchmark_plugin(&library, &config, iterations) {
+                Ok(report) if json => {
+                    let json = serde_json::to_string_pretty(&report)
+                        .unwrap_or_else(|_| "{}".to_string());
+                    println!("{json}");
+                }
+                Ok(report) => {
+                    println!("[RTSyn][INFO] {} process() calls:", report.iterations);
+                    println!("min: {:?}", report.min);
+                    println!("mean: {:?}", report.mean);
+                    println!("p99: {:?}", report.p99);
+                    println!("max: {:?}", report.max);
+                    match (report.allocations, report.allocated_bytes) {
+                        (Some(count), Some(bytes)) => {
+                            println!("allocations: {count} ({bytes} bytes)")
+                        }
+                        _ => println!(
+                            "allocations: not measurable for this library (allocation probe not loaded)"
+                        ),
+                    }
+                }
+                Err(err) => {
+                    eprintln!("[RTSyn][ERROR]: {err}");
+                    std::process::exit(1);
+                }
+            }
+        }
         Some(Commands::Daemon { command }) => match command {
             DaemonCommands::Run { detach } => {
                 if detach {
@@ -294,12 +355,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         to_plugin,
                         to_port,
                         kind,
+                        gain,
                     } => DaemonRequest::ConnectionAdd {
                         from_plugin,
                         from_port,
                         to_plugin,
                         to_port,
                         kind,
+                        gain,
                     },
                     ConnectionCommands::Remove {
                         from_plugin,
@@ -326,14 +389,20 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             } else {
                                 println!("[RTSyn][INFO] List of connections:");
                                 for conn in connections {
+                                    let gain = if conn.gain == 1.0 {
+                                        String::new()
+                                    } else {
+                                        format!(" x{}", conn.gain)
+                                    };
                                     println!(
-                                        "[{}] {}:{} -> {}:{} ({})",
+                                        "[{}] {}:{} -> {}:{} ({}){}",
                                         conn.index,
                                         conn.from_plugin,
                                         conn.from_port,
                                         conn.to_plugin,
                                         conn.to_port,
-                                        conn.kind
+                                        conn.kind,
+                                        gain
                                     );
                                 }
                             }
@@ -448,6 +517,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             println!("period_value: {}", settings.period_value);
                             println!("period_unit: {}", settings.period_unit);
                             println!("selected_cores: {:?}", settings.selected_cores);
+                            if settings.jitter_us > 0.0 {
+                                println!("jitter_us: {} (injected)", settings.jitter_us);
+                            }
                         }
                         DaemonResponse::RuntimeSettingsOptions { options } => {
                             if settings_json_query {
@@ -465,6 +537,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 "max_integration_steps: {}..={}",
                                 options.max_integration_steps_min, options.max_integration_steps_max
                             );
+                            println!(
+                                "missed_deadline_policy: {}",
+                                options.missed_deadline_policies.join(", ")
+                            );
                         }
                         DaemonResponse::RuntimeShow { id, kind, state } => {
                             println!("[RTSyn][INFO] {id} - {kind}");
@@ -511,6 +587,27 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Runs this `rtsyn benchmark` again in a child with the allocation probe
+/// preloaded, so that the plugin's allocations are counted. `None` when the
+/// probe is already loaded, was not built next to the binary, or this is
+/// already the child.
+fn rerun_with_allocation_probe() -> Option<ExitStatus> {
+    if allocation_probe_loaded() || std::env::var_os(ALLOC_PROBE_CHILD_ENV).is_some() {
+        return None;
+    }
+    let probe = allocation_probe_path()?;
+    let preload = match std::env::var("LD_PRELOAD") {
+        Ok(existing) if !existing.is_empty() => format!("{}:{existing}", probe.display()),
+        _ => probe.display().to_string(),
+    };
+    Command::new(std::env::current_exe().ok()?)
+        .args(std::env::args_os().skip(1))
+        .env("LD_PRELOAD", preload)
+        .env(ALLOC_PROBE_CHILD_ENV, "1")
+        .status()
+        .ok()
+}
+
 fn spawn_detached_daemon() -> Result<(), String> {
     let socket_path = std::path::Path::new(DEFAULT_SOCKET_PATH);
     if socket_path.exists() {
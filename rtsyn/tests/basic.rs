@@ -536,8 +536,20 @@ fn connection_add_validates_ports() {
         let _ = std::fs::remove_file(SOCKET_PATH);
     }
 
+    // The daemon saves into `app_workspaces` under its working directory, so
+    // it runs on a copy of the fixture to leave the tracked file untouched.
+    let temp_dir = tempfile::tempdir().expect("tempdir");
+    let workspace_dir = temp_dir.path().join("app_workspaces");
+    std::fs::create_dir_all(&workspace_dir).expect("create workspace dir");
+    std::fs::copy(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("app_workspaces/test_connections.json"),
+        workspace_dir.join("test_connections.json"),
+    )
+    .expect("copy workspace fixture");
+
     let mut child = Command::new(exe)
         .args(["daemon", "run"])
+        .current_dir(temp_dir.path())
         .spawn()
         .expect("run rtsyn daemon");
     assert!(wait_for_daemon(exe));
@@ -1,6 +1,10 @@
 use crate::{ConnectionDefinition, PluginDefinition};
 use std::collections::{HashMap, HashSet};
 
+/// Sum of the connections feeding `port` of `plugin_id`, each scaled by its
+/// gain. Terms are added in slice order; sort the connections with
+/// [`sort_connections_for_evaluation`] first for a result that does not
+/// depend on the order they were created in.
 pub fn input_sum(
     connections: &[ConnectionDefinition],
     outputs: &HashMap<(u64, String), f64>,
@@ -13,7 +17,7 @@ pub fn input_sum(
             if let Some(output) =
                 outputs.get(&(connection.from_plugin, connection.from_port.clone()))
             {
-                value += output;
+                value += connection.gain * output;
             }
         }
     }
@@ -33,6 +37,62 @@ pub fn input_sum_any(
     total
 }
 
+/// Value an unconnected `port` reads, declared by the plugin's
+/// `<port>_default` config entry.
+pub fn input_default(config: &serde_json::Value, port: &str) -> Option<f64> {
+    config
+        .get(format!("{port}_default"))
+        .and_then(|value| value.as_f64())
+}
+
+/// [`input_sum`] for a connected input; otherwise the input's declared
+/// default, or 0.0 without one.
+pub fn input_value(
+    connections: &[ConnectionDefinition],
+    outputs: &HashMap<(u64, String), f64>,
+    plugin: &PluginDefinition,
+    port: &str,
+) -> f64 {
+    input_value_any(connections, outputs, plugin, &[port.to_string()])
+}
+
+/// [`input_sum_any`] when any of `ports` is connected; otherwise the first
+/// declared default among them, or 0.0 without one.
+pub fn input_value_any(
+    connections: &[ConnectionDefinition],
+    outputs: &HashMap<(u64, String), f64>,
+    plugin: &PluginDefinition,
+    ports: &[String],
+) -> f64 {
+    let connected = connections
+        .iter()
+        .any(|conn| conn.to_plugin == plugin.id && ports.contains(&conn.to_port));
+    if connected {
+        return input_sum_any(connections, outputs, plugin.id, ports);
+    }
+    ports
+        .iter()
+        .find_map(|port| input_default(&plugin.config, port))
+        .unwrap_or(0.0)
+}
+
+/// Orders connections by target, then source, so every input sums its
+/// sources in the same order however the workspace was edited.
+pub fn sort_connections_for_evaluation(connections: &mut [ConnectionDefinition]) {
+    connections.sort_by(|a, b| {
+        (a.to_plugin, &a.to_port, a.from_plugin, &a.from_port)
+            .cmp(&(b.to_plugin, &b.to_port, b.from_plugin, &b.from_port))
+    });
+}
+
+/// Execution order for one tick.
+///
+/// Plugins run by ascending `priority`, so a lower value always runs first
+/// regardless of how plugins are wired. Within a priority, plugins are
+/// ordered so that a connection's source runs before its target, with ties
+/// broken by ascending plugin id; a feedforward chain therefore propagates
+/// within a single tick. A cycle is broken by running its lowest-id plugin
+/// first, which keeps plugins downstream of the cycle after their sources.
 pub fn order_plugins_for_execution(
     plugins: &[PluginDefinition],
     connections: &[ConnectionDefinition],
@@ -56,6 +116,7 @@ pub fn order_plugins_for_execution(
         let ids: HashSet<u64> = group.iter().map(|p| p.id).collect();
         let mut indegree: HashMap<u64, usize> = ids.iter().map(|id| (*id, 0)).collect();
         let mut edges: HashMap<u64, Vec<u64>> = HashMap::new();
+        let mut reverse_edges: HashMap<u64, Vec<u64>> = HashMap::new();
 
         for conn in connections {
             if ids.contains(&conn.from_plugin) && ids.contains(&conn.to_plugin) {
@@ -63,6 +124,10 @@ pub fn order_plugins_for_execution(
                     .entry(conn.from_plugin)
                     .or_default()
                     .push(conn.to_plugin);
+                reverse_edges
+                    .entry(conn.to_plugin)
+                    .or_default()
+                    .push(conn.from_plugin);
                 if let Some(count) = indegree.get_mut(&conn.to_plugin) {
                     *count += 1;
                 }
@@ -77,8 +142,27 @@ pub fn order_plugins_for_execution(
         ready.sort();
 
         let mut ordered_ids: Vec<u64> = Vec::new();
-        while let Some(id) = ready.first().copied() {
-            ready.remove(0);
+        let mut placed: HashSet<u64> = HashSet::new();
+        loop {
+            let next = if ready.is_empty() {
+                // Everything left waits on a cycle. Release the lowest id of
+                // a cycle nothing else feeds: all of its upstream plugins
+                // are also downstream of it.
+                let waiting = || group.iter().map(|p| p.id).filter(|id| !placed.contains(id));
+                waiting()
+                    .find(|id| {
+                        reach(*id, &reverse_edges, &placed).is_subset(&reach(*id, &edges, &placed))
+                    })
+                    .or_else(|| waiting().next())
+            } else {
+                Some(ready.remove(0))
+            };
+            let Some(id) = next else {
+                break;
+            };
+            if !placed.insert(id) {
+                continue;
+            }
             ordered_ids.push(id);
             if let Some(children) = edges.get(&id) {
                 for child in children {
@@ -95,23 +179,6 @@ pub fn order_plugins_for_execution(
             }
         }
 
-        let mut remaining: Vec<u64> = ids
-            .iter()
-            .filter(|id| !ordered_ids.contains(id))
-            .copied()
-            .collect();
-        let mut out_degree: HashMap<u64, usize> = HashMap::new();
-        for id in &remaining {
-            let count = edges.get(id).map(|v| v.len()).unwrap_or(0);
-            out_degree.insert(*id, count);
-        }
-        remaining.sort_by(|a, b| {
-            let a_sink = out_degree.get(a).copied().unwrap_or(0) == 0;
-            let b_sink = out_degree.get(b).copied().unwrap_or(0) == 0;
-            a_sink.cmp(&b_sink).then_with(|| a.cmp(b))
-        });
-        ordered_ids.extend(remaining);
-
         for id in ordered_ids {
             if let Some(plugin) = group.iter().find(|p| p.id == id) {
                 ordered.push(plugin.clone());
@@ -121,3 +188,17 @@ pub fn order_plugins_for_execution(
 
     ordered
 }
+
+/// Plugins reachable from `start` along `edges`, skipping placed ones.
+fn reach(start: u64, edges: &HashMap<u64, Vec<u64>>, placed: &HashSet<u64>) -> HashSet<u64> {
+    let mut seen = HashSet::new();
+    let mut stack = vec![start];
+    while let Some(id) = stack.pop() {
+        for next in edges.get(&id).into_iter().flatten() {
+            if !placed.contains(next) && seen.insert(*next) {
+                stack.push(*next);
+            }
+        }
+    }
+    seen
+}
@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
 
 pub mod execution;
-pub use execution::{input_sum, input_sum_any, order_plugins_for_execution};
+pub use execution::{
+    input_default, input_sum, input_sum_any, input_value, input_value_any,
+    order_plugins_for_execution, sort_connections_for_evaluation,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkspaceDefinition {
@@ -14,6 +18,34 @@ pub struct WorkspaceDefinition {
     pub connections: Vec<ConnectionDefinition>,
     #[serde(default)]
     pub settings: WorkspaceSettings,
+    /// Shared values that plugin config fields reference as `"$name"`, see
+    /// [`WorkspaceDefinition::resolve_parameters`].
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub parameters: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub metadata: WorkspaceMetadata,
+}
+
+/// Who made a workspace and when, with free-form notes, for finding an
+/// experiment again later. Times are Unix seconds, zero when unknown, as in
+/// files saved before they were recorded.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceMetadata {
+    pub author: String,
+    pub created_at: u64,
+    pub modified_at: u64,
+    pub notes: String,
+}
+
+impl WorkspaceMetadata {
+    /// Stamps a save at `now`; `created_at` is only set when unknown.
+    pub fn mark_saved(&mut self, now: u64) {
+        if self.created_at == 0 {
+            self.created_at = now;
+        }
+        self.modified_at = now;
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +55,16 @@ pub struct WorkspaceSettings {
     pub period_value: f64,
     pub period_unit: String,
     pub selected_cores: Vec<usize>,
+    #[serde(default)]
+    pub missed_deadline_policy: MissedDeadlinePolicy,
+    /// Bound in microseconds of a random offset injected into every tick,
+    /// to test controllers against imperfect timing. Zero disables it.
+    #[serde(default)]
+    pub jitter_us: f64,
+    /// Guards the workspace against accidental edits; plugins can still be
+    /// started and stopped.
+    #[serde(default)]
+    pub locked: bool,
 }
 
 impl Default for WorkspaceSettings {
@@ -33,6 +75,55 @@ impl Default for WorkspaceSettings {
             period_value: 1.0,
             period_unit: "ms".to_string(),
             selected_cores: vec![0],
+            missed_deadline_policy: MissedDeadlinePolicy::default(),
+            jitter_us: 0.0,
+            locked: false,
+        }
+    }
+}
+
+/// What the runtime does when a tick finishes after the next one was due.
+///
+/// Every policy sleeps until absolute deadlines one period apart, so ticks
+/// that finish in time never drift. `Skip` keeps ticks aligned to the wall
+/// clock: missed ticks are never processed, but the tick counter still
+/// advances over them, so tick-based time (such as the CSV recorder's time
+/// column) stays correct while integrating plugins lose those steps.
+/// `CatchUp` processes every missed tick back to back, keeping the number of
+/// simulated steps exact at the cost of bursts (and a growing backlog if the
+/// workload never fits in the period). `BestEffort` restarts the deadlines
+/// from the end of a late tick, so simulated time falls behind wall time by
+/// the overruns only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MissedDeadlinePolicy {
+    Skip,
+    CatchUp,
+    #[default]
+    BestEffort,
+}
+
+impl MissedDeadlinePolicy {
+    pub const ALL: [MissedDeadlinePolicy; 3] = [
+        MissedDeadlinePolicy::Skip,
+        MissedDeadlinePolicy::CatchUp,
+        MissedDeadlinePolicy::BestEffort,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "skip" => Some(MissedDeadlinePolicy::Skip),
+            "catch_up" => Some(MissedDeadlinePolicy::CatchUp),
+            "best_effort" => Some(MissedDeadlinePolicy::BestEffort),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MissedDeadlinePolicy::Skip => "skip",
+            MissedDeadlinePolicy::CatchUp => "catch_up",
+            MissedDeadlinePolicy::BestEffort => "best_effort",
         }
     }
 }
@@ -59,6 +150,13 @@ pub struct ConnectionDefinition {
     pub to_plugin: u64,
     pub to_port: String,
     pub kind: String,
+    /// Factor applied to the source output before it is summed into the input.
+    #[serde(default = "default_gain")]
+    pub gain: f64,
+}
+
+fn default_gain() -> f64 {
+    1.0
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -99,33 +197,71 @@ pub fn validate_connection(
     Ok(())
 }
 
-pub fn add_connection(
-    connections: &mut Vec<ConnectionDefinition>,
-    connection: ConnectionDefinition,
+/// Checks whether `candidate` may be added to `connections` without adding
+/// it, so callers can preview a connection before committing it.
+pub fn can_add_connection(
+    connections: &[ConnectionDefinition],
+    candidate: &ConnectionDefinition,
     max_per_input: usize,
 ) -> Result<(), ConnectionRuleError> {
     validate_connection(
         connections,
-        connection.from_plugin,
-        connection.to_plugin,
-        &connection.to_port,
+        candidate.from_plugin,
+        candidate.to_plugin,
+        &candidate.to_port,
         max_per_input,
     )?;
     
     // Check if same output is already connected to a different input of the same target plugin
     if connections.iter().any(|conn| {
-        conn.from_plugin == connection.from_plugin
-            && conn.from_port == connection.from_port
-            && conn.to_plugin == connection.to_plugin
-            && conn.to_port != connection.to_port
+        conn.from_plugin == candidate.from_plugin
+            && conn.from_port == candidate.from_port
+            && conn.to_plugin == candidate.to_plugin
+            && conn.to_port != candidate.to_port
     }) {
         return Err(ConnectionRuleError::DuplicateConnection);
     }
-    
+    Ok(())
+}
+
+pub fn add_connection(
+    connections: &mut Vec<ConnectionDefinition>,
+    connection: ConnectionDefinition,
+    max_per_input: usize,
+) -> Result<(), ConnectionRuleError> {
+    can_add_connection(connections, &connection, max_per_input)?;
     connections.push(connection);
     Ok(())
 }
 
+/// Connections fed by output `port` of `plugin_id`, in workspace order.
+pub fn fan_out<'a>(
+    connections: &'a [ConnectionDefinition],
+    plugin_id: u64,
+    port: &'a str,
+) -> impl Iterator<Item = &'a ConnectionDefinition> + 'a {
+    connections
+        .iter()
+        .filter(move |conn| conn.from_plugin == plugin_id && conn.from_port == port)
+}
+
+/// Removes the connections matching `connection` on both ends and in kind.
+/// Other branches fed by the same output are left in place.
+pub fn remove_connection(
+    connections: &mut Vec<ConnectionDefinition>,
+    connection: &ConnectionDefinition,
+) -> bool {
+    let before = connections.len();
+    connections.retain(|conn| {
+        !(conn.from_plugin == connection.from_plugin
+            && conn.from_port == connection.from_port
+            && conn.to_plugin == connection.to_plugin
+            && conn.to_port == connection.to_port
+            && conn.kind == connection.kind)
+    });
+    connections.len() != before
+}
+
 pub fn prune_extendable_inputs_plugin_connections(
     connections: &mut Vec<ConnectionDefinition>,
     recorder_id: u64,
@@ -187,7 +323,249 @@ pub fn remove_extendable_input(
     changed
 }
 
+/// Swaps the connections feeding inputs `a` and `b` of an extendable-input plugin.
+pub fn swap_extendable_inputs(
+    connections: &mut [ConnectionDefinition],
+    plugin_id: u64,
+    a: usize,
+    b: usize,
+) -> bool {
+    if a == b {
+        return false;
+    }
+    let mut changed = false;
+    for conn in connections.iter_mut().filter(|conn| conn.to_plugin == plugin_id) {
+        let input_idx = if conn.to_port == "in" {
+            Some(0)
+        } else {
+            conn.to_port
+                .strip_prefix("in_")
+                .and_then(|value| value.parse::<usize>().ok())
+        };
+        match input_idx {
+            Some(idx) if idx == a => {
+                conn.to_port = format!("in_{b}");
+                changed = true;
+            }
+            Some(idx) if idx == b => {
+                conn.to_port = format!("in_{a}");
+                changed = true;
+            }
+            _ => {}
+        }
+    }
+    changed
+}
+
+/// One incremental change to a workspace, applied by the runtime without
+/// reloading the whole definition.
+#[derive(Debug, Clone)]
+pub enum WorkspaceEdit {
+    AddPlugin(PluginDefinition),
+    RemovePlugin(u64),
+    UpdatePluginConfig(u64, serde_json::Value),
+    SetPluginRunning(u64, bool),
+    AddConnection(ConnectionDefinition),
+    RemoveConnection(ConnectionDefinition),
+}
+
+fn same_connection(a: &ConnectionDefinition, b: &ConnectionDefinition) -> bool {
+    a.from_plugin == b.from_plugin
+        && a.from_port == b.from_port
+        && a.to_plugin == b.to_plugin
+        && a.to_port == b.to_port
+        && a.kind == b.kind
+        && a.gain == b.gain
+}
+
+/// The edits that turn `old` into `new`, or `None` when the change cannot be
+/// expressed as edits (workspace fields, plugin kind or priority, plugin
+/// order) and the whole workspace must be sent again.
+pub fn workspace_edits(
+    old: &WorkspaceDefinition,
+    new: &WorkspaceDefinition,
+) -> Option<Vec<WorkspaceEdit>> {
+    if old.name != new.name
+        || old.description != new.description
+        || old.target_hz != new.target_hz
+        || serde_json::to_value(&old.settings).ok() != serde_json::to_value(&new.settings).ok()
+    {
+        return None;
+    }
+    let kept = |plugins: &[PluginDefinition], other: &[PluginDefinition]| -> Vec<u64> {
+        plugins
+            .iter()
+            .filter(|p| other.iter().any(|o| o.id == p.id))
+            .map(|p| p.id)
+            .collect()
+    };
+    if kept(&old.plugins, &new.plugins) != kept(&new.plugins, &old.plugins) {
+        return None;
+    }
+
+    let mut edits = Vec::new();
+    for plugin in &old.plugins {
+        if !new.plugins.iter().any(|p| p.id == plugin.id) {
+            edits.push(WorkspaceEdit::RemovePlugin(plugin.id));
+        }
+    }
+    for plugin in &new.plugins {
+        let Some(before) = old.plugins.iter().find(|p| p.id == plugin.id) else {
+            edits.push(WorkspaceEdit::AddPlugin(plugin.clone()));
+            continue;
+        };
+        if before.kind != plugin.kind || before.priority != plugin.priority {
+            return None;
+        }
+        if before.config != plugin.config {
+            edits.push(WorkspaceEdit::UpdatePluginConfig(plugin.id, plugin.config.clone()));
+        }
+        if before.running != plugin.running {
+            edits.push(WorkspaceEdit::SetPluginRunning(plugin.id, plugin.running));
+        }
+    }
+    let removed_plugin = |id: u64| !new.plugins.iter().any(|p| p.id == id);
+    for conn in &old.connections {
+        // Connections of removed plugins go with them
+        if removed_plugin(conn.from_plugin) || removed_plugin(conn.to_plugin) {
+            continue;
+        }
+        if !new.connections.iter().any(|c| same_connection(c, conn)) {
+            edits.push(WorkspaceEdit::RemoveConnection(conn.clone()));
+        }
+    }
+    for conn in &new.connections {
+        if !old.connections.iter().any(|c| same_connection(c, conn)) {
+            edits.push(WorkspaceEdit::AddConnection(conn.clone()));
+        }
+    }
+    Some(edits)
+}
+
+/// Prefix of plugin config keys only the GUI reads, such as a card's label
+/// or collapsed state. Changing them never reaches the runtime.
+pub const GUI_ONLY_KEY_PREFIX: &str = "_ui_";
+
+/// Name of the workspace parameter a config value refers to, for values
+/// written as `"$name"`.
+pub fn parameter_reference(value: &serde_json::Value) -> Option<&str> {
+    value
+        .as_str()?
+        .strip_prefix('$')
+        .filter(|name| !name.is_empty())
+}
+
 impl WorkspaceDefinition {
+    /// A copy with every top-level config value that references a defined
+    /// parameter replaced by the parameter's value, and without the GUI-only
+    /// keys starting with [`GUI_ONLY_KEY_PREFIX`]. This is what the runtime
+    /// is given; the workspace itself keeps the references. References to
+    /// unknown parameters are left as they are.
+    pub fn resolve_parameters(&self) -> WorkspaceDefinition {
+        let mut resolved = self.clone();
+        for plugin in &mut resolved.plugins {
+            let serde_json::Value::Object(map) = &mut plugin.config else {
+                continue;
+            };
+            map.retain(|key, _| !key.starts_with(GUI_ONLY_KEY_PREFIX));
+            for value in map.values_mut() {
+                if let Some(parameter) = parameter_reference(value).and_then(|name| self.parameters.get(name)) {
+                    *value = serde_json::Value::from(*parameter);
+                }
+            }
+        }
+        resolved
+    }
+
+    /// A copy with plugin ids renumbered from 1 in workspace order and every
+    /// connection endpoint remapped to match. Connections to plugins that
+    /// are not in the workspace are dropped.
+    pub fn renumbered(&self) -> WorkspaceDefinition {
+        let ids: HashMap<u64, u64> = self
+            .plugins
+            .iter()
+            .zip(1..)
+            .map(|(plugin, id)| (plugin.id, id))
+            .collect();
+        let mut copy = self.clone();
+        for plugin in &mut copy.plugins {
+            plugin.id = ids[&plugin.id];
+        }
+        copy.connections = self
+            .connections
+            .iter()
+            .filter_map(|conn| {
+                Some(ConnectionDefinition {
+                    from_plugin: *ids.get(&conn.from_plugin)?,
+                    to_plugin: *ids.get(&conn.to_plugin)?,
+                    ..conn.clone()
+                })
+            })
+            .collect();
+        copy
+    }
+
+    /// The signal graph in Graphviz DOT: a node per plugin, labeled with its
+    /// kind and id, and an edge per connection, labeled with its ports.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_labeled(|plugin| plugin.kind.clone())
+    }
+
+    /// [`Self::to_dot`] with node names from `label`, e.g. display names.
+    /// The kind is added below a name that differs from it. Connections
+    /// other than `shared_memory` show their kind, and scaled ones their
+    /// gain.
+    pub fn to_dot_labeled(&self, label: impl Fn(&PluginDefinition) -> String) -> String {
+        fn quoted(text: &str) -> String {
+            let escaped = text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+            format!("\"{escaped}\"")
+        }
+        let mut dot = format!("digraph {} {{\n", quoted(&self.name));
+        dot.push_str("    rankdir=LR;\n    node [shape=box];\n");
+        for plugin in &self.plugins {
+            let name = label(plugin);
+            let mut text = format!("{name} #{}", plugin.id);
+            if name != plugin.kind {
+                text.push('\n');
+                text.push_str(&plugin.kind);
+            }
+            dot.push_str(&format!("    p{} [label={}];\n", plugin.id, quoted(&text)));
+        }
+        for conn in &self.connections {
+            let mut text = format!("{} -> {}", conn.from_port, conn.to_port);
+            if conn.kind != "shared_memory" {
+                text.push_str(&format!(" [{}]", conn.kind));
+            }
+            if conn.gain != 1.0 {
+                text.push_str(&format!(" x{}", conn.gain));
+            }
+            dot.push_str(&format!(
+                "    p{} -> p{} [label={}];\n",
+                conn.from_plugin,
+                conn.to_plugin,
+                quoted(&text)
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Config fields referencing parameter `name`, as `(plugin id, key)`.
+    pub fn parameter_uses(&self, name: &str) -> Vec<(u64, String)> {
+        let mut uses = Vec::new();
+        for plugin in &self.plugins {
+            let Some(map) = plugin.config.as_object() else {
+                continue;
+            };
+            for (key, value) in map {
+                if parameter_reference(value) == Some(name) {
+                    uses.push((plugin.id, key.clone()));
+                }
+            }
+        }
+        uses
+    }
+
     pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), WorkspaceError> {
         let data = serde_json::to_vec_pretty(self)?;
         fs::write(path, data)?;
@@ -1,6 +1,8 @@
 use std::fs;
 use std::time::{SystemTime, UNIX_EPOCH};
-use workspace::{ConnectionDefinition, PluginDefinition, WorkspaceDefinition, WorkspaceSettings};
+use workspace::{
+    ConnectionDefinition, PluginDefinition, WorkspaceDefinition, WorkspaceMetadata, WorkspaceSettings,
+};
 
 #[test]
 fn save_and_load_workspace() {
@@ -28,8 +30,11 @@ fn save_and_load_workspace() {
             to_plugin: 2,
             to_port: "in".to_string(),
             kind: "shared_memory".to_string(),
+            gain: 1.0,
         }],
         settings: WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
     };
 
     workspace.save_to_file(&path).unwrap();
@@ -48,6 +53,50 @@ fn save_and_load_workspace() {
     fs::remove_file(&path).unwrap();
 }
 
+#[test]
+fn workspace_lock_defaults_to_unlocked_and_round_trips() {
+    let legacy = r#"{
+        "frequency_value": 1000.0,
+        "frequency_unit": "hz",
+        "period_value": 1.0,
+        "period_unit": "ms",
+        "selected_cores": [0]
+    }"#;
+    let settings: WorkspaceSettings = serde_json::from_str(legacy).unwrap();
+    assert!(!settings.locked);
+
+    let locked = WorkspaceSettings {
+        locked: true,
+        ..WorkspaceSettings::default()
+    };
+    let json = serde_json::to_string(&locked).unwrap();
+    let loaded: WorkspaceSettings = serde_json::from_str(&json).unwrap();
+    assert!(loaded.locked);
+}
+
+#[test]
+fn workspace_metadata_defaults_for_old_files_and_round_trips() {
+    let legacy = r#"{
+        "name": "old",
+        "description": "",
+        "target_hz": 1000,
+        "plugins": [],
+        "connections": []
+    }"#;
+    let mut workspace: WorkspaceDefinition = serde_json::from_str(legacy).unwrap();
+    assert_eq!(workspace.metadata, WorkspaceMetadata::default());
+
+    workspace.metadata.author = "Ada".to_string();
+    workspace.metadata.notes = "Rig B, 20 C".to_string();
+    workspace.metadata.mark_saved(100);
+    workspace.metadata.mark_saved(250);
+    assert_eq!((workspace.metadata.created_at, workspace.metadata.modified_at), (100, 250));
+
+    let json = serde_json::to_string(&workspace).unwrap();
+    let loaded: WorkspaceDefinition = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.metadata, workspace.metadata);
+}
+
 #[test]
 fn connection_rules() {
     let mut connections = vec![ConnectionDefinition {
@@ -56,6 +105,7 @@ fn connection_rules() {
         to_plugin: 2,
         to_port: "in_0".to_string(),
         kind: "shared_memory".to_string(),
+        gain: 1.0,
     }];
 
     let err = workspace::validate_connection(&connections, 2, 2, "in_0", 2).unwrap_err();
@@ -67,6 +117,7 @@ fn connection_rules() {
         to_plugin: 2,
         to_port: "in_0".to_string(),
         kind: "shared_memory".to_string(),
+        gain: 1.0,
     });
 
     let err = workspace::validate_connection(&connections, 4, 2, "in_0", 2).unwrap_err();
@@ -82,6 +133,7 @@ fn prune_extendable_inputs_plugin_connections_removes_excess_inputs() {
             to_plugin: 99,
             to_port: "in_0".to_string(),
             kind: "shared_memory".to_string(),
+            gain: 1.0,
         },
         ConnectionDefinition {
             from_plugin: 2,
@@ -89,6 +141,7 @@ fn prune_extendable_inputs_plugin_connections_removes_excess_inputs() {
             to_plugin: 99,
             to_port: "in_2".to_string(),
             kind: "shared_memory".to_string(),
+            gain: 1.0,
         },
     ];
 
@@ -97,6 +150,42 @@ fn prune_extendable_inputs_plugin_connections_removes_excess_inputs() {
     assert_eq!(connections[0].to_port, "in_0");
 }
 
+#[test]
+fn swap_extendable_inputs_moves_connections_between_ports() {
+    let mut connections = vec![
+        ConnectionDefinition {
+            from_plugin: 1,
+            from_port: "out".to_string(),
+            to_plugin: 99,
+            to_port: "in_0".to_string(),
+            kind: "shared_memory".to_string(),
+            gain: 1.0,
+        },
+        ConnectionDefinition {
+            from_plugin: 2,
+            from_port: "out".to_string(),
+            to_plugin: 99,
+            to_port: "in_1".to_string(),
+            kind: "shared_memory".to_string(),
+            gain: 1.0,
+        },
+        ConnectionDefinition {
+            from_plugin: 3,
+            from_port: "out".to_string(),
+            to_plugin: 99,
+            to_port: "in_2".to_string(),
+            kind: "shared_memory".to_string(),
+            gain: 1.0,
+        },
+    ];
+
+    assert!(workspace::swap_extendable_inputs(&mut connections, 99, 0, 1));
+    assert_eq!(connections[0].to_port, "in_1");
+    assert_eq!(connections[1].to_port, "in_0");
+    assert_eq!(connections[2].to_port, "in_2");
+    assert!(!workspace::swap_extendable_inputs(&mut connections, 42, 0, 1));
+}
+
 #[test]
 fn input_sum_helpers() {
     let connections = vec![
@@ -106,6 +195,7 @@ fn input_sum_helpers() {
             to_plugin: 2,
             to_port: "in_a".to_string(),
             kind: "shared_memory".to_string(),
+            gain: 1.0,
         },
         ConnectionDefinition {
             from_plugin: 3,
@@ -113,6 +203,7 @@ fn input_sum_helpers() {
             to_plugin: 2,
             to_port: "in_b".to_string(),
             kind: "shared_memory".to_string(),
+            gain: 1.0,
         },
     ];
     let mut outputs = std::collections::HashMap::new();
@@ -130,6 +221,138 @@ fn input_sum_helpers() {
     assert_eq!(sum_any, 1.0);
 }
 
+#[test]
+fn unconnected_inputs_read_their_declared_default() {
+    let connections = vec![ConnectionDefinition {
+        from_plugin: 1,
+        from_port: "out".to_string(),
+        to_plugin: 2,
+        to_port: "process_variable".to_string(),
+        kind: "shared_memory".to_string(),
+        gain: 1.0,
+    }];
+    let mut outputs = std::collections::HashMap::new();
+    outputs.insert((1, "out".to_string()), 0.0);
+    let plugin = PluginDefinition {
+        id: 2,
+        kind: "bang_bang".to_string(),
+        config: serde_json::json!({ "setpoint_default": 20.0, "process_variable_default": 5.0 }),
+        priority: 0,
+        running: true,
+    };
+
+    assert_eq!(workspace::input_value(&connections, &outputs, &plugin, "setpoint"), 20.0);
+    // A connected input ignores its default, even when its source reads 0
+    assert_eq!(
+        workspace::input_value(&connections, &outputs, &plugin, "process_variable"),
+        0.0
+    );
+    assert_eq!(workspace::input_value(&connections, &outputs, &plugin, "other"), 0.0);
+    assert_eq!(
+        workspace::input_value_any(
+            &connections,
+            &outputs,
+            &plugin,
+            &["other".to_string(), "setpoint".to_string()],
+        ),
+        20.0
+    );
+}
+
+#[test]
+fn input_sum_applies_connection_gain() {
+    let connection = |from: u64, gain: f64| ConnectionDefinition {
+        from_plugin: from,
+        from_port: "out".to_string(),
+        to_plugin: 9,
+        to_port: "in".to_string(),
+        kind: "shared_memory".to_string(),
+        gain,
+    };
+    let connections = vec![connection(1, 2.0), connection(2, -0.5)];
+    let mut outputs = std::collections::HashMap::new();
+    outputs.insert((1, "out".to_string()), 3.0);
+    outputs.insert((2, "out".to_string()), 4.0);
+    assert_eq!(workspace::input_sum(&connections, &outputs, 9, "in"), 4.0);
+
+    // Connections saved before gains existed pass their source through unchanged
+    let legacy: ConnectionDefinition = serde_json::from_str(
+        r#"{"from_plugin":1,"from_port":"out","to_plugin":9,"to_port":"in","kind":"pipe"}"#,
+    )
+    .unwrap();
+    assert_eq!(legacy.gain, 1.0);
+}
+
+#[test]
+fn can_add_connection_reports_every_rule_without_adding() {
+    let connection = |from: u64, from_port: &str, to: u64, to_port: &str| ConnectionDefinition {
+        from_plugin: from,
+        from_port: from_port.to_string(),
+        to_plugin: to,
+        to_port: to_port.to_string(),
+        kind: "shared_memory".to_string(),
+        gain: 1.0,
+    };
+    let connections = vec![connection(1, "out", 2, "in_0")];
+
+    assert_eq!(workspace::can_add_connection(&connections, &connection(1, "out", 3, "in_0"), 1), Ok(()));
+    assert_eq!(
+        workspace::can_add_connection(&connections, &connection(2, "out", 2, "in_1"), 1),
+        Err(workspace::ConnectionRuleError::SelfConnection)
+    );
+    assert_eq!(
+        workspace::can_add_connection(&connections, &connection(3, "out", 2, "in_0"), 1),
+        Err(workspace::ConnectionRuleError::InputLimitExceeded)
+    );
+    assert_eq!(workspace::can_add_connection(&connections, &connection(3, "out", 2, "in_0"), 2), Ok(()));
+    assert_eq!(
+        workspace::can_add_connection(&connections, &connection(1, "out", 2, "in_1"), 1),
+        Err(workspace::ConnectionRuleError::DuplicateConnection)
+    );
+    assert_eq!(connections.len(), 1);
+
+    let mut connections = connections;
+    let err = workspace::add_connection(&mut connections, connection(1, "out", 2, "in_1"), 1).unwrap_err();
+    assert_eq!(err, workspace::ConnectionRuleError::DuplicateConnection);
+    assert_eq!(connections.len(), 1);
+}
+
+#[test]
+fn fan_out_feeds_every_branch_and_removal_keeps_the_others() {
+    // One output wired to the first input of three plotters
+    let branch = |to: u64| ConnectionDefinition {
+        from_plugin: 1,
+        from_port: "out".to_string(),
+        to_plugin: to,
+        to_port: "in_0".to_string(),
+        kind: "shared_memory".to_string(),
+        gain: 1.0,
+    };
+    let mut connections = Vec::new();
+    for to in [2, 3, 4] {
+        workspace::add_connection(&mut connections, branch(to), 1).expect("fan-out branch");
+    }
+    let mut outputs = std::collections::HashMap::new();
+    outputs.insert((1, "out".to_string()), 2.5);
+
+    assert_eq!(workspace::fan_out(&connections, 1, "out").count(), 3);
+    assert_eq!(workspace::fan_out(&connections, 1, "other").count(), 0);
+    for to in [2, 3, 4] {
+        assert_eq!(workspace::input_sum(&connections, &outputs, to, "in_0"), 2.5);
+        assert_eq!(
+            workspace::input_sum_any(&connections, &outputs, to, &["in_0".to_string()]),
+            2.5
+        );
+    }
+
+    assert!(workspace::remove_connection(&mut connections, &branch(3)));
+    assert!(!workspace::remove_connection(&mut connections, &branch(3)));
+    assert_eq!(workspace::fan_out(&connections, 1, "out").count(), 2);
+    assert_eq!(workspace::input_sum(&connections, &outputs, 2, "in_0"), 2.5);
+    assert_eq!(workspace::input_sum(&connections, &outputs, 3, "in_0"), 0.0);
+    assert_eq!(workspace::input_sum(&connections, &outputs, 4, "in_0"), 2.5);
+}
+
 #[test]
 fn order_plugins_for_execution_respects_priority_and_id() {
     let plugins = vec![
@@ -159,3 +382,347 @@ fn order_plugins_for_execution_respects_priority_and_id() {
     let ids: Vec<u64> = ordered.iter().map(|p| p.id).collect();
     assert_eq!(ids, vec![3, 1, 2]);
 }
+
+#[test]
+fn order_plugins_for_execution_puts_priority_before_connections() {
+    let plugin = |id: u64, priority: i32| PluginDefinition {
+        id,
+        kind: "p".to_string(),
+        config: serde_json::json!({}),
+        priority,
+        running: true,
+    };
+    let connection = |from: u64, to: u64| ConnectionDefinition {
+        from_plugin: from,
+        from_port: "out".to_string(),
+        to_plugin: to,
+        to_port: "in".to_string(),
+        kind: "shared_memory".to_string(),
+        gain: 1.0,
+    };
+    // DAQ read (5) -> controller (3) -> DAQ write (4), all at priority 1,
+    // plus a logger (1) at priority 2 fed by the reader.
+    let plugins = vec![plugin(1, 2), plugin(3, 1), plugin(4, 1), plugin(5, 1)];
+    let connections = vec![connection(5, 3), connection(3, 4), connection(5, 1)];
+    let ordered = workspace::order_plugins_for_execution(&plugins, &connections);
+    let ids: Vec<u64> = ordered.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![5, 3, 4, 1]);
+
+    // A lower priority wins even against the connection direction.
+    let plugins = vec![plugin(1, 0), plugin(3, 1), plugin(4, 1), plugin(5, 1)];
+    let connections = vec![connection(4, 1)];
+    let ordered = workspace::order_plugins_for_execution(&plugins, &connections);
+    let ids: Vec<u64> = ordered.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![1, 3, 4, 5]);
+}
+
+#[test]
+fn order_plugins_for_execution_breaks_cycles_by_id_and_keeps_downstream_order() {
+    let plugin = |id: u64| PluginDefinition {
+        id,
+        kind: "p".to_string(),
+        config: serde_json::json!({}),
+        priority: 0,
+        running: true,
+    };
+    let connection = |from: u64, to: u64| ConnectionDefinition {
+        from_plugin: from,
+        from_port: "out".to_string(),
+        to_plugin: to,
+        to_port: "in".to_string(),
+        kind: "shared_memory".to_string(),
+        gain: 1.0,
+    };
+    // 4 <-> 6 form a loop that feeds the chain 6 -> 2 -> 1
+    let plugins = vec![plugin(1), plugin(2), plugin(4), plugin(6)];
+    let connections = vec![
+        connection(2, 1),
+        connection(6, 2),
+        connection(4, 6),
+        connection(6, 4),
+    ];
+    let ordered = workspace::order_plugins_for_execution(&plugins, &connections);
+    let ids: Vec<u64> = ordered.iter().map(|p| p.id).collect();
+    assert_eq!(ids, vec![4, 6, 2, 1]);
+}
+
+#[test]
+fn sorted_connections_sum_independent_of_insertion_order() {
+    let connection = |from: u64, to_port: &str| ConnectionDefinition {
+        from_plugin: from,
+        from_port: "out".to_string(),
+        to_plugin: 9,
+        to_port: to_port.to_string(),
+        kind: "shared_memory".to_string(),
+        gain: 1.0,
+    };
+    let mut outputs = std::collections::HashMap::new();
+    outputs.insert((1, "out".to_string()), 0.1);
+    outputs.insert((2, "out".to_string()), 0.2);
+    outputs.insert((3, "out".to_string()), 0.3);
+
+    let mut forward = vec![connection(1, "in"), connection(2, "in"), connection(3, "in")];
+    let mut backward = vec![connection(3, "in"), connection(2, "in"), connection(1, "in")];
+    workspace::sort_connections_for_evaluation(&mut forward);
+    workspace::sort_connections_for_evaluation(&mut backward);
+    let key = |c: &ConnectionDefinition| (c.to_port.clone(), c.from_plugin);
+    assert_eq!(
+        forward.iter().map(key).collect::<Vec<_>>(),
+        backward.iter().map(key).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        workspace::input_sum(&forward, &outputs, 9, "in").to_bits(),
+        workspace::input_sum(&backward, &outputs, 9, "in").to_bits()
+    );
+}
+
+#[test]
+fn workspace_edits_describe_plugin_and_connection_changes() {
+    use workspace::WorkspaceEdit;
+
+    let plugin = |id: u64| PluginDefinition {
+        id,
+        kind: "scale".to_string(),
+        config: serde_json::json!({ "gain": 1.0 }),
+        priority: 0,
+        running: true,
+    };
+    let connection = |from: u64, to: u64| ConnectionDefinition {
+        from_plugin: from,
+        from_port: "out".to_string(),
+        to_plugin: to,
+        to_port: "in".to_string(),
+        kind: "shared_memory".to_string(),
+        gain: 1.0,
+    };
+    let old = WorkspaceDefinition {
+        name: "edits".to_string(),
+        description: String::new(),
+        target_hz: 1000,
+        plugins: vec![plugin(1), plugin(2), plugin(3)],
+        connections: vec![connection(1, 2), connection(2, 3)],
+        settings: WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+    assert!(workspace::workspace_edits(&old, &old).unwrap().is_empty());
+
+    let mut new = old.clone();
+    new.plugins.retain(|p| p.id != 3);
+    new.connections.retain(|c| c.to_plugin != 3);
+    new.plugins[0].config = serde_json::json!({ "gain": 2.0 });
+    new.plugins[1].running = false;
+    new.plugins.push(plugin(4));
+    new.connections[0].gain = 0.5;
+    new.connections.push(connection(2, 4));
+    let edits: Vec<String> = workspace::workspace_edits(&old, &new)
+        .unwrap()
+        .into_iter()
+        .map(|edit| match edit {
+            WorkspaceEdit::AddPlugin(p) => format!("add {}", p.id),
+            WorkspaceEdit::RemovePlugin(id) => format!("remove {id}"),
+            WorkspaceEdit::UpdatePluginConfig(id, config) => format!("config {id} {config}"),
+            WorkspaceEdit::SetPluginRunning(id, running) => format!("running {id} {running}"),
+            WorkspaceEdit::AddConnection(c) => {
+                format!("connect {}->{} x{}", c.from_plugin, c.to_plugin, c.gain)
+            }
+            WorkspaceEdit::RemoveConnection(c) => {
+                format!("disconnect {}->{}", c.from_plugin, c.to_plugin)
+            }
+        })
+        .collect();
+    // The connection to the removed plugin goes with it
+    assert_eq!(
+        edits,
+        [
+            "remove 3",
+            "config 1 {\"gain\":2.0}",
+            "running 2 false",
+            "add 4",
+            "disconnect 1->2",
+            "connect 1->2 x0.5",
+            "connect 2->4 x1",
+        ]
+    );
+
+    // Changes the edits cannot express need the whole workspace
+    let mut renamed = old.clone();
+    renamed.name = "other".to_string();
+    assert!(workspace::workspace_edits(&old, &renamed).is_none());
+    let mut reprioritized = old.clone();
+    reprioritized.plugins[2].priority = 5;
+    assert!(workspace::workspace_edits(&old, &reprioritized).is_none());
+    let mut reordered = old.clone();
+    reordered.plugins.swap(0, 1);
+    assert!(workspace::workspace_edits(&old, &reordered).is_none());
+}
+
+#[test]
+fn gui_only_keys_are_kept_from_the_runtime() {
+    let mut workspace = WorkspaceDefinition {
+        name: "cards".to_string(),
+        description: String::new(),
+        target_hz: 1000,
+        plugins: vec![PluginDefinition {
+            id: 1,
+            kind: "scale".to_string(),
+            config: serde_json::json!({ "gain": 2.0, "_ui_label": "Motor", "_ui_collapsed": false }),
+            priority: 0,
+            running: true,
+        }],
+        connections: Vec::new(),
+        settings: WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+    let resolved = workspace.resolve_parameters();
+    assert_eq!(resolved.plugins[0].config, serde_json::json!({ "gain": 2.0 }));
+
+    // Collapsing a card is not a config change for the runtime
+    workspace.plugins[0].config["_ui_collapsed"] = serde_json::json!(true);
+    let edits = workspace::workspace_edits(&resolved, &workspace.resolve_parameters()).unwrap();
+    assert!(edits.is_empty());
+}
+
+#[test]
+fn parameter_references_resolve_for_the_runtime_only() {
+    let plugin = |id: u64, config: serde_json::Value| PluginDefinition {
+        id,
+        kind: "scale".to_string(),
+        config,
+        priority: 0,
+        running: true,
+    };
+    let mut workspace = WorkspaceDefinition {
+        name: "parameters".to_string(),
+        description: String::new(),
+        target_hz: 1000,
+        plugins: vec![
+            plugin(1, serde_json::json!({ "gain": "$gain", "offset": 1.0 })),
+            plugin(2, serde_json::json!({ "gain": "$gain", "offset": "$missing" })),
+            plugin(3, serde_json::json!({ "gain": 3.0, "label": "$" })),
+        ],
+        connections: Vec::new(),
+        settings: WorkspaceSettings::default(),
+        parameters: [("gain".to_string(), 2.5)].into_iter().collect(),
+        metadata: Default::default(),
+    };
+    assert_eq!(
+        workspace.parameter_uses("gain"),
+        [(1, "gain".to_string()), (2, "gain".to_string())]
+    );
+
+    let resolved = workspace.resolve_parameters();
+    assert_eq!(resolved.plugins[0].config, serde_json::json!({ "gain": 2.5, "offset": 1.0 }));
+    // Unknown references and plain strings stay as written
+    assert_eq!(
+        resolved.plugins[1].config,
+        serde_json::json!({ "gain": 2.5, "offset": "$missing" })
+    );
+    assert_eq!(resolved.plugins[2].config, workspace.plugins[2].config);
+    assert_eq!(workspace.plugins[0].config["gain"], "$gain");
+
+    // A parameter change reaches every referencing plugin as a config edit
+    workspace.parameters.insert("gain".to_string(), 4.0);
+    let edits = workspace::workspace_edits(&resolved, &workspace.resolve_parameters()).unwrap();
+    let updated: Vec<u64> = edits
+        .iter()
+        .map(|edit| match edit {
+            workspace::WorkspaceEdit::UpdatePluginConfig(id, config) => {
+                assert_eq!(config["gain"], 4.0);
+                *id
+            }
+            other => panic!("unexpected edit {other:?}"),
+        })
+        .collect();
+    assert_eq!(updated, [1, 2]);
+}
+
+#[test]
+fn renumbered_workspace_remaps_connection_endpoints() {
+    let plugin = |id| PluginDefinition {
+        id,
+        kind: "gain".to_string(),
+        config: serde_json::json!({}),
+        priority: 0,
+        running: false,
+    };
+    let connection = |from_plugin, to_plugin| ConnectionDefinition {
+        from_plugin,
+        from_port: "out".to_string(),
+        to_plugin,
+        to_port: "in".to_string(),
+        kind: "shared_memory".to_string(),
+        gain: 1.0,
+    };
+    let workspace = WorkspaceDefinition {
+        name: "branch".to_string(),
+        description: String::new(),
+        target_hz: 1000,
+        plugins: vec![plugin(7), plugin(3), plugin(12)],
+        connections: vec![connection(7, 3), connection(3, 12), connection(12, 99)],
+        settings: WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+
+    let copy = workspace.renumbered();
+    let ids: Vec<u64> = copy.plugins.iter().map(|p| p.id).collect();
+    assert_eq!(ids, [1, 2, 3]);
+    let endpoints: Vec<(u64, u64)> = copy
+        .connections
+        .iter()
+        .map(|c| (c.from_plugin, c.to_plugin))
+        .collect();
+    // The dangling connection to 99 is dropped
+    assert_eq!(endpoints, [(1, 2), (2, 3)]);
+    assert_eq!(workspace.plugins[0].id, 7);
+}
+
+#[test]
+fn dot_export_has_a_node_per_plugin_and_an_edge_per_connection() {
+    let plugin = |id, kind: &str| PluginDefinition {
+        id,
+        kind: kind.to_string(),
+        config: serde_json::json!({}),
+        priority: 0,
+        running: false,
+    };
+    let workspace = WorkspaceDefinition {
+        name: "rig \"A\"".to_string(),
+        description: String::new(),
+        target_hz: 1000,
+        plugins: vec![plugin(1, "stimulus"), plugin(2, "scale")],
+        connections: vec![
+            ConnectionDefinition {
+                from_plugin: 1,
+                from_port: "out".to_string(),
+                to_plugin: 2,
+                to_port: "in".to_string(),
+                kind: "shared_memory".to_string(),
+                gain: 1.0,
+            },
+            ConnectionDefinition {
+                from_plugin: 1,
+                from_port: "out".to_string(),
+                to_plugin: 2,
+                to_port: "offset".to_string(),
+                kind: "queue".to_string(),
+                gain: 0.5,
+            },
+        ],
+        settings: WorkspaceSettings::default(),
+        parameters: Default::default(),
+        metadata: Default::default(),
+    };
+
+    let dot = workspace.to_dot();
+    assert!(dot.starts_with("digraph \"rig \\\"A\\\"\" {\n"));
+    assert!(dot.contains("    p1 [label=\"stimulus #1\"];\n"));
+    assert!(dot.contains("    p1 -> p2 [label=\"out -> in\"];\n"));
+    assert!(dot.contains("    p1 -> p2 [label=\"out -> offset [queue] x0.5\"];\n"));
+    assert!(dot.ends_with("}\n"));
+
+    let labeled = workspace.to_dot_labeled(|p| if p.id == 2 { "Gain".to_string() } else { p.kind.clone() });
+    assert!(labeled.contains("    p2 [label=\"Gain #2\\nscale\"];\n"));
+}